@@ -4,8 +4,9 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 use mimalloc::MiMalloc;
 use modkit::bootstrap::{
-    AppConfig, dump_effective_modules_config_json, dump_effective_modules_config_yaml,
-    list_module_names, run_migrate, run_server,
+    AppConfig, MigrateCommand, dump_effective_modules_config_json,
+    dump_effective_modules_config_yaml, list_module_names, run_migrate, run_migrate_command,
+    run_server,
 };
 
 use std::path::PathBuf;
@@ -59,7 +60,26 @@ enum Commands {
     /// Do nothing
     Check,
     /// Run database migrations and exit (for cloud deployments)
-    Migrate,
+    Migrate {
+        #[command(subcommand)]
+        action: Option<MigrateAction>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MigrateAction {
+    /// List applied and pending migrations per module
+    Status,
+    /// Print the SQL of pending migrations without applying them
+    DryRun,
+    /// Roll back the last migrations of a module
+    Rollback {
+        /// Module whose migrations are rolled back
+        module: String,
+        /// Number of migrations to roll back
+        #[arg(long, default_value_t = 1)]
+        steps: usize,
+    },
 }
 
 #[tokio::main]
@@ -112,7 +132,19 @@ async fn main() -> Result<()> {
     // Dispatch subcommands (default: run)
     match cli.command.unwrap_or(Commands::Run) {
         Commands::Run => run_server(config).await,
-        Commands::Migrate => run_migrate(config).await,
+        Commands::Migrate { action: None } => run_migrate(config).await,
+        Commands::Migrate {
+            action: Some(action),
+        } => {
+            let command = match action {
+                MigrateAction::Status => MigrateCommand::Status,
+                MigrateAction::DryRun => MigrateCommand::DryRun,
+                MigrateAction::Rollback { module, steps } => {
+                    MigrateCommand::Rollback { module, steps }
+                }
+            };
+            run_migrate_command(config, command).await
+        }
         Commands::Check => Ok(()),
     }
 }
@@ -11,7 +11,7 @@ use modkit_db::odata::{LimitCfg, paginate_odata};
 use modkit_db::secure::{
     DBRunner, SecureDeleteExt, SecureEntityExt, secure_insert, secure_update_with_scope,
 };
-use modkit_odata::{ODataQuery, Page, SortDir};
+use modkit_odata::{ODataLimits, ODataQuery, Page, SortDir};
 use modkit_security::AccessScope;
 use sea_orm::sea_query::Expr;
 use sea_orm::{EntityTrait, QueryFilter, Set};
@@ -64,6 +64,7 @@ impl CitiesRepository for OrmCitiesRepository {
             query,
             ("id", SortDir::Desc),
             self.limit_cfg,
+            &ODataLimits::default(),
             Into::into,
         )
         .await
@@ -167,6 +167,7 @@ impl AuthZResolverClient for MockAuthZResolver {
                         pep_properties::OWNER_TENANT_ID,
                         [id],
                     ))],
+                    fields: vec![],
                 }],
                 None => vec![],
             };
@@ -346,7 +347,10 @@ impl AuthZResolverClient for OwnerCityAuthZResolver {
         Ok(EvaluationResponse {
             decision: true,
             context: EvaluationResponseContext {
-                constraints: vec![Constraint { predicates }],
+                constraints: vec![Constraint {
+                    predicates,
+                    fields: vec![],
+                }],
                 ..Default::default()
             },
         })
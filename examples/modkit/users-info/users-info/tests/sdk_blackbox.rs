@@ -54,6 +54,7 @@ impl AuthZResolverClient for MockAuthZResolver {
                         pep_properties::OWNER_TENANT_ID,
                         [id],
                     ))],
+                    fields: vec![],
                 }],
                 None => vec![],
             }
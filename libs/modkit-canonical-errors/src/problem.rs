@@ -1,10 +1,17 @@
 use serde::{Deserialize, Serialize};
 
+use crate::context::{FailedPrecondition, PreconditionViolation};
 use crate::error::CanonicalError;
 
 /// Media type for RFC 9457 `application/problem+json` responses.
 pub const APPLICATION_PROBLEM_JSON: &str = "application/problem+json";
 
+/// Precondition violation `type` of [`Problem::precondition_failed`].
+pub const ETAG_MISMATCH: &str = "ETAG_MISMATCH";
+
+/// Precondition violation `type` of [`Problem::precondition_required`].
+pub const PRECONDITION_MISSING: &str = "PRECONDITION_MISSING";
+
 // ---------------------------------------------------------------------------
 // Problem (RFC 9457)
 // ---------------------------------------------------------------------------
@@ -83,6 +90,42 @@ impl Problem {
         Ok(problem)
     }
 
+    /// `412 Precondition Failed` for a conditional request whose `header`
+    /// (`If-Match` or `If-None-Match`) did not hold (RFC 9110 §13.1).
+    ///
+    /// The body is the canonical `failed_precondition` problem with a single
+    /// `ETAG_MISMATCH` violation; only the status differs from the 400 used
+    /// for domain preconditions.
+    #[must_use]
+    pub fn precondition_failed(header: &str, description: impl Into<String>) -> Self {
+        let description = description.into();
+        let err = CanonicalError::__failed_precondition(FailedPrecondition::new(vec![
+            PreconditionViolation::new(ETAG_MISMATCH, header, description.clone()),
+        ]))
+        .with_detail(description);
+        let mut problem = Self::from(err);
+        problem.status = 412;
+        problem
+    }
+
+    /// `428 Precondition Required` for a state-changing request that must be
+    /// conditional but lacks `header` (RFC 6585 §3).
+    ///
+    /// Same canonical `failed_precondition` body as
+    /// [`precondition_failed`](Self::precondition_failed), with a
+    /// `PRECONDITION_MISSING` violation.
+    #[must_use]
+    pub fn precondition_required(header: &str, description: impl Into<String>) -> Self {
+        let description = description.into();
+        let err = CanonicalError::__failed_precondition(FailedPrecondition::new(vec![
+            PreconditionViolation::new(PRECONDITION_MISSING, header, description.clone()),
+        ]))
+        .with_detail(description);
+        let mut problem = Self::from(err);
+        problem.status = 428;
+        problem
+    }
+
     /// Set the `trace_id` field, returning `self` for chaining.
     #[must_use]
     pub fn with_trace_id(mut self, trace_id: impl Into<String>) -> Self {
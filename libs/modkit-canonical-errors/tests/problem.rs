@@ -56,6 +56,30 @@ fn problem_json_excludes_resource_type_when_none() {
     assert!(json["context"].get("resource_type").is_none());
 }
 
+#[test]
+fn precondition_failed_is_412_failed_precondition() {
+    let problem = Problem::precondition_failed("If-Match", "resource has changed");
+    assert_eq!(
+        problem.problem_type,
+        "gts://gts.cf.core.errors.err.v1~cf.core.err.failed_precondition.v1~"
+    );
+    assert_eq!(problem.status, 412);
+    assert_eq!(problem.detail, "resource has changed");
+    let violation = &problem.context["violations"][0];
+    assert_eq!(violation["type"], "ETAG_MISMATCH");
+    assert_eq!(violation["subject"], "If-Match");
+}
+
+#[test]
+fn precondition_required_is_428_failed_precondition() {
+    let problem = Problem::precondition_required("If-Match", "If-Match is required");
+    assert!(problem.problem_type.ends_with("failed_precondition.v1~"));
+    assert_eq!(problem.status, 428);
+    let violation = &problem.context["violations"][0];
+    assert_eq!(violation["type"], "PRECONDITION_MISSING");
+    assert_eq!(violation["subject"], "If-Match");
+}
+
 // =========================================================================
 // diagnostic() accessor
 // =========================================================================
@@ -39,6 +39,11 @@
 //! - **Type**: `type_col = "column_name"` OR `no_type`
 //! - **Unrestricted**: `unrestricted` (forbids all other attributes)
 //! - **Custom PEP property**: `pep_prop(property_name = "column_name")` (repeatable)
+//! - **Audit**: `audit` (optional) - stamp `created_at`/`created_by`/`updated_at`/`updated_by`
+//! - **Soft delete**: `soft_delete = "column_name"` (optional) - deletes set this column
+//! - **Version**: `version_col = "column_name"` (optional) - optimistic concurrency counter
+//! - **API field name**: `field(field_name = "column_name")` (repeatable) - field masks name
+//!   this column `field_name`
 //!
 //! ## Note on `OData` Macros
 //!
@@ -66,6 +71,16 @@ mod scopable;
 /// - `type_col = "column_name"` OR `no_type` - Type-based filtering column
 /// - `unrestricted` - Mark as global entity (forbids all other attributes)
 /// - `pep_prop(property_name = "column_name")` - Custom PEP property mapping (repeatable)
+/// - `audit` - Require subject and time stamps in the `created_at`, `created_by`,
+///   `updated_at`, `updated_by` columns (and `deleted_by` with `soft_delete`)
+/// - `soft_delete = "column_name"` - Turn secure deletes into setting this nullable
+///   timestamp column and hide such rows from secure selects
+/// - `version_col = "column_name"` - `i64` row version bumped by every secure update
+///   and checked by `expect_version`
+/// - `search = "col_a, col_b"` - Text columns matched by `OData` `$search` (also
+///   allowed with `unrestricted`)
+/// - `field(field_name = "column_name")` - API name of a column whose field name
+///   differs, used to resolve field masks (repeatable; also allowed with `unrestricted`)
 ///
 /// The macro auto-generates `resolve_property()` from dimension columns and `pep_prop` entries:
 /// - `tenant_col` → `"owner_tenant_id"`
@@ -43,8 +43,30 @@ struct SecureConfig {
 
     // Custom PEP property mappings: (property_name, column_name, span)
     pep_props: Vec<(String, String, Span)>,
+
+    // Audit columns (created_at/by, updated_at/by, deleted_by)
+    audit: Option<Span>,
+
+    // Soft-delete timestamp column
+    soft_delete: Option<(String, Span)>,
+
+    // Optimistic-concurrency version column
+    version_col: Option<(String, Span)>,
+
+    // Full-text `$search` columns
+    search: Option<(Vec<String>, Span)>,
+
+    // API field names of renamed columns: (field_name, column_name, span)
+    fields: Vec<(String, String, Span)>,
 }
 
+/// Conventional audit column names of `#[secure(audit)]`.
+const AUDIT_CREATED_AT: &str = "created_at";
+const AUDIT_CREATED_BY: &str = "created_by";
+const AUDIT_UPDATED_AT: &str = "updated_at";
+const AUDIT_UPDATED_BY: &str = "updated_by";
+const AUDIT_DELETED_BY: &str = "deleted_by";
+
 #[allow(clippy::needless_pass_by_value)] // DeriveInput is consumed by proc-macro pattern
 pub fn expand_derive_scopable(input: DeriveInput) -> TokenStream {
     // Verify this is a struct
@@ -63,6 +85,10 @@ pub fn expand_derive_scopable(input: DeriveInput) -> TokenStream {
 
     let entity_ident = syn::Ident::new("Entity", input.ident.span());
 
+    // Search columns are not a scope dimension, so unrestricted entities may declare them too
+    let search_impl = generate_search_impl(&config, input.ident.span());
+    let fields_impl = generate_resolve_field(&config, input.ident.span());
+
     // If unrestricted, generate simple implementation with all None
     if config.unrestricted.is_some() {
         return quote! {
@@ -88,6 +114,10 @@ pub fn expand_derive_scopable(input: DeriveInput) -> TokenStream {
                 fn resolve_property(_property: &str) -> ::core::option::Option<Self::Column> {
                     ::core::option::Option::None
                 }
+
+                #search_impl
+
+                #fields_impl
             }
         };
     }
@@ -113,6 +143,9 @@ pub fn expand_derive_scopable(input: DeriveInput) -> TokenStream {
     // Generate resolve_property implementation
     let resolve_property_impl = generate_resolve_property(&config, input.ident.span());
 
+    // Generate audit_cols / soft_delete_col / version_col overrides (trait defaults otherwise)
+    let audit_impl = generate_audit_impl(&config, input.ident.span());
+
     // Generate the implementation
     quote! {
         impl ::modkit_db::secure::ScopableEntity for #entity_ident {
@@ -127,6 +160,12 @@ pub fn expand_derive_scopable(input: DeriveInput) -> TokenStream {
             #type_col_impl
 
             #resolve_property_impl
+
+            #audit_impl
+
+            #search_impl
+
+            #fields_impl
         }
     }
 }
@@ -204,10 +243,102 @@ fn generate_resolve_property(config: &SecureConfig, span: Span) -> TokenStream {
     }
 }
 
+/// Generate `audit_cols`, `soft_delete_col` and `version_col` for the `audit` /
+/// `soft_delete` / `version_col` attributes.
+fn generate_audit_impl(config: &SecureConfig, span: Span) -> TokenStream {
+    let col = |name: &str| syn::Ident::new(&snake_to_upper_camel(name), span);
+    let mut out = TokenStream::new();
+
+    if config.audit.is_some() {
+        let created_at = col(AUDIT_CREATED_AT);
+        let created_by = col(AUDIT_CREATED_BY);
+        let updated_at = col(AUDIT_UPDATED_AT);
+        let updated_by = col(AUDIT_UPDATED_BY);
+        let deleted_by = if config.soft_delete.is_some() {
+            let deleted_by = col(AUDIT_DELETED_BY);
+            quote! { ::core::option::Option::Some(Self::Column::#deleted_by) }
+        } else {
+            quote! { ::core::option::Option::None }
+        };
+        out.extend(quote! {
+            fn audit_cols() -> ::core::option::Option<
+                ::modkit_db::secure::AuditColumns<Self::Column>,
+            > {
+                ::core::option::Option::Some(::modkit_db::secure::AuditColumns {
+                    created_at: Self::Column::#created_at,
+                    created_by: Self::Column::#created_by,
+                    updated_at: Self::Column::#updated_at,
+                    updated_by: Self::Column::#updated_by,
+                    deleted_by: #deleted_by,
+                })
+            }
+        });
+    }
+
+    if config.soft_delete.is_some() {
+        out.extend(generate_col_impl(
+            "soft_delete_col",
+            config.soft_delete.as_ref(),
+            span,
+        ));
+    }
+
+    if config.version_col.is_some() {
+        out.extend(generate_col_impl(
+            "version_col",
+            config.version_col.as_ref(),
+            span,
+        ));
+    }
+
+    out
+}
+
+/// Generate `search_cols` for the `search` attribute (trait default otherwise).
+fn generate_search_impl(config: &SecureConfig, span: Span) -> TokenStream {
+    let Some((cols, _)) = &config.search else {
+        return TokenStream::new();
+    };
+    let cols = cols
+        .iter()
+        .map(|c| syn::Ident::new(&snake_to_upper_camel(c), span));
+    quote! {
+        fn search_cols() -> ::std::vec::Vec<Self::Column> {
+            ::std::vec![#(Self::Column::#cols),*]
+        }
+    }
+}
+
+/// Generate `resolve_field` for `field(...)` entries (trait default otherwise).
+///
+/// Other field names still resolve to the column of the same name.
+fn generate_resolve_field(config: &SecureConfig, span: Span) -> TokenStream {
+    if config.fields.is_empty() {
+        return TokenStream::new();
+    }
+    let arms = config.fields.iter().map(|(field, column, _)| {
+        let col_ident = syn::Ident::new(&snake_to_upper_camel(column), span);
+        quote! {
+            #field => ::core::option::Option::Some(Self::Column::#col_ident),
+        }
+    });
+    quote! {
+        fn resolve_field(field: &str) -> ::core::option::Option<Self::Column> {
+            match field {
+                #(#arms)*
+                _ => ::modkit_db::secure::column_named::<Self>(field),
+            }
+        }
+    }
+}
+
 /// Validate the configuration for strict compile-time checks
 fn validate_config(config: &SecureConfig, input: &DeriveInput) {
     let struct_span = input.span();
 
+    // Field names are not a scope dimension, so unrestricted entities may declare them too
+    validate_fields(config);
+
     // If unrestricted is set, no other attributes should be present
     if let Some(unrestricted_span) = config.unrestricted {
         let has_other = config.tenant_col.is_some()
@@ -217,7 +348,10 @@ fn validate_config(config: &SecureConfig, input: &DeriveInput) {
             || config.owner_col.is_some()
             || config.no_owner.is_some()
             || config.type_col.is_some()
-            || config.no_type.is_some();
+            || config.no_type.is_some()
+            || config.audit.is_some()
+            || config.soft_delete.is_some()
+            || config.version_col.is_some();
 
         if has_other {
             abort!(
@@ -290,6 +424,19 @@ fn validate_pep_props(config: &SecureConfig) {
     }
 }
 
+/// Validate `field` entries for duplicates and empty values.
+fn validate_fields(config: &SecureConfig) {
+    let mut seen = std::collections::HashSet::new();
+    for (field, column, span) in &config.fields {
+        if column.is_empty() {
+            abort!(*span, "field: column name must not be empty");
+        }
+        if !seen.insert(field.clone()) {
+            abort!(*span, "field: duplicate field name '{}'", field);
+        }
+    }
+}
+
 /// Validate a single dimension has exactly one specification
 fn validate_dimension(
     name: &str,
@@ -338,6 +485,17 @@ fn parse_secure_attrs(input: &DeriveInput) -> SecureConfig {
                 return Ok(());
             }
 
+            if meta.path.is_ident("audit") {
+                if config.unrestricted.is_some() {
+                    abort!(span, "Cannot use 'audit' with 'unrestricted'");
+                }
+                if config.audit.is_some() {
+                    abort!(span, "duplicate attribute 'audit'");
+                }
+                config.audit = Some(span);
+                return Ok(());
+            }
+
             if meta.path.is_ident("no_tenant") {
                 if config.unrestricted.is_some() {
                     abort!(span, "Cannot use 'no_tenant' with 'unrestricted'");
@@ -426,6 +584,21 @@ fn parse_secure_attrs(input: &DeriveInput) -> SecureConfig {
                 return Ok(());
             }
 
+            // Check for field(api_name = "column") — API names of renamed columns
+            if meta.path.is_ident("field") {
+                meta.parse_nested_meta(|field_meta| {
+                    let field = field_meta
+                        .path
+                        .get_ident()
+                        .map(ToString::to_string)
+                        .unwrap_or_default();
+                    let column: String = field_meta.value()?.parse::<syn::LitStr>()?.value();
+                    config.fields.push((field, column, field_meta.path.span()));
+                    Ok(())
+                })?;
+                return Ok(());
+            }
+
             parse_key_value_attr(&mut config, meta);
             Ok(())
         });
@@ -521,12 +694,53 @@ fn parse_key_value_attr(config: &mut SecureConfig, meta: syn::meta::ParseNestedM
             }
             config.type_col = Some((value, span));
         }
+        "soft_delete" => {
+            if config.unrestricted.is_some() {
+                abort!(span, "Cannot use 'soft_delete' with 'unrestricted'");
+            }
+            if config.soft_delete.is_some() {
+                abort!(span, "duplicate attribute 'soft_delete'");
+            }
+            if value.is_empty() {
+                abort!(span, "soft_delete: column name must not be empty");
+            }
+            config.soft_delete = Some((value, span));
+        }
+        "search" => {
+            if config.search.is_some() {
+                abort!(span, "duplicate attribute 'search'");
+            }
+            let cols: Vec<String> = value.split(',').map(|c| c.trim().to_owned()).collect();
+            if cols.iter().any(String::is_empty) {
+                abort!(
+                    span,
+                    "search: expected a comma-separated list of column names"
+                );
+            }
+            let mut seen = std::collections::HashSet::new();
+            if let Some(dup) = cols.iter().find(|c| !seen.insert(*c)) {
+                abort!(span, "search: duplicate column '{}'", dup);
+            }
+            config.search = Some((cols, span));
+        }
+        "version_col" => {
+            if config.unrestricted.is_some() {
+                abort!(span, "Cannot use 'version_col' with 'unrestricted'");
+            }
+            if config.version_col.is_some() {
+                abort!(span, "duplicate attribute 'version_col'");
+            }
+            if value.is_empty() {
+                abort!(span, "version_col: column name must not be empty");
+            }
+            config.version_col = Some((value, span));
+        }
         _ => {
             abort!(
                 span,
                 "Unknown attribute '{}'. Valid attributes: tenant_col, no_tenant, \
                  resource_col, no_resource, owner_col, no_owner, type_col, no_type, \
-                 unrestricted, pep_prop",
+                 unrestricted, pep_prop, audit, soft_delete, version_col, search, field",
                 key
             );
         }
@@ -550,4 +764,72 @@ mod tests {
         assert_eq!(snake_to_upper_camel("owner_user_id"), "OwnerUserId");
         assert_eq!(snake_to_upper_camel("custom_col"), "CustomCol");
     }
+
+    #[test]
+    fn test_audit_impl_tracks_deleted_by_only_with_soft_delete() {
+        let span = Span::call_site();
+        let audited = SecureConfig {
+            audit: Some(span),
+            ..SecureConfig::default()
+        };
+        let tokens = generate_audit_impl(&audited, span).to_string();
+        assert!(tokens.contains("UpdatedBy"));
+        assert!(!tokens.contains("DeletedBy"));
+        assert!(!tokens.contains("soft_delete_col"));
+
+        let soft = SecureConfig {
+            audit: Some(span),
+            soft_delete: Some(("removed_at".to_owned(), span)),
+            ..SecureConfig::default()
+        };
+        let tokens = generate_audit_impl(&soft, span).to_string();
+        assert!(tokens.contains("DeletedBy"));
+        assert!(tokens.contains("RemovedAt"));
+
+        assert!(generate_audit_impl(&SecureConfig::default(), span).is_empty());
+    }
+
+    #[test]
+    fn test_version_col_is_independent_of_audit() {
+        let span = Span::call_site();
+        let versioned = SecureConfig {
+            version_col: Some(("row_version".to_owned(), span)),
+            ..SecureConfig::default()
+        };
+        let tokens = generate_audit_impl(&versioned, span).to_string();
+        assert!(tokens.contains("version_col"));
+        assert!(tokens.contains("RowVersion"));
+        assert!(!tokens.contains("audit_cols"));
+    }
+
+    #[test]
+    fn test_search_impl_lists_columns_in_order() {
+        let span = Span::call_site();
+        let searchable = SecureConfig {
+            search: Some((vec!["title".to_owned(), "body_text".to_owned()], span)),
+            ..SecureConfig::default()
+        };
+        let tokens = generate_search_impl(&searchable, span).to_string();
+        assert!(tokens.contains("search_cols"));
+        let title = tokens.find("Title").unwrap();
+        let body = tokens.find("BodyText").unwrap();
+        assert!(title < body);
+
+        assert!(generate_search_impl(&SecureConfig::default(), span).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_field_maps_renamed_columns() {
+        let span = Span::call_site();
+        let renamed = SecureConfig {
+            fields: vec![("mail".to_owned(), "email_address".to_owned(), span)],
+            ..SecureConfig::default()
+        };
+        let tokens = generate_resolve_field(&renamed, span).to_string();
+        assert!(tokens.contains("\"mail\""));
+        assert!(tokens.contains("EmailAddress"));
+        assert!(tokens.contains("column_named"));
+
+        assert!(generate_resolve_field(&SecureConfig::default(), span).is_empty());
+    }
 }
@@ -38,6 +38,9 @@ fn ui() {
     t.compile_fail("tests/ui/err_pep_duplicate_property.rs");
     t.compile_fail("tests/ui/err_unrestricted_with_pep.rs");
 
+    // Error cases: audit / soft delete
+    t.compile_fail("tests/ui/err_unrestricted_with_audit.rs");
+
     // Note: Compile-pass tests (ok_*.rs) exist on disk for documentation but are
     // not registered here — successful expansion requires the modkit-db crate which
     // is not available in the trybuild environment. The macro is tested in actual
@@ -0,0 +1,7 @@
+// audit cannot be used with unrestricted.
+
+use modkit_db_macros::Scopable;
+
+#[derive(Scopable)]
+#[secure(unrestricted, audit)]
+struct Model;
@@ -0,0 +1,26 @@
+// Entity with audit columns and soft delete - macro should expand.
+// Note: This test only validates macro expansion, not the full trait implementation.
+
+use modkit_db_macros::Scopable;
+
+#[derive(Scopable)]
+#[secure(
+    tenant_col = "tenant_id",
+    resource_col = "id",
+    no_owner,
+    no_type,
+    audit,
+    soft_delete = "deleted_at",
+)]
+struct Model {
+    tenant_id: String,
+    id: String,
+    created_at: String,
+    created_by: String,
+    updated_at: String,
+    updated_by: String,
+    deleted_at: Option<String>,
+    deleted_by: Option<String>,
+}
+
+fn main() {}
@@ -56,8 +56,8 @@ use tokio::runtime::Runtime;
 use tokio::sync::Notify;
 
 use modkit_db::outbox::{
-    Batch, EnqueueMessage, HandlerResult, LeasedHandler, Outbox, OutboxHandle, OutboxProfile,
-    Partitions, outbox_migrations,
+    Batch, EnqueueMessage, HandlerResult, LeasedHandler, MessageHeaders, Outbox, OutboxHandle,
+    OutboxProfile, Partitions, outbox_migrations,
 };
 use modkit_db::{ConnectOpts, Db, connect_db, migration_runner::run_migrations_for_testing};
 
@@ -915,6 +915,9 @@ async fn produce_range(
                     partition: local_part,
                     payload: format!("{local_part}:{seq}").into_bytes(),
                     payload_type: "bench/seq",
+                    deliver_after: None,
+                    headers: MessageHeaders::default(),
+                    dedup_key: None,
                 };
                 if let Some(bucket) = buckets.iter_mut().find(|(q, _)| q == &queue) {
                     bucket.1.push(msg);
@@ -1068,6 +1071,8 @@ async fn cleanup_outbox_tables(db_url: &str) {
 
     const TABLES: &[&str] = &[
         "modkit_outbox_dead_letters",
+        "modkit_outbox_dedup",
+        "modkit_outbox_inbox",
         "modkit_outbox_outgoing",
         "modkit_outbox_incoming",
         "modkit_outbox_vacuum_counter",
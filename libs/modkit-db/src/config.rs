@@ -27,6 +27,7 @@
 //! 3. **Params Merging**: `params` maps are merged, with module params taking precedence
 //! 4. **Pool Configuration**: Module pool config overrides server pool config entirely
 //! 5. **`SQLite` Paths**: `file`/`path` fields are module-only and never inherited from servers
+//! 6. **Replicas**: Module `replicas` replace server `replicas` entirely
 //!
 //! ## Conflict Detection
 //!
@@ -88,6 +89,15 @@ pub struct DbConnConfig {
     #[serde(default)]
     pub pool: Option<PoolCfg>,
 
+    /// Full DSNs of read replicas (`${VAR}` is expanded), same engine as the primary.
+    ///
+    /// Scoped reads on `Db::conn()` are spread over the replicas; writes,
+    /// transactions and `Db::conn_primary()` use the primary. Each replica gets
+    /// its own pool sized like the primary's. On `SQLite` the DSNs are not
+    /// opened: any entry adds one read-only pool on the primary's file.
+    #[serde(default)]
+    pub replicas: Option<Vec<String>>,
+
     // Module-level only: reference to a global server by name.
     // If absent, this module config must be fully self-sufficient (dsn or fields).
     pub server: Option<String>,
@@ -59,6 +59,15 @@ where
         self.db.conn().map_err(E::from)
     }
 
+    /// Create a non-transactional runner that also reads from the primary.
+    ///
+    /// # Errors
+    ///
+    /// Returns `E` if `Db::conn_primary()` fails (including the transaction-bypass guard).
+    pub fn conn_primary(&self) -> Result<DbConn<'_>, E> {
+        self.db.conn_primary().map_err(E::from)
+    }
+
     /// Execute a closure inside a database transaction.
     ///
     /// # Errors
@@ -90,6 +90,7 @@ mod db_provider;
 
 // Internal modules
 mod pool_opts;
+mod replicas;
 #[cfg(feature = "sqlite")]
 mod sqlite;
 
@@ -306,6 +307,7 @@ pub(crate) struct DbHandle {
     engine: DbEngine,
     dsn: String,
     sea: DatabaseConnection,
+    replicas: replicas::ReadReplicas,
 }
 
 #[cfg(feature = "sqlite")]
@@ -351,6 +353,7 @@ impl DbHandle {
                     engine,
                     dsn: dsn.to_owned(),
                     sea,
+                    replicas: replicas::ReadReplicas::default(),
                 })
             }
             #[cfg(not(feature = "pg"))]
@@ -364,6 +367,7 @@ impl DbHandle {
                     engine,
                     dsn: dsn.to_owned(),
                     sea,
+                    replicas: replicas::ReadReplicas::default(),
                 })
             }
             #[cfg(not(feature = "mysql"))]
@@ -443,6 +447,7 @@ impl DbHandle {
                     engine,
                     dsn: clean_dsn,
                     sea,
+                    replicas: replicas::ReadReplicas::default(),
                 })
             }
             #[cfg(not(feature = "sqlite"))]
@@ -497,6 +502,19 @@ impl DbHandle {
         &self.sea
     }
 
+    /// **INTERNAL**: Connection for reads that tolerate replica lag: the next
+    /// read replica, or the primary when none is configured.
+    #[must_use]
+    pub(crate) fn sea_read_ref(&self) -> &DatabaseConnection {
+        self.replicas.pick().unwrap_or(&self.sea)
+    }
+
+    /// Number of read replicas.
+    #[must_use]
+    pub(crate) fn replica_count(&self) -> usize {
+        self.replicas.len()
+    }
+
     // --- Advisory locks ---
 
     /// Acquire an advisory lock with the given key and module namespace.
@@ -143,6 +143,7 @@ impl DbManager {
             module = %module,
             engine = ?handle.engine(),
             dsn = %crate::options::redact_credentials_in_dsn(Some(handle.dsn())),
+            replicas = handle.replica_count(),
             "Built database handle for module"
         );
 
@@ -203,6 +204,11 @@ impl DbManager {
             module_cfg.pool = server_cfg.pool;
         }
 
+        // Replicas: module list replaces the server list
+        if module_cfg.replicas.is_none() {
+            module_cfg.replicas = server_cfg.replicas;
+        }
+
         // Note: file, path, and server fields are module-only and not merged
 
         module_cfg
@@ -20,11 +20,12 @@
 //! them using its privileged connection. Modules never receive raw database access.
 
 use sea_orm::{
-    ConnectionTrait, DatabaseBackend, DbErr, ExecResult, FromQueryResult, Statement,
-    TransactionTrait,
+    ConnectionTrait, Database, DatabaseBackend, DbErr, ExecResult, FromQueryResult,
+    ProxyDatabaseTrait, ProxyExecResult, ProxyRow, Statement, TransactionTrait,
 };
 use sea_orm_migration::MigrationTrait;
 use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
 use thiserror::Error;
 use tracing::{debug, info};
 use xxhash_rust::xxh3::xxh3_64;
@@ -59,6 +60,26 @@ pub enum MigrationError {
     /// Duplicate migration name found in provided migrations list.
     #[error("duplicate migration name '{name}' for module '{module}'")]
     DuplicateMigrationName { module: String, name: String },
+
+    /// A pending migration could not be rendered to SQL without a database.
+    #[error("cannot preview migration '{migration}' for module '{module}': {source}")]
+    PreviewFailed {
+        module: String,
+        migration: String,
+        source: DbErr,
+    },
+
+    /// An applied migration is not provided by the module, so it cannot be rolled back.
+    #[error("applied migration '{name}' of module '{module}' is not provided by the module")]
+    UnknownMigration { module: String, name: String },
+
+    /// Failed to remove a migration from the history table.
+    #[error("failed to unrecord migration '{migration}' for module '{module}': {source}")]
+    UnrecordFailed {
+        module: String,
+        migration: String,
+        source: DbErr,
+    },
 }
 
 /// Result of a migration run.
@@ -72,6 +93,26 @@ pub struct MigrationResult {
     pub applied_names: Vec<String>,
 }
 
+/// Applied and pending migrations of a module.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationStatus {
+    /// Names recorded in the module's history table, in application order
+    /// (by `applied_at`, then by name within the same timestamp).
+    /// May include migrations the module no longer provides.
+    pub applied: Vec<String>,
+    /// Names provided by the module but not applied yet, in application order.
+    pub pending: Vec<String>,
+}
+
+/// SQL a pending migration would execute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationPreview {
+    /// Migration name.
+    pub name: String,
+    /// Statements issued by `up()`, with values inlined.
+    pub statements: Vec<String>,
+}
+
 /// Internal model for querying migration history.
 #[derive(Debug, FromQueryResult)]
 struct MigrationRecord {
@@ -197,6 +238,93 @@ async fn get_applied_migrations(
     Ok(records.into_iter().map(|r| r.version).collect())
 }
 
+/// Query the applied migrations of a module in application order.
+///
+/// `applied_at` has second precision on `MySQL` and `SQLite`; migrations of
+/// the same run are applied in name order, so ties are broken by name.
+async fn get_applied_history(
+    conn: &impl ConnectionTrait,
+    table_name: &str,
+    module_name: &str,
+) -> Result<Vec<String>, MigrationError> {
+    let backend = conn.get_database_backend();
+
+    let sql = match backend {
+        DatabaseBackend::Postgres | DatabaseBackend::Sqlite => {
+            format!(r#"SELECT version FROM "{table_name}" ORDER BY applied_at, version"#)
+        }
+        DatabaseBackend::MySql => {
+            format!(r"SELECT version FROM `{table_name}` ORDER BY applied_at, version")
+        }
+    };
+
+    let records: Vec<MigrationRecord> =
+        MigrationRecord::find_by_statement(Statement::from_string(backend, sql))
+            .all(conn)
+            .await
+            .map_err(|e| MigrationError::QueryHistory {
+                module: module_name.to_owned(),
+                source: e,
+            })?;
+
+    Ok(records.into_iter().map(|r| r.version).collect())
+}
+
+/// Check whether a module's migration history table exists.
+///
+/// Propagates DB errors rather than treating them as "table missing".
+async fn migration_table_exists(
+    conn: &impl ConnectionTrait,
+    table_name: &str,
+    module_name: &str,
+) -> Result<bool, MigrationError> {
+    let backend = conn.get_database_backend();
+    Ok(match backend {
+        DatabaseBackend::Postgres => {
+            let sql = format!(
+                "SELECT EXISTS (SELECT 1 FROM information_schema.tables WHERE table_name = '{table_name}')"
+            );
+            let row = conn
+                .query_one(Statement::from_string(backend, sql))
+                .await
+                .map_err(|e| MigrationError::QueryHistory {
+                    module: module_name.to_owned(),
+                    source: e,
+                })?;
+            row.and_then(|r| r.try_get_by_index::<bool>(0).ok())
+                .unwrap_or(false)
+        }
+        DatabaseBackend::MySql => {
+            let sql = format!(
+                "SELECT COUNT(*) FROM information_schema.tables WHERE table_name = '{table_name}'"
+            );
+            let row = conn
+                .query_one(Statement::from_string(backend, sql))
+                .await
+                .map_err(|e| MigrationError::QueryHistory {
+                    module: module_name.to_owned(),
+                    source: e,
+                })?;
+            row.and_then(|r| r.try_get_by_index::<i64>(0).ok())
+                .is_some_and(|c| c > 0)
+        }
+        DatabaseBackend::Sqlite => {
+            let sql = format!(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='{table_name}'"
+            );
+            let row = conn
+                .query_one(Statement::from_string(backend, sql))
+                .await
+                .map_err(|e| MigrationError::QueryHistory {
+                    module: module_name.to_owned(),
+                    source: e,
+                })?;
+            row.and_then(|r| r.try_get_by_index::<i32>(0).ok())
+                .is_some_and(|c| c > 0)
+        }
+    })
+}
+
 /// Record a migration as applied.
 async fn record_migration(
     conn: &impl ConnectionTrait,
@@ -226,6 +354,35 @@ async fn record_migration(
     })
 }
 
+/// Remove a migration from the history table.
+async fn unrecord_migration(
+    conn: &impl ConnectionTrait,
+    table_name: &str,
+    module_name: &str,
+    migration_name: &str,
+) -> Result<ExecResult, MigrationError> {
+    let backend = conn.get_database_backend();
+
+    let sql = match backend {
+        DatabaseBackend::Postgres | DatabaseBackend::Sqlite => {
+            format!(r#"DELETE FROM "{table_name}" WHERE version = $1"#)
+        }
+        DatabaseBackend::MySql => format!(r"DELETE FROM `{table_name}` WHERE version = ?"),
+    };
+
+    conn.execute(Statement::from_sql_and_values(
+        backend,
+        &sql,
+        [migration_name.into()],
+    ))
+    .await
+    .map_err(|e| MigrationError::UnrecordFailed {
+        module: module_name.to_owned(),
+        migration: migration_name.to_owned(),
+        source: e,
+    })
+}
+
 /// Run migrations for a specific module using a `Db`.
 ///
 /// This is the main entry point for the runtime to execute module migrations.
@@ -491,53 +648,8 @@ async fn get_pending_migrations_internal(
 
     let table_name = migration_table_name(module_name);
 
-    // Check if table exists - if not, all migrations are pending.
-    // Propagate DB errors rather than treating them as "table missing".
-    let backend = conn.get_database_backend();
-    let table_exists = match backend {
-        DatabaseBackend::Postgres => {
-            let sql = format!(
-                "SELECT EXISTS (SELECT 1 FROM information_schema.tables WHERE table_name = '{table_name}')"
-            );
-            let row = conn
-                .query_one(Statement::from_string(backend, sql))
-                .await
-                .map_err(|e| MigrationError::QueryHistory {
-                    module: module_name.to_owned(),
-                    source: e,
-                })?;
-            row.and_then(|r| r.try_get_by_index::<bool>(0).ok())
-                .unwrap_or(false)
-        }
-        DatabaseBackend::MySql => {
-            let sql = format!(
-                "SELECT COUNT(*) FROM information_schema.tables WHERE table_name = '{table_name}'"
-            );
-            let row = conn
-                .query_one(Statement::from_string(backend, sql))
-                .await
-                .map_err(|e| MigrationError::QueryHistory {
-                    module: module_name.to_owned(),
-                    source: e,
-                })?;
-            row.and_then(|r| r.try_get_by_index::<i64>(0).ok())
-                .is_some_and(|c| c > 0)
-        }
-        DatabaseBackend::Sqlite => {
-            let sql = format!(
-                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='{table_name}'"
-            );
-            let row = conn
-                .query_one(Statement::from_string(backend, sql))
-                .await
-                .map_err(|e| MigrationError::QueryHistory {
-                    module: module_name.to_owned(),
-                    source: e,
-                })?;
-            row.and_then(|r| r.try_get_by_index::<i32>(0).ok())
-                .is_some_and(|c| c > 0)
-        }
-    };
+    // If the table does not exist, all migrations are pending.
+    let table_exists = migration_table_exists(conn, &table_name, module_name).await?;
 
     if !table_exists {
         return Ok(migrations.iter().map(|m| m.name().to_owned()).collect());
@@ -552,6 +664,225 @@ async fn get_pending_migrations_internal(
         .collect())
 }
 
+/// Report which migrations of a module are applied and which are pending.
+///
+/// Does not create the history table; a module that never ran migrations
+/// reports everything as pending.
+///
+/// # Errors
+///
+/// Returns `Err(MigrationError)` if the migration history cannot be queried.
+pub async fn get_migration_status(
+    db: &crate::Db,
+    module_name: &str,
+    migrations: &[Box<dyn MigrationTrait>],
+) -> Result<MigrationStatus, MigrationError> {
+    let conn = db.sea_internal();
+    let table_name = migration_table_name(module_name);
+
+    let applied = if migration_table_exists(&conn, &table_name, module_name).await? {
+        get_applied_history(&conn, &table_name, module_name).await?
+    } else {
+        vec![]
+    };
+
+    let mut pending: Vec<String> = migrations
+        .iter()
+        .map(|m| m.name().to_owned())
+        .filter(|n| !applied.contains(n))
+        .collect();
+    pending.sort();
+
+    Ok(MigrationStatus { applied, pending })
+}
+
+/// Render the SQL each pending migration of a module would execute, without
+/// applying anything.
+///
+/// `up()` runs against a recording proxy connection of the module's backend,
+/// so the database is only read to find the pending migrations.
+/// Migrations that query the database (e.g. `has_table`, raw `SELECT`s)
+/// cannot be previewed and fail with [`MigrationError::PreviewFailed`].
+///
+/// # Errors
+///
+/// Returns `Err(MigrationError)` if the history cannot be queried or a
+/// migration cannot be previewed.
+pub async fn preview_pending_migrations(
+    db: &crate::Db,
+    module_name: &str,
+    migrations: &[Box<dyn MigrationTrait>],
+) -> Result<Vec<MigrationPreview>, MigrationError> {
+    let backend = db.sea_internal().get_database_backend();
+    let pending = get_pending_migrations(db, module_name, migrations).await?;
+
+    let mut sorted: Vec<_> = migrations
+        .iter()
+        .filter(|m| pending.iter().any(|p| p.as_str() == m.name()))
+        .collect();
+    sorted.sort_by(|a, b| a.name().cmp(b.name()));
+
+    let mut previews = Vec::with_capacity(sorted.len());
+    for migration in sorted {
+        let name = migration.name().to_owned();
+        let statements = record_up_statements(backend, migration.as_ref())
+            .await
+            .map_err(|e| MigrationError::PreviewFailed {
+                module: module_name.to_owned(),
+                migration: name.clone(),
+                source: e,
+            })?;
+        previews.push(MigrationPreview { name, statements });
+    }
+    Ok(previews)
+}
+
+/// Proxy connection backend that records executed statements and refuses
+/// queries, which have no answer without a database.
+#[derive(Debug)]
+struct StatementRecorder {
+    statements: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl ProxyDatabaseTrait for StatementRecorder {
+    async fn query(&self, statement: Statement) -> Result<Vec<ProxyRow>, DbErr> {
+        Err(DbErr::Custom(format!(
+            "migration queries the database and cannot be previewed: {statement}"
+        )))
+    }
+
+    async fn execute(&self, statement: Statement) -> Result<ProxyExecResult, DbErr> {
+        self.statements
+            .lock()
+            .map_err(|_| DbErr::Custom("statement recorder poisoned".to_owned()))?
+            .push(statement.to_string());
+        Ok(ProxyExecResult::default())
+    }
+}
+
+/// Run `up()` against a recording connection and collect the statements it
+/// issued.
+async fn record_up_statements(
+    backend: DatabaseBackend,
+    migration: &dyn MigrationTrait,
+) -> Result<Vec<String>, DbErr> {
+    let statements = Arc::new(Mutex::new(Vec::new()));
+    let recorder: Box<dyn ProxyDatabaseTrait> = Box::new(StatementRecorder {
+        statements: Arc::clone(&statements),
+    });
+    let conn = Database::connect_proxy(backend, Arc::new(recorder)).await?;
+    migration
+        .up(&sea_orm_migration::SchemaManager::new(&conn))
+        .await?;
+
+    let statements = statements
+        .lock()
+        .map_err(|_| DbErr::Custom("statement recorder poisoned".to_owned()))?
+        .clone();
+    Ok(statements)
+}
+
+/// Roll back the last `steps` applied migrations of a module.
+///
+/// Migrations are undone in reverse application order (most recently applied
+/// first, see [`MigrationStatus::applied`]),
+/// each running `down()` and removing its history record in one best-effort
+/// transaction, like [`run_migrations_for_module`] does for `up()`.
+///
+/// # Returns
+///
+/// Names of the rolled back migrations, most recently applied first.
+///
+/// # Errors
+///
+/// Returns `Err(MigrationError::UnknownMigration)` before touching anything if
+/// one of the migrations to roll back is not provided by the module, and
+/// other `MigrationError`s if the history cannot be queried or a `down()`
+/// fails (earlier rollbacks of the same call stay committed).
+pub async fn rollback_migrations(
+    db: &crate::Db,
+    module_name: &str,
+    migrations: Vec<Box<dyn MigrationTrait>>,
+    steps: usize,
+) -> Result<Vec<String>, MigrationError> {
+    let conn = db.sea_internal();
+    rollback_module_migrations(&conn, module_name, migrations, steps).await
+}
+
+async fn rollback_module_migrations<C>(
+    conn: &C,
+    module_name: &str,
+    migrations: Vec<Box<dyn MigrationTrait>>,
+    steps: usize,
+) -> Result<Vec<String>, MigrationError>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let table_name = migration_table_name(module_name);
+    if steps == 0 || !migration_table_exists(conn, &table_name, module_name).await? {
+        return Ok(vec![]);
+    }
+
+    let mut applied = get_applied_history(conn, &table_name, module_name).await?;
+    applied.reverse();
+    applied.truncate(steps);
+
+    let mut to_undo = Vec::with_capacity(applied.len());
+    for name in applied {
+        let migration = migrations
+            .iter()
+            .find(|m| m.name() == name)
+            .ok_or_else(|| MigrationError::UnknownMigration {
+                module: module_name.to_owned(),
+                name: name.clone(),
+            })?;
+        to_undo.push((name, migration));
+    }
+
+    let mut rolled_back = Vec::with_capacity(to_undo.len());
+    for (name, migration) in to_undo {
+        info!(
+            module = module_name,
+            migration = %name,
+            "Rolling back migration"
+        );
+
+        let failed = |e| MigrationError::MigrationFailed {
+            module: module_name.to_owned(),
+            migration: name.clone(),
+            source: e,
+        };
+        let txn = conn.begin().await.map_err(failed)?;
+
+        let manager = sea_orm_migration::SchemaManager::new(&txn);
+        let res: Result<(), MigrationError> = (async {
+            migration.down(&manager).await.map_err(failed)?;
+            unrecord_migration(&txn, &table_name, module_name, &name).await?;
+            Ok(())
+        })
+        .await;
+
+        match res {
+            Ok(()) => txn.commit().await.map_err(failed)?,
+            Err(err) => {
+                _ = txn.rollback().await;
+                return Err(err);
+            }
+        }
+
+        rolled_back.push(name);
+    }
+
+    info!(
+        module = module_name,
+        rolled_back = rolled_back.len(),
+        "Migration rollback complete"
+    );
+
+    Ok(rolled_back)
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -861,6 +1192,125 @@ mod tests {
             assert_eq!(pending, vec!["m002_second"]);
         }
 
+        fn test_migrations(names: &[&str]) -> Vec<Box<dyn MigrationTrait>> {
+            names
+                .iter()
+                .map(|n| {
+                    Box::new(TestMigration {
+                        name: (*n).to_owned(),
+                    }) as Box<dyn MigrationTrait>
+                })
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn test_migration_status_and_preview() {
+            let db = setup_test_db().await;
+            let module_name = "test_status";
+            let all = test_migrations(&["m002_second", "m001_first"]);
+
+            let status = get_migration_status(&db, module_name, &all)
+                .await
+                .expect("Should succeed");
+            assert!(status.applied.is_empty());
+            assert_eq!(status.pending, vec!["m001_first", "m002_second"]);
+
+            run_migrations_for_module(&db, module_name, test_migrations(&["m001_first"]))
+                .await
+                .expect("Should succeed");
+
+            let status = get_migration_status(&db, module_name, &all)
+                .await
+                .expect("Should succeed");
+            assert_eq!(status.applied, vec!["m001_first"]);
+            assert_eq!(status.pending, vec!["m002_second"]);
+
+            let previews = preview_pending_migrations(&db, module_name, &all)
+                .await
+                .expect("Should succeed");
+            assert_eq!(previews.len(), 1);
+            assert_eq!(previews[0].name, "m002_second");
+            assert_eq!(
+                previews[0].statements,
+                vec![r#"CREATE TABLE IF NOT EXISTS "test_m002_second" (id INTEGER PRIMARY KEY)"#]
+            );
+
+            // The preview applied nothing
+            let pending = get_pending_migrations(&db, module_name, &all)
+                .await
+                .expect("Should succeed");
+            assert_eq!(pending, vec!["m002_second"]);
+        }
+
+        #[tokio::test]
+        async fn test_rollback_migrations() {
+            let db = setup_test_db().await;
+            let module_name = "test_rollback";
+            let names = ["m001_first", "m002_second", "m003_third"];
+
+            run_migrations_for_module(&db, module_name, test_migrations(&names))
+                .await
+                .expect("Should succeed");
+
+            let rolled_back = rollback_migrations(&db, module_name, test_migrations(&names), 2)
+                .await
+                .expect("Should succeed");
+            assert_eq!(rolled_back, vec!["m003_third", "m002_second"]);
+
+            let pending = get_pending_migrations(&db, module_name, &test_migrations(&names))
+                .await
+                .expect("Should succeed");
+            assert_eq!(pending, vec!["m002_second", "m003_third"]);
+
+            // The remaining migration is not provided, so nothing is rolled back
+            let err = rollback_migrations(&db, module_name, test_migrations(&["m002_second"]), 1)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, MigrationError::UnknownMigration { ref name, .. } if name == "m001_first")
+            );
+
+            let status = get_migration_status(&db, module_name, &test_migrations(&names))
+                .await
+                .expect("Should succeed");
+            assert_eq!(status.applied, vec!["m001_first"]);
+        }
+
+        #[tokio::test]
+        async fn test_status_and_rollback_follow_application_order() {
+            let db = setup_test_db().await;
+            let module_name = "test_application_order";
+            let names = ["m001_first", "m002_second"];
+
+            // m002 ships first; m001 is backported and applied later
+            run_migrations_for_module(&db, module_name, test_migrations(&["m002_second"]))
+                .await
+                .expect("Should succeed");
+            let conn = db.sea_internal();
+            conn.execute(Statement::from_string(
+                DatabaseBackend::Sqlite,
+                format!(
+                    r#"UPDATE "{}" SET applied_at = '2000-01-01 00:00:00'"#,
+                    migration_table_name(module_name)
+                ),
+            ))
+            .await
+            .expect("Should succeed");
+            run_migrations_for_module(&db, module_name, test_migrations(&names))
+                .await
+                .expect("Should succeed");
+
+            let status = get_migration_status(&db, module_name, &test_migrations(&names))
+                .await
+                .expect("Should succeed");
+            assert_eq!(status.applied, vec!["m002_second", "m001_first"]);
+
+            let rolled_back = rollback_migrations(&db, module_name, test_migrations(&names), 1)
+                .await
+                .expect("Should succeed");
+            assert_eq!(rolled_back, vec!["m001_first"]);
+        }
+
         #[tokio::test]
         async fn test_run_migrations_for_testing() {
             let db = setup_test_db().await;
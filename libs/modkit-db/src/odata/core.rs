@@ -2,21 +2,30 @@
 //! Parsing belongs to API/gateway. This module only consumes `modkit_odata::ast::Expr`.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use bigdecimal::{BigDecimal, ToPrimitive};
 use chrono::{NaiveDate, NaiveTime, Utc};
-use modkit_odata::{CursorV1, Error as ODataError, ODataOrderBy, ODataQuery, SortDir, ast as core};
+use modkit_odata::apply::{AggregateMethod, AggregateRow, Apply};
+use modkit_odata::{
+    CursorV1, Error as ODataError, ODataLimits, ODataOrderBy, ODataQuery, SortDir, ast as core,
+};
 use rust_decimal::Decimal;
 use sea_orm::{
-    ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
-    sea_query::{Expr, Order},
+    ColumnTrait, Condition, ConnectionTrait, DbBackend, DbErr, EntityTrait, FromQueryResult,
+    IdenStatic, Iterable, QueryFilter, QueryOrder, QueryResult, QuerySelect, QueryTrait,
+    sea_query::{Alias, Expr, Func, Order, Query, SelectStatement, SimpleExpr},
 };
 use thiserror::Error;
 
-use modkit_odata::filter::FieldKind;
+use modkit_odata::filter::{FieldFn, FieldKind};
 
 use crate::odata::LimitCfg;
-use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
+use crate::odata::sea_orm_filter::{custom_expr, field_fn_expr};
+use crate::secure::{
+    AccessScope, DBRunner, DBRunnerInternal, ScopableEntity, SeaOrmRunner, hidden_columns,
+    masks_column, not_deleted, scoped_exists_subquery,
+};
 
 /// Type alias for cursor extraction function to reduce type complexity
 type CursorExtractor<E> = fn(&<E as EntityTrait>::Model) -> String;
@@ -32,6 +41,7 @@ pub struct Field<E: EntityTrait> {
 #[must_use]
 pub struct FieldMap<E: EntityTrait> {
     map: HashMap<String, Field<E>>,
+    collections: HashMap<String, Collection<E>>,
 }
 
 impl<E: EntityTrait> Default for FieldMap<E> {
@@ -44,6 +54,7 @@ impl<E: EntityTrait> FieldMap<E> {
     pub fn new() -> Self {
         Self {
             map: HashMap::new(),
+            collections: HashMap::new(),
         }
     }
     pub fn insert(mut self, api_name: impl Into<String>, col: E::Column, kind: FieldKind) -> Self {
@@ -76,6 +87,55 @@ impl<E: EntityTrait> FieldMap<E> {
         self
     }
 
+    /// Declare a JSON array column for `any`/`all` lambdas, e.g.
+    /// `tags/any(t: t eq 'urgent')`.
+    ///
+    /// Elements are read as `kind`, which must be `String`, `I64`, `F64` or
+    /// `Bool`.
+    pub fn insert_json_array(
+        mut self,
+        api_name: impl Into<String>,
+        col: E::Column,
+        kind: FieldKind,
+    ) -> Self {
+        self.collections.insert(
+            api_name.into().to_lowercase(),
+            Collection::JsonArray { col, kind },
+        );
+        self
+    }
+
+    /// Declare the rows of `J` whose `child_col` equals `parent_col` as a
+    /// collection for `any`/`all` lambdas, e.g. `items/any(i: i/qty gt 5)`.
+    ///
+    /// The lambda predicate refers to fields of `child_map` as `var/field`.
+    /// It is compiled into a correlated `EXISTS` subquery that only sees rows
+    /// of `J` within the caller's scope (like `scope_via_exists`), so these
+    /// lambdas are only available through [`expr_to_scoped_condition`] and
+    /// `OPager`. Many-to-many relations are declared on the join entity.
+    pub fn insert_related<J>(
+        mut self,
+        api_name: impl Into<String>,
+        parent_col: E::Column,
+        child_col: J::Column,
+        child_map: FieldMap<J>,
+    ) -> Self
+    where
+        J: ScopableEntity,
+        J::Column: ColumnTrait + Copy,
+    {
+        let lambda: Arc<RelatedLambda> = Arc::new(
+            move |op: core::LambdaOperator,
+                  body: Option<(&str, &core::Expr)>,
+                  ctx: LambdaCtx<'_>| {
+                related_lambda::<E, J>(parent_col, child_col, &child_map, op, body, ctx)
+            },
+        );
+        self.collections
+            .insert(api_name.into().to_lowercase(), Collection::Related(lambda));
+        self
+    }
+
     pub fn encode_model_key(&self, model: &E::Model, field_name: &str) -> Option<String> {
         let f = self.get(field_name)?;
         f.to_string_for_cursor.map(|f| f(model))
@@ -84,6 +144,71 @@ impl<E: EntityTrait> FieldMap<E> {
     pub fn get(&self, name: &str) -> Option<&Field<E>> {
         self.map.get(&name.to_lowercase())
     }
+
+    /// Refuse the parts of `q` that would read columns masked in `scope`
+    /// around the masked projection of `scope_with`.
+    ///
+    /// Sorting (`$orderby`, the cursor order and `tiebreaker`), grouping or
+    /// aggregating by a masked field would reveal the withheld values, and
+    /// cursors would be built from masked ones. A `$select` of a field hidden
+    /// on every access path is refused as well. `$filter` is checked when it
+    /// is compiled with [`expr_to_scoped_condition`].
+    ///
+    /// # Errors
+    /// Returns `ODataError::FieldNotReadable` naming the first such field.
+    pub fn ensure_readable(
+        &self,
+        scope: &AccessScope,
+        q: &ODataQuery,
+        tiebreaker: Option<&str>,
+    ) -> Result<(), ODataError>
+    where
+        E: ScopableEntity,
+        E::Column: Copy,
+    {
+        let cursor_order = q
+            .cursor
+            .as_ref()
+            .and_then(|c| ODataOrderBy::from_signed_tokens(&c.s).ok())
+            .unwrap_or_default();
+        let sorted = q
+            .order
+            .0
+            .iter()
+            .chain(&cursor_order.0)
+            .map(|k| k.field.as_str());
+        let apply = q.apply.as_ref();
+        let grouped = apply
+            .into_iter()
+            .flat_map(|a| a.group_by.iter().map(String::as_str));
+        let aggregated = apply
+            .into_iter()
+            .flat_map(|a| a.aggregates.iter().filter_map(|agg| agg.field.as_deref()));
+
+        let masked = sorted
+            .chain(tiebreaker)
+            .chain(grouped)
+            .chain(aggregated)
+            .find(|name| {
+                self.get(name)
+                    .is_some_and(|f| masks_column::<E>(scope, f.col))
+            });
+        if let Some(name) = masked {
+            return Err(ODataError::FieldNotReadable(name.to_owned()));
+        }
+
+        // Refuse the field names of hidden columns in this map too
+        let columns = hidden_columns::<E>(scope);
+        let columns: Vec<&str> = columns.iter().map(IdenStatic::as_str).collect();
+        let mut hidden = scope.hidden_fields();
+        hidden.extend(
+            self.map
+                .iter()
+                .filter(|(_, f)| columns.contains(&f.col.as_str()))
+                .map(|(name, _)| name.as_str()),
+        );
+        modkit_odata::ensure_selectable(q.select.as_deref(), &hidden)
+    }
 }
 
 #[derive(Debug, Error, Clone)]
@@ -112,11 +237,25 @@ pub enum ODataBuildError {
     #[error("bare literal not allowed")]
     BareLiteral,
 
+    #[error("field is not readable: {0}")]
+    MaskedField(String),
+
     #[error("{0}")]
     Other(&'static str),
 }
 pub type ODataBuildResult<T> = Result<T, ODataBuildError>;
 
+impl ODataBuildError {
+    /// The `OData` error of a `$filter` that failed to compile.
+    #[must_use]
+    pub fn into_filter_error(self) -> ODataError {
+        match self {
+            Self::MaskedField(field) => ODataError::FieldNotReadable(field),
+            e => ODataError::InvalidFilter(e.to_string()),
+        }
+    }
+}
+
 /* ---------- coercion helpers ---------- */
 
 fn bigdecimal_to_decimal(bd: &BigDecimal) -> ODataBuildResult<Decimal> {
@@ -254,8 +393,8 @@ fn like_ends(s: &str) -> String {
 /* ---------- small guards ---------- */
 
 #[inline]
-fn ensure_string_field<E: EntityTrait>(f: &Field<E>, _field_name: &str) -> ODataBuildResult<()> {
-    if f.kind != FieldKind::String {
+fn ensure_string_field(kind: FieldKind) -> ODataBuildResult<()> {
+    if kind != FieldKind::String {
         return Err(ODataBuildError::TypeMismatch {
             expected: FieldKind::String,
             got: "non-string field",
@@ -343,7 +482,25 @@ pub fn build_cursor_predicate<E: EntityTrait>(
 where
     E::Column: ColumnTrait + Copy,
 {
-    if cursor.k.len() != order.0.len() {
+    let keys = order
+        .0
+        .iter()
+        .map(|key| {
+            let field = fmap
+                .get(&key.field)
+                .ok_or_else(|| ODataBuildError::UnknownField(key.field.clone()))?;
+            Ok((Expr::col(field.col).into(), field.kind, key.dir))
+        })
+        .collect::<ODataBuildResult<Vec<_>>>()?;
+    cursor_condition(cursor, &keys)
+}
+
+/// Sort key resolved to its SQL expression.
+type SortKey = (SimpleExpr, FieldKind, SortDir);
+
+/// Lexicographic cursor condition over resolved sort keys.
+fn cursor_condition(cursor: &CursorV1, keys: &[SortKey]) -> ODataBuildResult<Condition> {
+    if cursor.k.len() != keys.len() {
         return Err(ODataBuildError::Other(
             "cursor keys count mismatch with order fields",
         ));
@@ -351,13 +508,9 @@ where
 
     // Parse cursor values
     let mut cursor_values = Vec::new();
-    for (i, key_str) in cursor.k.iter().enumerate() {
-        let order_key = &order.0[i];
-        let field = fmap
-            .get(&order_key.field)
-            .ok_or_else(|| ODataBuildError::UnknownField(order_key.field.clone()))?;
-        let value = parse_cursor_value(field.kind, key_str)?;
-        cursor_values.push((field, value, order_key.dir));
+    for (key_str, (expr, kind, dir)) in cursor.k.iter().zip(keys) {
+        let value = parse_cursor_value(*kind, key_str)?;
+        cursor_values.push((expr, value, *dir));
     }
 
     // Determine if we're going backward
@@ -376,23 +529,24 @@ where
         let mut prefix_condition = Condition::all();
 
         // Add equality conditions for all previous fields
-        for (field, value, _) in cursor_values.iter().take(i) {
-            prefix_condition = prefix_condition.add(Expr::col(field.col).eq(value.clone()));
+        for (expr, value, _) in cursor_values.iter().take(i) {
+            prefix_condition = prefix_condition.add(Expr::expr((*expr).clone()).eq(value.clone()));
         }
 
         // Add the comparison condition for current field
-        let (field, value, dir) = &cursor_values[i];
+        let (expr, value, dir) = &cursor_values[i];
+        let expr = Expr::expr((*expr).clone());
         let comparison = if is_backward {
             // Backward: reverse the comparison
             match dir {
-                SortDir::Asc => Expr::col(field.col).lt(value.clone()),
-                SortDir::Desc => Expr::col(field.col).gt(value.clone()),
+                SortDir::Asc => expr.lt(value.clone()),
+                SortDir::Desc => expr.gt(value.clone()),
             }
         } else {
             // Forward: normal comparison
             match dir {
-                SortDir::Asc => Expr::col(field.col).gt(value.clone()),
-                SortDir::Desc => Expr::col(field.col).lt(value.clone()),
+                SortDir::Asc => expr.gt(value.clone()),
+                SortDir::Desc => expr.lt(value.clone()),
             }
         };
         prefix_condition = prefix_condition.add(comparison);
@@ -456,6 +610,11 @@ pub fn build_cursor_for_model<E: EntityTrait>(
 
 /// Convert an `OData` filter expression AST to a `SeaORM` Condition.
 ///
+/// `any`/`all` lambdas are rejected: over related entities they need the
+/// caller's scope, and over JSON arrays the backend (see
+/// [`expr_to_scoped_condition`]). So are the functions whose SQL differs per
+/// backend, such as `length`, `indexof` and the date parts.
+///
 /// # Errors
 /// Returns `ODataBuildError` if the expression contains unknown fields or unsupported operations.
 pub fn expr_to_condition<E: EntityTrait>(
@@ -465,29 +624,228 @@ pub fn expr_to_condition<E: EntityTrait>(
 where
     E::Column: ColumnTrait + Copy,
 {
+    compile(expr, fmap, LambdaCtx::default())
+}
+
+/// Convert an `OData` filter expression AST to a `SeaORM` Condition for
+/// `backend`, including `any`/`all` lambdas.
+///
+/// Lambdas over related entities only consider related rows within `scope`.
+///
+/// # Errors
+/// Returns `ODataBuildError` if the expression contains unknown fields or unsupported operations.
+pub fn expr_to_scoped_condition<E: ScopableEntity>(
+    expr: &core::Expr,
+    fmap: &FieldMap<E>,
+    backend: DbBackend,
+    scope: &AccessScope,
+) -> ODataBuildResult<Condition>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    let masked = masked_columns::<E>(scope);
+    let ctx = LambdaCtx {
+        backend: Some(backend),
+        scope: Some(scope),
+        masked: &masked,
+    };
+    compile(expr, fmap, ctx)
+}
+
+/// Names of the columns of `E` that `scope` masks.
+fn masked_columns<E: ScopableEntity>(scope: &AccessScope) -> Vec<String>
+where
+    E::Column: Copy,
+{
+    E::Column::iter()
+        .filter(|c| masks_column::<E>(scope, *c))
+        .map(|c| c.as_str().to_owned())
+        .collect()
+}
+
+/// Condition of `expr` in the dialect of `conn`, for the paging functions.
+fn filter_condition<E, C>(
+    expr: &core::Expr,
+    fmap: &FieldMap<E>,
+    conn: &C,
+) -> Result<Condition, ODataError>
+where
+    E: EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    C: DBRunner,
+{
+    let ctx = LambdaCtx {
+        backend: Some(DBRunnerInternal::as_seaorm(conn).backend()),
+        ..LambdaCtx::default()
+    };
+    compile(expr, fmap, ctx).map_err(|e| ODataError::InvalidFilter(e.to_string()))
+}
+
+/// What lambdas need beyond the field map.
+#[derive(Clone, Copy, Default)]
+struct LambdaCtx<'a> {
+    /// Dialect of lambdas over JSON arrays.
+    backend: Option<DbBackend>,
+    /// Scope of the rows seen by lambdas over related entities; fields it
+    /// masks cannot be compared.
+    scope: Option<&'a AccessScope>,
+    /// Columns of the entity being compiled that `scope` masks.
+    masked: &'a [String],
+}
+
+/// Resolves the names a filter refers to.
+trait Names {
+    /// Column expression and kind of a scalar field.
+    ///
+    /// Fields masked in the scope of `ctx` are refused: comparing them would
+    /// reveal the withheld values.
+    fn field(&self, name: &str, ctx: LambdaCtx<'_>) -> ODataBuildResult<(SimpleExpr, FieldKind)>;
+
+    /// `EXISTS` check of a lambda over a collection field.
+    fn lambda(
+        &self,
+        path: &str,
+        op: core::LambdaOperator,
+        body: Option<(&str, &core::Expr)>,
+        ctx: LambdaCtx<'_>,
+    ) -> ODataBuildResult<SimpleExpr>;
+}
+
+impl<E: EntityTrait> Names for FieldMap<E>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    fn field(&self, name: &str, ctx: LambdaCtx<'_>) -> ODataBuildResult<(SimpleExpr, FieldKind)> {
+        let f = self
+            .get(name)
+            .ok_or_else(|| ODataBuildError::UnknownField(name.to_owned()))?;
+        ensure_unmasked(f.col, name, ctx)?;
+        Ok((Expr::col(f.col).into(), f.kind))
+    }
+
+    fn lambda(
+        &self,
+        path: &str,
+        op: core::LambdaOperator,
+        body: Option<(&str, &core::Expr)>,
+        ctx: LambdaCtx<'_>,
+    ) -> ODataBuildResult<SimpleExpr> {
+        match self.collections.get(&path.to_lowercase()) {
+            Some(Collection::JsonArray { col, kind }) => {
+                ensure_unmasked(*col, path, ctx)?;
+                json_array_lambda::<E>(*col, *kind, op, body, ctx)
+            }
+            Some(Collection::Related(lambda)) => lambda(op, body, ctx),
+            None => Err(ODataBuildError::UnknownField(path.to_owned())),
+        }
+    }
+}
+
+/// Refuse `col` (named `name` in the query) if the scope of `ctx` masks it.
+fn ensure_unmasked<Col: ColumnTrait>(
+    col: Col,
+    name: &str,
+    ctx: LambdaCtx<'_>,
+) -> ODataBuildResult<()> {
+    if ctx.masked.iter().any(|m| m == col.as_str()) {
+        return Err(ODataBuildError::MaskedField(name.to_owned()));
+    }
+    Ok(())
+}
+
+/// Names inside a lambda over related rows: `var/field` is a field of `names`.
+struct Member<'a, N> {
+    var: &'a str,
+    names: &'a N,
+}
+
+impl<N> Member<'_, N> {
+    fn strip<'n>(&self, name: &'n str) -> ODataBuildResult<&'n str> {
+        name.strip_prefix(self.var)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .ok_or_else(|| ODataBuildError::UnknownField(name.to_owned()))
+    }
+}
+
+impl<N: Names> Names for Member<'_, N> {
+    fn field(&self, name: &str, ctx: LambdaCtx<'_>) -> ODataBuildResult<(SimpleExpr, FieldKind)> {
+        self.names.field(self.strip(name)?, ctx)
+    }
+
+    fn lambda(
+        &self,
+        path: &str,
+        op: core::LambdaOperator,
+        body: Option<(&str, &core::Expr)>,
+        ctx: LambdaCtx<'_>,
+    ) -> ODataBuildResult<SimpleExpr> {
+        self.names.lambda(self.strip(path)?, op, body, ctx)
+    }
+}
+
+/// Names inside a lambda over a JSON array: `var` is the current element.
+struct JsonElement<'a> {
+    var: &'a str,
+    expr: SimpleExpr,
+    kind: FieldKind,
+}
+
+impl Names for JsonElement<'_> {
+    fn field(&self, name: &str, _ctx: LambdaCtx<'_>) -> ODataBuildResult<(SimpleExpr, FieldKind)> {
+        if name == self.var {
+            Ok((self.expr.clone(), self.kind))
+        } else {
+            Err(ODataBuildError::UnknownField(name.to_owned()))
+        }
+    }
+
+    fn lambda(
+        &self,
+        path: &str,
+        _op: core::LambdaOperator,
+        _body: Option<(&str, &core::Expr)>,
+        _ctx: LambdaCtx<'_>,
+    ) -> ODataBuildResult<SimpleExpr> {
+        // JSON array elements are scalars
+        Err(ODataBuildError::UnknownField(path.to_owned()))
+    }
+}
+
+fn compile<N: Names>(
+    expr: &core::Expr,
+    names: &N,
+    ctx: LambdaCtx<'_>,
+) -> ODataBuildResult<Condition> {
     use core::CompareOperator as Op;
     use core::Expr as X;
 
     Ok(match expr {
         X::And(a, b) => {
-            let left = expr_to_condition::<E>(a, fmap)?;
-            let right = expr_to_condition::<E>(b, fmap)?;
+            let left = compile(a, names, ctx)?;
+            let right = compile(b, names, ctx)?;
             Condition::all().add(left).add(right) // AND
         }
         X::Or(a, b) => {
-            let left = expr_to_condition::<E>(a, fmap)?;
-            let right = expr_to_condition::<E>(b, fmap)?;
+            let left = compile(a, names, ctx)?;
+            let right = compile(b, names, ctx)?;
             Condition::any().add(left).add(right) // OR
         }
         X::Not(x) => {
-            let inner = expr_to_condition::<E>(x, fmap)?;
+            let inner = compile(x, names, ctx)?;
             Condition::all().add(inner).not()
         }
 
-        // Identifier op Value
+        // Identifier op Value, func(Identifier) op Value
         X::Compare(lhs, op, rhs) => {
-            let (name, rhs_val) = match (&**lhs, &**rhs) {
-                (X::Identifier(name), X::Value(val)) => (name, val),
+            let (col, kind, rhs_val) = match (&**lhs, &**rhs) {
+                (X::Identifier(name), X::Value(val)) => {
+                    let (col, kind) = names.field(name, ctx)?;
+                    (col, kind, val)
+                }
+                (X::Function(fname, args), X::Value(val)) => {
+                    let (expr, kind) = function_operand(fname, args, names, ctx)?;
+                    (expr, kind, val)
+                }
                 (X::Identifier(_), X::Identifier(_)) => {
                     return Err(ODataBuildError::Other(
                         "field-to-field comparison is not supported",
@@ -495,28 +853,25 @@ where
                 }
                 _ => return Err(ODataBuildError::Other("unsupported comparison form")),
             };
-            let field = fmap
-                .get(name)
-                .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
-            let col = field.col;
 
             // null handling
             if matches!(rhs_val, core::Value::Null) {
                 return Ok(match op {
-                    Op::Eq => Condition::all().add(Expr::col(col).is_null()),
-                    Op::Ne => Condition::all().add(Expr::col(col).is_not_null()),
+                    Op::Eq => Condition::all().add(Expr::expr(col).is_null()),
+                    Op::Ne => Condition::all().add(Expr::expr(col).is_not_null()),
                     _ => return Err(ODataBuildError::UnsupportedOp(*op)),
                 });
             }
 
-            let value = coerce(field.kind, rhs_val)?;
+            let value = coerce(kind, rhs_val)?;
+            let col = Expr::expr(col);
             let expr = match op {
-                Op::Eq => Expr::col(col).eq(value),
-                Op::Ne => Expr::col(col).ne(value),
-                Op::Gt => Expr::col(col).gt(value),
-                Op::Ge => Expr::col(col).gte(value),
-                Op::Lt => Expr::col(col).lt(value),
-                Op::Le => Expr::col(col).lte(value),
+                Op::Eq => col.eq(value),
+                Op::Ne => col.ne(value),
+                Op::Gt => col.gt(value),
+                Op::Ge => col.gte(value),
+                Op::Lt => col.lt(value),
+                Op::Le => col.lte(value),
             };
             Condition::all().add(expr)
         }
@@ -526,46 +881,47 @@ where
             let X::Identifier(name) = &**l else {
                 return Err(ODataBuildError::Other("left side of IN must be a field"));
             };
-            let f = fmap
-                .get(name)
-                .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
-            let col = f.col;
-            let vals = coerce_many(f.kind, list)?;
+            let (col, kind) = names.field(name, ctx)?;
+            let vals = coerce_many(kind, list)?;
             if vals.is_empty() {
                 // IN () → always false
                 Condition::all().add(Expr::value(1).eq(0))
             } else {
-                Condition::all().add(Expr::col(col).is_in(vals))
+                Condition::all().add(Expr::expr(col).is_in(vals))
             }
         }
 
-        // Supported functions: contains/startswith/endswith
+        // contains/startswith/endswith over a field or a string function of one
         X::Function(fname, args) => {
             let n = fname.to_ascii_lowercase();
-            match (n.as_str(), args.as_slice()) {
-                ("contains", [X::Identifier(name), X::Value(core::Value::String(s))]) => {
-                    let f = fmap
-                        .get(name)
-                        .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
-                    ensure_string_field(f, name)?;
-                    Condition::all().add(Expr::col(f.col).like(like_contains(s)))
+            let (operand, pattern) = match (n.as_str(), args.as_slice()) {
+                ("contains", [operand, X::Value(core::Value::String(s))]) => {
+                    (operand, like_contains(s))
                 }
-                ("startswith", [X::Identifier(name), X::Value(core::Value::String(s))]) => {
-                    let f = fmap
-                        .get(name)
-                        .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
-                    ensure_string_field(f, name)?;
-                    Condition::all().add(Expr::col(f.col).like(like_starts(s)))
+                ("startswith", [operand, X::Value(core::Value::String(s))]) => {
+                    (operand, like_starts(s))
                 }
-                ("endswith", [X::Identifier(name), X::Value(core::Value::String(s))]) => {
-                    let f = fmap
-                        .get(name)
-                        .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
-                    ensure_string_field(f, name)?;
-                    Condition::all().add(Expr::col(f.col).like(like_ends(s)))
+                ("endswith", [operand, X::Value(core::Value::String(s))]) => {
+                    (operand, like_ends(s))
                 }
                 _ => return Err(ODataBuildError::UnsupportedFn(fname.clone())),
+            };
+            let (col, kind) = match operand {
+                X::Identifier(name) => names.field(name, ctx)?,
+                X::Function(inner, inner_args) => function_operand(inner, inner_args, names, ctx)?,
+                _ => return Err(ODataBuildError::UnsupportedFn(fname.clone())),
+            };
+            ensure_string_field(kind)?;
+            Condition::all().add(Expr::expr(col).like(pattern))
+        }
+
+        // path/any(var: predicate), path/all(var: predicate)
+        X::Lambda(path, op, body) => {
+            if *op == core::LambdaOperator::All && body.is_none() {
+                return Err(ODataBuildError::Other("all() requires a predicate"));
             }
+            let body = body.as_ref().map(|(var, pred)| (var.as_str(), &**pred));
+            Condition::all().add(names.lambda(path, *op, body, ctx)?)
         }
 
         // Leaf forms are not valid WHERE by themselves
@@ -574,6 +930,180 @@ where
     })
 }
 
+/// `func(field)` as a comparison operand: its SQL in the dialect of `ctx`
+/// and the kind of its result.
+fn function_operand<N: Names>(
+    fname: &str,
+    args: &[core::Expr],
+    names: &N,
+    ctx: LambdaCtx<'_>,
+) -> ODataBuildResult<(SimpleExpr, FieldKind)> {
+    let (func, name) = FieldFn::from_call(fname, args)
+        .ok_or_else(|| ODataBuildError::UnsupportedFn(fname.to_owned()))?;
+    let (col, kind) = names.field(name, ctx)?;
+    if !func.accepts(kind) {
+        return Err(ODataBuildError::UnsupportedFn(fname.to_owned()));
+    }
+    let expr = field_fn_expr(&func, col, ctx.backend)
+        .map_err(|_| ODataBuildError::Other("this function needs the database backend"))?;
+    Ok((expr, func.result_kind()))
+}
+
+/* ---------- lambdas over collections ---------- */
+
+/// Compiles `path/any(...)` / `path/all(...)` for one related collection.
+type RelatedLambda = dyn Fn(
+        core::LambdaOperator,
+        Option<(&str, &core::Expr)>,
+        LambdaCtx<'_>,
+    ) -> ODataBuildResult<SimpleExpr>
+    + Send
+    + Sync;
+
+/// A collection field usable in `any`/`all` lambdas.
+#[derive(Clone)]
+enum Collection<E: EntityTrait> {
+    /// JSON array of scalars stored in a column of the entity itself.
+    JsonArray { col: E::Column, kind: FieldKind },
+    /// Rows of another scopable entity, see [`FieldMap::insert_related`].
+    Related(Arc<RelatedLambda>),
+}
+
+/// Members that decide a lambda: those matching the predicate for `any`,
+/// those failing it for `all`.
+fn deciding_members(op: core::LambdaOperator, predicate: Option<Condition>) -> Condition {
+    match (op, predicate) {
+        (_, None) => Condition::all(),
+        (core::LambdaOperator::Any, Some(p)) => p,
+        (core::LambdaOperator::All, Some(p)) => p.not(),
+    }
+}
+
+/// `EXISTS (sub)` for `any`, `NOT EXISTS (sub)` for `all`.
+fn exists(op: core::LambdaOperator, sub: SelectStatement) -> SimpleExpr {
+    match op {
+        core::LambdaOperator::Any => Expr::exists(sub),
+        core::LambdaOperator::All => Expr::exists(sub).not(),
+    }
+}
+
+/// `EXISTS (SELECT 1 FROM J WHERE J.child_col = E.parent_col AND ...)`,
+/// seeing only the rows of `J` in the caller's scope.
+fn related_lambda<E, J>(
+    parent_col: E::Column,
+    child_col: J::Column,
+    child_map: &FieldMap<J>,
+    op: core::LambdaOperator,
+    body: Option<(&str, &core::Expr)>,
+    ctx: LambdaCtx<'_>,
+) -> ODataBuildResult<SimpleExpr>
+where
+    E: EntityTrait,
+    J: ScopableEntity,
+    J::Column: ColumnTrait + Copy,
+{
+    let scope = ctx.scope.ok_or(ODataBuildError::Other(
+        "lambdas over related entities need a security scope",
+    ))?;
+    // The members are rows of `J`, masked as `J` resolves the scope's fields
+    let masked = masked_columns::<J>(scope);
+    let member_ctx = LambdaCtx {
+        masked: &masked,
+        ..ctx
+    };
+    let predicate = body
+        .map(|(var, pred)| {
+            let member = Member {
+                var,
+                names: child_map,
+            };
+            compile(pred, &member, member_ctx)
+        })
+        .transpose()?;
+
+    let mut cond = Condition::all()
+        .add(Expr::col((J::default(), child_col)).equals((E::default(), parent_col)))
+        .add(deciding_members(op, predicate));
+    if let Some(alive) = not_deleted::<J>() {
+        cond = cond.add(alive);
+    }
+    Ok(exists(op, scoped_exists_subquery::<J>(scope, cond)))
+}
+
+/// `EXISTS (SELECT 1 FROM <elements of col> AS elem WHERE ...)`.
+fn json_array_lambda<E>(
+    col: E::Column,
+    kind: FieldKind,
+    op: core::LambdaOperator,
+    body: Option<(&str, &core::Expr)>,
+    ctx: LambdaCtx<'_>,
+) -> ODataBuildResult<SimpleExpr>
+where
+    E: EntityTrait,
+    E::Column: ColumnTrait + Copy,
+{
+    let backend = ctx.backend.ok_or(ODataBuildError::Other(
+        "lambdas over JSON arrays need the database backend",
+    ))?;
+    let sql_type = json_element_type(kind, backend)?;
+    let elem = Alias::new("elem");
+    let array = Expr::col((E::default(), col));
+
+    let (elements, member) = match backend {
+        DbBackend::Postgres => (
+            Func::cust(Alias::new("jsonb_array_elements_text"))
+                .arg(array.cast_as(Alias::new("jsonb"))),
+            Expr::col((elem.clone(), elem.clone())).cast_as(Alias::new(sql_type)),
+        ),
+        DbBackend::MySql => (
+            Func::cust(Alias::new("JSON_TABLE"))
+                .arg(array)
+                .arg(Expr::cust(format!(
+                    "'$[*]' COLUMNS (value {sql_type} PATH '$')"
+                ))),
+            Expr::col((elem.clone(), Alias::new("value"))).into(),
+        ),
+        DbBackend::Sqlite => (
+            Func::cust(Alias::new("json_each")).arg(array),
+            Expr::col((elem.clone(), Alias::new("value"))).into(),
+        ),
+    };
+
+    let predicate = body
+        .map(|(var, pred)| {
+            let element = JsonElement {
+                var,
+                expr: member,
+                kind,
+            };
+            compile(pred, &element, ctx)
+        })
+        .transpose()?;
+
+    let mut sub = Query::select();
+    sub.expr(Expr::value(1))
+        .from_function(elements, elem)
+        .cond_where(deciding_members(op, predicate));
+    Ok(exists(op, sub))
+}
+
+/// SQL type JSON array elements of `kind` are read as.
+fn json_element_type(kind: FieldKind, backend: DbBackend) -> ODataBuildResult<&'static str> {
+    Ok(match (kind, backend) {
+        (FieldKind::String, DbBackend::MySql) => "VARCHAR(1024)",
+        (FieldKind::String, _) => "TEXT",
+        (FieldKind::I64, _) => "BIGINT",
+        (FieldKind::F64, DbBackend::Postgres) => "DOUBLE PRECISION",
+        (FieldKind::F64, _) => "DOUBLE",
+        (FieldKind::Bool, _) => "BOOLEAN",
+        _ => {
+            return Err(ODataBuildError::Other(
+                "JSON array elements must be strings, numbers or booleans",
+            ));
+        }
+    })
+}
+
 /// Apply an optional `OData` filter (via wrapper) to a plain `SeaORM` Select<E>.
 ///
 /// This extension does NOT parse the filter string — it only consumes a parsed AST
@@ -786,8 +1316,21 @@ fn clamp_limit(req: Option<u64>, cfg: LimitCfg) -> u64 {
 
 /// One-shot pagination combiner that handles filter → cursor predicate → order → overfetch/trim → build cursors.
 ///
+/// `$count=true` is answered when `odata_limits` allows it: the total comes
+/// from [`count_with_odata`] over the same select and is returned in
+/// `PageInfo::total_count`; otherwise it fails with
+/// `ODataError::CountNotAllowed`. `$apply` and `$search` are rejected with
+/// `ODataError::ApplyNotAllowed` and `ODataError::SearchNotAllowed`;
+/// endpoints that need them use `OPager::odata_limits`, which calls
+/// [`aggregate_with_odata`] with the same select and applies `$search`
+/// (optionally ordered by relevance).
+///
+/// A raw `select` carries no scope, so field masks are not checked here; call
+/// [`FieldMap::ensure_readable`] first when the scope masks fields.
+///
 /// # Errors
 /// Returns `ODataError` if filter application, cursor validation, or database query fails.
+#[allow(clippy::too_many_arguments)]
 pub async fn paginate_with_odata<E, D, F, C>(
     select: sea_orm::Select<E>,
     conn: &C,
@@ -795,167 +1338,525 @@ pub async fn paginate_with_odata<E, D, F, C>(
     fmap: &FieldMap<E>,
     tiebreaker: (&str, SortDir), // e.g. ("id", SortDir::Desc)
     limit_cfg: LimitCfg,         // e.g. { default: 25, max: 1000 }
+    odata_limits: &ODataLimits,  // e.g. ODataLimits::new().with_count()
     model_to_domain: F,
 ) -> Result<Page<D>, ODataError>
 where
     E: EntityTrait,
     E::Column: ColumnTrait + Copy,
+    E::Model: Sync,
     F: Fn(E::Model) -> D + Copy,
     C: DBRunner,
 {
-    let limit = clamp_limit(q.limit, limit_cfg);
-    let fetch = limit + 1;
-
-    // Effective order derivation based on new policy
-    let effective_order = if let Some(cur) = &q.cursor {
-        // Derive order from the cursor's signed tokens
-        modkit_odata::ODataOrderBy::from_signed_tokens(&cur.s)
-            .map_err(|_| ODataError::InvalidCursor)?
+    // Never answer `$count=true` with a page that lacks the total
+    odata_limits.validate_count(q.count)?;
+    // `$apply` changes the response shape; a page of models cannot answer it
+    if q.apply.is_some() {
+        return Err(ODataError::ApplyNotAllowed);
+    }
+    // `$search` needs the endpoint's search columns, which only `OPager` knows
+    if q.search.is_some() {
+        return Err(ODataError::SearchNotAllowed);
+    }
+
+    // Count over the same select before it is consumed by paging
+    let total_count = if q.count {
+        Some(count_with_odata(select.clone(), conn, q, fmap).await?)
     } else {
-        // Use client order; ensure tiebreaker
-        q.order
-            .clone()
-            .ensure_tiebreaker(tiebreaker.0, tiebreaker.1)
+        None
     };
 
-    // Validate cursor consistency (filter hash only) if cursor present
-    if let Some(cur) = &q.cursor
-        && let (Some(h), Some(cf)) = (q.filter_hash.as_deref(), cur.f.as_deref())
-        && h != cf
-    {
-        return Err(ODataError::FilterMismatch);
-    }
+    let paging = Paging {
+        fmap,
+        relevance: None,
+        tiebreaker,
+        limit_cfg,
+    };
+    let mut page = paging.fetch(select, conn, q, model_to_domain).await?;
+    page.page_info.total_count = total_count;
+    Ok(page)
+}
 
-    // Compose: filter → cursor predicate → order; apply limit+1 at the end
-    let mut s = select;
+/// Column alias of the relevance selected next to the model.
+const RELEVANCE_ALIAS: &str = "__relevance";
 
-    // Apply filter
-    if let Some(ast) = q.filter.as_deref() {
-        s = s.filter(
-            expr_to_condition::<E>(ast, fmap)
-                .map_err(|e| ODataError::InvalidFilter(e.to_string()))?,
-        );
+/// What a page is read with besides the query itself.
+pub struct Paging<'a, E: EntityTrait> {
+    pub fmap: &'a FieldMap<E>,
+    /// Relevance of the `$search`; enables the `relevance` sort key.
+    pub relevance: Option<SimpleExpr>,
+    pub tiebreaker: (&'a str, SortDir),
+    pub limit_cfg: LimitCfg,
+}
+
+/// A fetched model and, when ordering by relevance, its relevance.
+struct Ranked<M> {
+    model: M,
+    relevance: Option<f64>,
+}
+
+impl<M: FromQueryResult> FromQueryResult for Ranked<M> {
+    fn from_query_result(res: &QueryResult, pre: &str) -> Result<Self, DbErr> {
+        Ok(Self {
+            model: M::from_query_result(res, pre)?,
+            relevance: Some(res.try_get(pre, RELEVANCE_ALIAS)?),
+        })
     }
+}
 
-    // Check if we're paginating backward
-    let is_backward = q.cursor.as_ref().is_some_and(|c| c.d == "bwd");
+impl<E> Paging<'_, E>
+where
+    E: EntityTrait,
+    E::Column: ColumnTrait + Copy,
+{
+    /// Whether `field` is the relevance of the `$search`.
+    fn is_relevance(&self, field: &str) -> bool {
+        self.relevance.is_some() && field.eq_ignore_ascii_case(crate::odata::search::RELEVANCE)
+    }
 
-    // Apply cursor if present
-    if let Some(cursor) = &q.cursor {
-        s = s.filter(
-            build_cursor_predicate(cursor, &effective_order, fmap)
-                .map_err(|_| ODataError::InvalidCursor)?,
-        );
+    fn sort_keys(&self, order: &ODataOrderBy) -> Result<Vec<SortKey>, ODataError> {
+        order
+            .0
+            .iter()
+            .map(|key| match &self.relevance {
+                Some(rank) if self.is_relevance(&key.field) => {
+                    Ok((rank.clone(), FieldKind::F64, key.dir))
+                }
+                _ => {
+                    let field = resolve_field(self.fmap, &key.field)?;
+                    Ok((Expr::col(field.col).into(), field.kind, key.dir))
+                }
+            })
+            .collect()
     }
 
-    // Apply order (reverse it for backward pagination)
-    let query_order = if is_backward {
-        effective_order.clone().reverse_directions()
-    } else {
-        effective_order.clone()
-    };
-    s = s.apply_odata_order_page(&query_order, fmap)?;
+    pub(crate) async fn fetch<D, F, C>(
+        &self,
+        select: sea_orm::Select<E>,
+        conn: &C,
+        q: &ODataQuery,
+        model_to_domain: F,
+    ) -> Result<Page<D>, ODataError>
+    where
+        F: Fn(E::Model) -> D + Copy,
+        C: DBRunner,
+    {
+        let limit = clamp_limit(q.limit, self.limit_cfg);
+        let fetch = limit + 1;
+
+        // Effective order derivation based on new policy
+        let effective_order = if let Some(cur) = &q.cursor {
+            // Derive order from the cursor's signed tokens
+            modkit_odata::ODataOrderBy::from_signed_tokens(&cur.s)
+                .map_err(|_| ODataError::InvalidCursor)?
+        } else {
+            // Use client order; ensure tiebreaker
+            q.order
+                .clone()
+                .ensure_tiebreaker(self.tiebreaker.0, self.tiebreaker.1)
+        };
 
-    // Apply limit
-    s = s.limit(fetch);
+        // Validate cursor consistency (filter hash only) if cursor present
+        if let Some(cur) = &q.cursor
+            && let (Some(h), Some(cf)) = (q.filter_hash.as_deref(), cur.f.as_deref())
+            && h != cf
+        {
+            return Err(ODataError::FilterMismatch);
+        }
 
-    #[allow(clippy::disallowed_methods)]
-    let mut rows = match DBRunnerInternal::as_seaorm(conn) {
-        SeaOrmRunner::Conn(db) => s.all(db).await,
-        SeaOrmRunner::Tx(tx) => s.all(tx).await,
-    }
-    .map_err(|e| ODataError::Db(e.to_string()))?;
+        // Compose: filter → cursor predicate → order; apply limit+1 at the end
+        let mut s = select;
 
-    let has_more = (rows.len() as u64) > limit;
-
-    // For backward pagination with reversed ORDER BY:
-    // - DB returns items in opposite order
-    // - We fetch limit+1 to detect has_more
-    // - We need to: 1) trim, 2) reverse back to original order
-    if is_backward {
-        // Remove the extra item (furthest back in time, which is at the END after reversed query)
-        if has_more {
-            rows.pop();
-        }
-        // Reverse to restore original display order
-        rows.reverse();
-    } else if has_more {
-        // Forward pagination: just truncate the end
-        rows.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
-    }
-
-    // Build cursors
-    // After all the reversals, rows are in the display order (DESC)
-    // - rows.first() = newest item
-    // - rows.last() = oldest item
-    //
-    // For backward pagination:
-    //   - has_more means "more items backward" (older)
-    //   - next_cursor should always be present (we came from forward)
-    //   - prev_cursor based on has_more
-    // For forward pagination:
-    //   - has_more means "more items forward" (older in DESC)
-    //   - next_cursor based on has_more
-    //   - prev_cursor always present (unless at start)
-
-    let next_cursor = if is_backward {
-        // Going backward: always have items forward (unless this was the initial query)
-        // Build cursor from last item to go forward
-        build_cursor(&rows, &effective_order, fmap, tiebreaker, q, true, "fwd")?
-    } else if has_more {
-        // Going forward: only have more if has_more is true
-        build_cursor(&rows, &effective_order, fmap, tiebreaker, q, true, "fwd")?
-    } else {
-        None
-    };
+        // Apply filter
+        if let Some(ast) = q.filter.as_deref() {
+            s = s.filter(filter_condition(ast, self.fmap, conn)?);
+        }
+
+        // Check if we're paginating backward
+        let is_backward = q.cursor.as_ref().is_some_and(|c| c.d == "bwd");
+
+        // Apply cursor if present
+        let keys = self.sort_keys(&effective_order);
+        if let Some(cursor) = &q.cursor {
+            let keys = keys.as_deref().map_err(|_| ODataError::InvalidCursor)?;
+            s = s.filter(cursor_condition(cursor, keys).map_err(|_| ODataError::InvalidCursor)?);
+        }
+
+        // Apply order (reverse it for backward pagination)
+        for (expr, _, dir) in keys? {
+            let sea_order = match (dir, is_backward) {
+                (SortDir::Asc, false) | (SortDir::Desc, true) => Order::Asc,
+                (SortDir::Desc, false) | (SortDir::Asc, true) => Order::Desc,
+            };
+            s = s.order_by(expr, sea_order);
+        }
 
-    let prev_cursor = if is_backward {
-        // Going backward: only have more backward if has_more is true
-        if has_more {
-            build_cursor(&rows, &effective_order, fmap, tiebreaker, q, false, "bwd")?
+        // Apply limit
+        s = s.limit(fetch);
+
+        let ranked = effective_order
+            .0
+            .iter()
+            .any(|k| self.is_relevance(&k.field));
+        let rows = if let (Some(rank), true) = (&self.relevance, ranked) {
+            let s = s
+                .expr_as(rank.clone(), RELEVANCE_ALIAS)
+                .into_model::<Ranked<E::Model>>();
+            match DBRunnerInternal::as_seaorm_read(conn) {
+                SeaOrmRunner::Conn(db) => s.all(db).await,
+                SeaOrmRunner::Tx(tx) => s.all(tx).await,
+            }
         } else {
-            None
+            #[allow(clippy::disallowed_methods)]
+            let models = match DBRunnerInternal::as_seaorm_read(conn) {
+                SeaOrmRunner::Conn(db) => s.all(db).await,
+                SeaOrmRunner::Tx(tx) => s.all(tx).await,
+            };
+            models.map(|models| {
+                models
+                    .into_iter()
+                    .map(|model| Ranked {
+                        model,
+                        relevance: None,
+                    })
+                    .collect()
+            })
+        };
+        let mut rows = rows.map_err(|e| ODataError::Db(e.to_string()))?;
+
+        let has_more = (rows.len() as u64) > limit;
+
+        // For backward pagination with reversed ORDER BY:
+        // - DB returns items in opposite order
+        // - We fetch limit+1 to detect has_more
+        // - We need to: 1) trim, 2) reverse back to original order
+        if is_backward {
+            // Remove the extra item (furthest back in time, which is at the END after reversed query)
+            if has_more {
+                rows.pop();
+            }
+            // Reverse to restore original display order
+            rows.reverse();
+        } else if has_more {
+            // Forward pagination: just truncate the end
+            rows.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
         }
-    } else if q.cursor.is_some() {
-        // Going forward: have items backward only if this is NOT the initial query
-        // If q.cursor is None, we're at the start of the dataset
-        build_cursor(&rows, &effective_order, fmap, tiebreaker, q, false, "bwd")?
-    } else {
-        None
-    };
 
-    let items = rows.into_iter().map(model_to_domain).collect();
+        // Build cursors
+        // After all the reversals, rows are in the display order (DESC)
+        // - rows.first() = newest item
+        // - rows.last() = oldest item
+        //
+        // For backward pagination:
+        //   - has_more means "more items backward" (older)
+        //   - next_cursor should always be present (we came from forward)
+        //   - prev_cursor based on has_more
+        // For forward pagination:
+        //   - has_more means "more items forward" (older in DESC)
+        //   - next_cursor based on has_more
+        //   - prev_cursor always present (unless at start)
+        let cursor = |last: bool, direction: &str| {
+            self.build_cursor(&rows, &effective_order, q, last, direction)
+        };
 
-    Ok(Page {
-        items,
-        page_info: PageInfo {
-            next_cursor,
-            prev_cursor,
-            limit,
-        },
-    })
+        let next_cursor = if is_backward || has_more {
+            // Going backward: always have items forward (unless this was the initial query)
+            // Going forward: only have more if has_more is true
+            cursor(true, "fwd")?
+        } else {
+            None
+        };
+
+        let prev_cursor = if is_backward {
+            // Going backward: only have more backward if has_more is true
+            if has_more {
+                cursor(false, "bwd")?
+            } else {
+                None
+            }
+        } else if q.cursor.is_some() {
+            // Going forward: have items backward only if this is NOT the initial query
+            // If q.cursor is None, we're at the start of the dataset
+            cursor(false, "bwd")?
+        } else {
+            None
+        };
+
+        let items = rows
+            .into_iter()
+            .map(|row| model_to_domain(row.model))
+            .collect();
+
+        Ok(Page {
+            items,
+            page_info: PageInfo {
+                next_cursor,
+                prev_cursor,
+                limit,
+                total_count: None,
+            },
+        })
+    }
+
+    fn build_cursor(
+        &self,
+        rows: &[Ranked<E::Model>],
+        effective_order: &ODataOrderBy,
+        q: &ODataQuery,
+        last: bool,
+        direction: &str,
+    ) -> Result<Option<String>, ODataError> {
+        let Some(row) = (if last { rows.last() } else { rows.first() }) else {
+            return Ok(None);
+        };
+        let mut k = Vec::with_capacity(effective_order.0.len());
+        for key in &effective_order.0 {
+            let value = if self.is_relevance(&key.field) {
+                row.relevance.map(|r| r.to_string())
+            } else {
+                self.fmap.encode_model_key(&row.model, &key.field)
+            };
+            k.push(value.ok_or_else(|| ODataError::InvalidOrderByField(key.field.clone()))?);
+        }
+        let cursor = CursorV1 {
+            k,
+            o: self.tiebreaker.1,
+            s: effective_order.to_signed_tokens(),
+            f: q.filter_hash.clone(),
+            d: direction.to_owned(),
+        };
+        cursor
+            .encode()
+            .map(Some)
+            .map_err(|_| ODataError::InvalidCursor)
+    }
 }
 
-fn build_cursor<E: EntityTrait>(
-    rows: &[E::Model],
-    effective_order: &ODataOrderBy,
+/// Count the rows of `select` matching the `OData` filter of `q`.
+///
+/// Cursor, order and limit are ignored, so the result is the total across all
+/// pages. Pass the same (scoped) select as to [`paginate_with_odata`].
+///
+/// # Errors
+/// Returns `ODataError` if the filter cannot be applied or the query fails.
+pub async fn count_with_odata<E, C>(
+    select: sea_orm::Select<E>,
+    conn: &C,
+    q: &ODataQuery,
     fmap: &FieldMap<E>,
-    tiebreaker: (&str, SortDir),
+) -> Result<u64, ODataError>
+where
+    E: EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    E::Model: Sync,
+    C: DBRunner,
+{
+    use sea_orm::PaginatorTrait;
+
+    let mut s = select;
+    if let Some(ast) = q.filter.as_deref() {
+        s = s.filter(filter_condition(ast, fmap, conn)?);
+    }
+
+    #[allow(clippy::disallowed_methods)]
+    match DBRunnerInternal::as_seaorm_read(conn) {
+        SeaOrmRunner::Conn(db) => s.count(db).await,
+        SeaOrmRunner::Tx(tx) => s.count(tx).await,
+    }
+    .map_err(|e| ODataError::Db(e.to_string()))
+}
+
+/// Evaluate an `$apply` pipeline over `select`.
+///
+/// The `$filter` of `q` and the `filter()` step narrow the input rows, which
+/// are then grouped and aggregated in the database. Pass a scoped select so
+/// only rows visible to the caller are aggregated. Groups are ordered by the
+/// group fields and bounded like pages: `q.limit`, else `limits.default`, at
+/// most `limits.max`. More groups than that are refused rather than cut off,
+/// so a result is always the complete aggregation.
+///
+/// Grouping re-projects `select`, so callers with a masking scope must refuse
+/// masked group and aggregate fields with [`FieldMap::ensure_readable`]
+/// (`OPager::aggregate` does).
+///
+/// # Errors
+/// Returns `ODataError` if a field is unknown or cannot be aggregated with
+/// the requested method, a filter cannot be applied, or the query fails, and
+/// `ODataError::TooManyGroups` if there are more groups than the limit.
+pub async fn aggregate_with_odata<E, C>(
+    select: sea_orm::Select<E>,
+    conn: &C,
     q: &ODataQuery,
-    last: bool,
-    direction: &str,
-) -> Result<Option<String>, ODataError> {
-    if last { rows.last() } else { rows.first() }
-        .map(|m| {
-            build_cursor_for_model::<E>(
-                m,
-                effective_order,
-                fmap,
-                tiebreaker.1,
-                q.filter_hash.clone(),
-                direction,
-            )
-            .and_then(|c| c.encode().map_err(|_| ODataError::InvalidCursor))
+    apply: &Apply,
+    fmap: &FieldMap<E>,
+    limits: LimitCfg,
+) -> Result<Vec<AggregateRow>, ODataError>
+where
+    E: EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    C: DBRunner,
+{
+    let field = |name: &str| {
+        fmap.get(name)
+            .ok_or_else(|| ODataError::InvalidApply(format!("unknown field: {name}")))
+    };
+    let backend = DBRunnerInternal::as_seaorm(conn).backend();
+
+    let mut s = select;
+    for ast in [q.filter.as_deref(), apply.filter.as_deref()]
+        .into_iter()
+        .flatten()
+    {
+        s = s.filter(filter_condition(ast, fmap, conn)?);
+    }
+
+    // Scope and filters stay in WHERE; only the projection is replaced.
+    let mut s = s.select_only();
+    // Output columns with the kind they are decoded as.
+    let mut columns = Vec::with_capacity(apply.group_by.len() + apply.aggregates.len());
+    for name in &apply.group_by {
+        let f = field(name)?;
+        s = s
+            .column_as(f.col, name.as_str())
+            .group_by(f.col)
+            .order_by_asc(f.col);
+        columns.push((name.as_str(), f.kind));
+    }
+    for agg in &apply.aggregates {
+        let (expr, kind) = match &agg.field {
+            None => (Expr::cust("COUNT(*)"), FieldKind::I64),
+            Some(name) => {
+                let f = field(name)?;
+                let expr = aggregate_expr(agg.method, f.col, f.kind, backend).ok_or_else(|| {
+                    ODataError::InvalidApply(format!(
+                        "cannot compute {:?} of {} field '{name}'",
+                        agg.method, f.kind
+                    ))
+                })?;
+                (expr, aggregate_kind(agg.method, f.kind))
+            }
+        };
+        s = s.expr_as(expr, agg.alias.as_str());
+        columns.push((agg.alias.as_str(), kind));
+    }
+    // One more group than allowed tells a complete result from a cut one.
+    let limit = clamp_limit(q.limit, limits);
+    s = s.limit(limit.saturating_add(1));
+
+    // Decoded by kind: computed columns carry no declared type on SQLite,
+    // which `into_json` needs.
+    let stmt = QueryTrait::build(&s, backend);
+    let rows = match DBRunnerInternal::as_seaorm_read(conn) {
+        SeaOrmRunner::Conn(db) => db.query_all(stmt).await,
+        SeaOrmRunner::Tx(tx) => tx.query_all(stmt).await,
+    }
+    .map_err(|e| ODataError::Db(e.to_string()))?;
+    if u64::try_from(rows.len()).unwrap_or(u64::MAX) > limit {
+        return Err(ODataError::TooManyGroups(limit));
+    }
+
+    rows.iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|&(name, kind)| Ok((name.to_owned(), aggregate_value(row, name, kind)?)))
+                .collect::<Result<AggregateRow, DbErr>>()
         })
-        .transpose()
+        .collect::<Result<_, _>>()
+        .map_err(|e| ODataError::Db(e.to_string()))
+}
+
+/// Kind of `method` applied to a field of `kind`.
+fn aggregate_kind(method: AggregateMethod, kind: FieldKind) -> FieldKind {
+    match (method, kind) {
+        (AggregateMethod::Count, _) => FieldKind::I64,
+        (AggregateMethod::Average, FieldKind::I64) => FieldKind::F64,
+        _ => kind,
+    }
+}
+
+/// Read column `col` of an aggregate row as JSON; `NULL` (e.g. the sum of
+/// no rows) becomes `null`.
+fn aggregate_value(
+    row: &QueryResult,
+    col: &str,
+    kind: FieldKind,
+) -> Result<serde_json::Value, DbErr> {
+    use serde_json::Value;
+
+    Ok(match kind {
+        FieldKind::String => row.try_get::<Option<String>>("", col)?.map(Value::from),
+        FieldKind::I64 => row.try_get::<Option<i64>>("", col)?.map(Value::from),
+        FieldKind::F64 => row.try_get::<Option<f64>>("", col)?.map(Value::from),
+        FieldKind::Bool => row.try_get::<Option<bool>>("", col)?.map(Value::from),
+        FieldKind::Uuid => row
+            .try_get::<Option<uuid::Uuid>>("", col)?
+            .map(|v| Value::from(v.to_string())),
+        FieldKind::DateTimeUtc => row
+            .try_get::<Option<chrono::DateTime<Utc>>>("", col)?
+            .map(|v| Value::from(v.to_rfc3339())),
+        FieldKind::Date => row
+            .try_get::<Option<NaiveDate>>("", col)?
+            .map(|v| Value::from(v.to_string())),
+        FieldKind::Time => row
+            .try_get::<Option<NaiveTime>>("", col)?
+            .map(|v| Value::from(v.to_string())),
+        // As a string, like `Decimal` serializes, to keep the precision.
+        FieldKind::Decimal => row
+            .try_get::<Option<Decimal>>("", col)?
+            .map(|v| Value::from(v.to_string())),
+    }
+    .unwrap_or(Value::Null))
+}
+
+/// SQL for `method(col)`, or `None` if `kind` does not support `method`.
+///
+/// Integer sums and averages are cast so every backend returns plain JSON
+/// numbers instead of `NUMERIC`.
+fn aggregate_expr<Col>(
+    method: AggregateMethod,
+    col: Col,
+    kind: FieldKind,
+    backend: DbBackend,
+) -> Option<SimpleExpr>
+where
+    Col: ColumnTrait,
+{
+    let numeric = matches!(kind, FieldKind::I64 | FieldKind::F64 | FieldKind::Decimal);
+    let ordered = !matches!(kind, FieldKind::Bool | FieldKind::Uuid);
+
+    Some(match (method, backend) {
+        (AggregateMethod::Count, _) => Expr::col(col).count(),
+        (AggregateMethod::Min, _) if ordered => Expr::col(col).min(),
+        (AggregateMethod::Max, _) if ordered => Expr::col(col).max(),
+        (AggregateMethod::Sum, DbBackend::Postgres) if kind == FieldKind::I64 => custom_expr(
+            backend,
+            "CAST(SUM($1) AS BIGINT)",
+            vec![Expr::col(col).into()],
+        ),
+        (AggregateMethod::Sum, DbBackend::MySql) if kind == FieldKind::I64 => custom_expr(
+            backend,
+            "CAST(SUM($1) AS SIGNED)",
+            vec![Expr::col(col).into()],
+        ),
+        (AggregateMethod::Sum, _) if numeric => Expr::col(col).sum(),
+        (AggregateMethod::Average, DbBackend::Postgres)
+            if kind != FieldKind::Decimal && numeric =>
+        {
+            custom_expr(
+                backend,
+                "CAST(AVG($1) AS DOUBLE PRECISION)",
+                vec![Expr::col(col).into()],
+            )
+        }
+        (AggregateMethod::Average, DbBackend::MySql) if kind != FieldKind::Decimal && numeric => {
+            custom_expr(
+                backend,
+                "CAST(AVG($1) AS DOUBLE)",
+                vec![Expr::col(col).into()],
+            )
+        }
+        (AggregateMethod::Average, _) if numeric => Func::avg(Expr::col(col)).into(),
+        _ => return None,
+    })
 }
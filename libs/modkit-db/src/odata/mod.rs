@@ -18,6 +18,7 @@
 //! - `core`: Core `OData` to `SeaORM` translation (filters, cursors, ordering) - legacy `FieldMap` based
 //! - `sea_orm_filter`: Type-safe mapping from `FilterNode<F>` to `SeaORM` conditions
 //! - `pager`: Fluent builder for secure + `OData` pagination
+//! - `search`: `$search` on the backend's native full-text index
 
 // Core OData functionality (legacy FieldMap-based)
 mod core;
@@ -28,11 +29,14 @@ pub mod sea_orm_filter;
 // Fluent pagination builder
 pub mod pager;
 
+// Full-text $search
+pub mod search;
+
 // Re-export all public items from core (legacy API)
 pub use core::*;
 
 // Re-export SeaORM filter mapping and pagination
 pub use sea_orm_filter::{
     FieldToColumn, LimitCfg, ODataFieldMapping, encode_cursor_value, filter_node_to_condition,
-    paginate_odata, parse_cursor_value,
+    filter_node_to_condition_for, paginate_odata, parse_cursor_value,
 };
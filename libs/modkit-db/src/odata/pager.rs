@@ -96,19 +96,40 @@
 //! - Security scope is applied before any filters
 //! - Empty scopes result in deny-all (no data returned)
 //! - All queries are scoped by the `SecurityContext` provided
+//! - Fields masked by the scope are only returned masked; queries that would
+//!   filter, sort, search, group or aggregate by them are refused with
+//!   `ODataError::FieldNotReadable`
 //!
 //! # Performance
 //!
 //! - Uses cursor-based pagination for efficient large dataset traversal
 //! - Fetches limit+1 rows to detect "has more" without separate COUNT query
+//! - `$count=true` runs an extra scoped COUNT only on endpoints that enable it
+//!   via [`OPager::odata_limits`]
+//! - `$apply` is evaluated with `GROUP BY` in the database by
+//!   [`OPager::aggregate`], again only where enabled
 //! - Applies filters at the database level (not in application memory)
+//! - `any`/`all` lambdas over related entities compile to correlated `EXISTS`
+//!   subqueries that are scoped like the main query
+//! - `$search` uses the backend's full-text index (see [`crate::odata::search`])
+//!   and is enabled per endpoint like `$count`
 //! - Supports indexed columns via field mappings for optimal query performance
 
-use crate::odata::{FieldMap, LimitCfg, paginate_with_odata};
-use crate::secure::{DBRunner, ScopableEntity, SecureEntityExt};
-use modkit_odata::{Error as ODataError, ODataQuery, Page, SortDir};
+use crate::odata::search::{RELEVANCE, search_condition, search_rank};
+use crate::odata::{
+    FieldMap, LimitCfg, ODataBuildError, Paging, aggregate_with_odata, count_with_odata,
+    expr_to_scoped_condition,
+};
+use crate::secure::{
+    DBRunner, DBRunnerInternal, ScopableEntity, SecureEntityExt, ensure_maskable, masks_column,
+};
+use modkit_odata::apply::{AggregateRow, Apply};
+use modkit_odata::ast::Expr;
+use modkit_odata::{
+    Error as ODataError, ODataLimits, ODataOrderBy, ODataQuery, OrderKey, Page, SortDir,
+};
 use modkit_security::AccessScope;
-use sea_orm::{ColumnTrait, EntityTrait};
+use sea_orm::{ColumnTrait, EntityTrait, IdenStatic, QueryFilter, Select};
 
 /// Minimal fluent builder for Secure + `OData` pagination.
 ///
@@ -136,6 +157,8 @@ use sea_orm::{ColumnTrait, EntityTrait};
 ///
 /// - Tiebreaker: `("id", SortDir::Desc)` - ensures stable pagination
 /// - Limits: `{ default: 25, max: 1000 }` - reasonable defaults for most APIs
+/// - `OData` limits: [`ODataLimits::default`], so `$count=true`, `$apply`
+///   and `$search` are rejected
 #[must_use]
 pub struct OPager<'a, E, C>
 where
@@ -148,6 +171,7 @@ where
     fmap: &'a FieldMap<E>,
     tiebreaker: (&'a str, SortDir),
     limits: LimitCfg,
+    odata_limits: ODataLimits,
 }
 
 impl<'a, E, C> OPager<'a, E, C>
@@ -184,6 +208,7 @@ where
                 default: 25,
                 max: 1000,
             },
+            odata_limits: ODataLimits::default(),
         }
     }
 
@@ -228,6 +253,24 @@ where
         self
     }
 
+    /// Validate queries against `limits` (default: [`ODataLimits::default`]).
+    ///
+    /// Use `ODataLimits::new().with_count()` to honor `$count=true` on this
+    /// endpoint; the total is returned in `PageInfo::total_count`. Use
+    /// `with_apply([...])` to accept `$apply` grouped by the listed fields,
+    /// and `with_search(n)` to accept `$search` on entities that declare
+    /// search columns.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// pager.odata_limits(ODataLimits::new().with_count().with_apply(["type"]))
+    /// ```
+    pub fn odata_limits(mut self, limits: ODataLimits) -> Self {
+        self.odata_limits = limits;
+        self
+    }
+
     /// Execute paging and map models to domain DTOs.
     ///
     /// This is the terminal operation that:
@@ -237,6 +280,10 @@ where
     /// 4. Fetches limit+1 rows (to detect "has more")
     /// 5. Maps entity models to domain DTOs
     /// 6. Returns a `Page<D>` with items and pagination metadata
+    /// 7. For `$count=true`, counts all rows matching scope and filter
+    ///
+    /// With `$search`, only matching rows are returned and `relevance` becomes
+    /// a sort field; without `$orderby` the page is ordered by `relevance desc`.
     ///
     /// # Type Parameters
     ///
@@ -255,6 +302,10 @@ where
     /// - `OData` filter is invalid
     /// - Database query fails
     /// - Cursor is malformed or inconsistent
+    /// - `$count`, `$apply` or `$search` is requested but not enabled via
+    ///   [`OPager::odata_limits`]
+    /// - The query filters, sorts or searches by a field masked in the scope,
+    ///   or selects a field hidden on every access path
     ///
     /// # Example
     ///
@@ -270,21 +321,161 @@ where
     pub async fn fetch<D, F>(self, q: &ODataQuery, map: F) -> Result<Page<D>, ODataError>
     where
         E: ScopableEntity,
+        E::Model: Sync,
         F: Fn(E::Model) -> D + Copy,
     {
+        self.odata_limits.validate_count(q.count)?;
+        self.odata_limits.validate_search(q.search.as_ref())?;
+        self.ensure_readable(q, Some(self.tiebreaker.0))?;
+        // An enabled `$apply` must be answered by `aggregate`, never ignored
+        if q.apply.is_some() {
+            self.odata_limits.validate_apply(q.apply.as_ref())?;
+            return Err(ODataError::InvalidApply(
+                "$apply returns aggregates, not pages".to_owned(),
+            ));
+        }
+
         // Apply security scope first - this enforces tenant isolation
+        ensure_maskable::<E>(self.scope).map_err(|e| ODataError::Db(e.to_string()))?;
         let select = E::find().secure().scope_with(self.scope).inner;
+        let (select, mut q) = self.scoped_filter(select, q, None)?;
+        let relevance = match &q.search {
+            Some(search) => Some(
+                search_rank::<E>(search, self.backend())
+                    .map_err(|e| ODataError::InvalidSearch(e.to_string()))?,
+            ),
+            None => None,
+        };
+        // Best matches first unless the client orders otherwise
+        if q.search.is_some() && q.order.is_empty() && q.cursor.is_none() {
+            q.order = ODataOrderBy(vec![OrderKey {
+                field: RELEVANCE.to_owned(),
+                dir: SortDir::Desc,
+            }]);
+        }
+        let q = &q;
+
+        // Count over the same scoped select before it is consumed by paging
+        let total_count = if q.count {
+            Some(count_with_odata(select.clone(), self.conn, q, self.fmap).await?)
+        } else {
+            None
+        };
 
         // Now apply OData filters, cursor, order, and limits
-        paginate_with_odata::<E, D, _, _>(
-            select,
-            self.conn,
-            q,
-            self.fmap,
-            self.tiebreaker,
-            self.limits,
-            map,
-        )
-        .await
+        let paging = Paging {
+            fmap: self.fmap,
+            relevance,
+            tiebreaker: self.tiebreaker,
+            limit_cfg: self.limits,
+        };
+        let mut page = paging.fetch(select, self.conn, q, map).await?;
+        page.page_info.total_count = total_count;
+        Ok(page)
+    }
+
+    /// Evaluate the `$apply` of `q` and return one row per group.
+    ///
+    /// The security scope is applied before filtering and grouping, so
+    /// aggregates only cover rows the caller may read. The `$filter` and
+    /// `$search` of `q` narrow the input as well; groups are capped by the
+    /// pager limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ODataError` if:
+    /// - `$apply` is missing, malformed for this field map, or not enabled
+    ///   via [`OPager::odata_limits`]
+    /// - `$search` is present but not enabled
+    /// - A group field is not listed as groupable
+    /// - The query filters, groups, aggregates or searches by a field masked
+    ///   in the scope
+    /// - There are more groups than the limit (`TooManyGroups`)
+    /// - The database query fails
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // $apply=groupby((type),aggregate($count as groups))
+    /// let rows = OPager::<group::Entity, _>::new(&scope, &conn, &FMAP)
+    ///     .odata_limits(ODataLimits::new().with_apply(["type"]))
+    ///     .aggregate(&query)
+    ///     .await?;
+    /// ```
+    pub async fn aggregate(self, q: &ODataQuery) -> Result<Vec<AggregateRow>, ODataError>
+    where
+        E: ScopableEntity,
+    {
+        let Some(apply) = q.apply.as_ref() else {
+            return Err(ODataError::InvalidApply("$apply is required".to_owned()));
+        };
+        self.odata_limits.validate_apply(Some(apply))?;
+        self.odata_limits.validate_search(q.search.as_ref())?;
+        self.ensure_readable(q, None)?;
+
+        ensure_maskable::<E>(self.scope).map_err(|e| ODataError::Db(e.to_string()))?;
+        let select = E::find().secure().scope_with(self.scope).inner;
+        let (select, q) = self.scoped_filter(select, q, apply.filter.as_deref())?;
+        let apply = Apply {
+            filter: None,
+            ..apply.clone()
+        };
+        aggregate_with_odata(select, self.conn, &q, &apply, self.fmap, self.limits).await
+    }
+
+    /// Apply the `$filter` of `q` (and `extra`) in the caller's scope, so
+    /// `any`/`all` lambdas over related entities only see rows it may read,
+    /// followed by the `$search` condition.
+    ///
+    /// Returns `q` without its filter; the filter hash is kept for cursor
+    /// validation.
+    fn scoped_filter(
+        &self,
+        select: Select<E>,
+        q: &ODataQuery,
+        extra: Option<&Expr>,
+    ) -> Result<(Select<E>, ODataQuery), ODataError>
+    where
+        E: ScopableEntity,
+    {
+        let backend = self.backend();
+        let mut select = select;
+        for ast in [q.filter.as_deref(), extra].into_iter().flatten() {
+            let cond = expr_to_scoped_condition(ast, self.fmap, backend, self.scope)
+                .map_err(ODataBuildError::into_filter_error)?;
+            select = select.filter(cond);
+        }
+        if let Some(search) = &q.search {
+            let cond = search_condition::<E>(search, backend)
+                .map_err(|e| ODataError::InvalidSearch(e.to_string()))?;
+            select = select.filter(cond);
+        }
+        let q = ODataQuery {
+            filter: None,
+            ..q.clone()
+        };
+        Ok((select, q))
+    }
+
+    /// Refuse queries that read fields masked in the caller's scope around
+    /// the masked projection (see [`FieldMap::ensure_readable`]), including
+    /// `$search` over a masked search column.
+    fn ensure_readable(&self, q: &ODataQuery, tiebreaker: Option<&str>) -> Result<(), ODataError>
+    where
+        E: ScopableEntity,
+    {
+        self.fmap.ensure_readable(self.scope, q, tiebreaker)?;
+        if q.search.is_some()
+            && let Some(col) = E::search_cols()
+                .into_iter()
+                .find(|c| masks_column::<E>(self.scope, *c))
+        {
+            return Err(ODataError::FieldNotReadable(col.as_str().to_owned()));
+        }
+        Ok(())
+    }
+
+    fn backend(&self) -> sea_orm::DbBackend {
+        DBRunnerInternal::as_seaorm(self.conn).backend()
     }
 }
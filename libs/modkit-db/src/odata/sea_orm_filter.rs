@@ -4,16 +4,21 @@
 //! into `SeaORM` conditions. Concrete modules only need to provide a mapping from
 //! their DTO field enum to `SeaORM` Column types via the `FieldToColumn` trait.
 
-use crate::secure::{Scoped, SecureSelect};
+use crate::secure::{
+    AccessScope, ScopableEntity, Scoped, SecureSelect, column_named, ensure_maskable,
+    hidden_columns, masks_column,
+};
 use bigdecimal::ToPrimitive;
 use chrono::SecondsFormat;
 use modkit_odata::filter::{
-    FieldKind, FilterField, FilterNode, FilterOp, ODataValue, convert_expr_to_filter_node,
+    FieldFn, FieldKind, FilterField, FilterNode, FilterOp, ODataValue, convert_expr_to_filter_node,
+};
+use modkit_odata::{
+    CursorV1, Error as ODataError, ODataLimits, ODataOrderBy, Page, PageInfo, SortDir,
 };
-use modkit_odata::{CursorV1, Error as ODataError, ODataOrderBy, Page, PageInfo, SortDir};
 use sea_orm::{
-    Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
-    sea_query::{Expr, Order},
+    Condition, DbBackend, EntityTrait, IdenStatic, QueryFilter, QueryOrder, QuerySelect,
+    sea_query::{Alias, Expr, Func, Order, SimpleExpr},
 };
 
 use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
@@ -120,6 +125,10 @@ pub trait ODataFieldMapping<F: FilterField>: FieldToColumn<F> {
 /// all standard `OData` operations. Concrete modules only need to implement
 /// `FieldToColumn` to map their DTO fields to database columns.
 ///
+/// Only functions with portable SQL (`tolower`, `toupper`, `trim`) are
+/// accepted here; use [`filter_node_to_condition_for`] when the filter may
+/// contain `length`, `indexof`, `year`/`month`/`day`/`hour` or `date`.
+///
 /// # Type Parameters
 ///
 /// - `F`: The `FilterField` implementation (generated by `#[derive(ODataFilterable)]`)
@@ -144,6 +153,33 @@ pub trait ODataFieldMapping<F: FilterField>: FieldToColumn<F> {
 /// # Errors
 /// Returns an error string if the filter contains unsupported operations or invalid values.
 pub fn filter_node_to_condition<F, M>(filter: &FilterNode<F>) -> Result<Condition, String>
+where
+    F: FilterField,
+    M: FieldToColumn<F>,
+{
+    node_to_condition::<F, M>(filter, None)
+}
+
+/// Like [`filter_node_to_condition`], but renders every supported `OData`
+/// function in the SQL dialect of `backend`.
+///
+/// # Errors
+/// Returns an error string if the filter contains unsupported operations or invalid values.
+pub fn filter_node_to_condition_for<F, M>(
+    filter: &FilterNode<F>,
+    backend: DbBackend,
+) -> Result<Condition, String>
+where
+    F: FilterField,
+    M: FieldToColumn<F>,
+{
+    node_to_condition::<F, M>(filter, Some(backend))
+}
+
+fn node_to_condition<F, M>(
+    filter: &FilterNode<F>,
+    backend: Option<DbBackend>,
+) -> Result<Condition, String>
 where
     F: FilterField,
     M: FieldToColumn<F>,
@@ -152,7 +188,17 @@ where
         FilterNode::Binary { field, op, value } => {
             // Map DTO field to database column
             let column = M::map_field(*field);
-            build_binary_condition(column, *op, value)
+            build_binary_condition(Expr::col(column).into(), *op, value)
+        }
+        FilterNode::Function {
+            func,
+            field,
+            op,
+            value,
+        } => {
+            let column = M::map_field(*field);
+            let lhs = field_fn_expr(func, Expr::col(column).into(), backend)?;
+            build_binary_condition(lhs, *op, value)
         }
         FilterNode::InList { field, values } => {
             if values.is_empty() {
@@ -175,60 +221,152 @@ where
             };
 
             children.iter().try_fold(base, |acc, child| {
-                let child_cond = filter_node_to_condition::<F, M>(child)?;
+                let child_cond = node_to_condition::<F, M>(child, backend)?;
                 Ok(acc.add(child_cond))
             })
         }
         FilterNode::Not(inner) => {
             // FIXED: Call .not() AFTER adding the inner condition
-            let inner_cond = filter_node_to_condition::<F, M>(inner)?;
+            let inner_cond = node_to_condition::<F, M>(inner, backend)?;
             Ok(Condition::all().add(inner_cond).not())
         }
     }
 }
 
-/// Build a binary condition (field op value) for `SeaORM`.
+/// Render `func(arg)` as SQL.
+///
+/// Case and whitespace functions are portable; the others differ between
+/// Postgres, `MySQL` and `SQLite` and need the backend. Date parts are
+/// returned as integers so they compare with numeric literals everywhere.
+pub(crate) fn field_fn_expr(
+    func: &FieldFn,
+    arg: SimpleExpr,
+    backend: Option<DbBackend>,
+) -> Result<SimpleExpr, String> {
+    let Some(backend) = backend else {
+        // Same SQL on every backend.
+        return match func {
+            FieldFn::ToLower => Ok(Func::lower(arg).into()),
+            FieldFn::ToUpper => Ok(Func::upper(arg).into()),
+            FieldFn::Trim => Ok(Func::cust(Alias::new("TRIM")).arg(arg).into()),
+            _ => Err(format!(
+                "Function '{func}' needs the database backend; use filter_node_to_condition_for"
+            )),
+        };
+    };
+    let sql = fn_sql(func, backend);
+    match func {
+        // OData positions are zero-based and -1 when absent; SQL is one-based and 0.
+        FieldFn::IndexOf(sub) => Ok(custom_expr(
+            backend,
+            sql,
+            vec![arg, Expr::val(sub.clone()).into()],
+        )),
+        _ => Ok(custom_expr(backend, sql, vec![arg])),
+    }
+}
+
+/// Custom SQL with `$1`, `$2`, ... standing for `args`, rendered for `backend`.
 ///
-/// This handles all comparison and string function operations.
-fn build_binary_condition<C>(
-    column: C,
+/// `sea-query` only numbers placeholders on Postgres; `MySQL` and `SQLite`
+/// take one `?` per argument in order of appearance, so the template is
+/// rewritten for them.
+pub(crate) fn custom_expr(backend: DbBackend, template: &str, args: Vec<SimpleExpr>) -> SimpleExpr {
+    if backend == DbBackend::Postgres {
+        return Expr::cust_with_exprs(template, args);
+    }
+    let mut sql = String::with_capacity(template.len());
+    let mut ordered = Vec::with_capacity(args.len());
+    let mut rest = template;
+    while let Some(pos) = rest.find('$') {
+        sql.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        let digits = after.len() - after.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        match after[..digits]
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| args.get(n.checked_sub(1)?))
+        {
+            Some(arg) => {
+                sql.push('?');
+                ordered.push(arg.clone());
+            }
+            None => sql.push_str(&rest[pos..=pos + digits]),
+        }
+        rest = &after[digits..];
+    }
+    sql.push_str(rest);
+    Expr::cust_with_exprs(sql, ordered)
+}
+
+/// SQL template of a function; `$1` is the field, `$2` the `indexof` needle.
+fn fn_sql(func: &FieldFn, backend: DbBackend) -> &'static str {
+    match (func, backend) {
+        (FieldFn::ToLower, _) => "LOWER($1)",
+        (FieldFn::ToUpper, _) => "UPPER($1)",
+        (FieldFn::Trim, _) => "TRIM($1)",
+        (FieldFn::Length, DbBackend::Postgres | DbBackend::MySql) => "CHAR_LENGTH($1)",
+        (FieldFn::Length, DbBackend::Sqlite) => "LENGTH($1)",
+        (FieldFn::IndexOf(_), DbBackend::Postgres) => "(STRPOS($1, $2) - 1)",
+        (FieldFn::IndexOf(_), DbBackend::MySql | DbBackend::Sqlite) => "(INSTR($1, $2) - 1)",
+        (FieldFn::Year, DbBackend::Postgres) => "CAST(EXTRACT(YEAR FROM $1) AS INTEGER)",
+        (FieldFn::Month, DbBackend::Postgres) => "CAST(EXTRACT(MONTH FROM $1) AS INTEGER)",
+        (FieldFn::Day, DbBackend::Postgres) => "CAST(EXTRACT(DAY FROM $1) AS INTEGER)",
+        (FieldFn::Hour, DbBackend::Postgres) => "CAST(EXTRACT(HOUR FROM $1) AS INTEGER)",
+        (FieldFn::Year, DbBackend::MySql) => "EXTRACT(YEAR FROM $1)",
+        (FieldFn::Month, DbBackend::MySql) => "EXTRACT(MONTH FROM $1)",
+        (FieldFn::Day, DbBackend::MySql) => "EXTRACT(DAY FROM $1)",
+        (FieldFn::Hour, DbBackend::MySql) => "EXTRACT(HOUR FROM $1)",
+        (FieldFn::Year, DbBackend::Sqlite) => "CAST(strftime('%Y', $1) AS INTEGER)",
+        (FieldFn::Month, DbBackend::Sqlite) => "CAST(strftime('%m', $1) AS INTEGER)",
+        (FieldFn::Day, DbBackend::Sqlite) => "CAST(strftime('%d', $1) AS INTEGER)",
+        (FieldFn::Hour, DbBackend::Sqlite) => "CAST(strftime('%H', $1) AS INTEGER)",
+        (FieldFn::Date, DbBackend::Postgres) => "CAST($1 AS DATE)",
+        (FieldFn::Date, DbBackend::MySql) => "DATE($1)",
+        (FieldFn::Date, DbBackend::Sqlite) => "date($1)",
+    }
+}
+
+/// Build a binary condition (lhs op value) for `SeaORM`.
+///
+/// `lhs` is a column or a function of one. This handles all comparison and
+/// string function operations.
+fn build_binary_condition(
+    lhs: SimpleExpr,
     op: FilterOp,
     value: &ODataValue,
-) -> Result<Condition, String>
-where
-    C: sea_orm::Iden + sea_orm::ColumnTrait + sea_orm::IntoSimpleExpr + Clone + 'static,
-{
-    // Convert ODataValue to sea_orm::Value
-    let sea_value = odata_value_to_sea_value(value)?;
-
+) -> Result<Condition, String> {
     // Handle NULL specially
     if matches!(value, ODataValue::Null) {
         return Ok(match op {
-            FilterOp::Eq => Condition::all().add(Expr::col(column).is_null()),
-            FilterOp::Ne => Condition::all().add(Expr::col(column).is_not_null()),
+            FilterOp::Eq => Condition::all().add(Expr::expr(lhs).is_null()),
+            FilterOp::Ne => Condition::all().add(Expr::expr(lhs).is_not_null()),
             _ => return Err(format!("Unsupported operator for NULL: {op:?}")),
         });
     }
 
+    // Convert ODataValue to sea_orm::Value
+    let sea_value = odata_value_to_sea_value(value)?;
+
     // Build the expression based on the operator
     let expr = match op {
-        FilterOp::Eq => Expr::col(column).eq(sea_value),
-        FilterOp::Ne => Expr::col(column).ne(sea_value),
-        FilterOp::Gt => Expr::col(column).gt(sea_value),
-        FilterOp::Ge => Expr::col(column).gte(sea_value),
-        FilterOp::Lt => Expr::col(column).lt(sea_value),
-        FilterOp::Le => Expr::col(column).lte(sea_value),
+        FilterOp::Eq => Expr::expr(lhs).eq(sea_value),
+        FilterOp::Ne => Expr::expr(lhs).ne(sea_value),
+        FilterOp::Gt => Expr::expr(lhs).gt(sea_value),
+        FilterOp::Ge => Expr::expr(lhs).gte(sea_value),
+        FilterOp::Lt => Expr::expr(lhs).lt(sea_value),
+        FilterOp::Le => Expr::expr(lhs).lte(sea_value),
         FilterOp::Contains => {
             let s = extract_string(value)?;
-            Expr::col(column).like(format!("%{}%", escape_like(&s)))
+            Expr::expr(lhs).like(format!("%{}%", escape_like(&s)))
         }
         FilterOp::StartsWith => {
             let s = extract_string(value)?;
-            Expr::col(column).like(format!("{}%", escape_like(&s)))
+            Expr::expr(lhs).like(format!("{}%", escape_like(&s)))
         }
         FilterOp::EndsWith => {
             let s = extract_string(value)?;
-            Expr::col(column).like(format!("%{}", escape_like(&s)))
+            Expr::expr(lhs).like(format!("%{}", escape_like(&s)))
         }
         FilterOp::In | FilterOp::And | FilterOp::Or => {
             return Err(format!("Operator {op:?} not valid in binary context"));
@@ -442,6 +580,58 @@ fn clamp_limit(req: Option<u64>, cfg: LimitCfg) -> u64 {
     l
 }
 
+/// Refuse filtering or sorting by a field masked in `scope`, and a `$select`
+/// of a field hidden on every access path (see `FieldMap::ensure_readable`).
+fn ensure_readable<F, M>(
+    scope: &AccessScope,
+    query: &modkit_odata::ODataQuery,
+    filter: Option<&FilterNode<F>>,
+    order: &ODataOrderBy,
+) -> Result<(), ODataError>
+where
+    F: FilterField,
+    M: ODataFieldMapping<F>,
+    M::Entity: ScopableEntity,
+{
+    let mut fields = Vec::new();
+    if let Some(node) = filter {
+        filter_fields(node, &mut fields);
+    }
+    fields.extend(order.0.iter().filter_map(|k| F::from_name(&k.field)));
+    if let Some(field) = fields.into_iter().find(|f| {
+        column_named::<M::Entity>(M::map_field(*f).as_str())
+            .is_some_and(|col| masks_column::<M::Entity>(scope, col))
+    }) {
+        return Err(ODataError::FieldNotReadable(field.name().to_owned()));
+    }
+
+    // Refuse the filter names of hidden columns too
+    let columns = hidden_columns::<M::Entity>(scope);
+    let columns: Vec<&str> = columns.iter().map(IdenStatic::as_str).collect();
+    let mut hidden = scope.hidden_fields();
+    for field in F::FIELDS {
+        if columns.contains(&M::map_field(*field).as_str()) {
+            hidden.push(field.name());
+        }
+    }
+    modkit_odata::ensure_selectable(query.select.as_deref(), &hidden)
+}
+
+/// Collect the fields `node` compares.
+fn filter_fields<F: FilterField>(node: &FilterNode<F>, out: &mut Vec<F>) {
+    match node {
+        FilterNode::Binary { field, .. }
+        | FilterNode::Function { field, .. }
+        | FilterNode::InList { field, .. } => out.push(*field),
+        FilterNode::Composite { children, .. } => {
+            for child in children {
+                filter_fields(child, out);
+            }
+        }
+        FilterNode::Not(inner) => filter_fields(inner, out),
+    }
+}
+
 /// Type-safe `OData` pagination with filters, ordering, and cursors.
 ///
 /// This function provides complete cursor-based pagination using the type-safe
@@ -464,6 +654,7 @@ fn clamp_limit(req: Option<u64>, cfg: LimitCfg) -> u64 {
 /// - `query`: `OData` query with filter, order, cursor, and limit
 /// - `tiebreaker`: Default orderby field and direction for stable pagination
 /// - `limit_cfg`: Default and maximum page sizes
+/// - `odata_limits`: Optional query features the endpoint allows (`$count`)
 /// - `model_to_domain`: Function to convert entity models to domain types
 ///
 /// # Returns
@@ -479,10 +670,19 @@ fn clamp_limit(req: Option<u64>, cfg: LimitCfg) -> u64 {
 ///     &odata_query,
 ///     ("id", SortDir::Desc),
 ///     LimitCfg { default: 25, max: 1000 },
+///     &ODataLimits::new().with_count(),
 ///     |model| model.into(),
 /// ).await?;
 /// ```
 ///
+/// `$count=true` is answered when `odata_limits` allows it, with a `COUNT`
+/// over the scoped and filtered select returned in `PageInfo::total_count`;
+/// otherwise it fails with `ODataError::CountNotAllowed`. `$apply` and
+/// `$search` are rejected with `ODataError::ApplyNotAllowed` and
+/// `ODataError::SearchNotAllowed`; endpoints that need them use `OPager`
+/// with `odata_limits`. Filtering or sorting by a field masked in the scope of
+/// `select` fails with `ODataError::FieldNotReadable`.
+///
 /// # Errors
 /// Returns `ODataError` if filter application, cursor validation, or database query fails.
 pub async fn paginate_odata<F, M, E, D, Mapper, C>(
@@ -491,15 +691,28 @@ pub async fn paginate_odata<F, M, E, D, Mapper, C>(
     query: &modkit_odata::ODataQuery,
     tiebreaker: (&str, SortDir),
     limit_cfg: LimitCfg,
+    odata_limits: &ODataLimits,
     model_to_domain: Mapper,
 ) -> Result<Page<D>, ODataError>
 where
     F: FilterField,
     M: ODataFieldMapping<F, Entity = E>,
-    E: EntityTrait,
+    E: ScopableEntity,
+    E::Model: Sync,
     Mapper: Fn(E::Model) -> D,
     C: DBRunner,
 {
+    // Never answer `$count=true` with a page that lacks the total
+    odata_limits.validate_count(query.count)?;
+    // `$apply` changes the response shape; a page of models cannot answer it
+    if query.apply.is_some() {
+        return Err(ODataError::ApplyNotAllowed);
+    }
+    // `$search` needs the endpoint's search columns, which only `OPager` knows
+    if query.search.is_some() {
+        return Err(ODataError::SearchNotAllowed);
+    }
+
     let limit = clamp_limit(query.limit, limit_cfg);
     let fetch = limit + 1;
 
@@ -521,18 +734,47 @@ where
         return Err(ODataError::FilterMismatch);
     }
 
+    let filter_node = query
+        .filter
+        .as_deref()
+        .map(convert_expr_to_filter_node::<F>)
+        .transpose()
+        .map_err(|e| ODataError::InvalidFilter(e.to_string()))?;
+    ensure_readable::<F, M>(
+        select.scope(),
+        query,
+        filter_node.as_ref(),
+        &effective_order,
+    )?;
+
+    ensure_maskable::<E>(select.scope()).map_err(|e| ODataError::Db(e.to_string()))?;
     let mut s = select.inner;
 
     // Apply filter using type-safe FilterNode
-    if let Some(ast) = query.filter.as_deref() {
-        let filter_node = convert_expr_to_filter_node::<F>(ast)
-            .map_err(|e| ODataError::InvalidFilter(e.to_string()))?;
+    if let Some(filter_node) = filter_node {
+        let backend = DBRunnerInternal::as_seaorm(conn).backend();
 
         s = s.filter(
-            filter_node_to_condition::<F, M>(&filter_node).map_err(ODataError::InvalidFilter)?,
+            filter_node_to_condition_for::<F, M>(&filter_node, backend)
+                .map_err(ODataError::InvalidFilter)?,
         );
     }
 
+    // Count the scoped, filtered rows before cursor and limit narrow them
+    let total_count = if query.count {
+        use sea_orm::PaginatorTrait;
+
+        #[allow(clippy::disallowed_methods)]
+        let count = match DBRunnerInternal::as_seaorm_read(conn) {
+            SeaOrmRunner::Conn(db) => s.clone().count(db).await,
+            SeaOrmRunner::Tx(tx) => s.clone().count(tx).await,
+        }
+        .map_err(|e| ODataError::Db(e.to_string()))?;
+        Some(count)
+    } else {
+        None
+    };
+
     let is_backward = query.cursor.as_ref().is_some_and(|c| c.d == "bwd");
 
     // Apply cursor predicate
@@ -562,7 +804,7 @@ where
     s = s.limit(fetch);
 
     #[allow(clippy::disallowed_methods)]
-    let mut rows = match DBRunnerInternal::as_seaorm(conn) {
+    let mut rows = match DBRunnerInternal::as_seaorm_read(conn) {
         SeaOrmRunner::Conn(db) => s.all(db).await,
         SeaOrmRunner::Tx(tx) => s.all(tx).await,
     }
@@ -625,6 +867,7 @@ where
             next_cursor,
             prev_cursor,
             limit,
+            total_count,
         },
     })
 }
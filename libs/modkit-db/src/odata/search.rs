@@ -0,0 +1,306 @@
+//! `$search` on the database's native full-text engine.
+//!
+//! An entity opts in by listing its searchable text columns
+//! (`#[secure(search = "title, body")]`, see [`ScopableEntity::search_cols`])
+//! and creating the matching index in a migration with
+//! [`search_index_statements`]. Each backend uses its own engine:
+//!
+//! | Backend  | Index                                        | Relevance         |
+//! |----------|----------------------------------------------|-------------------|
+//! | Postgres | GIN over `to_tsvector('simple', ...)`        | `ts_rank`         |
+//! | `MySQL`  | `FULLTEXT` over the columns, boolean mode    | `MATCH ... AGAINST` |
+//! | `SQLite` | FTS5 table `<table>_fts` kept by triggers    | `-bm25`           |
+//!
+//! The search condition is added to the `WHERE` of the scoped select, next
+//! to the `$filter`, so it never widens what the caller may read. Higher
+//! relevance is better on every backend; `OPager` exposes it as the
+//! [`RELEVANCE`] sort field, which can also be a cursor key.
+
+use modkit_odata::search::{Search, SearchTerm};
+use sea_orm::sea_query::{Alias, Expr, SimpleExpr};
+use sea_orm::{ColumnTrait, DbBackend, EntityTrait, IdenStatic};
+
+pub use modkit_odata::search::RELEVANCE;
+
+use crate::odata::sea_orm_filter::custom_expr;
+use crate::odata::{ODataBuildError, ODataBuildResult};
+use crate::secure::ScopableEntity;
+
+/// Text search configuration of the Postgres document; `simple` does not
+/// stem, so it behaves the same for every language.
+const PG_CONFIG: &str = "simple";
+
+/// Condition that keeps the rows of `E` matching `search`.
+///
+/// # Errors
+/// Returns `ODataBuildError` if `E` declares no search columns.
+pub fn search_condition<E>(search: &Search, backend: DbBackend) -> ODataBuildResult<SimpleExpr>
+where
+    E: ScopableEntity,
+    E::Column: ColumnTrait + Copy,
+{
+    let cols = search_cols::<E>()?;
+    Ok(match backend {
+        DbBackend::Postgres => {
+            Expr::cust_with_exprs("$1 @@ ($2)", [pg_document::<E>(&cols), pg_query(search)])
+        }
+        DbBackend::MySql => mysql_match::<E>(&cols, search),
+        DbBackend::Sqlite => {
+            let fts = sqlite_fts_table::<E>();
+            custom_expr(
+                backend,
+                &format!("$1 IN (SELECT rowid FROM {fts} WHERE {fts} MATCH $2)"),
+                vec![sqlite_rowid::<E>(), Expr::val(fts5_query(search)).into()],
+            )
+        }
+    })
+}
+
+/// Relevance of a row of `E` for `search` as a double; higher is better.
+///
+/// Only meaningful for rows matching [`search_condition`].
+///
+/// # Errors
+/// Returns `ODataBuildError` if `E` declares no search columns.
+pub fn search_rank<E>(search: &Search, backend: DbBackend) -> ODataBuildResult<SimpleExpr>
+where
+    E: ScopableEntity,
+    E::Column: ColumnTrait + Copy,
+{
+    let cols = search_cols::<E>()?;
+    Ok(match backend {
+        DbBackend::Postgres => Expr::cust_with_exprs(
+            "CAST(ts_rank($1, $2) AS DOUBLE PRECISION)",
+            [pg_document::<E>(&cols), pg_query(search)],
+        ),
+        DbBackend::MySql => mysql_match::<E>(&cols, search),
+        DbBackend::Sqlite => {
+            let fts = sqlite_fts_table::<E>();
+            custom_expr(
+                backend,
+                &format!("(SELECT -bm25({fts}) FROM {fts} WHERE {fts} MATCH $2 AND rowid = $1)"),
+                vec![sqlite_rowid::<E>(), Expr::val(fts5_query(search)).into()],
+            )
+        }
+    })
+}
+
+/// DDL creating the full-text index `$search` on `E` relies on, for use in
+/// a migration after the table exists.
+///
+/// On `SQLite` this is an external-content FTS5 table with triggers that keep
+/// it in sync, filled from the existing rows. The statements must be re-run
+/// (after dropping the index) when the search columns change.
+///
+/// # Errors
+/// Returns `ODataBuildError` if `E` declares no search columns.
+pub fn search_index_statements<E>(backend: DbBackend) -> ODataBuildResult<Vec<String>>
+where
+    E: ScopableEntity,
+    E::Column: ColumnTrait + Copy,
+{
+    let columns = search_cols::<E>()?;
+    let cols: Vec<&str> = columns.iter().map(IdenStatic::as_str).collect();
+    let table = E::default().table_name().to_owned();
+
+    Ok(match backend {
+        DbBackend::Postgres => {
+            let document = cols
+                .iter()
+                .map(|c| format!("coalesce({}, '')", quote('"', c)))
+                .collect::<Vec<_>>()
+                .join(" || ' ' || ");
+            vec![format!(
+                "CREATE INDEX IF NOT EXISTS {} ON {} USING GIN (to_tsvector('{PG_CONFIG}', {document}))",
+                quote('"', &format!("{table}_search_idx")),
+                quote('"', &table),
+            )]
+        }
+        DbBackend::MySql => {
+            let cols = cols.iter().map(|c| quote('`', c)).collect::<Vec<_>>();
+            vec![format!(
+                "CREATE FULLTEXT INDEX {} ON {} ({})",
+                quote('`', &format!("{table}_search_idx")),
+                quote('`', &table),
+                cols.join(", "),
+            )]
+        }
+        DbBackend::Sqlite => {
+            let fts = sqlite_fts_table::<E>();
+            let name = |suffix: &str| quote('"', &format!("{table}_fts_{suffix}"));
+            let table = quote('"', &table);
+            let list = cols.iter().map(|c| quote('"', c)).collect::<Vec<_>>();
+            let fields = list.join(", ");
+            let of = |row: &str| {
+                list.iter()
+                    .map(|c| format!("{row}.{c}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            let (new, old) = (of("new"), of("old"));
+            let insert = format!("INSERT INTO {fts}(rowid, {fields}) VALUES (new.rowid, {new});");
+            let delete = format!(
+                "INSERT INTO {fts}({fts}, rowid, {fields}) VALUES ('delete', old.rowid, {old});"
+            );
+            vec![
+                format!(
+                    "CREATE VIRTUAL TABLE IF NOT EXISTS {fts} USING fts5({fields}, content={table}, content_rowid='rowid')"
+                ),
+                format!(
+                    "CREATE TRIGGER IF NOT EXISTS {} AFTER INSERT ON {table} BEGIN {insert} END",
+                    name("ai")
+                ),
+                format!(
+                    "CREATE TRIGGER IF NOT EXISTS {} AFTER DELETE ON {table} BEGIN {delete} END",
+                    name("ad")
+                ),
+                format!(
+                    "CREATE TRIGGER IF NOT EXISTS {} AFTER UPDATE ON {table} BEGIN {delete} {insert} END",
+                    name("au")
+                ),
+                format!("INSERT INTO {fts}({fts}) VALUES ('rebuild')"),
+            ]
+        }
+    })
+}
+
+fn search_cols<E>() -> ODataBuildResult<Vec<E::Column>>
+where
+    E: ScopableEntity,
+{
+    let cols = E::search_cols();
+    if cols.is_empty() {
+        return Err(ODataBuildError::Other(
+            "$search needs search columns on the entity",
+        ));
+    }
+    Ok(cols)
+}
+
+/// `to_tsvector` over the columns; must stay identical to the GIN index
+/// expression of [`search_index_statements`] for the index to be used.
+fn pg_document<E>(cols: &[E::Column]) -> SimpleExpr
+where
+    E: EntityTrait,
+    E::Column: ColumnTrait + Copy,
+{
+    let document = cols
+        .iter()
+        .map(|c| Expr::cust_with_expr("coalesce($1, '')", Expr::col((E::default(), *c))))
+        .reduce(|acc, next| Expr::cust_with_exprs("$1 || ' ' || $2", [acc, next]))
+        .unwrap_or_else(|| Expr::val("").into());
+    Expr::cust_with_expr(format!("to_tsvector('{PG_CONFIG}', $1)"), document)
+}
+
+/// `tsquery` requiring the positive terms and excluding the negated ones.
+fn pg_query(search: &Search) -> SimpleExpr {
+    let mut sql = Vec::with_capacity(search.terms.len());
+    let mut texts = Vec::with_capacity(search.terms.len());
+    for (i, term) in search.terms.iter().enumerate() {
+        let func = if term.phrase {
+            "phraseto_tsquery"
+        } else {
+            "plainto_tsquery"
+        };
+        let not = if term.negated { "!!" } else { "" };
+        sql.push(format!("{not}{func}('{PG_CONFIG}', ${})", i + 1));
+        texts.push(Expr::val(term.text.clone()).into());
+    }
+    Expr::cust_with_exprs(sql.join(" && "), texts)
+}
+
+/// `MATCH (cols) AGAINST (... IN BOOLEAN MODE)`: a condition and, read as
+/// a number, the relevance.
+fn mysql_match<E>(cols: &[E::Column], search: &Search) -> SimpleExpr
+where
+    E: EntityTrait,
+    E::Column: ColumnTrait + Copy,
+{
+    // Every term is a quoted phrase, so no other boolean-mode operator applies.
+    let against = search
+        .terms
+        .iter()
+        .map(|t| {
+            let sign = if t.negated { '-' } else { '+' };
+            format!("{sign}\"{}\"", t.text.replace('"', " "))
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let placeholders = (1..=cols.len())
+        .map(|i| format!("${i}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut args: Vec<SimpleExpr> = cols
+        .iter()
+        .map(|c| Expr::col((E::default(), *c)).into())
+        .collect();
+    args.push(Expr::val(against).into());
+    custom_expr(
+        DbBackend::MySql,
+        &format!(
+            "MATCH ({placeholders}) AGAINST (${} IN BOOLEAN MODE)",
+            cols.len() + 1
+        ),
+        args,
+    )
+}
+
+/// FTS5 query: quoted positive terms joined by `AND`, then `NOT` per negated
+/// term (FTS5 has no unary `NOT`).
+fn fts5_query(search: &Search) -> String {
+    let quoted = |t: &SearchTerm| format!("\"{}\"", t.text.replace('"', "\"\""));
+    let mut query = search
+        .positive()
+        .map(quoted)
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    for term in search.negative() {
+        query.push_str(" NOT ");
+        query.push_str(&quoted(term));
+    }
+    query
+}
+
+fn sqlite_fts_table<E: EntityTrait>() -> String {
+    quote('"', &format!("{}_fts", E::default().table_name()))
+}
+
+fn sqlite_rowid<E: EntityTrait>() -> SimpleExpr {
+    Expr::col((E::default(), Alias::new("rowid"))).into()
+}
+
+/// Quote an identifier, doubling embedded quote characters.
+fn quote(q: char, ident: &str) -> String {
+    format!("{q}{}{q}", ident.replace(q, &q.to_string().repeat(2)))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fts5_query_quotes_terms_and_appends_exclusions() {
+        let search = modkit_odata::parse_search(r#"-draft budget "q3 report""#).unwrap();
+        assert_eq!(
+            fts5_query(&search),
+            r#""budget" AND "q3 report" NOT "draft""#
+        );
+
+        let odd = Search {
+            terms: vec![SearchTerm {
+                text: "a\"b".to_owned(),
+                phrase: false,
+                negated: false,
+            }],
+        };
+        assert_eq!(fts5_query(&odd), r#""a""b""#);
+    }
+
+    #[test]
+    fn quote_doubles_embedded_quotes() {
+        assert_eq!(quote('"', "a\"b"), "\"a\"\"b\"");
+        assert_eq!(quote('`', "docs"), "`docs`");
+    }
+}
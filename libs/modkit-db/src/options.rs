@@ -92,6 +92,7 @@ impl DbConnectOptions {
                     engine: crate::DbEngine::Sqlite,
                     dsn: format!("sqlite://{filename}"),
                     sea,
+                    replicas: crate::replicas::ReadReplicas::default(),
                 };
 
                 Ok(handle)
@@ -113,6 +114,7 @@ impl DbConnectOptions {
                         opts.get_database().unwrap_or("")
                     ),
                     sea,
+                    replicas: crate::replicas::ReadReplicas::default(),
                 };
 
                 Ok(handle)
@@ -129,6 +131,7 @@ impl DbConnectOptions {
                     engine: crate::DbEngine::MySql,
                     dsn: "mysql://<redacted>@...".to_owned(),
                     sea,
+                    replicas: crate::replicas::ReadReplicas::default(),
                 };
 
                 Ok(handle)
@@ -286,18 +289,71 @@ pub(crate) async fn build_db_handle(
     };
 
     // Build pool configuration
-    let pool_cfg = cfg.pool.unwrap_or_default();
+    let pool_cfg = cfg.pool.clone().unwrap_or_default();
 
     // Log connection attempt (without credentials)
     let log_dsn = redact_credentials_in_dsn(cfg.dsn.as_deref());
     tracing::debug!(dsn = log_dsn, engine = ?engine, "Building database connection");
 
     // Connect to database
-    let handle = connect_options.connect(pool_cfg).await?;
+    let mut handle = connect_options.connect(pool_cfg.clone()).await?;
+
+    if let Some(replicas) = &cfg.replicas {
+        let pools = connect_replicas(&cfg, engine, replicas, &connect_options, &pool_cfg).await?;
+        handle.replicas = crate::replicas::ReadReplicas::new(pools);
+    }
 
     Ok(handle)
 }
 
+/// Open a pool per read replica of `cfg`.
+async fn connect_replicas(
+    cfg: &DbConnConfig,
+    engine: DbEngineCfg,
+    replicas: &[String],
+    primary: &DbConnectOptions,
+    pool_cfg: &PoolCfg,
+) -> Result<Vec<sea_orm::DatabaseConnection>> {
+    if replicas.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    match primary {
+        // A replica of an SQLite file is the file itself, opened read-only
+        #[cfg(feature = "sqlite")]
+        DbConnectOptions::Sqlite(opts) => {
+            if is_memory_filename(opts.get_filename()) {
+                tracing::debug!("In-memory SQLite has no replicas; reads use the primary");
+                return Ok(Vec::new());
+            }
+            let read_only = DbConnectOptions::Sqlite(opts.clone().read_only(true));
+            return Ok(vec![read_only.connect(pool_cfg.clone()).await?.sea]);
+        }
+        #[allow(unreachable_patterns)]
+        _ => {}
+    }
+
+    let mut pools = Vec::with_capacity(replicas.len());
+    for dsn in replicas {
+        let dsn = expand_env_vars(dsn)?;
+        let inferred = engine_from_dsn(&dsn)?;
+        if inferred != engine {
+            return Err(DbError::ConfigConflict(format!(
+                "replica engine '{inferred:?}' differs from primary engine '{engine:?}'"
+            )));
+        }
+        let replica = DbConnConfig {
+            engine: Some(engine),
+            dsn: Some(dsn),
+            params: cfg.params.clone(),
+            ..Default::default()
+        };
+        let options = build_server_options(&replica, engine)?;
+        pools.push(options.connect(pool_cfg.clone()).await?.sea);
+    }
+    Ok(pools)
+}
+
 fn determine_engine(cfg: &DbConnConfig) -> Result<DbEngineCfg> {
     // If both engine and DSN are provided, validate they don't conflict.
     // (We do the same check in validate_config_consistency, but keep this here to ensure
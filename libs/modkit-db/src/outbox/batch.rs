@@ -111,6 +111,7 @@ impl<'a> Batch<'a> {
 mod tests {
     use super::*;
     use crate::outbox::handler::OutboxMessage;
+    use crate::outbox::headers::MessageHeaders;
 
     fn make_msg(seq: i64) -> OutboxMessage {
         OutboxMessage {
@@ -118,6 +119,7 @@ mod tests {
             seq,
             payload: vec![],
             payload_type: "test".into(),
+            headers: MessageHeaders::default(),
             created_at: chrono::Utc::now(),
             attempts: 0,
         }
@@ -10,6 +10,7 @@ use super::handler::{
     LeasedHandler, PerMessageAdapter, TransactionalHandler, TransactionalMessageHandler,
 };
 use super::manager::{OutboxBuilder, QueueDeclaration};
+use super::repartition::PartitionFence;
 use super::stats::StatsRegistry;
 use super::strategy::{LeasedStrategy, TransactionalStrategy, generate_worker_id};
 use super::taskward::{
@@ -30,16 +31,18 @@ pub struct SpawnContext {
     pub partition_notify: Arc<Notify>,
     pub processor_sem: Arc<Semaphore>,
     pub start_notify: Arc<Notify>,
-    #[allow(dead_code)]
     pub outbox: Arc<Outbox>,
     /// Shared stats registry for processor workers. `None` when stats disabled.
     pub stats_registry: Option<Arc<std::sync::Mutex<StatsRegistry>>>,
     /// Processor worker tuning (batch size, pacing, retry backoff).
     pub tuning: WorkerTuning,
+    /// Repartition ordering fence, for current partitions of a queue with a
+    /// previous generation.
+    pub fence: Option<PartitionFence>,
 }
 
 /// Trait for creating processor workers. One impl per processing mode.
-pub trait ProcessorFactory: Send {
+pub trait ProcessorFactory: Send + Sync {
     fn spawn(&self, ctx: SpawnContext) -> (String, Pin<Box<dyn Future<Output = ()> + Send>>);
 }
 
@@ -48,7 +51,14 @@ fn build_processor_worker<S: super::strategy::ProcessingStrategy + 'static>(
     ctx: &SpawnContext,
     strategy: S,
 ) -> (String, Pin<Box<dyn Future<Output = ()> + Send>>) {
-    let processor = PartitionProcessor::new(strategy, ctx.pid, ctx.tuning.clone(), ctx.db.clone());
+    let processor = PartitionProcessor::new(
+        strategy,
+        ctx.pid,
+        ctx.tuning.clone(),
+        ctx.db.clone(),
+        ctx.outbox.partition_metrics(ctx.pid),
+    )
+    .with_fence(ctx.fence.clone());
     let name = format!("processor-{}", ctx.pid);
     let (poker_notify, _poker_handle) =
         super::taskward::poker(ctx.tuning.idle_interval, ctx.cancel.clone());
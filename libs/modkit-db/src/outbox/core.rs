@@ -1,14 +1,26 @@
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 
 use dashmap::DashMap;
 use sea_orm::{ConnectionTrait, DbBackend, FromQueryResult, Statement, TransactionTrait};
 use tokio::sync::{Notify, RwLock};
 
-use super::dialect::Dialect;
+use super::dead_letter::{DeadLetterFilter, DeadLetterScope};
+use super::dialect::{Dialect, NewMessage};
+use super::headers::MessageHeaders;
+use super::health::{PartitionHealth, QueueHealth};
+use super::inbox::{InboxMessage, Received};
 use super::manager::OutboxBuilder;
+use super::metrics::{OutboxMetrics, PartitionMetrics};
 use super::prioritizer::SharedPrioritizer;
-use super::types::{EnqueueMessage, OutboxConfig, OutboxError, OutboxMessageId};
+use super::repartition::{
+    Generations, PartitionFence, PartitionRow, PreviousGeneration, current_generation,
+    fence_sources, pending_partitions,
+};
+use super::stats::{CategorySnapshot, StatsRegistry};
+use super::types::{EnqueueMessage, OutboxConfig, OutboxError, OutboxMessageId, Partitions};
+use super::workers::listener::{INCOMING_CHANNEL, REPARTITION_CHANNEL};
 use crate::Db;
 use crate::secure::SeaOrmRunner;
 
@@ -18,6 +30,9 @@ type PartitionNotifyMap = Arc<HashMap<i64, Arc<Notify>>>;
 /// Maximum payload size in bytes (64 KiB).
 const MAX_PAYLOAD_SIZE: usize = 64 * 1024;
 
+/// Maximum delivery delay accepted by the delayed enqueue variants (365 days).
+const MAX_DELIVERY_DELAY: Duration = Duration::from_hours(365 * 24);
+
 /// Max rows per multi-row INSERT statement to avoid parameter limits.
 const BATCH_CHUNK_SIZE: usize = 100;
 
@@ -25,9 +40,23 @@ const BATCH_CHUNK_SIZE: usize = 100;
 pub struct Outbox {
     config: OutboxConfig,
     /// Cached partition lookup: `partitions[queue_name][partition_number] = partitions.id` (PK).
+    /// Holds the newest generation of each queue, which moves past the
+    /// registered one when the queue is repartitioned while this instance runs.
     partitions: DashMap<String, Vec<i64>>,
+    /// Partition count each queue was registered with: the count producers
+    /// on this instance compute partition indexes with.
+    registered: DashMap<String, usize>,
+    /// Cached newest generation of each queue. Refreshed by the
+    /// repartition watcher, not on enqueue.
+    generations: DashMap<String, i16>,
+    /// Woken when a queue is repartitioned (Postgres `NOTIFY`), so the
+    /// repartition watcher refreshes before its next poll.
+    repartitioned: Notify,
     /// Reverse map: `partition_id → queue_name`. Populated during `register_queue`.
+    /// Also covers the previous generation of repartitioned queues.
     partition_to_queue: DashMap<i64, String>,
+    /// Previous partition generation of repartitioned queues.
+    previous: DashMap<String, PreviousGeneration>,
     /// Flattened, sorted, deduplicated snapshot of all partition IDs.
     /// Rebuilt on each `register_queue` call.
     all_partition_ids: RwLock<Vec<i64>>,
@@ -36,11 +65,10 @@ pub struct Outbox {
     /// Per-partition notify map for direct signaling from sequencer to processors.
     /// Set once during `start()` after all processors are spawned.
     partition_notify: RwLock<Option<PartitionNotifyMap>>,
-}
-
-#[derive(Debug, FromQueryResult)]
-struct PartitionRow {
-    id: i64,
+    /// Worker stats registry. Set during `start()` when stats are enabled.
+    stats: OnceLock<Arc<StatsRegistry>>,
+    /// OpenTelemetry instruments (no-op without the `otel` feature).
+    metrics: OutboxMetrics,
 }
 
 impl Outbox {
@@ -59,16 +87,26 @@ impl Outbox {
             config,
             partitions: DashMap::new(),
             partition_to_queue: DashMap::new(),
+            previous: DashMap::new(),
+            registered: DashMap::new(),
+            generations: DashMap::new(),
+            repartitioned: Notify::new(),
             all_partition_ids: RwLock::new(Vec::new()),
             prioritizer: RwLock::new(None),
             partition_notify: RwLock::new(None),
+            stats: OnceLock::new(),
+            metrics: OutboxMetrics::new(),
         }
     }
 
     /// Register a queue with `num_partitions` partitions `[0, num_partitions)`.
     ///
-    /// Idempotent when the partition count matches. Returns
-    /// [`OutboxError::PartitionCountMismatch`] if the count differs.
+    /// Idempotent when the partition count matches. The count of the
+    /// queue's previous generation is accepted as well, so an instance
+    /// still built with it keeps starting after a repartition; its enqueues
+    /// then go to the previous generation. Returns
+    /// [`OutboxError::PartitionCountMismatch`] for any other count; see
+    /// [`repartition_queue`](Self::repartition_queue) to change it.
     ///
     /// # Errors
     ///
@@ -94,68 +132,188 @@ impl Outbox {
         let backend = txn.get_database_backend();
         let dialect = Dialect::from(backend);
 
-        let ids =
+        let generations =
             Self::ensure_partition_rows(&txn, backend, &dialect, queue, num_partitions).await?;
+        Self::ensure_processor_rows(&txn, backend, &dialect, &generations.current).await?;
+        Self::ensure_vacuum_counter_rows(&txn, backend, &dialect, &generations.current).await?;
+        let previous = Self::previous_generation(&generations);
+
+        txn.commit().await?;
+
+        self.registered
+            .insert(queue.to_owned(), usize::from(num_partitions));
+        self.populate_caches(queue, &generations, previous).await;
+        Ok(())
+    }
+
+    /// Change the partition count of a registered queue without draining it.
+    ///
+    /// Inserts `partitions` new partitions as the queue's next generation.
+    /// Running pipelines pick it up within their repartition check interval
+    /// ([`OutboxBuilder::repartition_check_interval`]), or right away on
+    /// Postgres with `pg_notify`, and start processors for the new
+    /// partitions. From then on their enqueues go to the new generation
+    /// when shrinking, where an index of the old count maps to
+    /// `index % new`. When growing, an index of the old count does not say
+    /// which new partition its key belongs to, so instances registered with
+    /// the old count, including ones started with it afterwards, keep
+    /// enqueueing into the previous generation. Its partitions keep being
+    /// processed, and each new partition holds its messages back whenever a
+    /// previous partition that may carry the same keys
+    /// ([`Partitions::index_for`]) has work, so per-key order is kept across
+    /// the switch.
+    ///
+    /// No-op if the queue already has `partitions` partitions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OutboxError::QueueNotRegistered`] if the queue has no
+    /// partitions, [`OutboxError::RepartitionInProgress`] while the previous
+    /// generation is still draining, [`OutboxError::RepartitionLimitReached`]
+    /// when the queue has been repartitioned too often, or an error if the
+    /// database operation fails.
+    pub async fn repartition_queue(
+        db: &Db,
+        queue: &str,
+        partitions: Partitions,
+    ) -> Result<(), OutboxError> {
+        super::validation::validate_queue_name(queue)?;
+        let conn = db.sea_internal();
+        let txn = conn.begin().await?;
+        let backend = txn.get_database_backend();
+        let dialect = Dialect::from(backend);
+
+        // Serialize with concurrent repartitions of the same queue.
+        if let Some(lock) = dialect.lock_queue_partitions() {
+            txn.execute(Statement::from_sql_and_values(
+                backend,
+                lock,
+                [queue.into()],
+            ))
+            .await?;
+        }
+        let rows = Self::select_partition_rows(&txn, backend, &dialect, queue).await?;
+        if rows.is_empty() {
+            return Err(OutboxError::QueueNotRegistered(queue.to_owned()));
+        }
+        let generations = Generations::from_rows(&rows);
+        if generations.current.len() == usize::from(partitions.count()) {
+            return Ok(());
+        }
+        if generations.generation > 0
+            && !pending_partitions(&txn, queue, generations.generation - 1)
+                .await?
+                .is_empty()
+        {
+            return Err(OutboxError::RepartitionInProgress(queue.to_owned()));
+        }
+        let (generation, slots) = generations
+            .next(partitions.count())
+            .ok_or_else(|| OutboxError::RepartitionLimitReached(queue.to_owned()))?;
+
+        for slot in slots {
+            txn.execute(Statement::from_sql_and_values(
+                backend,
+                dialect.register_queue_insert(),
+                [queue.into(), slot.into(), generation.into()],
+            ))
+            .await?;
+        }
+        let rows = Self::select_partition_rows(&txn, backend, &dialect, queue).await?;
+        let ids = Generations::from_rows(&rows).current;
         Self::ensure_processor_rows(&txn, backend, &dialect, &ids).await?;
         Self::ensure_vacuum_counter_rows(&txn, backend, &dialect, &ids).await?;
 
+        if let Some(sql) = dialect.notify_wakeup() {
+            txn.execute(Statement::from_sql_and_values(
+                backend,
+                sql,
+                [REPARTITION_CHANNEL.into(), queue.into()],
+            ))
+            .await?;
+        }
+
         txn.commit().await?;
 
-        self.populate_caches(queue, &ids).await;
+        tracing::info!(
+            queue,
+            from = generations.current.len(),
+            to = partitions.count(),
+            generation,
+            "outbox: queue repartitioned"
+        );
         Ok(())
     }
 
+    /// All partition rows of a queue, in `partition` order.
+    async fn select_partition_rows<C: ConnectionTrait>(
+        conn: &C,
+        backend: DbBackend,
+        dialect: &Dialect,
+        queue: &str,
+    ) -> Result<Vec<PartitionRow>, OutboxError> {
+        Ok(
+            PartitionRow::find_by_statement(Statement::from_sql_and_values(
+                backend,
+                dialect.register_queue_select(),
+                [queue.into()],
+            ))
+            .all(conn)
+            .await?,
+        )
+    }
+
     /// Check existing partition rows; insert new ones if absent.
     ///
     /// Returns the partition IDs (PKs) for the queue — whether they were
-    /// already present or freshly inserted.
+    /// already present or freshly inserted — grouped by generation.
     async fn ensure_partition_rows<C: ConnectionTrait>(
         conn: &C,
         backend: DbBackend,
         dialect: &Dialect,
         queue: &str,
         num_partitions: u16,
-    ) -> Result<Vec<i64>, OutboxError> {
-        let existing = PartitionRow::find_by_statement(Statement::from_sql_and_values(
-            backend,
-            dialect.register_queue_select(),
-            [queue.into()],
-        ))
-        .all(conn)
-        .await?;
+    ) -> Result<Generations, OutboxError> {
+        let existing = Self::select_partition_rows(conn, backend, dialect, queue).await?;
 
         if !existing.is_empty() {
-            if existing.len() != usize::from(num_partitions) {
+            let generations = Generations::from_rows(&existing);
+            let count = usize::from(num_partitions);
+            if generations.current.len() != count && generations.previous.len() != count {
                 return Err(OutboxError::PartitionCountMismatch {
                     queue: queue.to_owned(),
                     expected: num_partitions,
-                    found: existing.len(),
+                    found: generations.current.len(),
                 });
             }
-            return Ok(existing.into_iter().map(|r| r.id).collect());
+            return Ok(generations);
         }
 
-        // First registration — insert partition rows
+        // First registration — insert partition rows (generation 0)
         for p in 0..num_partitions {
             conn.execute(Statement::from_sql_and_values(
                 backend,
                 dialect.register_queue_insert(),
                 #[allow(clippy::cast_possible_wrap)]
-                [queue.into(), (p as i16).into()],
+                [queue.into(), (p as i16).into(), 0i16.into()],
             ))
             .await?;
         }
 
         // Read back inserted rows to get their PKs
-        let rows = PartitionRow::find_by_statement(Statement::from_sql_and_values(
-            backend,
-            dialect.register_queue_select(),
-            [queue.into()],
-        ))
-        .all(conn)
-        .await?;
+        let rows = Self::select_partition_rows(conn, backend, dialect, queue).await?;
+        Ok(Generations::from_rows(&rows))
+    }
 
-        Ok(rows.into_iter().map(|r| r.id).collect())
+    /// The previous generation of a repartitioned queue.
+    fn previous_generation(generations: &Generations) -> Option<PreviousGeneration> {
+        if generations.previous.is_empty() {
+            return None;
+        }
+        Some(PreviousGeneration {
+            generation: generations.generation - 1,
+            ids: generations.previous.clone(),
+        })
     }
 
     /// Insert a processor row for each partition ID (idempotent via
@@ -197,31 +355,141 @@ impl Outbox {
     }
 
     /// Update in-memory caches with the partition IDs for a queue.
-    async fn populate_caches(&self, queue: &str, ids: &[i64]) {
+    async fn populate_caches(
+        &self,
+        queue: &str,
+        generations: &Generations,
+        previous: Option<PreviousGeneration>,
+    ) {
+        let ids = &generations.current;
         for &id in ids {
             self.partition_to_queue.insert(id, queue.to_owned());
         }
-        self.partitions.insert(queue.to_owned(), ids.to_vec());
+        self.partitions.insert(queue.to_owned(), ids.clone());
+        self.generations
+            .insert(queue.to_owned(), generations.generation);
+        match previous {
+            Some(previous) => {
+                for &id in &previous.ids {
+                    self.partition_to_queue.insert(id, queue.to_owned());
+                }
+                self.previous.insert(queue.to_owned(), previous);
+            }
+            None => {
+                self.previous.remove(queue);
+            }
+        }
         self.rebuild_partition_id_cache().await;
     }
 
     /// Resolve the `partition_id` (PK) for a `(queue, partition)` pair from cache.
+    ///
+    /// `partition` is an index of the registered partition count. After a
+    /// shrinking repartition it maps to index `partition % count` of the
+    /// newest generation, as [`Partitions::index_for`] keys do. After a
+    /// growing one the key's new partition is unknown, so it names a
+    /// partition of the previous generation, which fences the new one.
+    /// Only an instance that missed two repartitions has no generation of
+    /// its count left to enqueue into.
     fn resolve_partition(&self, queue: &str, partition: u32) -> Result<i64, OutboxError> {
+        let registered = self
+            .registered
+            .get(queue)
+            .map(|count| *count)
+            .ok_or_else(|| OutboxError::QueueNotRegistered(queue.to_owned()))?;
+        if partition as usize >= registered {
+            return Err(OutboxError::PartitionOutOfRange {
+                queue: queue.to_owned(),
+                partition,
+                #[allow(clippy::cast_possible_truncation)]
+                max: registered as u32,
+            });
+        }
         let entry = self
             .partitions
             .get(queue)
             .ok_or_else(|| OutboxError::QueueNotRegistered(queue.to_owned()))?;
         let ids = entry.value();
-        ids.get(partition as usize)
-            .copied()
-            .ok_or_else(|| OutboxError::PartitionOutOfRange {
+        if ids.len() <= registered {
+            return Ok(ids[partition as usize % ids.len()]);
+        }
+        self.previous
+            .get(queue)
+            .filter(|previous| previous.ids.len() == registered)
+            .map(|previous| previous.ids[partition as usize])
+            .ok_or_else(|| OutboxError::StalePartitionCount {
                 queue: queue.to_owned(),
-                partition,
-                #[allow(clippy::cast_possible_truncation)]
-                max: ids.len() as u32,
+                registered,
+                current: ids.len(),
             })
     }
 
+    /// Pick up repartitions of registered queues made elsewhere.
+    ///
+    /// Reloads the partitions of each queue whose newest generation moved
+    /// past the cached one and returns the new partition IDs per queue, for
+    /// the caller to start processors on. Called by the repartition watcher.
+    pub(crate) async fn refresh_generations(
+        &self,
+        db: &Db,
+    ) -> Result<Vec<(String, Vec<i64>)>, OutboxError> {
+        let conn = db.sea_internal();
+        let cached: Vec<(String, i16)> = self
+            .generations
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect();
+        let mut switched = Vec::new();
+        for (queue, generation) in cached {
+            let Some(generations) = Self::newer_generations(&conn, &queue, generation).await?
+            else {
+                continue;
+            };
+            let previous = Self::previous_generation(&generations);
+            tracing::info!(
+                queue,
+                generation = generations.generation,
+                "outbox: switched to the new partition generation"
+            );
+            let ids = generations.current.clone();
+            self.populate_caches(&queue, &generations, previous).await;
+            switched.push((queue, ids));
+        }
+        Ok(switched)
+    }
+
+    /// Wake the repartition watcher after `queue` was repartitioned on
+    /// another instance. No-op for queues not registered here.
+    #[cfg(feature = "pg")]
+    pub(crate) fn notify_repartitioned(&self, queue: &str) {
+        if self.generations.contains_key(queue) {
+            self.repartitioned.notify_one();
+        }
+    }
+
+    /// Signal woken by [`notify_repartitioned`](Self::notify_repartitioned).
+    pub(crate) fn repartitioned(&self) -> &Notify {
+        &self.repartitioned
+    }
+
+    /// The partitions of `queue` if its newest generation is above `cached`.
+    async fn newer_generations<C: ConnectionTrait>(
+        conn: &C,
+        queue: &str,
+        cached: i16,
+    ) -> Result<Option<Generations>, OutboxError> {
+        match current_generation(conn, queue).await? {
+            Some(generation) if generation > cached => {
+                let backend = conn.get_database_backend();
+                let rows =
+                    Self::select_partition_rows(conn, backend, &Dialect::from(backend), queue)
+                        .await?;
+                Ok(Some(Generations::from_rows(&rows)))
+            }
+            _ => Ok(None),
+        }
+    }
+
     /// Validate payload size.
     fn validate_payload(payload: &[u8]) -> Result<(), OutboxError> {
         if payload.len() > MAX_PAYLOAD_SIZE {
@@ -233,6 +501,21 @@ impl Outbox {
         Ok(())
     }
 
+    /// Validate a delivery delay and convert it to the milliseconds bound
+    /// into the incoming INSERT. A zero delay is stored as "immediate".
+    fn delay_millis(delay: Option<Duration>) -> Result<Option<i64>, OutboxError> {
+        match delay {
+            None => Ok(None),
+            Some(d) if d > MAX_DELIVERY_DELAY => Err(OutboxError::DeliveryDelayTooLarge {
+                delay: d,
+                max: MAX_DELIVERY_DELAY,
+            }),
+            Some(d) if d.is_zero() => Ok(None),
+            // Bounded by MAX_DELIVERY_DELAY, so the conversion cannot fail.
+            Some(d) => Ok(Some(i64::try_from(d.as_millis()).unwrap_or(i64::MAX))),
+        }
+    }
+
     /// Enqueue a single message. Accepts `&impl DBRunner` — use within a transaction
     /// for atomicity with business data, or with a standalone connection.
     ///
@@ -247,15 +530,191 @@ impl Outbox {
         payload: Vec<u8>,
         payload_type: &str,
     ) -> Result<OutboxMessageId, OutboxError> {
-        super::validation::validate_queue_name(queue)?;
-        super::validation::validate_payload_type(payload_type)?;
-        Self::validate_payload(&payload)?;
-        let partition_id = self.resolve_partition(queue, partition)?;
+        let msg = EnqueueMessage {
+            partition,
+            payload,
+            payload_type,
+            deliver_after: None,
+            headers: MessageHeaders::default(),
+            dedup_key: None,
+        };
+        self.enqueue_message(db, queue, msg).await
+    }
 
+    /// Enqueue a single message that becomes visible to the processor only
+    /// after `delay` has elapsed.
+    ///
+    /// The due time is computed from the database clock at insert time.
+    /// A delayed message is sequenced when it becomes due, so it is ordered
+    /// after every message of the partition that was sequenced before that
+    /// moment — including messages enqueued later without a delay. Messages
+    /// that become due together keep their enqueue order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on validation failure (including a delay above
+    /// 365 days) or database error.
+    pub async fn enqueue_after(
+        &self,
+        db: &(impl crate::secure::DBRunner + Sync + ?Sized),
+        queue: &str,
+        partition: u32,
+        payload: Vec<u8>,
+        payload_type: &str,
+        delay: Duration,
+    ) -> Result<OutboxMessageId, OutboxError> {
+        let msg = EnqueueMessage {
+            partition,
+            payload,
+            payload_type,
+            deliver_after: Some(delay),
+            headers: MessageHeaders::default(),
+            dedup_key: None,
+        };
+        self.enqueue_message(db, queue, msg).await
+    }
+
+    /// Enqueue a single message that becomes visible to the processor at
+    /// `deliver_at`. A time in the past delivers immediately.
+    ///
+    /// `deliver_at` is converted to a delay against the local clock, then
+    /// handled exactly like [`enqueue_after`](Self::enqueue_after).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on validation failure or database error.
+    pub async fn enqueue_at(
+        &self,
+        db: &(impl crate::secure::DBRunner + Sync + ?Sized),
+        queue: &str,
+        partition: u32,
+        payload: Vec<u8>,
+        payload_type: &str,
+        deliver_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<OutboxMessageId, OutboxError> {
+        let delay = (deliver_at - chrono::Utc::now())
+            .to_std()
+            .unwrap_or(Duration::ZERO);
+        self.enqueue_after(db, queue, partition, payload, payload_type, delay)
+            .await
+    }
+
+    /// Enqueue a single message with explicit headers and/or delay.
+    ///
+    /// [`enqueue`](Self::enqueue) and [`enqueue_after`](Self::enqueue_after)
+    /// are shorthands for this with empty headers. The current span's
+    /// `traceparent` is added unless `msg.headers` already carries one.
+    ///
+    /// With a [`dedup_key`](EnqueueMessage::dedup_key), a repeat of a key
+    /// enqueued on this queue within the dedup retention window inserts
+    /// nothing and returns [`OutboxError::AlreadyEnqueued`]. The key is
+    /// claimed in the caller's transaction, so a rollback frees it again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on validation failure (including invalid headers),
+    /// a repeated dedup key, or database error.
+    pub async fn enqueue_message(
+        &self,
+        db: &(impl crate::secure::DBRunner + Sync + ?Sized),
+        queue: &str,
+        msg: EnqueueMessage<'_>,
+    ) -> Result<OutboxMessageId, OutboxError> {
+        let dedup_key = msg.dedup_key;
+        let runner = db.as_seaorm();
+        let new = self.prepare(queue, msg)?;
+
+        if let Some(key) = dedup_key {
+            self.claim_dedup_keys(&runner, queue, &[key]).await?;
+        }
+        self.insert_prepared(&runner, queue, new).await
+    }
+
+    /// Receive a message from an external system into an inbox queue.
+    ///
+    /// Records `msg.message_id` for the queue and enqueues the message in
+    /// the same transaction. A message ID already recorded (within the
+    /// inbox retention) is not enqueued again and yields
+    /// [`Received::Duplicate`], so redelivered webhooks are safe to
+    /// acknowledge. Call it inside the transaction that acknowledges the
+    /// sender; outside one, a failed enqueue still records the ID.
+    ///
+    /// See [`InboxQueueBuilder`](super::InboxQueueBuilder) for processing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on validation failure or database error.
+    pub async fn receive(
+        &self,
+        db: &(impl crate::secure::DBRunner + Sync + ?Sized),
+        queue: &str,
+        msg: InboxMessage<'_>,
+    ) -> Result<Received, OutboxError> {
+        super::validation::validate_message_id(msg.message_id)?;
+        let message_id = msg.message_id;
         let runner = db.as_seaorm();
-        let incoming_id =
-            Self::insert_body_and_incoming(&runner, partition_id, payload, payload_type).await?;
+        let new = self.prepare(queue, msg.into_enqueue())?;
+
+        let (conn, backend): (&dyn ConnectionTrait, DbBackend) = match &runner {
+            SeaOrmRunner::Conn(c) => (*c, c.get_database_backend()),
+            SeaOrmRunner::Tx(t) => (*t, t.get_database_backend()),
+        };
+        let claimed = conn
+            .execute(Statement::from_sql_and_values(
+                backend,
+                Dialect::from(backend).claim_inbox_message(),
+                [queue.into(), message_id.into()],
+            ))
+            .await?
+            .rows_affected()
+            == 1;
+        if !claimed {
+            return Ok(Received::Duplicate);
+        }
+        self.insert_prepared(&runner, queue, new)
+            .await
+            .map(Received::Accepted)
+    }
+
+    /// Validate a message and resolve its partition. No DB access.
+    fn prepare<'a>(
+        &self,
+        queue: &str,
+        msg: EnqueueMessage<'a>,
+    ) -> Result<NewMessage<'a>, OutboxError> {
+        super::validation::validate_queue_name(queue)?;
+        super::validation::validate_payload_type(msg.payload_type)?;
+        Self::validate_payload(&msg.payload)?;
+        let delay_ms = Self::delay_millis(msg.deliver_after)?;
+        let headers = msg.headers.encode_for_enqueue()?;
+        if let Some(key) = msg.dedup_key {
+            super::validation::validate_dedup_key(key)?;
+        }
+        let partition_id = self.resolve_partition(queue, msg.partition)?;
+        Ok(NewMessage {
+            partition_id,
+            payload: msg.payload,
+            payload_type: msg.payload_type,
+            headers,
+            delay_ms,
+        })
+    }
+
+    /// Insert a prepared message and mark its partition dirty.
+    async fn insert_prepared(
+        &self,
+        runner: &SeaOrmRunner<'_>,
+        queue: &str,
+        msg: NewMessage<'_>,
+    ) -> Result<OutboxMessageId, OutboxError> {
+        let partition_id = msg.partition_id;
+        let incoming_id = Self::insert_body_and_incoming(runner, msg).await?;
+        self.notify_wakeup(runner, INCOMING_CHANNEL, [partition_id])
+            .await?;
+        self.metrics.enqueued(queue, 1);
 
+        // Delayed rows also mark the partition dirty: the sequencer's
+        // next-due lookup turns that into a wakeup at the due time.
         self.push_dirty(partition_id);
 
         Ok(OutboxMessageId(incoming_id))
@@ -265,9 +724,14 @@ impl Outbox {
     /// All validation happens before any DB writes — a single invalid message
     /// rejects the entire batch.
     ///
+    /// Dedup keys are claimed before any message is inserted. If any key is
+    /// a repeat (including a key used twice in the batch), nothing is
+    /// inserted and [`OutboxError::AlreadyEnqueued`] names the first one.
+    ///
     /// # Errors
     ///
-    /// Returns an error on validation failure or database error.
+    /// Returns an error on validation failure, a repeated dedup key, or
+    /// database error.
     pub async fn enqueue_batch(
         &self,
         db: &(impl crate::secure::DBRunner + Sync + ?Sized),
@@ -276,16 +740,30 @@ impl Outbox {
     ) -> Result<Vec<OutboxMessageId>, OutboxError> {
         // Validate ALL items first
         super::validation::validate_queue_name(queue)?;
+        let runner = db.as_seaorm();
         let mut resolved = Vec::with_capacity(items.len());
+        let mut delays = Vec::with_capacity(items.len());
+        let mut headers = Vec::with_capacity(items.len());
         for item in items {
             super::validation::validate_payload_type(item.payload_type)?;
             Self::validate_payload(&item.payload)?;
+            delays.push(Self::delay_millis(item.deliver_after)?);
+            headers.push(item.headers.encode_for_enqueue()?);
+            if let Some(key) = item.dedup_key {
+                super::validation::validate_dedup_key(key)?;
+            }
             let partition_id = self.resolve_partition(queue, item.partition)?;
             resolved.push(partition_id);
         }
 
-        let runner = db.as_seaorm();
-        let ids = Self::insert_batch(&runner, &resolved, items).await?;
+        let dedup_keys: Vec<&str> = items.iter().filter_map(|item| item.dedup_key).collect();
+        self.claim_dedup_keys(&runner, queue, &dedup_keys).await?;
+        let ids = Self::insert_batch(&runner, &resolved, &delays, &headers, items).await?;
+        let distinct: BTreeSet<i64> = resolved.iter().copied().collect();
+        self.notify_wakeup(&runner, INCOMING_CHANNEL, distinct)
+            .await?;
+        self.metrics
+            .enqueued(queue, u64::try_from(ids.len()).unwrap_or(u64::MAX));
 
         // Push dirty for each distinct partition_id in the batch
         for &pid in &resolved {
@@ -295,10 +773,55 @@ impl Outbox {
         Ok(ids)
     }
 
+    /// Claim producer dedup keys before anything is inserted. On a repeat,
+    /// releases the keys this call already claimed and returns
+    /// [`OutboxError::AlreadyEnqueued`].
+    async fn claim_dedup_keys(
+        &self,
+        runner: &SeaOrmRunner<'_>,
+        queue: &str,
+        keys: &[&str],
+    ) -> Result<(), OutboxError> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        let (conn, backend): (&dyn ConnectionTrait, DbBackend) = match runner {
+            SeaOrmRunner::Conn(c) => (*c, c.get_database_backend()),
+            SeaOrmRunner::Tx(t) => (*t, t.get_database_backend()),
+        };
+        let dialect = Dialect::from(backend);
+        let retention_ms =
+            i64::try_from(self.config.dedup_retention.as_millis()).unwrap_or(i64::MAX);
+
+        for (i, &key) in keys.iter().enumerate() {
+            if dialect
+                .exec_claim_dedup_key(conn, backend, queue, key, retention_ms)
+                .await?
+            {
+                continue;
+            }
+            for &claimed in &keys[..i] {
+                conn.execute(Statement::from_sql_and_values(
+                    backend,
+                    dialect.release_dedup_key(),
+                    [queue.into(), claimed.into()],
+                ))
+                .await?;
+            }
+            return Err(OutboxError::AlreadyEnqueued {
+                queue: queue.to_owned(),
+                dedup_key: key.to_owned(),
+            });
+        }
+        Ok(())
+    }
+
     /// Insert a batch of body + incoming rows using multi-row INSERTs.
     async fn insert_batch(
         runner: &SeaOrmRunner<'_>,
         partition_ids: &[i64],
+        delays_ms: &[Option<i64>],
+        headers: &[Option<String>],
         items: &[EnqueueMessage<'_>],
     ) -> Result<Vec<OutboxMessageId>, OutboxError> {
         let (conn, backend): (&dyn ConnectionTrait, DbBackend) = match runner {
@@ -314,10 +837,14 @@ impl Outbox {
         let mut all_body_ids: Vec<i64> = Vec::with_capacity(items.len());
 
         // Insert body rows in chunks
-        for chunk in items.chunks(BATCH_CHUNK_SIZE) {
-            let payloads: Vec<(&[u8], &str)> = chunk
+        for (chunk, chunk_headers) in items
+            .chunks(BATCH_CHUNK_SIZE)
+            .zip(headers.chunks(BATCH_CHUNK_SIZE))
+        {
+            let payloads: Vec<(&[u8], &str, Option<&str>)> = chunk
                 .iter()
-                .map(|item| (item.payload.as_slice(), item.payload_type))
+                .zip(chunk_headers)
+                .map(|(item, h)| (item.payload.as_slice(), item.payload_type, h.as_deref()))
                 .collect();
             let chunk_ids = dialect
                 .exec_insert_body_batch(conn, backend, &payloads)
@@ -330,8 +857,8 @@ impl Outbox {
         // Insert incoming rows in chunks
         for chunk_start in (0..items.len()).step_by(BATCH_CHUNK_SIZE) {
             let chunk_end = (chunk_start + BATCH_CHUNK_SIZE).min(items.len());
-            let entries: Vec<(i64, i64)> = (chunk_start..chunk_end)
-                .map(|i| (partition_ids[i], all_body_ids[i]))
+            let entries: Vec<(i64, i64, Option<i64>)> = (chunk_start..chunk_end)
+                .map(|i| (partition_ids[i], all_body_ids[i], delays_ms[i]))
                 .collect();
             let chunk_ids = dialect
                 .exec_insert_incoming_batch(conn, backend, &entries)
@@ -345,9 +872,7 @@ impl Outbox {
     /// Insert body + incoming rows, returning the `incoming_id`.
     async fn insert_body_and_incoming(
         runner: &SeaOrmRunner<'_>,
-        partition_id: i64,
-        payload: Vec<u8>,
-        payload_type: &str,
+        msg: NewMessage<'_>,
     ) -> Result<i64, OutboxError> {
         let (conn, backend): (&dyn ConnectionTrait, DbBackend) = match runner {
             SeaOrmRunner::Conn(c) => (*c, c.get_database_backend()),
@@ -356,7 +881,7 @@ impl Outbox {
         let dialect = Dialect::from(backend);
 
         let incoming_id = dialect
-            .exec_insert_body_and_incoming(conn, backend, partition_id, payload, payload_type)
+            .exec_insert_body_and_incoming(conn, backend, msg)
             .await?;
 
         Ok(incoming_id)
@@ -456,6 +981,109 @@ impl Outbox {
         super::dead_letter::dead_letter_cleanup(db.as_seaorm(), scope).await
     }
 
+    /// Re-enqueue dead letters into the partition they failed on.
+    ///
+    /// Claims the dead letters matching `scope` (as
+    /// [`dead_letter_replay`](Self::dead_letter_replay) does), enqueues each
+    /// payload again, and marks them `resolved`. Returns the number of
+    /// requeued messages. Run it inside a transaction: otherwise a failure
+    /// halfway leaves messages claimed until `timeout` expires, and some
+    /// may be enqueued twice on the next attempt. Headers are not kept.
+    ///
+    /// # Errors
+    /// Returns [`OutboxError::PartitionNotRegistered`] if a dead letter's
+    /// partition is not registered with this outbox, or an error if the
+    /// database operation fails.
+    pub async fn dead_letter_requeue(
+        &self,
+        db: &(impl crate::secure::DBRunner + Sync),
+        scope: &DeadLetterScope,
+        timeout: std::time::Duration,
+    ) -> Result<u64, OutboxError> {
+        let claimed = self.dead_letter_replay(db, scope, timeout).await?;
+        for msg in &claimed {
+            let (queue, partition) = self
+                .partition_location(msg.partition_id)
+                .ok_or(OutboxError::PartitionNotRegistered(msg.partition_id))?;
+            self.enqueue(
+                db,
+                &queue,
+                partition,
+                msg.payload.clone(),
+                &msg.payload_type,
+            )
+            .await?;
+        }
+        let ids: Vec<i64> = claimed.iter().map(|m| m.id).collect();
+        self.dead_letter_resolve(db, &ids).await
+    }
+
+    /// Per-partition backlog, lag, and processor state of all queues.
+    ///
+    /// # Errors
+    /// Returns error if the database operation fails.
+    pub async fn partition_health(
+        &self,
+        db: &(impl crate::secure::DBRunner + Sync),
+    ) -> Result<Vec<PartitionHealth>, OutboxError> {
+        super::health::partition_health(db.as_seaorm(), None).await
+    }
+
+    /// [`partition_health`](Self::partition_health) of a single queue; empty
+    /// when the queue is not registered.
+    ///
+    /// # Errors
+    /// Returns error if the database operation fails.
+    pub async fn queue_partition_health(
+        &self,
+        db: &(impl crate::secure::DBRunner + Sync),
+        queue: &str,
+    ) -> Result<Vec<PartitionHealth>, OutboxError> {
+        super::health::partition_health(db.as_seaorm(), Some(queue)).await
+    }
+
+    /// Per-queue backlog, lag, retrying partitions, and pending dead letters.
+    ///
+    /// # Errors
+    /// Returns error if the database operation fails.
+    pub async fn queue_health(
+        &self,
+        db: &(impl crate::secure::DBRunner + Sync),
+    ) -> Result<Vec<QueueHealth>, OutboxError> {
+        let partitions = self.partition_health(db).await?;
+        self.queue_health_of(db, &partitions).await
+    }
+
+    /// [`queue_health`](Self::queue_health) from already read partitions.
+    pub(crate) async fn queue_health_of(
+        &self,
+        db: &(impl crate::secure::DBRunner + Sync),
+        partitions: &[PartitionHealth],
+    ) -> Result<Vec<QueueHealth>, OutboxError> {
+        let mut queues = super::health::aggregate(partitions);
+        for q in &mut queues {
+            let filter = DeadLetterFilter::default().queue(q.queue.clone());
+            q.pending_dead_letters = self.dead_letter_count(db, &filter).await?;
+        }
+        Ok(queues)
+    }
+
+    /// Lifetime worker counters by category (`"processor"`, `"sequencer"`,
+    /// `"vacuum"`). Empty when stats are disabled via
+    /// [`OutboxBuilder::stats_interval()`] or before `start()`.
+    #[must_use]
+    pub fn worker_stats(&self) -> Vec<(String, CategorySnapshot)> {
+        self.stats
+            .get()
+            .map(|registry| registry.totals_all())
+            .unwrap_or_default()
+    }
+
+    /// Install the stats registry. Called once during `start()`.
+    pub(crate) fn set_stats_registry(&self, registry: Arc<StatsRegistry>) {
+        _ = self.stats.set(registry);
+    }
+
     /// Install the shared prioritizer. Called once during `start()`.
     pub(crate) async fn set_prioritizer(&self, prioritizer: Arc<SharedPrioritizer>) {
         *self.prioritizer.write().await = Some(prioritizer);
@@ -471,11 +1099,68 @@ impl Outbox {
         }
     }
 
+    /// Notify `channel` once per partition so listeners on other instances
+    /// wake up. In a transaction, delivery happens on commit. No-op unless
+    /// the backend is Postgres and [`OutboxConfig::pg_notify`] is set.
+    pub(crate) async fn notify_wakeup(
+        &self,
+        runner: &SeaOrmRunner<'_>,
+        channel: &str,
+        partition_ids: impl IntoIterator<Item = i64>,
+    ) -> Result<(), OutboxError> {
+        let (conn, backend): (&dyn ConnectionTrait, DbBackend) = match runner {
+            SeaOrmRunner::Conn(c) => (*c, c.get_database_backend()),
+            SeaOrmRunner::Tx(t) => (*t, t.get_database_backend()),
+        };
+        let Some(sql) = Dialect::from(backend).notify_wakeup() else {
+            return Ok(());
+        };
+        if !self.config.pg_notify {
+            return Ok(());
+        }
+        for pid in partition_ids {
+            conn.execute(Statement::from_sql_and_values(
+                backend,
+                sql,
+                [channel.into(), pid.to_string().into()],
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// OpenTelemetry instruments of this outbox.
+    #[cfg(feature = "otel")]
+    pub(crate) fn metrics(&self) -> &OutboxMetrics {
+        &self.metrics
+    }
+
+    /// Instruments bound to a partition's queue and ID.
+    pub(crate) fn partition_metrics(&self, partition_id: i64) -> PartitionMetrics {
+        let queue = self.partition_to_queue(partition_id).unwrap_or_default();
+        self.metrics.partition(&queue, partition_id)
+    }
+
+    /// Whether `partition_id` belongs to a queue registered on this outbox.
+    #[cfg(any(feature = "pg", feature = "otel"))]
+    pub(crate) fn owns_partition(&self, partition_id: i64) -> bool {
+        self.partition_to_queue.contains_key(&partition_id)
+    }
+
     /// Install the per-partition notify map. Called once during `start()`.
     pub(crate) async fn set_partition_notify(&self, map: PartitionNotifyMap) {
         *self.partition_notify.write().await = Some(map);
     }
 
+    /// Add a processor's notify for a partition that became current after
+    /// `start()`, e.g. by a repartition.
+    pub(crate) async fn add_partition_notify(&self, partition_id: i64, notify: Arc<Notify>) {
+        let mut guard = self.partition_notify.write().await;
+        let mut map = guard.as_deref().cloned().unwrap_or_default();
+        map.insert(partition_id, notify);
+        *guard = Some(Arc::new(map));
+    }
+
     /// Signal a partition's processor that new outgoing rows are available.
     pub(crate) fn notify_partition(&self, partition_id: i64) {
         if let Some(guard) = self.partition_notify.try_read().ok()
@@ -554,6 +1239,43 @@ impl Outbox {
             .unwrap_or_default()
     }
 
+    /// Partition IDs of a queue's previous generation, which get processors
+    /// next to the current ones.
+    pub(crate) fn previous_partition_ids(&self, queue: &str) -> Vec<i64> {
+        self.previous
+            .get(queue)
+            .map(|p| p.ids.clone())
+            .unwrap_or_default()
+    }
+
+    /// The ordering fence of a current-generation partition whose queue has
+    /// a previous generation.
+    pub(crate) fn partition_fence(&self, partition_id: i64) -> Option<PartitionFence> {
+        let queue = self.partition_to_queue(partition_id)?;
+        let previous = self.previous.get(&queue)?;
+        let current = self.partitions.get(&queue)?;
+        let index = current.iter().position(|&id| id == partition_id)?;
+        let waits_on = fence_sources(index, current.len(), previous.ids.len())
+            .filter_map(|i| previous.ids.get(i).copied())
+            .collect();
+        Some(PartitionFence::new(queue, previous.generation, waits_on))
+    }
+
+    /// Look up the queue name and partition index for a partition ID.
+    /// A previous-generation partition maps to its index modulo the
+    /// current count.
+    fn partition_location(&self, partition_id: i64) -> Option<(String, u32)> {
+        let queue = self.partition_to_queue(partition_id)?;
+        let current = self.partitions.get(&queue)?;
+        let index = if let Some(index) = current.iter().position(|&id| id == partition_id) {
+            index
+        } else {
+            let previous = self.previous.get(&queue)?;
+            previous.ids.iter().position(|&id| id == partition_id)? % current.len()
+        };
+        Some((queue, u32::try_from(index).ok()?))
+    }
+
     /// Look up the queue name for a partition ID.
     #[must_use]
     pub fn partition_to_queue(&self, partition_id: i64) -> Option<String> {
@@ -582,6 +1304,7 @@ mod tests {
     #[test]
     fn resolve_partition_cache_hit() {
         let outbox = make_default_outbox();
+        outbox.registered.insert("orders".to_owned(), 3);
         outbox
             .partitions
             .insert("orders".to_owned(), vec![10, 20, 30]);
@@ -602,6 +1325,7 @@ mod tests {
     #[test]
     fn resolve_partition_out_of_range() {
         let outbox = make_default_outbox();
+        outbox.registered.insert("orders".to_owned(), 3);
         outbox
             .partitions
             .insert("orders".to_owned(), vec![10, 20, 30]);
@@ -613,6 +1337,64 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn resolve_partition_maps_index_into_smaller_generation() {
+        let outbox = make_default_outbox();
+        outbox.registered.insert("orders".to_owned(), 4);
+        outbox.partitions.insert("orders".to_owned(), vec![50, 60]);
+
+        assert_eq!(outbox.resolve_partition("orders", 1).unwrap(), 60);
+        assert_eq!(outbox.resolve_partition("orders", 2).unwrap(), 50);
+        assert_eq!(outbox.resolve_partition("orders", 3).unwrap(), 60);
+        assert!(outbox.resolve_partition("orders", 4).is_err());
+    }
+
+    #[test]
+    fn resolve_partition_maps_index_of_smaller_count_into_previous_generation() {
+        let outbox = make_default_outbox();
+        outbox.registered.insert("orders".to_owned(), 2);
+        outbox
+            .partitions
+            .insert("orders".to_owned(), vec![50, 60, 70, 80]);
+        outbox.previous.insert(
+            "orders".to_owned(),
+            PreviousGeneration {
+                generation: 0,
+                ids: vec![10, 20],
+            },
+        );
+
+        assert_eq!(outbox.resolve_partition("orders", 0).unwrap(), 10);
+        assert_eq!(outbox.resolve_partition("orders", 1).unwrap(), 20);
+        assert!(outbox.resolve_partition("orders", 2).is_err());
+    }
+
+    #[test]
+    fn resolve_partition_rejects_index_of_count_two_generations_back() {
+        let outbox = make_default_outbox();
+        outbox.registered.insert("orders".to_owned(), 2);
+        outbox
+            .partitions
+            .insert("orders".to_owned(), vec![50, 60, 70, 80]);
+        outbox.previous.insert(
+            "orders".to_owned(),
+            PreviousGeneration {
+                generation: 1,
+                ids: vec![90],
+            },
+        );
+
+        let err = outbox.resolve_partition("orders", 1).unwrap_err();
+        assert!(matches!(
+            err,
+            OutboxError::StalePartitionCount {
+                registered: 2,
+                current: 4,
+                ..
+            }
+        ));
+    }
+
     // -- validate_payload tests --
 
     #[test]
@@ -633,11 +1415,39 @@ mod tests {
         assert!(Outbox::validate_payload(&[]).is_ok());
     }
 
+    // -- delay_millis tests --
+
+    #[test]
+    fn delay_millis_none_and_zero_are_immediate() {
+        assert_eq!(Outbox::delay_millis(None).unwrap(), None);
+        assert_eq!(Outbox::delay_millis(Some(Duration::ZERO)).unwrap(), None);
+    }
+
+    #[test]
+    fn delay_millis_converts_to_millis() {
+        assert_eq!(
+            Outbox::delay_millis(Some(Duration::from_millis(1500))).unwrap(),
+            Some(1500)
+        );
+        assert_eq!(
+            Outbox::delay_millis(Some(MAX_DELIVERY_DELAY)).unwrap(),
+            Some(365 * 24 * 3600 * 1000)
+        );
+    }
+
+    #[test]
+    fn delay_millis_rejects_above_max() {
+        let err =
+            Outbox::delay_millis(Some(MAX_DELIVERY_DELAY + Duration::from_secs(1))).unwrap_err();
+        assert!(matches!(err, OutboxError::DeliveryDelayTooLarge { .. }));
+    }
+
     // -- enqueue_batch validation tests (no DB needed) --
 
     #[tokio::test]
     async fn enqueue_batch_rejects_out_of_range_partition() {
         let outbox = make_default_outbox();
+        outbox.registered.insert("q".to_owned(), 2);
         outbox.partitions.insert("q".to_owned(), vec![10, 20]);
 
         let err = outbox.resolve_partition("q", 5).unwrap_err();
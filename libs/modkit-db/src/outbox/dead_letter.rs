@@ -24,17 +24,25 @@
 //!
 //! | Operation  | Purpose | Parameter | Concurrency |
 //! |------------|---------|-----------|-------------|
-//! | `list`     | Inspect dead letters | `&DeadLetterFilter` | Safe |
+//! | `list`     | Inspect dead letters, newest first | `&DeadLetterFilter` | Safe |
 //! | `count`    | Count matching | `&DeadLetterFilter` | Safe |
 //! | `replay`   | Claim for reprocessing | `&DeadLetterScope` + `Duration` | Row-locked |
 //! | `resolve`  | Mark as resolved | `&[i64]` | Safe |
 //! | `reject`   | Return to pending | `&[i64]` + reason | Safe |
 //! | `discard`  | Soft-delete | `&DeadLetterScope` | Row-locked |
 //! | `cleanup`  | Delete terminal states | `&DeadLetterScope` | Safe |
+//!
+//! A [`DeadLetterFilter`] can also be built from an `OData` `$filter` via
+//! [`DeadLetterFilter::from_odata`] (fields: [`DeadLetterFilterField`]).
 
 use std::fmt::Write as _;
 use std::time::Duration;
 
+use modkit_odata::ast::Expr;
+use modkit_odata::filter::{
+    FieldKind, FilterError, FilterField, FilterNode, FilterOp, FilterResult, ODataValue,
+    convert_expr_to_filter_node,
+};
 use sea_orm::{
     ConnectionTrait, DbBackend, FromQueryResult, Statement, TransactionTrait, TryGetError,
     TryGetable,
@@ -121,6 +129,7 @@ pub struct DeadLetterMessage {
 /// operations hardcode their own status logic.
 #[derive(Debug, Default)]
 pub struct DeadLetterScope {
+    pub id: Option<i64>,
     pub partition_id: Option<i64>,
     pub queue: Option<String>,
     pub payload_type: Option<String>,
@@ -128,6 +137,13 @@ pub struct DeadLetterScope {
 }
 
 impl DeadLetterScope {
+    /// Target a single dead letter.
+    #[must_use]
+    pub fn id(mut self, id: i64) -> Self {
+        self.id = Some(id);
+        self
+    }
+
     #[must_use]
     pub fn partition(mut self, id: i64) -> Self {
         self.partition_id = Some(id);
@@ -159,6 +175,9 @@ impl DeadLetterScope {
 pub struct DeadLetterFilter {
     pub scope: DeadLetterScope,
     pub status: Option<DeadLetterStatus>,
+    /// Keyset cursor: only dead letters with a smaller ID. `list` returns
+    /// the newest first, so the last ID of a page continues the listing.
+    pub before_id: Option<i64>,
 }
 
 impl DeadLetterFilter {
@@ -168,9 +187,77 @@ impl DeadLetterFilter {
         Self {
             scope,
             status: Some(DeadLetterStatus::Pending),
+            before_id: None,
+        }
+    }
+
+    /// Build a filter from a parsed `OData` `$filter` expression.
+    ///
+    /// Supports `eq` on the [`DeadLetterFilterField`] fields, combined with
+    /// `and`. Without a `status` term only `pending` rows match, as for
+    /// [`DeadLetterFilter::default`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FilterError`] for unknown fields, other operators, a field
+    /// given twice, or an unknown status.
+    pub fn from_odata(expr: &Expr) -> FilterResult<Self> {
+        let node = convert_expr_to_filter_node::<DeadLetterFilterField>(expr)?;
+        let mut filter = Self::from_scope(DeadLetterScope::default()).any_status();
+        filter.apply_node(&node)?;
+        filter.status.get_or_insert(DeadLetterStatus::Pending);
+        Ok(filter)
+    }
+
+    fn apply_node(&mut self, node: &FilterNode<DeadLetterFilterField>) -> FilterResult<()> {
+        match node {
+            FilterNode::Composite {
+                op: FilterOp::And,
+                children,
+            } => children.iter().try_for_each(|child| self.apply_node(child)),
+            FilterNode::Binary {
+                field,
+                op: FilterOp::Eq,
+                value,
+            } => self.apply_eq(*field, value),
+            _ => Err(FilterError::UnsupportedOperation(
+                "dead letter filters support only 'eq' combined with 'and'".to_owned(),
+            )),
+        }
+    }
+
+    fn apply_eq(&mut self, field: DeadLetterFilterField, value: &ODataValue) -> FilterResult<()> {
+        let invalid =
+            || FilterError::InvalidExpression(format!("invalid value for '{}'", field.name()));
+        match (field, value) {
+            (DeadLetterFilterField::Id, ODataValue::Number(n)) => {
+                let id = n.to_string().parse().map_err(|_| invalid())?;
+                set_once(&mut self.scope.id, id, field)
+            }
+            (DeadLetterFilterField::PartitionId, ODataValue::Number(n)) => {
+                let id = n.to_string().parse().map_err(|_| invalid())?;
+                set_once(&mut self.scope.partition_id, id, field)
+            }
+            (DeadLetterFilterField::Queue, ODataValue::String(s)) => {
+                set_once(&mut self.scope.queue, s.clone(), field)
+            }
+            (DeadLetterFilterField::PayloadType, ODataValue::String(s)) => {
+                set_once(&mut self.scope.payload_type, s.clone(), field)
+            }
+            (DeadLetterFilterField::Status, ODataValue::String(s)) => {
+                let status = s.parse().map_err(FilterError::InvalidExpression)?;
+                set_once(&mut self.status, status, field)
+            }
+            _ => Err(invalid()),
         }
     }
 
+    #[must_use]
+    pub fn id(mut self, id: i64) -> Self {
+        self.scope.id = Some(id);
+        self
+    }
+
     #[must_use]
     pub fn partition(mut self, id: i64) -> Self {
         self.scope.partition_id = Some(id);
@@ -207,6 +294,62 @@ impl DeadLetterFilter {
         self.status = None;
         self
     }
+
+    /// Continue a listing after the dead letter `id` (see
+    /// [`before_id`](Self::before_id)).
+    #[must_use]
+    pub fn before(mut self, id: i64) -> Self {
+        self.before_id = Some(id);
+        self
+    }
+}
+
+fn set_once<T>(slot: &mut Option<T>, value: T, field: DeadLetterFilterField) -> FilterResult<()> {
+    if slot.is_some() {
+        return Err(FilterError::InvalidExpression(format!(
+            "'{}' is given more than once",
+            field.name()
+        )));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+/// `OData` `$filter` fields for dead letters.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DeadLetterFilterField {
+    Id,
+    Queue,
+    PartitionId,
+    PayloadType,
+    Status,
+}
+
+impl FilterField for DeadLetterFilterField {
+    const FIELDS: &'static [Self] = &[
+        Self::Id,
+        Self::Queue,
+        Self::PartitionId,
+        Self::PayloadType,
+        Self::Status,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Queue => "queue",
+            Self::PartitionId => "partition_id",
+            Self::PayloadType => "payload_type",
+            Self::Status => "status",
+        }
+    }
+
+    fn kind(&self) -> FieldKind {
+        match self {
+            Self::Id | Self::PartitionId => FieldKind::I64,
+            Self::Queue | Self::PayloadType | Self::Status => FieldKind::String,
+        }
+    }
 }
 
 impl Default for DeadLetterFilter {
@@ -214,6 +357,7 @@ impl Default for DeadLetterFilter {
         Self {
             scope: DeadLetterScope::default(),
             status: Some(DeadLetterStatus::Pending),
+            before_id: None,
         }
     }
 }
@@ -470,7 +614,7 @@ impl QueryBuilder {
     }
 
     fn finish(mut self, limit: Option<u32>) -> (String, Vec<sea_orm::Value>) {
-        self.sql.push_str(" ORDER BY failed_at DESC");
+        self.sql.push_str(" ORDER BY d.id DESC");
         if let Some(n) = limit {
             #[allow(clippy::let_underscore_must_use)]
             let _ = write!(self.sql, " LIMIT {n}");
@@ -501,6 +645,10 @@ impl QueryBuilder {
 }
 
 fn apply_scope(qb: &mut QueryBuilder, scope: &DeadLetterScope) {
+    if let Some(id) = scope.id {
+        let idx = qb.param_idx;
+        qb.add_condition(&format!("d.id = ${idx}"), id.into());
+    }
     if let Some(pid) = scope.partition_id {
         let idx = qb.param_idx;
         qb.add_condition(&format!("d.partition_id = ${idx}"), pid.into());
@@ -529,6 +677,10 @@ fn apply_filter(qb: &mut QueryBuilder, filter: &DeadLetterFilter) {
         let idx = qb.param_idx;
         qb.add_condition(&format!("d.status = ${idx}"), status.to_string().into());
     }
+    if let Some(before) = filter.before_id {
+        let idx = qb.param_idx;
+        qb.add_condition(&format!("d.id < ${idx}"), before.into());
+    }
 }
 
 const SELECT_COLUMNS: &str = "SELECT d.id, d.partition_id, d.seq, d.payload, d.payload_type, \
@@ -772,6 +924,16 @@ mod tests {
         assert_eq!(values.len(), 3); // partition_id, queue, status
     }
 
+    #[test]
+    fn scope_id_filter() {
+        let scope = DeadLetterScope::default().id(7);
+        let mut qb = QueryBuilder::new("SELECT 1 FROM t d", DbBackend::Postgres);
+        apply_scope(&mut qb, &scope);
+        let (sql, values) = qb.finish_no_order(None);
+        assert!(sql.contains("d.id = $1"));
+        assert_eq!(values.len(), 1);
+    }
+
     #[test]
     fn scope_payload_type_filter() {
         let scope = DeadLetterScope::default().payload_type("order.created");
@@ -1000,6 +1162,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_select_query_pages_by_id() {
+        let filter = DeadLetterFilter::default().before(42).limit(10);
+        let (sql, values) = build_select_query(DbBackend::Postgres, &filter);
+        assert!(sql.contains("d.id < $2"), "got: {sql}");
+        assert!(sql.ends_with("ORDER BY d.id DESC LIMIT 10"), "got: {sql}");
+        assert_eq!(values.last(), Some(&sea_orm::Value::from(42_i64)));
+    }
+
     // --- Column list ---
 
     #[test]
@@ -1011,4 +1182,44 @@ mod tests {
         assert!(sql.contains("d.deadline"));
         assert!(!sql.contains("d.replayed_at"));
     }
+
+    // --- OData filter tests ---
+
+    fn odata(raw: &str) -> FilterResult<DeadLetterFilter> {
+        let expr = modkit_odata::parse_filter_string(raw).unwrap().into_expr();
+        DeadLetterFilter::from_odata(&expr)
+    }
+
+    #[test]
+    fn odata_filter_maps_eq_terms() {
+        let filter = odata(
+            "queue eq 'orders' and partition_id eq 3 and payload_type eq 'order.created' \
+             and status eq 'resolved' and id eq 9",
+        )
+        .unwrap();
+        assert_eq!(filter.scope.queue.as_deref(), Some("orders"));
+        assert_eq!(filter.scope.partition_id, Some(3));
+        assert_eq!(filter.scope.payload_type.as_deref(), Some("order.created"));
+        assert_eq!(filter.scope.id, Some(9));
+        assert_eq!(filter.status, Some(DeadLetterStatus::Resolved));
+    }
+
+    #[test]
+    fn odata_filter_defaults_to_pending() {
+        let filter = odata("queue eq 'orders'").unwrap();
+        assert_eq!(filter.status, Some(DeadLetterStatus::Pending));
+    }
+
+    #[test]
+    fn odata_filter_rejects_unsupported_expressions() {
+        for raw in [
+            "queue eq 'a' or queue eq 'b'",
+            "queue ne 'a'",
+            "queue eq 'a' and queue eq 'b'",
+            "status eq 'gone'",
+            "last_error eq 'x'",
+        ] {
+            assert!(odata(raw).is_err(), "{raw}");
+        }
+    }
 }
@@ -51,6 +51,17 @@ pub struct ClaimSql {
     pub select: String,
 }
 
+/// Column values for one enqueued message (body row + incoming row).
+pub struct NewMessage<'a> {
+    pub partition_id: i64,
+    pub payload: Vec<u8>,
+    pub payload_type: &'a str,
+    /// JSON-encoded headers, `None` when the message has none.
+    pub headers: Option<String>,
+    /// Delivery delay in milliseconds, `None` for immediate delivery.
+    pub delay_ms: Option<i64>,
+}
+
 /// SQL for the sequencer's sequence-allocation operation.
 pub enum AllocSql {
     /// `Pg`/`SQLite`: single `UPDATE ... RETURNING` statement.
@@ -65,32 +76,87 @@ pub enum AllocSql {
 // -- Registration queries --
 
 impl Dialect {
+    /// All partitions of a queue, every generation, in `partition` order.
     pub fn register_queue_select(self) -> &'static str {
         match self {
             Self::Postgres | Self::Sqlite => {
-                "SELECT id FROM modkit_outbox_partitions \
+                "SELECT id, partition AS slot, generation FROM modkit_outbox_partitions \
                  WHERE queue = $1 ORDER BY partition ASC"
             }
             Self::MySql => {
-                "SELECT id FROM modkit_outbox_partitions \
+                "SELECT id, `partition` AS slot, generation FROM modkit_outbox_partitions \
                  WHERE queue = ? ORDER BY `partition` ASC"
             }
         }
     }
 
+    /// Insert a partition row: `(queue, partition, generation)`.
     pub fn register_queue_insert(self) -> &'static str {
         match self {
             Self::Postgres => {
-                "INSERT INTO modkit_outbox_partitions (queue, partition) \
-                 VALUES ($1, $2) ON CONFLICT (queue, partition) DO NOTHING"
+                "INSERT INTO modkit_outbox_partitions (queue, partition, generation) \
+                 VALUES ($1, $2, $3) ON CONFLICT (queue, partition) DO NOTHING"
             }
             Self::Sqlite => {
-                "INSERT OR IGNORE INTO modkit_outbox_partitions (queue, partition) \
-                 VALUES ($1, $2)"
+                "INSERT OR IGNORE INTO modkit_outbox_partitions (queue, partition, generation) \
+                 VALUES ($1, $2, $3)"
             }
             Self::MySql => {
-                "INSERT IGNORE INTO modkit_outbox_partitions (queue, `partition`) \
-                 VALUES (?, ?)"
+                "INSERT IGNORE INTO modkit_outbox_partitions (queue, `partition`, generation) \
+                 VALUES (?, ?, ?)"
+            }
+        }
+    }
+
+    /// Newest partition generation of a queue; `NULL` when unregistered.
+    pub fn current_generation(self) -> &'static str {
+        match self {
+            Self::Postgres | Self::Sqlite => {
+                "SELECT MAX(generation) AS generation FROM modkit_outbox_partitions \
+                 WHERE queue = $1"
+            }
+            Self::MySql => {
+                "SELECT MAX(generation) AS generation FROM modkit_outbox_partitions \
+                 WHERE queue = ?"
+            }
+        }
+    }
+
+    /// Lock all partition rows of a queue so concurrent repartitions
+    /// serialize. `None` for `SQLite` (single writer).
+    pub fn lock_queue_partitions(self) -> Option<&'static str> {
+        match self {
+            Self::Postgres => {
+                Some("SELECT id FROM modkit_outbox_partitions WHERE queue = $1 FOR UPDATE")
+            }
+            Self::MySql => {
+                Some("SELECT id FROM modkit_outbox_partitions WHERE queue = ? FOR UPDATE")
+            }
+            Self::Sqlite => None,
+        }
+    }
+
+    /// Partitions of one generation of a queue that still have work: a
+    /// sequenced message not yet processed, or an incoming message. Delayed
+    /// messages count before they are due, as their keys are still in the
+    /// generation.
+    pub fn pending_generation_partitions(self) -> &'static str {
+        match self {
+            Self::Postgres | Self::Sqlite => {
+                "SELECT p.id AS partition_id FROM modkit_outbox_partitions p \
+                 JOIN modkit_outbox_processor pr ON pr.partition_id = p.id \
+                 WHERE p.queue = $1 AND p.generation = $2 \
+                   AND (p.sequence > pr.processed_seq \
+                        OR EXISTS (SELECT 1 FROM modkit_outbox_incoming i \
+                                   WHERE i.partition_id = p.id))"
+            }
+            Self::MySql => {
+                "SELECT p.id AS partition_id FROM modkit_outbox_partitions p \
+                 JOIN modkit_outbox_processor pr ON pr.partition_id = p.id \
+                 WHERE p.queue = ? AND p.generation = ? \
+                   AND (p.sequence > pr.processed_seq \
+                        OR EXISTS (SELECT 1 FROM modkit_outbox_incoming i \
+                                   WHERE i.partition_id = p.id))"
             }
         }
     }
@@ -105,11 +171,11 @@ impl Dialect {
         match self {
             Self::Postgres => Some(
                 "WITH b AS (\
-                   INSERT INTO modkit_outbox_body (payload, payload_type) \
-                   VALUES ($1, $2) RETURNING id\
+                   INSERT INTO modkit_outbox_body (payload, payload_type, headers) \
+                   VALUES ($1, $2, $3) RETURNING id\
                  ) \
-                 INSERT INTO modkit_outbox_incoming (partition_id, body_id) \
-                 SELECT $3, id FROM b RETURNING id",
+                 INSERT INTO modkit_outbox_incoming (partition_id, body_id, deliver_at) \
+                 SELECT $4, id, NOW() + $5 * INTERVAL '1 millisecond' FROM b RETURNING id",
             ),
             // SQLite: writable CTEs require 3.35+; the bundled libsqlite3
             // version may be older, so fall back to two separate INSERTs.
@@ -120,29 +186,48 @@ impl Dialect {
     pub fn insert_body(self) -> &'static str {
         match self {
             Self::Postgres | Self::Sqlite => {
-                "INSERT INTO modkit_outbox_body (payload, payload_type) \
-                 VALUES ($1, $2) RETURNING id"
+                "INSERT INTO modkit_outbox_body (payload, payload_type, headers) \
+                 VALUES ($1, $2, $3) RETURNING id"
             }
             Self::MySql => {
-                "INSERT INTO modkit_outbox_body (payload, payload_type) \
-                 VALUES (?, ?)"
+                "INSERT INTO modkit_outbox_body (payload, payload_type, headers) \
+                 VALUES (?, ?, ?)"
             }
         }
     }
 
+    /// Single incoming INSERT. The third parameter is the delivery delay in
+    /// milliseconds (`NULL` = deliver immediately); `deliver_at` is computed
+    /// from the database clock so all comparisons use the same time source.
     pub fn insert_incoming(self) -> &'static str {
         match self {
-            Self::Postgres | Self::Sqlite => {
-                "INSERT INTO modkit_outbox_incoming (partition_id, body_id) \
-                 VALUES ($1, $2) RETURNING id"
+            Self::Postgres => {
+                "INSERT INTO modkit_outbox_incoming (partition_id, body_id, deliver_at) \
+                 VALUES ($1, $2, NOW() + $3 * INTERVAL '1 millisecond') RETURNING id"
+            }
+            Self::Sqlite => {
+                "INSERT INTO modkit_outbox_incoming (partition_id, body_id, deliver_at) \
+                 VALUES ($1, $2, strftime('%Y-%m-%d %H:%M:%f', 'now', '+' || ($3 / 1000.0) || ' seconds')) \
+                 RETURNING id"
             }
             Self::MySql => {
-                "INSERT INTO modkit_outbox_incoming (partition_id, body_id) \
-                 VALUES (?, ?)"
+                "INSERT INTO modkit_outbox_incoming (partition_id, body_id, deliver_at) \
+                 VALUES (?, ?, TIMESTAMPADD(MICROSECOND, ? * 1000, NOW(6)))"
             }
         }
     }
 
+    /// `deliver_at` expression for a delay (milliseconds) bound at placeholder `idx`.
+    fn deliver_at_expr(self, idx: usize) -> String {
+        match self {
+            Self::Postgres => format!("NOW() + ${idx} * INTERVAL '1 millisecond'"),
+            Self::Sqlite => format!(
+                "strftime('%Y-%m-%d %H:%M:%f', 'now', '+' || (${idx} / 1000.0) || ' seconds')"
+            ),
+            Self::MySql => "TIMESTAMPADD(MICROSECOND, ? * 1000, NOW(6))".to_owned(),
+        }
+    }
+
     fn supports_returning(self) -> bool {
         match self {
             Self::Postgres | Self::Sqlite => true,
@@ -165,28 +250,54 @@ impl Dialect {
     /// for a single multi-row INSERT when `innodb_autoinc_lock_mode` is 0 or 1.
     pub fn build_insert_body_batch(self, count: usize) -> String {
         let mut sql =
-            String::from("INSERT INTO modkit_outbox_body (payload, payload_type) VALUES ");
-        self.append_value_tuples(&mut sql, count, 2);
+            String::from("INSERT INTO modkit_outbox_body (payload, payload_type, headers) VALUES ");
+        self.append_value_tuples(&mut sql, count, 3);
         if self.supports_returning() {
             sql.push_str(" RETURNING id");
         }
         sql
     }
 
+    /// Build a multi-row INSERT for incoming rows: `(partition_id, body_id,
+    /// delay_ms)` per row, with `deliver_at` derived from the delay.
     pub fn build_insert_incoming_batch(self, count: usize) -> String {
-        let mut sql =
-            String::from("INSERT INTO modkit_outbox_incoming (partition_id, body_id) VALUES ");
-        self.append_value_tuples(&mut sql, count, 2);
+        let mut sql = String::from(
+            "INSERT INTO modkit_outbox_incoming (partition_id, body_id, deliver_at) VALUES ",
+        );
+        for i in 0..count {
+            if i > 0 {
+                sql.push_str(", ");
+            }
+            match self {
+                Self::Postgres | Self::Sqlite => {
+                    let base = i * 3;
+                    // Writing to a String is infallible.
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = write!(
+                        sql,
+                        "(${}, ${}, {})",
+                        base + 1,
+                        base + 2,
+                        self.deliver_at_expr(base + 3)
+                    );
+                }
+                Self::MySql => {
+                    #[allow(clippy::let_underscore_must_use)]
+                    let _ = write!(sql, "(?, ?, {})", self.deliver_at_expr(0));
+                }
+            }
+        }
         if self.supports_returning() {
             sql.push_str(" RETURNING id");
         }
         sql
     }
 
-    /// Build `SELECT id, payload, payload_type, created_at FROM modkit_outbox_body WHERE id IN (...)`.
+    /// Build `SELECT id, payload, payload_type, headers, created_at FROM modkit_outbox_body WHERE id IN (...)`.
     pub fn build_read_body_batch(self, count: usize) -> String {
         let mut sql = String::from(
-            "SELECT id, payload, payload_type, created_at FROM modkit_outbox_body WHERE id IN (",
+            "SELECT id, payload, payload_type, headers, created_at \
+             FROM modkit_outbox_body WHERE id IN (",
         );
         self.append_in_placeholders(&mut sql, count);
         sql.push(')');
@@ -250,16 +361,17 @@ impl Dialect {
         self,
         conn: &dyn ConnectionTrait,
         backend: DbBackend,
-        payloads: &[(&[u8], &str)],
+        payloads: &[(&[u8], &str, Option<&str>)],
     ) -> Result<Vec<i64>, DbErr> {
         if payloads.is_empty() {
             return Ok(Vec::new());
         }
         let sql = self.build_insert_body_batch(payloads.len());
-        let mut values: Vec<sea_orm::Value> = Vec::with_capacity(payloads.len() * 2);
-        for &(payload, payload_type) in payloads {
+        let mut values: Vec<sea_orm::Value> = Vec::with_capacity(payloads.len() * 3);
+        for &(payload, payload_type, headers) in payloads {
             values.push(payload.to_vec().into());
             values.push(payload_type.into());
+            values.push(headers.map(str::to_owned).into());
         }
 
         if self.supports_returning() {
@@ -294,16 +406,17 @@ impl Dialect {
         self,
         conn: &dyn ConnectionTrait,
         backend: DbBackend,
-        entries: &[(i64, i64)],
+        entries: &[(i64, i64, Option<i64>)],
     ) -> Result<Vec<i64>, DbErr> {
         if entries.is_empty() {
             return Ok(Vec::new());
         }
         let sql = self.build_insert_incoming_batch(entries.len());
-        let mut values: Vec<sea_orm::Value> = Vec::with_capacity(entries.len() * 2);
-        for &(partition_id, body_id) in entries {
+        let mut values: Vec<sea_orm::Value> = Vec::with_capacity(entries.len() * 3);
+        for &(partition_id, body_id, delay_ms) in entries {
             values.push(partition_id.into());
             values.push(body_id.into());
+            values.push(delay_ms.into());
         }
 
         if self.supports_returning() {
@@ -374,12 +487,13 @@ impl Dialect {
         backend: DbBackend,
         payload: Vec<u8>,
         payload_type: &str,
+        headers: Option<String>,
     ) -> Result<i64, DbErr> {
         self.exec_insert_returning_id(
             conn,
             backend,
             self.insert_body(),
-            vec![payload.into(), payload_type.into()],
+            vec![payload.into(), payload_type.into(), headers.into()],
             "body",
         )
         .await
@@ -392,12 +506,13 @@ impl Dialect {
         backend: DbBackend,
         partition_id: i64,
         body_id: i64,
+        delay_ms: Option<i64>,
     ) -> Result<i64, DbErr> {
         self.exec_insert_returning_id(
             conn,
             backend,
             self.insert_incoming(),
-            vec![partition_id.into(), body_id.into()],
+            vec![partition_id.into(), body_id.into(), delay_ms.into()],
             "incoming",
         )
         .await
@@ -409,25 +524,29 @@ impl Dialect {
         self,
         conn: &dyn ConnectionTrait,
         backend: DbBackend,
-        partition_id: i64,
-        payload: Vec<u8>,
-        payload_type: &str,
+        msg: NewMessage<'_>,
     ) -> Result<i64, DbErr> {
         if let Some(cte) = self.insert_body_and_incoming_cte() {
             self.exec_insert_returning_id(
                 conn,
                 backend,
                 cte,
-                vec![payload.into(), payload_type.into(), partition_id.into()],
+                vec![
+                    msg.payload.into(),
+                    msg.payload_type.into(),
+                    msg.headers.into(),
+                    msg.partition_id.into(),
+                    msg.delay_ms.into(),
+                ],
                 "incoming",
             )
             .await
         } else {
             // MySQL: two separate round-trips (no CTE INSERT support)
             let body_id = self
-                .exec_insert_body(conn, backend, payload, payload_type)
+                .exec_insert_body(conn, backend, msg.payload, msg.payload_type, msg.headers)
                 .await?;
-            self.exec_insert_incoming(conn, backend, partition_id, body_id)
+            self.exec_insert_incoming(conn, backend, msg.partition_id, body_id, msg.delay_ms)
                 .await
         }
     }
@@ -508,6 +627,7 @@ impl Dialect {
                     "SELECT id, body_id \
                      FROM modkit_outbox_incoming \
                      WHERE partition_id = $1 \
+                       AND (deliver_at IS NULL OR deliver_at <= NOW()) \
                      ORDER BY id \
                      LIMIT {batch_size} \
                      FOR UPDATE SKIP LOCKED"
@@ -518,6 +638,8 @@ impl Dialect {
                     "SELECT id, body_id \
                      FROM modkit_outbox_incoming \
                      WHERE partition_id = $1 \
+                       AND (deliver_at IS NULL \
+                            OR deliver_at <= strftime('%Y-%m-%d %H:%M:%f', 'now')) \
                      ORDER BY id \
                      LIMIT {batch_size}"
                 ),
@@ -529,6 +651,7 @@ impl Dialect {
                     "SELECT id, body_id \
                      FROM modkit_outbox_incoming \
                      WHERE partition_id = ? \
+                       AND (deliver_at IS NULL OR deliver_at <= NOW(6)) \
                      ORDER BY id \
                      LIMIT {batch_size} \
                      FOR UPDATE SKIP LOCKED"
@@ -597,6 +720,31 @@ impl Dialect {
         }
     }
 
+    /// Milliseconds until the earliest not-yet-due incoming row of a
+    /// partition, or `NULL` if none are scheduled. Rounded up so a timer
+    /// armed with the result never fires before the row is claimable.
+    pub fn next_due_delay_ms(self) -> &'static str {
+        match self {
+            Self::Postgres => {
+                "SELECT CAST(CEIL(EXTRACT(EPOCH FROM (MIN(deliver_at) - NOW())) * 1000) AS BIGINT) \
+                 FROM modkit_outbox_incoming \
+                 WHERE partition_id = $1 AND deliver_at > NOW()"
+            }
+            Self::Sqlite => {
+                // No CEIL: math functions are optional in SQLite builds.
+                "SELECT CAST((julianday(MIN(deliver_at)) - julianday('now')) * 86400000.0 AS INTEGER) + 1 \
+                 FROM modkit_outbox_incoming \
+                 WHERE partition_id = $1 \
+                   AND deliver_at > strftime('%Y-%m-%d %H:%M:%f', 'now')"
+            }
+            Self::MySql => {
+                "SELECT CAST(CEIL(TIMESTAMPDIFF(MICROSECOND, NOW(6), MIN(deliver_at)) / 1000) AS SIGNED) \
+                 FROM modkit_outbox_incoming \
+                 WHERE partition_id = ? AND deliver_at > NOW(6)"
+            }
+        }
+    }
+
     /// Cold-path discovery: find all partition IDs with pending incoming rows.
     /// Uses the existing `(partition_id, id)` index for an index-only skip scan.
     /// Same SQL for all backends — `DISTINCT` on the leading index column is portable.
@@ -608,6 +756,16 @@ impl Dialect {
             }
         }
     }
+
+    /// Wake listeners on other connections. Parameters: channel, payload.
+    /// Inside a transaction the notification is delivered on commit.
+    /// `None` for `SQLite` and `MySQL`, which rely on polling.
+    pub fn notify_wakeup(self) -> Option<&'static str> {
+        match self {
+            Self::Postgres => Some("SELECT pg_notify($1, $2)"),
+            Self::Sqlite | Self::MySql => None,
+        }
+    }
 }
 
 // -- Processor queries --
@@ -943,6 +1101,242 @@ impl Dialect {
     }
 }
 
+// -- Producer dedup queries --
+
+impl Dialect {
+    /// Delete a dedup key whose retention (milliseconds) has elapsed, so
+    /// the claim that follows can take it over.
+    /// Parameters: `queue`, `dedup_key`, `retention_ms`.
+    pub fn delete_expired_dedup_key(self) -> &'static str {
+        match self {
+            Self::Postgres => {
+                "DELETE FROM modkit_outbox_dedup \
+                 WHERE queue = $1 AND dedup_key = $2 \
+                   AND created_at < NOW() - $3 * INTERVAL '1 millisecond'"
+            }
+            Self::Sqlite => {
+                "DELETE FROM modkit_outbox_dedup \
+                 WHERE queue = $1 AND dedup_key = $2 \
+                   AND created_at < strftime('%Y-%m-%d %H:%M:%f', 'now', '-' || ($3 / 1000.0) || ' seconds')"
+            }
+            Self::MySql => {
+                "DELETE FROM modkit_outbox_dedup \
+                 WHERE queue = ? AND dedup_key = ? \
+                   AND created_at < TIMESTAMPADD(MICROSECOND, ? * -1000, NOW(6))"
+            }
+        }
+    }
+
+    /// Claim a dedup key. Affects one row when claimed, zero when the key
+    /// is already held. Never raises a unique violation, so a duplicate
+    /// does not abort the caller's transaction on `Postgres`.
+    /// Parameters: `queue`, `dedup_key`.
+    pub fn claim_dedup_key(self) -> &'static str {
+        match self {
+            Self::Postgres => {
+                "INSERT INTO modkit_outbox_dedup (queue, dedup_key) \
+                 VALUES ($1, $2) ON CONFLICT (queue, dedup_key) DO NOTHING"
+            }
+            Self::Sqlite => {
+                "INSERT OR IGNORE INTO modkit_outbox_dedup (queue, dedup_key) \
+                 VALUES ($1, $2)"
+            }
+            Self::MySql => {
+                "INSERT IGNORE INTO modkit_outbox_dedup (queue, dedup_key) \
+                 VALUES (?, ?)"
+            }
+        }
+    }
+
+    /// Release a dedup key claimed earlier in a failed batch enqueue.
+    /// Parameters: `queue`, `dedup_key`.
+    pub fn release_dedup_key(self) -> &'static str {
+        match self {
+            Self::Postgres | Self::Sqlite => {
+                "DELETE FROM modkit_outbox_dedup WHERE queue = $1 AND dedup_key = $2"
+            }
+            Self::MySql => "DELETE FROM modkit_outbox_dedup WHERE queue = ? AND dedup_key = ?",
+        }
+    }
+
+    /// Vacuum: delete a bounded chunk of expired dedup keys.
+    /// Parameters: `retention_ms`, limit.
+    pub fn vacuum_expired_dedup(self) -> &'static str {
+        match self {
+            Self::Postgres => {
+                "DELETE FROM modkit_outbox_dedup WHERE ctid IN ( \
+                 SELECT ctid FROM modkit_outbox_dedup \
+                 WHERE created_at < NOW() - $1 * INTERVAL '1 millisecond' LIMIT $2)"
+            }
+            Self::Sqlite => {
+                "DELETE FROM modkit_outbox_dedup WHERE rowid IN ( \
+                 SELECT rowid FROM modkit_outbox_dedup \
+                 WHERE created_at < strftime('%Y-%m-%d %H:%M:%f', 'now', '-' || ($1 / 1000.0) || ' seconds') \
+                 LIMIT $2)"
+            }
+            Self::MySql => {
+                "DELETE FROM modkit_outbox_dedup \
+                 WHERE created_at < TIMESTAMPADD(MICROSECOND, ? * -1000, NOW(6)) LIMIT ?"
+            }
+        }
+    }
+
+    /// Claim a dedup key, first taking over an expired holder.
+    /// Returns `false` if the key is already held within the retention window.
+    pub async fn exec_claim_dedup_key(
+        self,
+        conn: &dyn ConnectionTrait,
+        backend: DbBackend,
+        queue: &str,
+        dedup_key: &str,
+        retention_ms: i64,
+    ) -> Result<bool, DbErr> {
+        conn.execute(Statement::from_sql_and_values(
+            backend,
+            self.delete_expired_dedup_key(),
+            [queue.into(), dedup_key.into(), retention_ms.into()],
+        ))
+        .await?;
+        let result = conn
+            .execute(Statement::from_sql_and_values(
+                backend,
+                self.claim_dedup_key(),
+                [queue.into(), dedup_key.into()],
+            ))
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+// -- Inbox queries --
+
+impl Dialect {
+    /// Record a received message ID. Affects one row for a first delivery,
+    /// zero for a duplicate; never raises a unique violation.
+    /// Parameters: `queue`, `message_id`.
+    pub fn claim_inbox_message(self) -> &'static str {
+        match self {
+            Self::Postgres => {
+                "INSERT INTO modkit_outbox_inbox (queue, message_id) \
+                 VALUES ($1, $2) ON CONFLICT (queue, message_id) DO NOTHING"
+            }
+            Self::Sqlite => {
+                "INSERT OR IGNORE INTO modkit_outbox_inbox (queue, message_id) \
+                 VALUES ($1, $2)"
+            }
+            Self::MySql => {
+                "INSERT IGNORE INTO modkit_outbox_inbox (queue, message_id) \
+                 VALUES (?, ?)"
+            }
+        }
+    }
+
+    /// Stamp an inbox entry as processed (runs in the handler's transaction).
+    /// Parameters: `queue`, `message_id`.
+    pub fn mark_inbox_processed(self) -> &'static str {
+        match self {
+            Self::Postgres => {
+                "UPDATE modkit_outbox_inbox SET processed_at = NOW() \
+                 WHERE queue = $1 AND message_id = $2"
+            }
+            Self::Sqlite => {
+                "UPDATE modkit_outbox_inbox \
+                 SET processed_at = strftime('%Y-%m-%d %H:%M:%f', 'now') \
+                 WHERE queue = $1 AND message_id = $2"
+            }
+            Self::MySql => {
+                "UPDATE modkit_outbox_inbox SET processed_at = NOW(6) \
+                 WHERE queue = ? AND message_id = ?"
+            }
+        }
+    }
+
+    /// Vacuum: delete a bounded chunk of processed (succeeded or
+    /// dead-lettered) inbox entries received before the retention window.
+    /// Unprocessed entries are kept, so a message still being retried keeps
+    /// rejecting redeliveries.
+    /// Parameters: `retention_ms`, limit.
+    pub fn vacuum_expired_inbox(self) -> &'static str {
+        match self {
+            Self::Postgres => {
+                "DELETE FROM modkit_outbox_inbox WHERE ctid IN ( \
+                 SELECT ctid FROM modkit_outbox_inbox \
+                 WHERE received_at < NOW() - $1 * INTERVAL '1 millisecond' \
+                 AND processed_at IS NOT NULL LIMIT $2)"
+            }
+            Self::Sqlite => {
+                "DELETE FROM modkit_outbox_inbox WHERE rowid IN ( \
+                 SELECT rowid FROM modkit_outbox_inbox \
+                 WHERE received_at < strftime('%Y-%m-%d %H:%M:%f', 'now', '-' || ($1 / 1000.0) || ' seconds') \
+                 AND processed_at IS NOT NULL LIMIT $2)"
+            }
+            Self::MySql => {
+                "DELETE FROM modkit_outbox_inbox \
+                 WHERE received_at < TIMESTAMPADD(MICROSECOND, ? * -1000, NOW(6)) \
+                 AND processed_at IS NOT NULL LIMIT ?"
+            }
+        }
+    }
+}
+
+// -- Health queries --
+
+impl Dialect {
+    /// Per-partition backlog and processor state, ordered by queue and
+    /// partition. `lag_ms` is the age of the oldest sequenced but
+    /// unprocessed message by the database clock (NULL when caught up).
+    ///
+    /// With `one_queue`, only the partitions of the queue bound as the single
+    /// parameter.
+    pub fn partition_health(self, one_queue: bool) -> String {
+        let select = match self {
+            Self::Postgres => {
+                "SELECT p.id AS partition_id, p.queue, p.partition AS partition_index, \
+                 p.generation, p.sequence, pr.processed_seq, pr.attempts, pr.last_error, \
+                 (SELECT COUNT(*) FROM modkit_outbox_incoming i \
+                  WHERE i.partition_id = p.id) AS incoming, \
+                 (SELECT (EXTRACT(EPOCH FROM (NOW() - MIN(o.sequenced_at))) * 1000)::BIGINT \
+                  FROM modkit_outbox_outgoing o \
+                  WHERE o.partition_id = p.id AND o.seq > pr.processed_seq) AS lag_ms \
+                 FROM modkit_outbox_partitions p \
+                 JOIN modkit_outbox_processor pr ON pr.partition_id = p.id"
+            }
+            Self::Sqlite => {
+                "SELECT p.id AS partition_id, p.queue, p.partition AS partition_index, \
+                 p.generation, p.sequence, pr.processed_seq, pr.attempts, pr.last_error, \
+                 (SELECT COUNT(*) FROM modkit_outbox_incoming i \
+                  WHERE i.partition_id = p.id) AS incoming, \
+                 (SELECT CAST((julianday('now') - julianday(MIN(o.sequenced_at))) * 86400000 AS INTEGER) \
+                  FROM modkit_outbox_outgoing o \
+                  WHERE o.partition_id = p.id AND o.seq > pr.processed_seq) AS lag_ms \
+                 FROM modkit_outbox_partitions p \
+                 JOIN modkit_outbox_processor pr ON pr.partition_id = p.id"
+            }
+            Self::MySql => {
+                "SELECT p.id AS partition_id, p.queue, p.`partition` AS partition_index, \
+                 p.generation, p.sequence, pr.processed_seq, pr.attempts, pr.last_error, \
+                 (SELECT COUNT(*) FROM modkit_outbox_incoming i \
+                  WHERE i.partition_id = p.id) AS incoming, \
+                 (SELECT TIMESTAMPDIFF(MICROSECOND, MIN(o.sequenced_at), NOW(6)) DIV 1000 \
+                  FROM modkit_outbox_outgoing o \
+                  WHERE o.partition_id = p.id AND o.seq > pr.processed_seq) AS lag_ms \
+                 FROM modkit_outbox_partitions p \
+                 JOIN modkit_outbox_processor pr ON pr.partition_id = p.id"
+            }
+        };
+        let filter = match (one_queue, self) {
+            (false, _) => "",
+            (true, Self::Postgres) => " WHERE p.queue = $1",
+            (true, Self::Sqlite | Self::MySql) => " WHERE p.queue = ?",
+        };
+        let order = match self {
+            Self::MySql => " ORDER BY p.queue, p.`partition`",
+            Self::Postgres | Self::Sqlite => " ORDER BY p.queue, p.partition",
+        };
+        format!("{select}{filter}{order}")
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -959,7 +1353,7 @@ mod tests {
     fn postgres_uses_dollar_placeholders() {
         let d = Dialect::Postgres;
         assert!(d.insert_body().contains("$1"));
-        assert!(d.insert_body().contains("$2"));
+        assert!(d.insert_body().contains("$3"));
         assert!(d.insert_body().contains("RETURNING"));
     }
 
@@ -988,14 +1382,14 @@ mod tests {
     #[test]
     fn batch_body_pg_placeholder_format() {
         let sql = Dialect::Postgres.build_insert_body_batch(3);
-        assert!(sql.contains("($1, $2), ($3, $4), ($5, $6)"));
+        assert!(sql.contains("($1, $2, $3), ($4, $5, $6), ($7, $8, $9)"));
         assert!(sql.ends_with("RETURNING id"));
     }
 
     #[test]
     fn batch_body_mysql_placeholder_format() {
         let sql = Dialect::MySql.build_insert_body_batch(3);
-        assert!(sql.contains("(?, ?), (?, ?), (?, ?)"));
+        assert!(sql.contains("(?, ?, ?), (?, ?, ?), (?, ?, ?)"));
         assert!(!sql.contains("RETURNING"));
     }
 
@@ -1022,6 +1416,50 @@ mod tests {
         assert!(claim.select.contains('?'));
     }
 
+    #[test]
+    fn claim_filters_undue_rows() {
+        for d in [Dialect::Postgres, Dialect::Sqlite, Dialect::MySql] {
+            let claim = d.claim_incoming(100);
+            assert!(claim.select.contains("deliver_at IS NULL OR deliver_at <="));
+        }
+    }
+
+    #[test]
+    fn insert_incoming_binds_delay() {
+        assert!(
+            Dialect::Postgres
+                .insert_incoming()
+                .contains("$3 * INTERVAL")
+        );
+        assert!(Dialect::Sqlite.insert_incoming().contains("($3 / 1000.0)"));
+        assert_eq!(Dialect::MySql.insert_incoming().matches('?').count(), 3);
+        let cte = Dialect::Postgres.insert_body_and_incoming_cte().unwrap();
+        assert!(cte.contains("$5 * INTERVAL"));
+    }
+
+    #[test]
+    fn batch_incoming_placeholder_format() {
+        let pg = Dialect::Postgres.build_insert_incoming_batch(2);
+        assert!(pg.contains(
+            "($1, $2, NOW() + $3 * INTERVAL '1 millisecond'), \
+             ($4, $5, NOW() + $6 * INTERVAL '1 millisecond')"
+        ));
+        assert!(pg.ends_with("RETURNING id"));
+
+        let mysql = Dialect::MySql.build_insert_incoming_batch(2);
+        assert_eq!(mysql.matches('?').count(), 6);
+        assert!(!mysql.contains("RETURNING"));
+    }
+
+    #[test]
+    fn next_due_delay_placeholders() {
+        assert!(Dialect::Postgres.next_due_delay_ms().contains("$1"));
+        assert!(Dialect::Sqlite.next_due_delay_ms().contains("$1"));
+        let mysql = Dialect::MySql.next_due_delay_ms();
+        assert_eq!(mysql.matches('?').count(), 1);
+        assert!(!mysql.contains('$'));
+    }
+
     #[test]
     fn delete_incoming_batch_placeholders() {
         let pg = Dialect::Postgres.delete_incoming_batch(3);
@@ -1051,6 +1489,29 @@ mod tests {
         assert!(d.register_queue_insert().contains("`partition`"));
     }
 
+    #[test]
+    fn lock_queue_partitions_correct() {
+        assert!(Dialect::Postgres.lock_queue_partitions().is_some());
+        assert!(Dialect::MySql.lock_queue_partitions().is_some());
+        assert!(Dialect::Sqlite.lock_queue_partitions().is_none());
+    }
+
+    #[test]
+    fn pending_generation_partitions_counts_future_deliveries() {
+        for d in [Dialect::Postgres, Dialect::Sqlite, Dialect::MySql] {
+            let sql = d.pending_generation_partitions();
+            assert!(!sql.contains("deliver_at"), "{d:?}");
+            assert!(sql.contains("p.sequence > pr.processed_seq"), "{d:?}");
+        }
+    }
+
+    #[test]
+    fn notify_wakeup_postgres_only() {
+        assert!(Dialect::Postgres.notify_wakeup().is_some());
+        assert!(Dialect::Sqlite.notify_wakeup().is_none());
+        assert!(Dialect::MySql.notify_wakeup().is_none());
+    }
+
     // -- Processor dialect tests --
 
     #[test]
@@ -1111,7 +1572,7 @@ mod tests {
     fn build_read_body_batch_placeholders() {
         let pg = Dialect::Postgres.build_read_body_batch(3);
         assert!(pg.contains("$1, $2, $3"));
-        assert!(pg.contains("SELECT id, payload, payload_type, created_at"));
+        assert!(pg.contains("SELECT id, payload, payload_type, headers, created_at"));
 
         let mysql = Dialect::MySql.build_read_body_batch(3);
         assert!(mysql.contains("?, ?, ?"));
@@ -1241,4 +1702,73 @@ mod tests {
         let mysql = Dialect::MySql.vacuum_cleanup();
         assert!(mysql.select_outgoing_chunk.contains('?'));
     }
+
+    #[test]
+    fn dedup_claim_never_raises_conflict() {
+        assert!(
+            Dialect::Postgres
+                .claim_dedup_key()
+                .contains("ON CONFLICT (queue, dedup_key) DO NOTHING")
+        );
+        assert!(
+            Dialect::Sqlite
+                .claim_dedup_key()
+                .contains("INSERT OR IGNORE")
+        );
+        assert!(Dialect::MySql.claim_dedup_key().contains("INSERT IGNORE"));
+    }
+
+    #[test]
+    fn dedup_expiry_placeholders() {
+        let pg = Dialect::Postgres.delete_expired_dedup_key();
+        assert!(pg.contains("$3 * INTERVAL '1 millisecond'"));
+        let sqlite = Dialect::Sqlite.delete_expired_dedup_key();
+        assert!(sqlite.contains("'-' || ($3 / 1000.0)"));
+        let mysql = Dialect::MySql.delete_expired_dedup_key();
+        assert_eq!(mysql.matches('?').count(), 3);
+    }
+
+    #[test]
+    fn vacuum_expired_dedup_is_bounded() {
+        assert!(
+            Dialect::Postgres
+                .vacuum_expired_dedup()
+                .contains("LIMIT $2")
+        );
+        assert!(Dialect::Sqlite.vacuum_expired_dedup().contains("LIMIT $2"));
+        let mysql = Dialect::MySql.vacuum_expired_dedup();
+        assert!(mysql.ends_with("LIMIT ?"));
+        assert_eq!(mysql.matches('?').count(), 2);
+    }
+
+    #[test]
+    fn inbox_claim_never_raises_conflict() {
+        assert!(
+            Dialect::Postgres
+                .claim_inbox_message()
+                .contains("ON CONFLICT (queue, message_id) DO NOTHING")
+        );
+        assert!(
+            Dialect::Sqlite
+                .claim_inbox_message()
+                .contains("INSERT OR IGNORE")
+        );
+        assert!(
+            Dialect::MySql
+                .claim_inbox_message()
+                .contains("INSERT IGNORE")
+        );
+    }
+
+    #[test]
+    fn vacuum_expired_inbox_is_bounded() {
+        assert!(
+            Dialect::Postgres
+                .vacuum_expired_inbox()
+                .contains("LIMIT $2")
+        );
+        let mysql = Dialect::MySql.vacuum_expired_inbox();
+        assert!(mysql.ends_with("LIMIT ?"));
+        assert_eq!(mysql.matches('?').count(), 2);
+    }
 }
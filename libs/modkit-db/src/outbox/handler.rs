@@ -1,8 +1,10 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use sea_orm::ConnectionTrait;
+use tracing::Instrument;
 
 use super::batch::Batch;
+use super::headers::MessageHeaders;
 
 /// A message read from the outbox for handler processing.
 ///
@@ -14,12 +16,35 @@ pub struct OutboxMessage {
     pub seq: i64,
     pub payload: Vec<u8>,
     pub payload_type: String,
+    /// Headers supplied at enqueue, including the producer's `traceparent`.
+    pub headers: MessageHeaders,
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// How many times this message has been retried (0 on first attempt).
     /// The handler uses this to decide when to give up and return Reject.
     pub attempts: i16,
 }
 
+impl OutboxMessage {
+    /// Span for handling this message, parented to the trace context that
+    /// was current when it was enqueued.
+    ///
+    /// Per-message handlers already run inside it; batch handlers can use it
+    /// to instrument their own per-message work.
+    #[must_use]
+    pub fn handler_span(&self) -> tracing::Span {
+        let span = tracing::info_span!(
+            "outbox.handle",
+            partition_id = self.partition_id,
+            seq = self.seq,
+            payload_type = %self.payload_type,
+            attempts = self.attempts,
+            trace_id = tracing::field::Empty,
+        );
+        self.headers.set_span_parent(&span);
+        span
+    }
+}
+
 /// The result of a handler invocation.
 #[derive(Debug, Clone)]
 pub enum HandlerResult {
@@ -88,7 +113,11 @@ pub trait LeasedMessageHandler: Send + Sync {
 impl<H: LeasedMessageHandler> LeasedHandler for H {
     async fn handle(&self, batch: &mut Batch<'_>) -> HandlerResult {
         while let Some(msg) = batch.next_msg() {
-            match LeasedMessageHandler::handle(self, msg).await {
+            let span = msg.handler_span();
+            match LeasedMessageHandler::handle(self, msg)
+                .instrument(span)
+                .await
+            {
                 MessageResult::Ok => batch.ack(),
                 MessageResult::Retry => {
                     return HandlerResult::Retry {
@@ -169,7 +198,11 @@ impl<H: TransactionalMessageHandler> TransactionalHandler for PerMessageAdapter<
     async fn handle(&self, txn: &dyn ConnectionTrait, msgs: &[OutboxMessage]) -> HandlerResult {
         self.processed.store(0, Ordering::Release);
         for msg in msgs {
-            let result = self.handler.handle(txn, msg).await;
+            let result = self
+                .handler
+                .handle(txn, msg)
+                .instrument(msg.handler_span())
+                .await;
             if !matches!(result, HandlerResult::Success) {
                 return result;
             }
@@ -195,6 +228,7 @@ mod tests {
             seq,
             payload: vec![],
             payload_type: "test".into(),
+            headers: MessageHeaders::default(),
             created_at: chrono::Utc::now(),
             attempts: 0,
         }
@@ -0,0 +1,312 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use tracing::Span;
+
+use super::types::OutboxError;
+
+/// W3C Trace Context header name.
+pub const TRACEPARENT: &str = "traceparent";
+
+/// Maximum header key length.
+const MAX_HEADER_KEY_LEN: usize = 256;
+
+/// Maximum encoded size of all headers of one message (8 KiB).
+const MAX_HEADERS_SIZE: usize = 8 * 1024;
+
+/// Key/value headers carried with an outbox message.
+///
+/// Persisted alongside the message body and handed back to the handler on
+/// [`OutboxMessage::headers`](super::OutboxMessage::headers). Use them for
+/// tenant, correlation, or causation identifiers that must survive the hop
+/// through the outbox.
+///
+/// On enqueue the outbox adds the W3C [`traceparent`](TRACEPARENT) of the
+/// current span unless the caller already set one (requires the `otel`
+/// feature). Per-message handlers run inside a span whose parent is
+/// restored from it.
+///
+/// Keys are 1-256 chars of `[a-zA-Z0-9._-]`; values are printable ASCII.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MessageHeaders(BTreeMap<String, String>);
+
+impl MessageHeaders {
+    /// Create an empty header map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a header, returning the previous value for the key.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        self.0.insert(key.into(), value.into())
+    }
+
+    /// Builder-style [`insert`](Self::insert).
+    #[must_use]
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    /// Look up a header value.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    /// Remove a header, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.0.remove(key)
+    }
+
+    /// The W3C `traceparent` header, if present.
+    #[must_use]
+    pub fn traceparent(&self) -> Option<&str> {
+        self.get(TRACEPARENT)
+    }
+
+    /// Number of headers.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no headers.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate headers in key order.
+    pub fn iter(&self) -> std::collections::btree_map::Iter<'_, String, String> {
+        self.0.iter()
+    }
+
+    /// Validate, add the current trace context, and encode for storage.
+    /// Returns `None` when there is nothing to store.
+    pub(crate) fn encode_for_enqueue(&self) -> Result<Option<String>, OutboxError> {
+        validate(self)?;
+        let mut headers = self.clone();
+        if headers.traceparent().is_none() {
+            imp::inject_current_span(&mut headers);
+        }
+        if headers.is_empty() {
+            return Ok(None);
+        }
+        let encoded = serde_json::to_string(&headers)
+            .map_err(|e| OutboxError::InvalidHeader(format!("encoding failed: {e}")))?;
+        if encoded.len() > MAX_HEADERS_SIZE {
+            return Err(OutboxError::InvalidHeader(format!(
+                "encoded headers are {} bytes, maximum is {MAX_HEADERS_SIZE}",
+                encoded.len()
+            )));
+        }
+        Ok(Some(encoded))
+    }
+
+    /// Decode the stored column. Malformed data yields empty headers — a
+    /// corrupt header column must not make the message undeliverable.
+    pub(crate) fn decode(stored: Option<&str>) -> Self {
+        let Some(raw) = stored else {
+            return Self::default();
+        };
+        serde_json::from_str(raw).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "outbox: ignoring malformed message headers");
+            Self::default()
+        })
+    }
+
+    /// Make the trace context carried in these headers the parent of `span`.
+    pub(crate) fn set_span_parent(&self, span: &Span) {
+        imp::set_parent(span, self);
+    }
+}
+
+impl<'a> IntoIterator for &'a MessageHeaders {
+    type Item = (&'a String, &'a String);
+    type IntoIter = std::collections::btree_map::Iter<'a, String, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for MessageHeaders {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+fn validate(headers: &MessageHeaders) -> Result<(), OutboxError> {
+    for (key, value) in headers {
+        let key_ok = !key.is_empty()
+            && key.len() <= MAX_HEADER_KEY_LEN
+            && key
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'.' || b == b'_' || b == b'-');
+        if !key_ok {
+            return Err(OutboxError::InvalidHeader(format!("invalid key '{key}'")));
+        }
+        if !value.bytes().all(|b| (0x20..=0x7E).contains(&b)) {
+            return Err(OutboxError::InvalidHeader(format!(
+                "value of '{key}' must be printable ASCII"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Parse the trace ID from a W3C traceparent (`00-{trace_id}-{span_id}-{flags}`).
+fn parse_trace_id(traceparent: &str) -> Option<&str> {
+    let mut parts = traceparent.split('-');
+    match (parts.next(), parts.next()) {
+        (Some("00"), Some(trace_id)) if trace_id.len() == 32 => Some(trace_id),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "otel")]
+mod imp {
+    use opentelemetry::propagation::{Extractor, Injector};
+    use opentelemetry::{Context, global};
+    use tracing::Span;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    use super::{MessageHeaders, parse_trace_id};
+
+    impl Injector for MessageHeaders {
+        fn set(&mut self, key: &str, value: String) {
+            self.insert(key, value);
+        }
+    }
+
+    impl Extractor for MessageHeaders {
+        fn get(&self, key: &str) -> Option<&str> {
+            MessageHeaders::get(self, key)
+        }
+
+        fn keys(&self) -> Vec<&str> {
+            self.0.keys().map(String::as_str).collect()
+        }
+    }
+
+    /// Inject the current span's context via the global propagator.
+    pub fn inject_current_span(headers: &mut MessageHeaders) {
+        let cx = Span::current().context();
+        global::get_text_map_propagator(|propagator| propagator.inject_context(&cx, headers));
+    }
+
+    /// Set the span parent from the carried trace context.
+    pub fn set_parent(span: &Span, headers: &MessageHeaders) {
+        if headers.traceparent().is_none() {
+            return;
+        }
+        let parent_cx: Context =
+            global::get_text_map_propagator(|propagator| propagator.extract(headers));
+        _ = span.set_parent(parent_cx);
+        if let Some(trace_id) = headers.traceparent().and_then(parse_trace_id) {
+            span.record("trace_id", trace_id);
+        }
+    }
+}
+
+#[cfg(not(feature = "otel"))]
+mod imp {
+    use tracing::Span;
+
+    use super::{MessageHeaders, parse_trace_id};
+
+    /// No-op: OpenTelemetry is disabled.
+    pub fn inject_current_span(_headers: &mut MessageHeaders) {}
+
+    /// Without OTEL, only record the trace ID for log correlation.
+    pub fn set_parent(span: &Span, headers: &MessageHeaders) {
+        if let Some(trace_id) = headers.traceparent().and_then(parse_trace_id) {
+            span.record("trace_id", trace_id);
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    const TP: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn insert_get_remove() {
+        let mut h = MessageHeaders::new().with("tenant-id", "t1");
+        assert_eq!(h.get("tenant-id"), Some("t1"));
+        assert_eq!(h.insert("tenant-id", "t2"), Some("t1".to_owned()));
+        assert_eq!(h.remove("tenant-id"), Some("t2".to_owned()));
+        assert!(h.is_empty());
+    }
+
+    #[test]
+    fn encode_decode_roundtrip() {
+        let h = MessageHeaders::new()
+            .with(TRACEPARENT, TP)
+            .with("correlation-id", "abc");
+        let encoded = h.encode_for_enqueue().unwrap().unwrap();
+        assert_eq!(MessageHeaders::decode(Some(&encoded)), h);
+    }
+
+    #[test]
+    fn encode_keeps_explicit_traceparent() {
+        let h = MessageHeaders::new().with(TRACEPARENT, TP);
+        let encoded = h.encode_for_enqueue().unwrap().unwrap();
+        assert_eq!(
+            MessageHeaders::decode(Some(&encoded)).traceparent(),
+            Some(TP)
+        );
+    }
+
+    #[test]
+    fn decode_missing_or_malformed_is_empty() {
+        assert!(MessageHeaders::decode(None).is_empty());
+        assert!(MessageHeaders::decode(Some("not json")).is_empty());
+    }
+
+    #[test]
+    fn encode_rejects_invalid_key() {
+        for key in ["", "has space", "\u{fc}nicode"] {
+            let err = MessageHeaders::new()
+                .with(key, "v")
+                .encode_for_enqueue()
+                .unwrap_err();
+            assert!(matches!(err, OutboxError::InvalidHeader(_)), "{key:?}");
+        }
+    }
+
+    #[test]
+    fn encode_rejects_non_printable_value() {
+        let err = MessageHeaders::new()
+            .with("k", "line\nbreak")
+            .encode_for_enqueue()
+            .unwrap_err();
+        assert!(matches!(err, OutboxError::InvalidHeader(_)));
+    }
+
+    #[test]
+    fn encode_rejects_oversized_headers() {
+        let headers: MessageHeaders = (0..100)
+            .map(|i| (format!("key-{i}"), "x".repeat(100)))
+            .collect();
+        let err = headers.encode_for_enqueue().unwrap_err();
+        assert!(matches!(err, OutboxError::InvalidHeader(_)));
+    }
+
+    #[test]
+    fn parse_trace_id_valid_and_invalid() {
+        assert_eq!(parse_trace_id(TP), Some("4bf92f3577b34da6a3ce929d0e0e4736"));
+        assert_eq!(parse_trace_id("01-abc-def-00"), None);
+        assert_eq!(parse_trace_id("garbage"), None);
+    }
+}
@@ -0,0 +1,216 @@
+//! Queue health snapshots for operators.
+
+use sea_orm::{ConnectionTrait, FromQueryResult, Statement};
+
+use super::dialect::Dialect;
+use super::types::OutboxError;
+use crate::secure::SeaOrmRunner;
+
+/// Backlog and processor state of one partition.
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct PartitionHealth {
+    pub partition_id: i64,
+    pub queue: String,
+    /// Index within the partition's generation.
+    pub partition_index: i16,
+    /// Partition generation; each repartition of the queue adds one.
+    pub generation: i16,
+    /// Last sequence number assigned to the partition.
+    pub sequence: i64,
+    /// Last sequence number acknowledged by the processor.
+    pub processed_seq: i64,
+    /// Failed attempts on the message at the head of the partition.
+    pub attempts: i16,
+    pub last_error: Option<String>,
+    /// Messages not yet sequenced, including delayed ones.
+    pub incoming: i64,
+    /// Age in milliseconds of the oldest sequenced but unprocessed message,
+    /// measured by the database clock. `None` when the partition is caught up.
+    pub lag_ms: Option<i64>,
+}
+
+impl PartitionHealth {
+    /// Sequenced messages waiting for the processor.
+    #[must_use]
+    pub fn backlog(&self) -> i64 {
+        self.sequence - self.processed_seq
+    }
+}
+
+/// Health of a queue, aggregated over its partitions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueHealth {
+    pub queue: String,
+    /// Current partition generation.
+    pub generation: i16,
+    /// Partitions of the current generation.
+    pub partitions: usize,
+    /// Partitions of earlier generations that still hold messages after a
+    /// repartition.
+    pub draining_partitions: usize,
+    /// Sum of [`PartitionHealth::backlog`].
+    pub backlog: i64,
+    /// Sum of [`PartitionHealth::incoming`].
+    pub incoming: i64,
+    /// Largest [`PartitionHealth::lag_ms`].
+    pub max_lag_ms: Option<i64>,
+    /// Partitions whose head message has failed at least once.
+    pub retrying_partitions: usize,
+    /// Dead letters of the queue in `pending` state.
+    pub pending_dead_letters: u64,
+}
+
+pub(super) async fn partition_health(
+    runner: SeaOrmRunner<'_>,
+    queue: Option<&str>,
+) -> Result<Vec<PartitionHealth>, OutboxError> {
+    let backend = match &runner {
+        SeaOrmRunner::Conn(c) => c.get_database_backend(),
+        SeaOrmRunner::Tx(t) => t.get_database_backend(),
+    };
+    let sql = Dialect::from(backend).partition_health(queue.is_some());
+    let stmt = match queue {
+        Some(queue) => Statement::from_sql_and_values(backend, sql, [queue.into()]),
+        None => Statement::from_string(backend, sql),
+    };
+    let mut rows = match &runner {
+        SeaOrmRunner::Conn(c) => PartitionHealth::find_by_statement(stmt).all(*c).await?,
+        SeaOrmRunner::Tx(t) => PartitionHealth::find_by_statement(stmt).all(*t).await?,
+    };
+    index_within_generation(&mut rows);
+    Ok(rows)
+}
+
+/// Turn the `partition` column into indexes within each generation. Rows
+/// are ordered by queue and `partition`; a generation's values are
+/// contiguous.
+fn index_within_generation(rows: &mut [PartitionHealth]) {
+    for generation in rows.chunk_by_mut(|a, b| a.queue == b.queue && a.generation == b.generation) {
+        let Some(base) = generation.first().map(|p| p.partition_index) else {
+            continue;
+        };
+        for p in generation {
+            p.partition_index -= base;
+        }
+    }
+}
+
+/// Aggregate partitions (ordered by queue) into per-queue health.
+/// `pending_dead_letters` is left at zero.
+pub(super) fn aggregate(partitions: &[PartitionHealth]) -> Vec<QueueHealth> {
+    let mut queues: Vec<QueueHealth> = Vec::new();
+    for group in partitions.chunk_by(|a, b| a.queue == b.queue) {
+        let Some(first) = group.first() else {
+            continue;
+        };
+        let generation = group.iter().map(|p| p.generation).max().unwrap_or(0);
+        let mut q = QueueHealth {
+            queue: first.queue.clone(),
+            generation,
+            partitions: 0,
+            draining_partitions: 0,
+            backlog: 0,
+            incoming: 0,
+            max_lag_ms: None,
+            retrying_partitions: 0,
+            pending_dead_letters: 0,
+        };
+        for p in group {
+            if p.generation == generation {
+                q.partitions += 1;
+            } else if p.backlog() > 0 || p.incoming > 0 {
+                q.draining_partitions += 1;
+            }
+            q.backlog += p.backlog();
+            q.incoming += p.incoming;
+            q.max_lag_ms = q.max_lag_ms.max(p.lag_ms);
+            if p.attempts > 0 {
+                q.retrying_partitions += 1;
+            }
+        }
+        queues.push(q);
+    }
+    queues
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn partition(
+        queue: &str,
+        sequence: i64,
+        processed_seq: i64,
+        lag_ms: Option<i64>,
+    ) -> PartitionHealth {
+        PartitionHealth {
+            partition_id: 1,
+            queue: queue.to_owned(),
+            partition_index: 0,
+            generation: 0,
+            sequence,
+            processed_seq,
+            attempts: 0,
+            last_error: None,
+            incoming: 1,
+            lag_ms,
+        }
+    }
+
+    #[test]
+    fn aggregate_groups_consecutive_partitions() {
+        let mut retrying = partition("a", 5, 2, Some(40));
+        retrying.attempts = 3;
+        let queues = aggregate(&[
+            partition("a", 10, 10, None),
+            retrying,
+            partition("b", 1, 0, Some(7)),
+        ]);
+
+        assert_eq!(queues.len(), 2);
+        assert_eq!(queues[0].queue, "a");
+        assert_eq!(queues[0].partitions, 2);
+        assert_eq!(queues[0].backlog, 3);
+        assert_eq!(queues[0].incoming, 2);
+        assert_eq!(queues[0].max_lag_ms, Some(40));
+        assert_eq!(queues[0].retrying_partitions, 1);
+        assert_eq!(queues[1].queue, "b");
+        assert_eq!(queues[1].max_lag_ms, Some(7));
+    }
+
+    #[test]
+    fn aggregate_counts_current_generation_and_draining_partitions() {
+        let mut drained = partition("a", 4, 4, None);
+        drained.incoming = 0;
+        let mut current = vec![partition("a", 0, 0, None), partition("a", 0, 0, None)];
+        for p in &mut current {
+            p.generation = 1;
+            p.incoming = 0;
+        }
+        let mut rows = vec![drained, partition("a", 6, 5, Some(3))];
+        rows.extend(current);
+
+        let queues = aggregate(&rows);
+        assert_eq!(queues.len(), 1);
+        assert_eq!(queues[0].generation, 1);
+        assert_eq!(queues[0].partitions, 2);
+        assert_eq!(queues[0].draining_partitions, 1);
+        assert_eq!(queues[0].backlog, 1);
+    }
+
+    #[test]
+    fn index_within_generation_rebases_each_generation() {
+        let mut rows: Vec<PartitionHealth> = (0..6)
+            .map(|slot| {
+                let mut p = partition("a", 0, 0, None);
+                p.partition_index = slot;
+                p.generation = i16::from(slot >= 2);
+                p
+            })
+            .collect();
+        index_within_generation(&mut rows);
+        let indexes: Vec<i16> = rows.iter().map(|p| p.partition_index).collect();
+        assert_eq!(indexes, vec![0, 1, 0, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,163 @@
+use sea_orm::{ConnectionTrait, Statement};
+
+use super::dialect::Dialect;
+use super::handler::{HandlerResult, OutboxMessage, TransactionalMessageHandler};
+use super::headers::MessageHeaders;
+use super::manager::OutboxBuilder;
+use super::types::{EnqueueMessage, OutboxMessageId, Partitions};
+
+/// Header carrying the external message ID of a received message.
+pub const INBOX_MESSAGE_ID: &str = "inbox-message-id";
+
+/// A message received from an external system (webhook, peer service).
+///
+/// Passed to [`Outbox::receive()`](super::Outbox::receive).
+pub struct InboxMessage<'a> {
+    /// Target partition index. Messages that must be processed in order
+    /// (e.g. events of one aggregate) should share a partition.
+    pub partition: u32,
+    /// Sender-assigned ID, unique per inbox queue. 1-255 printable ASCII chars.
+    pub message_id: &'a str,
+    /// Message payload bytes.
+    pub payload: Vec<u8>,
+    /// Type tag for the payload.
+    pub payload_type: &'a str,
+    /// Headers handed to the handler. [`INBOX_MESSAGE_ID`] is added.
+    pub headers: MessageHeaders,
+}
+
+impl<'a> InboxMessage<'a> {
+    pub(crate) fn into_enqueue(self) -> EnqueueMessage<'a> {
+        EnqueueMessage {
+            partition: self.partition,
+            payload: self.payload,
+            payload_type: self.payload_type,
+            deliver_after: None,
+            headers: self.headers.with(INBOX_MESSAGE_ID, self.message_id),
+            dedup_key: None,
+        }
+    }
+}
+
+/// Outcome of [`Outbox::receive()`](super::Outbox::receive).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Received {
+    /// First delivery; the message was enqueued for processing.
+    Accepted(OutboxMessageId),
+    /// The message ID was already received; nothing was enqueued.
+    Duplicate,
+}
+
+impl Received {
+    /// Whether the message had been received before.
+    #[must_use]
+    pub fn is_duplicate(self) -> bool {
+        matches!(self, Self::Duplicate)
+    }
+}
+
+/// Builder for registering an inbox queue.
+///
+/// Obtained via [`OutboxBuilder::inbox`]. An inbox is an outbox queue fed by
+/// [`Outbox::receive()`](super::Outbox::receive) and processed in
+/// transactional mode only: the handler's writes, the cursor advance, and
+/// the inbox entry's `processed_at` commit together. Ordering, retries, and
+/// dead letters work exactly as for any other queue; a rejected message's
+/// entry is stamped along with its dead letter.
+#[must_use = "an inbox builder does nothing until a handler is registered via .transactional()"]
+pub struct InboxQueueBuilder {
+    builder: OutboxBuilder,
+    name: String,
+    partitions: Partitions,
+}
+
+impl InboxQueueBuilder {
+    pub(crate) fn new(builder: OutboxBuilder, name: String, partitions: Partitions) -> Self {
+        Self {
+            builder,
+            name,
+            partitions,
+        }
+    }
+
+    /// Register a single-message transactional handler.
+    #[must_use]
+    pub fn transactional(
+        self,
+        handler: impl TransactionalMessageHandler + 'static,
+    ) -> OutboxBuilder {
+        let handler = InboxHandler::new(&self.name, handler);
+        self.builder
+            .queue(&self.name, self.partitions)
+            .transactional(handler)
+    }
+}
+
+/// Stamps the inbox entry as processed in the handler's transaction once
+/// the inner handler is done with the message: it succeeded, or rejected it
+/// into a dead letter. Vacuum collects processed entries only, so a message
+/// still being retried keeps rejecting redeliveries.
+pub struct InboxHandler<H> {
+    queue: String,
+    inner: H,
+}
+
+impl<H> InboxHandler<H> {
+    pub fn new(queue: &str, inner: H) -> Self {
+        Self {
+            queue: queue.to_owned(),
+            inner,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<H: TransactionalMessageHandler> TransactionalMessageHandler for InboxHandler<H> {
+    async fn handle(&self, txn: &dyn ConnectionTrait, msg: &OutboxMessage) -> HandlerResult {
+        let result = self.inner.handle(txn, msg).await;
+        if matches!(result, HandlerResult::Retry { .. }) {
+            return result;
+        }
+        // Messages enqueued directly (not via `receive`) have no entry.
+        let Some(message_id) = msg.headers.get(INBOX_MESSAGE_ID) else {
+            return result;
+        };
+        let backend = txn.get_database_backend();
+        let stamp = txn
+            .execute(Statement::from_sql_and_values(
+                backend,
+                Dialect::from(backend).mark_inbox_processed(),
+                [self.queue.as_str().into(), message_id.into()],
+            ))
+            .await;
+        match stamp {
+            Ok(_) => result,
+            // Retry rather than commit the handler's writes without the stamp.
+            Err(e) => HandlerResult::Retry {
+                reason: format!("inbox: failed to mark message processed: {e}"),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_enqueue_adds_message_id_header() {
+        let msg = InboxMessage {
+            partition: 2,
+            message_id: "evt-1",
+            payload: b"x".to_vec(),
+            payload_type: "application/json",
+            headers: MessageHeaders::new().with("source", "billing"),
+        };
+        let enqueue = msg.into_enqueue();
+        assert_eq!(enqueue.partition, 2);
+        assert_eq!(enqueue.headers.get(INBOX_MESSAGE_ID), Some("evt-1"));
+        assert_eq!(enqueue.headers.get("source"), Some("billing"));
+        assert!(enqueue.dedup_key.is_none());
+    }
+}
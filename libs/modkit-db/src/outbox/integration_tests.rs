@@ -26,12 +26,15 @@ use super::handler::{
     HandlerResult, LeasedHandler, LeasedMessageHandler, MessageResult, OutboxMessage,
     PerMessageAdapter, TransactionalHandler, TransactionalMessageHandler,
 };
+use super::inbox::InboxHandler;
+use super::metrics::OutboxMetrics;
 use super::prioritizer::SharedPrioritizer;
 use super::strategy::{LeasedStrategy, ProcessContext, ProcessingStrategy, TransactionalStrategy};
 use super::taskward::{Directive, WorkerAction};
 use super::types::{EnqueueMessage, LeaseConfig, OutboxConfig, SequencerConfig, WorkerTuning};
+use super::workers::processor::PartitionProcessor;
 use super::workers::sequencer::Sequencer;
-use super::{Outbox, OutboxError, Partitions};
+use super::{InboxMessage, MessageHeaders, Outbox, OutboxError, Partitions, Received, TRACEPARENT};
 use crate::migration_runner::run_migrations_for_testing;
 use crate::outbox::OutboxMessageId;
 use crate::{ConnectOpts, Db, connect_db};
@@ -206,6 +209,57 @@ async fn expire_lease(db: &Db, partition_id: i64) {
     .expect("expire_lease");
 }
 
+/// Move every delayed incoming row's due time into the past.
+async fn make_delayed_rows_due(db: &Db) {
+    let conn = db.sea_internal();
+    conn.execute(Statement::from_string(
+        DbBackend::Sqlite,
+        "UPDATE modkit_outbox_incoming \
+         SET deliver_at = strftime('%Y-%m-%d %H:%M:%f', 'now', '-1 seconds') \
+         WHERE deliver_at IS NOT NULL",
+    ))
+    .await
+    .expect("make_delayed_rows_due");
+}
+
+/// Age every dedup key by `secs` seconds.
+async fn age_dedup_keys(db: &Db, secs: i64) {
+    let conn = db.sea_internal();
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Sqlite,
+        "UPDATE modkit_outbox_dedup \
+         SET created_at = strftime('%Y-%m-%d %H:%M:%f', 'now', '-' || $1 || ' seconds')",
+        [secs.into()],
+    ))
+    .await
+    .expect("age_dedup_keys");
+}
+
+/// Stamp the inbox entry of `message_id` as processed, as a handler does.
+async fn mark_inbox_processed(db: &Db, queue: &str, message_id: &str) {
+    let conn = db.sea_internal();
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Sqlite,
+        Dialect::Sqlite.mark_inbox_processed(),
+        [queue.into(), message_id.into()],
+    ))
+    .await
+    .expect("mark_inbox_processed");
+}
+
+/// Age every inbox entry by `secs` seconds.
+async fn age_inbox_entries(db: &Db, secs: i64) {
+    let conn = db.sea_internal();
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Sqlite,
+        "UPDATE modkit_outbox_inbox \
+         SET received_at = strftime('%Y-%m-%d %H:%M:%f', 'now', '-' || $1 || ' seconds')",
+        [secs.into()],
+    ))
+    .await
+    .expect("age_inbox_entries");
+}
+
 // ======================================================================
 // Layer C — Observations (read state only)
 // ======================================================================
@@ -227,6 +281,26 @@ async fn count_rows(db: &Db, table: &str) -> i64 {
     .cnt
 }
 
+async fn read_inbox_processed(db: &Db, message_id: &str) -> bool {
+    #[derive(Debug, FromQueryResult)]
+    struct Row {
+        processed: i64,
+    }
+    let conn = db.sea_internal();
+    Row::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Sqlite,
+        "SELECT processed_at IS NOT NULL AS processed \
+         FROM modkit_outbox_inbox WHERE message_id = $1",
+        [message_id.into()],
+    ))
+    .one(&conn)
+    .await
+    .expect("inbox query")
+    .expect("inbox row")
+    .processed
+        != 0
+}
+
 async fn read_processor_state(db: &Db, partition_id: i64) -> ProcessorSnapshot {
     #[derive(Debug, FromQueryResult)]
     struct Row {
@@ -423,6 +497,18 @@ impl LeasedMessageHandler for AttemptsRecorder {
     }
 }
 
+struct HeadersRecorder {
+    seen: Arc<Mutex<Vec<MessageHeaders>>>,
+}
+
+#[async_trait::async_trait]
+impl LeasedMessageHandler for HeadersRecorder {
+    async fn handle(&self, msg: &OutboxMessage) -> MessageResult {
+        self.seen.lock().unwrap().push(msg.headers.clone());
+        MessageResult::Ok
+    }
+}
+
 struct CountingTxHandler {
     count: Arc<AtomicU32>,
 }
@@ -636,6 +722,9 @@ async fn enqueue_batch_creates_n_items() {
             partition: 0,
             payload: format!("msg-{i}").into_bytes(),
             payload_type: "text/plain",
+            deliver_after: None,
+            headers: MessageHeaders::default(),
+            dedup_key: None,
         })
         .collect();
     let conn = db.conn().unwrap();
@@ -657,21 +746,33 @@ async fn enqueue_batch_mixed_partitions() {
             partition: 0,
             payload: b"a".to_vec(),
             payload_type: "text/plain",
+            deliver_after: None,
+            headers: MessageHeaders::default(),
+            dedup_key: None,
         },
         EnqueueMessage {
             partition: 1,
             payload: b"b".to_vec(),
             payload_type: "text/plain",
+            deliver_after: None,
+            headers: MessageHeaders::default(),
+            dedup_key: None,
         },
         EnqueueMessage {
             partition: 0,
             payload: b"c".to_vec(),
             payload_type: "text/plain",
+            deliver_after: None,
+            headers: MessageHeaders::default(),
+            dedup_key: None,
         },
         EnqueueMessage {
             partition: 1,
             payload: b"d".to_vec(),
             payload_type: "text/plain",
+            deliver_after: None,
+            headers: MessageHeaders::default(),
+            dedup_key: None,
         },
     ];
     let conn = db.conn().unwrap();
@@ -692,11 +793,17 @@ async fn enqueue_batch_one_invalid_rejects_entire_batch() {
             partition: 0,
             payload: b"ok".to_vec(),
             payload_type: "text/plain",
+            deliver_after: None,
+            headers: MessageHeaders::default(),
+            dedup_key: None,
         },
         EnqueueMessage {
             partition: 0,
             payload: oversized,
             payload_type: "text/plain",
+            deliver_after: None,
+            headers: MessageHeaders::default(),
+            dedup_key: None,
         },
     ];
     let conn = db.conn().unwrap();
@@ -731,6 +838,9 @@ async fn enqueue_batch_over_chunk_size_works() {
             partition: 0,
             payload: format!("msg-{i}").into_bytes(),
             payload_type: "text/plain",
+            deliver_after: None,
+            headers: MessageHeaders::default(),
+            dedup_key: None,
         })
         .collect();
     let conn = db.conn().unwrap();
@@ -757,6 +867,170 @@ async fn enqueue_oversized_payload_rejected() {
     assert!(matches!(err, OutboxError::PayloadTooLarge { .. }));
 }
 
+#[tokio::test]
+async fn enqueue_message_rejects_invalid_header() {
+    let db = setup_db("ch2_invalid_header").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    let conn = db.conn().unwrap();
+    let msg = EnqueueMessage {
+        partition: 0,
+        payload: b"x".to_vec(),
+        payload_type: "text/plain",
+        deliver_after: None,
+        headers: MessageHeaders::new().with("bad key", "v"),
+        dedup_key: None,
+    };
+    let err = t.outbox.enqueue_message(&conn, "q", msg).await.unwrap_err();
+    assert!(matches!(err, OutboxError::InvalidHeader(_)));
+    assert_eq!(count_rows(&db, "modkit_outbox_body").await, 0);
+}
+
+fn dedup_msg(key: &str) -> EnqueueMessage<'_> {
+    EnqueueMessage {
+        partition: 0,
+        payload: b"x".to_vec(),
+        payload_type: "text/plain",
+        deliver_after: None,
+        headers: MessageHeaders::default(),
+        dedup_key: Some(key),
+    }
+}
+
+#[tokio::test]
+async fn enqueue_dedup_key_rejects_repeat() {
+    let db = setup_db("ch2_dedup_repeat").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+    t.outbox.register_queue(&db, "other", 1).await.unwrap();
+
+    let conn = db.conn().unwrap();
+    t.outbox
+        .enqueue_message(&conn, "q", dedup_msg("order-1"))
+        .await
+        .unwrap();
+    let err = t
+        .outbox
+        .enqueue_message(&conn, "q", dedup_msg("order-1"))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, OutboxError::AlreadyEnqueued { queue, dedup_key } if queue == "q" && dedup_key == "order-1")
+    );
+    assert_eq!(count_rows(&db, "modkit_outbox_body").await, 1);
+
+    // Keys are scoped per queue.
+    t.outbox
+        .enqueue_message(&conn, "other", dedup_msg("order-1"))
+        .await
+        .unwrap();
+    assert_eq!(count_rows(&db, "modkit_outbox_body").await, 2);
+}
+
+#[tokio::test]
+async fn enqueue_dedup_key_rollback_frees_key() {
+    let db = setup_db("ch2_dedup_rollback").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    let (db, result) = t
+        .outbox
+        .transaction(db, |tx| {
+            let outbox = Arc::clone(&t.outbox);
+            Box::pin(async move {
+                outbox
+                    .enqueue_message(tx, "q", dedup_msg("k"))
+                    .await
+                    .map_err(|e| anyhow::anyhow!("{e}"))?;
+                Err::<(), _>(anyhow::anyhow!("rollback"))
+            })
+        })
+        .await;
+    assert!(result.is_err());
+
+    let conn = db.conn().unwrap();
+    t.outbox
+        .enqueue_message(&conn, "q", dedup_msg("k"))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn enqueue_dedup_key_reusable_after_retention() {
+    let db = setup_db("ch2_dedup_retention").await;
+    let t = make_test_outbox(OutboxConfig {
+        dedup_retention: Duration::from_mins(1),
+        ..Default::default()
+    })
+    .await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    let conn = db.conn().unwrap();
+    t.outbox
+        .enqueue_message(&conn, "q", dedup_msg("k"))
+        .await
+        .unwrap();
+    age_dedup_keys(&db, 120).await;
+    t.outbox
+        .enqueue_message(&conn, "q", dedup_msg("k"))
+        .await
+        .unwrap();
+    assert_eq!(count_rows(&db, "modkit_outbox_body").await, 2);
+    assert_eq!(count_rows(&db, "modkit_outbox_dedup").await, 1);
+}
+
+#[tokio::test]
+async fn enqueue_batch_with_repeated_dedup_key_inserts_nothing() {
+    let db = setup_db("ch2_dedup_batch").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    let conn = db.conn().unwrap();
+    t.outbox
+        .enqueue_message(&conn, "q", dedup_msg("b"))
+        .await
+        .unwrap();
+
+    let err = t
+        .outbox
+        .enqueue_batch(&conn, "q", &[dedup_msg("a"), dedup_msg("b")])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OutboxError::AlreadyEnqueued { .. }));
+    assert_eq!(count_rows(&db, "modkit_outbox_body").await, 1);
+
+    // "a" was released when the batch failed.
+    t.outbox
+        .enqueue_message(&conn, "q", dedup_msg("a"))
+        .await
+        .unwrap();
+
+    // A key repeated within one batch is rejected too.
+    let err = t
+        .outbox
+        .enqueue_batch(&conn, "q", &[dedup_msg("c"), dedup_msg("c")])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OutboxError::AlreadyEnqueued { .. }));
+    assert_eq!(count_rows(&db, "modkit_outbox_dedup").await, 2);
+}
+
+#[tokio::test]
+async fn enqueue_rejects_invalid_dedup_key() {
+    let db = setup_db("ch2_dedup_invalid").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    let conn = db.conn().unwrap();
+    let err = t
+        .outbox
+        .enqueue_message(&conn, "q", dedup_msg(""))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OutboxError::InvalidDedupKey(_)));
+}
+
 #[tokio::test]
 async fn enqueue_unregistered_queue_rejected() {
     let db = setup_db("ch2_unreg").await;
@@ -1087,98 +1361,289 @@ async fn sequencer_skips_empty_partitions() {
     assert_eq!(out1.len(), 1, "partition 1 should have 1 outgoing");
 }
 
-// ======================================================================
-// Chapter 4: Transactional Processing
-// ======================================================================
-
-async fn run_transactional(
-    db: &Db,
-    partition_id: i64,
-    handler: impl TransactionalHandler + 'static,
-    msg_batch_size: u32,
-) -> Option<super::strategy::ProcessResult> {
-    let conn = db.sea_internal();
-    let backend = conn.get_database_backend();
-    let dialect = Dialect::from(backend);
-    drop(conn);
-
-    let strategy = TransactionalStrategy::new(Box::new(handler));
-    let ctx = ProcessContext {
-        db,
-        backend,
-        dialect,
-        partition_id,
-    };
-    strategy.process(&ctx, msg_batch_size).await.unwrap()
-}
-
 #[tokio::test]
-async fn transactional_success_advances_cursor() {
-    let db = setup_db("ch4_tx_success").await;
+async fn sequencer_leaves_delayed_message_until_due() {
+    let db = setup_db("ch3_delayed_undue").await;
     let t = make_default_test_outbox().await;
     t.outbox.register_queue(&db, "q", 1).await.unwrap();
     let pid = t.outbox.all_partition_ids()[0];
 
-    enqueue_and_sequence(&t, &db, "q", 0, &["a", "b", "c"]).await;
+    let conn = db.conn().unwrap();
+    t.outbox
+        .enqueue_after(
+            &conn,
+            "q",
+            0,
+            b"later".to_vec(),
+            "text/plain",
+            Duration::from_hours(1),
+        )
+        .await
+        .unwrap();
+    enqueue_msgs(&t.outbox, &db, "q", 0, &["now"]).await;
 
-    let count = Arc::new(AtomicU32::new(0));
-    run_transactional(
-        &db,
-        pid,
-        CountingTxHandler {
-            count: count.clone(),
-        },
-        3,
-    )
-    .await;
+    run_sequencer_once(&t, &db).await;
 
-    assert_eq!(count.load(Ordering::Relaxed), 3);
-    let snap = read_processor_state(&db, pid).await;
-    assert_eq!(snap.processed_seq, 3);
-    assert_eq!(snap.attempts, 0);
+    // Only the immediate message is sequenced; the delayed one stays put
+    // and does not block the partition.
+    assert_eq!(count_rows(&db, "modkit_outbox_incoming").await, 1);
+    assert_eq!(read_outgoing(&db, pid).await.len(), 1);
+
+    // The partition is parked until the delayed row is due.
+    assert!(t.prioritizer.take().is_none());
+    let next = t.prioritizer.next_ready_in().expect("partition deferred");
+    assert!(next > Duration::from_mins(59), "deferred for {next:?}");
 }
 
 #[tokio::test]
-async fn transactional_retry_increments_attempts() {
-    let db = setup_db("ch4_tx_retry").await;
+async fn sequencer_orders_delayed_message_by_due_time() {
+    let db = setup_db("ch3_delayed_order").await;
     let t = make_default_test_outbox().await;
     t.outbox.register_queue(&db, "q", 1).await.unwrap();
     let pid = t.outbox.all_partition_ids()[0];
 
-    enqueue_and_sequence(&t, &db, "q", 0, &["msg"]).await;
+    let conn = db.conn().unwrap();
+    t.outbox
+        .enqueue_after(
+            &conn,
+            "q",
+            0,
+            b"later".to_vec(),
+            "text/plain",
+            Duration::from_hours(1),
+        )
+        .await
+        .unwrap();
+    enqueue_msgs(&t.outbox, &db, "q", 0, &["now"]).await;
+    run_sequencer_once(&t, &db).await;
 
-    run_transactional(&db, pid, AlwaysRetryTxHandler, 10).await;
+    make_delayed_rows_due(&db).await;
+    // A fresh dirty signal pulls the deferred partition forward.
+    t.prioritizer.push_dirty(pid);
+    run_sequencer_once(&t, &db).await;
 
-    let snap = read_processor_state(&db, pid).await;
-    assert_eq!(snap.processed_seq, 0, "cursor not advanced");
-    assert_eq!(snap.attempts, 1);
-    assert_eq!(snap.last_error.as_deref(), Some("transient tx failure"));
+    assert_eq!(count_rows(&db, "modkit_outbox_incoming").await, 0);
+    let outgoing = read_outgoing(&db, pid).await;
+    let seqs: Vec<i64> = outgoing.iter().map(|r| r.seq).collect();
+    assert_eq!(seqs, vec![1, 2]);
+    // The delayed message was enqueued first (lower body id) but is
+    // sequenced after the immediate one.
+    assert!(outgoing[0].body_id > outgoing[1].body_id);
+    assert!(t.prioritizer.next_ready_in().is_none());
 }
 
 #[tokio::test]
-async fn transactional_reject_creates_dead_letter_and_advances() {
-    let db = setup_db("ch4_tx_reject").await;
+async fn sequencer_wakes_for_delayed_message_without_push() {
+    let db = setup_db("ch3_delayed_wake").await;
     let t = make_default_test_outbox().await;
     t.outbox.register_queue(&db, "q", 1).await.unwrap();
     let pid = t.outbox.all_partition_ids()[0];
 
-    enqueue_and_sequence(&t, &db, "q", 0, &["poison"]).await;
-
-    run_transactional(&db, pid, AlwaysRejectTxHandler, 10).await;
+    let conn = db.conn().unwrap();
+    t.outbox
+        .enqueue_after(
+            &conn,
+            "q",
+            0,
+            b"soon".to_vec(),
+            "text/plain",
+            Duration::from_millis(100),
+        )
+        .await
+        .unwrap();
 
-    let snap = read_processor_state(&db, pid).await;
-    assert_eq!(snap.processed_seq, 1, "cursor advanced past rejected msg");
+    let mut seq = make_sequencer(&t, SequencerConfig::default(), &db);
+    let cancel = CancellationToken::new();
+    let first = seq.execute(&cancel).await.unwrap();
+    assert_eq!(first.payload().rows_claimed, 0);
+    assert!(
+        matches!(first, Directive::Sleep(d, _) if d <= Duration::from_millis(150)),
+        "sequencer should sleep until the message is due"
+    );
 
-    let dls = read_dead_letters(&db).await;
-    assert_eq!(dls.len(), 1);
-    assert!(dls[0].id > 0);
-    assert_eq!(dls[0].partition_id, pid);
-    assert_eq!(dls[0].seq, 1);
-    assert_eq!(dls[0].last_error.as_deref(), Some("permanently bad tx"));
-    assert_eq!(dls[0].payload, b"poison");
-    assert_eq!(dls[0].payload_type, "text/plain");
-    assert_eq!(dls[0].attempts, 0);
-    assert_eq!(dls[0].status, "pending");
+    // No producer push: honoring the Sleep directive is enough for the
+    // deferred partition to be taken again once due.
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(2);
+    while read_outgoing(&db, pid).await.is_empty() {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "delayed message was never sequenced"
+        );
+        if let Directive::Sleep(d, _) = seq.execute(&cancel).await.unwrap() {
+            tokio::time::sleep(d).await;
+        }
+    }
+    assert_eq!(count_rows(&db, "modkit_outbox_incoming").await, 0);
+}
+
+#[tokio::test]
+async fn enqueue_batch_with_deliver_after() {
+    let db = setup_db("ch3_delayed_batch").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+    let pid = t.outbox.all_partition_ids()[0];
+
+    let items = vec![
+        EnqueueMessage {
+            partition: 0,
+            payload: b"later".to_vec(),
+            payload_type: "text/plain",
+            deliver_after: Some(Duration::from_hours(1)),
+            headers: MessageHeaders::default(),
+            dedup_key: None,
+        },
+        EnqueueMessage {
+            partition: 0,
+            payload: b"now".to_vec(),
+            payload_type: "text/plain",
+            deliver_after: None,
+            headers: MessageHeaders::default(),
+            dedup_key: None,
+        },
+    ];
+    let conn = db.conn().unwrap();
+    t.outbox.enqueue_batch(&conn, "q", &items).await.unwrap();
+    run_sequencer_once(&t, &db).await;
+
+    assert_eq!(count_rows(&db, "modkit_outbox_incoming").await, 1);
+    assert_eq!(read_outgoing(&db, pid).await.len(), 1);
+}
+
+#[tokio::test]
+async fn enqueue_at_in_the_past_delivers_immediately() {
+    let db = setup_db("ch3_delayed_past").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    let conn = db.conn().unwrap();
+    let past = chrono::Utc::now() - chrono::TimeDelta::minutes(5);
+    t.outbox
+        .enqueue_at(&conn, "q", 0, b"x".to_vec(), "text/plain", past)
+        .await
+        .unwrap();
+    run_sequencer_once(&t, &db).await;
+
+    assert_eq!(count_rows(&db, "modkit_outbox_incoming").await, 0);
+    assert_eq!(count_rows(&db, "modkit_outbox_outgoing").await, 1);
+}
+
+#[tokio::test]
+async fn enqueue_after_rejects_excessive_delay() {
+    let db = setup_db("ch3_delayed_too_long").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    let conn = db.conn().unwrap();
+    let err = t
+        .outbox
+        .enqueue_after(
+            &conn,
+            "q",
+            0,
+            b"x".to_vec(),
+            "text/plain",
+            Duration::from_hours(366 * 24),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OutboxError::DeliveryDelayTooLarge { .. }));
+    assert_eq!(count_rows(&db, "modkit_outbox_body").await, 0);
+}
+
+// ======================================================================
+// Chapter 4: Transactional Processing
+// ======================================================================
+
+async fn run_transactional(
+    db: &Db,
+    partition_id: i64,
+    handler: impl TransactionalHandler + 'static,
+    msg_batch_size: u32,
+) -> Option<super::strategy::ProcessResult> {
+    let conn = db.sea_internal();
+    let backend = conn.get_database_backend();
+    let dialect = Dialect::from(backend);
+    drop(conn);
+
+    let strategy = TransactionalStrategy::new(Box::new(handler));
+    let metrics = OutboxMetrics::new().partition("q", partition_id);
+    let ctx = ProcessContext {
+        db,
+        backend,
+        dialect,
+        partition_id,
+        metrics: &metrics,
+    };
+    strategy.process(&ctx, msg_batch_size).await.unwrap()
+}
+
+#[tokio::test]
+async fn transactional_success_advances_cursor() {
+    let db = setup_db("ch4_tx_success").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+    let pid = t.outbox.all_partition_ids()[0];
+
+    enqueue_and_sequence(&t, &db, "q", 0, &["a", "b", "c"]).await;
+
+    let count = Arc::new(AtomicU32::new(0));
+    run_transactional(
+        &db,
+        pid,
+        CountingTxHandler {
+            count: count.clone(),
+        },
+        3,
+    )
+    .await;
+
+    assert_eq!(count.load(Ordering::Relaxed), 3);
+    let snap = read_processor_state(&db, pid).await;
+    assert_eq!(snap.processed_seq, 3);
+    assert_eq!(snap.attempts, 0);
+}
+
+#[tokio::test]
+async fn transactional_retry_increments_attempts() {
+    let db = setup_db("ch4_tx_retry").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+    let pid = t.outbox.all_partition_ids()[0];
+
+    enqueue_and_sequence(&t, &db, "q", 0, &["msg"]).await;
+
+    run_transactional(&db, pid, AlwaysRetryTxHandler, 10).await;
+
+    let snap = read_processor_state(&db, pid).await;
+    assert_eq!(snap.processed_seq, 0, "cursor not advanced");
+    assert_eq!(snap.attempts, 1);
+    assert_eq!(snap.last_error.as_deref(), Some("transient tx failure"));
+}
+
+#[tokio::test]
+async fn transactional_reject_creates_dead_letter_and_advances() {
+    let db = setup_db("ch4_tx_reject").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+    let pid = t.outbox.all_partition_ids()[0];
+
+    enqueue_and_sequence(&t, &db, "q", 0, &["poison"]).await;
+
+    run_transactional(&db, pid, AlwaysRejectTxHandler, 10).await;
+
+    let snap = read_processor_state(&db, pid).await;
+    assert_eq!(snap.processed_seq, 1, "cursor advanced past rejected msg");
+
+    let dls = read_dead_letters(&db).await;
+    assert_eq!(dls.len(), 1);
+    assert!(dls[0].id > 0);
+    assert_eq!(dls[0].partition_id, pid);
+    assert_eq!(dls[0].seq, 1);
+    assert_eq!(dls[0].last_error.as_deref(), Some("permanently bad tx"));
+    assert_eq!(dls[0].payload, b"poison");
+    assert_eq!(dls[0].payload_type, "text/plain");
+    assert_eq!(dls[0].attempts, 0);
+    assert_eq!(dls[0].status, "pending");
 }
 
 #[tokio::test]
@@ -1230,11 +1695,13 @@ async fn run_leased(
             headroom: Duration::from_secs(2),
         },
     );
+    let metrics = OutboxMetrics::new().partition("q", partition_id);
     let ctx = ProcessContext {
         db,
         backend,
         dialect,
         partition_id,
+        metrics: &metrics,
     };
     strategy.process(&ctx, msg_batch_size).await.unwrap()
 }
@@ -1422,6 +1889,75 @@ async fn decoupled_each_message_adapter_processes_individually() {
     assert_eq!(count.load(Ordering::Relaxed), 3);
 }
 
+#[tokio::test]
+async fn decoupled_handler_receives_enqueued_headers() {
+    let db = setup_db("ch5_dc_headers").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+    let pid = t.outbox.all_partition_ids()[0];
+
+    let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+    let single = MessageHeaders::new()
+        .with(TRACEPARENT, traceparent)
+        .with("tenant-id", "t1");
+    let batched = MessageHeaders::new().with("correlation-id", "c2");
+
+    let conn = db.conn().unwrap();
+    t.outbox
+        .enqueue_message(
+            &conn,
+            "q",
+            EnqueueMessage {
+                partition: 0,
+                payload: b"a".to_vec(),
+                payload_type: "text/plain",
+                deliver_after: None,
+                headers: single.clone(),
+                dedup_key: None,
+            },
+        )
+        .await
+        .unwrap();
+    t.outbox
+        .enqueue_batch(
+            &conn,
+            "q",
+            &[
+                EnqueueMessage {
+                    partition: 0,
+                    payload: b"b".to_vec(),
+                    payload_type: "text/plain",
+                    deliver_after: None,
+                    headers: batched.clone(),
+                    dedup_key: None,
+                },
+                EnqueueMessage {
+                    partition: 0,
+                    payload: b"c".to_vec(),
+                    payload_type: "text/plain",
+                    deliver_after: None,
+                    headers: MessageHeaders::default(),
+                    dedup_key: None,
+                },
+            ],
+        )
+        .await
+        .unwrap();
+    run_sequencer_once(&t, &db).await;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let handler = HeadersRecorder { seen: seen.clone() };
+    run_leased(&db, pid, handler, Duration::from_secs(30), 3).await;
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 3);
+    assert_eq!(seen[0], single);
+    assert_eq!(seen[0].traceparent(), Some(traceparent));
+    assert_eq!(seen[1], batched);
+    // No span is active in the test, so nothing is injected.
+    assert!(seen[2].is_empty());
+}
+
 // ======================================================================
 // Chapter 6: Crash Detection & Recovery
 // ======================================================================
@@ -2209,6 +2745,34 @@ async fn dead_letter_filter_with_limit() {
     assert_eq!(items.len(), 2);
 }
 
+#[tokio::test]
+async fn dead_letter_list_pages_by_id() {
+    let db = setup_db("ch9_dl_pages").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    create_dead_letters(&t, &db, "q", 0, &["a", "b", "c", "d", "e"]).await;
+
+    let mut seen = Vec::new();
+    let mut filter = DeadLetterFilter::default().limit(2);
+    loop {
+        let page = t
+            .outbox
+            .dead_letter_list(&db.conn().unwrap(), &filter)
+            .await
+            .unwrap();
+        let Some(last) = page.last() else { break };
+        filter = DeadLetterFilter::default().limit(2).before(last.id);
+        seen.extend(page.iter().map(|m| m.id));
+    }
+
+    assert_eq!(seen.len(), 5);
+    assert!(
+        seen.windows(2).all(|w| w[0] > w[1]),
+        "newest first: {seen:?}"
+    );
+}
+
 // ======================================================================
 // Chapter 10: Builder API
 // ======================================================================
@@ -3440,6 +4004,100 @@ async fn vacuum_concurrent_workers_safe() {
     assert_eq!(read_vacuum_counter(&db, pid).await, 0);
 }
 
+#[tokio::test]
+async fn vacuum_deletes_expired_dedup_keys() {
+    use super::workers::vacuum::VacuumTask;
+
+    let db = setup_db("ch16_vac_dedup").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    let conn = db.conn().unwrap();
+    for key in ["a", "b", "c"] {
+        t.outbox
+            .enqueue_message(&conn, "q", dedup_msg(key))
+            .await
+            .unwrap();
+    }
+    age_dedup_keys(&db, 120).await;
+    t.outbox
+        .enqueue_message(&conn, "q", dedup_msg("fresh"))
+        .await
+        .unwrap();
+
+    // Batch size 2 forces more than one chunk.
+    let cancel = CancellationToken::new();
+    let mut vac = VacuumTask::new(db.clone(), 2).with_dedup_retention(Duration::from_mins(1));
+    let directive = vac.execute(&cancel).await.unwrap();
+
+    assert_eq!(directive.payload().dedup_keys_deleted, 3);
+    assert_eq!(count_rows(&db, "modkit_outbox_dedup").await, 1);
+}
+
+#[tokio::test]
+async fn vacuum_deletes_expired_inbox_entries() {
+    use super::workers::vacuum::VacuumTask;
+
+    let db = setup_db("ch16_vac_inbox").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    let conn = db.conn().unwrap();
+    for id in ["a", "b", "c"] {
+        t.outbox.receive(&conn, "q", inbox_msg(id)).await.unwrap();
+        mark_inbox_processed(&db, "q", id).await;
+    }
+    age_inbox_entries(&db, 120).await;
+    t.outbox
+        .receive(&conn, "q", inbox_msg("fresh"))
+        .await
+        .unwrap();
+    mark_inbox_processed(&db, "q", "fresh").await;
+
+    let cancel = CancellationToken::new();
+    let mut vac = VacuumTask::new(db.clone(), 2).with_inbox_retention(Duration::from_mins(1));
+    let directive = vac.execute(&cancel).await.unwrap();
+
+    assert_eq!(directive.payload().inbox_entries_deleted, 3);
+    assert_eq!(count_rows(&db, "modkit_outbox_inbox").await, 1);
+}
+
+#[tokio::test]
+async fn vacuum_keeps_unprocessed_expired_inbox_entries() {
+    use super::workers::vacuum::VacuumTask;
+
+    let db = setup_db("ch16_vac_inbox_unprocessed").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    let conn = db.conn().unwrap();
+    t.outbox
+        .receive(&conn, "q", inbox_msg("done"))
+        .await
+        .unwrap();
+    mark_inbox_processed(&db, "q", "done").await;
+    t.outbox
+        .receive(&conn, "q", inbox_msg("retrying"))
+        .await
+        .unwrap();
+    age_inbox_entries(&db, 120).await;
+
+    let cancel = CancellationToken::new();
+    let mut vac = VacuumTask::new(db.clone(), 10).with_inbox_retention(Duration::from_mins(1));
+    let directive = vac.execute(&cancel).await.unwrap();
+
+    assert_eq!(directive.payload().inbox_entries_deleted, 1);
+    assert!(!read_inbox_processed(&db, "retrying").await);
+
+    // The surviving entry still rejects a redelivery.
+    let again = t
+        .outbox
+        .receive(&conn, "q", inbox_msg("retrying"))
+        .await
+        .unwrap();
+    assert!(matches!(again, Received::Duplicate), "got {again:?}");
+}
+
 // ======================================================================
 // Chapter 17: Priority Semaphore
 // ======================================================================
@@ -3930,3 +4588,748 @@ async fn batch_transactional_respects_configured_batch_size() {
     drop(db);
     handle.stop().await;
 }
+
+// ======================================================================
+// Chapter 23: Inbox
+// ======================================================================
+
+fn inbox_msg(message_id: &str) -> InboxMessage<'_> {
+    InboxMessage {
+        partition: 0,
+        message_id,
+        payload: b"x".to_vec(),
+        payload_type: "text/plain",
+        headers: MessageHeaders::default(),
+    }
+}
+
+#[tokio::test]
+async fn receive_rejects_duplicate_message_id() {
+    let db = setup_db("ch23_inbox_duplicate").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+    t.outbox.register_queue(&db, "other", 1).await.unwrap();
+
+    let conn = db.conn().unwrap();
+    let first = t
+        .outbox
+        .receive(&conn, "q", inbox_msg("evt-1"))
+        .await
+        .unwrap();
+    assert!(matches!(first, Received::Accepted(_)));
+    let second = t
+        .outbox
+        .receive(&conn, "q", inbox_msg("evt-1"))
+        .await
+        .unwrap();
+    assert_eq!(second, Received::Duplicate);
+    assert_eq!(count_rows(&db, "modkit_outbox_body").await, 1);
+
+    // Message IDs are scoped per queue.
+    let other = t
+        .outbox
+        .receive(&conn, "other", inbox_msg("evt-1"))
+        .await
+        .unwrap();
+    assert!(!other.is_duplicate());
+    assert_eq!(count_rows(&db, "modkit_outbox_body").await, 2);
+}
+
+#[tokio::test]
+async fn receive_rollback_frees_message_id() {
+    let db = setup_db("ch23_inbox_rollback").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    let (db, result) = t
+        .outbox
+        .transaction(db, |tx| {
+            let outbox = Arc::clone(&t.outbox);
+            Box::pin(async move {
+                outbox
+                    .receive(tx, "q", inbox_msg("evt-1"))
+                    .await
+                    .map_err(|e| anyhow::anyhow!("{e}"))?;
+                Err::<(), _>(anyhow::anyhow!("rollback"))
+            })
+        })
+        .await;
+    assert!(result.is_err());
+    assert_eq!(count_rows(&db, "modkit_outbox_inbox").await, 0);
+
+    let conn = db.conn().unwrap();
+    let received = t
+        .outbox
+        .receive(&conn, "q", inbox_msg("evt-1"))
+        .await
+        .unwrap();
+    assert!(!received.is_duplicate());
+}
+
+#[tokio::test]
+async fn receive_rejects_invalid_message_id() {
+    let db = setup_db("ch23_inbox_invalid").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    let conn = db.conn().unwrap();
+    let err = t
+        .outbox
+        .receive(&conn, "q", inbox_msg(""))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OutboxError::InvalidMessageId(_)));
+    assert_eq!(count_rows(&db, "modkit_outbox_inbox").await, 0);
+}
+
+#[tokio::test]
+async fn inbox_handler_stamps_processed_on_success() {
+    let db = setup_db("ch23_inbox_stamp").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+    let pid = t.outbox.all_partition_ids()[0];
+
+    let conn = db.conn().unwrap();
+    t.outbox
+        .receive(&conn, "q", inbox_msg("evt-1"))
+        .await
+        .unwrap();
+    run_sequencer_once(&t, &db).await;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let handler = PerMessageAdapter::new(InboxHandler::new(
+        "q",
+        TxPartialFailureHandler {
+            seen_seqs: seen.clone(),
+            poison_seq: 0, // never
+            reject: false,
+        },
+    ));
+    run_transactional(&db, pid, handler, 10).await;
+
+    assert_eq!(*seen.lock().unwrap(), vec![1]);
+    assert!(read_inbox_processed(&db, "evt-1").await);
+    assert_eq!(read_processor_state(&db, pid).await.processed_seq, 1);
+}
+
+#[tokio::test]
+async fn inbox_handler_leaves_entry_unprocessed_on_retry() {
+    let db = setup_db("ch23_inbox_retry").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+    let pid = t.outbox.all_partition_ids()[0];
+
+    let conn = db.conn().unwrap();
+    t.outbox
+        .receive(&conn, "q", inbox_msg("evt-1"))
+        .await
+        .unwrap();
+    run_sequencer_once(&t, &db).await;
+
+    let handler = PerMessageAdapter::new(InboxHandler::new(
+        "q",
+        TxPartialFailureHandler {
+            seen_seqs: Arc::new(Mutex::new(Vec::new())),
+            poison_seq: 1,
+            reject: false,
+        },
+    ));
+    run_transactional(&db, pid, handler, 10).await;
+
+    assert!(!read_inbox_processed(&db, "evt-1").await);
+    assert_eq!(read_processor_state(&db, pid).await.processed_seq, 0);
+}
+
+#[tokio::test]
+async fn vacuum_collects_inbox_entries_of_rejected_messages() {
+    use super::workers::vacuum::VacuumTask;
+
+    let db = setup_db("ch23_inbox_reject_vacuum").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+    let pid = t.outbox.all_partition_ids()[0];
+
+    let conn = db.conn().unwrap();
+    t.outbox
+        .receive(&conn, "q", inbox_msg("evt-1"))
+        .await
+        .unwrap();
+    run_sequencer_once(&t, &db).await;
+
+    let handler = PerMessageAdapter::new(InboxHandler::new(
+        "q",
+        TxPartialFailureHandler {
+            seen_seqs: Arc::new(Mutex::new(Vec::new())),
+            poison_seq: 1,
+            reject: true,
+        },
+    ));
+    run_transactional(&db, pid, handler, 10).await;
+
+    // Dead-lettered counts as done: the entry is stamped and expires.
+    assert_eq!(count_rows(&db, "modkit_outbox_dead_letters").await, 1);
+    assert!(read_inbox_processed(&db, "evt-1").await);
+
+    age_inbox_entries(&db, 120).await;
+    let cancel = CancellationToken::new();
+    let mut vac = VacuumTask::new(db.clone(), 10).with_inbox_retention(Duration::from_mins(1));
+    let directive = vac.execute(&cancel).await.unwrap();
+
+    assert_eq!(directive.payload().inbox_entries_deleted, 1);
+    assert_eq!(count_rows(&db, "modkit_outbox_inbox").await, 0);
+}
+
+// ======================================================================
+// Chapter 24: Health & Admin
+// ======================================================================
+
+#[tokio::test]
+async fn partition_health_reports_backlog_and_incoming() {
+    let db = setup_db("ch24_partition_health").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 2).await.unwrap();
+    let ids = t.outbox.all_partition_ids();
+
+    enqueue_and_sequence(&t, &db, "q", 0, &["a", "b"]).await;
+    enqueue_msgs(&t.outbox, &db, "q", 1, &["c"]).await;
+
+    let health = t
+        .outbox
+        .partition_health(&db.conn().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(health.len(), 2);
+
+    let p0 = &health[0];
+    assert_eq!(p0.partition_id, ids[0]);
+    assert_eq!(p0.partition_index, 0);
+    assert_eq!(p0.backlog(), 2);
+    assert_eq!(p0.incoming, 0);
+    assert!(p0.lag_ms.is_some_and(|ms| ms >= 0));
+
+    let p1 = &health[1];
+    assert_eq!(p1.partition_index, 1);
+    assert_eq!(p1.backlog(), 0);
+    assert_eq!(p1.incoming, 1);
+    assert_eq!(p1.lag_ms, None);
+}
+
+#[tokio::test]
+async fn queue_partition_health_reads_only_that_queue() {
+    let db = setup_db("ch24_queue_partition_health").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "a", 1).await.unwrap();
+    t.outbox.register_queue(&db, "b", 2).await.unwrap();
+    let conn = db.conn().unwrap();
+
+    let health = t.outbox.queue_partition_health(&conn, "b").await.unwrap();
+    assert_eq!(health.len(), 2);
+    assert!(health.iter().all(|p| p.queue == "b"));
+    assert_eq!(health[1].partition_index, 1);
+
+    let missing = t.outbox.queue_partition_health(&conn, "c").await.unwrap();
+    assert!(missing.is_empty());
+}
+
+#[tokio::test]
+async fn queue_health_counts_pending_dead_letters() {
+    let db = setup_db("ch24_queue_health").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "a", 1).await.unwrap();
+    t.outbox.register_queue(&db, "b", 2).await.unwrap();
+
+    create_dead_letters(&t, &db, "a", 0, &["x", "y"]).await;
+
+    let health = t.outbox.queue_health(&db.conn().unwrap()).await.unwrap();
+    assert_eq!(health.len(), 2);
+    assert_eq!(health[0].queue, "a");
+    assert_eq!(health[0].partitions, 1);
+    assert_eq!(health[0].backlog, 0);
+    assert_eq!(health[0].pending_dead_letters, 2);
+    assert_eq!(health[1].queue, "b");
+    assert_eq!(health[1].partitions, 2);
+    assert_eq!(health[1].pending_dead_letters, 0);
+}
+
+#[tokio::test]
+async fn dead_letter_requeue_enqueues_and_resolves() {
+    let db = setup_db("ch24_dl_requeue").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+
+    create_dead_letters(&t, &db, "q", 0, &["msg"]).await;
+    assert_eq!(count_rows(&db, "modkit_outbox_incoming").await, 0);
+
+    let (db, result) = t
+        .outbox
+        .transaction(db, |tx| {
+            let outbox = Arc::clone(&t.outbox);
+            Box::pin(async move {
+                outbox
+                    .dead_letter_requeue(tx, &DeadLetterScope::default(), Duration::from_mins(1))
+                    .await
+                    .map_err(|e| anyhow::anyhow!("{e}"))
+            })
+        })
+        .await;
+    assert_eq!(result.unwrap(), 1);
+
+    assert_eq!(count_rows(&db, "modkit_outbox_incoming").await, 1);
+    let dls = read_dead_letters(&db).await;
+    assert_eq!(dls.len(), 1);
+    assert_eq!(dls[0].status, "resolved");
+}
+
+// ======================================================================
+// Chapter 25: Repartitioning
+// ======================================================================
+
+struct PayloadRecorderTx {
+    seen: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl TransactionalHandler for PayloadRecorderTx {
+    async fn handle(&self, _txn: &dyn ConnectionTrait, msgs: &[OutboxMessage]) -> HandlerResult {
+        let mut seen = self.seen.lock().unwrap();
+        for msg in msgs {
+            seen.push(String::from_utf8(msg.payload.clone()).unwrap());
+        }
+        HandlerResult::Success
+    }
+}
+
+#[tokio::test]
+async fn repartition_grow_adds_next_generation() {
+    let db = setup_db("ch25_grow").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 2).await.unwrap();
+    let old = t.outbox.partition_ids_for_queue("q");
+    enqueue_and_sequence(&t, &db, "q", 1, &["a"]).await;
+
+    Outbox::repartition_queue(&db, "q", Partitions::of(4))
+        .await
+        .unwrap();
+    assert_eq!(count_rows(&db, "modkit_outbox_partitions").await, 6);
+    assert_eq!(count_rows(&db, "modkit_outbox_processor").await, 6);
+    assert_eq!(count_rows(&db, "modkit_outbox_vacuum_counter").await, 6);
+
+    // A restarted instance registers the new or the old count, no other.
+    let t2 = make_default_test_outbox().await;
+    t2.outbox.register_queue(&db, "q", 4).await.unwrap();
+    let err = t2.outbox.register_queue(&db, "q", 8).await.unwrap_err();
+    assert!(matches!(
+        err,
+        OutboxError::PartitionCountMismatch {
+            expected: 8,
+            found: 4,
+            ..
+        }
+    ));
+    let t3 = make_default_test_outbox().await;
+    t3.outbox.register_queue(&db, "q", 2).await.unwrap();
+    let new = t2.outbox.partition_ids_for_queue("q");
+    assert_eq!(new.len(), 4);
+    assert!(new.iter().all(|id| !old.contains(id)));
+    assert_eq!(t3.outbox.partition_ids_for_queue("q"), new);
+    assert_eq!(t2.outbox.previous_partition_ids("q"), old);
+    assert_eq!(t2.outbox.partition_to_queue(old[1]).as_deref(), Some("q"));
+
+    // New partition 3 shares keys with old partition 1 (3 % 2), which still
+    // has a message; new partition 2 only with the empty old partition 0.
+    let fence3 = t2.outbox.partition_fence(new[3]).unwrap();
+    let fence2 = t2.outbox.partition_fence(new[2]).unwrap();
+    assert!(!fence3.is_lifted(&db).await.unwrap());
+    assert!(fence2.is_lifted(&db).await.unwrap());
+
+    let count = Arc::new(AtomicU32::new(0));
+    run_transactional(
+        &db,
+        old[1],
+        CountingTxHandler {
+            count: count.clone(),
+        },
+        10,
+    )
+    .await;
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+    assert!(fence3.is_lifted(&db).await.unwrap());
+}
+
+#[tokio::test]
+async fn repartition_fence_keeps_key_order_across_switch() {
+    let db = setup_db("ch25_order").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+    let old = t.outbox.partition_ids_for_queue("q")[0];
+    enqueue_and_sequence(&t, &db, "q", 0, &["k-1", "k-2"]).await;
+
+    Outbox::repartition_queue(&db, "q", Partitions::of(2))
+        .await
+        .unwrap();
+    let t2 = make_default_test_outbox().await;
+    t2.outbox.register_queue(&db, "q", 2).await.unwrap();
+    let new = t2.outbox.partition_ids_for_queue("q")[0];
+    enqueue_and_sequence(&t2, &db, "q", 0, &["k-3"]).await;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let strategy = TransactionalStrategy::new(Box::new(PayloadRecorderTx {
+        seen: Arc::clone(&seen),
+    }));
+    let mut processor = PartitionProcessor::new(
+        strategy,
+        new,
+        WorkerTuning::processor_default(),
+        db.clone(),
+        t2.outbox.partition_metrics(new),
+    )
+    .with_fence(t2.outbox.partition_fence(new));
+    let cancel = CancellationToken::new();
+
+    // Fenced: the old partition still holds earlier messages.
+    let directive = processor.execute(&cancel).await.unwrap();
+    assert!(matches!(directive, Directive::Idle(r) if r.messages_processed == 0));
+    assert!(seen.lock().unwrap().is_empty());
+
+    run_transactional(
+        &db,
+        old,
+        PayloadRecorderTx {
+            seen: Arc::clone(&seen),
+        },
+        10,
+    )
+    .await;
+    processor.execute(&cancel).await.unwrap();
+
+    assert_eq!(*seen.lock().unwrap(), vec!["k-1", "k-2", "k-3"]);
+}
+
+#[tokio::test]
+async fn repartition_rejected_while_previous_generation_drains() {
+    let db = setup_db("ch25_in_progress").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 2).await.unwrap();
+    enqueue_msgs(&t.outbox, &db, "q", 0, &["unsequenced"]).await;
+
+    Outbox::repartition_queue(&db, "q", Partitions::of(4))
+        .await
+        .unwrap();
+    let err = Outbox::repartition_queue(&db, "q", Partitions::of(8))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OutboxError::RepartitionInProgress(q) if q == "q"));
+    assert_eq!(count_rows(&db, "modkit_outbox_partitions").await, 6);
+
+    // Repeating the completed switch is still a no-op.
+    Outbox::repartition_queue(&db, "q", Partitions::of(4))
+        .await
+        .unwrap();
+    assert_eq!(count_rows(&db, "modkit_outbox_partitions").await, 6);
+}
+
+#[tokio::test]
+async fn repartition_shrink_fences_on_delayed_messages() {
+    let db = setup_db("ch25_shrink").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 4).await.unwrap();
+    let conn = db.conn().unwrap();
+    t.outbox
+        .enqueue_after(
+            &conn,
+            "q",
+            3,
+            b"later".to_vec(),
+            "text/plain",
+            Duration::from_hours(1),
+        )
+        .await
+        .unwrap();
+
+    Outbox::repartition_queue(&db, "q", Partitions::of(4))
+        .await
+        .unwrap();
+    assert_eq!(count_rows(&db, "modkit_outbox_partitions").await, 4);
+
+    Outbox::repartition_queue(&db, "q", Partitions::of(1))
+        .await
+        .unwrap();
+    let t2 = make_default_test_outbox().await;
+    t2.outbox.register_queue(&db, "q", 1).await.unwrap();
+    let new = t2.outbox.partition_ids_for_queue("q")[0];
+
+    // The only old message is not due yet, but its key may follow it into
+    // the new partition, so it holds the fence.
+    let fence = t2.outbox.partition_fence(new).unwrap();
+    assert!(!fence.is_lifted(&db).await.unwrap());
+
+    let err = Outbox::repartition_queue(&db, "missing", Partitions::of(2))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OutboxError::QueueNotRegistered(_)));
+}
+
+async fn read_incoming_partition(db: &Db, id: OutboxMessageId) -> i64 {
+    #[derive(Debug, FromQueryResult)]
+    struct Row {
+        partition_id: i64,
+    }
+    let conn = db.sea_internal();
+    Row::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Sqlite,
+        "SELECT partition_id FROM modkit_outbox_incoming WHERE id = $1",
+        [id.0.into()],
+    ))
+    .one(&conn)
+    .await
+    .expect("incoming query")
+    .expect("incoming row")
+    .partition_id
+}
+
+/// Growing 2 -> 4 while instances registered with 2 still run or start:
+/// they keep enqueueing into the old generation, and a key keeps its order
+/// across both generations however the enqueues interleave.
+#[tokio::test]
+async fn repartition_grow_keeps_key_order_with_mixed_instances() {
+    let db = setup_db("ch25_mixed_grow").await;
+    let stale = make_default_test_outbox().await;
+    stale.outbox.register_queue(&db, "q", 2).await.unwrap();
+    let old = stale.outbox.partition_ids_for_queue("q");
+
+    // Key hash 3: index 1 of 2, index 3 of 4.
+    enqueue_and_sequence(&stale, &db, "q", 1, &["k-1"]).await;
+    Outbox::repartition_queue(&db, "q", Partitions::of(4))
+        .await
+        .unwrap();
+    let current = make_default_test_outbox().await;
+    current.outbox.register_queue(&db, "q", 4).await.unwrap();
+    let new = current.outbox.partition_ids_for_queue("q");
+
+    let ids = enqueue_msgs(&stale.outbox, &db, "q", 1, &["k-2"]).await;
+    assert_eq!(read_incoming_partition(&db, ids[0]).await, old[1]);
+    enqueue_and_sequence(&current, &db, "q", 3, &["k-3"]).await;
+    run_sequencer_once(&stale, &db).await;
+
+    // Once the stale instance sees the new generation it processes it, and
+    // its index of 2 partitions still names the key's old partition.
+    let switched = stale.outbox.refresh_generations(&db).await.unwrap();
+    assert_eq!(switched, vec![("q".to_owned(), new.clone())]);
+    assert!(
+        stale
+            .outbox
+            .refresh_generations(&db)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(stale.outbox.partition_ids_for_queue("q"), new);
+    assert_eq!(stale.outbox.previous_partition_ids("q"), old);
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let strategy = TransactionalStrategy::new(Box::new(PayloadRecorderTx {
+        seen: Arc::clone(&seen),
+    }));
+    let mut processor = PartitionProcessor::new(
+        strategy,
+        new[3],
+        WorkerTuning::processor_default(),
+        db.clone(),
+        current.outbox.partition_metrics(new[3]),
+    )
+    .with_fence(current.outbox.partition_fence(new[3]));
+    let cancel = CancellationToken::new();
+    processor.execute(&cancel).await.unwrap();
+    assert!(seen.lock().unwrap().is_empty());
+
+    let drain_old = || {
+        run_transactional(
+            &db,
+            old[1],
+            PayloadRecorderTx {
+                seen: Arc::clone(&seen),
+            },
+            10,
+        )
+    };
+    drain_old().await;
+    processor.execute(&cancel).await.unwrap();
+    assert_eq!(*seen.lock().unwrap(), vec!["k-1", "k-2", "k-3"]);
+
+    // An instance started with the old count enqueues into the drained old
+    // partition, which fences the new one again.
+    let late = make_default_test_outbox().await;
+    late.outbox.register_queue(&db, "q", 2).await.unwrap();
+    enqueue_and_sequence(&late, &db, "q", 1, &["k-4"]).await;
+    enqueue_and_sequence(&current, &db, "q", 3, &["k-5"]).await;
+    processor.execute(&cancel).await.unwrap();
+    assert_eq!(seen.lock().unwrap().len(), 3);
+
+    drain_old().await;
+    processor.execute(&cancel).await.unwrap();
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec!["k-1", "k-2", "k-3", "k-4", "k-5"]
+    );
+}
+
+/// When shrinking, a stale index maps to the new partition of its keys.
+#[tokio::test]
+async fn repartition_shrink_maps_stale_enqueue_index() {
+    let db = setup_db("ch25_stale_shrink").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 4).await.unwrap();
+
+    Outbox::repartition_queue(&db, "q", Partitions::of(2))
+        .await
+        .unwrap();
+    let t2 = make_default_test_outbox().await;
+    t2.outbox.register_queue(&db, "q", 2).await.unwrap();
+    let new = t2.outbox.partition_ids_for_queue("q");
+    t.outbox.refresh_generations(&db).await.unwrap();
+
+    let ids = enqueue_msgs(&t.outbox, &db, "q", 3, &["k"]).await;
+    assert_eq!(read_incoming_partition(&db, ids[0]).await, new[1]);
+}
+
+#[tokio::test]
+async fn queue_health_reports_generation_and_draining_partitions() {
+    let db = setup_db("ch25_health").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 2).await.unwrap();
+    enqueue_and_sequence(&t, &db, "q", 1, &["a"]).await;
+    Outbox::repartition_queue(&db, "q", Partitions::of(4))
+        .await
+        .unwrap();
+
+    let conn = db.conn().unwrap();
+    let partitions = t.outbox.partition_health(&conn).await.unwrap();
+    let indexes: Vec<(i16, i16)> = partitions
+        .iter()
+        .map(|p| (p.generation, p.partition_index))
+        .collect();
+    assert_eq!(
+        indexes,
+        vec![(0, 0), (0, 1), (1, 0), (1, 1), (1, 2), (1, 3)]
+    );
+
+    let queues = t.outbox.queue_health(&conn).await.unwrap();
+    assert_eq!(queues.len(), 1);
+    assert_eq!(queues[0].generation, 1);
+    assert_eq!(queues[0].partitions, 4);
+    assert_eq!(queues[0].draining_partitions, 1);
+    assert_eq!(queues[0].backlog, 1);
+}
+
+/// A builder declaring a new count fails to start unless repartitioning is
+/// allowed; then messages left in the old partition are still delivered.
+#[tokio::test]
+async fn pipeline_allow_repartition_drains_old_partitions() {
+    let db = setup_db("ch25_pipeline").await;
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 1).await.unwrap();
+    enqueue_msgs(&t.outbox, &db, "q", 0, &["old"]).await;
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let notify = Arc::new(tokio::sync::Notify::new());
+    let builder = || {
+        Outbox::builder(db.clone())
+            .processor_tuning(
+                WorkerTuning::processor_default().idle_interval(Duration::from_millis(50)),
+            )
+            .processors(2)
+            .maintenance(1, 1)
+    };
+
+    let result = builder()
+        .queue("q", Partitions::of(2))
+        .leased(CountingHandler {
+            counter: Arc::clone(&counter),
+            notify: Arc::clone(&notify),
+        })
+        .start()
+        .await;
+    assert!(matches!(
+        result,
+        Err(OutboxError::PartitionCountMismatch { .. })
+    ));
+
+    let handle = builder()
+        .allow_repartition()
+        .queue("q", Partitions::of(2))
+        .leased(CountingHandler {
+            counter: Arc::clone(&counter),
+            notify: Arc::clone(&notify),
+        })
+        .start()
+        .await
+        .unwrap();
+    enqueue_msgs(handle.outbox(), &db, "q", 1, &["new"]).await;
+    handle.outbox().flush();
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    while counter.load(Ordering::Acquire) < 2 {
+        let remaining = deadline
+            .checked_duration_since(tokio::time::Instant::now())
+            .unwrap_or(Duration::ZERO);
+        assert!(
+            !remaining.is_zero(),
+            "timed out waiting for delivery (consumed: {})",
+            counter.load(Ordering::Relaxed)
+        );
+        tokio::time::timeout(remaining, notify.notified())
+            .await
+            .ok();
+    }
+    assert_eq!(count_rows(&db, "modkit_outbox_partitions").await, 3);
+
+    handle.stop().await;
+}
+
+/// A running pipeline starts processors for a repartition made elsewhere
+/// without being restarted.
+#[tokio::test]
+async fn pipeline_processes_new_generation_without_restart() {
+    let db = setup_db("ch25_pipeline_watch").await;
+    let counter = Arc::new(AtomicUsize::new(0));
+    let notify = Arc::new(tokio::sync::Notify::new());
+    let handle = Outbox::builder(db.clone())
+        .processor_tuning(
+            WorkerTuning::processor_default().idle_interval(Duration::from_millis(50)),
+        )
+        .processors(2)
+        .maintenance(1, 1)
+        .repartition_check_interval(Duration::from_millis(50))
+        .queue("q", Partitions::of(1))
+        .leased(CountingHandler {
+            counter: Arc::clone(&counter),
+            notify: Arc::clone(&notify),
+        })
+        .start()
+        .await
+        .unwrap();
+
+    Outbox::repartition_queue(&db, "q", Partitions::of(2))
+        .await
+        .unwrap();
+    let t = make_default_test_outbox().await;
+    t.outbox.register_queue(&db, "q", 2).await.unwrap();
+    enqueue_and_sequence(&t, &db, "q", 1, &["new"]).await;
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    while counter.load(Ordering::Acquire) < 1 {
+        let remaining = deadline
+            .checked_duration_since(tokio::time::Instant::now())
+            .unwrap_or(Duration::ZERO);
+        assert!(!remaining.is_zero(), "timed out waiting for delivery");
+        tokio::time::timeout(remaining, notify.notified())
+            .await
+            .ok();
+    }
+    assert_eq!(
+        handle.outbox().partition_ids_for_queue("q"),
+        t.outbox.partition_ids_for_queue("q")
+    );
+
+    handle.stop().await;
+}
@@ -8,6 +8,7 @@ use tokio_util::sync::CancellationToken;
 
 use super::builder::QueueBuilder;
 use super::core::Outbox;
+use super::inbox::InboxQueueBuilder;
 use super::prioritizer::SharedPrioritizer;
 use super::stats::{StatsListener, StatsRegistry, StatsReporter};
 use super::taskward::{
@@ -107,6 +108,12 @@ pub struct OutboxBuilder {
     maintenance_guaranteed: Option<usize>,
     maintenance_shared: Option<usize>,
     stats_interval: Option<Duration>,
+    health_metrics_interval: Option<Duration>,
+    dedup_retention: Duration,
+    inbox_retention: Duration,
+    allow_repartition: bool,
+    repartition_check_interval: Duration,
+    pg_notify: bool,
     profile: Option<OutboxProfile>,
     processor_tuning: Option<WorkerTuning>,
     sequencer_tuning: Option<WorkerTuning>,
@@ -125,6 +132,12 @@ impl OutboxBuilder {
             maintenance_guaranteed: None,
             maintenance_shared: None,
             stats_interval: Some(Duration::from_mins(1)),
+            health_metrics_interval: Some(Duration::from_secs(30)),
+            dedup_retention: super::types::DEFAULT_DEDUP_RETENTION,
+            inbox_retention: super::types::DEFAULT_INBOX_RETENTION,
+            allow_repartition: false,
+            repartition_check_interval: super::types::DEFAULT_REPARTITION_CHECK_INTERVAL,
+            pg_notify: false,
             profile: None,
             processor_tuning: None,
             sequencer_tuning: None,
@@ -212,6 +225,91 @@ impl OutboxBuilder {
         self
     }
 
+    /// How often backlog, lag, and dead-letter gauges are sampled for
+    /// OpenTelemetry (feature `otel`). Default: 30s.
+    /// `Duration::ZERO` disables sampling; counters and histograms are
+    /// recorded regardless.
+    #[must_use]
+    pub fn health_metrics_interval(mut self, d: Duration) -> Self {
+        self.health_metrics_interval = if d.is_zero() { None } else { Some(d) };
+        self
+    }
+
+    /// How long a producer dedup key rejects repeats of the same message.
+    /// Expired keys are deleted by the vacuum. Default: 24h.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `d` is zero.
+    #[must_use]
+    pub fn dedup_retention(mut self, d: Duration) -> Self {
+        assert!(!d.is_zero(), "dedup_retention must be > 0");
+        self.dedup_retention = d;
+        self
+    }
+
+    /// How long inbox entries are kept to reject redelivered messages.
+    /// Should exceed the longest redelivery window of any sender. Expired
+    /// entries are deleted by the vacuum once processed. Default: 7 days.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `d` is zero.
+    #[must_use]
+    pub fn inbox_retention(mut self, d: Duration) -> Self {
+        assert!(!d.is_zero(), "inbox_retention must be > 0");
+        self.inbox_retention = d;
+        self
+    }
+
+    /// Repartition queues whose declared [`Partitions`] differ from the
+    /// registered count instead of failing `start()` with
+    /// [`OutboxError::PartitionCountMismatch`].
+    ///
+    /// See [`Outbox::repartition_queue()`] for how messages already in the
+    /// old partitions are handled. Enable it on the instances that should
+    /// move the queue to the new count. Instances still declaring the old
+    /// count keep starting and enqueue into the previous generation, so a
+    /// rolling deploy or a rollback needs no coordination.
+    #[must_use]
+    pub fn allow_repartition(mut self) -> Self {
+        self.allow_repartition = true;
+        self
+    }
+
+    /// How often running instances check their queues for a repartition
+    /// made elsewhere. On Postgres with [`pg_notify`](Self::pg_notify) the
+    /// check also runs as soon as the repartition commits. Default: 10s.
+    ///
+    /// Until an instance notices a repartition it keeps enqueueing to the
+    /// previous generation; see [`Outbox::repartition_queue()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `d` is zero.
+    #[must_use]
+    pub fn repartition_check_interval(mut self, d: Duration) -> Self {
+        assert!(!d.is_zero(), "repartition_check_interval must be > 0");
+        self.repartition_check_interval = d;
+        self
+    }
+
+    /// Wake workers across instances with Postgres `LISTEN`/`NOTIFY`.
+    /// Default: false.
+    ///
+    /// When enabled on Postgres, enqueue and the sequencer send a `NOTIFY`
+    /// per touched partition in their transaction and each instance keeps
+    /// one extra connection listening, so messages written on another
+    /// instance are picked up within milliseconds instead of the next idle
+    /// poll. Other backends always poll. Enable it on every instance of a
+    /// deployment: the notifications cost a round-trip per partition on
+    /// each enqueue, which only pays off where a listener runs.
+    #[must_use]
+    pub fn pg_notify(mut self, enabled: bool) -> Self {
+        self.pg_notify = enabled;
+        self
+    }
+
     /// Set a baseline tuning profile for all worker types.
     ///
     /// Individual worker tunings (e.g. [`processor_tuning()`](Self::processor_tuning))
@@ -257,6 +355,12 @@ impl OutboxBuilder {
         QueueBuilder::new(self, name.to_owned(), partitions)
     }
 
+    /// Begin building an inbox registration for messages received via
+    /// [`Outbox::receive()`](super::Outbox::receive).
+    pub fn inbox(self, name: &str, partitions: Partitions) -> InboxQueueBuilder {
+        InboxQueueBuilder::new(self, name.to_owned(), partitions)
+    }
+
     // ------------------------------------------------------------------
     // Private helpers for start()
     // ------------------------------------------------------------------
@@ -290,6 +394,24 @@ impl OutboxBuilder {
         resolved
     }
 
+    /// Register a declared queue, repartitioning it first when allowed and
+    /// the registered partition count differs.
+    async fn register_declared_queue(
+        db: &Db,
+        outbox: &Outbox,
+        decl: &QueueDeclaration,
+        allow_repartition: bool,
+    ) -> Result<(), OutboxError> {
+        let count = decl.partitions.count();
+        match outbox.register_queue(db, &decl.name, count).await {
+            Err(OutboxError::PartitionCountMismatch { .. }) if allow_repartition => {
+                Outbox::repartition_queue(db, &decl.name, decl.partitions).await?;
+                outbox.register_queue(db, &decl.name, count).await
+            }
+            result => result,
+        }
+    }
+
     /// Spawn parallel sequencer workers (`guaranteed + shared`).
     fn spawn_sequencers(
         ctx: &mut StartContext<'_>,
@@ -353,10 +475,14 @@ impl OutboxBuilder {
         tuning: &WorkerTuning,
         shared_sem: &Arc<Semaphore>,
         count: usize,
+        dedup_retention: Duration,
+        inbox_retention: Duration,
     ) {
         for i in 0..count {
             #[allow(unused_mut)]
-            let vacuum = VacuumTask::new(ctx.db.clone(), tuning.batch_size as usize);
+            let vacuum = VacuumTask::new(ctx.db.clone(), tuning.batch_size as usize)
+                .with_dedup_retention(dedup_retention)
+                .with_inbox_retention(inbox_retention);
             let name = format!("vacuum-{i}");
             let (poker_notify, _poker_handle) = poker(tuning.idle_interval, ctx.cancel.clone());
             let mut builder = WorkerBuilder::<VacuumReport>::new(&name, ctx.cancel.clone())
@@ -416,12 +542,90 @@ impl OutboxBuilder {
         ctx.task_set.spawn(name, worker.run());
     }
 
+    /// Spawn the watcher that starts processors for repartitions made while
+    /// the pipeline runs. Takes over the queue factories.
+    fn spawn_repartition_watcher(
+        ctx: &mut StartContext<'_>,
+        outbox: &Arc<Outbox>,
+        declarations: Vec<QueueDeclaration>,
+        processor_sem: &Arc<Semaphore>,
+        tuning: &WorkerTuning,
+        interval: Duration,
+    ) {
+        let watcher = super::workers::repartition_watcher::RepartitionWatcher {
+            outbox: Arc::clone(outbox),
+            db: ctx.db.clone(),
+            factories: declarations
+                .into_iter()
+                .map(|decl| (decl.name, decl.factory))
+                .collect(),
+            processor_sem: Arc::clone(processor_sem),
+            tuning: tuning.clone(),
+            interval,
+        };
+        ctx.task_set
+            .spawn("repartition-watcher", watcher.run(ctx.cancel.clone()));
+    }
+
+    /// Spawn the `LISTEN` task feeding other instances' wakeups into the
+    /// prioritizer and processors. No-op unless the backend is Postgres.
+    #[cfg(feature = "pg")]
+    fn spawn_listener(
+        ctx: &mut StartContext<'_>,
+        outbox: &Arc<Outbox>,
+        prioritizer: &Arc<SharedPrioritizer>,
+    ) {
+        if ctx.db.backend() != sea_orm::DbBackend::Postgres {
+            return;
+        }
+        let listener = super::workers::listener::run(
+            ctx.db.clone(),
+            Arc::clone(outbox),
+            Arc::clone(prioritizer),
+            ctx.cancel.clone(),
+        );
+        ctx.task_set.spawn("pg-listener", listener);
+    }
+
+    #[cfg(not(feature = "pg"))]
+    fn spawn_listener(
+        _ctx: &mut StartContext<'_>,
+        _outbox: &Arc<Outbox>,
+        _prioritizer: &Arc<SharedPrioritizer>,
+    ) {
+    }
+
+    /// Spawn the worker sampling queue health into OpenTelemetry gauges.
+    #[cfg(feature = "otel")]
+    fn spawn_health_sampler(ctx: &mut StartContext<'_>, outbox: &Arc<Outbox>, interval: Duration) {
+        let sampler = super::metrics::HealthSampler {
+            outbox: Arc::clone(outbox),
+            db: ctx.db.clone(),
+        };
+        let name = "health-sampler";
+        let (poker_notify, _poker_handle) = poker(interval, ctx.cancel.clone());
+        let worker = WorkerBuilder::new(name, ctx.cancel.clone())
+            .notifier(poker_notify)
+            .notifier(Arc::clone(ctx.start_notify))
+            .on_panic(PanicPolicy::CatchAndRetry)
+            .build(sampler);
+        ctx.task_set.spawn(name, worker.run());
+    }
+
+    #[cfg(not(feature = "otel"))]
+    fn spawn_health_sampler(
+        _ctx: &mut StartContext<'_>,
+        _outbox: &Arc<Outbox>,
+        _interval: Duration,
+    ) {
+    }
+
     /// Spawn stats reporter (if enabled).
     fn spawn_stats_reporter(
         ctx: &mut StartContext<'_>,
         stats_registry_shared: Option<Arc<std::sync::Mutex<StatsRegistry>>>,
         interval: Duration,
-    ) {
+    ) -> Arc<StatsRegistry> {
         // Extract the registry — all workers have registered by now.
         #[allow(clippy::expect_used)]
         let registry = stats_registry_shared
@@ -430,7 +634,8 @@ impl OutboxBuilder {
             .ok()
             .map(|mut guard| std::mem::replace(&mut *guard, StatsRegistry::new()))
             .expect("stats registry mutex not poisoned");
-        let reporter = StatsReporter::new(Arc::new(registry));
+        let registry = Arc::new(registry);
+        let reporter = StatsReporter::new(Arc::clone(&registry));
         let name = "stats-reporter";
         let (poker_notify, _poker_handle) = poker(interval, ctx.cancel.clone());
         let worker = WorkerBuilder::new(name, ctx.cancel.clone())
@@ -438,6 +643,7 @@ impl OutboxBuilder {
             .on_panic(PanicPolicy::CatchAndRetry)
             .build(reporter);
         ctx.task_set.spawn(name, worker.run());
+        registry
     }
 
     /// Spawn background tasks and return a handle to the running pipeline.
@@ -467,6 +673,8 @@ impl OutboxBuilder {
                 partition_batch_limit: self.partition_batch_limit,
                 max_inner_iterations: self.max_inner_iterations,
             },
+            dedup_retention: self.dedup_retention,
+            pg_notify: self.pg_notify,
         };
         let outbox = Arc::new(Outbox::new(config));
         let cancel = CancellationToken::new();
@@ -488,13 +696,15 @@ impl OutboxBuilder {
 
         // 3. Register queues and spawn processor workers via factories
         for decl in &mut self.queue_declarations {
-            outbox
-                .register_queue(&self.db, &decl.name, decl.partitions.count())
-                .await?;
+            Self::register_declared_queue(&self.db, &outbox, decl, self.allow_repartition).await?;
 
-            let partition_ids = outbox.partition_ids_for_queue(&decl.name);
+            // A previous generation gets processors as well.
+            let partition_ids = outbox
+                .partition_ids_for_queue(&decl.name)
+                .into_iter()
+                .chain(outbox.previous_partition_ids(&decl.name));
 
-            for &pid in &partition_ids {
+            for pid in partition_ids {
                 let notify = Arc::new(Notify::new());
                 partition_notify.insert(pid, Arc::clone(&notify));
                 let spawn_ctx = super::builder::SpawnContext {
@@ -507,6 +717,7 @@ impl OutboxBuilder {
                     outbox: Arc::clone(&outbox),
                     stats_registry: stats_registry_shared.clone(),
                     tuning: tuning.processor.clone(),
+                    fence: outbox.partition_fence(pid),
                 };
                 let (name, future) = decl.factory.spawn(spawn_ctx);
                 task_set.spawn(name, future);
@@ -560,18 +771,45 @@ impl OutboxBuilder {
             sequencer_count,
         );
 
-        // 8. Spawn cold reconciler
+        // 8. Spawn cold reconciler and, on Postgres, the NOTIFY listener
         Self::spawn_cold_reconciler(&mut ctx, &outbox, &shared_prioritizer, &tuning.reconciler);
+        if self.pg_notify {
+            Self::spawn_listener(&mut ctx, &outbox, &shared_prioritizer);
+        }
 
-        // 9. Spawn vacuum workers
-        Self::spawn_vacuum_workers(&mut ctx, &tuning.vacuum, &shared_sem, shared);
+        // 9. Spawn the repartition watcher
+        Self::spawn_repartition_watcher(
+            &mut ctx,
+            &outbox,
+            std::mem::take(&mut self.queue_declarations),
+            &processor_sem,
+            &tuning.processor,
+            self.repartition_check_interval,
+        );
 
-        // 10. Spawn stats reporter (if enabled)
+        // 10. Spawn vacuum workers
+        Self::spawn_vacuum_workers(
+            &mut ctx,
+            &tuning.vacuum,
+            &shared_sem,
+            shared,
+            self.dedup_retention,
+            self.inbox_retention,
+        );
+
+        // 11. Spawn stats reporter (if enabled)
         if let Some(interval) = self.stats_interval {
-            Self::spawn_stats_reporter(&mut ctx, stats_registry_shared.clone(), interval);
+            let registry =
+                Self::spawn_stats_reporter(&mut ctx, stats_registry_shared.clone(), interval);
+            outbox.set_stats_registry(registry);
+        }
+
+        // 12. Spawn health metrics sampler (if enabled)
+        if let Some(interval) = self.health_metrics_interval {
+            Self::spawn_health_sampler(&mut ctx, &outbox, interval);
         }
 
-        // 11. Signal all workers to start
+        // 13. Signal all workers to start
         start_notify.notify_waiters();
 
         Ok(OutboxHandle {
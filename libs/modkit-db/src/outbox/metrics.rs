@@ -0,0 +1,321 @@
+//! OpenTelemetry instruments of the outbox pipeline (feature `otel`).
+//!
+//! Instruments are created on the global meter provider, which `modkit`'s
+//! telemetry installs when metrics are enabled; otherwise recording is a
+//! no-op. Without the `otel` feature every method compiles to nothing.
+//!
+//! | Instrument | Kind | Attributes |
+//! |------------|------|------------|
+//! | `outbox.enqueued` | counter | `outbox.queue` |
+//! | `outbox.sequenced` | counter | queue, `outbox.partition_id` |
+//! | `outbox.processed` | counter | queue, partition, `outbox.outcome` |
+//! | `outbox.processor.duration` | histogram (s) | queue, partition, outcome |
+//! | `outbox.retries` | counter | queue, partition |
+//! | `outbox.dead_lettered` | counter | queue, partition |
+//! | `outbox.lease.expired` | counter | queue, partition |
+//! | `outbox.partition.backlog` | gauge | queue, partition |
+//! | `outbox.partition.incoming` | gauge | queue, partition |
+//! | `outbox.partition.lag` | gauge (s) | queue, partition |
+//! | `outbox.partition.attempts` | gauge | queue, partition |
+//! | `outbox.dead_letters.pending` | gauge | queue |
+//!
+//! The gauges are sampled from [`Outbox::queue_health`](super::Outbox::queue_health)
+//! by a background worker on every instance, so each instance reports the
+//! same values; aggregate them with `max`, not `sum`.
+
+#[cfg(feature = "otel")]
+pub use imp::HealthSampler;
+pub use imp::{OutboxMetrics, PartitionMetrics};
+
+#[cfg(feature = "otel")]
+mod imp {
+    use std::convert::Infallible;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use opentelemetry::metrics::{Counter, Gauge, Histogram, Meter};
+    use opentelemetry::{InstrumentationScope, KeyValue, global};
+    use tokio_util::sync::CancellationToken;
+    use tracing::warn;
+
+    use super::super::core::Outbox;
+    use super::super::handler::HandlerResult;
+    use super::super::health::{PartitionHealth, QueueHealth};
+    use super::super::taskward::{Directive, WorkerAction};
+    use super::super::types::OutboxError;
+    use crate::Db;
+
+    const QUEUE: &str = "outbox.queue";
+    const PARTITION_ID: &str = "outbox.partition_id";
+    const OUTCOME: &str = "outbox.outcome";
+
+    struct Instruments {
+        enqueued: Counter<u64>,
+        sequenced: Counter<u64>,
+        processed: Counter<u64>,
+        duration: Histogram<f64>,
+        retries: Counter<u64>,
+        dead_lettered: Counter<u64>,
+        lease_expired: Counter<u64>,
+        backlog: Gauge<i64>,
+        incoming: Gauge<i64>,
+        lag: Gauge<f64>,
+        attempts: Gauge<i64>,
+        pending_dead_letters: Gauge<u64>,
+    }
+
+    impl Instruments {
+        fn new(meter: &Meter) -> Self {
+            Self {
+                enqueued: meter
+                    .u64_counter("outbox.enqueued")
+                    .with_description("Messages written to the outbox")
+                    .build(),
+                sequenced: meter
+                    .u64_counter("outbox.sequenced")
+                    .with_description("Messages assigned a sequence number")
+                    .build(),
+                processed: meter
+                    .u64_counter("outbox.processed")
+                    .with_description("Messages in batches handed to a handler")
+                    .build(),
+                duration: meter
+                    .f64_histogram("outbox.processor.duration")
+                    .with_description("Duration of one processor batch, including the ack")
+                    .with_unit("s")
+                    .build(),
+                retries: meter
+                    .u64_counter("outbox.retries")
+                    .with_description("Batches the handler asked to retry")
+                    .build(),
+                dead_lettered: meter
+                    .u64_counter("outbox.dead_lettered")
+                    .with_description("Messages moved to the dead-letter table")
+                    .build(),
+                lease_expired: meter
+                    .u64_counter("outbox.lease.expired")
+                    .with_description("Leased batches whose lease expired before the ack")
+                    .build(),
+                backlog: meter
+                    .i64_gauge("outbox.partition.backlog")
+                    .with_description("Sequenced messages not yet processed")
+                    .build(),
+                incoming: meter
+                    .i64_gauge("outbox.partition.incoming")
+                    .with_description("Messages waiting to be sequenced")
+                    .build(),
+                lag: meter
+                    .f64_gauge("outbox.partition.lag")
+                    .with_description("Age of the oldest unprocessed message")
+                    .with_unit("s")
+                    .build(),
+                attempts: meter
+                    .i64_gauge("outbox.partition.attempts")
+                    .with_description("Consecutive failed attempts of the current batch")
+                    .build(),
+                pending_dead_letters: meter
+                    .u64_gauge("outbox.dead_letters.pending")
+                    .with_description("Dead letters awaiting replay or resolution")
+                    .build(),
+            }
+        }
+    }
+
+    /// Outbox-wide instruments.
+    #[derive(Clone)]
+    pub struct OutboxMetrics {
+        inner: Arc<Instruments>,
+    }
+
+    impl OutboxMetrics {
+        #[must_use]
+        pub fn new() -> Self {
+            let scope = InstrumentationScope::builder("modkit-db.outbox").build();
+            let meter = global::meter_with_scope(scope);
+            Self {
+                inner: Arc::new(Instruments::new(&meter)),
+            }
+        }
+
+        pub fn enqueued(&self, queue: &str, count: u64) {
+            self.inner
+                .enqueued
+                .add(count, &[KeyValue::new(QUEUE, queue.to_owned())]);
+        }
+
+        /// Instruments bound to one partition.
+        #[must_use]
+        pub fn partition(&self, queue: &str, partition_id: i64) -> PartitionMetrics {
+            PartitionMetrics {
+                inner: Arc::clone(&self.inner),
+                attrs: partition_attrs(queue, partition_id),
+            }
+        }
+
+        /// Record the sampled health gauges.
+        pub fn record_health(&self, partitions: &[PartitionHealth], queues: &[QueueHealth]) {
+            let m = &self.inner;
+            for p in partitions {
+                let attrs = partition_attrs(&p.queue, p.partition_id);
+                m.backlog.record(p.backlog(), &attrs);
+                m.incoming.record(p.incoming, &attrs);
+                m.attempts.record(i64::from(p.attempts), &attrs);
+                #[allow(clippy::cast_precision_loss)]
+                let lag = p.lag_ms.map_or(0.0, |ms| ms as f64 / 1000.0);
+                m.lag.record(lag, &attrs);
+            }
+            for q in queues {
+                m.pending_dead_letters.record(
+                    q.pending_dead_letters,
+                    &[KeyValue::new(QUEUE, q.queue.clone())],
+                );
+            }
+        }
+    }
+
+    impl Default for OutboxMetrics {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// Value of the `outbox.outcome` attribute for a handler result.
+    fn outcome(result: &HandlerResult) -> &'static str {
+        match result {
+            HandlerResult::Success => "success",
+            HandlerResult::Retry { .. } => "retry",
+            HandlerResult::Reject { .. } => "reject",
+        }
+    }
+
+    fn partition_attrs(queue: &str, partition_id: i64) -> [KeyValue; 2] {
+        [
+            KeyValue::new(QUEUE, queue.to_owned()),
+            KeyValue::new(PARTITION_ID, partition_id),
+        ]
+    }
+
+    /// Instruments bound to the attributes of one partition.
+    #[derive(Clone)]
+    pub struct PartitionMetrics {
+        inner: Arc<Instruments>,
+        attrs: [KeyValue; 2],
+    }
+
+    impl PartitionMetrics {
+        pub fn sequenced(&self, count: u64) {
+            self.inner.sequenced.add(count, &self.attrs);
+        }
+
+        /// Record a processed batch of `count` messages.
+        pub fn batch(&self, elapsed: Duration, count: u32, result: &HandlerResult) {
+            let [queue, partition] = self.attrs.clone();
+            let attrs = [queue, partition, KeyValue::new(OUTCOME, outcome(result))];
+            self.inner.processed.add(u64::from(count), &attrs);
+            self.inner.duration.record(elapsed.as_secs_f64(), &attrs);
+            if matches!(result, HandlerResult::Retry { .. }) {
+                self.inner.retries.add(1, &self.attrs);
+            }
+        }
+
+        pub fn dead_lettered(&self, count: u64) {
+            if count > 0 {
+                self.inner.dead_lettered.add(count, &self.attrs);
+            }
+        }
+
+        pub fn lease_expired(&self) {
+            self.inner.lease_expired.add(1, &self.attrs);
+        }
+    }
+
+    /// Periodically samples queue health into the gauges.
+    pub struct HealthSampler {
+        pub outbox: Arc<Outbox>,
+        pub db: Db,
+    }
+
+    impl WorkerAction for HealthSampler {
+        type Payload = ();
+        type Error = Infallible;
+
+        async fn execute(&mut self, _cancel: &CancellationToken) -> Result<Directive, Infallible> {
+            let conn = match self.db.conn() {
+                Ok(conn) => conn,
+                Err(e) => {
+                    warn!(error = %e, "outbox metrics: no database connection");
+                    return Ok(Directive::idle());
+                }
+            };
+            let sampled = async {
+                let mut partitions = self.outbox.partition_health(&conn).await?;
+                // Other outboxes may share the tables.
+                partitions.retain(|p| self.outbox.owns_partition(p.partition_id));
+                let queues = self.outbox.queue_health_of(&conn, &partitions).await?;
+                Ok::<_, OutboxError>((partitions, queues))
+            };
+            match sampled.await {
+                Ok((partitions, queues)) => {
+                    self.outbox.metrics().record_health(&partitions, &queues);
+                }
+                Err(e) => warn!(error = %e, "outbox metrics: failed to sample health"),
+            }
+            Ok(Directive::idle())
+        }
+    }
+
+    #[cfg(test)]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn outcome_names() {
+            assert_eq!(outcome(&HandlerResult::Success), "success");
+            let retry = HandlerResult::Retry { reason: "x".into() };
+            assert_eq!(outcome(&retry), "retry");
+            let reject = HandlerResult::Reject { reason: "x".into() };
+            assert_eq!(outcome(&reject), "reject");
+        }
+    }
+}
+
+#[cfg(not(feature = "otel"))]
+#[allow(clippy::unused_self)]
+mod imp {
+    use std::time::Duration;
+
+    use super::super::handler::HandlerResult;
+
+    /// No-op: OpenTelemetry is disabled.
+    #[derive(Clone, Default)]
+    pub struct OutboxMetrics;
+
+    impl OutboxMetrics {
+        #[must_use]
+        pub fn new() -> Self {
+            Self
+        }
+
+        pub fn enqueued(&self, _queue: &str, _count: u64) {}
+
+        #[must_use]
+        pub fn partition(&self, _queue: &str, _partition_id: i64) -> PartitionMetrics {
+            PartitionMetrics
+        }
+    }
+
+    /// No-op: OpenTelemetry is disabled.
+    #[derive(Clone, Default)]
+    pub struct PartitionMetrics;
+
+    impl PartitionMetrics {
+        pub fn sequenced(&self, _count: u64) {}
+
+        pub fn batch(&self, _elapsed: Duration, _count: u32, _result: &HandlerResult) {}
+
+        pub fn dead_lettered(&self, _count: u64) {}
+
+        pub fn lease_expired(&self) {}
+    }
+}
@@ -439,8 +439,313 @@ async fn create_vacuum_counter(
     Ok(())
 }
 
+/// Adds `deliver_at` to the incoming table for delayed delivery.
+///
+/// `NULL` means "deliver immediately", so existing rows keep their behavior.
+/// The `(partition_id, deliver_at)` index serves the sequencer's next-due
+/// lookup after a partition has been drained.
+struct AddIncomingDeliverAt;
+
+impl MigrationName for AddIncomingDeliverAt {
+    fn name(&self) -> &'static str {
+        "m002_add_modkit_outbox_incoming_deliver_at"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddIncomingDeliverAt {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        let backend = conn.get_database_backend();
+
+        conn.execute(Statement::from_string(
+            backend,
+            match backend {
+                DatabaseBackend::Postgres => {
+                    "ALTER TABLE modkit_outbox_incoming ADD COLUMN deliver_at TIMESTAMPTZ NULL"
+                }
+                DatabaseBackend::Sqlite => {
+                    "ALTER TABLE modkit_outbox_incoming ADD COLUMN deliver_at TEXT NULL"
+                }
+                DatabaseBackend::MySql => {
+                    "ALTER TABLE modkit_outbox_incoming ADD COLUMN deliver_at TIMESTAMP(6) NULL"
+                }
+            },
+        ))
+        .await?;
+
+        conn.execute(Statement::from_string(
+            backend,
+            "CREATE INDEX idx_modkit_outbox_incoming_deliver_at \
+             ON modkit_outbox_incoming (partition_id, deliver_at)",
+        ))
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        let backend = conn.get_database_backend();
+
+        conn.execute(Statement::from_string(
+            backend,
+            match backend {
+                DatabaseBackend::Postgres | DatabaseBackend::Sqlite => {
+                    "DROP INDEX IF EXISTS idx_modkit_outbox_incoming_deliver_at"
+                }
+                DatabaseBackend::MySql => {
+                    "DROP INDEX idx_modkit_outbox_incoming_deliver_at ON modkit_outbox_incoming"
+                }
+            },
+        ))
+        .await?;
+
+        conn.execute(Statement::from_string(
+            backend,
+            "ALTER TABLE modkit_outbox_incoming DROP COLUMN deliver_at",
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
+/// Adds a nullable `headers` column (JSON object of string pairs) to the
+/// body table. `NULL` means the message has no headers.
+struct AddBodyHeaders;
+
+impl MigrationName for AddBodyHeaders {
+    fn name(&self) -> &'static str {
+        "m003_add_modkit_outbox_body_headers"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddBodyHeaders {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        let backend = conn.get_database_backend();
+
+        conn.execute(Statement::from_string(
+            backend,
+            "ALTER TABLE modkit_outbox_body ADD COLUMN headers TEXT NULL",
+        ))
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        let backend = conn.get_database_backend();
+
+        conn.execute(Statement::from_string(
+            backend,
+            "ALTER TABLE modkit_outbox_body DROP COLUMN headers",
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
+/// Creates the producer dedup table. One row per `(queue, dedup_key)`;
+/// `created_at` drives the retention window and the vacuum's cleanup.
+struct CreateDedup;
+
+impl MigrationName for CreateDedup {
+    fn name(&self) -> &'static str {
+        "m004_create_modkit_outbox_dedup"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateDedup {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        let backend = conn.get_database_backend();
+
+        conn.execute(Statement::from_string(
+            backend,
+            match backend {
+                DatabaseBackend::Postgres => {
+                    "CREATE TABLE IF NOT EXISTS modkit_outbox_dedup (
+                    queue      VARCHAR(1024) NOT NULL,
+                    dedup_key  VARCHAR(255)  NOT NULL,
+                    created_at TIMESTAMPTZ   NOT NULL DEFAULT now(),
+                    PRIMARY KEY (queue, dedup_key)
+                )"
+                }
+                DatabaseBackend::Sqlite => {
+                    "CREATE TABLE IF NOT EXISTS modkit_outbox_dedup (
+                    queue      TEXT NOT NULL,
+                    dedup_key  TEXT NOT NULL,
+                    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now')),
+                    PRIMARY KEY (queue, dedup_key)
+                )"
+                }
+                DatabaseBackend::MySql => {
+                    "CREATE TABLE IF NOT EXISTS modkit_outbox_dedup (
+                    queue      VARCHAR(1024) CHARACTER SET ascii NOT NULL,
+                    dedup_key  VARCHAR(255)  CHARACTER SET ascii NOT NULL,
+                    created_at TIMESTAMP(6)  NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+                    PRIMARY KEY (queue, dedup_key)
+                )"
+                }
+            },
+        ))
+        .await?;
+
+        conn.execute(Statement::from_string(
+            backend,
+            "CREATE INDEX idx_modkit_outbox_dedup_created_at \
+             ON modkit_outbox_dedup (created_at)",
+        ))
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        let backend = conn.get_database_backend();
+
+        conn.execute(Statement::from_string(
+            backend,
+            "DROP TABLE IF EXISTS modkit_outbox_dedup",
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
+/// Creates the inbox table: one row per received `(queue, message_id)`.
+/// `processed_at` is stamped in the handler's transaction; the vacuum removes
+/// processed rows by `received_at` once past retention.
+struct CreateInbox;
+
+impl MigrationName for CreateInbox {
+    fn name(&self) -> &'static str {
+        "m005_create_modkit_outbox_inbox"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateInbox {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        let backend = conn.get_database_backend();
+
+        conn.execute(Statement::from_string(
+            backend,
+            match backend {
+                DatabaseBackend::Postgres => {
+                    "CREATE TABLE IF NOT EXISTS modkit_outbox_inbox (
+                    queue        VARCHAR(1024) NOT NULL,
+                    message_id   VARCHAR(255)  NOT NULL,
+                    received_at  TIMESTAMPTZ   NOT NULL DEFAULT now(),
+                    processed_at TIMESTAMPTZ,
+                    PRIMARY KEY (queue, message_id)
+                )"
+                }
+                DatabaseBackend::Sqlite => {
+                    "CREATE TABLE IF NOT EXISTS modkit_outbox_inbox (
+                    queue        TEXT NOT NULL,
+                    message_id   TEXT NOT NULL,
+                    received_at  TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now')),
+                    processed_at TEXT,
+                    PRIMARY KEY (queue, message_id)
+                )"
+                }
+                DatabaseBackend::MySql => {
+                    "CREATE TABLE IF NOT EXISTS modkit_outbox_inbox (
+                    queue        VARCHAR(1024) CHARACTER SET ascii NOT NULL,
+                    message_id   VARCHAR(255)  CHARACTER SET ascii NOT NULL,
+                    received_at  TIMESTAMP(6)  NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+                    processed_at TIMESTAMP(6)  NULL,
+                    PRIMARY KEY (queue, message_id)
+                )"
+                }
+            },
+        ))
+        .await?;
+
+        conn.execute(Statement::from_string(
+            backend,
+            "CREATE INDEX idx_modkit_outbox_inbox_received_at \
+             ON modkit_outbox_inbox (received_at)",
+        ))
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        let backend = conn.get_database_backend();
+
+        conn.execute(Statement::from_string(
+            backend,
+            "DROP TABLE IF EXISTS modkit_outbox_inbox",
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
+/// Adds `generation` to the partitions table for online repartitioning.
+///
+/// Existing partitions are generation 0. A repartition inserts the new
+/// partitions as the next generation, numbered above every existing
+/// `partition` of the queue so `UNIQUE (queue, partition)` still holds.
+struct AddPartitionGeneration;
+
+impl MigrationName for AddPartitionGeneration {
+    fn name(&self) -> &'static str {
+        "m006_add_modkit_outbox_partitions_generation"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddPartitionGeneration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        let backend = conn.get_database_backend();
+
+        conn.execute(Statement::from_string(
+            backend,
+            match backend {
+                DatabaseBackend::Postgres | DatabaseBackend::MySql => {
+                    "ALTER TABLE modkit_outbox_partitions \
+                     ADD COLUMN generation SMALLINT NOT NULL DEFAULT 0"
+                }
+                DatabaseBackend::Sqlite => {
+                    "ALTER TABLE modkit_outbox_partitions \
+                     ADD COLUMN generation INTEGER NOT NULL DEFAULT 0"
+                }
+            },
+        ))
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        let backend = conn.get_database_backend();
+
+        conn.execute(Statement::from_string(
+            backend,
+            "ALTER TABLE modkit_outbox_partitions DROP COLUMN generation",
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
 /// Returns all outbox migrations in dependency order.
 #[must_use]
 pub fn outbox_migrations() -> Vec<Box<dyn MigrationTrait>> {
-    vec![Box::new(CreateOutboxSchema)]
+    vec![
+        Box::new(CreateOutboxSchema),
+        Box::new(AddIncomingDeliverAt),
+        Box::new(AddBodyHeaders),
+        Box::new(CreateDedup),
+        Box::new(CreateInbox),
+        Box::new(AddPartitionGeneration),
+    ]
 }
@@ -21,6 +21,61 @@
 //! - **Leased** — handler runs outside any transaction, with lease-based
 //!   locking. Provides at-least-once delivery; handlers must be idempotent.
 //!
+//! # Delayed delivery
+//!
+//! [`Outbox::enqueue_after()`] and [`Outbox::enqueue_at()`] (or
+//! [`EnqueueMessage::deliver_after`] for batches) store a due time on the
+//! incoming row. The sequencer leaves the row in `incoming` until it is due,
+//! so it is never visible to the processor early. A drained partition that
+//! still holds delayed rows is parked in the prioritizer until the earliest
+//! becomes due, so no polling table is needed.
+//!
+//! Ordering: a delayed message is sequenced when it becomes due. It gets a
+//! sequence number after everything the partition sequenced before that
+//! moment, including messages enqueued later without a delay. Messages
+//! that are due at the same time keep their enqueue order. Delayed rows do
+//! not block the partition.
+//!
+//! # Deduplication
+//!
+//! [`EnqueueMessage::dedup_key`] makes an enqueue idempotent: a key is
+//! unique per queue for the dedup retention window
+//! ([`OutboxBuilder::dedup_retention()`], default 24h), and a repeat returns
+//! [`OutboxError::AlreadyEnqueued`] without inserting anything. The key is
+//! claimed inside the caller's transaction, so retried business
+//! transactions are safe. The vacuum deletes expired keys.
+//!
+//! # Inbox
+//!
+//! The consumer-side counterpart for messages arriving from external
+//! systems (webhooks, peer services). [`Outbox::receive()`] records the
+//! sender's message ID and enqueues the message in the caller's
+//! transaction; a redelivery returns [`Received::Duplicate`] and enqueues
+//! nothing. Inbox queues are registered via [`OutboxBuilder::inbox()`] with
+//! a transactional handler, which stamps the entry's `processed_at` in the
+//! same transaction as its own writes. Partition ordering, retries, and
+//! dead letters are those of a regular queue. The vacuum deletes entries
+//! older than [`OutboxBuilder::inbox_retention()`] (default 7 days).
+//!
+//! # Headers
+//!
+//! [`MessageHeaders`] are stored with the body and returned on
+//! [`OutboxMessage::headers`]. On enqueue the current span's W3C
+//! `traceparent` is captured (feature `otel`); per-message handlers run in
+//! an `outbox.handle` span that continues that trace. Batch handlers can
+//! use [`OutboxMessage::handler_span()`] themselves.
+//!
+//! # Repartitioning
+//!
+//! [`Outbox::repartition_queue()`] (or [`OutboxBuilder::allow_repartition()`]
+//! at startup) changes a queue's partition count without draining it. The
+//! new partitions form the queue's next generation; each instance switches
+//! to them when it registers the queue with the new count, and the previous
+//! generation is processed until drained. A new partition holds its
+//! messages back while a previous partition that may carry the same keys
+//! ([`Partitions::index_for`]) still has due or unprocessed messages, so
+//! per-key order is kept across the switch.
+//!
 //! # Usage
 //!
 //! ```ignore
@@ -36,7 +91,11 @@
 //! # Backend notes
 //!
 //! - **`PostgreSQL`** — Full support. Uses `FOR UPDATE SKIP LOCKED` for partition
-//!   locking and `INSERT ... RETURNING` for body ID retrieval.
+//!   locking and `INSERT ... RETURNING` for body ID retrieval. Optionally,
+//!   enqueue and the sequencer send `NOTIFY` wakeups that a `LISTEN`
+//!   connection on every instance feeds to its workers, so writes from other
+//!   instances are picked up without waiting for the idle poll
+//!   ([`OutboxBuilder::pg_notify()`]). The other backends poll.
 //! - **`MySQL` 8.0+** — Requires `MySQL` 8.0 or later for `FOR UPDATE SKIP LOCKED`
 //!   support (added in 8.0.1). Earlier versions will fail at runtime when
 //!   attempting to acquire partition locks. Uses `LAST_INSERT_ID()` for body IDs.
@@ -64,6 +123,10 @@
 //! Dead letters have a status lifecycle: `pending → reprocessing → resolved`
 //! (or `pending → discarded`). The [`DeadLetterStatus`] enum tracks this.
 //!
+//! To send dead letters back through their original partition instead,
+//! [`dead_letter_requeue`](Outbox::dead_letter_requeue) re-enqueues their
+//! payloads and resolves them in one call.
+//!
 //! ## Example: application-level consumption
 //!
 //! The library provides the building blocks; the application decides **when**
@@ -83,6 +146,22 @@
 //!     };
 //! }
 //! ```
+//!
+//! # Health
+//!
+//! [`Outbox::queue_health`] and [`Outbox::partition_health`] report the
+//! backlog (sequenced but unprocessed messages), incoming count, lag of the
+//! oldest unprocessed message, and processor retry state.
+//! [`Outbox::worker_stats`] exposes lifetime worker counters when stats are
+//! enabled. `modkit` mounts these and the dead-letter operations as a REST
+//! admin API (`modkit::api::outbox_admin`).
+//!
+//! With the `otel` feature the pipeline also records OpenTelemetry
+//! instruments on the global meter provider: enqueue, sequencing, and
+//! processing counters, a processor batch-duration histogram, retries, dead
+//! letters, and lease expirations, plus per-partition backlog and lag gauges
+//! sampled every [`OutboxBuilder::health_metrics_interval()`]. All carry
+//! `outbox.queue`, and per-partition ones `outbox.partition_id`.
 
 mod batch;
 mod builder;
@@ -90,9 +169,14 @@ mod core;
 mod dead_letter;
 mod dialect;
 mod handler;
+mod headers;
+mod health;
+mod inbox;
 mod manager;
+mod metrics;
 mod migrations;
 pub(crate) mod prioritizer;
+mod repartition;
 pub(crate) mod stats;
 mod strategy;
 #[doc(hidden)]
@@ -109,13 +193,19 @@ mod integration_tests;
 pub use batch::Batch;
 pub use builder::{LeasedQueueBuilder, QueueBuilder};
 pub use core::Outbox;
-pub use dead_letter::{DeadLetterFilter, DeadLetterMessage, DeadLetterScope, DeadLetterStatus};
+pub use dead_letter::{
+    DeadLetterFilter, DeadLetterFilterField, DeadLetterMessage, DeadLetterScope, DeadLetterStatus,
+};
 pub use handler::{
     HandlerResult, LeasedHandler, LeasedMessageHandler, MessageResult, OutboxMessage,
     PerMessageAdapter, TransactionalHandler, TransactionalMessageHandler,
 };
+pub use headers::{MessageHeaders, TRACEPARENT};
+pub use health::{PartitionHealth, QueueHealth};
+pub use inbox::{INBOX_MESSAGE_ID, InboxMessage, InboxQueueBuilder, Received};
 pub use manager::{OutboxBuilder, OutboxHandle};
 pub use migrations::outbox_migrations;
+pub use stats::{CategorySnapshot, StatsSnapshot};
 pub use types::{
     EnqueueMessage, LeaseConfig, OutboxError, OutboxMessageId, OutboxProfile, Partitions,
     WorkerTuning,
@@ -63,7 +63,8 @@ struct ErrorEntry {
 ///
 /// Invariant: every partition ID is in exactly one of
 /// {idle (not in any set), `pending`, `claimed`}.
-/// The `redirtied` set is a secondary flag on `claimed`.
+/// The `redirtied` set is a secondary flag on `claimed`; `deferred` is a
+/// secondary index on `pending`.
 pub struct PartitionScheduler {
     /// Priority queue: `(dirty_since, partition_id)`. Oldest first.
     pending: BTreeSet<(Instant, i64)>,
@@ -73,6 +74,10 @@ pub struct PartitionScheduler {
     claimed: HashSet<i64>,
     /// Partitions that were re-dirtied while claimed.
     redirtied: HashSet<i64>,
+    /// Pending partitions parked until their next delayed row is due,
+    /// keyed to the `dirty_since` they were parked at. Unlike an error
+    /// cooldown, a fresh push pulls these forward.
+    deferred: HashMap<i64, Instant>,
     /// Sparse error state: only partitions that have errored.
     error_state: HashMap<i64, ErrorEntry>,
     /// Monotonic timestamp of the last stale-entry sweep.
@@ -88,6 +93,7 @@ impl PartitionScheduler {
             pending_ids: HashSet::new(),
             claimed: HashSet::new(),
             redirtied: HashSet::new(),
+            deferred: HashMap::new(),
             error_state: HashMap::new(),
             last_sweep: Instant::now(),
             backoff: DEFAULT_BACKOFF,
@@ -95,10 +101,18 @@ impl PartitionScheduler {
     }
 
     /// Absorb drained inbox entries. Deduplicates against `pending_ids`
-    /// (skip) and `claimed` (insert into `redirtied`).
+    /// (skip, or pull a deferred entry forward) and `claimed` (insert into
+    /// `redirtied`).
     fn absorb(&mut self, entries: impl Iterator<Item = (i64, Instant)>) {
         for (pid, dirty_since) in entries {
             if self.pending_ids.contains(&pid) {
+                if let Some(&due) = self.deferred.get(&pid)
+                    && dirty_since < due
+                {
+                    self.pending.remove(&(due, pid));
+                    self.pending.insert((dirty_since, pid));
+                    self.deferred.remove(&pid);
+                }
                 continue;
             }
             if self.claimed.contains(&pid) {
@@ -129,6 +143,7 @@ impl PartitionScheduler {
         }
         self.pending.remove(&(dirty_since, pid));
         self.pending_ids.remove(&pid);
+        self.deferred.remove(&pid);
         self.claimed.insert(pid);
         Some((pid, dirty_since))
     }
@@ -145,6 +160,14 @@ impl PartitionScheduler {
             .is_some_and(|&(dirty_since, _)| dirty_since <= now)
     }
 
+    /// Time until the earliest pending entry becomes eligible
+    /// (`Duration::ZERO` if one is ready now), or `None` if nothing is pending.
+    fn next_ready_in(&self, now: Instant) -> Option<Duration> {
+        self.pending
+            .first()
+            .map(|&(dirty_since, _)| dirty_since.saturating_duration_since(now))
+    }
+
     /// Ack: partition processed successfully. Removes from `claimed`, clears
     /// error state. Returns [`AckResult::Redirtied`] if the partition was
     /// re-dirtied while claimed (re-inserted at `Instant::now()`).
@@ -159,6 +182,22 @@ impl PartitionScheduler {
         }
     }
 
+    /// Ack: partition processed, but it still holds rows that become due at
+    /// `due`. Parks it in `pending` until then — unless it was re-dirtied
+    /// while claimed, in which case it is re-inserted at `Instant::now()`.
+    fn ack_deferred(&mut self, pid: i64, due: Instant) -> AckResult {
+        self.claimed.remove(&pid);
+        self.error_state.remove(&pid);
+        if self.redirtied.remove(&pid) {
+            self.reinsert(pid, Instant::now());
+            AckResult::Redirtied
+        } else {
+            self.reinsert(pid, due);
+            self.deferred.insert(pid, due);
+            AckResult::Consumed
+        }
+    }
+
     /// Ack: partition was skipped or guard dropped without ack.
     /// Restores the partition at its original `dirty_since` (no penalty).
     fn ack_requeue(&mut self, pid: i64, dirty_since: Instant) {
@@ -288,6 +327,9 @@ impl Inbox {
 ///                   → IDLE
 ///
 ///   Error cooldown: dirty_since = now + 100ms * 2^error_count (max 30s).
+///   deferred(delay): like processed(), but a partition with delayed rows
+///   goes back to PENDING at now + delay. A later push_dirty() pulls it
+///   forward; an error cooldown is never pulled forward.
 ///   take() skips entries where dirty_since > now.
 ///   Error state expires after 5 min, swept every 60s.
 /// ```
@@ -418,6 +460,17 @@ impl SharedPrioritizer {
             acked: false,
         })
     }
+
+    /// Time until the earliest pending partition becomes eligible, or
+    /// `None` if nothing is pending. Sequencers sleep for this long when
+    /// [`take()`](Self::take) comes back empty, so deferred partitions and
+    /// error cooldowns are picked up on time without a producer push.
+    pub fn next_ready_in(&self) -> Option<Duration> {
+        self.scheduler
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .next_ready_in(Instant::now())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -462,6 +515,25 @@ impl PartitionGuard {
         }
     }
 
+    /// Partition was fully processed up to its due rows, but still holds
+    /// delayed rows; the earliest becomes due after `delay`. The partition
+    /// returns to `pending` at `now + delay` instead of going idle.
+    pub fn deferred(mut self, delay: Duration) {
+        self.acked = true;
+        let mut sched = self
+            .prioritizer
+            .scheduler
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let now = Instant::now();
+        let result = sched.ack_deferred(self.pid, now + delay);
+        let should_notify = matches!(result, AckResult::Redirtied) || sched.has_ready_work(now);
+        drop(sched);
+        if should_notify {
+            self.prioritizer.notify.notify_one();
+        }
+    }
+
     /// Partition lock was held by another worker (`SKIP LOCKED`).
     /// Dirty signal preserved — partition goes back to `pending` at its
     /// **original** `dirty_since` (no penalty).
@@ -657,6 +729,51 @@ mod tests {
         assert_eq!(sched.pop(t1).unwrap().0, 10);
     }
 
+    #[test]
+    fn sched_ack_deferred_parks_until_due() {
+        let mut sched = PartitionScheduler::new();
+        let now = Instant::now();
+        sched.absorb([(42, now)].into_iter());
+        sched.pop(now);
+        let due = now + Duration::from_secs(5);
+        assert!(matches!(sched.ack_deferred(42, due), AckResult::Consumed));
+        assert!(sched.pop(now).is_none());
+        assert_eq!(sched.next_ready_in(now), Some(Duration::from_secs(5)));
+        assert_eq!(sched.pop(due).unwrap().0, 42);
+        assert!(sched.deferred.is_empty());
+    }
+
+    #[test]
+    fn sched_deferred_pulled_forward_by_absorb() {
+        let mut sched = PartitionScheduler::new();
+        let now = Instant::now();
+        sched.absorb([(42, now)].into_iter());
+        sched.pop(now);
+        sched.ack_deferred(42, now + Duration::from_mins(1));
+        // Fresh enqueue on the same partition — must not wait for the delay.
+        sched.absorb([(42, now)].into_iter());
+        assert_eq!(sched.pop(now).unwrap().0, 42);
+        assert!(sched.pending.is_empty());
+    }
+
+    #[test]
+    fn sched_ack_deferred_requeues_now_if_redirtied() {
+        let mut sched = PartitionScheduler::new();
+        let now = Instant::now();
+        sched.absorb([(42, now)].into_iter());
+        sched.pop(now);
+        sched.absorb([(42, now)].into_iter()); // redirtied
+        let result = sched.ack_deferred(42, now + Duration::from_mins(1));
+        assert!(matches!(result, AckResult::Redirtied));
+        assert!(sched.pop(Instant::now()).is_some());
+    }
+
+    #[test]
+    fn sched_next_ready_in_empty_is_none() {
+        let sched = PartitionScheduler::new();
+        assert_eq!(sched.next_ready_in(Instant::now()), None);
+    }
+
     #[test]
     fn sched_sweep_removes_stale_error_entries() {
         let mut sched = PartitionScheduler::new();
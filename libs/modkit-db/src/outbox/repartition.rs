@@ -0,0 +1,229 @@
+//! Partition generations and the ordering fence of online repartitioning.
+//!
+//! A repartition inserts the new partitions of a queue as the next
+//! generation, numbered above every existing `partition` value of the
+//! queue. Running instances switch to the new generation when their
+//! repartition watcher notices it. The previous generation keeps its rows
+//! and its processors, and takes the enqueues of instances still
+//! registered with its count, e.g. during a rolling deploy or a rollback.
+
+use sea_orm::{ConnectionTrait, FromQueryResult, Statement};
+
+use super::dialect::Dialect;
+use super::types::OutboxError;
+use crate::Db;
+
+/// A row of `modkit_outbox_partitions`.
+#[derive(Debug, FromQueryResult)]
+pub struct PartitionRow {
+    pub id: i64,
+    /// The `partition` column.
+    pub slot: i16,
+    pub generation: i16,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct GenerationRow {
+    generation: Option<i16>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct PendingRow {
+    partition_id: i64,
+}
+
+/// Partitions of a queue, split into the current and the previous generation.
+#[derive(Debug)]
+pub struct Generations {
+    pub generation: i16,
+    /// Partition IDs of the current generation, by index.
+    pub current: Vec<i64>,
+    /// Partition IDs of the previous generation, by index. Empty for
+    /// generation 0.
+    pub previous: Vec<i64>,
+    /// Highest `partition` value in use by the queue.
+    max_slot: i16,
+}
+
+impl Generations {
+    /// Group the rows of one queue, ordered by `partition`.
+    #[must_use]
+    pub fn from_rows(rows: &[PartitionRow]) -> Self {
+        let generation = rows.iter().map(|r| r.generation).max().unwrap_or(0);
+        let ids_of = |g: i16| -> Vec<i64> {
+            rows.iter()
+                .filter(|r| r.generation == g)
+                .map(|r| r.id)
+                .collect()
+        };
+        Self {
+            generation,
+            current: ids_of(generation),
+            previous: if generation > 0 {
+                ids_of(generation - 1)
+            } else {
+                Vec::new()
+            },
+            max_slot: rows.iter().map(|r| r.slot).max().unwrap_or(-1),
+        }
+    }
+
+    /// Generation number and `partition` values for a next generation of
+    /// `count` partitions. `None` when the `SMALLINT` column is exhausted.
+    #[must_use]
+    pub fn next(&self, count: u16) -> Option<(i16, Vec<i16>)> {
+        let generation = self.generation.checked_add(1)?;
+        let slots = (1..=count)
+            .map(|i| self.max_slot.checked_add(i16::try_from(i).ok()?))
+            .collect::<Option<Vec<_>>>()?;
+        Some((generation, slots))
+    }
+}
+
+/// Indexes of the previous generation that may hold keys now routed to
+/// `index` of the current generation: those congruent to `index` modulo
+/// the smaller of the two counts (see [`Partitions::index_for`]).
+///
+/// [`Partitions::index_for`]: super::Partitions::index_for
+pub fn fence_sources(
+    index: usize,
+    current_count: usize,
+    previous_count: usize,
+) -> impl Iterator<Item = usize> {
+    let modulus = current_count.min(previous_count).max(1);
+    (0..previous_count).filter(move |i| i % modulus == index % modulus)
+}
+
+/// Previous partition generation of a registered queue. Its partitions
+/// keep processors and fence the current generation, as instances still
+/// registered with its count enqueue into it.
+#[derive(Debug, Clone)]
+pub struct PreviousGeneration {
+    pub generation: i16,
+    /// Partition IDs, by index.
+    pub ids: Vec<i64>,
+}
+
+/// Holds a current-generation partition back while the previous-generation
+/// partitions that may contain the same keys have work.
+#[derive(Debug, Clone)]
+pub struct PartitionFence {
+    queue: String,
+    generation: i16,
+    waits_on: Vec<i64>,
+}
+
+impl PartitionFence {
+    #[must_use]
+    pub fn new(queue: String, generation: i16, waits_on: Vec<i64>) -> Self {
+        Self {
+            queue,
+            generation,
+            waits_on,
+        }
+    }
+
+    /// Whether every awaited partition is drained right now. Checked afresh
+    /// each time: an instance that has not switched generations yet can
+    /// still enqueue into a drained partition, which puts the fence back up.
+    pub async fn is_lifted(&self, db: &Db) -> Result<bool, OutboxError> {
+        let conn = db.sea_internal();
+        let pending = pending_partitions(&conn, &self.queue, self.generation).await?;
+        Ok(!self.waits_on.iter().any(|id| pending.contains(id)))
+    }
+}
+
+/// Newest partition generation of a queue, `None` if it has no partitions.
+pub async fn current_generation<C: ConnectionTrait>(
+    conn: &C,
+    queue: &str,
+) -> Result<Option<i16>, OutboxError> {
+    let backend = conn.get_database_backend();
+    let row = GenerationRow::find_by_statement(Statement::from_sql_and_values(
+        backend,
+        Dialect::from(backend).current_generation(),
+        [queue.into()],
+    ))
+    .one(conn)
+    .await?;
+    Ok(row.and_then(|r| r.generation))
+}
+
+/// IDs of the partitions of `generation` that still have an incoming
+/// message, due or not, or a sequenced message not yet processed.
+pub async fn pending_partitions<C: ConnectionTrait>(
+    conn: &C,
+    queue: &str,
+    generation: i16,
+) -> Result<Vec<i64>, OutboxError> {
+    let backend = conn.get_database_backend();
+    let rows = PendingRow::find_by_statement(Statement::from_sql_and_values(
+        backend,
+        Dialect::from(backend).pending_generation_partitions(),
+        [queue.into(), generation.into()],
+    ))
+    .all(conn)
+    .await?;
+    Ok(rows.into_iter().map(|r| r.partition_id).collect())
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn row(id: i64, slot: i16, generation: i16) -> PartitionRow {
+        PartitionRow {
+            id,
+            slot,
+            generation,
+        }
+    }
+
+    #[test]
+    fn from_rows_splits_current_and_previous() {
+        let rows = [
+            row(1, 0, 0),
+            row(2, 1, 0),
+            row(3, 2, 1),
+            row(4, 3, 1),
+            row(5, 4, 2),
+            row(6, 5, 2),
+            row(7, 6, 2),
+            row(8, 7, 2),
+        ];
+        let g = Generations::from_rows(&rows);
+        assert_eq!(g.generation, 2);
+        assert_eq!(g.current, vec![5, 6, 7, 8]);
+        assert_eq!(g.previous, vec![3, 4]);
+        assert_eq!(g.next(2), Some((3, vec![8, 9])));
+    }
+
+    #[test]
+    fn from_rows_first_generation_has_no_previous() {
+        let g = Generations::from_rows(&[row(1, 0, 0), row(2, 1, 0)]);
+        assert_eq!(g.generation, 0);
+        assert_eq!(g.current, vec![1, 2]);
+        assert!(g.previous.is_empty());
+    }
+
+    #[test]
+    fn next_fails_when_slots_are_exhausted() {
+        let g = Generations::from_rows(&[row(1, i16::MAX - 1, 4)]);
+        assert_eq!(g.next(1), Some((5, vec![i16::MAX])));
+        assert_eq!(g.next(2), None);
+    }
+
+    #[test]
+    fn fence_sources_when_growing() {
+        // 2 -> 4: new index 3 receives keys of old index 1 only.
+        assert_eq!(fence_sources(3, 4, 2).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(fence_sources(0, 4, 2).collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[test]
+    fn fence_sources_when_shrinking() {
+        // 8 -> 2: new index 1 receives keys of old indexes 1, 3, 5, 7.
+        assert_eq!(fence_sources(1, 2, 8).collect::<Vec<_>>(), vec![1, 3, 5, 7]);
+    }
+}
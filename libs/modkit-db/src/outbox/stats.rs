@@ -62,6 +62,20 @@ impl StatsListener {
         }
     }
 
+    /// Read all counters without draining them.
+    pub fn peek(&self) -> StatsSnapshot {
+        let inner = &self.inner;
+        StatsSnapshot {
+            executions: inner.executions.load(Ordering::Relaxed),
+            noop_execs: inner.noop_execs.load(Ordering::Relaxed),
+            failures: inner.failures.load(Ordering::Relaxed),
+            total_exec_us: inner.total_exec_us.load(Ordering::Relaxed),
+            max_exec_us: inner.max_exec_us.load(Ordering::Relaxed),
+            total_idle_us: inner.total_idle_us.load(Ordering::Relaxed),
+            total_msgs: inner.total_msgs.load(Ordering::Relaxed),
+        }
+    }
+
     /// Atomically drain all counters and return a snapshot.
     pub fn snapshot_and_reset(&self) -> StatsSnapshot {
         let inner = &self.inner;
@@ -151,27 +165,37 @@ impl<P: Send + Sync + 'static> WorkerListener<P> for StatsListener {
 /// Point-in-time snapshot of a single worker's counters.
 #[derive(Debug, Clone, Default)]
 pub struct StatsSnapshot {
+    /// Worker executions.
     pub executions: u64,
+    /// Executions that found no work.
     pub noop_execs: u64,
+    /// Executions that returned an error.
     pub failures: u64,
+    /// Total execution time in microseconds.
     pub total_exec_us: u64,
+    /// Longest single execution in microseconds.
     pub max_exec_us: u64,
+    /// Total time spent idle between executions in microseconds.
     pub total_idle_us: u64,
+    /// Messages handled across all executions.
     pub total_msgs: u64,
 }
 
 impl StatsSnapshot {
     /// Average execution time in microseconds (0 if no executions).
+    #[must_use]
     pub fn avg_exec_us(&self) -> u64 {
         self.total_exec_us.checked_div(self.executions).unwrap_or(0)
     }
 
     /// Average messages per execution (0 if no executions).
+    #[must_use]
     pub fn avg_msgs(&self) -> u64 {
         self.total_msgs.checked_div(self.executions).unwrap_or(0)
     }
 
     /// Returns true if no activity was recorded.
+    #[must_use]
     pub fn is_empty(&self) -> bool {
         self.executions == 0 && self.failures == 0
     }
@@ -191,7 +215,9 @@ impl StatsSnapshot {
 /// Aggregated snapshot for a category of workers.
 #[derive(Debug, Clone)]
 pub struct CategorySnapshot {
+    /// Number of workers in the category.
     pub workers: usize,
+    /// Counters summed over the category's workers.
     pub snapshot: StatsSnapshot,
 }
 
@@ -202,21 +228,28 @@ pub struct CategorySnapshot {
 /// Registry of per-worker stats listeners.
 ///
 /// Built during `OutboxBuilder::start()`, then shared via `Arc` with the
-/// `StatsReporter`. Immutable after construction.
+/// `StatsReporter` and the [`Outbox`](super::Outbox). The listener set is
+/// immutable after construction; drained counters are folded into
+/// lifetime totals.
 pub struct StatsRegistry {
     listeners: Vec<(String, StatsListener)>,
+    totals: Mutex<Vec<StatsSnapshot>>,
 }
 
 impl StatsRegistry {
     pub fn new() -> Self {
         Self {
             listeners: Vec::new(),
+            totals: Mutex::new(Vec::new()),
         }
     }
 
     /// Register a listener under a category (e.g. `"processor"`, `"sequencer"`).
     pub fn register(&mut self, category: String, listener: StatsListener) {
         self.listeners.push((category, listener));
+        if let Ok(totals) = self.totals.get_mut() {
+            totals.push(StatsSnapshot::default());
+        }
     }
 
     /// Drain all listeners and return aggregated `(category, snapshot)` pairs.
@@ -224,33 +257,69 @@ impl StatsRegistry {
     /// Workers sharing the same category are merged into a single
     /// [`CategorySnapshot`] with summed counters and a `workers` count.
     pub fn snapshot_all(&self) -> Vec<(String, CategorySnapshot)> {
-        // Drain all listeners, preserving insertion order for categories.
-        let mut order: Vec<String> = Vec::new();
-        let mut map: std::collections::HashMap<String, CategorySnapshot> =
-            std::collections::HashMap::new();
-
-        for (category, listener) in &self.listeners {
-            let snap = listener.snapshot_and_reset();
-            if let Some(cat) = map.get_mut(category) {
-                cat.workers += 1;
-                cat.snapshot.merge(&snap);
-            } else {
-                order.push(category.clone());
-                map.insert(
-                    category.clone(),
-                    CategorySnapshot {
-                        workers: 1,
-                        snapshot: snap,
-                    },
-                );
-            }
-        }
+        let mut totals = self.totals.lock().ok();
+        let snaps = self
+            .listeners
+            .iter()
+            .enumerate()
+            .map(|(i, (category, listener))| {
+                let snap = listener.snapshot_and_reset();
+                if let Some(total) = totals.as_mut().and_then(|t| t.get_mut(i)) {
+                    total.merge(&snap);
+                }
+                (category, snap)
+            });
+        aggregate(snaps)
+    }
+
+    /// Lifetime counters per category, including activity not yet drained
+    /// by the reporter. Does not reset anything.
+    pub fn totals_all(&self) -> Vec<(String, CategorySnapshot)> {
+        let totals = self.totals.lock().ok();
+        let snaps = self
+            .listeners
+            .iter()
+            .enumerate()
+            .map(|(i, (category, listener))| {
+                let mut snap = totals
+                    .as_ref()
+                    .and_then(|t| t.get(i).cloned())
+                    .unwrap_or_default();
+                snap.merge(&listener.peek());
+                (category, snap)
+            });
+        aggregate(snaps)
+    }
+}
 
-        order
-            .into_iter()
-            .filter_map(|cat| map.remove(&cat).map(|cs| (cat, cs)))
-            .collect()
+/// Merge per-worker snapshots by category, preserving first-seen order.
+fn aggregate<'a>(
+    snaps: impl Iterator<Item = (&'a String, StatsSnapshot)>,
+) -> Vec<(String, CategorySnapshot)> {
+    let mut order: Vec<String> = Vec::new();
+    let mut map: std::collections::HashMap<String, CategorySnapshot> =
+        std::collections::HashMap::new();
+
+    for (category, snap) in snaps {
+        if let Some(cat) = map.get_mut(category) {
+            cat.workers += 1;
+            cat.snapshot.merge(&snap);
+        } else {
+            order.push(category.clone());
+            map.insert(
+                category.clone(),
+                CategorySnapshot {
+                    workers: 1,
+                    snapshot: snap,
+                },
+            );
+        }
     }
+
+    order
+        .into_iter()
+        .filter_map(|cat| map.remove(&cat).map(|cs| (cat, cs)))
+        .collect()
 }
 
 // ---------------------------------------------------------------------------
@@ -504,6 +573,22 @@ mod tests {
         assert_eq!(categories[1].1.snapshot.executions, 7);
     }
 
+    #[test]
+    fn registry_totals_survive_drains() {
+        let mut registry = StatsRegistry::new();
+        let l = StatsListener::new(noop_extractor());
+        registry.register("vacuum".to_owned(), l.clone());
+
+        l.inner.executions.store(4, Ordering::Relaxed);
+        registry.snapshot_all();
+        l.inner.executions.store(2, Ordering::Relaxed);
+
+        let totals = registry.totals_all();
+        assert_eq!(totals[0].1.snapshot.executions, 6);
+        // Peeking leaves the undrained counters for the reporter.
+        assert_eq!(l.inner.executions.load(Ordering::Relaxed), 2);
+    }
+
     #[test]
     fn reporter_suppresses_empty() {
         let registry = Arc::new(StatsRegistry::new());
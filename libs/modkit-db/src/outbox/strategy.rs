@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use super::batch::Batch;
 use super::dialect::Dialect;
 use super::handler::{HandlerResult, LeasedHandler, OutboxMessage, TransactionalHandler};
+use super::headers::MessageHeaders;
+use super::metrics::PartitionMetrics;
 use super::types::{LeaseConfig, OutboxError};
 use crate::Db;
 use sea_orm::{ConnectionTrait, DbBackend, FromQueryResult, Statement, TransactionTrait};
@@ -13,6 +15,7 @@ pub struct ProcessContext<'a> {
     pub backend: DbBackend,
     pub dialect: Dialect,
     pub partition_id: i64,
+    pub metrics: &'a PartitionMetrics,
 }
 
 /// Sealed trait for compile-time processing mode dispatch.
@@ -64,6 +67,7 @@ struct BodyRow {
     id: i64,
     payload: Vec<u8>,
     payload_type: String,
+    headers: Option<String>,
     created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -119,6 +123,7 @@ async fn read_messages(
             seq: row.seq,
             payload: body.payload.clone(),
             payload_type: body.payload_type.clone(),
+            headers: MessageHeaders::decode(body.headers.as_deref()),
             created_at: body.created_at,
             attempts: proc_row.attempts,
         });
@@ -297,6 +302,9 @@ impl ProcessingStrategy for TransactionalStrategy {
         .await?;
 
         txn.commit().await?;
+        if matches!(result, HandlerResult::Reject { .. }) {
+            ctx.metrics.dead_lettered(u64::from(count));
+        }
 
         Ok(Some(ProcessResult {
             count,
@@ -465,6 +473,7 @@ async fn lease_guarded_ack(
 
     // All three branches persist rejections first, then differ in cursor behavior.
     persist_rejections(&ack_txn, ctx, msgs, rejections).await?;
+    let mut dead_lettered = rejections.len();
 
     let lease_ok = match &result {
         HandlerResult::Success => {
@@ -489,6 +498,7 @@ async fn lease_guarded_ack(
         HandlerResult::Reject { reason } => {
             // Dead-letter the unprocessed tail (rejections already persisted above).
             let skip = (processed as usize).min(msgs.len());
+            dead_lettered += msgs.len() - skip;
             for msg in &msgs[skip..] {
                 insert_dead_letter(&ack_txn, ctx, msg, reason).await?;
             }
@@ -504,10 +514,13 @@ async fn lease_guarded_ack(
             "lease expired before ack, another processor may have taken over"
         );
         ack_txn.rollback().await?;
+        ctx.metrics.lease_expired();
         return Ok(None);
     }
 
     ack_txn.commit().await?;
+    ctx.metrics
+        .dead_lettered(u64::try_from(dead_lettered).unwrap_or(u64::MAX));
 
     Ok(Some(ProcessResult {
         count,
@@ -601,8 +614,11 @@ impl ProcessingStrategy for LeasedStrategy {
 
         let result = tokio::time::timeout_at(deadline, self.handler.handle(&mut batch))
             .await
-            .unwrap_or_else(|_| HandlerResult::Retry {
-                reason: "lease expired".into(),
+            .unwrap_or_else(|_| {
+                ctx.metrics.lease_expired();
+                HandlerResult::Retry {
+                    reason: "lease expired".into(),
+                }
             });
 
         // Phase 3: lease-guarded ack
@@ -2,12 +2,23 @@ use std::time::Duration;
 
 use thiserror::Error;
 
+use super::headers::MessageHeaders;
+
 /// Default batch size for the sequencer (rows per cycle).
 pub const DEFAULT_SEQUENCER_BATCH_SIZE: u32 = 1000;
 
 /// Default poll interval (safety net fallback for sequencer and processors).
 pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_mins(10);
 
+/// Default retention window for producer dedup keys (24 hours).
+pub const DEFAULT_DEDUP_RETENTION: Duration = Duration::from_hours(24);
+
+/// Default retention window for inbox entries (7 days).
+pub const DEFAULT_INBOX_RETENTION: Duration = Duration::from_hours(7 * 24);
+
+/// Default interval of the repartition check of running pipelines (10 seconds).
+pub const DEFAULT_REPARTITION_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
 /// Default partition batch limit for the sequencer (max partitions per cycle).
 pub const DEFAULT_PARTITION_BATCH_LIMIT: u32 = 128;
 
@@ -50,6 +61,18 @@ impl Partitions {
     pub const fn count(self) -> u16 {
         self.0
     }
+
+    /// Partition index for a key hash: `key_hash % count`.
+    ///
+    /// Producers that route keys with this mapping keep per-key ordering
+    /// across [`Outbox::repartition_queue`](super::Outbox::repartition_queue):
+    /// both counts are powers of 2, so a key's old and new partitions share
+    /// `key_hash % min(old, new)`, which is what the repartition fence uses.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)] // the result is below 64
+    pub fn index_for(self, key_hash: u64) -> u32 {
+        (key_hash % u64::from(self.0)) as u32
+    }
 }
 
 /// Identifier for an enqueued outbox message (the `modkit_outbox_incoming.id`).
@@ -70,6 +93,16 @@ pub struct EnqueueMessage<'a> {
     pub payload: Vec<u8>,
     /// Type tag for the payload (e.g. `"application/json"`, schema name).
     pub payload_type: &'a str,
+    /// Keep the message invisible to the processor for this long after
+    /// enqueue. `None` delivers immediately. See [`Outbox::enqueue_after`].
+    pub deliver_after: Option<Duration>,
+    /// Headers persisted with the message and handed to the handler. The
+    /// current span's `traceparent` is added unless one is already set.
+    pub headers: MessageHeaders,
+    /// Producer-side idempotency key, unique per queue within the dedup
+    /// retention window. A repeat is rejected with
+    /// [`OutboxError::AlreadyEnqueued`] and nothing is inserted.
+    pub dedup_key: Option<&'a str>,
 }
 
 /// Errors from the outbox subsystem.
@@ -78,6 +111,9 @@ pub enum OutboxError {
     #[error("queue '{0}' is not registered")]
     QueueNotRegistered(String),
 
+    #[error("partition {0} is not registered")]
+    PartitionNotRegistered(i64),
+
     #[error("partition {partition} is out of range for queue '{queue}' (max {max})")]
     PartitionOutOfRange {
         queue: String,
@@ -95,20 +131,68 @@ pub enum OutboxError {
         found: usize,
     },
 
+    #[error("queue '{0}' is still draining the partitions of its previous repartition")]
+    RepartitionInProgress(String),
+
+    #[error("queue '{0}' has no partition numbers left for another repartition")]
+    RepartitionLimitReached(String),
+
+    #[error(
+        "queue '{queue}' was repartitioned twice since this instance registered \
+         {registered} partitions and now has {current}; it must register the new count \
+         before enqueueing"
+    )]
+    StalePartitionCount {
+        queue: String,
+        registered: usize,
+        current: usize,
+    },
+
     #[error("invalid queue name: '{0}'")]
     InvalidQueueName(String),
 
     #[error("invalid payload type: '{0}'")]
     InvalidPayloadType(String),
 
+    #[error("invalid message header: {0}")]
+    InvalidHeader(String),
+
+    #[error("invalid dedup key: '{0}'")]
+    InvalidDedupKey(String),
+
+    #[error("message with dedup key '{dedup_key}' is already enqueued on queue '{queue}'")]
+    AlreadyEnqueued { queue: String, dedup_key: String },
+
+    #[error("invalid inbox message id: '{0}'")]
+    InvalidMessageId(String),
+
+    #[error("delivery delay {delay:?} exceeds maximum {max:?}")]
+    DeliveryDelayTooLarge { delay: Duration, max: Duration },
+
     #[error(transparent)]
     Database(#[from] sea_orm::DbErr),
 }
 
 /// Configuration for the outbox subsystem.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct OutboxConfig {
     pub sequencer: SequencerConfig,
+    /// How long a dedup key blocks repeats before it may be reused and is
+    /// removed by the vacuum. Default: 24h.
+    pub dedup_retention: Duration,
+    /// Send `NOTIFY` wakeups on enqueue and sequencing (Postgres only).
+    /// Default: false.
+    pub pg_notify: bool,
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            sequencer: SequencerConfig::default(),
+            dedup_retention: DEFAULT_DEDUP_RETENTION,
+            pg_notify: false,
+        }
+    }
 }
 
 /// Configuration for the sequencer background task.
@@ -621,6 +705,21 @@ mod tests {
             .validate();
     }
 
+    #[test]
+    fn partitions_index_for_is_stable_across_power_of_two_counts() {
+        for hash in [0, 1, 7, 12345, u64::MAX] {
+            let small = Partitions::of(4).index_for(hash);
+            let large = Partitions::of(16).index_for(hash);
+            assert!(small < 4 && large < 16);
+            assert_eq!(large % 4, small);
+        }
+    }
+
+    #[test]
+    fn outbox_config_does_not_notify_by_default() {
+        assert!(!OutboxConfig::default().pg_notify);
+    }
+
     #[test]
     fn lease_config_default() {
         let cfg = LeaseConfig::default();
@@ -6,6 +6,9 @@ const MAX_QUEUE_NAME_LEN: usize = 1024;
 /// Maximum payload type length.
 const MAX_PAYLOAD_TYPE_LEN: usize = 1024;
 
+/// Maximum dedup key / inbox message ID length (fits VARCHAR(255) column).
+const MAX_DEDUP_KEY_LEN: usize = 255;
+
 /// Validate a queue name: `[a-zA-Z0-9._-]{1,1024}`, must start and end with
 /// alphanumeric.
 pub fn validate_queue_name(name: &str) -> Result<(), OutboxError> {
@@ -43,6 +46,22 @@ pub fn validate_payload_type(payload_type: &str) -> Result<(), OutboxError> {
     Ok(())
 }
 
+/// Validate a dedup key: 1-255 printable ASCII chars (`0x20..=0x7E`).
+pub fn validate_dedup_key(key: &str) -> Result<(), OutboxError> {
+    if key.is_empty()
+        || key.len() > MAX_DEDUP_KEY_LEN
+        || !key.bytes().all(|b| (0x20..=0x7E).contains(&b))
+    {
+        return Err(OutboxError::InvalidDedupKey(key.to_owned()));
+    }
+    Ok(())
+}
+
+/// Validate an inbox message ID: same rules as a dedup key.
+pub fn validate_message_id(id: &str) -> Result<(), OutboxError> {
+    validate_dedup_key(id).map_err(|_| OutboxError::InvalidMessageId(id.to_owned()))
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -173,4 +192,29 @@ mod tests {
     fn payload_type_non_ascii() {
         assert!(validate_payload_type("\u{0434}\u{0430}\u{043d}\u{043d}\u{044b}\u{0435}").is_err());
     }
+
+    // --- Dedup key ---
+
+    #[test]
+    fn dedup_key_valid() {
+        assert!(validate_dedup_key("order-42:created").is_ok());
+        assert!(validate_dedup_key(&"k".repeat(255)).is_ok());
+    }
+
+    #[test]
+    fn dedup_key_invalid() {
+        assert!(validate_dedup_key("").is_err());
+        assert!(validate_dedup_key(&"k".repeat(256)).is_err());
+        assert!(validate_dedup_key("tab\there").is_err());
+        assert!(validate_dedup_key("\u{0434}").is_err());
+    }
+
+    #[test]
+    fn message_id_maps_error_variant() {
+        assert!(validate_message_id("evt_01HZX").is_ok());
+        assert!(matches!(
+            validate_message_id(""),
+            Err(OutboxError::InvalidMessageId(_))
+        ));
+    }
 }
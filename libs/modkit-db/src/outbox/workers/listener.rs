@@ -0,0 +1,113 @@
+//! Cross-instance wakeups via Postgres `LISTEN`/`NOTIFY`.
+//!
+//! Enqueue notifies [`INCOMING_CHANNEL`] and the sequencer notifies
+//! [`OUTGOING_CHANNEL`] in the writing transaction, with the partition ID as
+//! payload. Every instance runs one listener that turns the notifications
+//! into the same in-process signals a local enqueue produces, so an idle
+//! worker reacts to another instance's writes without waiting for its poll
+//! interval. A repartition notifies [`REPARTITION_CHANNEL`] with the queue
+//! name, which wakes the repartition watcher. Polling stays in place as the
+//! fallback for lost notifications.
+
+/// Channel notified when a partition receives incoming messages.
+pub const INCOMING_CHANNEL: &str = "modkit_outbox_incoming";
+
+/// Channel notified when a partition receives sequenced messages.
+pub const OUTGOING_CHANNEL: &str = "modkit_outbox_outgoing";
+
+/// Channel notified when a queue gets a new partition generation.
+pub const REPARTITION_CHANNEL: &str = "modkit_outbox_repartition";
+
+#[cfg(feature = "pg")]
+pub use pg::run;
+
+#[cfg(feature = "pg")]
+mod pg {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use sqlx::postgres::{PgListener, PgNotification};
+    use tokio_util::sync::CancellationToken;
+    use tracing::{debug, warn};
+
+    use super::{INCOMING_CHANNEL, OUTGOING_CHANNEL, REPARTITION_CHANNEL};
+    use crate::Db;
+    use crate::outbox::core::Outbox;
+    use crate::outbox::prioritizer::SharedPrioritizer;
+
+    /// Delay before connecting again after a listener error.
+    const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+    /// Listen until `cancel` fires, reconnecting after errors.
+    pub async fn run(
+        db: Db,
+        outbox: Arc<Outbox>,
+        prioritizer: Arc<SharedPrioritizer>,
+        cancel: CancellationToken,
+    ) {
+        loop {
+            let result = tokio::select! {
+                () = cancel.cancelled() => return,
+                r = listen(&db, &outbox, &prioritizer) => r,
+            };
+            if let Err(e) = result {
+                warn!(error = %e, "outbox listener: connection failed, retrying");
+            }
+            tokio::select! {
+                () = cancel.cancelled() => return,
+                () = tokio::time::sleep(RECONNECT_DELAY) => {}
+            }
+        }
+    }
+
+    async fn listen(
+        db: &Db,
+        outbox: &Outbox,
+        prioritizer: &SharedPrioritizer,
+    ) -> Result<(), sqlx::Error> {
+        let sea = db.sea_internal();
+        let mut listener = PgListener::connect_with(sea.get_postgres_connection_pool()).await?;
+        listener
+            .listen_all([INCOMING_CHANNEL, OUTGOING_CHANNEL, REPARTITION_CHANNEL])
+            .await?;
+        debug!("outbox listener: listening");
+
+        loop {
+            if let Some(n) = listener.try_recv().await? {
+                dispatch(&n, outbox, prioritizer);
+            } else {
+                // The connection dropped; the next call reconnects and
+                // listens again. Anything notified meanwhile is picked up by
+                // polling.
+                debug!("outbox listener: connection lost, reconnecting");
+            }
+        }
+    }
+
+    fn dispatch(n: &PgNotification, outbox: &Outbox, prioritizer: &SharedPrioritizer) {
+        if n.channel() == REPARTITION_CHANNEL {
+            outbox.notify_repartitioned(n.payload());
+            return;
+        }
+        let Ok(partition_id) = n.payload().parse::<i64>() else {
+            warn!(
+                channel = n.channel(),
+                payload = n.payload(),
+                "outbox listener: ignoring malformed notification"
+            );
+            return;
+        };
+        // The channels are shared by every outbox in the database.
+        if !outbox.owns_partition(partition_id) {
+            return;
+        }
+        match n.channel() {
+            INCOMING_CHANNEL => {
+                prioritizer.push_dirty(partition_id);
+                outbox.flush();
+            }
+            OUTGOING_CHANNEL => outbox.notify_partition(partition_id),
+            _ => {}
+        }
+    }
+}
@@ -1,4 +1,6 @@
+pub mod listener;
 pub mod processor;
 pub mod reconciler;
+pub mod repartition_watcher;
 pub mod sequencer;
 pub mod vacuum;
@@ -1,10 +1,12 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use sea_orm::ConnectionTrait;
 use tokio_util::sync::CancellationToken;
 use tracing::debug;
 
 use super::super::handler::HandlerResult;
+use super::super::metrics::PartitionMetrics;
+use super::super::repartition::PartitionFence;
 use super::super::strategy::{ProcessContext, ProcessingStrategy};
 use super::super::taskward::{Directive, WorkerAction};
 use super::super::types::OutboxError;
@@ -157,6 +159,10 @@ pub struct PartitionProcessor<S: ProcessingStrategy> {
     tuning: super::super::types::WorkerTuning,
     db: Db,
     partition_mode: PartitionMode,
+    /// Set when the queue has a previous partition generation, which may
+    /// hold messages that must be processed first. Checked every cycle.
+    fence: Option<PartitionFence>,
+    metrics: PartitionMetrics,
 }
 
 impl<S: ProcessingStrategy> PartitionProcessor<S> {
@@ -165,6 +171,7 @@ impl<S: ProcessingStrategy> PartitionProcessor<S> {
         partition_id: i64,
         tuning: super::super::types::WorkerTuning,
         db: Db,
+        metrics: PartitionMetrics,
     ) -> Self {
         Self {
             strategy,
@@ -172,8 +179,17 @@ impl<S: ProcessingStrategy> PartitionProcessor<S> {
             tuning,
             db,
             partition_mode: PartitionMode::new(),
+            fence: None,
+            metrics,
         }
     }
+
+    /// Hold processing back until `fence` lifts.
+    #[must_use]
+    pub fn with_fence(mut self, fence: Option<PartitionFence>) -> Self {
+        self.fence = fence;
+        self
+    }
 }
 
 impl<S: ProcessingStrategy> WorkerAction for PartitionProcessor<S> {
@@ -184,6 +200,16 @@ impl<S: ProcessingStrategy> WorkerAction for PartitionProcessor<S> {
         &mut self,
         _cancel: &CancellationToken,
     ) -> Result<Directive<ProcessorReport>, OutboxError> {
+        if let Some(fence) = &self.fence
+            && !fence.is_lifted(&self.db).await?
+        {
+            return Ok(Directive::Idle(ProcessorReport {
+                partition_id: self.partition_id,
+                messages_processed: 0,
+                handler_result: HandlerResult::Success,
+            }));
+        }
+
         let (backend, dialect) = {
             let sea_conn = self.db.sea_internal();
             let b = sea_conn.get_database_backend();
@@ -199,11 +225,15 @@ impl<S: ProcessingStrategy> WorkerAction for PartitionProcessor<S> {
             backend,
             dialect,
             partition_id: self.partition_id,
+            metrics: &self.metrics,
         };
 
+        let started = Instant::now();
         let result = self.strategy.process(&ctx, effective_size).await?;
 
         if let Some(pr) = result {
+            self.metrics
+                .batch(started.elapsed(), pr.count, &pr.handler_result);
             let has_more = pr.count >= effective_size;
             let clamped_pc = pr.processed_count.map(|pc| pc.min(pr.count));
             self.partition_mode.transition(
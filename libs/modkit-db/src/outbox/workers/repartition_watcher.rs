@@ -0,0 +1,102 @@
+//! Picks up repartitions made while the pipeline runs.
+//!
+//! The watcher checks the cached partition generation of every registered
+//! queue on an interval, and right away when the listener reports a
+//! repartition. When a queue moved to a new generation it switches the
+//! outbox caches over and starts processors for the new partitions, so a
+//! repartition needs no restart to be processed.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{Notify, Semaphore};
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use super::super::builder::{ProcessorFactory, SpawnContext};
+use super::super::core::Outbox;
+use super::super::taskward::TaskSet;
+use super::super::types::WorkerTuning;
+use crate::Db;
+
+/// Refreshes partition generations and spawns processors for new ones.
+pub struct RepartitionWatcher {
+    pub outbox: Arc<Outbox>,
+    pub db: Db,
+    /// Processor factories of the declared queues, by queue name.
+    pub factories: HashMap<String, Box<dyn ProcessorFactory>>,
+    pub processor_sem: Arc<Semaphore>,
+    pub tuning: WorkerTuning,
+    pub interval: Duration,
+}
+
+impl RepartitionWatcher {
+    /// Watch until `cancel` fires, then stop the processors it started.
+    pub async fn run(mut self, cancel: CancellationToken) {
+        let mut processors = TaskSet::new(cancel.child_token());
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => break,
+                () = tokio::time::sleep(self.interval) => {}
+                () = self.outbox.repartitioned().notified() => {}
+            }
+            self.check(&mut processors, &cancel).await;
+        }
+        processors.shutdown().await;
+    }
+
+    /// Switch queues with a new generation and start their processors.
+    async fn check(&mut self, processors: &mut TaskSet, cancel: &CancellationToken) {
+        let switched = match self.outbox.refresh_generations(&self.db).await {
+            Ok(switched) => switched,
+            Err(e) => {
+                warn!(error = %e, "repartition watcher: failed to refresh generations");
+                return;
+            }
+        };
+        for (queue, ids) in switched {
+            let mut notifies = Vec::with_capacity(ids.len());
+            for &pid in &ids {
+                let notify = Arc::new(Notify::new());
+                self.outbox
+                    .add_partition_notify(pid, Arc::clone(&notify))
+                    .await;
+                notifies.push((pid, notify));
+            }
+            self.spawn_processors(processors, cancel, &queue, notifies);
+        }
+    }
+
+    fn spawn_processors(
+        &self,
+        processors: &mut TaskSet,
+        cancel: &CancellationToken,
+        queue: &str,
+        notifies: Vec<(i64, Arc<Notify>)>,
+    ) {
+        let Some(factory) = self.factories.get(queue) else {
+            return;
+        };
+        for (pid, notify) in notifies {
+            // Workers wait for a start signal before their first cycle.
+            let start_notify = Arc::new(Notify::new());
+            start_notify.notify_one();
+            let ctx = SpawnContext {
+                pid,
+                db: self.db.clone(),
+                cancel: cancel.clone(),
+                partition_notify: notify,
+                processor_sem: Arc::clone(&self.processor_sem),
+                start_notify,
+                outbox: Arc::clone(&self.outbox),
+                // The stats registry is sealed once the pipeline started.
+                stats_registry: None,
+                tuning: self.tuning.clone(),
+                fence: self.outbox.partition_fence(pid),
+            };
+            let (name, future) = factory.spawn(ctx);
+            processors.spawn(name, future);
+        }
+    }
+}
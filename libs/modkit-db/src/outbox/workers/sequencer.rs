@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use sea_orm::{ConnectionTrait, DbBackend, FromQueryResult, Statement, TransactionTrait};
 use tokio_util::sync::CancellationToken;
@@ -9,8 +10,10 @@ use super::super::dialect::{AllocSql, Dialect};
 use super::super::prioritizer::SharedPrioritizer;
 use super::super::taskward::{Directive, WorkerAction};
 use super::super::types::{OutboxError, SequencerConfig};
+use super::listener::OUTGOING_CHANNEL;
 use crate::Db;
 use crate::contention::is_retryable_contention;
+use crate::secure::SeaOrmRunner;
 
 /// Report emitted by a sequencer execution cycle.
 #[derive(Debug, Clone)]
@@ -71,6 +74,7 @@ impl Sequencer {
         let dialect = Dialect::from(backend);
 
         let mut drained = true;
+        let mut next_due = None;
         let mut total_claimed: u32 = 0;
 
         for _iteration in 0..self.config.max_inner_iterations {
@@ -94,8 +98,9 @@ impl Sequencer {
                 .await?;
 
             if claimed.is_empty() {
-                // Nothing to process — partition is fully drained
+                // Nothing due — partition is drained (delayed rows may remain)
                 drained = true;
+                next_due = Self::next_due(&txn, backend, &dialect, partition_id).await?;
                 drop(txn);
                 break;
             }
@@ -127,6 +132,16 @@ impl Sequencer {
             ))
             .await?;
 
+            self.outbox
+                .notify_wakeup(&SeaOrmRunner::Tx(&txn), OUTGOING_CHANNEL, [partition_id])
+                .await?;
+
+            let due_after = if drained_this_iteration {
+                Self::next_due(&txn, backend, &dialect, partition_id).await?
+            } else {
+                None
+            };
+
             txn.commit().await?;
 
             #[allow(clippy::cast_possible_truncation)]
@@ -136,9 +151,13 @@ impl Sequencer {
 
             // Post-commit: notify the partition's processor
             self.outbox.notify_partition(partition_id);
+            self.outbox
+                .partition_metrics(partition_id)
+                .sequenced(u64::try_from(claimed.len()).unwrap_or(u64::MAX));
 
             if drained_this_iteration {
                 drained = true;
+                next_due = due_after;
                 break;
             }
 
@@ -148,14 +167,27 @@ impl Sequencer {
         Ok(PartitionProcessResult {
             drained,
             rows_claimed: total_claimed,
+            next_due,
         })
     }
+
+    /// Directive for a cycle with no work: sleep until the earliest pending
+    /// partition (deferred or cooling down) becomes eligible, else idle.
+    fn idle(&self, report: SequencerReport) -> Directive<SequencerReport> {
+        match self.shared_prioritizer.next_ready_in() {
+            Some(delay) => Directive::Sleep(delay, report),
+            None => Directive::Idle(report),
+        }
+    }
 }
 
 /// Internal result for a single partition's processing.
 struct PartitionProcessResult {
     drained: bool,
     rows_claimed: u32,
+    /// Set when a drained partition still holds delayed rows: time until
+    /// the earliest of them becomes due.
+    next_due: Option<Duration>,
 }
 
 /// Internal error type distinguishing skipped vs DB errors.
@@ -185,7 +217,7 @@ impl WorkerAction for Sequencer {
         _cancel: &CancellationToken,
     ) -> Result<Directive<SequencerReport>, OutboxError> {
         let Some(guard) = self.shared_prioritizer.take() else {
-            return Ok(Directive::Idle(SequencerReport {
+            return Ok(self.idle(SequencerReport {
                 partition_id: -1,
                 rows_claimed: 0,
             }));
@@ -198,7 +230,12 @@ impl WorkerAction for Sequencer {
                     partition_id: pid,
                     rows_claimed: result.rows_claimed,
                 };
-                guard.processed();
+                // Drained partitions with delayed rows are parked until the
+                // earliest becomes due; otherwise the dirty signal is consumed.
+                match result.next_due {
+                    Some(delay) => guard.deferred(delay),
+                    None => guard.processed(),
+                }
 
                 // Re-dirty only when saturated (partition still has rows).
                 // Drained partitions don't go back — new enqueues re-dirty them.
@@ -210,7 +247,7 @@ impl WorkerAction for Sequencer {
                 // or empty). Any work → Proceed, because other dirty partitions
                 // may be queued in the SharedPrioritizer.
                 if result.rows_claimed == 0 {
-                    Ok(Directive::Idle(report))
+                    Ok(self.idle(report))
                 } else {
                     Ok(Directive::Proceed(report))
                 }
@@ -305,6 +342,30 @@ impl Sequencer {
         Ok(rows)
     }
 
+    /// Time until the partition's earliest delayed incoming row becomes due,
+    /// or `None` if it has none. Evaluated against the database clock.
+    async fn next_due(
+        txn: &impl ConnectionTrait,
+        backend: DbBackend,
+        dialect: &Dialect,
+        partition_id: i64,
+    ) -> Result<Option<Duration>, OutboxError> {
+        let row = txn
+            .query_one(Statement::from_sql_and_values(
+                backend,
+                dialect.next_due_delay_ms(),
+                [partition_id.into()],
+            ))
+            .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let delay_ms: Option<i64> = row.try_get_by_index(0).map_err(|e| {
+            OutboxError::Database(sea_orm::DbErr::Custom(format!("next_due column: {e}")))
+        })?;
+        Ok(delay_ms.map(|ms| Duration::from_millis(u64::try_from(ms).unwrap_or(0))))
+    }
+
     /// Atomically allocate sequence numbers for a partition.
     /// Returns the `start_seq` (items get `start_seq` + 1, `start_seq` + 2, etc.).
     async fn allocate_sequences(
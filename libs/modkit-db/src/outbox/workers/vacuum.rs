@@ -4,7 +4,7 @@ use tracing::{debug, warn};
 
 use super::super::dialect::Dialect;
 use super::super::taskward::{Directive, WorkerAction};
-use super::super::types::OutboxError;
+use super::super::types::{DEFAULT_DEDUP_RETENTION, DEFAULT_INBOX_RETENTION, OutboxError};
 use crate::Db;
 
 /// Page size for the dirty-partition cursor.
@@ -21,6 +21,10 @@ pub struct VacuumReport {
     pub partitions_swept: usize,
     /// Total outgoing + body rows deleted across all partitions.
     pub rows_deleted: u64,
+    /// Expired producer dedup keys deleted.
+    pub dedup_keys_deleted: u64,
+    /// Expired inbox entries deleted.
+    pub inbox_entries_deleted: u64,
 }
 
 /// Standalone vacuum background task that garbage-collects processed
@@ -34,12 +38,17 @@ pub struct VacuumReport {
 /// the counter by the snapshot value, then idles until poked.
 /// Partitions dirtied during the sweep are picked up in the next cycle.
 ///
+/// Every sweep also deletes producer dedup keys and inbox entries older
+/// than their retention windows, in chunks of `batch_size`.
+///
 /// Resilient to transient DB errors: a failed snapshot or per-partition
 /// error is logged and the sweep continues (or retries after backoff).
 /// The vacuum never kills itself on a transient failure.
 pub struct VacuumTask {
     db: Db,
     batch_size: usize,
+    dedup_retention: std::time::Duration,
+    inbox_retention: std::time::Duration,
 }
 
 impl VacuumTask {
@@ -48,7 +57,26 @@ impl VacuumTask {
             batch_size > 0,
             "vacuum batch_size must be greater than zero"
         );
-        Self { db, batch_size }
+        Self {
+            db,
+            batch_size,
+            dedup_retention: DEFAULT_DEDUP_RETENTION,
+            inbox_retention: DEFAULT_INBOX_RETENTION,
+        }
+    }
+
+    /// Retention window after which dedup keys are deleted.
+    #[must_use]
+    pub fn with_dedup_retention(mut self, retention: std::time::Duration) -> Self {
+        self.dedup_retention = retention;
+        self
+    }
+
+    /// Retention window after which inbox entries are deleted.
+    #[must_use]
+    pub fn with_inbox_retention(mut self, retention: std::time::Duration) -> Self {
+        self.inbox_retention = retention;
+        self
     }
 }
 
@@ -101,9 +129,37 @@ impl WorkerAction for VacuumTask {
             }
         }
 
+        // Phase 3: Expired dedup keys and inbox entries (errors logged, not propagated)
+        let dedup_sql = dialect.vacuum_expired_dedup();
+        let dedup_keys_deleted = match self
+            .vacuum_expired(backend, dedup_sql, self.dedup_retention, cancel)
+            .await
+        {
+            Ok(deleted) => deleted,
+            Err(e) => {
+                warn!(error = %e, "vacuum: failed to delete expired dedup keys");
+                errors += 1;
+                0
+            }
+        };
+        let inbox_sql = dialect.vacuum_expired_inbox();
+        let inbox_entries_deleted = match self
+            .vacuum_expired(backend, inbox_sql, self.inbox_retention, cancel)
+            .await
+        {
+            Ok(deleted) => deleted,
+            Err(e) => {
+                warn!(error = %e, "vacuum: failed to delete expired inbox entries");
+                errors += 1;
+                0
+            }
+        };
+
         let elapsed = sweep_start.elapsed();
         debug!(
             partitions = dirty.len(),
+            dedup_keys_deleted,
+            inbox_entries_deleted,
             errors,
             elapsed_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
             "vacuum: sweep complete",
@@ -112,6 +168,8 @@ impl WorkerAction for VacuumTask {
         let report = VacuumReport {
             partitions_swept: dirty.len(),
             rows_deleted: total_deleted,
+            dedup_keys_deleted,
+            inbox_entries_deleted,
         };
         Ok(Directive::Idle(report))
     }
@@ -268,6 +326,38 @@ impl VacuumTask {
         Ok(total_deleted)
     }
 
+    /// Run a retention delete (`vacuum_expired_*` SQL) in bounded chunks
+    /// until fewer than `batch_size` rows are deleted. Returns rows deleted.
+    async fn vacuum_expired(
+        &self,
+        backend: DbBackend,
+        sql: &str,
+        retention: std::time::Duration,
+        cancel: &CancellationToken,
+    ) -> Result<u64, OutboxError> {
+        let retention_ms = i64::try_from(retention.as_millis()).unwrap_or(i64::MAX);
+        let limit = i64::try_from(self.batch_size).unwrap_or(i64::MAX);
+        let mut total_deleted: u64 = 0;
+
+        while !cancel.is_cancelled() {
+            let conn = self.db.sea_internal();
+            let deleted = conn
+                .execute(Statement::from_sql_and_values(
+                    backend,
+                    sql,
+                    [retention_ms.into(), limit.into()],
+                ))
+                .await?
+                .rows_affected();
+            total_deleted += deleted;
+            if deleted < self.batch_size as u64 {
+                break;
+            }
+        }
+
+        Ok(total_deleted)
+    }
+
     /// Execute one bounded chunk of cleanup for a single partition.
     /// Returns the number of outgoing rows deleted.
     async fn delete_chunk(
@@ -0,0 +1,35 @@
+//! Read replica pools of a database handle.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use sea_orm::DatabaseConnection;
+
+/// Replica connections, picked round-robin for reads.
+#[derive(Debug, Clone, Default)]
+pub struct ReadReplicas {
+    pools: Arc<[DatabaseConnection]>,
+    next: Arc<AtomicUsize>,
+}
+
+impl ReadReplicas {
+    pub fn new(pools: Vec<DatabaseConnection>) -> Self {
+        Self {
+            pools: pools.into(),
+            next: Arc::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pools.len()
+    }
+
+    /// Next replica in turn, or `None` without replicas.
+    pub fn pick(&self) -> Option<&DatabaseConnection> {
+        if self.pools.is_empty() {
+            return None;
+        }
+        let i = self.next.fetch_add(1, Ordering::Relaxed) % self.pools.len();
+        self.pools.get(i)
+    }
+}
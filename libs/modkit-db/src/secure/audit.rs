@@ -0,0 +1,221 @@
+//! Audit stamping and soft delete.
+//!
+//! Entities opt in through `#[derive(Scopable)]`:
+//!
+//! - `audit` — `created_by`/`created_at` on insert and `updated_by`/`updated_at`
+//!   on update are taken from the caller's [`SecurityContext`]. The secure
+//!   write paths that take a context stamp them, overwriting whatever the
+//!   caller set; the others refuse audited entities.
+//! - `soft_delete = "deleted_at"` — secure deletes set the column instead of
+//!   removing rows, and secure selects skip rows where it is set. With
+//!   `audit`, `deleted_by` records the deleting subject.
+//!
+//! See the [docs module](super::docs) for an example.
+
+use modkit_security::SecurityContext;
+use sea_orm::sea_query::{Expr, SimpleExpr};
+use sea_orm::{ActiveModelTrait, ColumnTrait};
+use time::OffsetDateTime;
+
+use crate::secure::{ScopableEntity, ScopeError};
+
+/// Set the created and updated audit columns of a new row.
+///
+/// No-op for entities without `audit`.
+///
+/// # Panics
+///
+/// Panics if an audit column does not have the documented type
+/// (`OffsetDateTime` for `*_at`, `Uuid` for `*_by`, optionally nullable).
+pub fn stamp_insert<A>(am: &mut A, ctx: &SecurityContext)
+where
+    A: ActiveModelTrait,
+    A::Entity: ScopableEntity,
+{
+    let Some(cols) = <A::Entity as ScopableEntity>::audit_cols() else {
+        return;
+    };
+    let now = OffsetDateTime::now_utc();
+    am.set(cols.created_at, now.into());
+    am.set(cols.created_by, ctx.subject_id().into());
+    am.set(cols.updated_at, now.into());
+    am.set(cols.updated_by, ctx.subject_id().into());
+}
+
+/// Set the updated audit columns of a changed row and leave the created ones
+/// as stored.
+///
+/// No-op for entities without `audit`.
+///
+/// # Panics
+///
+/// Panics if an audit column does not have the documented type.
+pub fn stamp_update<A>(am: &mut A, ctx: &SecurityContext)
+where
+    A: ActiveModelTrait,
+    A::Entity: ScopableEntity,
+{
+    let Some(cols) = <A::Entity as ScopableEntity>::audit_cols() else {
+        return;
+    };
+    am.not_set(cols.created_at);
+    am.not_set(cols.created_by);
+    am.set(cols.updated_at, OffsetDateTime::now_utc().into());
+    am.set(cols.updated_by, ctx.subject_id().into());
+}
+
+/// Refuse an insert of an audited entity without a [`SecurityContext`].
+pub fn refuse_unaudited_insert<E: ScopableEntity>() -> Result<(), ScopeError> {
+    match E::audit_cols() {
+        Some(_) => Err(ScopeError::Invalid(
+            "audited insert needs a SecurityContext: use secure_insert_audited()",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Refuse an update of an audited entity without a [`SecurityContext`].
+pub fn refuse_unaudited_update<E: ScopableEntity>() -> Result<(), ScopeError> {
+    match E::audit_cols() {
+        Some(_) => Err(ScopeError::Invalid(
+            "audited update needs a SecurityContext: use secure_update_audited()",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// `SET` expressions recording an update by `ctx` in `update_many`.
+pub fn update_exprs<E>(ctx: &SecurityContext) -> Vec<(E::Column, SimpleExpr)>
+where
+    E: ScopableEntity,
+{
+    E::audit_cols().map_or_else(Vec::new, |cols| {
+        vec![
+            (cols.updated_at, Expr::value(OffsetDateTime::now_utc())),
+            (cols.updated_by, Expr::value(ctx.subject_id())),
+        ]
+    })
+}
+
+/// Filter excluding soft-deleted rows, or `None` if the entity deletes rows.
+pub fn not_deleted<E>() -> Option<SimpleExpr>
+where
+    E: ScopableEntity,
+    E::Column: ColumnTrait,
+{
+    E::soft_delete_col().map(|col| Expr::col(col).is_null())
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::secure::AuditColumns;
+    use sea_orm::Set;
+    use sea_orm::entity::prelude::*;
+    use uuid::Uuid;
+
+    mod doc {
+        use super::*;
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(table_name = "audit_docs")]
+        pub struct Model {
+            #[sea_orm(primary_key, auto_increment = false)]
+            pub id: Uuid,
+            pub created_at: OffsetDateTime,
+            pub created_by: Uuid,
+            pub updated_at: OffsetDateTime,
+            pub updated_by: Uuid,
+            pub deleted_at: Option<OffsetDateTime>,
+            pub deleted_by: Option<Uuid>,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+
+        impl ScopableEntity for Entity {
+            fn tenant_col() -> Option<Column> {
+                None
+            }
+            fn resource_col() -> Option<Column> {
+                Some(Column::Id)
+            }
+            fn owner_col() -> Option<Column> {
+                None
+            }
+            fn type_col() -> Option<Column> {
+                None
+            }
+            fn resolve_property(_property: &str) -> Option<Column> {
+                None
+            }
+            fn audit_cols() -> Option<AuditColumns<Column>> {
+                Some(AuditColumns {
+                    created_at: Column::CreatedAt,
+                    created_by: Column::CreatedBy,
+                    updated_at: Column::UpdatedAt,
+                    updated_by: Column::UpdatedBy,
+                    deleted_by: Some(Column::DeletedBy),
+                })
+            }
+            fn soft_delete_col() -> Option<Column> {
+                Some(Column::DeletedAt)
+            }
+        }
+    }
+
+    fn ctx(subject: Uuid) -> SecurityContext {
+        SecurityContext::builder()
+            .subject_id(subject)
+            .subject_tenant_id(Uuid::new_v4())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn stamp_insert_overwrites_created_and_updated() {
+        let subject = Uuid::new_v4();
+        let mut am = doc::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            created_by: Set(Uuid::new_v4()),
+            ..Default::default()
+        };
+        assert!(refuse_unaudited_insert::<doc::Entity>().is_err());
+
+        stamp_insert(&mut am, &ctx(subject));
+        assert_eq!(am.created_by, Set(subject));
+        assert_eq!(am.updated_by, Set(subject));
+        assert!(am.deleted_by.is_not_set());
+    }
+
+    #[test]
+    fn stamp_update_sets_only_updated() {
+        let subject = Uuid::new_v4();
+        let mut am = doc::ActiveModel {
+            created_by: Set(Uuid::new_v4()),
+            updated_by: Set(Uuid::new_v4()),
+            ..Default::default()
+        };
+        assert!(refuse_unaudited_update::<doc::Entity>().is_err());
+
+        stamp_update(&mut am, &ctx(subject));
+        assert_eq!(am.updated_by, Set(subject));
+        assert!(am.created_by.is_not_set());
+    }
+
+    #[test]
+    fn update_exprs_cover_updated_columns() {
+        let cols: Vec<_> = update_exprs::<doc::Entity>(&ctx(Uuid::new_v4()))
+            .into_iter()
+            .map(|(col, _)| col)
+            .collect();
+        assert!(matches!(
+            cols.as_slice(),
+            [doc::Column::UpdatedAt, doc::Column::UpdatedBy]
+        ));
+        assert!(not_deleted::<doc::Entity>().is_some());
+    }
+}
@@ -1,9 +1,12 @@
-use sea_orm::sea_query::{Alias, Query};
-use sea_orm::{ColumnTrait, Condition, EntityTrait, sea_query::Expr};
+use sea_orm::sea_query::{Alias, CaseStatement, Query, SelectStatement, SimpleExpr};
+use sea_orm::{
+    ColumnTrait, ColumnType, Condition, EntityTrait, IdenStatic, Iterable, sea_query::Expr,
+};
 
-use crate::secure::{AccessScope, ScopableEntity};
+use crate::secure::{AccessScope, ScopableEntity, ScopeError};
 use modkit_security::access_scope::{
-    ScopeConstraint, ScopeFilter, ScopeValue, rg_tables, tenant_tables,
+    FieldMask, MaskedField, REDACTED_VALUE, ScopeConstraint, ScopeFilter, ScopeValue, rg_tables,
+    tenant_tables,
 };
 
 /// Convert a [`ScopeValue`] to a `sea_query::SimpleExpr` for SQL binding.
@@ -82,6 +85,164 @@ where
     }
 }
 
+/// `SELECT 1 FROM J WHERE cond AND <scope of J>`, the subquery of a scoped
+/// `EXISTS` check.
+///
+/// `cond` may reference columns of the outer query to correlate the check.
+pub fn scoped_exists_subquery<J>(scope: &AccessScope, cond: Condition) -> SelectStatement
+where
+    J: ScopableEntity + EntityTrait,
+    J::Column: ColumnTrait + Copy,
+{
+    Query::select()
+        .expr(Expr::value(1))
+        .from(J::default())
+        .cond_where(
+            Condition::all()
+                .add(cond)
+                .add(build_scope_condition::<J>(scope)),
+        )
+        .to_owned()
+}
+
+/// How `constraint` masks `col`, if at all.
+///
+/// Masks name fields as the API exposes them; they are resolved to columns
+/// through [`ScopableEntity::resolve_field`], here and nowhere else.
+fn column_mask<E>(constraint: &ScopeConstraint, col: E::Column) -> Option<FieldMask>
+where
+    E: ScopableEntity + EntityTrait,
+{
+    constraint
+        .masked_fields()
+        .iter()
+        .find(|m| E::resolve_field(m.field()).is_some_and(|c| c.as_str() == col.as_str()))
+        .map(MaskedField::mask)
+}
+
+/// Returns `true` if any constraint of `scope` masks `col`, i.e. some rows
+/// the caller reaches withhold it.
+#[must_use]
+pub fn masks_column<E>(scope: &AccessScope, col: E::Column) -> bool
+where
+    E: ScopableEntity + EntityTrait,
+{
+    scope
+        .constraints()
+        .iter()
+        .any(|c| column_mask::<E>(c, col).is_some())
+}
+
+/// Columns masked on every access path of `scope`, i.e. never readable.
+#[must_use]
+pub fn hidden_columns<E>(scope: &AccessScope) -> Vec<E::Column>
+where
+    E: ScopableEntity + EntityTrait,
+{
+    if !scope.has_masked_fields() {
+        return Vec::new();
+    }
+    E::Column::iter()
+        .filter(|col| {
+            scope
+                .constraints()
+                .iter()
+                .all(|c| column_mask::<E>(c, *col).is_some())
+        })
+        .collect()
+}
+
+/// Whether the masks of `scope` on `col` show the redacted value (`true`) or
+/// `NULL` (`false`), or `None` if no constraint masks the column.
+///
+/// Redaction applies when every masking constraint redacts a text column.
+fn redacts<E>(scope: &AccessScope, col: E::Column) -> Option<bool>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait,
+{
+    let mut masks = scope
+        .constraints()
+        .iter()
+        .filter_map(|c| column_mask::<E>(c, col))
+        .peekable();
+    masks.peek()?;
+    Some(
+        masks.all(|m| m == FieldMask::Redact)
+            && matches!(
+                col.def().get_column_type(),
+                ColumnType::String(_) | ColumnType::Text | ColumnType::Char(_)
+            ),
+    )
+}
+
+/// Message of the `ScopeError::Invalid` returned by [`ensure_maskable`].
+pub const UNMASKABLE_FIELD: &str = "field mask would project NULL into a non-nullable \
+     column; hidden columns must be `Option` fields of the model";
+
+/// Refuse masks of `scope` that would project `NULL` into a column that is
+/// not nullable, since such rows cannot be decoded into the model.
+///
+/// Checked by `scope_with`; the queries it scopes return this error when run.
+///
+/// # Errors
+/// Returns `ScopeError::Invalid` if a hidden (or redacted non-text) column
+/// is not an `Option` field of the model.
+pub fn ensure_maskable<E>(scope: &AccessScope) -> Result<(), ScopeError>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait,
+{
+    if !scope.has_masked_fields() {
+        return Ok(());
+    }
+    if E::Column::iter().any(|col| redacts::<E>(scope, col) == Some(false) && !col.def().is_null())
+    {
+        return Err(ScopeError::Invalid(UNMASKABLE_FIELD));
+    }
+    Ok(())
+}
+
+/// Projection of `col` applying the field masks of `scope`, or `None` if no
+/// constraint masks the column (select it as is).
+///
+/// Renders `CASE WHEN <a constraint not masking col> THEN col ELSE <mask> END`,
+/// so each row shows the column only if some access path reaching it leaves
+/// the column readable. The mask is the redacted value when every masking
+/// constraint redacts a text column, `NULL` otherwise; hidden columns must
+/// therefore be `Option` fields of the model (see [`ensure_maskable`]).
+pub fn masked_column<E>(scope: &AccessScope, col: E::Column) -> Option<SimpleExpr>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+{
+    let redact = redacts::<E>(scope, col)?;
+
+    let readable: Vec<Condition> = scope
+        .constraints()
+        .iter()
+        .filter(|c| column_mask::<E>(c, col).is_none())
+        .filter_map(build_constraint_condition::<E>)
+        .collect();
+    let when = if readable.is_empty() {
+        deny_all()
+    } else {
+        readable.into_iter().fold(Condition::any(), Condition::add)
+    };
+    let masked = if redact {
+        Expr::value(REDACTED_VALUE)
+    } else {
+        Expr::cust("NULL")
+    };
+
+    Some(
+        CaseStatement::new()
+            .case(when, col.select_as(Expr::col((E::default(), col))))
+            .finally(masked)
+            .into(),
+    )
+}
+
 /// Build SQL for a single constraint (AND of filters).
 ///
 /// Returns `None` if any filter references an unknown property (fail-closed).
@@ -3,7 +3,8 @@
 //! This module provides the primary entry point for secure database access:
 //!
 //! - [`Db`]: The database handle. NOT Clone, NOT storable by services.
-//! - [`DbConn`]: Non-transactional runner (borrows from `Db`).
+//! - [`DbConn`]: Non-transactional runner (borrows from `Db`). Its scoped
+//!   reads go to a read replica when one is configured.
 //! - [`DbTx`]: Transactional runner (lives inside transaction closure).
 //!
 //! # Security Model
@@ -185,6 +186,10 @@ impl Db {
     ///     .await?;
     /// ```
     ///
+    /// With read replicas configured, scoped reads (`SecureSelect`, `OPager`)
+    /// on this runner go to a replica and may lag behind recent writes; writes
+    /// always go to the primary. Use [`Db::conn_primary`] to read your writes.
+    ///
     /// The `Result` itself is `#[must_use]`; this method does not add an extra must-use
     /// marker to avoid clippy `double_must_use`.
     pub fn conn(&self) -> Result<DbConn<'_>, DbError> {
@@ -193,6 +198,28 @@ impl Db {
         }
         Ok(DbConn {
             conn: self.handle.sea_internal_ref(),
+            read: self.handle.sea_read_ref(),
+        })
+    }
+
+    /// Create a non-transactional runner whose reads also go to the primary.
+    ///
+    /// Use it where a read must observe writes made just before, e.g. to
+    /// return a resource right after creating it. Without replicas it is the
+    /// same as [`Db::conn`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError::ConnRequestedInsideTx` if called from within a transaction
+    /// closure (reads inside a transaction already use the primary).
+    pub fn conn_primary(&self) -> Result<DbConn<'_>, DbError> {
+        if is_in_transaction() {
+            return Err(DbError::ConnRequestedInsideTx);
+        }
+        let primary = self.handle.sea_internal_ref();
+        Ok(DbConn {
+            conn: primary,
+            read: primary,
         })
     }
 
@@ -738,6 +765,8 @@ impl Db {
 /// ```
 pub struct DbConn<'a> {
     pub(crate) conn: &'a DatabaseConnection,
+    /// Connection for replica-tolerant reads; the primary without replicas.
+    pub(crate) read: &'a DatabaseConnection,
 }
 
 impl std::fmt::Debug for DbConn<'_> {
@@ -1,10 +1,17 @@
+use modkit_security::SecurityContext;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, InsertResult, IntoActiveModel, ModelTrait,
-    QueryFilter,
-    sea_query::{IntoIden, OnConflict, SimpleExpr},
+    ActiveModelTrait, ColumnTrait, DbBackend, DbErr, EntityTrait, InsertResult, IntoActiveModel,
+    Iterable, ModelTrait, QueryFilter,
+    sea_query::{Expr, IntoIden, OnConflict, SimpleExpr},
 };
 use std::marker::PhantomData;
+use std::sync::Arc;
+use time::OffsetDateTime;
 
+use crate::secure::audit::{
+    not_deleted, refuse_unaudited_insert, refuse_unaudited_update, stamp_insert, stamp_update,
+    update_exprs,
+};
 use crate::secure::cond::build_scope_condition;
 use crate::secure::error::ScopeError;
 use crate::secure::{
@@ -130,6 +137,8 @@ where
 ///
 /// - Does **not** inspect the `SecurityContext` or enforce tenant scoping rules.
 /// - Does **not** automatically populate any entity fields.
+/// - Refuses `#[secure(audit)]` entities; use [`secure_insert_audited`] for
+///   them.
 /// - Callers are responsible for:
 ///   - Setting all required fields before calling.
 ///   - Validating that the operation is authorized within the current
@@ -176,11 +185,52 @@ where
 /// - Returns `ScopeError::Db` if the database insert fails.
 /// - Returns `ScopeError::Denied` if the `ActiveModel` values do not satisfy any scope constraint.
 /// - Returns `ScopeError::TenantNotInScope` for tenant isolation violations.
+/// - Returns `ScopeError::Invalid` for an audited entity.
 pub async fn secure_insert<E>(
     am: E::ActiveModel,
     scope: &AccessScope,
     runner: &impl DBRunner,
 ) -> Result<E::Model, ScopeError>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+    E::Model: sea_orm::IntoActiveModel<E::ActiveModel>,
+{
+    refuse_unaudited_insert::<E>()?;
+    insert_scoped::<E>(am, scope, runner).await
+}
+
+/// [`secure_insert`] recording the insert as made by the subject of `ctx`.
+///
+/// Stamps the created and updated audit columns of `#[secure(audit)]`
+/// entities, overwriting any values set on `am`; the same as
+/// [`secure_insert`] for other entities.
+///
+/// # Errors
+/// Same as [`secure_insert`], except that audited entities are accepted.
+pub async fn secure_insert_audited<E>(
+    mut am: E::ActiveModel,
+    ctx: &SecurityContext,
+    scope: &AccessScope,
+    runner: &impl DBRunner,
+) -> Result<E::Model, ScopeError>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+    E::Model: sea_orm::IntoActiveModel<E::ActiveModel>,
+{
+    stamp_insert(&mut am, ctx);
+    insert_scoped::<E>(am, scope, runner).await
+}
+
+/// Insert of [`secure_insert`] once audit columns are settled.
+async fn insert_scoped<E>(
+    am: E::ActiveModel,
+    scope: &AccessScope,
+    runner: &impl DBRunner,
+) -> Result<E::Model, ScopeError>
 where
     E: ScopableEntity + EntityTrait,
     E::Column: ColumnTrait + Copy,
@@ -206,17 +256,69 @@ where
 ///
 /// # Security
 /// - Verifies the target row exists **within the scope** before updating.
+///   Soft-deleted rows are not in scope.
 /// - For tenant-scoped entities, forbids changing `tenant_id` (immutable).
 ///
+/// Versioned entities are written only if the row is still at the expected
+/// version, which is then incremented.
+///
 /// # Errors
 /// - `ScopeError::Denied` if the row is not accessible in the scope.
 /// - `ScopeError::Denied("tenant_id is immutable")` if caller attempts to change `tenant_id`.
+/// - `ScopeError::Invalid` for an audited entity; use
+///   [`secure_update_audited`] for them.
+/// - `ScopeError::VersionConflict` if the entity has a `version_col` and the
+///   version set on `am` (the expected one) is no longer the stored one.
 pub async fn secure_update_with_scope<E>(
     am: E::ActiveModel,
     scope: &AccessScope,
     id: uuid::Uuid,
     runner: &impl DBRunner,
 ) -> Result<E::Model, ScopeError>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+    E::Model: sea_orm::IntoActiveModel<E::ActiveModel> + sea_orm::ModelTrait<Entity = E>,
+{
+    refuse_unaudited_update::<E>()?;
+    update_scoped::<E>(am, scope, id, runner).await
+}
+
+/// [`secure_update_with_scope`] recording the update as made by the subject
+/// of `ctx`.
+///
+/// Stamps the updated audit columns of `#[secure(audit)]` entities,
+/// overwriting any values set on `am`, and keeps the created ones as stored;
+/// the same as [`secure_update_with_scope`] for other entities.
+///
+/// # Errors
+/// Same as [`secure_update_with_scope`], except that audited entities are
+/// accepted.
+pub async fn secure_update_audited<E>(
+    mut am: E::ActiveModel,
+    ctx: &SecurityContext,
+    scope: &AccessScope,
+    id: uuid::Uuid,
+    runner: &impl DBRunner,
+) -> Result<E::Model, ScopeError>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+    E::Model: sea_orm::IntoActiveModel<E::ActiveModel> + sea_orm::ModelTrait<Entity = E>,
+{
+    stamp_update(&mut am, ctx);
+    update_scoped::<E>(am, scope, id, runner).await
+}
+
+/// Update of [`secure_update_with_scope`] once audit columns are settled.
+async fn update_scoped<E>(
+    am: E::ActiveModel,
+    scope: &AccessScope,
+    id: uuid::Uuid,
+    runner: &impl DBRunner,
+) -> Result<E::Model, ScopeError>
 where
     E: ScopableEntity + EntityTrait,
     E::Column: ColumnTrait + Copy,
@@ -227,7 +329,7 @@ where
         .secure()
         .scope_with(scope)
         .and_id(id)?
-        .one(runner)
+        .one_on_primary(runner)
         .await?;
 
     let Some(existing) = existing else {
@@ -262,9 +364,94 @@ where
         }
     }
 
+    if let Some(vcol) = E::version_col() {
+        return update_versioned::<E>(am, &existing, vcol, scope, id, runner).await;
+    }
+
     match DBRunnerInternal::as_seaorm(runner) {
-        SeaOrmRunner::Conn(db) => Ok(am.update(db).await?),
-        SeaOrmRunner::Tx(tx) => Ok(am.update(tx).await?),
+        SeaOrmRunner::Conn(db) => am.update(db).await?,
+        SeaOrmRunner::Tx(tx) => am.update(tx).await?,
+    };
+
+    // The written row comes back unmasked; read it again through the scope
+    reread_scoped::<E>(scope, id, runner).await
+}
+
+/// Compare-and-swap update of a versioned row.
+///
+/// The expected version is the one set on `am`, or the stored one if unset;
+/// the row is written with the version bumped only if it still matches.
+async fn update_versioned<E>(
+    mut am: E::ActiveModel,
+    existing: &E::Model,
+    vcol: E::Column,
+    scope: &AccessScope,
+    id: uuid::Uuid,
+    runner: &impl DBRunner,
+) -> Result<E::Model, ScopeError>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+    E::Model: sea_orm::IntoActiveModel<E::ActiveModel> + sea_orm::ModelTrait<Entity = E>,
+{
+    let current = version_value(&existing.get(vcol))?;
+    let expected = match am.get(vcol) {
+        sea_orm::ActiveValue::Set(v) | sea_orm::ActiveValue::Unchanged(v) => version_value(&v)?,
+        sea_orm::ActiveValue::NotSet => current,
+    };
+    if expected != current {
+        return Err(ScopeError::VersionConflict { expected });
+    }
+    am.not_set(vcol);
+    let rcol = E::resource_col().ok_or(ScopeError::Invalid(
+        "Entity must have a resource_col to use and_id()",
+    ))?;
+
+    // `am` was checked for audit stamps and tenant changes by the caller.
+    SecureUpdateMany::<E, Unscoped> {
+        inner: E::update_many().set(am),
+        _state: PhantomData,
+        tenant_update_attempted: false,
+        audited: true,
+        expected_version: Some(expected),
+    }
+    .scope_with(scope)
+    .filter(sea_orm::Condition::all().add(Expr::col(rcol).eq(id)))
+    .exec(runner)
+    .await?;
+
+    reread_scoped::<E>(scope, id, runner).await
+}
+
+/// Read an updated row back through `scope`, so masked fields stay masked.
+async fn reread_scoped<E>(
+    scope: &AccessScope,
+    id: uuid::Uuid,
+    runner: &impl DBRunner,
+) -> Result<E::Model, ScopeError>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+{
+    E::find()
+        .secure()
+        .scope_with(scope)
+        .and_id(id)?
+        .one_on_primary(runner)
+        .await?
+        .ok_or(ScopeError::Denied(
+            "entity not found or not accessible in current security scope",
+        ))
+}
+
+fn version_value(v: &sea_orm::Value) -> Result<i64, ScopeError> {
+    match v {
+        sea_orm::Value::BigInt(Some(v)) => Ok(*v),
+        sea_orm::Value::Int(Some(v)) => Ok(i64::from(*v)),
+        _ => Err(ScopeError::Invalid(
+            "version column must be a non-null integer",
+        )),
     }
 }
 
@@ -393,11 +580,14 @@ where
     /// # Errors
     /// - Returns `ScopeError::Denied` if the `ActiveModel` values do not satisfy
     ///   any scope constraint.
+    /// - Returns `ScopeError::Invalid` for an audited entity; use
+    ///   [`secure_insert_audited`] for them.
     pub fn scope_with_model(
         self,
         scope: &AccessScope,
         am: &A,
     ) -> Result<SecureInsertOne<A, Scoped>, ScopeError> {
+        refuse_unaudited_insert::<A::Entity>()?;
         validate_insert_scope(am, scope)?;
         Ok(SecureInsertOne {
             inner: self.inner,
@@ -651,6 +841,254 @@ where
     }
 }
 
+/// Outcome of one row of [`secure_insert_many`] / [`secure_upsert_many`].
+#[derive(Debug)]
+pub enum BulkRowOutcome {
+    /// The row was written: inserted, or handed to the `ON CONFLICT` clause of
+    /// an upsert (which may have updated the stored row or left it as is).
+    Written,
+    /// The row failed validation and was not sent to the database.
+    Rejected(ScopeError),
+    /// The statement carrying the row failed (e.g. a unique violation); the
+    /// error is shared by every row of that statement.
+    Failed(Arc<DbErr>),
+    /// The row was valid but not sent, because an earlier statement failed.
+    NotAttempted,
+}
+
+/// Per-row report of a bulk insert or upsert.
+#[derive(Debug, Default)]
+#[must_use = "a bulk write reports rejected and failed rows instead of returning an error"]
+pub struct BulkWriteReport {
+    /// One outcome per input row, in input order.
+    pub rows: Vec<BulkRowOutcome>,
+    /// Rows the database reported as affected, summed over all statements.
+    ///
+    /// Backend-specific for upserts: `MySQL` counts an updated row twice and
+    /// no backend counts rows skipped by `DO NOTHING`.
+    pub rows_affected: u64,
+}
+
+impl BulkWriteReport {
+    /// Number of rows that were written.
+    #[must_use]
+    pub fn written(&self) -> usize {
+        self.rows
+            .iter()
+            .filter(|r| matches!(r, BulkRowOutcome::Written))
+            .count()
+    }
+
+    /// Rejected rows as `(input index, reason)`.
+    pub fn rejected(&self) -> impl Iterator<Item = (usize, &ScopeError)> {
+        self.rows.iter().enumerate().filter_map(|(i, r)| match r {
+            BulkRowOutcome::Rejected(e) => Some((i, e)),
+            _ => None,
+        })
+    }
+
+    /// Error of the failed statement, if one failed. Its rows are
+    /// [`Failed`](BulkRowOutcome::Failed) and the rows it stopped are
+    /// [`NotAttempted`](BulkRowOutcome::NotAttempted).
+    #[must_use]
+    pub fn failure(&self) -> Option<&DbErr> {
+        self.rows.iter().find_map(|r| match r {
+            BulkRowOutcome::Failed(e) => Some(e.as_ref()),
+            _ => None,
+        })
+    }
+
+    /// Whether every row was written.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.rows
+            .iter()
+            .all(|r| matches!(r, BulkRowOutcome::Written))
+    }
+}
+
+/// Upper bound on rows per statement, to keep statements a reasonable size.
+const MAX_BULK_BATCH_ROWS: usize = 1000;
+
+/// Rows per multi-row `INSERT` that stay within the backend's bind parameter
+/// limit, assuming every column of `E` is bound. One extra column per row is
+/// reserved for `ON CONFLICT` update values.
+fn bulk_batch_rows<E: EntityTrait>(backend: DbBackend) -> usize {
+    let max_params: usize = match backend {
+        DbBackend::Postgres | DbBackend::MySql => 65_535,
+        DbBackend::Sqlite => 32_766,
+    };
+    let per_row = E::Column::iter().count() + 1;
+    max_params
+        .checked_div(per_row)
+        .unwrap_or(1)
+        .clamp(1, MAX_BULK_BATCH_ROWS)
+}
+
+/// Secure multi-row insert for Scopable entities.
+///
+/// Every row is validated like [`secure_insert`] does (tenant set, entity
+/// not audited, values within `scope`). Rows failing validation are
+/// reported as [`BulkRowOutcome::Rejected`] and skipped; the others are
+/// inserted with multi-row statements sized for the backend. Only rows that
+/// set the same columns share a statement, so columns a row leaves unset
+/// keep their database defaults.
+///
+/// The first statement that fails stops the write: its rows are reported as
+/// [`BulkRowOutcome::Failed`] and the rows after it as
+/// [`BulkRowOutcome::NotAttempted`], while rows of earlier statements stay
+/// written. Statements are not atomic as a group: run this in a transaction
+/// and roll back unless the report is
+/// [`complete`](BulkWriteReport::is_complete), when partial imports are not
+/// acceptable.
+///
+/// # Example
+///
+/// ```ignore
+/// let report = secure_insert_many::<member::Entity>(rows, &scope, tx).await;
+/// for (i, err) in report.rejected() {
+///     tracing::warn!(row = i, error = %err, "membership not imported");
+/// }
+/// if let Some(err) = report.failure() {
+///     return Err(err.to_string().into());
+/// }
+/// ```
+pub async fn secure_insert_many<E>(
+    rows: Vec<E::ActiveModel>,
+    scope: &AccessScope,
+    runner: &impl DBRunner,
+) -> BulkWriteReport
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+    E::Model: sea_orm::IntoActiveModel<E::ActiveModel>,
+{
+    bulk_write::<E>(rows, scope, None, runner).await
+}
+
+/// Secure multi-row upsert for Scopable entities.
+///
+/// Same validation, batching and reporting as [`secure_insert_many`], with
+/// `on_conflict` applied to every statement. [`SecureOnConflict`] guarantees
+/// a conflicting row never moves to another tenant.
+///
+/// # Example
+///
+/// ```ignore
+/// let on_conflict = SecureOnConflict::<settings::Entity>::columns([
+///         settings::Column::TenantId,
+///         settings::Column::UserId,
+///     ])
+///     .update_columns([settings::Column::Theme])?;
+/// let report = secure_upsert_many::<settings::Entity>(rows, &scope, on_conflict, conn).await;
+/// ```
+pub async fn secure_upsert_many<E>(
+    rows: Vec<E::ActiveModel>,
+    scope: &AccessScope,
+    on_conflict: SecureOnConflict<E>,
+    runner: &impl DBRunner,
+) -> BulkWriteReport
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+    E::Model: sea_orm::IntoActiveModel<E::ActiveModel>,
+{
+    bulk_write::<E>(rows, scope, Some(on_conflict.build()), runner).await
+}
+
+/// Rows that set the same columns (the mask), with their input indexes.
+type ColumnGroup<A> = (Vec<bool>, Vec<(usize, A)>);
+
+async fn bulk_write<E>(
+    rows: Vec<E::ActiveModel>,
+    scope: &AccessScope,
+    on_conflict: Option<OnConflict>,
+    runner: &impl DBRunner,
+) -> BulkWriteReport
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+    E::Model: sea_orm::IntoActiveModel<E::ActiveModel>,
+{
+    let mut report = BulkWriteReport {
+        rows: Vec::with_capacity(rows.len()),
+        rows_affected: 0,
+    };
+    // A multi-row INSERT binds NULL wherever a row leaves a column unset
+    // that another row sets, overriding the column default; rows are only
+    // batched with rows that set the same columns.
+    let mut groups: Vec<ColumnGroup<E::ActiveModel>> = Vec::new();
+    for (i, am) in rows.into_iter().enumerate() {
+        match validate_bulk_row(&am, scope) {
+            Ok(()) => {
+                report.rows.push(BulkRowOutcome::NotAttempted);
+                let set: Vec<bool> = E::Column::iter()
+                    .map(|c| !matches!(am.get(c), sea_orm::ActiveValue::NotSet))
+                    .collect();
+                match groups.iter_mut().find(|(cols, _)| *cols == set) {
+                    Some((_, group)) => group.push((i, am)),
+                    None => groups.push((set, vec![(i, am)])),
+                }
+            }
+            Err(e) => report.rows.push(BulkRowOutcome::Rejected(e)),
+        }
+    }
+
+    let runner = DBRunnerInternal::as_seaorm(runner);
+    let batch_rows = bulk_batch_rows::<E>(runner.backend());
+    for (_, group) in groups {
+        let mut group = group.into_iter().peekable();
+        while group.peek().is_some() {
+            let (indexes, batch): (Vec<usize>, Vec<E::ActiveModel>) =
+                group.by_ref().take(batch_rows).unzip();
+            let mut insert = E::insert_many(batch);
+            if let Some(on_conflict) = &on_conflict {
+                insert = insert.on_conflict(on_conflict.clone());
+            }
+            let written = match &runner {
+                SeaOrmRunner::Conn(db) => insert.exec_without_returning(*db).await,
+                SeaOrmRunner::Tx(tx) => insert.exec_without_returning(*tx).await,
+            };
+            match written {
+                Ok(affected) => {
+                    report.rows_affected += affected;
+                    for i in indexes {
+                        report.rows[i] = BulkRowOutcome::Written;
+                    }
+                }
+                Err(e) => {
+                    let e = Arc::new(e);
+                    for i in indexes {
+                        report.rows[i] = BulkRowOutcome::Failed(Arc::clone(&e));
+                    }
+                    return report;
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Per-row checks of [`secure_insert`].
+fn validate_bulk_row<A>(am: &A, scope: &AccessScope) -> Result<(), ScopeError>
+where
+    A: ActiveModelTrait,
+    A::Entity: ScopableEntity + EntityTrait,
+    <A::Entity as EntityTrait>::Column: ColumnTrait + Copy,
+{
+    if let Some(tenant_col) = <A::Entity as ScopableEntity>::tenant_col()
+        && let sea_orm::ActiveValue::NotSet = am.get(tenant_col)
+    {
+        return Err(ScopeError::Invalid("tenant_id is required"));
+    }
+    refuse_unaudited_insert::<A::Entity>()?;
+    validate_insert_scope(am, scope)
+}
+
 /// A type-safe wrapper around `SeaORM`'s `UpdateMany` that enforces scoping.
 ///
 /// This wrapper uses the typestate pattern to ensure that update operations
@@ -668,11 +1106,15 @@ where
 ///     .exec(conn)         // Now can execute
 ///     .await?;
 /// ```
+///
+/// Updates of audited entities must call [`audited`](Self::audited).
 #[derive(Clone, Debug)]
 pub struct SecureUpdateMany<E: EntityTrait, S> {
     pub(crate) inner: sea_orm::UpdateMany<E>,
     pub(crate) _state: PhantomData<S>,
     pub(crate) tenant_update_attempted: bool,
+    pub(crate) audited: bool,
+    pub(crate) expected_version: Option<i64>,
 }
 
 // Fluent builder methods (available in all typestates).
@@ -699,6 +1141,28 @@ where
         self.inner = QueryFilter::filter(self.inner, filter);
         self
     }
+
+    /// Record the update as made by the subject of `ctx` now.
+    ///
+    /// Sets the updated audit columns; a no-op for entities without audit.
+    #[must_use]
+    pub fn audited(mut self, ctx: &SecurityContext) -> Self {
+        for (col, expr) in update_exprs::<E>(ctx) {
+            self.inner = self.inner.col_expr(col, expr);
+        }
+        self.audited = true;
+        self
+    }
+
+    /// Only update rows still at `version`; fails with
+    /// [`ScopeError::VersionConflict`] if none is.
+    ///
+    /// Requires an entity with `version_col`.
+    #[must_use]
+    pub fn expect_version(mut self, version: i64) -> Self {
+        self.expected_version = Some(version);
+        self
+    }
 }
 
 /// Extension trait to convert a regular `SeaORM` `UpdateMany` into a `SecureUpdateMany`.
@@ -717,6 +1181,8 @@ where
             inner: self,
             _state: PhantomData,
             tenant_update_attempted: false,
+            audited: false,
+            expected_version: None,
         }
     }
 }
@@ -742,6 +1208,8 @@ where
             inner: self.inner.filter(cond),
             _state: PhantomData,
             tenant_update_attempted: self.tenant_update_attempted,
+            audited: self.audited,
+            expected_version: self.expected_version,
         }
     }
 }
@@ -749,20 +1217,54 @@ where
 // Methods available only on Scoped updates
 impl<E> SecureUpdateMany<E, Scoped>
 where
-    E: EntityTrait,
+    E: ScopableEntity + EntityTrait,
 {
     /// Execute the update operation.
     ///
+    /// Versioned entities have their version column incremented.
+    ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database operation fails.
+    /// - Returns `ScopeError::Db` if the database operation fails.
+    /// - Returns `ScopeError::Invalid` for an audited entity without
+    ///   [`audited`](Self::audited), or for
+    ///   [`expect_version`](Self::expect_version) on an entity without
+    ///   `version_col`.
+    /// - Returns `ScopeError::VersionConflict` if an expected version was set
+    ///   and no row was updated.
     #[allow(clippy::disallowed_methods)]
     pub async fn exec(self, runner: &impl DBRunner) -> Result<sea_orm::UpdateResult, ScopeError> {
         if self.tenant_update_attempted {
             return Err(ScopeError::Denied("tenant_id is immutable"));
         }
-        match DBRunnerInternal::as_seaorm(runner) {
-            SeaOrmRunner::Conn(db) => Ok(self.inner.exec(db).await?),
-            SeaOrmRunner::Tx(tx) => Ok(self.inner.exec(tx).await?),
+        if E::audit_cols().is_some() && !self.audited {
+            return Err(ScopeError::Invalid(
+                "audited update must call .audited(ctx) before exec()",
+            ));
+        }
+        let mut inner = self.inner;
+        match (E::version_col(), self.expected_version) {
+            (Some(vcol), expected) => {
+                inner = inner.col_expr(vcol, Expr::col(vcol).add(1));
+                if let Some(expected) = expected {
+                    inner = QueryFilter::filter(inner, Expr::col(vcol).eq(expected));
+                }
+            }
+            (None, Some(_)) => {
+                return Err(ScopeError::Invalid(
+                    "expect_version() requires an entity with version_col",
+                ));
+            }
+            (None, None) => {}
+        }
+        let result = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => inner.exec(db).await?,
+            SeaOrmRunner::Tx(tx) => inner.exec(tx).await?,
+        };
+        match self.expected_version {
+            Some(expected) if result.rows_affected == 0 => {
+                Err(ScopeError::VersionConflict { expected })
+            }
+            _ => Ok(result),
         }
     }
 
@@ -794,10 +1296,24 @@ where
 ///     .exec(conn)         // Now can execute
 ///     .await?;
 /// ```
+///
+/// # Soft Delete
+/// For entities with `soft_delete_col()`, `exec` sets that column (and
+/// `deleted_by` for audited entities, which must call
+/// [`audited`](Self::audited)) on rows not yet deleted instead of removing
+/// them. The condition of the `UPDATE` is built from the scope and the
+/// filters added through the wrapper, so soft deletes start from
+/// [`SecureDeleteEntityExt::secure_delete`], which takes no `DeleteMany`
+/// whose own filters would be lost.
 #[derive(Clone, Debug)]
 pub struct SecureDeleteMany<E: EntityTrait, S> {
     pub(crate) inner: sea_orm::DeleteMany<E>,
     pub(crate) _state: PhantomData<S>,
+    /// The soft delete as an update; `None` removes rows.
+    pub(crate) soft: Option<sea_orm::UpdateMany<E>>,
+    /// Built from a caller's `DeleteMany`, which may carry filters of its own.
+    pub(crate) from_query: bool,
+    pub(crate) deleted_by: Option<uuid::Uuid>,
 }
 
 /// Extension trait to convert a regular `SeaORM` `DeleteMany` into a `SecureDeleteMany`.
@@ -815,6 +1331,31 @@ where
         SecureDeleteMany {
             inner: self,
             _state: PhantomData,
+            soft: None,
+            from_query: true,
+            deleted_by: None,
+        }
+    }
+}
+
+/// Extension trait starting a secure delete of all rows of an entity.
+///
+/// Filters can only be added through the wrapper, after `.scope_with()`,
+/// which makes this the way to delete rows of soft-delete entities.
+pub trait SecureDeleteEntityExt: EntityTrait {
+    /// Start a secure (unscoped) delete.
+    /// You must call `.scope_with()` before executing.
+    fn secure_delete() -> SecureDeleteMany<Self, Unscoped>;
+}
+
+impl<E> SecureDeleteEntityExt for E
+where
+    E: EntityTrait,
+{
+    fn secure_delete() -> SecureDeleteMany<E, Unscoped> {
+        SecureDeleteMany {
+            from_query: false,
+            ..E::delete_many().secure()
         }
     }
 }
@@ -836,9 +1377,14 @@ where
     #[must_use]
     pub fn scope_with(self, scope: &AccessScope) -> SecureDeleteMany<E, Scoped> {
         let cond = build_scope_condition::<E>(scope);
+        let soft =
+            not_deleted::<E>().map(|live| E::update_many().filter(cond.clone()).filter(live));
         SecureDeleteMany {
             inner: self.inner.filter(cond),
             _state: PhantomData,
+            soft,
+            from_query: self.from_query,
+            deleted_by: self.deleted_by,
         }
     }
 }
@@ -852,20 +1398,70 @@ where
     /// The scope conditions remain in place.
     #[must_use]
     pub fn filter(mut self, filter: sea_orm::Condition) -> Self {
+        self.soft = self.soft.map(|u| QueryFilter::filter(u, filter.clone()));
         self.inner = QueryFilter::filter(self.inner, filter);
         self
     }
 
+    /// Record the delete as made by the subject of `ctx`.
+    ///
+    /// Required for soft deletes of audited entities; ignored otherwise.
+    #[must_use]
+    pub fn audited(mut self, ctx: &SecurityContext) -> Self {
+        self.deleted_by = Some(ctx.subject_id());
+        self
+    }
+
+    /// Remove the rows even if the entity soft-deletes, e.g. to purge rows
+    /// after their retention period.
+    #[must_use]
+    pub fn hard(mut self) -> Self {
+        self.soft = None;
+        self
+    }
+
     /// Execute the delete operation.
     ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database operation fails.
+    /// - Returns `ScopeError::Db` if the database operation fails.
+    /// - Returns `ScopeError::Invalid` for a soft delete that did not start
+    ///   from [`SecureDeleteEntityExt::secure_delete`], or of an audited
+    ///   entity without [`audited`](Self::audited).
     #[allow(clippy::disallowed_methods)]
-    pub async fn exec(self, runner: &impl DBRunner) -> Result<sea_orm::DeleteResult, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
-            SeaOrmRunner::Conn(db) => Ok(self.inner.exec(db).await?),
-            SeaOrmRunner::Tx(tx) => Ok(self.inner.exec(tx).await?),
+    pub async fn exec(self, runner: &impl DBRunner) -> Result<sea_orm::DeleteResult, ScopeError>
+    where
+        E: ScopableEntity,
+        E::Column: ColumnTrait + Copy,
+    {
+        let Some(mut update) = self.soft else {
+            return match DBRunnerInternal::as_seaorm(runner) {
+                SeaOrmRunner::Conn(db) => Ok(self.inner.exec(db).await?),
+                SeaOrmRunner::Tx(tx) => Ok(self.inner.exec(tx).await?),
+            };
+        };
+        if self.from_query {
+            return Err(ScopeError::Invalid(
+                "soft delete must start from Entity::secure_delete(), not DeleteMany::secure()",
+            ));
         }
+        if let Some(col) = E::soft_delete_col() {
+            update = update.col_expr(col, Expr::value(OffsetDateTime::now_utc()));
+        }
+        if let Some(col) = E::audit_cols().and_then(|cols| cols.deleted_by) {
+            let Some(subject) = self.deleted_by else {
+                return Err(ScopeError::Invalid(
+                    "audited soft delete must call .audited(ctx) before exec()",
+                ));
+            };
+            update = update.col_expr(col, Expr::value(subject));
+        }
+        let result = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => update.exec(db).await?,
+            SeaOrmRunner::Tx(tx) => update.exec(tx).await?,
+        };
+        Ok(sea_orm::DeleteResult {
+            rows_affected: result.rows_affected,
+        })
     }
 
     /// Unwrap the inner `SeaORM` `DeleteMany` for advanced use cases.
@@ -224,6 +224,69 @@
 //! (or using higher-level helpers like `OData` pagination). Module code should not unwrap raw `SeaORM`
 //! builders.
 //!
+//! ### Example 7: Audit trail and soft delete
+//!
+//! Entities declared with `#[secure(..., audit, soft_delete = "deleted_at")]`
+//! record who changed them and keep deleted rows:
+//!
+//! ```rust,ignore
+//! use modkit_db::secure::{SecureDeleteEntityExt, SecureEntityExt, secure_insert_audited};
+//!
+//! let am = document::ActiveModel {
+//!     id: Set(id),
+//!     tenant_id: Set(tenant_id),
+//!     ..Default::default()
+//! };
+//! // created_*/updated_* from ctx.subject_id()
+//! secure_insert_audited::<document::Entity>(am, &ctx, &scope, &conn).await?;
+//!
+//! // UPDATE documents SET deleted_at = ..., deleted_by = ... WHERE <scope> AND id = ...
+//! document::Entity::secure_delete()
+//!     .scope_with(&scope)
+//!     .filter(Condition::all().add(document::Column::Id.eq(id)))
+//!     .audited(&ctx)
+//!     .exec(&conn)
+//!     .await?;
+//!
+//! // Soft-deleted rows are excluded unless requested.
+//! let all = document::Entity::find()
+//!     .secure()
+//!     .with_deleted()
+//!     .scope_with(&scope)
+//!     .all(&conn)
+//!     .await?;
+//! ```
+//!
+//! Updates go through `secure_update_audited` or `update_many(..).audited(&ctx)`.
+//! Writes of an audited entity without a `SecurityContext` fail with
+//! `ScopeError::Invalid`, and so does a soft delete built from a `DeleteMany`,
+//! whose own filters could not be carried over.
+//!
+//! ### Example 8: Optimistic concurrency
+//!
+//! Entities declared with `#[secure(..., version_col = "version")]` have the
+//! `i64` column incremented by every secure update. A caller that read version
+//! `v` (e.g. from an `If-Match` header) can make its write conditional on it:
+//!
+//! ```rust,ignore
+//! // UPDATE documents SET title = ..., version = version + 1
+//! //   WHERE <scope> AND id = ... AND version = 7
+//! let res = document::Entity::update_many()
+//!     .secure()
+//!     .scope_with(&scope)
+//!     .col_expr(document::Column::Title, Expr::value(title))
+//!     .filter(Condition::all().add(document::Column::Id.eq(id)))
+//!     .expect_version(7)
+//!     .exec(&conn)
+//!     .await;
+//! if let Err(ScopeError::VersionConflict { .. }) = res {
+//!     // Someone else wrote first: 412 Precondition Failed
+//! }
+//! ```
+//!
+//! `secure_update_with_scope` takes the expected version from the active
+//! model's version field.
+//!
 //! ## Integration with Repository Pattern
 //!
 //! A typical repository would look like:
@@ -282,7 +345,6 @@
 //! - `#[derive(Scopable)]` macro to auto-implement `ScopableEntity`
 //! - Support for scoped UPDATE and DELETE operations
 //! - Row-level security helpers for `PostgreSQL`
-//! - Audit logging integration
 //! - Policy composition (e.g., role-based filters)
 //!
 //! ## Error Handling
@@ -1,4 +1,4 @@
-use sea_orm::EntityTrait;
+use sea_orm::{EntityTrait, IdenStatic, Iterable};
 
 /// Defines the contract for entities that can be scoped by tenant, resource, owner, and type.
 ///
@@ -85,6 +85,48 @@ use sea_orm::EntityTrait;
 /// //   _                 => None
 /// ```
 ///
+/// # Audit and Soft Delete
+/// ```rust,ignore
+/// #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Scopable)]
+/// #[sea_orm(table_name = "documents")]
+/// #[secure(
+///     tenant_col = "tenant_id",
+///     resource_col = "id",
+///     no_owner,
+///     no_type,
+///     audit,
+///     soft_delete = "deleted_at",
+/// )]
+/// pub struct Model {
+///     #[sea_orm(primary_key)]
+///     pub id: Uuid,
+///     pub tenant_id: Uuid,
+///     pub created_at: OffsetDateTime,
+///     pub created_by: Uuid,
+///     pub updated_at: OffsetDateTime,
+///     pub updated_by: Uuid,
+///     pub deleted_at: Option<OffsetDateTime>,
+///     pub deleted_by: Option<Uuid>,
+/// }
+/// // Macro generates audit_cols() with the conventional column names above
+/// // and soft_delete_col() => Some(Column::DeletedAt).
+/// ```
+///
+/// # Full-Text Search
+/// ```rust,ignore
+/// #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Scopable)]
+/// #[sea_orm(table_name = "messages")]
+/// #[secure(tenant_col = "tenant_id", no_resource, no_owner, no_type, search = "title, body")]
+/// pub struct Model {
+///     #[sea_orm(primary_key)]
+///     pub id: Uuid,
+///     pub tenant_id: Uuid,
+///     pub title: String,
+///     pub body: Option<String>,
+/// }
+/// // Macro generates search_cols() => vec![Column::Title, Column::Body].
+/// ```
+///
 /// # Unrestricted Entities
 /// ```rust,ignore
 /// #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Scopable)]
@@ -152,4 +194,91 @@ pub trait ScopableEntity: EntityTrait {
     /// Manual implementors must provide all property arms explicitly.
     #[must_use]
     fn resolve_property(property: &str) -> Option<Self::Column>;
+
+    /// Returns the columns stamped with the acting subject and time on every
+    /// write, or `None` if the entity is not audited.
+    ///
+    /// Set via the `audit` flag of `#[derive(Scopable)]`.
+    ///
+    /// Default: `None`
+    #[must_use]
+    fn audit_cols() -> Option<AuditColumns<Self::Column>> {
+        None
+    }
+
+    /// Returns the nullable timestamp column that marks a row as deleted, or
+    /// `None` if deletes remove rows.
+    ///
+    /// When set, secure deletes become updates of this column and secure
+    /// selects skip rows where it is not `NULL`.
+    ///
+    /// Set via `soft_delete = "..."` of `#[derive(Scopable)]`.
+    ///
+    /// Default: `None`
+    #[must_use]
+    fn soft_delete_col() -> Option<Self::Column> {
+        None
+    }
+
+    /// Returns the `i64` row-version column used for optimistic concurrency,
+    /// or `None` if updates are last-write-wins.
+    ///
+    /// When set, secure updates increment it and may require the caller's
+    /// expected value (see `SecureUpdateMany::expect_version`).
+    ///
+    /// Set via `version_col = "..."` of `#[derive(Scopable)]`.
+    ///
+    /// Default: `None`
+    #[must_use]
+    fn version_col() -> Option<Self::Column> {
+        None
+    }
+
+    /// Returns the text columns matched by `$search`, or an empty list if the
+    /// entity is not searchable.
+    ///
+    /// The database needs a full-text index over exactly these columns; see
+    /// `odata::search::search_index_statements`.
+    ///
+    /// Set via `search = "col_a, col_b"` of `#[derive(Scopable)]`.
+    ///
+    /// Default: empty
+    #[must_use]
+    fn search_cols() -> Vec<Self::Column> {
+        Vec::new()
+    }
+
+    /// Resolve an API field name to the column that stores it.
+    ///
+    /// Field masks of an `AccessScope` name fields as the API exposes them;
+    /// every masking check resolves them through this method, so a column
+    /// renamed in the API is masked under its API name.
+    ///
+    /// Set via `field(api_name = "column")` entries of `#[derive(Scopable)]`.
+    ///
+    /// Default: the column of the same name ([`column_named`])
+    #[must_use]
+    fn resolve_field(field: &str) -> Option<Self::Column> {
+        column_named::<Self>(field)
+    }
+}
+
+/// The column of `E` named `name`, if any.
+#[must_use]
+pub fn column_named<E: EntityTrait>(name: &str) -> Option<E::Column> {
+    E::Column::iter().find(|c| c.as_str() == name)
+}
+
+/// Audit columns of a [`ScopableEntity`].
+///
+/// The `*_at` columns hold `OffsetDateTime` values and the `*_by` columns the
+/// `Uuid` of the acting subject (`SecurityContext::subject_id`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditColumns<C> {
+    pub created_at: C,
+    pub created_by: C,
+    pub updated_at: C,
+    pub updated_by: C,
+    /// Subject that soft-deleted the row; only present on soft-delete entities.
+    pub deleted_by: Option<C>,
 }
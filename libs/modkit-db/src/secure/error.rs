@@ -18,6 +18,11 @@ pub enum ScopeError {
     /// Operation denied - entity not accessible in current security scope.
     #[error("access denied: {0}")]
     Denied(&'static str),
+
+    /// Optimistic-concurrency conflict: no row in scope is at the expected
+    /// version, i.e. it changed since the caller read it (or is not visible).
+    #[error("version conflict: expected version {expected}")]
+    VersionConflict { expected: i64 },
 }
 
 impl ScopeError {
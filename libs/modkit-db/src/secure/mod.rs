@@ -89,6 +89,8 @@
 //! - **Implicit policy**: Automatic deny-all for empty scopes
 //! - **Multi-tenant support**: Enforces tenant isolation when applicable
 //! - **Resource-level access**: Fine-grained control via explicit IDs
+//! - **Audit and soft delete**: Opt-in per entity, see [`secure_insert_audited`]
+//! - **Optimistic concurrency**: `version_col` entities, see [`SecureUpdateMany::expect_version`]
 //! - **Zero runtime overhead**: All checks at compile/build time
 //!
 //! # Policy
@@ -103,6 +105,7 @@
 //! See the [docs module](docs) for comprehensive examples and usage patterns.
 
 // Module declarations
+mod audit;
 mod cond;
 mod db;
 mod db_ops;
@@ -121,7 +124,7 @@ mod tx_error;
 // Public API re-exports
 
 // Core types
-pub use entity_traits::ScopableEntity;
+pub use entity_traits::{AuditColumns, ScopableEntity, column_named};
 pub use error::{ScopeError, is_unique_violation};
 
 // Security types from modkit-security
@@ -139,6 +142,13 @@ pub use runner::DBRunner;
 
 pub(crate) use runner::{DBRunnerInternal, SeaOrmRunner};
 
+// Scoped EXISTS building blocks for `OData` lambdas
+pub(crate) use audit::not_deleted;
+pub(crate) use cond::scoped_exists_subquery;
+
+// Field masks, as projected by `scope_with`
+pub use cond::{ensure_maskable, hidden_columns, masked_column, masks_column};
+
 // Primary database types (new secure API)
 pub use db::{DEFAULT_TX_RETRY_ATTEMPTS, Db, DbConn, DbTx};
 
@@ -156,9 +166,10 @@ pub use select::{
 
 // Update/Delete/Insert operations
 pub use db_ops::{
-    SecureDeleteExt, SecureDeleteMany, SecureInsertExt, SecureInsertOne, SecureOnConflict,
-    SecureUpdateExt, SecureUpdateMany, secure_insert, secure_update_with_scope,
-    validate_tenant_in_scope,
+    BulkRowOutcome, BulkWriteReport, SecureDeleteEntityExt, SecureDeleteExt, SecureDeleteMany,
+    SecureInsertExt, SecureInsertOne, SecureOnConflict, SecureUpdateExt, SecureUpdateMany,
+    secure_insert, secure_insert_audited, secure_insert_many, secure_update_audited,
+    secure_update_with_scope, secure_upsert_many, validate_tenant_in_scope,
 };
 
 // Provider pattern for advanced tenant filtering
@@ -26,9 +26,28 @@ pub enum SeaOrmRunner<'a> {
     Tx(&'a sea_orm::DatabaseTransaction),
 }
 
+impl SeaOrmRunner<'_> {
+    /// Backend of the underlying connection, for dialect-specific SQL.
+    pub(crate) fn backend(&self) -> sea_orm::DbBackend {
+        use sea_orm::ConnectionTrait;
+
+        match self {
+            Self::Conn(db) => db.get_database_backend(),
+            Self::Tx(tx) => tx.get_database_backend(),
+        }
+    }
+}
+
 /// Internal-only bridge to `SeaORM`'s executor types.
 pub trait DBRunnerInternal: sealed::Sealed + Send + Sync {
     fn as_seaorm(&self) -> SeaOrmRunner<'_>;
+
+    /// Executor for scoped reads that may be served by a read replica.
+    ///
+    /// Defaults to [`Self::as_seaorm`]; only `DbConn` reads from replicas.
+    fn as_seaorm_read(&self) -> SeaOrmRunner<'_> {
+        self.as_seaorm()
+    }
 }
 
 /// Hidden capability marker used by repositories and services.
@@ -47,6 +66,10 @@ impl DBRunnerInternal for DbConn<'_> {
     fn as_seaorm(&self) -> SeaOrmRunner<'_> {
         SeaOrmRunner::Conn(self.conn)
     }
+
+    fn as_seaorm_read(&self) -> SeaOrmRunner<'_> {
+        SeaOrmRunner::Conn(self.read)
+    }
 }
 impl DBRunner for DbConn<'_> {}
 
@@ -51,19 +51,21 @@ use std::{future::Future, pin::Pin};
 
 use sea_orm::{
     AccessMode, ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
-    IsolationLevel, QueryFilter, TransactionTrait, sea_query::Expr,
+    IsolationLevel, TransactionTrait, sea_query::Expr,
 };
 use uuid::Uuid;
 
 use crate::secure::tx_error::{InfraError, TxError};
 
-use modkit_security::AccessScope;
+use modkit_security::{AccessScope, SecurityContext};
 
 use crate::secure::tx_config::TxConfig;
 
 use crate::secure::{ScopableEntity, ScopeError, Scoped, SecureEntityExt, SecureSelect};
 
-use crate::secure::db_ops::{SecureDeleteExt, SecureDeleteMany, SecureUpdateExt, SecureUpdateMany};
+use crate::secure::db_ops::{
+    SecureDeleteEntityExt, SecureDeleteMany, SecureUpdateExt, SecureUpdateMany,
+};
 
 /// Secure transaction wrapper (capability).
 ///
@@ -242,7 +244,7 @@ impl SecureConn {
         E: ScopableEntity + EntityTrait,
         E::Column: ColumnTrait + Copy,
     {
-        E::delete_many().secure().scope_with(scope)
+        E::secure_delete().scope_with(scope)
     }
 
     /// Create a scoped insert builder with `on_conflict()` support.
@@ -384,6 +386,9 @@ impl SecureConn {
     /// Delete a single entity by ID (scoped).
     ///
     /// This validates the entity exists in scope before deleting.
+    /// Soft-delete entities that are audited need
+    /// [`delete_by_id_audited`](Self::delete_by_id_audited) instead, which
+    /// records the deleting subject.
     ///
     /// # Example
     ///
@@ -401,6 +406,41 @@ impl SecureConn {
     ///
     /// Returns `ScopeError::Invalid` if the entity does not have a `resource_col` defined.
     pub async fn delete_by_id<E>(&self, scope: &AccessScope, id: Uuid) -> Result<bool, ScopeError>
+    where
+        E: ScopableEntity + EntityTrait,
+        E::Column: ColumnTrait + Copy,
+    {
+        self.delete_by_id_with::<E>(scope, id, None).await
+    }
+
+    /// Delete a single entity by ID (scoped), recording the subject of `ctx`.
+    ///
+    /// Soft deletes of audited entities set `deleted_by` to
+    /// `ctx.subject_id()`; otherwise the same as
+    /// [`delete_by_id`](Self::delete_by_id).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopeError::Invalid` if the entity does not have a `resource_col` defined.
+    pub async fn delete_by_id_audited<E>(
+        &self,
+        ctx: &SecurityContext,
+        scope: &AccessScope,
+        id: Uuid,
+    ) -> Result<bool, ScopeError>
+    where
+        E: ScopableEntity + EntityTrait,
+        E::Column: ColumnTrait + Copy,
+    {
+        self.delete_by_id_with::<E>(scope, id, Some(ctx)).await
+    }
+
+    async fn delete_by_id_with<E>(
+        &self,
+        scope: &AccessScope,
+        id: Uuid,
+        ctx: Option<&SecurityContext>,
+    ) -> Result<bool, ScopeError>
     where
         E: ScopableEntity + EntityTrait,
         E::Column: ColumnTrait + Copy,
@@ -409,12 +449,13 @@ impl SecureConn {
             ScopeError::Invalid("Entity must have a resource_col to use delete_by_id()")
         })?;
 
-        let result = E::delete_many()
-            .filter(sea_orm::Condition::all().add(Expr::col(resource_col).eq(id)))
-            .secure()
+        let mut delete = E::secure_delete()
             .scope_with(scope)
-            .exec(self)
-            .await?;
+            .filter(sea_orm::Condition::all().add(Expr::col(resource_col).eq(id)));
+        if let Some(ctx) = ctx {
+            delete = delete.audited(ctx);
+        }
+        let result = delete.exec(self).await?;
 
         Ok(result.rows_affected > 0)
     }
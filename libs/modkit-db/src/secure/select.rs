@@ -1,10 +1,13 @@
 use sea_orm::{
-    ColumnTrait, EntityTrait, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
-    Related, sea_query::Expr,
+    ColumnTrait, EntityTrait, IdenStatic, Iterable, ModelTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Related, sea_query::Expr,
 };
 use std::sync::Arc;
 
-use crate::secure::cond::build_scope_condition;
+use crate::secure::audit::not_deleted;
+use crate::secure::cond::{
+    UNMASKABLE_FIELD, build_scope_condition, ensure_maskable, masked_column, scoped_exists_subquery,
+};
 use crate::secure::error::ScopeError;
 use crate::secure::{AccessScope, DBRunner, DBRunnerInternal, ScopableEntity, SeaOrmRunner};
 
@@ -22,6 +25,19 @@ pub struct Unscoped;
 #[derive(Debug, Clone)]
 pub struct Scoped {
     scope: Arc<AccessScope>,
+    /// The scope masks a column the model cannot hold `NULL` in (see
+    /// [`ensure_maskable`]); running the query fails.
+    unmaskable: bool,
+}
+
+impl Scoped {
+    /// Refuse to run a query whose masked projection cannot be decoded.
+    fn ensure_maskable(&self) -> Result<(), ScopeError> {
+        if self.unmaskable {
+            return Err(ScopeError::Invalid(UNMASKABLE_FIELD));
+        }
+        Ok(())
+    }
 }
 
 /// A type-safe wrapper around `SeaORM`'s `Select` that enforces scoping.
@@ -34,6 +50,9 @@ pub struct Scoped {
 /// `find_with_related`) to automatically apply the same scope to related
 /// entities without requiring the scope to be passed again.
 ///
+/// On a [`DbConn`](crate::secure::DbConn) with read replicas, the query runs on
+/// a replica; in a transaction or on `Db::conn_primary()` it runs on the primary.
+///
 /// # Type Parameters
 /// - `E`: The `SeaORM` entity type
 /// - `S`: The typestate (`Unscoped` or `Scoped`)
@@ -62,6 +81,8 @@ pub struct Scoped {
 pub struct SecureSelect<E: EntityTrait, S> {
     pub(crate) inner: sea_orm::Select<E>,
     pub(crate) state: S,
+    /// Skip the soft-delete filter when scoping.
+    pub(crate) with_deleted: bool,
 }
 
 /// A type-safe wrapper around `SeaORM`'s `SelectTwo` that enforces scoping.
@@ -143,6 +164,7 @@ where
         SecureSelect {
             inner: self,
             state: Unscoped,
+            with_deleted: false,
         }
     }
 }
@@ -164,14 +186,12 @@ where
     /// - Resources only → filter by resource IDs
     /// - Both → AND them together
     ///
+    /// Columns masked by the scope's constraints are projected as `NULL` or
+    /// a redacted value on the rows the caller may not read them on. If a
+    /// `NULL` would land in a non-`Option` field, running the query returns
+    /// `ScopeError::Invalid` instead of failing to decode the rows.
     pub fn scope_with(self, scope: &AccessScope) -> SecureSelect<E, Scoped> {
-        let cond = build_scope_condition::<E>(scope);
-        SecureSelect {
-            inner: self.inner.filter(cond),
-            state: Scoped {
-                scope: Arc::new(scope.clone()),
-            },
-        }
+        self.scope_with_arc(Arc::new(scope.clone()))
     }
 
     /// Apply access control scope using an `Arc<AccessScope>`.
@@ -179,12 +199,44 @@ where
     /// This is useful when you already have the scope in an `Arc` and want to
     /// avoid an extra clone.
     pub fn scope_with_arc(self, scope: Arc<AccessScope>) -> SecureSelect<E, Scoped> {
-        let cond = build_scope_condition::<E>(&scope);
+        let mut inner = self.inner.filter(build_scope_condition::<E>(&scope));
+        if !self.with_deleted
+            && let Some(cond) = not_deleted::<E>()
+        {
+            inner = inner.filter(cond);
+        }
+        if scope.has_masked_fields() {
+            inner = project_masked(inner, &scope);
+        }
+        let unmaskable = ensure_maskable::<E>(&scope).is_err();
         SecureSelect {
-            inner: self.inner.filter(cond),
-            state: Scoped { scope },
+            inner,
+            state: Scoped { scope, unmaskable },
+            with_deleted: self.with_deleted,
         }
     }
+
+    /// Include soft-deleted rows in the results.
+    ///
+    /// No-op for entities without `soft_delete_col()`.
+    pub fn with_deleted(mut self) -> Self {
+        self.with_deleted = true;
+        self
+    }
+}
+
+/// Re-select every column of `E`, masking the ones `scope` restricts.
+fn project_masked<E>(select: sea_orm::Select<E>, scope: &AccessScope) -> sea_orm::Select<E>
+where
+    E: ScopableEntity + EntityTrait,
+    E::Column: ColumnTrait + Copy,
+{
+    E::Column::iter().fold(select.select_only(), |select, col| {
+        match masked_column::<E>(scope, col) {
+            Some(expr) => select.column_as(expr, col.as_str()),
+            None => select.column(col),
+        }
+    })
 }
 
 // Methods available only on Scoped queries
@@ -195,10 +247,12 @@ where
     /// Execute the query and return all matching results.
     ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database query fails.
+    /// Returns `ScopeError::Invalid` if the scope's field masks cannot be
+    /// projected into the model, and `ScopeError::Db` if the query fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn all(self, runner: &impl DBRunner) -> Result<Vec<E::Model>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        self.state.ensure_maskable()?;
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.all(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.all(tx).await?),
         }
@@ -207,9 +261,25 @@ where
     /// Execute the query and return at most one result.
     ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database query fails.
+    /// Returns `ScopeError::Invalid` if the scope's field masks cannot be
+    /// projected into the model, and `ScopeError::Db` if the query fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn one(self, runner: &impl DBRunner) -> Result<Option<E::Model>, ScopeError> {
+        self.state.ensure_maskable()?;
+        match DBRunnerInternal::as_seaorm_read(runner) {
+            SeaOrmRunner::Conn(db) => Ok(self.inner.one(db).await?),
+            SeaOrmRunner::Tx(tx) => Ok(self.inner.one(tx).await?),
+        }
+    }
+
+    /// Like [`Self::one`], but never served by a read replica; for the reads
+    /// of write helpers.
+    #[allow(clippy::disallowed_methods)]
+    pub(crate) async fn one_on_primary(
+        self,
+        runner: &impl DBRunner,
+    ) -> Result<Option<E::Model>, ScopeError> {
+        self.state.ensure_maskable()?;
         match DBRunnerInternal::as_seaorm(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.one(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.one(tx).await?),
@@ -219,13 +289,15 @@ where
     /// Execute the query and return the number of matching results.
     ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database query fails.
+    /// Returns `ScopeError::Invalid` if the scope's field masks cannot be
+    /// projected into the model, and `ScopeError::Db` if the query fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn count(self, runner: &impl DBRunner) -> Result<u64, ScopeError>
     where
         E::Model: sea_orm::FromQueryResult + Send + Sync,
     {
-        match DBRunnerInternal::as_seaorm(runner) {
+        self.state.ensure_maskable()?;
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.count(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.count(tx).await?),
         }
@@ -335,7 +407,8 @@ where
     /// # Note
     /// This is a simplified EXISTS check (no join predicate linking back to the
     /// primary entity). For complex join predicates, use `into_inner()` and build
-    /// custom EXISTS clauses.
+    /// custom EXISTS clauses. `OData` `any`/`all` lambdas over collections
+    /// declared with `FieldMap::insert_related` build the correlated form.
     ///
     /// # Example
     /// ```ignore
@@ -352,12 +425,7 @@ where
         J: ScopableEntity + EntityTrait,
         J::Column: ColumnTrait + Copy,
     {
-        use sea_orm::sea_query::Query;
-
-        let cond = build_scope_condition::<J>(scope);
-
-        let mut sub = Query::select();
-        sub.expr(Expr::value(1)).from(J::default()).cond_where(cond);
+        let sub = scoped_exists_subquery::<J>(scope, sea_orm::Condition::all());
 
         self.inner =
             QueryFilter::filter(self.inner, sea_orm::Condition::all().add(Expr::exists(sub)));
@@ -374,6 +442,9 @@ where
     /// Because the method consumes `SecureSelect<E, Scoped>`, the compiler
     /// guarantees that scope was applied before the projection runs.
     ///
+    /// A custom projection replaces the masked one of [`SecureSelect::scope_with`],
+    /// so it is refused when the scope masks any field.
+    ///
     /// # Example
     /// ```rust,ignore
     /// let counts: Vec<ChatCount> = MsgEntity::find()
@@ -391,7 +462,8 @@ where
     /// ```
     ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database query fails.
+    /// Returns `ScopeError::Denied` if the scope masks fields, and
+    /// `ScopeError::Db` if the database query fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn project_all<T, C, F>(self, runner: &C, project: F) -> Result<Vec<T>, ScopeError>
     where
@@ -399,8 +471,13 @@ where
         C: DBRunner,
         F: FnOnce(sea_orm::Select<E>) -> sea_orm::Selector<sea_orm::SelectModel<T>>,
     {
+        if self.state.scope.has_masked_fields() {
+            return Err(ScopeError::Denied(
+                "custom projections would bypass the field masks of the scope",
+            ));
+        }
         let selector = project(self.inner);
-        match DBRunnerInternal::as_seaorm(runner) {
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(selector.all(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(selector.all(tx).await?),
         }
@@ -411,7 +488,12 @@ where
     /// Prefer [`project_all`](Self::project_all) for custom projections —
     /// it preserves compile-time scope enforcement. Use `into_inner()` only
     /// when the query must be passed to an API that requires a raw `Select<E>`
-    /// (e.g., `paginate_odata`).
+    /// (e.g., `paginate_with_odata`).
+    ///
+    /// The returned select keeps the masked projection, but re-projecting
+    /// (`select_only`, `column`), filtering or ordering it by a masked column
+    /// reads the raw values; check [`AccessScope::has_masked_fields`] first,
+    /// or use `OPager`, which refuses such queries.
     #[must_use]
     pub fn into_inner(self) -> sea_orm::Select<E> {
         self.inner
@@ -610,13 +692,15 @@ where
     /// Returns pairs of `(E::Model, Option<F::Model>)`.
     ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database query fails.
+    /// Returns `ScopeError::Invalid` if the scope's field masks cannot be
+    /// projected into the model, and `ScopeError::Db` if the query fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn all(
         self,
         runner: &impl DBRunner,
     ) -> Result<Vec<(E::Model, Option<F::Model>)>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        self.state.ensure_maskable()?;
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.all(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.all(tx).await?),
         }
@@ -625,13 +709,15 @@ where
     /// Execute the query and return at most one result.
     ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database query fails.
+    /// Returns `ScopeError::Invalid` if the scope's field masks cannot be
+    /// projected into the model, and `ScopeError::Db` if the query fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn one(
         self,
         runner: &impl DBRunner,
     ) -> Result<Option<(E::Model, Option<F::Model>)>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        self.state.ensure_maskable()?;
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.one(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.one(tx).await?),
         }
@@ -691,13 +777,15 @@ where
     /// Returns pairs of `(E::Model, Vec<F::Model>)`.
     ///
     /// # Errors
-    /// Returns `ScopeError::Db` if the database query fails.
+    /// Returns `ScopeError::Invalid` if the scope's field masks cannot be
+    /// projected into the model, and `ScopeError::Db` if the query fails.
     #[allow(clippy::disallowed_methods)]
     pub async fn all(
         self,
         runner: &impl DBRunner,
     ) -> Result<Vec<(E::Model, Vec<F::Model>)>, ScopeError> {
-        match DBRunnerInternal::as_seaorm(runner) {
+        self.state.ensure_maskable()?;
+        match DBRunnerInternal::as_seaorm_read(runner) {
             SeaOrmRunner::Conn(db) => Ok(self.inner.all(db).await?),
             SeaOrmRunner::Tx(tx) => Ok(self.inner.all(tx).await?),
         }
@@ -810,6 +898,7 @@ mod tests {
         let scope = AccessScope::default();
         let scoped = Scoped {
             scope: Arc::new(scope),
+            unmaskable: false,
         };
         assert!(!scoped.scope.has_property(pep_properties::OWNER_TENANT_ID)); // default scope has no tenants
     }
@@ -820,6 +909,7 @@ mod tests {
         let scope = AccessScope::for_tenants(vec![tenant_id]);
         let scoped = Scoped {
             scope: Arc::new(scope),
+            unmaskable: false,
         };
 
         // Verify the scope is accessible
@@ -844,6 +934,7 @@ mod tests {
         let scope = AccessScope::for_tenants(vec![uuid::Uuid::new_v4()]);
         let scoped = Scoped {
             scope: Arc::new(scope),
+            unmaskable: false,
         };
 
         // Cloning should share the Arc
@@ -30,6 +30,7 @@ fn test_dbconnconfig_serialization() {
             ..Default::default()
         }),
         server: Some("test_server".to_owned()),
+        replicas: None,
     };
 
     // Test serialization to JSON
@@ -70,6 +70,7 @@ async fn test_dbmanager_server_merge() {
                 ..Default::default()
             }),
             server: None,
+            replicas: None,
         },
     );
 
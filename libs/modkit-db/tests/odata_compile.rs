@@ -6,9 +6,15 @@ mod tests {
     use sea_orm::entity::prelude::*;
     use std::str::FromStr;
 
-    use modkit_db::odata::{FieldMap, expr_to_condition};
+    use modkit_db::odata::search::{search_condition, search_index_statements};
+    use modkit_db::odata::{
+        FieldMap, FieldToColumn, expr_to_condition, expr_to_scoped_condition,
+        filter_node_to_condition, filter_node_to_condition_for,
+    };
+    use modkit_db::secure::{AccessScope, ScopableEntity};
     use modkit_odata::ast::{CompareOperator, Expr, Value};
-    use modkit_odata::filter::FieldKind;
+    use modkit_odata::filter::{FieldKind, FilterField, parse_odata_filter};
+    use sea_orm::{DbBackend, QueryTrait};
 
     // Simple test entity for compilation tests
     #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
@@ -19,6 +25,8 @@ mod tests {
         pub name: String,
         pub score: i64,
         pub email: String,
+        pub created_at: DateTimeUtc,
+        pub tags: Json,
     }
 
     #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -26,6 +34,67 @@ mod tests {
 
     impl ActiveModelBehavior for ActiveModel {}
 
+    mod item {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(table_name = "test_items")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: uuid::Uuid,
+            pub user_id: uuid::Uuid,
+            pub tenant_id: uuid::Uuid,
+            pub qty: i64,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    impl ScopableEntity for item::Entity {
+        fn tenant_col() -> Option<item::Column> {
+            Some(item::Column::TenantId)
+        }
+        fn resource_col() -> Option<item::Column> {
+            None
+        }
+        fn owner_col() -> Option<item::Column> {
+            None
+        }
+        fn type_col() -> Option<item::Column> {
+            None
+        }
+        fn resolve_property(property: &str) -> Option<item::Column> {
+            match property {
+                "owner_tenant_id" => Self::tenant_col(),
+                _ => None,
+            }
+        }
+    }
+
+    impl ScopableEntity for Entity {
+        fn tenant_col() -> Option<Column> {
+            None
+        }
+        fn resource_col() -> Option<Column> {
+            Some(Column::Id)
+        }
+        fn owner_col() -> Option<Column> {
+            None
+        }
+        fn type_col() -> Option<Column> {
+            None
+        }
+        fn resolve_property(_property: &str) -> Option<Column> {
+            None
+        }
+        fn search_cols() -> Vec<Column> {
+            vec![Column::Name, Column::Email]
+        }
+    }
+
     fn setup_field_map() -> FieldMap<Entity> {
         FieldMap::<Entity>::new()
             .insert("id", Column::Id, FieldKind::Uuid)
@@ -125,4 +194,245 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("unknown field"));
     }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    enum Field {
+        Name,
+        Score,
+        CreatedAt,
+    }
+
+    impl FilterField for Field {
+        const FIELDS: &'static [Self] = &[Field::Name, Field::Score, Field::CreatedAt];
+
+        fn name(&self) -> &'static str {
+            match self {
+                Field::Name => "name",
+                Field::Score => "score",
+                Field::CreatedAt => "created_at",
+            }
+        }
+
+        fn kind(&self) -> FieldKind {
+            match self {
+                Field::Name => FieldKind::String,
+                Field::Score => FieldKind::I64,
+                Field::CreatedAt => FieldKind::DateTimeUtc,
+            }
+        }
+    }
+
+    struct FieldMapper;
+
+    impl FieldToColumn<Field> for FieldMapper {
+        type Column = Column;
+
+        fn map_field(field: Field) -> Column {
+            match field {
+                Field::Name => Column::Name,
+                Field::Score => Column::Score,
+                Field::CreatedAt => Column::CreatedAt,
+            }
+        }
+    }
+
+    fn where_sql(filter: &str, backend: DbBackend) -> String {
+        let node = parse_odata_filter::<Field>(filter).unwrap();
+        let condition = filter_node_to_condition_for::<Field, FieldMapper>(&node, backend).unwrap();
+        let sql = Entity::find().filter(condition).build(backend).to_string();
+        sql.split_once(" WHERE ").unwrap().1.to_owned()
+    }
+
+    #[test]
+    fn test_string_functions_are_portable() {
+        for backend in [DbBackend::Postgres, DbBackend::MySql, DbBackend::Sqlite] {
+            let sql = where_sql("tolower(name) eq 'bob' and trim(name) ne ''", backend);
+            assert!(sql.contains("LOWER("), "{sql}");
+            assert!(sql.contains("TRIM("), "{sql}");
+            assert!(!sql.contains('$'), "{sql}");
+        }
+
+        let node = parse_odata_filter::<Field>("toupper(name) eq 'BOB'").unwrap();
+        assert!(filter_node_to_condition::<Field, FieldMapper>(&node).is_ok());
+        let node = parse_odata_filter::<Field>("length(name) gt 2").unwrap();
+        assert!(filter_node_to_condition::<Field, FieldMapper>(&node).is_err());
+    }
+
+    #[test]
+    fn test_dialect_functions_follow_backend() {
+        let sql = where_sql("length(name) eq 3", DbBackend::Postgres);
+        assert!(sql.contains("CHAR_LENGTH(\"name\")"), "{sql}");
+        let sql = where_sql("length(name) eq 3", DbBackend::Sqlite);
+        assert!(sql.contains("LENGTH(\"name\")"), "{sql}");
+
+        let sql = where_sql("indexof(name, 'o') eq 1", DbBackend::Postgres);
+        assert!(sql.contains("(STRPOS(\"name\", 'o') - 1)) = 1"), "{sql}");
+        let sql = where_sql("indexof(name, 'o') eq 1", DbBackend::MySql);
+        assert!(sql.contains("(INSTR(`name`, 'o') - 1)) = 1"), "{sql}");
+
+        let sql = where_sql("year(created_at) eq 2024", DbBackend::Postgres);
+        assert!(sql.contains("EXTRACT(YEAR FROM \"created_at\")"), "{sql}");
+        let sql = where_sql("hour(created_at) ge 9", DbBackend::Sqlite);
+        assert!(sql.contains("strftime('%H', \"created_at\")"), "{sql}");
+        let sql = where_sql("date(created_at) eq 2024-01-31", DbBackend::MySql);
+        assert!(sql.contains("DATE(`created_at`)"), "{sql}");
+    }
+
+    #[test]
+    fn test_null_literal_becomes_is_null() {
+        let sql = where_sql("name eq null", DbBackend::Postgres);
+        assert_eq!(sql, "\"name\" IS NULL");
+        let sql = where_sql("name ne null", DbBackend::Postgres);
+        assert_eq!(sql, "\"name\" IS NOT NULL");
+        let sql = where_sql("tolower(name) eq null", DbBackend::Postgres);
+        assert!(sql.ends_with("IS NULL"), "{sql}");
+    }
+
+    fn lambda_where_sql(filter: &str, backend: DbBackend) -> String {
+        let fmap = setup_field_map()
+            .insert_json_array("tags", Column::Tags, FieldKind::String)
+            .insert_related(
+                "items",
+                Column::Id,
+                item::Column::UserId,
+                FieldMap::<item::Entity>::new().insert("qty", item::Column::Qty, FieldKind::I64),
+            );
+        let ast = modkit_odata::parse_filter_string(filter)
+            .unwrap()
+            .into_expr();
+        let scope = AccessScope::for_tenants(vec![uuid::Uuid::nil()]);
+        let condition = expr_to_scoped_condition(&ast, &fmap, backend, &scope).unwrap();
+        let sql = Entity::find().filter(condition).build(backend).to_string();
+        sql.split_once(" WHERE ").unwrap().1.to_owned()
+    }
+
+    #[test]
+    fn test_related_lambda_is_scoped_exists() {
+        let sql = lambda_where_sql("items/any(i: i/qty gt 5)", DbBackend::Postgres);
+        assert!(sql.starts_with("EXISTS"), "{sql}");
+        assert!(sql.contains("FROM \"test_items\""), "{sql}");
+        assert!(
+            sql.contains("\"test_items\".\"user_id\" = \"test_users\".\"id\""),
+            "{sql}"
+        );
+        assert!(sql.contains("\"qty\" > 5"), "{sql}");
+        assert!(sql.contains("\"tenant_id\" IN"), "{sql}");
+
+        let sql = lambda_where_sql("items/all(i: i/qty gt 5)", DbBackend::Postgres);
+        assert!(sql.starts_with("NOT EXISTS"), "{sql}");
+        assert!(sql.matches("NOT").count() == 2, "{sql}");
+
+        let sql = lambda_where_sql("not items/any()", DbBackend::Postgres);
+        assert!(sql.contains("EXISTS"), "{sql}");
+        assert!(!sql.contains("\"qty\""), "{sql}");
+    }
+
+    #[test]
+    fn test_json_array_lambda_follows_backend() {
+        let sql = lambda_where_sql("tags/any(t: t eq 'urgent')", DbBackend::Sqlite);
+        assert!(sql.contains("json_each(\"test_users\".\"tags\")"), "{sql}");
+        assert!(sql.contains("\"elem\".\"value\" = 'urgent'"), "{sql}");
+
+        let sql = lambda_where_sql("tags/any(t: startswith(t, 'ur'))", DbBackend::Postgres);
+        assert!(
+            sql.contains("jsonb_array_elements_text(CAST(\"test_users\".\"tags\" AS jsonb))"),
+            "{sql}"
+        );
+        assert!(
+            sql.contains("CAST(\"elem\".\"elem\" AS TEXT) LIKE 'ur%'"),
+            "{sql}"
+        );
+
+        let sql = lambda_where_sql("tags/all(t: t ne 'spam')", DbBackend::MySql);
+        assert!(sql.starts_with("NOT EXISTS"), "{sql}");
+        assert!(
+            sql.contains("COLUMNS (value VARCHAR(1024) PATH '$')"),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_lambdas_need_scoped_compilation() {
+        let fmap = setup_field_map().insert_json_array("tags", Column::Tags, FieldKind::String);
+        let ast = modkit_odata::parse_filter_string("tags/any(t: t eq 'x')")
+            .unwrap()
+            .into_expr();
+        assert!(expr_to_condition::<Entity>(&ast, &fmap).is_err());
+
+        // Outer fields and unknown members are not resolved inside the lambda
+        for filter in ["tags/any(t: name eq 'x')", "items/any(i: i/qty gt 1)"] {
+            let ast = modkit_odata::parse_filter_string(filter)
+                .unwrap()
+                .into_expr();
+            let scope = AccessScope::for_tenants(vec![uuid::Uuid::nil()]);
+            assert!(
+                expr_to_scoped_condition(&ast, &fmap, DbBackend::Postgres, &scope).is_err(),
+                "{filter}"
+            );
+        }
+    }
+
+    fn search_where_sql(search: &str, backend: DbBackend) -> String {
+        let search = modkit_odata::parse_search(search).unwrap();
+        let condition = search_condition::<Entity>(&search, backend).unwrap();
+        let sql = Entity::find().filter(condition).build(backend).to_string();
+        sql.split_once(" WHERE ").unwrap().1.to_owned()
+    }
+
+    #[test]
+    fn test_search_uses_native_full_text() {
+        let sql = search_where_sql("budget -draft", DbBackend::Postgres);
+        assert!(
+            sql.starts_with("to_tsvector('simple', coalesce(\"test_users\".\"name\", '')"),
+            "{sql}"
+        );
+        assert!(
+            sql.contains(
+                "@@ (plainto_tsquery('simple', 'budget') && !!plainto_tsquery('simple', 'draft'))"
+            ),
+            "{sql}"
+        );
+
+        let sql = search_where_sql("budget \"q3 report\"", DbBackend::MySql);
+        assert!(
+            sql.starts_with("MATCH (`test_users`.`name`, `test_users`.`email`) AGAINST ("),
+            "{sql}"
+        );
+        assert!(sql.ends_with(" IN BOOLEAN MODE)"), "{sql}");
+        assert!(sql.contains("q3 report"), "{sql}");
+
+        let sql = search_where_sql("budget", DbBackend::Sqlite);
+        assert!(
+            sql.starts_with(
+                "\"test_users\".\"rowid\" IN (SELECT rowid FROM \"test_users_fts\" \
+                 WHERE \"test_users_fts\" MATCH "
+            ),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_search_index_matches_condition() {
+        let pg = search_index_statements::<Entity>(DbBackend::Postgres).unwrap();
+        assert_eq!(pg.len(), 1);
+        let document = "coalesce(\"name\", '') || ' ' || coalesce(\"email\", '')";
+        assert!(
+            pg[0].contains(&format!("USING GIN (to_tsvector('simple', {document}))")),
+            "{}",
+            pg[0]
+        );
+
+        let sqlite = search_index_statements::<Entity>(DbBackend::Sqlite).unwrap();
+        assert!(sqlite[0].contains("USING fts5(\"name\", \"email\", content=\"test_users\""));
+        assert!(
+            sqlite
+                .iter()
+                .any(|s| s.contains("AFTER UPDATE ON \"test_users\""))
+        );
+
+        // Entities without search columns cannot be searched
+        let search = modkit_odata::parse_search("budget").unwrap();
+        assert!(search_condition::<item::Entity>(&search, DbBackend::Postgres).is_err());
+        assert!(search_index_statements::<item::Entity>(DbBackend::MySql).is_err());
+    }
 }
@@ -9,12 +9,12 @@
 
 use anyhow::anyhow;
 use modkit_db::migration_runner::run_migrations_for_testing;
-use modkit_db::odata::FieldMap;
 use modkit_db::odata::pager::OPager;
-use modkit_db::secure::{Db, DbConn, ScopableEntity, secure_insert};
+use modkit_db::odata::{FieldMap, LimitCfg, paginate_with_odata};
+use modkit_db::secure::{Db, DbConn, ScopableEntity, SecureEntityExt, secure_insert};
 use modkit_db::{ConnectOpts, connect_db};
-use modkit_odata::ODataQuery;
 use modkit_odata::filter::FieldKind;
+use modkit_odata::{CursorV1, Error as ODataError, ODataLimits, ODataQuery, SortDir};
 use modkit_security::{AccessScope, pep_properties};
 use sea_orm::Set;
 use sea_orm::entity::prelude::*;
@@ -190,3 +190,334 @@ async fn paginate_odata_works_with_secure_conn() {
 
     assert_eq!(page.items.len(), 2, "page size");
 }
+
+#[tokio::test]
+async fn paginate_with_odata_rejects_unsupported_options() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+
+    let fmap: FieldMap<ent::Entity> = FieldMap::new()
+        .insert_with_extractor("id", ent::Column::Id, FieldKind::I64, |m: &ent::Model| {
+            m.id.to_string()
+        })
+        .insert("score", ent::Column::Score, FieldKind::I64);
+    let select = || {
+        ent::Entity::find()
+            .secure()
+            .scope_with(&test_db.scope)
+            .into_inner()
+    };
+    let limits = LimitCfg {
+        default: 10,
+        max: 100,
+    };
+
+    let q = ODataQuery {
+        count: true,
+        ..Default::default()
+    };
+    let err = paginate_with_odata(
+        select(),
+        &conn,
+        &q,
+        &fmap,
+        ("id", SortDir::Asc),
+        limits,
+        &ODataLimits::default(),
+        |m| m.id,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, ODataError::CountNotAllowed), "got {err:?}");
+
+    // Opted in, the total covers every scoped row, not just the page.
+    let q = ODataQuery {
+        count: true,
+        limit: Some(1),
+        ..Default::default()
+    };
+    let page = paginate_with_odata(
+        select(),
+        &conn,
+        &q,
+        &fmap,
+        ("id", SortDir::Asc),
+        limits,
+        &ODataLimits::new().with_count(),
+        |m| m.id,
+    )
+    .await
+    .expect("counted page");
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.page_info.total_count, Some(4));
+
+    let q = ODataQuery::new()
+        .with_apply(modkit_odata::parse_apply("groupby((score),aggregate($count as n))").unwrap());
+    let err = paginate_with_odata(
+        select(),
+        &conn,
+        &q,
+        &fmap,
+        ("id", SortDir::Asc),
+        limits,
+        &ODataLimits::default(),
+        |m| m.id,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, ODataError::ApplyNotAllowed), "got {err:?}");
+
+    let q = ODataQuery::new().with_search(modkit_odata::parse_search("alice").unwrap());
+    let err = paginate_with_odata(
+        select(),
+        &conn,
+        &q,
+        &fmap,
+        ("id", SortDir::Asc),
+        limits,
+        &ODataLimits::default(),
+        |m| m.id,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, ODataError::SearchNotAllowed), "got {err:?}");
+
+    let page = paginate_with_odata(
+        select(),
+        &conn,
+        &ODataQuery::default(),
+        &fmap,
+        ("id", SortDir::Asc),
+        limits,
+        &ODataLimits::default(),
+        |m| m.id,
+    )
+    .await
+    .expect("plain page");
+    assert_eq!(page.items.len(), 4);
+}
+
+#[tokio::test]
+async fn opager_count_is_opt_in_and_respects_filter() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+    // Rows of another tenant must not be counted.
+    let other_tenant = Uuid::new_v4();
+    seed(
+        &conn,
+        other_tenant,
+        &AccessScope::for_tenants(vec![other_tenant]),
+    )
+    .await;
+
+    let fmap: FieldMap<ent::Entity> = FieldMap::new()
+        .insert_with_extractor("id", ent::Column::Id, FieldKind::I64, |m: &ent::Model| {
+            m.id.to_string()
+        })
+        .insert("score", ent::Column::Score, FieldKind::I64);
+
+    let q = ODataQuery::new()
+        .with_filter(
+            modkit_odata::parse_filter_string("score ge 20")
+                .unwrap()
+                .into_expr(),
+        )
+        .with_limit(1)
+        .with_count(true);
+
+    let err = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.score)
+        .await
+        .expect_err("count not enabled");
+    assert!(matches!(err, modkit_odata::Error::CountNotAllowed));
+
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .odata_limits(ODataLimits::new().with_count())
+        .fetch(&q, |m| m.score)
+        .await
+        .expect("fetch");
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.page_info.total_count, Some(3));
+
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .odata_limits(ODataLimits::new().with_count())
+        .fetch(&q.clone().with_count(false), |m| m.score)
+        .await
+        .expect("fetch without count");
+    assert_eq!(page.page_info.total_count, None);
+}
+
+#[tokio::test]
+async fn opager_filter_compiles_string_functions() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+
+    let fmap: FieldMap<ent::Entity> = FieldMap::new()
+        .insert_with_extractor("id", ent::Column::Id, FieldKind::I64, |m: &ent::Model| {
+            m.id.to_string()
+        })
+        .insert("name", ent::Column::Name, FieldKind::String)
+        .insert("score", ent::Column::Score, FieldKind::I64);
+
+    let names = |filter: &str| {
+        let q = ODataQuery::new().with_filter(
+            modkit_odata::parse_filter_string(filter)
+                .unwrap()
+                .into_expr(),
+        );
+        let fmap = &fmap;
+        let conn = &conn;
+        let scope = &test_db.scope;
+        async move {
+            OPager::<ent::Entity, _>::new(scope, conn, fmap)
+                .fetch(&q, |m| m.name)
+                .await
+                .map(|page| {
+                    let mut names = page.items;
+                    names.sort();
+                    names
+                })
+        }
+    };
+
+    assert_eq!(names("toupper(name) eq 'BOB'").await.unwrap(), ["bob"]);
+    assert_eq!(names("tolower(name) eq 'dave'").await.unwrap(), ["dave"]);
+    assert_eq!(
+        names("length(name) gt 4").await.unwrap(),
+        ["alice", "charlie"]
+    );
+    assert_eq!(names("indexof(name, 'li') eq 1").await.unwrap(), ["alice"]);
+    assert_eq!(
+        names("contains(toupper(name), 'AR')").await.unwrap(),
+        ["charlie"]
+    );
+
+    let err = names("length(score) gt 1").await.unwrap_err();
+    assert!(matches!(err, ODataError::InvalidFilter(_)), "got {err:?}");
+}
+
+#[tokio::test]
+async fn opager_aggregate_covers_only_scoped_rows() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+    let other_tenant = Uuid::new_v4();
+    seed(
+        &conn,
+        other_tenant,
+        &AccessScope::for_tenants(vec![other_tenant]),
+    )
+    .await;
+
+    let fmap: FieldMap<ent::Entity> = FieldMap::new()
+        .insert("name", ent::Column::Name, FieldKind::String)
+        .insert("score", ent::Column::Score, FieldKind::I64);
+    let limits = ODataLimits::new().with_apply(["name"]);
+
+    let q = ODataQuery::new().with_apply(
+        modkit_odata::parse_apply(
+            "filter(score ge 20)/aggregate($count as n,score with sum as total,score with max as top)",
+        )
+        .unwrap(),
+    );
+    let rows = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .odata_limits(limits.clone())
+        .aggregate(&q)
+        .await
+        .expect("aggregate");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["n"], 3);
+    assert_eq!(rows[0]["total"], 90);
+    assert_eq!(rows[0]["top"], 40);
+
+    let q = ODataQuery::new()
+        .with_apply(modkit_odata::parse_apply("groupby((name),aggregate($count as n))").unwrap());
+    let rows = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .odata_limits(limits.clone())
+        .aggregate(&q)
+        .await
+        .expect("groupby");
+    let names: Vec<_> = rows.iter().map(|r| r["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["alice", "bob", "charlie", "dave"]);
+    assert!(rows.iter().all(|r| r["n"] == 1));
+
+    // Exactly as many groups as the limit is complete; one more is refused.
+    let rows = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .odata_limits(limits.clone())
+        .aggregate(&q.clone().with_limit(4))
+        .await
+        .expect("groups at the limit");
+    assert_eq!(rows.len(), 4);
+    let err = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .odata_limits(limits.clone())
+        .aggregate(&q.clone().with_limit(3))
+        .await
+        .expect_err("groups over the limit");
+    assert!(
+        matches!(err, modkit_odata::Error::TooManyGroups(3)),
+        "got {err:?}"
+    );
+
+    let err = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .aggregate(&q)
+        .await
+        .expect_err("apply not enabled");
+    assert!(matches!(err, modkit_odata::Error::ApplyNotAllowed));
+
+    let err = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .odata_limits(ODataLimits::new().with_apply(["score"]))
+        .aggregate(&q)
+        .await
+        .expect_err("name not groupable");
+    assert!(matches!(err, modkit_odata::Error::InvalidApply(_)));
+}
+
+#[tokio::test]
+async fn opager_pages_backward_from_any_page() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+
+    let fmap: FieldMap<ent::Entity> = FieldMap::new()
+        .insert_with_extractor("id", ent::Column::Id, FieldKind::I64, |m: &ent::Model| {
+            m.id.to_string()
+        })
+        .insert_with_extractor(
+            "score",
+            ent::Column::Score,
+            FieldKind::I64,
+            |m: &ent::Model| m.score.to_string(),
+        );
+    let q = ODataQuery::new().with_limit(1);
+    let fetch = |cursor: Option<&String>| {
+        let q = match cursor {
+            Some(c) => q.clone().with_cursor(CursorV1::decode(c).unwrap()),
+            None => q.clone(),
+        };
+        let pager = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+            .tiebreaker("score", SortDir::Asc);
+        async move { pager.fetch(&q, |m| m.score).await.expect("fetch") }
+    };
+
+    let first = fetch(None).await;
+    assert_eq!(first.items, [10]);
+    assert!(first.page_info.prev_cursor.is_none());
+    let second = fetch(first.page_info.next_cursor.as_ref()).await;
+    let third = fetch(second.page_info.next_cursor.as_ref()).await;
+    assert_eq!((second.items[0], third.items[0]), (20, 30));
+
+    // Step back from the third page to the start
+    let back = fetch(third.page_info.prev_cursor.as_ref()).await;
+    assert_eq!(back.items, [20]);
+    assert!(back.page_info.next_cursor.is_some());
+    let start = fetch(back.page_info.prev_cursor.as_ref()).await;
+    assert_eq!(start.items, [10]);
+    assert!(start.page_info.prev_cursor.is_none());
+
+    // Forward again from a page reached backward
+    let again = fetch(start.page_info.next_cursor.as_ref()).await;
+    assert_eq!(again.items, [20]);
+}
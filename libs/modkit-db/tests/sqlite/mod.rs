@@ -9,9 +9,14 @@ mod concurrency_tests;
 mod manager;
 mod options;
 mod pooling_tests;
+mod read_replicas;
+mod secure_audit_soft_delete;
+mod secure_bulk_insert;
+mod secure_field_masks;
 mod secure_insert_tenant_validation;
 mod secure_select_project_all;
 mod secure_update_tenant_safety;
+mod secure_version;
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod sqlite_tests;
 mod transaction;
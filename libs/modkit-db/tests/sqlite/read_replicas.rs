@@ -0,0 +1,115 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Read replica routing on `SQLite`, where the replica is a read-only pool on
+//! the primary's file.
+
+use figment::Figment;
+use figment::providers::Serialized;
+use modkit_db::DbManager;
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::secure::{SecureEntityExt, secure_insert, secure_update_with_scope};
+use modkit_security::AccessScope;
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm_migration::prelude as mig;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+mod doc_ent {
+    use super::*;
+    use modkit_db::secure::Scopable;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Scopable)]
+    #[sea_orm(table_name = "replica_docs")]
+    #[secure(tenant_col = "tenant_id", resource_col = "id", no_owner, no_type)]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub tenant_id: Uuid,
+        pub title: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+struct CreateReplicaDocs;
+
+impl mig::MigrationName for CreateReplicaDocs {
+    fn name(&self) -> &'static str {
+        "m001_create_replica_docs"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateReplicaDocs {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        let col = |name: &'static str| mig::ColumnDef::new(mig::Alias::new(name));
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("replica_docs"))
+                    .if_not_exists()
+                    .col(col("id").uuid().not_null().primary_key())
+                    .col(col("tenant_id").uuid().not_null())
+                    .col(col("title").string().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[tokio::test]
+async fn writes_go_to_primary_and_reads_to_replica() {
+    let temp_dir = TempDir::new().unwrap();
+    let figment = Figment::new().merge(Serialized::defaults(serde_json::json!({
+        "modules": {
+            "docs": {
+                "database": {
+                    "engine": "sqlite",
+                    "file": "docs.db",
+                    "replicas": ["sqlite://docs.db"]
+                }
+            }
+        }
+    })));
+    let manager = DbManager::from_figment(figment, temp_dir.path().to_path_buf()).unwrap();
+    let db = manager.get("docs").await.unwrap().expect("db");
+    run_migrations_for_testing(&db, vec![Box::new(CreateReplicaDocs)])
+        .await
+        .expect("migrate");
+
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenants(vec![tenant]);
+    let id = Uuid::new_v4();
+    let conn = db.conn().unwrap();
+
+    // The replica pool is read-only, so these fail if routed to it
+    let am = doc_ent::ActiveModel {
+        id: Set(id),
+        tenant_id: Set(tenant),
+        title: Set("draft".to_owned()),
+    };
+    secure_insert::<doc_ent::Entity>(am, &scope, &conn)
+        .await
+        .expect("insert on primary");
+    let am = doc_ent::ActiveModel {
+        id: Set(id),
+        title: Set("final".to_owned()),
+        ..Default::default()
+    };
+    secure_update_with_scope::<doc_ent::Entity>(am, &scope, id, &conn)
+        .await
+        .expect("update on primary");
+
+    let find = || doc_ent::Entity::find().secure().scope_with(&scope);
+    let on_replica = find().all(&conn).await.expect("replica read");
+    assert_eq!(on_replica.len(), 1);
+    assert_eq!(on_replica[0].title, "final");
+
+    let primary = db.conn_primary().unwrap();
+    let on_primary = find().one(&primary).await.expect("primary read");
+    assert_eq!(on_primary.unwrap().title, "final");
+}
@@ -0,0 +1,355 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for audit stamping and soft delete.
+//!
+//! Security contract:
+//! - No raw SQL in tests.
+//! - Schema is created via `sea-orm-migration` definitions executed by the migration runner.
+
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::secure::{
+    BulkRowOutcome, Db, DbConn, ScopeError, SecureDeleteEntityExt, SecureDeleteExt,
+    SecureEntityExt, SecureUpdateExt, secure_insert, secure_insert_audited, secure_insert_many,
+    secure_update_audited, secure_update_with_scope,
+};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_security::{AccessScope, SecurityContext};
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm_migration::prelude as mig;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+mod doc_ent {
+    use super::*;
+    use modkit_db::secure::Scopable;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Scopable)]
+    #[sea_orm(table_name = "audit_docs")]
+    #[secure(
+        tenant_col = "tenant_id",
+        resource_col = "id",
+        no_owner,
+        no_type,
+        audit,
+        soft_delete = "deleted_at"
+    )]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub tenant_id: Uuid,
+        pub title: String,
+        pub created_at: OffsetDateTime,
+        pub created_by: Uuid,
+        pub updated_at: OffsetDateTime,
+        pub updated_by: Uuid,
+        pub deleted_at: Option<OffsetDateTime>,
+        pub deleted_by: Option<Uuid>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+struct CreateAuditDocs;
+
+impl mig::MigrationName for CreateAuditDocs {
+    fn name(&self) -> &'static str {
+        "m001_create_audit_docs"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateAuditDocs {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        let col = |name: &'static str| mig::ColumnDef::new(mig::Alias::new(name));
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("audit_docs"))
+                    .if_not_exists()
+                    .col(col("id").uuid().not_null().primary_key())
+                    .col(col("tenant_id").uuid().not_null())
+                    .col(col("title").string().not_null())
+                    .col(col("created_at").timestamp_with_time_zone().not_null())
+                    .col(col("created_by").uuid().not_null())
+                    .col(col("updated_at").timestamp_with_time_zone().not_null())
+                    .col(col("updated_by").uuid().not_null())
+                    .col(col("deleted_at").timestamp_with_time_zone().null())
+                    .col(col("deleted_by").uuid().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("audit_docs"))
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+struct TestDb {
+    db: Db,
+}
+
+impl TestDb {
+    async fn new() -> Self {
+        let test_id = Uuid::new_v4();
+        let dsn = format!("sqlite:file:memdb_secure_audit_{test_id}?mode=memory&cache=shared");
+
+        let opts = ConnectOpts {
+            max_conns: Some(1),
+            min_conns: Some(1),
+            ..Default::default()
+        };
+
+        let db = connect_db(&dsn, opts).await.expect("connect");
+
+        run_migrations_for_testing(&db, vec![Box::new(CreateAuditDocs)])
+            .await
+            .expect("migrate");
+
+        Self { db }
+    }
+
+    fn conn(&self) -> DbConn<'_> {
+        self.db.conn().expect("conn")
+    }
+}
+
+fn ctx(subject: Uuid) -> SecurityContext {
+    SecurityContext::builder()
+        .subject_id(subject)
+        .subject_tenant_id(Uuid::new_v4())
+        .build()
+        .unwrap()
+}
+
+async fn insert_doc(conn: &DbConn<'_>, scope: &AccessScope, tenant: Uuid, by: Uuid) -> Uuid {
+    let id = Uuid::new_v4();
+    let am = doc_ent::ActiveModel {
+        id: Set(id),
+        tenant_id: Set(tenant),
+        title: Set("draft".to_owned()),
+        ..Default::default()
+    };
+    secure_insert_audited::<doc_ent::Entity>(am, &ctx(by), scope, conn)
+        .await
+        .expect("insert");
+    id
+}
+
+fn by_id(id: Uuid) -> sea_orm::Condition {
+    sea_orm::Condition::all().add(Expr::col(doc_ent::Column::Id).eq(id))
+}
+
+#[tokio::test]
+async fn insert_and_update_record_subjects() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let (author, editor) = (Uuid::new_v4(), Uuid::new_v4());
+
+    let id = insert_doc(&conn, &scope, tenant, author).await;
+
+    // Audit columns set by the caller are overwritten from the context.
+    let forged = Uuid::new_v4();
+    let am = doc_ent::ActiveModel {
+        id: Set(id),
+        title: Set("final".to_owned()),
+        created_by: Set(forged),
+        updated_by: Set(forged),
+        ..Default::default()
+    };
+    let updated = secure_update_audited::<doc_ent::Entity>(am, &ctx(editor), &scope, id, &conn)
+        .await
+        .expect("update");
+
+    assert_eq!(updated.created_by, author);
+    assert_eq!(updated.updated_by, editor);
+    assert!(updated.updated_at >= updated.created_at);
+}
+
+#[tokio::test]
+async fn writes_without_context_are_rejected() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+
+    let err = secure_insert::<doc_ent::Entity>(
+        doc_ent::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant),
+            title: Set("draft".to_owned()),
+            ..Default::default()
+        },
+        &scope,
+        &conn,
+    )
+    .await
+    .expect_err("insert without context");
+    assert!(matches!(err, ScopeError::Invalid(_)));
+
+    let id = insert_doc(&conn, &scope, tenant, Uuid::new_v4()).await;
+    let forged = Uuid::new_v4();
+    let err = secure_update_with_scope::<doc_ent::Entity>(
+        doc_ent::ActiveModel {
+            id: Set(id),
+            title: Set("x".to_owned()),
+            updated_at: Set(OffsetDateTime::now_utc()),
+            updated_by: Set(forged),
+            ..Default::default()
+        },
+        &scope,
+        id,
+        &conn,
+    )
+    .await
+    .expect_err("self-stamped update");
+    assert!(matches!(err, ScopeError::Invalid(_)));
+
+    let report = secure_insert_many::<doc_ent::Entity>(
+        vec![doc_ent::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant),
+            title: Set("draft".to_owned()),
+            created_at: Set(OffsetDateTime::now_utc()),
+            created_by: Set(forged),
+            updated_at: Set(OffsetDateTime::now_utc()),
+            updated_by: Set(forged),
+            ..Default::default()
+        }],
+        &scope,
+        &conn,
+    )
+    .await;
+    assert!(matches!(
+        report.rows.as_slice(),
+        [BulkRowOutcome::Rejected(ScopeError::Invalid(_))]
+    ));
+    let err = doc_ent::Entity::update_many()
+        .secure()
+        .scope_with(&scope)
+        .col_expr(doc_ent::Column::Title, Expr::value("x"))
+        .filter(by_id(id))
+        .exec(&conn)
+        .await
+        .expect_err("unaudited update_many");
+    assert!(matches!(err, ScopeError::Invalid(_)));
+
+    let err = doc_ent::Entity::secure_delete()
+        .scope_with(&scope)
+        .filter(by_id(id))
+        .exec(&conn)
+        .await
+        .expect_err("unaudited delete");
+    assert!(matches!(err, ScopeError::Invalid(_)));
+}
+
+#[tokio::test]
+async fn delete_is_soft_and_hidden_from_selects() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let remover = Uuid::new_v4();
+
+    let id = insert_doc(&conn, &scope, tenant, Uuid::new_v4()).await;
+    let kept = insert_doc(&conn, &scope, tenant, Uuid::new_v4()).await;
+
+    let result = doc_ent::Entity::secure_delete()
+        .scope_with(&scope)
+        .filter(by_id(id))
+        .audited(&ctx(remover))
+        .exec(&conn)
+        .await
+        .expect("soft delete");
+    assert_eq!(result.rows_affected, 1);
+
+    let visible = doc_ent::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .all(&conn)
+        .await
+        .expect("select");
+    assert_eq!(visible.iter().map(|d| d.id).collect::<Vec<_>>(), [kept]);
+
+    let deleted = doc_ent::Entity::find()
+        .secure()
+        .with_deleted()
+        .scope_with(&scope)
+        .filter(by_id(id))
+        .one(&conn)
+        .await
+        .expect("select with deleted")
+        .expect("row kept");
+    assert!(deleted.deleted_at.is_some());
+    assert_eq!(deleted.deleted_by, Some(remover));
+
+    // Deleting again does not touch the row.
+    let again = doc_ent::Entity::secure_delete()
+        .scope_with(&scope)
+        .filter(by_id(id))
+        .audited(&ctx(Uuid::new_v4()))
+        .exec(&conn)
+        .await
+        .expect("repeat delete");
+    assert_eq!(again.rows_affected, 0);
+}
+
+#[tokio::test]
+async fn soft_delete_rejects_delete_many_queries() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let id = insert_doc(&conn, &scope, tenant, Uuid::new_v4()).await;
+
+    let err = doc_ent::Entity::delete_many()
+        .filter(by_id(id))
+        .secure()
+        .scope_with(&scope)
+        .audited(&ctx(Uuid::new_v4()))
+        .exec(&conn)
+        .await
+        .expect_err("filter would be dropped");
+    assert!(matches!(err, ScopeError::Invalid(_)));
+}
+
+#[tokio::test]
+async fn hard_delete_removes_rows() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let id = insert_doc(&conn, &scope, tenant, Uuid::new_v4()).await;
+
+    doc_ent::Entity::delete_many()
+        .filter(by_id(id))
+        .secure()
+        .scope_with(&scope)
+        .hard()
+        .exec(&conn)
+        .await
+        .expect("hard delete");
+
+    let all = doc_ent::Entity::find()
+        .secure()
+        .with_deleted()
+        .scope_with(&scope)
+        .all(&conn)
+        .await
+        .expect("select");
+    assert!(all.is_empty());
+}
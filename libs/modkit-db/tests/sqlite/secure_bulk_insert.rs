@@ -0,0 +1,292 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for `secure_insert_many` / `secure_upsert_many`.
+//!
+//! Security contract:
+//! - No raw SQL in tests.
+//! - Schema is created via `sea-orm-migration` definitions executed by the migration runner.
+
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::secure::{
+    BulkRowOutcome, DbConn, ScopeError, SecureEntityExt, SecureOnConflict, secure_insert_many,
+    secure_upsert_many,
+};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_security::AccessScope;
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm_migration::prelude as mig;
+use uuid::Uuid;
+
+mod setting_ent {
+    use super::*;
+    use modkit_db::secure::Scopable;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Scopable)]
+    #[sea_orm(table_name = "bulk_settings")]
+    #[secure(tenant_col = "tenant_id", resource_col = "id", no_owner, no_type)]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub tenant_id: Uuid,
+        pub key: String,
+        pub value: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+struct CreateBulkSettings;
+
+impl mig::MigrationName for CreateBulkSettings {
+    fn name(&self) -> &'static str {
+        "m001_create_bulk_settings"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateBulkSettings {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        let col = |name: &'static str| mig::ColumnDef::new(mig::Alias::new(name));
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("bulk_settings"))
+                    .if_not_exists()
+                    .col(col("id").uuid().not_null().primary_key())
+                    .col(col("tenant_id").uuid().not_null())
+                    .col(col("key").string().not_null())
+                    .col(col("value").string().not_null().default("unset"))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                mig::Index::create()
+                    .name("bulk_settings_tenant_key")
+                    .table(mig::Alias::new("bulk_settings"))
+                    .col(mig::Alias::new("tenant_id"))
+                    .col(mig::Alias::new("key"))
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+async fn setup() -> modkit_db::secure::Db {
+    let dsn = format!(
+        "sqlite:file:memdb_secure_bulk_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db(&dsn, opts).await.expect("connect");
+    run_migrations_for_testing(&db, vec![Box::new(CreateBulkSettings)])
+        .await
+        .expect("migrate");
+    db
+}
+
+fn setting(tenant: Uuid, key: &str, value: &str) -> setting_ent::ActiveModel {
+    setting_ent::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        tenant_id: Set(tenant),
+        key: Set(key.to_owned()),
+        value: Set(value.to_owned()),
+    }
+}
+
+async fn load_all(conn: &DbConn<'_>, scope: &AccessScope) -> Vec<setting_ent::Model> {
+    setting_ent::Entity::find()
+        .secure()
+        .scope_with(scope)
+        .all(conn)
+        .await
+        .expect("select")
+}
+
+#[tokio::test]
+async fn insert_many_batches_rows_and_rejects_out_of_scope_ones() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenants(vec![tenant]);
+
+    // More rows than fit in one statement
+    let mut rows: Vec<_> = (0..1500)
+        .map(|i| setting(tenant, &format!("k{i}"), "v"))
+        .collect();
+    rows.insert(3, setting(Uuid::new_v4(), "foreign", "v"));
+    rows.insert(
+        7,
+        setting_ent::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            key: Set("no-tenant".to_owned()),
+            value: Set("v".to_owned()),
+            ..Default::default()
+        },
+    );
+
+    let report = secure_insert_many::<setting_ent::Entity>(rows, &scope, &conn).await;
+
+    assert_eq!(report.rows.len(), 1502);
+    assert_eq!(report.written(), 1500);
+    assert_eq!(report.rows_affected, 1500);
+    assert!(!report.is_complete());
+    let rejected: Vec<_> = report.rejected().map(|(i, _)| i).collect();
+    assert_eq!(rejected, [3, 7]);
+    assert!(matches!(
+        report.rows[3],
+        BulkRowOutcome::Rejected(ScopeError::Denied(_))
+    ));
+    assert!(matches!(
+        report.rows[7],
+        BulkRowOutcome::Rejected(ScopeError::Invalid(_))
+    ));
+
+    // Nothing outside the scope was written
+    assert_eq!(load_all(&conn, &scope).await.len(), 1500);
+    assert_eq!(load_all(&conn, &AccessScope::allow_all()).await.len(), 1500);
+}
+
+#[tokio::test]
+async fn upsert_many_updates_conflicting_rows_in_place() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenants(vec![tenant]);
+
+    let seeded = secure_insert_many::<setting_ent::Entity>(
+        vec![
+            setting(tenant, "theme", "light"),
+            setting(tenant, "lang", "en"),
+        ],
+        &scope,
+        &conn,
+    )
+    .await;
+    assert!(seeded.is_complete());
+
+    let on_conflict = || {
+        SecureOnConflict::<setting_ent::Entity>::columns([
+            setting_ent::Column::TenantId,
+            setting_ent::Column::Key,
+        ])
+        .update_columns([setting_ent::Column::Value])
+        .unwrap()
+    };
+    let report = secure_upsert_many::<setting_ent::Entity>(
+        vec![
+            setting(tenant, "theme", "dark"),
+            setting(tenant, "tz", "UTC"),
+        ],
+        &scope,
+        on_conflict(),
+        &conn,
+    )
+    .await;
+    assert!(report.is_complete());
+
+    let mut stored: Vec<_> = load_all(&conn, &scope)
+        .await
+        .into_iter()
+        .map(|m| (m.key, m.value))
+        .collect();
+    stored.sort();
+    assert_eq!(
+        stored,
+        [
+            ("lang".to_owned(), "en".to_owned()),
+            ("theme".to_owned(), "dark".to_owned()),
+            ("tz".to_owned(), "UTC".to_owned()),
+        ]
+    );
+
+    // The tenant column can never be part of the update
+    let moved = SecureOnConflict::<setting_ent::Entity>::columns([setting_ent::Column::Id])
+        .update_columns([setting_ent::Column::TenantId]);
+    assert!(matches!(moved, Err(ScopeError::Denied(_))));
+}
+
+#[tokio::test]
+async fn insert_many_keeps_defaults_of_columns_a_row_leaves_unset() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenants(vec![tenant]);
+
+    let without_value = setting_ent::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        tenant_id: Set(tenant),
+        key: Set("lang".to_owned()),
+        ..Default::default()
+    };
+    let report = secure_insert_many::<setting_ent::Entity>(
+        vec![setting(tenant, "theme", "dark"), without_value],
+        &scope,
+        &conn,
+    )
+    .await;
+    assert!(report.is_complete());
+
+    let mut stored: Vec<_> = load_all(&conn, &scope)
+        .await
+        .into_iter()
+        .map(|m| (m.key, m.value))
+        .collect();
+    stored.sort();
+    assert_eq!(
+        stored,
+        [
+            ("lang".to_owned(), "unset".to_owned()),
+            ("theme".to_owned(), "dark".to_owned()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn insert_many_reports_rows_of_a_failed_statement() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenants(vec![tenant]);
+
+    // Rows 0 and 2 share a statement that violates the unique key; row 1
+    // sets other columns and goes in a later statement.
+    let without_value = setting_ent::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        tenant_id: Set(tenant),
+        key: Set("lang".to_owned()),
+        ..Default::default()
+    };
+    let report = secure_insert_many::<setting_ent::Entity>(
+        vec![
+            setting(tenant, "theme", "dark"),
+            without_value,
+            setting(tenant, "theme", "light"),
+        ],
+        &scope,
+        &conn,
+    )
+    .await;
+
+    assert!(!report.is_complete());
+    assert!(report.failure().is_some());
+    assert_eq!(report.rows_affected, 0);
+    assert!(matches!(
+        report.rows.as_slice(),
+        [
+            BulkRowOutcome::Failed(_),
+            BulkRowOutcome::NotAttempted,
+            BulkRowOutcome::Failed(_),
+        ]
+    ));
+    assert!(load_all(&conn, &scope).await.is_empty());
+}
@@ -0,0 +1,380 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for field masks carried by `AccessScope` constraints.
+//!
+//! Security contract:
+//! - No raw SQL in tests.
+//! - Schema is created via `sea-orm-migration` definitions executed by the migration runner.
+
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::odata::FieldMap;
+use modkit_db::odata::pager::OPager;
+use modkit_db::secure::{ScopeError, SecureEntityExt, secure_insert, secure_update_with_scope};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_odata::filter::FieldKind;
+use modkit_odata::{Error as ODataError, ODataLimits, ODataOrderBy, ODataQuery, OrderKey, SortDir};
+use modkit_security::{
+    AccessScope, FieldMask, MaskedField, REDACTED_VALUE, ScopeConstraint, ScopeFilter,
+    pep_properties,
+};
+use sea_orm::entity::prelude::*;
+use sea_orm::{QuerySelect, Set};
+use sea_orm_migration::prelude as mig;
+use uuid::Uuid;
+
+mod employee_ent {
+    use super::*;
+    use modkit_db::secure::Scopable;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Scopable)]
+    #[sea_orm(table_name = "masked_employees")]
+    #[secure(
+        tenant_col = "tenant_id",
+        resource_col = "id",
+        no_owner,
+        no_type,
+        field(pay = "salary")
+    )]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub tenant_id: Uuid,
+        pub email: String,
+        pub salary: Option<i64>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+struct CreateEmployees;
+
+impl mig::MigrationName for CreateEmployees {
+    fn name(&self) -> &'static str {
+        "m001_create_masked_employees"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateEmployees {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        let col = |name: &'static str| mig::ColumnDef::new(mig::Alias::new(name));
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("masked_employees"))
+                    .if_not_exists()
+                    .col(col("id").uuid().not_null().primary_key())
+                    .col(col("tenant_id").uuid().not_null())
+                    .col(col("email").string().not_null())
+                    .col(col("salary").big_integer().null())
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+async fn setup() -> modkit_db::secure::Db {
+    let dsn = format!(
+        "sqlite:file:memdb_secure_masks_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db(&dsn, opts).await.expect("connect");
+    run_migrations_for_testing(&db, vec![Box::new(CreateEmployees)])
+        .await
+        .expect("migrate");
+    db
+}
+
+fn tenant_constraint(tenant: Uuid, masks: Vec<MaskedField>) -> ScopeConstraint {
+    ScopeConstraint::new(vec![ScopeFilter::in_uuids(
+        pep_properties::OWNER_TENANT_ID,
+        vec![tenant],
+    )])
+    .with_masked_fields(masks)
+}
+
+#[tokio::test]
+async fn masked_columns_are_withheld_per_access_path() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let (own, partner) = (Uuid::new_v4(), Uuid::new_v4());
+
+    let writer = AccessScope::for_tenants(vec![own, partner]);
+    for (id, tenant) in [(Uuid::new_v4(), own), (Uuid::new_v4(), partner)] {
+        let am = employee_ent::ActiveModel {
+            id: Set(id),
+            tenant_id: Set(tenant),
+            email: Set(format!("{id}@example.com")),
+            salary: Set(Some(100)),
+        };
+        secure_insert::<employee_ent::Entity>(am, &writer, &conn)
+            .await
+            .unwrap();
+    }
+
+    // Own tenant: salary hidden. Partner tenant: salary hidden, email redacted.
+    let scope = AccessScope::from_constraints(vec![
+        tenant_constraint(own, vec![MaskedField::new("salary", FieldMask::Hide)]),
+        tenant_constraint(
+            partner,
+            vec![
+                MaskedField::new("salary", FieldMask::Hide),
+                MaskedField::new("email", FieldMask::Redact),
+            ],
+        ),
+    ]);
+    assert_eq!(scope.hidden_fields(), ["salary"]);
+
+    let rows = employee_ent::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .all(&conn)
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+    for row in &rows {
+        assert_eq!(row.salary, None);
+        if row.tenant_id == own {
+            assert_eq!(row.email, format!("{}@example.com", row.id));
+        } else {
+            assert_eq!(row.email, REDACTED_VALUE);
+        }
+    }
+
+    // An unmasked path to the same rows makes the columns readable again.
+    let widened = AccessScope::from_constraints(
+        scope
+            .constraints()
+            .iter()
+            .cloned()
+            .chain([tenant_constraint(partner, vec![])])
+            .collect(),
+    );
+    let partner_row = employee_ent::Entity::find()
+        .filter(employee_ent::Column::TenantId.eq(partner))
+        .secure()
+        .scope_with(&widened)
+        .one(&conn)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(partner_row.salary, Some(100));
+    assert_ne!(partner_row.email, REDACTED_VALUE);
+}
+
+#[tokio::test]
+async fn updated_model_is_returned_masked() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let (tenant, id) = (Uuid::new_v4(), Uuid::new_v4());
+
+    let am = employee_ent::ActiveModel {
+        id: Set(id),
+        tenant_id: Set(tenant),
+        email: Set("alice@example.com".to_owned()),
+        salary: Set(Some(100)),
+    };
+    secure_insert::<employee_ent::Entity>(am, &AccessScope::for_tenant(tenant), &conn)
+        .await
+        .unwrap();
+
+    // Updating the salary must not hand back the email the scope redacts.
+    let scope = AccessScope::from_constraints(vec![tenant_constraint(
+        tenant,
+        vec![MaskedField::new("email", FieldMask::Redact)],
+    )]);
+    let am = employee_ent::ActiveModel {
+        id: sea_orm::ActiveValue::Unchanged(id),
+        salary: Set(Some(200)),
+        ..Default::default()
+    };
+    let updated = secure_update_with_scope::<employee_ent::Entity>(am, &scope, id, &conn)
+        .await
+        .unwrap();
+    assert_eq!(updated.email, REDACTED_VALUE);
+    assert_eq!(updated.salary, Some(200));
+}
+
+fn employee_fmap() -> FieldMap<employee_ent::Entity> {
+    FieldMap::new()
+        .insert_with_extractor(
+            "id",
+            employee_ent::Column::Id,
+            FieldKind::Uuid,
+            |m: &employee_ent::Model| m.id.to_string(),
+        )
+        .insert("email", employee_ent::Column::Email, FieldKind::String)
+        .insert("pay", employee_ent::Column::Salary, FieldKind::I64)
+}
+
+#[tokio::test]
+async fn masked_fields_are_refused_in_odata_queries() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+
+    let writer = AccessScope::for_tenants(vec![tenant]);
+    let am = employee_ent::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        tenant_id: Set(tenant),
+        email: Set("a@example.com".to_owned()),
+        salary: Set(Some(100)),
+    };
+    secure_insert::<employee_ent::Entity>(am, &writer, &conn)
+        .await
+        .unwrap();
+
+    let scope = AccessScope::from_constraints(vec![tenant_constraint(
+        tenant,
+        vec![
+            MaskedField::new("salary", FieldMask::Hide),
+            MaskedField::new("email", FieldMask::Redact),
+        ],
+    )]);
+    let fmap = employee_fmap();
+    let pager = || OPager::<employee_ent::Entity, _>::new(&scope, &conn, &fmap);
+
+    // Unmasked queries return masked values
+    let page = pager().fetch(&ODataQuery::default(), |m| m).await.unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].salary, None);
+    assert_eq!(page.items[0].email, REDACTED_VALUE);
+
+    let refused = [
+        ODataQuery::new().with_filter(
+            modkit_odata::parse_filter_string("pay gt 50")
+                .unwrap()
+                .into_expr(),
+        ),
+        ODataQuery::new().with_order(ODataOrderBy(vec![OrderKey {
+            field: "email".to_owned(),
+            dir: SortDir::Asc,
+        }])),
+        ODataQuery::new().with_select(vec!["id".to_owned(), "pay".to_owned()]),
+    ];
+    for q in &refused {
+        let err = pager().fetch(q, |m| m).await.unwrap_err();
+        assert!(
+            matches!(err, ODataError::FieldNotReadable(_)),
+            "got {err:?}"
+        );
+    }
+
+    let q = ODataQuery::new()
+        .with_apply(modkit_odata::parse_apply("groupby((email),aggregate($count as n))").unwrap());
+    let err = pager()
+        .odata_limits(ODataLimits::new().with_apply(["email"]))
+        .aggregate(&q)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, ODataError::FieldNotReadable(_)),
+        "got {err:?}"
+    );
+
+    // Custom projections would read the raw columns
+    let err = employee_ent::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .project_all(&conn, |q| {
+            q.select_only()
+                .column(employee_ent::Column::Salary)
+                .into_model::<SalaryRow>()
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ScopeError::Denied(_)), "got {err:?}");
+}
+
+#[tokio::test]
+async fn masks_name_fields_as_the_api_does() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+
+    let writer = AccessScope::for_tenants(vec![tenant]);
+    let am = employee_ent::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        tenant_id: Set(tenant),
+        email: Set("a@example.com".to_owned()),
+        salary: Set(Some(100)),
+    };
+    secure_insert::<employee_ent::Entity>(am, &writer, &conn)
+        .await
+        .unwrap();
+
+    // The API calls the `salary` column `pay`
+    let scope = AccessScope::from_constraints(vec![tenant_constraint(
+        tenant,
+        vec![MaskedField::new("pay", FieldMask::Hide)],
+    )]);
+    let row = employee_ent::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .one(&conn)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(row.salary, None);
+
+    let fmap = employee_fmap();
+    let pager = || OPager::<employee_ent::Entity, _>::new(&scope, &conn, &fmap);
+    let page = pager().fetch(&ODataQuery::default(), |m| m).await.unwrap();
+    assert_eq!(page.items[0].salary, None);
+    let q = ODataQuery::new().with_filter(
+        modkit_odata::parse_filter_string("pay gt 50")
+            .unwrap()
+            .into_expr(),
+    );
+    let err = pager().fetch(&q, |m| m).await.unwrap_err();
+    assert!(
+        matches!(err, ODataError::FieldNotReadable(_)),
+        "got {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn hiding_a_non_nullable_column_is_refused() {
+    let db = setup().await;
+    let conn = db.conn().unwrap();
+    let tenant = Uuid::new_v4();
+
+    let scope = AccessScope::from_constraints(vec![tenant_constraint(
+        tenant,
+        vec![MaskedField::new("email", FieldMask::Hide)],
+    )]);
+    let err = employee_ent::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .all(&conn)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ScopeError::Invalid(_)), "got {err:?}");
+
+    // Redacting the text column stays decodable
+    let scope = AccessScope::from_constraints(vec![tenant_constraint(
+        tenant,
+        vec![MaskedField::new("email", FieldMask::Redact)],
+    )]);
+    let rows = employee_ent::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .all(&conn)
+        .await
+        .unwrap();
+    assert!(rows.is_empty());
+}
+
+#[derive(Debug, sea_orm::FromQueryResult)]
+struct SalaryRow {
+    #[allow(dead_code)]
+    salary: Option<i64>,
+}
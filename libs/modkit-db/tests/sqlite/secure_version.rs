@@ -0,0 +1,223 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for optimistic concurrency on `version_col` entities.
+//!
+//! Security contract:
+//! - No raw SQL in tests.
+//! - Schema is created via `sea-orm-migration` definitions executed by the migration runner.
+
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::secure::{
+    Db, DbConn, ScopeError, SecureEntityExt, SecureUpdateExt, secure_insert,
+    secure_update_with_scope,
+};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_security::AccessScope;
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm_migration::prelude as mig;
+use uuid::Uuid;
+
+mod note_ent {
+    use super::*;
+    use modkit_db::secure::Scopable;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Scopable)]
+    #[sea_orm(table_name = "versioned_notes")]
+    #[secure(
+        tenant_col = "tenant_id",
+        resource_col = "id",
+        no_owner,
+        no_type,
+        version_col = "version"
+    )]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub tenant_id: Uuid,
+        pub body: String,
+        pub version: i64,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+struct CreateVersionedNotes;
+
+impl mig::MigrationName for CreateVersionedNotes {
+    fn name(&self) -> &'static str {
+        "m001_create_versioned_notes"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateVersionedNotes {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        let col = |name: &'static str| mig::ColumnDef::new(mig::Alias::new(name));
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("versioned_notes"))
+                    .if_not_exists()
+                    .col(col("id").uuid().not_null().primary_key())
+                    .col(col("tenant_id").uuid().not_null())
+                    .col(col("body").string().not_null())
+                    .col(col("version").big_integer().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("versioned_notes"))
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+struct TestDb {
+    db: Db,
+}
+
+impl TestDb {
+    async fn new() -> Self {
+        let test_id = Uuid::new_v4();
+        let dsn = format!("sqlite:file:memdb_secure_version_{test_id}?mode=memory&cache=shared");
+
+        let opts = ConnectOpts {
+            max_conns: Some(1),
+            min_conns: Some(1),
+            ..Default::default()
+        };
+
+        let db = connect_db(&dsn, opts).await.expect("connect");
+
+        run_migrations_for_testing(&db, vec![Box::new(CreateVersionedNotes)])
+            .await
+            .expect("migrate");
+
+        Self { db }
+    }
+
+    fn conn(&self) -> DbConn<'_> {
+        self.db.conn().expect("conn")
+    }
+}
+
+async fn insert_note(conn: &DbConn<'_>, scope: &AccessScope, tenant: Uuid) -> Uuid {
+    let id = Uuid::new_v4();
+    let am = note_ent::ActiveModel {
+        id: Set(id),
+        tenant_id: Set(tenant),
+        body: Set("v1".to_owned()),
+        version: Set(1),
+    };
+    secure_insert::<note_ent::Entity>(am, scope, conn)
+        .await
+        .expect("insert");
+    id
+}
+
+async fn load(conn: &DbConn<'_>, scope: &AccessScope, id: Uuid) -> note_ent::Model {
+    note_ent::Entity::find()
+        .secure()
+        .scope_with(scope)
+        .and_id(id)
+        .unwrap()
+        .one(conn)
+        .await
+        .expect("select")
+        .expect("row")
+}
+
+fn by_id(id: Uuid) -> sea_orm::Condition {
+    sea_orm::Condition::all().add(Expr::col(note_ent::Column::Id).eq(id))
+}
+
+#[tokio::test]
+async fn update_many_checks_and_bumps_version() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let id = insert_note(&conn, &scope, tenant).await;
+
+    let update = |body: &'static str, version: i64| {
+        note_ent::Entity::update_many()
+            .secure()
+            .scope_with(&scope)
+            .col_expr(note_ent::Column::Body, Expr::value(body))
+            .filter(by_id(id))
+            .expect_version(version)
+    };
+
+    let result = update("v2", 1).exec(&conn).await.expect("update");
+    assert_eq!(result.rows_affected, 1);
+    assert_eq!(load(&conn, &scope, id).await.version, 2);
+
+    // A writer still holding version 1 lost the race.
+    let err = update("stale", 1)
+        .exec(&conn)
+        .await
+        .expect_err("stale version");
+    assert!(matches!(err, ScopeError::VersionConflict { expected: 1 }));
+
+    let note = load(&conn, &scope, id).await;
+    assert_eq!((note.body.as_str(), note.version), ("v2", 2));
+}
+
+#[tokio::test]
+async fn unconditional_updates_still_bump_version() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let id = insert_note(&conn, &scope, tenant).await;
+
+    note_ent::Entity::update_many()
+        .secure()
+        .scope_with(&scope)
+        .col_expr(note_ent::Column::Body, Expr::value("v2"))
+        .filter(by_id(id))
+        .exec(&conn)
+        .await
+        .expect("update");
+
+    assert_eq!(load(&conn, &scope, id).await.version, 2);
+}
+
+#[tokio::test]
+async fn update_with_scope_uses_version_from_active_model() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let tenant = Uuid::new_v4();
+    let scope = AccessScope::for_tenant(tenant);
+    let id = insert_note(&conn, &scope, tenant).await;
+
+    let edit = |body: &str, version: i64| note_ent::ActiveModel {
+        id: Set(id),
+        body: Set(body.to_owned()),
+        version: Set(version),
+        ..Default::default()
+    };
+
+    let updated = secure_update_with_scope::<note_ent::Entity>(edit("v2", 1), &scope, id, &conn)
+        .await
+        .expect("update");
+    assert_eq!((updated.body.as_str(), updated.version), ("v2", 2));
+
+    let err = secure_update_with_scope::<note_ent::Entity>(edit("stale", 1), &scope, id, &conn)
+        .await
+        .expect_err("stale version");
+    assert!(matches!(err, ScopeError::VersionConflict { expected: 1 }));
+    assert_eq!(load(&conn, &scope, id).await.body, "v2");
+}
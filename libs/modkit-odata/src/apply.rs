@@ -0,0 +1,302 @@
+//! `$apply` aggregation: a pragmatic subset of `OData` Data Aggregation v4.
+//!
+//! Supported transformations, separated by `/`:
+//! - `filter(<expr>)`: same grammar as `$filter`, applied before grouping
+//! - `groupby((f1,f2))` and `groupby((f1,f2),aggregate(...))`
+//! - `aggregate(<agg>, ...)` without grouping (a single result row)
+//!
+//! An aggregate is either `<field> with <sum|min|max|average> as <alias>` or
+//! `$count as <alias>`. At most one `filter` may precede a single `groupby`
+//! or `aggregate`.
+//!
+//! ```text
+//! $apply=filter(status eq 'active')/groupby((model),aggregate($count as chats))
+//! $apply=groupby((type,region),aggregate(size with sum as total,size with max as largest))
+//! ```
+
+use crate::{Error, ast};
+
+/// One result row: group values and aggregate aliases, keyed by name.
+pub type AggregateRow = serde_json::Map<String, serde_json::Value>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AggregateMethod {
+    /// `$count`: number of rows in the group.
+    Count,
+    Sum,
+    Min,
+    Max,
+    Average,
+}
+
+impl AggregateMethod {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "sum" => Some(Self::Sum),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "average" => Some(Self::Average),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Aggregate {
+    pub method: AggregateMethod,
+    /// Aggregated field; `None` for `$count`.
+    pub field: Option<String>,
+    /// Name of the value in the result rows.
+    pub alias: String,
+}
+
+/// Parsed `$apply` pipeline.
+#[derive(Clone, Debug, Default)]
+pub struct Apply {
+    /// Condition applied to the input rows before grouping.
+    pub filter: Option<Box<ast::Expr>>,
+    /// Grouping fields; empty when aggregating over all rows.
+    pub group_by: Vec<String>,
+    pub aggregates: Vec<Aggregate>,
+}
+
+impl Apply {
+    /// Names of the result columns, group fields first.
+    pub fn output_names(&self) -> impl Iterator<Item = &str> {
+        self.group_by
+            .iter()
+            .map(String::as_str)
+            .chain(self.aggregates.iter().map(|a| a.alias.as_str()))
+    }
+}
+
+/// Parse a raw `$apply` string.
+///
+/// # Errors
+/// Returns `Error::InvalidApply` if the pipeline is malformed or uses an
+/// unsupported transformation, and `Error::InvalidFilter` if the expression
+/// of a `filter(...)` step does not parse.
+pub fn parse_apply(raw: &str) -> Result<Apply, Error> {
+    let mut apply = Apply::default();
+    let mut grouped = false;
+
+    for step in split_top_level(raw.trim(), '/')? {
+        if grouped {
+            return Err(invalid(format!(
+                "'{step}' after groupby/aggregate is not supported"
+            )));
+        }
+        if let Some(expr) = call_args(step, "filter") {
+            if apply.filter.is_some() {
+                return Err(invalid("only one filter() is supported"));
+            }
+            let parsed = crate::parse_filter_string(expr)?;
+            apply.filter = Some(Box::new(parsed.into_expr()));
+        } else if let Some(args) = call_args(step, "groupby") {
+            parse_groupby(args, &mut apply)?;
+            grouped = true;
+        } else if let Some(args) = call_args(step, "aggregate") {
+            apply.aggregates = parse_aggregates(args)?;
+            grouped = true;
+        } else {
+            return Err(invalid(format!("unsupported transformation '{step}'")));
+        }
+    }
+
+    if !grouped {
+        return Err(invalid("groupby() or aggregate() is required"));
+    }
+    let mut seen = std::collections::HashSet::new();
+    if let Some(dup) = apply
+        .output_names()
+        .find(|n| !seen.insert(n.to_lowercase()))
+    {
+        return Err(invalid(format!("duplicate name '{dup}'")));
+    }
+    Ok(apply)
+}
+
+fn parse_groupby(args: &str, apply: &mut Apply) -> Result<(), Error> {
+    let parts = split_top_level(args, ',')?;
+    let (fields, rest) = parts
+        .split_first()
+        .ok_or_else(|| invalid("groupby() needs a field list"))?;
+    let fields = fields
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .ok_or_else(|| invalid("groupby() fields must be parenthesized"))?;
+
+    for field in split_top_level(fields, ',')? {
+        apply.group_by.push(identifier(field)?.to_owned());
+    }
+
+    match rest {
+        [] => Ok(()),
+        [aggregate] => {
+            let args = call_args(aggregate, "aggregate")
+                .ok_or_else(|| invalid(format!("unsupported groupby argument '{aggregate}'")))?;
+            apply.aggregates = parse_aggregates(args)?;
+            Ok(())
+        }
+        _ => Err(invalid("groupby() takes a field list and one aggregate()")),
+    }
+}
+
+fn parse_aggregates(args: &str) -> Result<Vec<Aggregate>, Error> {
+    split_top_level(args, ',')?
+        .into_iter()
+        .map(|item| {
+            let tokens: Vec<&str> = item.split_whitespace().collect();
+            let (method, field, alias) = match tokens.as_slice() {
+                ["$count", "as", alias] => (AggregateMethod::Count, None, *alias),
+                [field, "with", method, "as", alias] => {
+                    let method = AggregateMethod::parse(method).ok_or_else(|| {
+                        invalid(format!("unsupported aggregation method '{method}'"))
+                    })?;
+                    (method, Some(identifier(field)?.to_owned()), *alias)
+                }
+                _ => return Err(invalid(format!("invalid aggregate '{item}'"))),
+            };
+            Ok(Aggregate {
+                method,
+                field,
+                alias: identifier(alias)?.to_owned(),
+            })
+        })
+        .collect()
+}
+
+/// Arguments of `name(...)` if `step` is exactly one call of `name`.
+fn call_args<'a>(step: &'a str, name: &str) -> Option<&'a str> {
+    let inner = step
+        .strip_prefix(name)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')?;
+    // `filter(a) and (b)` must not pass as a single call.
+    split_top_level(inner, ',').ok()?;
+    Some(inner.trim())
+}
+
+/// Split on `sep` outside parentheses and string literals, trimming parts.
+fn split_top_level(s: &str, sep: char) -> Result<Vec<&str>, Error> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '\'' => in_string = !in_string,
+            _ if in_string => {}
+            '(' => depth += 1,
+            ')' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| invalid("unbalanced parentheses"))?;
+            }
+            _ if c == sep && depth == 0 => {
+                parts.push(s[start..i].trim());
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 || in_string {
+        return Err(invalid("unbalanced parentheses or quotes"));
+    }
+    parts.push(s[start..].trim());
+
+    if parts.iter().any(|p| p.is_empty()) {
+        return Err(invalid(format!("empty element in '{s}'")));
+    }
+    Ok(parts)
+}
+
+fn identifier(s: &str) -> Result<&str, Error> {
+    let mut chars = s.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(s)
+    } else {
+        Err(invalid(format!("invalid name '{s}'")))
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> Error {
+    Error::InvalidApply(msg.into())
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groupby_with_aggregates() {
+        let apply =
+            parse_apply("groupby((type, region), aggregate($count as n, size with sum as total))")
+                .unwrap();
+        assert!(apply.filter.is_none());
+        assert_eq!(apply.group_by, ["type", "region"]);
+        assert_eq!(
+            apply.aggregates,
+            [
+                Aggregate {
+                    method: AggregateMethod::Count,
+                    field: None,
+                    alias: "n".to_owned(),
+                },
+                Aggregate {
+                    method: AggregateMethod::Sum,
+                    field: Some("size".to_owned()),
+                    alias: "total".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            apply.output_names().collect::<Vec<_>>(),
+            ["type", "region", "n", "total"]
+        );
+    }
+
+    #[test]
+    fn filter_runs_before_grouping() {
+        let apply = parse_apply("filter(name eq 'a/b (c)')/groupby((model))").unwrap();
+        assert!(apply.filter.is_some());
+        assert_eq!(apply.group_by, ["model"]);
+        assert!(apply.aggregates.is_empty());
+
+        let apply = parse_apply("aggregate(score with average as avg_score)").unwrap();
+        assert!(apply.group_by.is_empty());
+        assert_eq!(apply.aggregates[0].method, AggregateMethod::Average);
+    }
+
+    #[test]
+    fn rejects_unsupported_pipelines() {
+        for raw in [
+            "",
+            "filter(a eq 1)",
+            "groupby((a))/filter(a eq 1)",
+            "groupby(a)",
+            "groupby((a),aggregate(b with median as m))",
+            "aggregate(b with sum)",
+            "groupby((a),aggregate($count as a))",
+            "topcount(2, a)",
+            "filter(a eq 1) and (b eq 2)/groupby((a))",
+            "groupby((a)",
+        ] {
+            assert!(
+                matches!(parse_apply(raw), Err(Error::InvalidApply(_))),
+                "{raw:?} should be rejected"
+            );
+        }
+        assert!(matches!(
+            parse_apply("filter(a eq)/groupby((a))"),
+            Err(Error::InvalidFilter(_))
+        ));
+    }
+}
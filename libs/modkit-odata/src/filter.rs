@@ -114,6 +114,97 @@ impl fmt::Display for FilterOp {
     }
 }
 
+/// Scalar function applied to a field before it is compared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldFn {
+    ToLower,
+    ToUpper,
+    Trim,
+    Length,
+    /// Zero-based position of the substring, `-1` if absent.
+    IndexOf(String),
+    Year,
+    Month,
+    Day,
+    Hour,
+    /// Date part of a timestamp.
+    Date,
+}
+
+impl FieldFn {
+    /// Recognize `func(field[, args])`, returning the function and the
+    /// name of the field it is applied to.
+    #[must_use]
+    pub fn from_call<'a>(func_name: &str, args: &'a [odata_ast::Expr]) -> Option<(Self, &'a str)> {
+        use odata_ast::Expr as E;
+
+        let (name, func) = match (func_name.to_ascii_lowercase().as_str(), args) {
+            ("tolower", [E::Identifier(name)]) => (name, FieldFn::ToLower),
+            ("toupper", [E::Identifier(name)]) => (name, FieldFn::ToUpper),
+            ("trim", [E::Identifier(name)]) => (name, FieldFn::Trim),
+            ("length", [E::Identifier(name)]) => (name, FieldFn::Length),
+            ("indexof", [E::Identifier(name), E::Value(odata_ast::Value::String(sub))]) => {
+                (name, FieldFn::IndexOf(sub.clone()))
+            }
+            ("year", [E::Identifier(name)]) => (name, FieldFn::Year),
+            ("month", [E::Identifier(name)]) => (name, FieldFn::Month),
+            ("day", [E::Identifier(name)]) => (name, FieldFn::Day),
+            ("hour", [E::Identifier(name)]) => (name, FieldFn::Hour),
+            ("date", [E::Identifier(name)]) => (name, FieldFn::Date),
+            _ => return None,
+        };
+        Some((func, name.as_str()))
+    }
+
+    /// Kind of the value the function produces.
+    #[must_use]
+    pub fn result_kind(&self) -> FieldKind {
+        match self {
+            FieldFn::ToLower | FieldFn::ToUpper | FieldFn::Trim => FieldKind::String,
+            FieldFn::Length
+            | FieldFn::IndexOf(_)
+            | FieldFn::Year
+            | FieldFn::Month
+            | FieldFn::Day
+            | FieldFn::Hour => FieldKind::I64,
+            FieldFn::Date => FieldKind::Date,
+        }
+    }
+
+    /// Whether the function can be applied to a field of `kind`.
+    #[must_use]
+    pub fn accepts(&self, kind: FieldKind) -> bool {
+        match self {
+            FieldFn::ToLower
+            | FieldFn::ToUpper
+            | FieldFn::Trim
+            | FieldFn::Length
+            | FieldFn::IndexOf(_) => kind == FieldKind::String,
+            FieldFn::Year | FieldFn::Month | FieldFn::Day => {
+                matches!(kind, FieldKind::DateTimeUtc | FieldKind::Date)
+            }
+            FieldFn::Hour | FieldFn::Date => kind == FieldKind::DateTimeUtc,
+        }
+    }
+}
+
+impl fmt::Display for FieldFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldFn::ToLower => write!(f, "tolower"),
+            FieldFn::ToUpper => write!(f, "toupper"),
+            FieldFn::Trim => write!(f, "trim"),
+            FieldFn::Length => write!(f, "length"),
+            FieldFn::IndexOf(_) => write!(f, "indexof"),
+            FieldFn::Year => write!(f, "year"),
+            FieldFn::Month => write!(f, "month"),
+            FieldFn::Day => write!(f, "day"),
+            FieldFn::Hour => write!(f, "hour"),
+            FieldFn::Date => write!(f, "date"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum FilterNode<F: FilterField> {
     Binary {
@@ -121,6 +212,14 @@ pub enum FilterNode<F: FilterField> {
         op: FilterOp,
         value: ODataValue,
     },
+    /// `func(field) op value`, e.g. `tolower(name) eq 'bob'` or
+    /// `contains(tolower(name), 'bo')`.
+    Function {
+        func: FieldFn,
+        field: F,
+        op: FilterOp,
+        value: ODataValue,
+    },
     InList {
         field: F,
         values: Vec<ODataValue>,
@@ -231,23 +330,6 @@ pub fn convert_expr_to_filter_node<F: FilterField>(
         }
 
         E::Compare(left, op, right) => {
-            let (field_name, value) = match (&**left, &**right) {
-                (E::Identifier(name), E::Value(val)) => (name.as_str(), val.clone()),
-                (E::Identifier(_), E::Identifier(_)) => {
-                    return Err(FilterError::FieldToFieldComparison);
-                }
-                _ => {
-                    return Err(FilterError::InvalidExpression(
-                        "Comparison must be between field and value".to_owned(),
-                    ));
-                }
-            };
-
-            let field = F::from_name(field_name)
-                .ok_or_else(|| FilterError::UnknownField(field_name.to_owned()))?;
-
-            validate_value_type(field, &value)?;
-
             let filter_op = match op {
                 odata_ast::CompareOperator::Eq => FilterOp::Eq,
                 odata_ast::CompareOperator::Ne => FilterOp::Ne,
@@ -257,43 +339,46 @@ pub fn convert_expr_to_filter_node<F: FilterField>(
                 odata_ast::CompareOperator::Le => FilterOp::Le,
             };
 
-            Ok(FilterNode::binary(field, filter_op, value))
-        }
-
-        E::Function(func_name, args) => {
-            let name_lower = func_name.to_ascii_lowercase();
-            match (name_lower.as_str(), args.as_slice()) {
-                (
-                    "contains",
-                    [
-                        E::Identifier(field_name),
-                        E::Value(odata_ast::Value::String(s)),
-                    ],
-                ) => {
+            match (&**left, &**right) {
+                (E::Identifier(field_name), E::Value(value)) => {
                     let field = F::from_name(field_name)
                         .ok_or_else(|| FilterError::UnknownField(field_name.clone()))?;
-
-                    if field.kind() != FieldKind::String {
-                        return Err(FilterError::TypeMismatch {
-                            field: field_name.clone(),
-                            expected: FieldKind::String,
-                            got: "non-string".to_owned(),
-                        });
-                    }
-
-                    Ok(FilterNode::binary(
+                    validate_compared_value(field.name(), field.kind(), filter_op, value)?;
+                    Ok(FilterNode::binary(field, filter_op, value.clone()))
+                }
+                (E::Function(func_name, args), E::Value(value)) => {
+                    let (func, field) = resolve_field_fn::<F>(func_name, args)?;
+                    validate_compared_value(func_name, func.result_kind(), filter_op, value)?;
+                    Ok(FilterNode::Function {
+                        func,
                         field,
-                        FilterOp::Contains,
-                        odata_ast::Value::String(s.clone()),
-                    ))
+                        op: filter_op,
+                        value: value.clone(),
+                    })
                 }
-                (
-                    "startswith",
-                    [
-                        E::Identifier(field_name),
-                        E::Value(odata_ast::Value::String(s)),
-                    ],
-                ) => {
+                (E::Identifier(_), E::Identifier(_)) => Err(FilterError::FieldToFieldComparison),
+                _ => Err(FilterError::InvalidExpression(
+                    "Comparison must be between field and value".to_owned(),
+                )),
+            }
+        }
+
+        E::Function(func_name, args) => {
+            let unsupported =
+                || FilterError::UnsupportedOperation(format!("Function '{func_name}'"));
+            let op = match func_name.to_ascii_lowercase().as_str() {
+                "contains" => FilterOp::Contains,
+                "startswith" => FilterOp::StartsWith,
+                "endswith" => FilterOp::EndsWith,
+                _ => return Err(unsupported()),
+            };
+            let [operand, E::Value(odata_ast::Value::String(s))] = args.as_slice() else {
+                return Err(unsupported());
+            };
+            let value = odata_ast::Value::String(s.clone());
+
+            match operand {
+                E::Identifier(field_name) => {
                     let field = F::from_name(field_name)
                         .ok_or_else(|| FilterError::UnknownField(field_name.clone()))?;
 
@@ -305,39 +390,26 @@ pub fn convert_expr_to_filter_node<F: FilterField>(
                         });
                     }
 
-                    Ok(FilterNode::binary(
-                        field,
-                        FilterOp::StartsWith,
-                        odata_ast::Value::String(s.clone()),
-                    ))
+                    Ok(FilterNode::binary(field, op, value))
                 }
-                (
-                    "endswith",
-                    [
-                        E::Identifier(field_name),
-                        E::Value(odata_ast::Value::String(s)),
-                    ],
-                ) => {
-                    let field = F::from_name(field_name)
-                        .ok_or_else(|| FilterError::UnknownField(field_name.clone()))?;
-
-                    if field.kind() != FieldKind::String {
+                // e.g. contains(tolower(name), 'bob')
+                E::Function(inner_name, inner_args) => {
+                    let (func, field) = resolve_field_fn::<F>(inner_name, inner_args)?;
+                    if func.result_kind() != FieldKind::String {
                         return Err(FilterError::TypeMismatch {
-                            field: field_name.clone(),
+                            field: inner_name.clone(),
                             expected: FieldKind::String,
                             got: "non-string".to_owned(),
                         });
                     }
-
-                    Ok(FilterNode::binary(
+                    Ok(FilterNode::Function {
+                        func,
                         field,
-                        FilterOp::EndsWith,
-                        odata_ast::Value::String(s.clone()),
-                    ))
+                        op,
+                        value,
+                    })
                 }
-                _ => Err(FilterError::UnsupportedOperation(format!(
-                    "Function '{func_name}'"
-                ))),
+                _ => Err(unsupported()),
             }
         }
 
@@ -378,16 +450,78 @@ pub fn convert_expr_to_filter_node<F: FilterField>(
             Ok(FilterNode::InList { field, values })
         }
 
+        E::Lambda(path, ..) => Err(FilterError::UnsupportedOperation(format!(
+            "Lambda over '{path}'"
+        ))),
         E::Identifier(name) => Err(FilterError::BareIdentifier(name.clone())),
         E::Value(_) => Err(FilterError::BareLiteral),
     }
 }
 
+/// Resolve `func(field[, args])` into a [`FieldFn`] and the field it is applied to.
+fn resolve_field_fn<F: FilterField>(
+    func_name: &str,
+    args: &[odata_ast::Expr],
+) -> FilterResult<(FieldFn, F)> {
+    let (func, field_name) = FieldFn::from_call(func_name, args)
+        .ok_or_else(|| FilterError::UnsupportedOperation(format!("Function '{func_name}'")))?;
+
+    let field =
+        F::from_name(field_name).ok_or_else(|| FilterError::UnknownField(field_name.to_owned()))?;
+    if !func.accepts(field.kind()) {
+        return Err(FilterError::UnsupportedOperation(format!(
+            "Function '{func}' on {} field '{field_name}'",
+            field.kind()
+        )));
+    }
+    Ok((func, field))
+}
+
+/// Validate the right-hand side of `lhs op value`, where `lhs` has `kind`.
+///
+/// `null` is only comparable with `eq` and `ne`.
+fn validate_compared_value(
+    lhs: &str,
+    kind: FieldKind,
+    op: FilterOp,
+    value: &odata_ast::Value,
+) -> FilterResult<()> {
+    if matches!(value, odata_ast::Value::Null) {
+        return match op {
+            FilterOp::Eq | FilterOp::Ne => Ok(()),
+            _ => Err(FilterError::UnsupportedOperation(format!(
+                "'{op}' with null on '{lhs}'"
+            ))),
+        };
+    }
+    if value_matches_kind(kind, value) {
+        Ok(())
+    } else {
+        Err(FilterError::TypeMismatch {
+            field: lhs.to_owned(),
+            expected: kind,
+            got: value.to_string(),
+        })
+    }
+}
+
 fn validate_value_type<F: FilterField>(field: F, value: &odata_ast::Value) -> FilterResult<()> {
+    let kind = field.kind();
+    if value_matches_kind(kind, value) {
+        Ok(())
+    } else {
+        Err(FilterError::TypeMismatch {
+            field: field.name().to_owned(),
+            expected: kind,
+            got: value.to_string(),
+        })
+    }
+}
+
+fn value_matches_kind(kind: FieldKind, value: &odata_ast::Value) -> bool {
     use odata_ast::Value as V;
 
-    let kind = field.kind();
-    let matches = matches!(
+    matches!(
         (kind, value),
         (FieldKind::String, V::String(_))
             | (
@@ -399,15 +533,125 @@ fn validate_value_type<F: FilterField>(field: F, value: &odata_ast::Value) -> Fi
             | (FieldKind::DateTimeUtc, V::DateTime(_))
             | (FieldKind::Date, V::Date(_))
             | (FieldKind::Time, V::Time(_))
-    );
+    )
+}
 
-    if matches {
-        Ok(())
-    } else {
-        Err(FilterError::TypeMismatch {
-            field: field.name().to_owned(),
-            expected: kind,
-            got: value.to_string(),
-        })
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    enum Field {
+        Name,
+        Score,
+        CreatedAt,
+    }
+
+    impl FilterField for Field {
+        const FIELDS: &'static [Self] = &[Field::Name, Field::Score, Field::CreatedAt];
+
+        fn name(&self) -> &'static str {
+            match self {
+                Field::Name => "name",
+                Field::Score => "score",
+                Field::CreatedAt => "created_at",
+            }
+        }
+
+        fn kind(&self) -> FieldKind {
+            match self {
+                Field::Name => FieldKind::String,
+                Field::Score => FieldKind::I64,
+                Field::CreatedAt => FieldKind::DateTimeUtc,
+            }
+        }
+    }
+
+    fn parse(raw: &str) -> FilterResult<FilterNode<Field>> {
+        parse_odata_filter::<Field>(raw)
+    }
+
+    #[test]
+    fn string_functions_wrap_the_field() {
+        let node = parse("tolower(name) eq 'bob'").unwrap();
+        assert!(matches!(
+            node,
+            FilterNode::Function {
+                func: FieldFn::ToLower,
+                field: Field::Name,
+                op: FilterOp::Eq,
+                ..
+            }
+        ));
+
+        let node = parse("contains(toupper(name), 'BO')").unwrap();
+        assert!(matches!(
+            node,
+            FilterNode::Function {
+                func: FieldFn::ToUpper,
+                op: FilterOp::Contains,
+                ..
+            }
+        ));
+
+        let node = parse("indexof(name, 'o') ge 1").unwrap();
+        assert!(matches!(
+            node,
+            FilterNode::Function { func: FieldFn::IndexOf(ref sub), op: FilterOp::Ge, .. } if sub == "o"
+        ));
+    }
+
+    #[test]
+    fn function_results_are_type_checked() {
+        assert!(parse("length(name) gt 3").is_ok());
+        assert!(matches!(
+            parse("length(name) eq 'x'"),
+            Err(FilterError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            parse("contains(length(name), 'x')"),
+            Err(FilterError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            parse("year(name) eq 2024"),
+            Err(FilterError::UnsupportedOperation(_))
+        ));
+        assert!(matches!(
+            parse("substring(name, 1) eq 'x'"),
+            Err(FilterError::UnsupportedOperation(_))
+        ));
+    }
+
+    #[test]
+    fn date_functions_accept_timestamps() {
+        assert!(parse("year(created_at) eq 2024 and hour(created_at) lt 12").is_ok());
+        let node = parse("date(created_at) eq 2024-03-01").unwrap();
+        assert!(matches!(
+            node,
+            FilterNode::Function {
+                func: FieldFn::Date,
+                value: ODataValue::Date(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn null_is_only_compared_for_equality() {
+        assert!(matches!(
+            parse("name eq null").unwrap(),
+            FilterNode::Binary {
+                op: FilterOp::Eq,
+                value: ODataValue::Null,
+                ..
+            }
+        ));
+        assert!(parse("score ne null").is_ok());
+        assert!(parse("tolower(name) eq null").is_ok());
+        assert!(matches!(
+            parse("score gt null"),
+            Err(FilterError::UnsupportedOperation(_))
+        ));
     }
 }
@@ -1,4 +1,5 @@
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+pub mod apply;
 pub mod builder;
 pub mod errors;
 pub mod filter;
@@ -9,12 +10,15 @@ pub mod page;
 pub mod pagination;
 pub mod problem_mapping;
 pub mod schema;
+pub mod search;
 
+pub use apply::{Apply, parse_apply};
 pub use builder::QueryBuilder;
 pub use limits::ODataLimits;
 pub use page::{Page, PageInfo};
-pub use pagination::{normalize_filter_for_hash, short_filter_hash};
+pub use pagination::{normalize_filter_for_hash, short_filter_hash, short_query_hash};
 pub use schema::{FieldRef, Schema};
+pub use search::{Search, parse_search};
 
 pub mod ast {
     use bigdecimal::BigDecimal;
@@ -29,6 +33,12 @@ pub mod ast {
         Compare(Box<Expr>, CompareOperator, Box<Expr>),
         In(Box<Expr>, Vec<Expr>),
         Function(String, Vec<Expr>),
+        /// `path/any(var: predicate)` / `path/all(var: predicate)` over a
+        /// collection field; the predicate is absent for `path/any()`.
+        ///
+        /// Inside the predicate, `var` names a collection member and
+        /// `var/field` one of its fields.
+        Lambda(String, LambdaOperator, Option<(String, Box<Expr>)>),
         Identifier(String),
         Value(Value),
     }
@@ -68,6 +78,12 @@ pub mod ast {
         }
     }
 
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum LambdaOperator {
+        Any,
+        All,
+    }
+
     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     pub enum CompareOperator {
         Eq,
@@ -275,6 +291,12 @@ impl std::fmt::Display for ODataOrderBy {
 /// - `InvalidFilter` → 422 `gts...~hx.odata.errors.invalid_filter.v1`
 /// - `InvalidOrderByField` → 422 `gts...~hx.odata.errors.invalid_orderby.v1`
 /// - Cursor errors → 422 `gts...~hx.odata.errors.invalid_cursor.v1`
+/// - `InvalidApply` → 422 `gts...~hx.odata.errors.invalid_apply.v1`
+/// - `InvalidSearch` → 422 `gts...~hx.odata.errors.invalid_search.v1`
+/// - `CountNotAllowed`, `ApplyNotAllowed`, `SearchNotAllowed` → 400
+///   `gts...~hx.odata.errors.unsupported_option.v1`
+/// - `TooManyGroups` → 400 `gts...~hx.odata.errors.too_many_groups.v1`
+/// - `FieldNotReadable` → 403 `gts...~hx.odata.errors.field_not_readable.v1`
 #[derive(thiserror::Error, Debug, Clone)]
 pub enum Error {
     // Filter parsing and validation errors
@@ -285,6 +307,14 @@ pub enum Error {
     #[error("unsupported $orderby field: {0}")]
     InvalidOrderByField(String),
 
+    // $apply parsing and validation errors
+    #[error("invalid $apply: {0}")]
+    InvalidApply(String),
+
+    // $search parsing and validation errors
+    #[error("invalid $search: {0}")]
+    InvalidSearch(String),
+
     // Pagination and cursor errors
     #[error("ORDER_MISMATCH")]
     OrderMismatch,
@@ -301,6 +331,23 @@ pub enum Error {
     #[error("ORDER_WITH_CURSOR")]
     OrderWithCursor,
 
+    #[error("$count is not supported by this endpoint")]
+    CountNotAllowed,
+
+    #[error("$apply is not supported by this endpoint")]
+    ApplyNotAllowed,
+
+    #[error("$search is not supported by this endpoint")]
+    SearchNotAllowed,
+
+    // An $apply aggregation has more groups than one response may return
+    #[error("$apply yields more than {0} groups")]
+    TooManyGroups(u64),
+
+    // A field masked by the caller's access scope was used in the query
+    #[error("field is not readable: {0}")]
+    FieldNotReadable(String),
+
     // Cursor parsing errors (previously CursorError variants)
     #[error("invalid cursor: invalid base64url encoding")]
     CursorInvalidBase64,
@@ -350,6 +397,29 @@ pub fn validate_cursor_against(
     Ok(())
 }
 
+/// Refuse a `$select` naming a field the caller can never read.
+///
+/// `hidden_fields` are the fields masked on every access path of the caller's
+/// scope (`AccessScope::hidden_fields()`), which would always come back empty.
+/// Matching is case-insensitive, and a nested selector (`address.city`) is
+/// refused when its top-level field is hidden.
+///
+/// # Errors
+/// Returns `Error::FieldNotReadable` naming the first selected hidden field.
+pub fn ensure_selectable(
+    selected_fields: Option<&[String]>,
+    hidden_fields: &[&str],
+) -> Result<(), Error> {
+    let refused = selected_fields.unwrap_or_default().iter().find(|field| {
+        let top = field.split('.').next().unwrap_or_default();
+        hidden_fields.iter().any(|h| h.eq_ignore_ascii_case(top))
+    });
+    match refused {
+        Some(field) => Err(Error::FieldNotReadable(field.clone())),
+        None => Ok(()),
+    }
+}
+
 // Cursor v1
 #[derive(Clone, Debug)]
 pub struct CursorV1 {
@@ -468,6 +538,12 @@ pub struct ODataQuery {
     pub cursor: Option<CursorV1>,
     pub filter_hash: Option<String>,
     pub select: Option<Vec<String>>,
+    /// `$count=true`: the client asks for the total number of matching items.
+    pub count: bool,
+    /// `$apply`: the client asks for grouped aggregates instead of items.
+    pub apply: Option<apply::Apply>,
+    /// `$search`: free-text terms matched by the database's full-text index.
+    pub search: Option<search::Search>,
 }
 
 impl ODataQuery {
@@ -505,6 +581,21 @@ impl ODataQuery {
         self
     }
 
+    pub fn with_count(mut self, count: bool) -> Self {
+        self.count = count;
+        self
+    }
+
+    pub fn with_apply(mut self, apply: apply::Apply) -> Self {
+        self.apply = Some(apply);
+        self
+    }
+
+    pub fn with_search(mut self, search: search::Search) -> Self {
+        self.search = Some(search);
+        self
+    }
+
     /// Get filter as AST
     #[must_use]
     pub fn filter(&self) -> Option<&ast::Expr> {
@@ -550,9 +641,18 @@ mod odata_parse_tests;
 mod tests;
 
 mod convert_odata_filters {
-    use super::ast::{CompareOperator, Expr, Value};
+    use super::ast::{CompareOperator, Expr, LambdaOperator, Value};
     use crate::odata_filters as od;
 
+    impl From<od::LambdaOperator> for LambdaOperator {
+        fn from(op: od::LambdaOperator) -> Self {
+            match op {
+                od::LambdaOperator::Any => LambdaOperator::Any,
+                od::LambdaOperator::All => LambdaOperator::All,
+            }
+        }
+    }
+
     impl From<od::CompareOperator> for CompareOperator {
         fn from(op: od::CompareOperator) -> Self {
             use od::CompareOperator::{
@@ -586,7 +686,7 @@ mod convert_odata_filters {
 
     impl From<od::Expr> for Expr {
         fn from(e: od::Expr) -> Self {
-            use od::Expr::{And, Compare, Function, Identifier, In, Not, Or, Value};
+            use od::Expr::{And, Compare, Function, Identifier, In, Lambda, Not, Or, Value};
             match e {
                 And(a, b) => Expr::And(Box::new((*a).into()), Box::new((*b).into())),
                 Or(a, b) => Expr::Or(Box::new((*a).into()), Box::new((*b).into())),
@@ -599,6 +699,11 @@ mod convert_odata_filters {
                     list.into_iter().map(Into::into).collect(),
                 ),
                 Function(n, args) => Expr::Function(n, args.into_iter().map(Into::into).collect()),
+                Lambda(path, op, body) => Expr::Lambda(
+                    path,
+                    op.into(),
+                    body.map(|(var, pred)| (var, Box::new((*pred).into()))),
+                ),
                 Identifier(s) => Expr::Identifier(s),
                 Value(v) => Expr::Value(v.into()),
             }
@@ -652,9 +757,10 @@ pub fn parse_filter_string(raw: &str) -> Result<ParsedFilter, Error> {
 
     /// Count nodes in AST for complexity budget enforcement.
     fn count_ast_nodes(e: &od::Expr) -> usize {
-        use od::Expr::{And, Compare, Function, Identifier, In, Not, Or, Value};
+        use od::Expr::{And, Compare, Function, Identifier, In, Lambda, Not, Or, Value};
         match e {
             Value(_) | Identifier(_) => 1,
+            Lambda(_, _, body) => 1 + body.as_ref().map_or(0, |(_, pred)| count_ast_nodes(pred)),
             Not(x) => 1 + count_ast_nodes(x),
             And(a, b) | Or(a, b) | Compare(a, _, b) => 1 + count_ast_nodes(a) + count_ast_nodes(b),
             In(a, list) => 1 + count_ast_nodes(a) + list.iter().map(count_ast_nodes).sum::<usize>(),
@@ -4,9 +4,14 @@
 //! - Maximum `$top` value
 //! - Maximum number of `$orderby` fields
 //! - Maximum filter expression length
+//! - Whether `$count` may trigger a `COUNT` query
+//! - Whether `$apply` is accepted and which fields it may group by
+//! - Whether `$search` is accepted and how many terms it may have
 //! - Cursor integrity checks (HMAC signing)
 
 use crate::Error;
+use crate::apply::Apply;
+use crate::search::Search;
 
 /// Default configuration for `OData` input limits
 #[derive(Debug, Clone)]
@@ -22,6 +27,12 @@ pub struct ODataLimits {
     pub require_signed_cursors: bool,
     /// HMAC key for cursor signing (if enabled)
     pub cursor_hmac_key: Option<Vec<u8>>,
+    /// Whether `$count=true` is honored (default: false, counts are expensive)
+    pub allow_count: bool,
+    /// Fields `$apply` may group by; `None` rejects `$apply` (default)
+    pub groupable_fields: Option<Vec<String>>,
+    /// Maximum number of `$search` terms; `None` rejects `$search` (default)
+    pub max_search_terms: Option<usize>,
 }
 
 impl Default for ODataLimits {
@@ -32,6 +43,9 @@ impl Default for ODataLimits {
             max_filter_length: 2000,
             require_signed_cursors: false,
             cursor_hmac_key: None,
+            allow_count: false,
+            groupable_fields: None,
+            max_search_terms: None,
         }
     }
 }
@@ -67,6 +81,33 @@ impl ODataLimits {
         self
     }
 
+    /// Allow `$count=true` to request the total number of matching items
+    pub fn with_count(mut self) -> Self {
+        self.allow_count = true;
+        self
+    }
+
+    /// Accept `$apply`, grouping only by the given fields.
+    ///
+    /// An empty list still allows `aggregate(...)` over all matching rows.
+    pub fn with_apply<I, S>(mut self, groupable: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.groupable_fields = Some(groupable.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Accept `$search` with at most `max_terms` words and phrases.
+    ///
+    /// The entity must declare searchable columns and the matching full-text
+    /// index (see `modkit_db::odata::search`).
+    pub fn with_search(mut self, max_terms: usize) -> Self {
+        self.max_search_terms = Some(max_terms);
+        self
+    }
+
     /// Validate a $top value against limits.
     ///
     /// # Errors
@@ -105,6 +146,58 @@ impl ODataLimits {
         }
         Ok(())
     }
+
+    /// Validate a `$count` request.
+    ///
+    /// # Errors
+    /// Returns `Error::CountNotAllowed` if a count is requested but not enabled.
+    pub fn validate_count(&self, requested: bool) -> Result<(), Error> {
+        if requested && !self.allow_count {
+            return Err(Error::CountNotAllowed);
+        }
+        Ok(())
+    }
+
+    /// Validate an `$apply` request.
+    ///
+    /// # Errors
+    /// Returns `Error::ApplyNotAllowed` if `$apply` is not enabled and
+    /// `Error::InvalidApply` if it groups by a field that is not groupable.
+    pub fn validate_apply(&self, apply: Option<&Apply>) -> Result<(), Error> {
+        let Some(apply) = apply else {
+            return Ok(());
+        };
+        let groupable = self
+            .groupable_fields
+            .as_deref()
+            .ok_or(Error::ApplyNotAllowed)?;
+        if let Some(field) = apply
+            .group_by
+            .iter()
+            .find(|f| !groupable.iter().any(|g| g.eq_ignore_ascii_case(f)))
+        {
+            return Err(Error::InvalidApply(format!(
+                "field '{field}' is not groupable"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validate a `$search` request.
+    ///
+    /// # Errors
+    /// Returns `Error::SearchNotAllowed` if `$search` is not enabled and
+    /// `Error::InvalidSearch` if it has too many terms.
+    pub fn validate_search(&self, search: Option<&Search>) -> Result<(), Error> {
+        let Some(search) = search else {
+            return Ok(());
+        };
+        let max = self.max_search_terms.ok_or(Error::SearchNotAllowed)?;
+        if search.terms.len() > max {
+            return Err(Error::InvalidSearch(format!("too many terms (max: {max})")));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -119,6 +212,9 @@ mod tests {
         assert_eq!(limits.max_orderby_fields, 5);
         assert_eq!(limits.max_filter_length, 2000);
         assert!(!limits.require_signed_cursors);
+        assert!(!limits.allow_count);
+        assert!(limits.groupable_fields.is_none());
+        assert!(limits.max_search_terms.is_none());
     }
 
     #[test]
@@ -160,6 +256,58 @@ mod tests {
         assert!(limits.validate_orderby_count(6).is_err());
     }
 
+    #[test]
+    fn test_validate_count_is_opt_in() {
+        let limits = ODataLimits::default();
+        assert!(limits.validate_count(false).is_ok());
+        assert!(matches!(
+            limits.validate_count(true),
+            Err(Error::CountNotAllowed)
+        ));
+        assert!(ODataLimits::new().with_count().validate_count(true).is_ok());
+    }
+
+    #[test]
+    fn test_validate_apply_is_opt_in_per_field() {
+        let apply = crate::parse_apply("groupby((model),aggregate($count as n))").unwrap();
+        assert!(ODataLimits::default().validate_apply(None).is_ok());
+        assert!(matches!(
+            ODataLimits::default().validate_apply(Some(&apply)),
+            Err(Error::ApplyNotAllowed)
+        ));
+
+        let limits = ODataLimits::new().with_apply(["Model"]);
+        assert!(limits.validate_apply(Some(&apply)).is_ok());
+        assert!(matches!(
+            ODataLimits::new()
+                .with_apply(["status"])
+                .validate_apply(Some(&apply)),
+            Err(Error::InvalidApply(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_search_is_opt_in() {
+        let search = crate::parse_search("budget -draft").unwrap();
+        assert!(ODataLimits::default().validate_search(None).is_ok());
+        assert!(matches!(
+            ODataLimits::default().validate_search(Some(&search)),
+            Err(Error::SearchNotAllowed)
+        ));
+        assert!(
+            ODataLimits::new()
+                .with_search(2)
+                .validate_search(Some(&search))
+                .is_ok()
+        );
+        assert!(matches!(
+            ODataLimits::new()
+                .with_search(1)
+                .validate_search(Some(&search)),
+            Err(Error::InvalidSearch(_))
+        ));
+    }
+
     #[test]
     fn test_custom_limits() {
         let limits = ODataLimits::new()
@@ -62,6 +62,10 @@ pub enum Expr {
     /// Function call with a name and a list of arguments.
     Function(String, Vec<Expr>),
 
+    /// Lambda over a collection property: the collection path, the operator,
+    /// and the range variable with its predicate (absent for `any()`).
+    Lambda(String, LambdaOperator, Option<(String, Box<Expr>)>),
+
     /// An identifier.
     Identifier(String),
 
@@ -91,6 +95,16 @@ pub enum CompareOperator {
     LessOrEqual,
 }
 
+/// Represents the lambda operators over collections.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LambdaOperator {
+    /// True if the predicate holds for at least one member.
+    Any,
+
+    /// True if the predicate holds for every member.
+    All,
+}
+
 /// Represents the various value types.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Value {
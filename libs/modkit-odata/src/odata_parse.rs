@@ -1,4 +1,4 @@
-use crate::odata_filters::{CompareOperator, Expr, ParseError, Value};
+use crate::odata_filters::{CompareOperator, Expr, LambdaOperator, ParseError, Value};
 use bigdecimal::BigDecimal;
 use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, Utc};
 use std::str::FromStr;
@@ -33,7 +33,7 @@ enum AfterValueExpr {
 peg::parser! {
     /// Parses `OData` v4 `$filter` expressions.
     grammar odata_filter() for str {
-        use super::{Expr, CompareOperator, Value, ParseError};
+        use super::{Expr, CompareOperator, LambdaOperator, Value, ParseError};
 
         /// Entry point for parsing a filter expression string.
         pub(super) rule parse_str() -> Result<Expr, ParseError>
@@ -75,9 +75,10 @@ peg::parser! {
             / "in" _ "(" _ r:filter_list() _ ")" { Ok(AfterValueExpr::In(r?)) }
             / { Ok(AfterValueExpr::End) }
 
-        /// Parses a value expression, which can be a function call, a value, or a property path.
+        /// Parses a value expression, which can be a lambda, a function call, a value, or a property path.
         rule value_expr() -> Result<Expr, ParseError>
-            = function_call()
+            = lambda()
+            / function_call()
             / v:value() { Ok(Expr::Value(v?)) }
             / p:property_path() { Ok(Expr::Identifier(p)) }
 
@@ -94,6 +95,29 @@ peg::parser! {
         rule function_call() -> Result<Expr, ParseError>
             = f:identifier() _ "(" _ l:filter_list() _ ")" { Ok(Expr::Function(f, l?)) }
 
+        /// Parses a lambda over a collection path, e.g. `tags/any(t: t eq 'x')`,
+        /// `tags/any()` or `items/all(i: i/qty gt 0)`.
+        /// OData 4.01 ABNF §5.1.1.13 — `anyExpr` / `allExpr`; `all` requires a predicate.
+        rule lambda() -> Result<Expr, ParseError>
+            = p:lambda_path() "/" "any" _ "(" _ b:lambda_body()? _ ")" {
+                Ok(Expr::Lambda(p, LambdaOperator::Any, b.transpose()?))
+            }
+            / p:lambda_path() "/" "all" _ "(" _ b:lambda_body() _ ")" {
+                Ok(Expr::Lambda(p, LambdaOperator::All, Some(b?)))
+            }
+
+        /// Parses the collection path in front of a lambda operator.
+        rule lambda_path() -> String
+            = s:$(identifier() ("/" !(lambda_op() _ "(") identifier())*) { s.to_owned() }
+
+        /// Matches a lambda operator name.
+        rule lambda_op()
+            = "any" / "all"
+
+        /// Parses `variable: predicate` inside a lambda.
+        rule lambda_body() -> Result<(String, Box<Expr>), ParseError>
+            = v:identifier() _ ":" _ f:filter() { Ok((v, Box::new(f?))) }
+
         /// Parses an OData property path (e.g., `hierarchy/depth`, `address/city`).
         /// OData 4.01 ABNF §5.1.1.15 — property paths use `/` as segment separator:
         ///   firstMemberExpr = memberExpr / inscopeVariableExpr [ "/" memberExpr ]
@@ -1,5 +1,5 @@
 use crate::odata_filters::CompareOperator::{self, *};
-use crate::odata_filters::{Expr, Value, parse_str};
+use crate::odata_filters::{Expr, LambdaOperator, Value, parse_str};
 use bigdecimal::BigDecimal;
 use std::str::FromStr;
 
@@ -1052,3 +1052,73 @@ fn odata_navigation_path_empty_segment_rejected() {
 fn odata_navigation_path_double_slash_rejected() {
     parse_str("hierarchy//depth eq 1").expect_err("double slash should be rejected");
 }
+
+#[test]
+fn lambda_any_with_predicate() {
+    let result = parse_str("tags/any(t: t eq 'urgent')").expect("valid filter tree");
+    assert_eq!(
+        result,
+        Expr::Lambda(
+            "tags".to_owned(),
+            LambdaOperator::Any,
+            Some((
+                "t".to_owned(),
+                Expr::Compare(
+                    Expr::Identifier("t".to_owned()).into(),
+                    CompareOperator::Equal,
+                    Expr::Value(Value::String("urgent".to_owned())).into()
+                )
+                .into()
+            ))
+        )
+    );
+}
+
+#[test]
+fn lambda_all_over_navigation_path() {
+    let result =
+        parse_str("order/items/all(i: i/qty gt 0) and name eq 'x'").expect("valid filter tree");
+    let Expr::And(left, _) = &result else {
+        panic!("expected And, got: {result:?}");
+    };
+    assert_eq!(
+        **left,
+        Expr::Lambda(
+            "order/items".to_owned(),
+            LambdaOperator::All,
+            Some((
+                "i".to_owned(),
+                Expr::Compare(
+                    Expr::Identifier("i/qty".to_owned()).into(),
+                    CompareOperator::GreaterThan,
+                    Expr::Value(Value::Number(BigDecimal::from_str("0").unwrap())).into()
+                )
+                .into()
+            ))
+        )
+    );
+}
+
+#[test]
+fn lambda_any_without_predicate() {
+    let result = parse_str("not tags/any()").expect("valid filter tree");
+    assert_eq!(
+        result,
+        Expr::Not(Expr::Lambda("tags".to_owned(), LambdaOperator::Any, None).into())
+    );
+}
+
+#[test]
+fn lambda_operator_names_are_still_paths() {
+    let result = parse_str("stats/anything eq 1").expect("valid filter tree");
+    assert!(matches!(
+        result,
+        Expr::Compare(ref l, _, _) if **l == Expr::Identifier("stats/anything".to_owned())
+    ));
+}
+
+#[test]
+fn lambda_all_requires_predicate() {
+    parse_str("tags/all()").expect_err("all() without a predicate should be rejected");
+    parse_str("tags/any(t)").expect_err("lambda variable without predicate should be rejected");
+}
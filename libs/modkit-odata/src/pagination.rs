@@ -1,6 +1,7 @@
 //! Filter hashing utilities for `OData` pagination
 
 use crate::ast;
+use crate::search::Search;
 use chrono::SecondsFormat;
 use sha2::{Digest, Sha256};
 
@@ -51,6 +52,21 @@ pub fn normalize_filter_for_hash(expr: &ast::Expr) -> String {
                     .join(",");
                 format!("FN({},{})", name.to_lowercase(), args_str)
             }
+            ast::Expr::Lambda(path, op, body) => {
+                let op_str = match op {
+                    ast::LambdaOperator::Any => "ANY",
+                    ast::LambdaOperator::All => "ALL",
+                };
+                match body {
+                    Some((var, pred)) => format!(
+                        "{op_str}({},{},{})",
+                        path.to_lowercase(),
+                        var.to_lowercase(),
+                        normalize_expr(pred)
+                    ),
+                    None => format!("{op_str}({})", path.to_lowercase()),
+                }
+            }
             ast::Expr::Identifier(name) => {
                 format!("ID({})", name.to_lowercase())
             }
@@ -90,6 +106,20 @@ pub fn short_filter_hash(expr: Option<&ast::Expr>) -> Option<String> {
     })
 }
 
+/// Like [`short_filter_hash`], but also covers `$search`, so cursors of one
+/// search are rejected for another. Without a search the result equals
+/// `short_filter_hash(filter)`.
+#[must_use]
+pub fn short_query_hash(filter: Option<&ast::Expr>, search: Option<&Search>) -> Option<String> {
+    let Some(search) = search else {
+        return short_filter_hash(filter);
+    };
+    let filter = filter.map(normalize_filter_for_hash).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{filter}|SEARCH({})", search.normalized()).as_bytes());
+    Some(hex::encode(&hasher.finalize()[..8]))
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -137,4 +167,25 @@ mod tests {
     fn test_short_filter_hash_none() {
         assert_eq!(short_filter_hash(None), None);
     }
+
+    #[test]
+    fn test_short_query_hash_covers_search() {
+        let expr = Expr::Identifier("archived".to_owned());
+        let budget = crate::parse_search("budget").unwrap();
+        let draft = crate::parse_search("draft").unwrap();
+
+        assert_eq!(
+            short_query_hash(Some(&expr), None),
+            short_filter_hash(Some(&expr))
+        );
+        assert!(short_query_hash(None, Some(&budget)).is_some());
+        assert_ne!(
+            short_query_hash(Some(&expr), Some(&budget)),
+            short_query_hash(Some(&expr), Some(&draft))
+        );
+        assert_ne!(
+            short_query_hash(Some(&expr), Some(&budget)),
+            short_query_hash(None, Some(&budget))
+        );
+    }
 }
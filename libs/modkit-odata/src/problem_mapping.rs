@@ -11,10 +11,11 @@ use modkit_errors::problem::Problem;
 impl From<Error> for Problem {
     fn from(err: Error) -> Self {
         use Error::{
-            CursorInvalidBase64, CursorInvalidDirection, CursorInvalidFields, CursorInvalidJson,
-            CursorInvalidKeys, CursorInvalidVersion, Db, FilterMismatch, InvalidCursor,
-            InvalidFilter, InvalidLimit, InvalidOrderByField, OrderMismatch, OrderWithCursor,
-            ParsingUnavailable,
+            ApplyNotAllowed, CountNotAllowed, CursorInvalidBase64, CursorInvalidDirection,
+            CursorInvalidFields, CursorInvalidJson, CursorInvalidKeys, CursorInvalidVersion, Db,
+            FieldNotReadable, FilterMismatch, InvalidApply, InvalidCursor, InvalidFilter,
+            InvalidLimit, InvalidOrderByField, InvalidSearch, OrderMismatch, OrderWithCursor,
+            ParsingUnavailable, SearchNotAllowed, TooManyGroups,
         };
 
         match err {
@@ -26,6 +27,14 @@ impl From<Error> for Problem {
             InvalidOrderByField(field) => ErrorCode::odata_errors_invalid_orderby_v1()
                 .as_problem(format!("Unsupported $orderby field: {field}")),
 
+            // $apply parsing and validation errors → 422
+            InvalidApply(msg) => ErrorCode::odata_errors_invalid_apply_v1()
+                .as_problem(format!("Invalid $apply: {msg}")),
+
+            // $search parsing and validation errors → 422
+            InvalidSearch(msg) => ErrorCode::odata_errors_invalid_search_v1()
+                .as_problem(format!("Invalid $search: {msg}")),
+
             // All cursor-related errors → 422
             InvalidCursor => {
                 ErrorCode::odata_errors_invalid_cursor_v1().as_problem("invalid cursor")
@@ -63,6 +72,23 @@ impl From<Error> for Problem {
             OrderWithCursor => ErrorCode::odata_errors_invalid_cursor_v1()
                 .as_problem("Cannot specify both $orderby and cursor parameters"),
 
+            CountNotAllowed => ErrorCode::odata_errors_unsupported_option_v1()
+                .as_problem("$count is not supported by this endpoint"),
+
+            ApplyNotAllowed => ErrorCode::odata_errors_unsupported_option_v1()
+                .as_problem("$apply is not supported by this endpoint"),
+
+            SearchNotAllowed => ErrorCode::odata_errors_unsupported_option_v1()
+                .as_problem("$search is not supported by this endpoint"),
+
+            TooManyGroups(limit) => ErrorCode::odata_errors_too_many_groups_v1().as_problem(
+                format!("$apply yields more than {limit} groups; narrow the filter or raise $top"),
+            ),
+
+            // Fields masked by the access scope → 403
+            FieldNotReadable(field) => ErrorCode::odata_errors_field_not_readable_v1()
+                .as_problem(format!("Field is not readable: {field}")),
+
             // Database errors → 500 (should be caught earlier)
             Db(_msg) => {
                 // Use filter error as safe default for unexpected DB errors
@@ -121,4 +147,57 @@ mod tests {
         assert!(problem.code.contains("odata"));
         assert!(problem.code.contains("invalid_cursor"));
     }
+
+    #[test]
+    fn test_count_not_allowed_converts_to_problem() {
+        use http::StatusCode;
+
+        let problem: Problem = Error::CountNotAllowed.into();
+
+        assert_eq!(problem.status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.title, "Unsupported Query Option");
+        assert!(problem.code.contains("unsupported_option"));
+    }
+
+    #[test]
+    fn test_apply_errors_convert_to_problem() {
+        use http::StatusCode;
+
+        let problem: Problem = Error::InvalidApply("bad".to_owned()).into();
+        assert_eq!(problem.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem.title, "Invalid Apply");
+        assert!(problem.detail.contains("bad"));
+
+        let problem: Problem = Error::ApplyNotAllowed.into();
+        assert_eq!(problem.title, "Unsupported Query Option");
+
+        let problem: Problem = Error::TooManyGroups(50).into();
+        assert_eq!(problem.status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.title, "Too Many Groups");
+        assert!(problem.detail.contains("50"));
+    }
+
+    #[test]
+    fn test_search_errors_convert_to_problem() {
+        use http::StatusCode;
+
+        let problem: Problem = Error::InvalidSearch("OR is not supported".to_owned()).into();
+        assert_eq!(problem.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem.title, "Invalid Search");
+        assert!(problem.code.contains("invalid_search"));
+
+        let problem: Problem = Error::SearchNotAllowed.into();
+        assert_eq!(problem.title, "Unsupported Query Option");
+    }
+
+    #[test]
+    fn test_field_not_readable_converts_to_problem() {
+        use http::StatusCode;
+
+        let problem: Problem = Error::FieldNotReadable("salary".to_owned()).into();
+        assert_eq!(problem.status, StatusCode::FORBIDDEN);
+        assert_eq!(problem.title, "Field Not Readable");
+        assert!(problem.detail.contains("salary"));
+        assert!(problem.code.contains("field_not_readable"));
+    }
 }
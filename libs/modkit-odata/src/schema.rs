@@ -7,7 +7,7 @@
 //!
 //! These types are protocol-level abstractions independent of SDK implementation.
 
-use crate::ast::{CompareOperator, Expr, Value};
+use crate::ast::{CompareOperator, Expr, LambdaOperator, Value};
 use std::marker::PhantomData;
 
 /// Schema trait defining field enums and their string mappings.
@@ -398,3 +398,65 @@ impl<S: Schema> FieldRef<S, String> {
         )
     }
 }
+
+/// Lambda operations (only available for collection fields).
+impl<S: Schema, T> FieldRef<S, Vec<T>> {
+    /// Create an `any` lambda: `field/any(var: predicate)`
+    ///
+    /// Inside `predicate`, refer to the member as `var` (or `var/field` for
+    /// members with fields).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let in_stock = Expr::Compare(
+    ///     Box::new(Expr::Identifier("i/qty".to_owned())),
+    ///     CompareOperator::Gt,
+    ///     Box::new(Expr::Value(Value::Number(0.into()))),
+    /// );
+    /// let filter = ITEMS.any("i", in_stock);
+    /// ```
+    #[must_use]
+    pub fn any(self, var: &str, predicate: Expr) -> Expr {
+        Expr::Lambda(
+            self.name().to_owned(),
+            LambdaOperator::Any,
+            Some((var.to_owned(), Box::new(predicate))),
+        )
+    }
+
+    /// Create an `all` lambda: `field/all(var: predicate)`
+    #[must_use]
+    pub fn all(self, var: &str, predicate: Expr) -> Expr {
+        Expr::Lambda(
+            self.name().to_owned(),
+            LambdaOperator::All,
+            Some((var.to_owned(), Box::new(predicate))),
+        )
+    }
+
+    /// Create a non-empty check: `field/any()`
+    #[must_use]
+    pub fn is_not_empty(self) -> Expr {
+        Expr::Lambda(self.name().to_owned(), LambdaOperator::Any, None)
+    }
+
+    /// Create a membership check: `field/any(x: x eq value)`
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let filter = TAGS.has("urgent");
+    /// ```
+    #[must_use]
+    pub fn has<V: IntoODataValue>(self, value: V) -> Expr {
+        self.any(
+            "x",
+            Expr::Compare(
+                Box::new(Expr::Identifier("x".to_owned())),
+                CompareOperator::Eq,
+                Box::new(Expr::Value(value.into_odata_value())),
+            ),
+        )
+    }
+}
@@ -0,0 +1,200 @@
+//! `$search`: free-text search over the columns an entity declares searchable.
+//!
+//! Supported syntax is the conjunctive subset of `OData` `$search`:
+//! - words (`budget`) and double-quoted phrases (`"quarterly report"`)
+//! - `AND` between terms, which is also implied by whitespace
+//! - `NOT` (or a leading `-`) to exclude a word or phrase
+//!
+//! `OR` and parentheses are rejected, and at least one term must not be
+//! negated. Matching and relevance are up to the database's full-text engine.
+//!
+//! ```text
+//! $search=budget "quarterly report"
+//! $search=invoice AND NOT draft
+//! ```
+
+use crate::Error;
+
+/// Name of the pseudo field that orders search results by relevance, e.g.
+/// `$orderby=relevance desc`. Only valid together with `$search`.
+pub const RELEVANCE: &str = "relevance";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchTerm {
+    /// Word or phrase text, without quotes.
+    pub text: String,
+    /// Whether the text was quoted and must match as a phrase.
+    pub phrase: bool,
+    /// Whether matching rows are excluded.
+    pub negated: bool,
+}
+
+/// Parsed `$search`: all terms must hold.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Search {
+    pub terms: Vec<SearchTerm>,
+}
+
+impl Search {
+    /// Terms that must match.
+    pub fn positive(&self) -> impl Iterator<Item = &SearchTerm> {
+        self.terms.iter().filter(|t| !t.negated)
+    }
+
+    /// Terms that must not match.
+    pub fn negative(&self) -> impl Iterator<Item = &SearchTerm> {
+        self.terms.iter().filter(|t| t.negated)
+    }
+
+    /// Stable representation for cursor hashing.
+    #[must_use]
+    pub fn normalized(&self) -> String {
+        self.terms
+            .iter()
+            .map(|t| {
+                let kind = if t.phrase { "PHRASE" } else { "WORD" };
+                let not = if t.negated { "NOT " } else { "" };
+                format!("{not}{kind}({})", t.text.to_lowercase())
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Parse a raw `$search` string.
+///
+/// # Errors
+/// Returns `Error::InvalidSearch` if the string is empty, uses `OR` or
+/// parentheses, has an unterminated phrase or dangling operator, or only
+/// excludes terms.
+pub fn parse_search(raw: &str) -> Result<Search, Error> {
+    let mut terms = Vec::new();
+    let mut negate = false;
+    let mut after_and = false;
+    let mut rest = raw.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        let (token, quoted, tail) = if c == '"' {
+            let end = rest[1..]
+                .find('"')
+                .ok_or_else(|| invalid("unterminated phrase"))?;
+            (&rest[1..=end], true, &rest[end + 2..])
+        } else {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == '"')
+                .unwrap_or(rest.len());
+            (&rest[..end], false, &rest[end..])
+        };
+        rest = tail.trim_start();
+
+        if !quoted {
+            match token {
+                "AND" => {
+                    if terms.is_empty() || negate || after_and {
+                        return Err(invalid("AND must join two terms"));
+                    }
+                    after_and = true;
+                    continue;
+                }
+                "NOT" => {
+                    if negate {
+                        return Err(invalid("NOT must precede a term"));
+                    }
+                    negate = true;
+                    continue;
+                }
+                "OR" => return Err(invalid("OR is not supported")),
+                _ => {}
+            }
+            if token.contains(['(', ')']) {
+                return Err(invalid("parentheses are not supported"));
+            }
+        }
+
+        let (text, negated) = match token.strip_prefix('-') {
+            Some(word) if !quoted && !negate => (word, true),
+            _ => (token, negate),
+        };
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            return Err(invalid("empty search term"));
+        }
+        terms.push(SearchTerm {
+            text,
+            phrase: quoted,
+            negated,
+        });
+        negate = false;
+        after_and = false;
+    }
+
+    if negate || after_and {
+        return Err(invalid("operator without a term"));
+    }
+    let search = Search { terms };
+    if search.positive().next().is_none() {
+        return Err(invalid("at least one term must not be negated"));
+    }
+    Ok(search)
+}
+
+fn invalid(msg: &str) -> Error {
+    Error::InvalidSearch(msg.to_owned())
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn term(text: &str, phrase: bool, negated: bool) -> SearchTerm {
+        SearchTerm {
+            text: text.to_owned(),
+            phrase,
+            negated,
+        }
+    }
+
+    #[test]
+    fn words_and_phrases_are_conjunctive() {
+        let search =
+            parse_search(r#"budget AND "quarterly   report" -draft NOT "old plan""#).unwrap();
+        assert_eq!(
+            search.terms,
+            [
+                term("budget", false, false),
+                term("quarterly report", true, false),
+                term("draft", false, true),
+                term("old plan", true, true),
+            ]
+        );
+        assert_eq!(search.positive().count(), 2);
+        assert_eq!(
+            search.normalized(),
+            "WORD(budget),PHRASE(quarterly report),NOT WORD(draft),NOT PHRASE(old plan)"
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_searches() {
+        for raw in [
+            "",
+            "   ",
+            "a OR b",
+            "(a b)",
+            "\"open",
+            "AND a",
+            "a AND",
+            "a AND AND b",
+            "a NOT",
+            "NOT NOT a",
+            "-a NOT b",
+            "\"\"",
+        ] {
+            assert!(
+                matches!(parse_search(raw), Err(Error::InvalidSearch(_))),
+                "{raw:?} should be rejected"
+            );
+        }
+    }
+}
@@ -390,3 +390,18 @@ fn test_parse_filter_string_error_contains_position() {
         "InvalidFilter should contain position and expectation info, got: {msg}"
     );
 }
+
+#[test]
+fn test_ensure_selectable_refuses_hidden_fields() {
+    let hidden = ["salary"];
+
+    assert!(crate::ensure_selectable(None, &hidden).is_ok());
+    let visible = vec!["id".to_owned(), "email".to_owned()];
+    assert!(crate::ensure_selectable(Some(&visible), &hidden).is_ok());
+
+    let selected = vec!["id".to_owned(), "Salary.base".to_owned()];
+    assert!(matches!(
+        crate::ensure_selectable(Some(&selected), &hidden),
+        Err(Error::FieldNotReadable(field)) if field == "Salary.base"
+    ));
+}
@@ -4,6 +4,10 @@
 //! This module provides a reusable cursor-based pager that converts a page-fetching function
 //! into a Stream of pages or items, hiding cursor management from SDK users.
 //!
+//! Pagers start at the first page and follow `next_cursor`. To walk back from a
+//! page, resume from its `prev_cursor`: the pager then follows `prev_cursor`
+//! until the start of the collection is reached.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -35,6 +39,11 @@
 //!         Err(PagerError::InvalidCursor(c)) => eprintln!("Invalid cursor: {}", c),
 //!     }
 //! }
+//!
+//! // Items before a page, nearest first
+//! let earlier = CursorPager::resume(query, page.page_info.prev_cursor.unwrap(), |q| async move {
+//!     client.list_users(q).await
+//! });
 //! ```
 
 use futures_core::Stream;
@@ -94,7 +103,8 @@ pin_project! {
         Fut: Future<Output = Result<Page<T>, E>>,
     {
         base_query: ODataQuery,
-        next_cursor: Option<String>,
+        cursor: Option<String>,
+        backward: bool,
         buffer: VecDeque<T>,
         done: bool,
         fetcher: F,
@@ -125,13 +135,34 @@ where
     pub fn new(base_query: ODataQuery, fetcher: F) -> Self {
         Self {
             base_query,
-            next_cursor: None,
+            cursor: None,
+            backward: false,
             buffer: VecDeque::new(),
             done: false,
             fetcher,
             current_fetch: None,
         }
     }
+
+    /// Continue paging from a cursor of a page already fetched.
+    ///
+    /// A `next_cursor` yields the items after that page in order. A
+    /// `prev_cursor` yields the items before it in reverse order, nearest
+    /// first, ending at the first item of the collection.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let earlier = CursorPager::resume(query, prev_cursor, |q| async move {
+    ///     client.list_users(q).await
+    /// });
+    /// ```
+    pub fn resume(base_query: ODataQuery, cursor: String, fetcher: F) -> Self {
+        Self {
+            cursor: Some(cursor),
+            ..Self::new(base_query, fetcher)
+        }
+    }
 }
 
 impl<T, E, F, Fut> Stream for CursorPager<T, E, F, Fut>
@@ -158,13 +189,17 @@ where
                     Poll::Ready(Ok(page)) => {
                         this.current_fetch.set(None);
 
-                        this.next_cursor.clone_from(&page.page_info.next_cursor);
+                        *this.cursor = continuation(&page, *this.backward);
 
-                        if this.next_cursor.is_none() {
+                        if this.cursor.is_none() {
                             *this.done = true;
                         }
 
-                        this.buffer.extend(page.items);
+                        if *this.backward {
+                            this.buffer.extend(page.items.into_iter().rev());
+                        } else {
+                            this.buffer.extend(page.items);
+                        }
 
                         continue;
                     }
@@ -180,8 +215,9 @@ where
             // Allocation strategy: base_query cloned once per page fetch.
             // Filter AST is built once in QueryBuilder and reused here.
             let mut query = this.base_query.clone();
-            if let Some(cursor_str) = this.next_cursor.as_ref() {
+            if let Some(cursor_str) = this.cursor.as_ref() {
                 if let Ok(cursor) = modkit_odata::CursorV1::decode(cursor_str) {
+                    *this.backward = cursor.d == "bwd";
                     query = query.with_cursor(cursor);
                 } else {
                     *this.done = true;
@@ -212,7 +248,8 @@ pin_project! {
         Fut: Future<Output = Result<Page<T>, E>>,
     {
         base_query: ODataQuery,
-        next_cursor: Option<String>,
+        cursor: Option<String>,
+        backward: bool,
         done: bool,
         fetcher: F,
         #[pin]
@@ -242,12 +279,25 @@ where
     pub fn new(base_query: ODataQuery, fetcher: F) -> Self {
         Self {
             base_query,
-            next_cursor: None,
+            cursor: None,
+            backward: false,
             done: false,
             fetcher,
             current_fetch: None,
         }
     }
+
+    /// Continue paging from a cursor of a page already fetched.
+    ///
+    /// A `next_cursor` yields the following pages; a `prev_cursor` yields the
+    /// preceding pages, nearest first. Items within each page keep the
+    /// requested order.
+    pub fn resume(base_query: ODataQuery, cursor: String, fetcher: F) -> Self {
+        Self {
+            cursor: Some(cursor),
+            ..Self::new(base_query, fetcher)
+        }
+    }
 }
 
 impl<T, E, F, Fut> Stream for PagesPager<T, E, F, Fut>
@@ -270,9 +320,9 @@ where
                     Poll::Ready(Ok(page)) => {
                         this.current_fetch.set(None);
 
-                        this.next_cursor.clone_from(&page.page_info.next_cursor);
+                        *this.cursor = continuation(&page, *this.backward);
 
-                        if this.next_cursor.is_none() {
+                        if this.cursor.is_none() {
                             *this.done = true;
                         }
 
@@ -290,8 +340,9 @@ where
             // Allocation strategy: base_query cloned once per page fetch.
             // Filter AST is built once in QueryBuilder and reused here.
             let mut query = this.base_query.clone();
-            if let Some(cursor_str) = this.next_cursor.as_ref() {
+            if let Some(cursor_str) = this.cursor.as_ref() {
                 if let Ok(cursor) = modkit_odata::CursorV1::decode(cursor_str) {
+                    *this.backward = cursor.d == "bwd";
                     query = query.with_cursor(cursor);
                 } else {
                     *this.done = true;
@@ -308,6 +359,15 @@ where
     }
 }
 
+/// Cursor of the page to fetch after `page` in the paging direction.
+fn continuation<T>(page: &Page<T>, backward: bool) -> Option<String> {
+    if backward {
+        page.page_info.prev_cursor.clone()
+    } else {
+        page.page_info.next_cursor.clone()
+    }
+}
+
 #[cfg(test)]
 #[path = "pager_tests.rs"]
 mod pager_tests;
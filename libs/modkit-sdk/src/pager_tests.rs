@@ -68,6 +68,7 @@ async fn test_cursor_pager_two_pages() {
             next_cursor: Some(encoded_cursor.clone()),
             prev_cursor: None,
             limit: 2,
+            total_count: None,
         },
     );
 
@@ -86,6 +87,7 @@ async fn test_cursor_pager_two_pages() {
             next_cursor: None,
             prev_cursor: Some(encoded_cursor),
             limit: 2,
+            total_count: None,
         },
     );
 
@@ -117,6 +119,7 @@ async fn test_cursor_pager_empty_page() {
             next_cursor: None,
             prev_cursor: None,
             limit: 10,
+            total_count: None,
         },
     );
 
@@ -155,6 +158,7 @@ async fn test_cursor_pager_error_propagation() {
             next_cursor: Some(encoded_cursor),
             prev_cursor: None,
             limit: 1,
+            total_count: None,
         },
     );
 
@@ -208,6 +212,7 @@ async fn test_pages_pager_two_pages() {
             next_cursor: Some(encoded_cursor.clone()),
             prev_cursor: None,
             limit: 2,
+            total_count: None,
         },
     );
 
@@ -220,6 +225,7 @@ async fn test_pages_pager_two_pages() {
             next_cursor: None,
             prev_cursor: Some(encoded_cursor),
             limit: 2,
+            total_count: None,
         },
     );
 
@@ -254,6 +260,7 @@ async fn test_pages_pager_single_page() {
             next_cursor: None,
             prev_cursor: None,
             limit: 10,
+            total_count: None,
         },
     );
 
@@ -282,6 +289,7 @@ async fn test_cursor_pager_invalid_cursor() {
             next_cursor: Some("invalid_cursor_string".to_owned()),
             prev_cursor: None,
             limit: 1,
+            total_count: None,
         },
     );
 
@@ -318,6 +326,7 @@ async fn test_pages_pager_invalid_cursor() {
             next_cursor: Some("invalid_cursor_string".to_owned()),
             prev_cursor: None,
             limit: 1,
+            total_count: None,
         },
     );
 
@@ -365,6 +374,7 @@ async fn test_pages_pager_error_propagation() {
             next_cursor: Some(encoded_cursor),
             prev_cursor: None,
             limit: 1,
+            total_count: None,
         },
     );
 
@@ -423,3 +433,109 @@ fn test_pages_pager_polls_new_future_immediately() {
     // If we don't poll immediately after installing the future, this would be 0.
     assert_eq!(polls.load(Ordering::SeqCst), 1);
 }
+
+fn users(ids: &[i32]) -> Vec<User> {
+    ids.iter()
+        .map(|&id| User {
+            id,
+            name: format!("user{id}"),
+        })
+        .collect()
+}
+
+fn bwd_cursor(key: i32) -> String {
+    modkit_odata::CursorV1 {
+        k: vec![key.to_string()],
+        o: modkit_odata::SortDir::Asc,
+        s: "+id".to_owned(),
+        f: None,
+        d: "bwd".to_owned(),
+    }
+    .encode()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_cursor_pager_resumes_backward() {
+    // Pages before item 5, as the server returns them (display order)
+    let page_before = Page::new(
+        users(&[3, 4]),
+        PageInfo {
+            next_cursor: Some("ignored".to_owned()),
+            prev_cursor: Some(bwd_cursor(3)),
+            limit: 2,
+            total_count: None,
+        },
+    );
+    let first_page = Page::new(
+        users(&[1, 2]),
+        PageInfo {
+            next_cursor: Some("ignored".to_owned()),
+            prev_cursor: None,
+            limit: 2,
+            total_count: None,
+        },
+    );
+
+    let fetcher = FakeFetcher::new(vec![page_before, first_page]);
+    let directions = Arc::new(Mutex::new(Vec::new()));
+    let seen = directions.clone();
+    let query = ODataQuery::new().with_limit(2);
+
+    let pager = CursorPager::resume(query, bwd_cursor(5), move |q| {
+        seen.lock()
+            .unwrap()
+            .push(q.cursor.as_ref().map(|c| c.d.clone()));
+        let fetcher = fetcher.clone();
+        async move { fetcher.fetch(q) }
+    });
+
+    let items: Vec<Result<User, PagerError<FakeError>>> = pager.collect().await;
+    let ids: Vec<i32> = items.into_iter().map(|r| r.unwrap().id).collect();
+
+    // Nearest first, ending at the start of the collection
+    assert_eq!(ids, [4, 3, 2, 1]);
+    assert_eq!(
+        *directions.lock().unwrap(),
+        [Some("bwd".to_owned()), Some("bwd".to_owned())]
+    );
+}
+
+#[tokio::test]
+async fn test_pages_pager_resumes_backward() {
+    let page_before = Page::new(
+        users(&[3, 4]),
+        PageInfo {
+            next_cursor: Some("ignored".to_owned()),
+            prev_cursor: Some(bwd_cursor(3)),
+            limit: 2,
+            total_count: None,
+        },
+    );
+    let first_page = Page::new(
+        users(&[1, 2]),
+        PageInfo {
+            next_cursor: Some("ignored".to_owned()),
+            prev_cursor: None,
+            limit: 2,
+            total_count: None,
+        },
+    );
+
+    let fetcher = FakeFetcher::new(vec![page_before, first_page]);
+    let query = ODataQuery::new().with_limit(2);
+
+    let pager = PagesPager::resume(query, bwd_cursor(5), move |q| {
+        let fetcher = fetcher.clone();
+        async move { fetcher.fetch(q) }
+    });
+
+    let pages: Vec<Result<Page<User>, PagerError<FakeError>>> = pager.collect().await;
+    let ids: Vec<Vec<i32>> = pages
+        .into_iter()
+        .map(|p| p.unwrap().items.iter().map(|u| u.id).collect())
+        .collect();
+
+    // Preceding pages nearest first; each page keeps its own order
+    assert_eq!(ids, [vec![3, 4], vec![1, 2]]);
+}
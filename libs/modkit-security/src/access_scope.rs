@@ -598,20 +598,94 @@ impl<'a> Iterator for ScopeFilterValuesIter<'a> {
     }
 }
 
+/// Value returned in place of a [`FieldMask::Redact`]ed text field.
+pub const REDACTED_VALUE: &str = "***";
+
+/// How a field the caller may not read is returned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FieldMask {
+    /// Returned as `NULL`.
+    #[default]
+    Hide,
+    /// Returned as [`REDACTED_VALUE`] for text fields, `NULL` otherwise.
+    Redact,
+}
+
+/// A field masked on the rows reached through one access path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaskedField {
+    field: String,
+    mask: FieldMask,
+}
+
+impl MaskedField {
+    /// Mask `field` (a resource field, as the API names it) with `mask`.
+    #[must_use]
+    pub fn new(field: impl Into<String>, mask: FieldMask) -> Self {
+        Self {
+            field: field.into(),
+            mask,
+        }
+    }
+
+    /// The masked field name.
+    #[inline]
+    #[must_use]
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// How the field is masked.
+    #[inline]
+    #[must_use]
+    pub fn mask(&self) -> FieldMask {
+        self.mask
+    }
+}
+
 /// A conjunction (AND) of scope filters — one access path.
 ///
 /// All filters within a constraint must match simultaneously for a row
-/// to be accessible via this path.
+/// to be accessible via this path. Rows reached through the path have its
+/// masked fields withheld, unless another path also reaches them without
+/// masking the field.
 #[derive(Clone, Debug, PartialEq)]
 pub struct ScopeConstraint {
     filters: Vec<ScopeFilter>,
+    masked_fields: Vec<MaskedField>,
 }
 
 impl ScopeConstraint {
     /// Create a new scope constraint from a list of filters.
     #[must_use]
     pub fn new(filters: Vec<ScopeFilter>) -> Self {
-        Self { filters }
+        Self {
+            filters,
+            masked_fields: Vec::new(),
+        }
+    }
+
+    /// Mask the given fields on rows reached through this constraint.
+    #[must_use]
+    pub fn with_masked_fields(mut self, masked_fields: Vec<MaskedField>) -> Self {
+        self.masked_fields = masked_fields;
+        self
+    }
+
+    /// The fields masked on rows reached through this constraint.
+    #[inline]
+    #[must_use]
+    pub fn masked_fields(&self) -> &[MaskedField] {
+        &self.masked_fields
+    }
+
+    /// How `field` is masked by this constraint, if at all.
+    #[must_use]
+    pub fn mask_for(&self, field: &str) -> Option<FieldMask> {
+        self.masked_fields
+            .iter()
+            .find(|m| m.field == field)
+            .map(MaskedField::mask)
     }
 
     /// The filters in this constraint (AND-ed together).
@@ -758,6 +832,36 @@ impl AccessScope {
         !self.unconstrained && self.constraints.is_empty()
     }
 
+    /// Returns `true` if any constraint masks a field.
+    #[must_use]
+    pub fn has_masked_fields(&self) -> bool {
+        self.constraints.iter().any(|c| !c.masked_fields.is_empty())
+    }
+
+    /// Returns `true` if any constraint masks `field`, i.e. some rows the
+    /// caller may read withhold it.
+    #[must_use]
+    pub fn masks_field(&self, field: &str) -> bool {
+        self.constraints.iter().any(|c| c.mask_for(field).is_some())
+    }
+
+    /// Fields masked on every access path, i.e. never readable by the caller.
+    ///
+    /// Fields masked on only some paths are readable on the rows reached
+    /// through the others, so they are not listed.
+    #[must_use]
+    pub fn hidden_fields(&self) -> Vec<&str> {
+        let Some((first, rest)) = self.constraints.split_first() else {
+            return Vec::new();
+        };
+        first
+            .masked_fields
+            .iter()
+            .map(MaskedField::field)
+            .filter(|field| rest.iter().all(|c| c.mask_for(field).is_some()))
+            .collect()
+    }
+
     /// Collect all values for a given property across all constraints.
     #[must_use]
     pub fn all_values_for(&self, property: &str) -> Vec<&ScopeValue> {
@@ -892,7 +996,9 @@ impl AccessScope {
                 if owner_filters.is_empty() {
                     let mut filters = c.filters().to_vec();
                     filters.push(owner_filter.clone());
-                    return Some(ScopeConstraint::new(filters));
+                    return Some(
+                        ScopeConstraint::new(filters).with_masked_fields(c.masked_fields.clone()),
+                    );
                 }
 
                 // Intersection semantics: ALL owner_id predicates must contain
@@ -917,7 +1023,7 @@ impl AccessScope {
                     .cloned()
                     .collect();
                 filters.push(owner_filter.clone());
-                Some(ScopeConstraint::new(filters))
+                Some(ScopeConstraint::new(filters).with_masked_fields(c.masked_fields.clone()))
             })
             .collect();
 
@@ -945,7 +1051,7 @@ impl AccessScope {
                 if kept.is_empty() {
                     None
                 } else {
-                    Some(ScopeConstraint::new(kept))
+                    Some(ScopeConstraint::new(kept).with_masked_fields(c.masked_fields.clone()))
                 }
             })
             .collect();
@@ -1363,4 +1469,47 @@ mod tests {
         )]));
         assert!(!scope.contains_uuid(pep_properties::OWNER_TENANT_ID, uid(T1)));
     }
+
+    // --- field masks ---
+
+    #[test]
+    fn hidden_fields_are_masked_on_every_path() {
+        let tenant = ScopeConstraint::new(vec![ScopeFilter::eq(
+            pep_properties::OWNER_TENANT_ID,
+            uid(T1),
+        )])
+        .with_masked_fields(vec![
+            MaskedField::new("email", FieldMask::Redact),
+            MaskedField::new("salary", FieldMask::Hide),
+        ]);
+        let owner = ScopeConstraint::new(vec![ScopeFilter::eq(pep_properties::OWNER_ID, uid(T2))])
+            .with_masked_fields(vec![MaskedField::new("salary", FieldMask::Hide)]);
+        let scope = AccessScope::from_constraints(vec![tenant.clone(), owner]);
+
+        assert!(scope.has_masked_fields());
+        assert_eq!(scope.hidden_fields(), ["salary"]);
+        assert!(scope.masks_field("email"));
+        assert!(!scope.masks_field("name"));
+        assert_eq!(tenant.mask_for("email"), Some(FieldMask::Redact));
+        assert_eq!(tenant.mask_for("name"), None);
+        assert!(AccessScope::allow_all().hidden_fields().is_empty());
+    }
+
+    #[test]
+    fn narrowing_keeps_field_masks() {
+        let masks = vec![MaskedField::new("email", FieldMask::Hide)];
+        let scope = AccessScope::single(
+            ScopeConstraint::new(vec![ScopeFilter::eq(
+                pep_properties::OWNER_TENANT_ID,
+                uid(T1),
+            )])
+            .with_masked_fields(masks.clone()),
+        );
+
+        assert_eq!(scope.tenant_only().constraints()[0].masked_fields(), masks);
+        assert_eq!(
+            scope.ensure_owner(uid(T2)).constraints()[0].masked_fields(),
+            masks
+        );
+    }
 }
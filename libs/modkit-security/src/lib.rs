@@ -6,9 +6,9 @@ pub mod context;
 pub mod prelude;
 
 pub use access_scope::{
-    AccessScope, EqScopeFilter, InGroupScopeFilter, InGroupSubtreeScopeFilter, InScopeFilter,
-    InTenantSubtreeScopeFilter, ScopeConstraint, ScopeFilter, ScopeValue, pep_properties,
-    rg_tables, tenant_tables,
+    AccessScope, EqScopeFilter, FieldMask, InGroupScopeFilter, InGroupSubtreeScopeFilter,
+    InScopeFilter, InTenantSubtreeScopeFilter, MaskedField, REDACTED_VALUE, ScopeConstraint,
+    ScopeFilter, ScopeValue, pep_properties, rg_tables, tenant_tables,
 };
 pub use context::{SecurityContext, SecurityContextBuildError};
 
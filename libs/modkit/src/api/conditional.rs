@@ -0,0 +1,369 @@
+//! Conditional requests (RFC 9110 §13) for lost-update protection.
+//!
+//! Handlers of versioned resources return an [`ETag`] next to the body on
+//! GET and read [`Preconditions`] on PUT/PATCH/DELETE, typically feeding
+//! [`Preconditions::expected_version`] into the secure ORM's
+//! `expect_version`. Failed preconditions map to the canonical
+//! `412 Precondition Failed` problem.
+//!
+//! `OperationBuilder::conditional` documents the headers, answers a
+//! matching `If-None-Match` on GET with `304 Not Modified`, and rejects a
+//! PUT/PATCH/DELETE without `If-Match` or `If-None-Match` with
+//! `428 Precondition Required`.
+//!
+//! ```rust,ignore
+//! async fn get_note(Path(id): Path<Uuid>, ...) -> ApiResult<impl IntoResponse> {
+//!     let note = svc.get(id).await?;
+//!     Ok((ETag::from_version(note.version), Json(note)))
+//! }
+//!
+//! async fn put_note(pre: Preconditions, ...) -> ApiResult<impl IntoResponse> {
+//!     let expected = pre.expected_version()?;
+//!     match svc.update(id, body, expected).await {
+//!         Err(DomainError::VersionConflict) => Err(Problem::precondition_failed(
+//!             IF_MATCH.as_str(),
+//!             "resource has been modified",
+//!         )),
+//!         ...
+//!     }
+//! }
+//! ```
+
+use std::convert::Infallible;
+use std::fmt;
+
+use axum::extract::{FromRequestParts, Request};
+use axum::http::header::{ETAG, IF_MATCH, IF_NONE_MATCH};
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, IntoResponseParts, Response, ResponseParts};
+use modkit_canonical_errors::Problem;
+
+/// Strong entity tag of a resource representation.
+///
+/// Serialized as a quoted header value, e.g. `"7"` for version 7.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ETag(String);
+
+impl ETag {
+    /// Entity tag with the given opaque value.
+    ///
+    /// Returns `None` if `opaque` contains characters not allowed in an
+    /// entity tag (`"`, whitespace, controls).
+    #[must_use]
+    pub fn new(opaque: impl Into<String>) -> Option<Self> {
+        let opaque = opaque.into();
+        opaque
+            .bytes()
+            .all(|b| b == 0x21 || (0x23..=0x7e).contains(&b) || b >= 0x80)
+            .then_some(Self(opaque))
+    }
+
+    /// Entity tag of a row version (`version_col`).
+    #[must_use]
+    pub fn from_version(version: i64) -> Self {
+        Self(version.to_string())
+    }
+
+    /// The row version this tag was made from, if any.
+    #[must_use]
+    pub fn version(&self) -> Option<i64> {
+        self.0.parse().ok()
+    }
+
+    /// The opaque value, without quotes.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\"", self.0)
+    }
+}
+
+impl IntoResponseParts for ETag {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        // Validated by construction.
+        if let Ok(value) = HeaderValue::from_str(&self.to_string()) {
+            res.headers_mut().insert(ETAG, value);
+        }
+        Ok(res)
+    }
+}
+
+/// Value of an `If-Match` / `If-None-Match` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TagList {
+    Any,
+    /// `(weak, tag)` pairs; unparsable members are dropped.
+    Tags(Vec<(bool, ETag)>),
+}
+
+impl TagList {
+    fn from_headers(headers: &HeaderMap, name: &axum::http::HeaderName) -> Option<Self> {
+        let mut tags = Vec::new();
+        let mut present = false;
+        for value in headers.get_all(name) {
+            present = true;
+            let Ok(value) = value.to_str() else { continue };
+            for member in value.split(',').map(str::trim) {
+                if member == "*" {
+                    return Some(Self::Any);
+                }
+                let (weak, quoted) = match member.strip_prefix("W/") {
+                    Some(rest) => (true, rest),
+                    None => (false, member),
+                };
+                if let Some(tag) = quoted
+                    .strip_prefix('"')
+                    .and_then(|s| s.strip_suffix('"'))
+                    .and_then(ETag::new)
+                {
+                    tags.push((weak, tag));
+                }
+            }
+        }
+        present.then_some(Self::Tags(tags))
+    }
+
+    /// Strong comparison: weak tags never match.
+    fn matches_strong(&self, current: &ETag) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Tags(tags) => tags.iter().any(|(weak, tag)| !weak && tag == current),
+        }
+    }
+
+    /// Weak comparison: the weak indicator is ignored.
+    fn matches_weak(&self, current: &ETag) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Tags(tags) => tags.iter().any(|(_, tag)| tag == current),
+        }
+    }
+}
+
+/// The `If-Match` and `If-None-Match` preconditions of a request.
+///
+/// Extract it in handlers of conditional operations; it never rejects.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Preconditions {
+    if_match: Option<TagList>,
+    if_none_match: Option<TagList>,
+}
+
+impl Preconditions {
+    /// Parse the precondition headers of a request.
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            if_match: TagList::from_headers(headers, &IF_MATCH),
+            if_none_match: TagList::from_headers(headers, &IF_NONE_MATCH),
+        }
+    }
+
+    /// Whether the request carries no precondition.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.if_match.is_none() && self.if_none_match.is_none()
+    }
+
+    /// Row version required by `If-Match`; `None` without `If-Match`.
+    ///
+    /// Pass it to the secure ORM so the check and the write are atomic.
+    ///
+    /// # Errors
+    ///
+    /// Returns the canonical `412 Precondition Failed` problem if `If-Match`
+    /// is anything but exactly one strong version tag (`*`, a weak tag,
+    /// several tags, or a tag that is not a version), so the write is never
+    /// made unconditionally.
+    #[allow(clippy::result_large_err)]
+    pub fn expected_version(&self) -> Result<Option<i64>, Problem> {
+        let Some(if_match) = &self.if_match else {
+            return Ok(None);
+        };
+        match if_match {
+            TagList::Tags(tags) => match tags.as_slice() {
+                [(false, tag)] => tag.version().map(Some),
+                _ => None,
+            },
+            TagList::Any => None,
+        }
+        .ok_or_else(|| {
+            Problem::precondition_failed(
+                IF_MATCH.as_str(),
+                "If-Match must name exactly one strong entity tag of the resource",
+            )
+        })
+    }
+
+    /// Evaluate the preconditions of a state-changing request against the
+    /// current representation (`None` if the resource does not exist).
+    ///
+    /// # Errors
+    ///
+    /// Returns the canonical `412 Precondition Failed` problem if `If-Match`
+    /// does not match or `If-None-Match` does.
+    #[allow(clippy::result_large_err)]
+    pub fn check(&self, current: Option<&ETag>) -> Result<(), Problem> {
+        if let Some(if_match) = &self.if_match
+            && !current.is_some_and(|etag| if_match.matches_strong(etag))
+        {
+            return Err(Problem::precondition_failed(
+                IF_MATCH.as_str(),
+                "resource has been modified or does not exist",
+            ));
+        }
+        if let Some(if_none_match) = &self.if_none_match
+            && current.is_some_and(|etag| if_none_match.matches_weak(etag))
+        {
+            return Err(Problem::precondition_failed(
+                IF_NONE_MATCH.as_str(),
+                "resource matches If-None-Match",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether a GET/HEAD of the representation tagged `current` can be
+    /// answered with `304 Not Modified`.
+    #[must_use]
+    pub fn is_not_modified(&self, current: &ETag) -> bool {
+        self.if_none_match
+            .as_ref()
+            .is_some_and(|tags| tags.matches_weak(current))
+    }
+}
+
+impl<S> FromRequestParts<S> for Preconditions
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    #[allow(clippy::manual_async_fn)]
+    fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> impl core::future::Future<Output = Result<Self, Self::Rejection>> + Send {
+        let preconditions = Self::from_headers(&parts.headers);
+        async move { Ok(preconditions) }
+    }
+}
+
+/// Route middleware rejecting a request without `If-Match` or
+/// `If-None-Match` with `428 Precondition Required`.
+pub(crate) async fn precondition_required_middleware(request: Request, next: Next) -> Response {
+    if Preconditions::from_headers(request.headers()).is_empty() {
+        return Problem::precondition_required(
+            IF_MATCH.as_str(),
+            "this operation requires If-Match (or If-None-Match: * to create)",
+        )
+        .into_response();
+    }
+    next.run(request).await
+}
+
+/// Route middleware answering a GET/HEAD whose `If-None-Match` matches the
+/// `ETag` of the handler's successful response with `304 Not Modified`.
+pub(crate) async fn not_modified_middleware(request: Request, next: Next) -> Response {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return next.run(request).await;
+    }
+    let preconditions = Preconditions::from_headers(request.headers());
+    let response = next.run(request).await;
+    if !response.status().is_success() {
+        return response;
+    }
+
+    let current = response
+        .headers()
+        .get(ETAG)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix('"')?.strip_suffix('"'))
+        .and_then(ETag::new);
+    match current {
+        Some(etag) if preconditions.is_not_modified(&etag) => {
+            (StatusCode::NOT_MODIFIED, etag, ()).into_response()
+        }
+        _ => response,
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(axum::http::HeaderName, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.append(name.clone(), HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn etag_round_trips_version() {
+        let etag = ETag::from_version(7);
+        assert_eq!(etag.to_string(), "\"7\"");
+        assert_eq!(etag.version(), Some(7));
+        assert!(ETag::new("has \"quote").is_none());
+        assert_eq!(ETag::new("abc").unwrap().version(), None);
+    }
+
+    #[test]
+    fn if_match_uses_strong_comparison() {
+        let current = ETag::from_version(3);
+        let pre = Preconditions::from_headers(&headers(&[(IF_MATCH, "\"2\", \"3\"")]));
+        assert!(pre.check(Some(&current)).is_ok());
+        assert!(pre.check(None).is_err());
+
+        let weak = Preconditions::from_headers(&headers(&[(IF_MATCH, "W/\"3\"")]));
+        let problem = weak.check(Some(&current)).unwrap_err();
+        assert_eq!(problem.status, 412);
+        assert_eq!(problem.context["violations"][0]["subject"], "if-match");
+    }
+
+    #[test]
+    fn single_if_match_tag_is_expected_version() {
+        let pre = Preconditions::from_headers(&headers(&[(IF_MATCH, "\"5\"")]));
+        assert_eq!(pre.expected_version().unwrap(), Some(5));
+        assert_eq!(Preconditions::default().expected_version().unwrap(), None);
+        assert!(Preconditions::default().is_empty());
+    }
+
+    #[test]
+    fn if_match_without_single_strong_version_is_412() {
+        for value in ["*", "W/\"5\"", "\"4\", \"5\"", "\"abc\"", "garbage"] {
+            let pre = Preconditions::from_headers(&headers(&[(IF_MATCH, value)]));
+            let problem = pre.expected_version().unwrap_err();
+            assert_eq!(problem.status, 412, "{value}");
+        }
+    }
+
+    #[test]
+    fn if_none_match_star_rejects_existing_resource() {
+        let pre = Preconditions::from_headers(&headers(&[(IF_NONE_MATCH, "*")]));
+        assert!(pre.check(None).is_ok());
+        assert!(pre.check(Some(&ETag::from_version(1))).is_err());
+    }
+
+    #[test]
+    fn if_none_match_uses_weak_comparison_for_reads() {
+        let pre = Preconditions::from_headers(&headers(&[
+            (IF_NONE_MATCH, "W/\"1\""),
+            (IF_NONE_MATCH, "\"4\""),
+        ]));
+        assert!(pre.is_not_modified(&ETag::from_version(1)));
+        assert!(pre.is_not_modified(&ETag::from_version(4)));
+        assert!(!pre.is_not_modified(&ETag::from_version(2)));
+    }
+}
@@ -6,10 +6,13 @@
 
 pub mod api_dto;
 pub mod canonical_trace;
+pub mod conditional;
 pub mod error_layer;
 pub mod odata;
 pub mod openapi_registry;
 pub mod operation_builder;
+#[cfg(feature = "preview-outbox")]
+pub mod outbox_admin;
 pub mod problem;
 pub mod response;
 pub mod select;
@@ -28,10 +31,10 @@ pub use operation_builder::{
     ResponseSpec, state,
 };
 pub use problem::{
-    APPLICATION_PROBLEM_JSON, Problem, ValidationError, bad_request, conflict, internal_error,
-    not_found,
+    APPLICATION_PROBLEM_JSON, Problem, ValidationError, bad_request, conflict, forbidden,
+    internal_error, not_found,
 };
-pub use select::{apply_select, page_to_projected_json, project_json};
+pub use select::{apply_select, ensure_selectable, page_to_projected_json, project_json};
 pub use trace_layer::{WithRequestContext, WithTraceContext};
 
 /// Prelude module that re-exports common API types and utilities for module authors
@@ -74,6 +77,9 @@ pub mod canonical_prelude {
     // docs — scheduled for deletion when the canonical error middleware lands.
     pub use super::canonical_trace::CanonicalProblemMigrationExt;
 
+    // Conditional requests (ETag / If-Match), which fail with canonical 412s
+    pub use super::conditional::{ETag, Preconditions};
+
     // Same response sugar / OData / axum re-exports as the legacy prelude
     pub use super::response::{JsonBody, JsonPage, created_json, no_content, ok_json};
     pub use super::select::apply_select;
@@ -21,6 +21,12 @@ pub struct ODataParams {
     pub select: Option<String>,
     pub limit: Option<u64>,
     pub cursor: Option<String>,
+    #[serde(rename = "$count")]
+    pub count: Option<bool>,
+    #[serde(rename = "$apply")]
+    pub apply: Option<String>,
+    #[serde(rename = "$search")]
+    pub search: Option<String>,
 }
 
 pub const MAX_FILTER_LEN: usize = 8 * 1024;
@@ -29,6 +35,8 @@ pub const MAX_ORDERBY_LEN: usize = 1024;
 pub const MAX_ORDER_FIELDS: usize = 10;
 pub const MAX_SELECT_LEN: usize = 2048;
 pub const MAX_SELECT_FIELDS: usize = 100;
+pub const MAX_APPLY_LEN: usize = 8 * 1024;
+pub const MAX_SEARCH_LEN: usize = 1024;
 
 /// Parse $select string into a list of field names.
 /// Format: "field1, field2, field3, ..."
@@ -135,7 +143,7 @@ pub fn parse_orderby(raw: &str) -> Result<ODataOrderBy, modkit_odata::Error> {
 }
 
 /// Extract and validate full `OData` query from request parts.
-/// - Parses $filter, $orderby, limit, cursor
+/// - Parses $filter, $orderby, $select, $count, $apply, $search, limit, cursor
 /// - Enforces budgets and validates formats
 /// - Returns unified `ODataQuery`
 ///
@@ -236,6 +244,34 @@ where
         query = query.with_select(fields);
     }
 
+    // Parse count; whether it is honored is decided by the endpoint's `ODataLimits`
+    if let Some(count) = params.count {
+        query = query.with_count(count);
+    }
+
+    // Parse apply; groupable fields are checked by the endpoint's `ODataLimits`
+    if let Some(raw_apply) = params.apply.as_ref() {
+        if raw_apply.len() > MAX_APPLY_LEN {
+            return Err(crate::api::bad_request("$apply too long"));
+        }
+        let apply = modkit_odata::parse_apply(raw_apply)
+            .map_err(|e| crate::api::odata::odata_error_to_problem(&e, parts.uri.path(), None))?;
+        query = query.with_apply(apply);
+    }
+
+    // Parse search; the cursor hash covers it so pages of different searches don't mix
+    if let Some(raw_search) = params.search.as_ref() {
+        if raw_search.len() > MAX_SEARCH_LEN {
+            return Err(crate::api::bad_request("$search too long"));
+        }
+        let search = modkit_odata::parse_search(raw_search)
+            .map_err(|e| crate::api::odata::odata_error_to_problem(&e, parts.uri.path(), None))?;
+        if let Some(hash) = modkit_odata::short_query_hash(query.filter(), Some(&search)) {
+            query = query.with_filter_hash(hash);
+        }
+        query = query.with_search(search);
+    }
+
     Ok(query)
 }
 
@@ -161,6 +161,66 @@ mod tests {
         assert!(query.cursor.is_none());
     }
 
+    #[tokio::test]
+    async fn test_extract_odata_query_count() {
+        let request = Request::builder().uri("/?%24count=true").body(()).unwrap();
+        let (mut parts, _body) = request.into_parts();
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+        assert!(query.count);
+
+        let request = Request::builder().uri("/?%24count=yes").body(()).unwrap();
+        let (mut parts, _body) = request.into_parts();
+        let problem = extract_odata_query(&mut parts, &()).await.unwrap_err();
+        assert_eq!(problem.status, http::StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_extract_odata_query_apply() {
+        let request = Request::builder()
+            .uri("/?%24apply=groupby((model),aggregate(%24count%20as%20n))")
+            .body(())
+            .unwrap();
+        let (mut parts, _body) = request.into_parts();
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+        let apply = query.apply.unwrap();
+        assert_eq!(apply.group_by, ["model"]);
+        assert_eq!(apply.aggregates[0].alias, "n");
+
+        let request = Request::builder()
+            .uri("/?%24apply=topcount(1,model)")
+            .body(())
+            .unwrap();
+        let (mut parts, _body) = request.into_parts();
+        let problem = extract_odata_query(&mut parts, &()).await.unwrap_err();
+        assert_eq!(problem.status, http::StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(problem.code.contains("invalid_apply"));
+    }
+
+    #[tokio::test]
+    async fn test_extract_odata_query_search() {
+        let request = Request::builder()
+            .uri("/?%24filter=score%20gt%201&%24search=budget%20%22q3%20report%22")
+            .body(())
+            .unwrap();
+        let (mut parts, _body) = request.into_parts();
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+        assert_eq!(query.search.as_ref().unwrap().terms.len(), 2);
+        // The cursor hash covers the search, not just the filter
+        assert_ne!(
+            query.filter_hash,
+            modkit_odata::short_filter_hash(query.filter())
+        );
+
+        let request = Request::builder()
+            .uri("/?%24search=a%20OR%20b")
+            .body(())
+            .unwrap();
+        let (mut parts, _body) = request.into_parts();
+        let problem = extract_odata_query(&mut parts, &()).await.unwrap_err();
+        assert_eq!(problem.status, http::StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(problem.code.contains("invalid_search"));
+    }
+
     #[tokio::test]
     async fn test_extract_odata_query_limit_zero_error() {
         let uri = "/?limit=0";
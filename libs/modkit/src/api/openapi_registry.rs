@@ -128,30 +128,7 @@ impl OpenApiRegistryImpl {
             }
 
             // Vendor extensions
-            let mut ext = utoipa::openapi::extensions::Extensions::default();
-
-            // Rate limit
-            if let Some(rl) = spec.rate_limit.as_ref() {
-                ext.insert("x-rate-limit-rps".to_owned(), serde_json::json!(rl.rps));
-                ext.insert("x-rate-limit-burst".to_owned(), serde_json::json!(rl.burst));
-                ext.insert(
-                    "x-in-flight-limit".to_owned(),
-                    serde_json::json!(rl.in_flight),
-                );
-            }
-
-            // Pagination
-            if let Some(pagination) = spec.vendor_extensions.x_odata_filter.as_ref()
-                && let Ok(value) = serde_json::to_value(pagination)
-            {
-                ext.insert("x-odata-filter".to_owned(), value);
-            }
-            if let Some(pagination) = spec.vendor_extensions.x_odata_orderby.as_ref()
-                && let Ok(value) = serde_json::to_value(pagination)
-            {
-                ext.insert("x-odata-orderby".to_owned(), value);
-            }
-
+            let ext = vendor_extensions(&spec);
             if !ext.is_empty() {
                 op = op.extensions(Some(ext));
             }
@@ -410,6 +387,42 @@ impl OpenApiRegistry for OpenApiRegistryImpl {
     }
 }
 
+/// `x-*` extensions of an operation: rate limits and the `OData` options.
+fn vendor_extensions(
+    spec: &operation_builder::OperationSpec,
+) -> utoipa::openapi::extensions::Extensions {
+    let mut ext = utoipa::openapi::extensions::Extensions::default();
+
+    // Rate limit
+    if let Some(rl) = spec.rate_limit.as_ref() {
+        ext.insert("x-rate-limit-rps".to_owned(), serde_json::json!(rl.rps));
+        ext.insert("x-rate-limit-burst".to_owned(), serde_json::json!(rl.burst));
+        ext.insert(
+            "x-in-flight-limit".to_owned(),
+            serde_json::json!(rl.in_flight),
+        );
+    }
+
+    // Pagination
+    if let Some(pagination) = spec.vendor_extensions.x_odata_filter.as_ref()
+        && let Ok(value) = serde_json::to_value(pagination)
+    {
+        ext.insert("x-odata-filter".to_owned(), value);
+    }
+    if let Some(pagination) = spec.vendor_extensions.x_odata_orderby.as_ref()
+        && let Ok(value) = serde_json::to_value(pagination)
+    {
+        ext.insert("x-odata-orderby".to_owned(), value);
+    }
+    if let Some(apply) = spec.vendor_extensions.x_odata_apply.as_ref()
+        && let Ok(value) = serde_json::to_value(apply)
+    {
+        ext.insert("x-odata-apply".to_owned(), value);
+    }
+
+    ext
+}
+
 /// Walk the finalized `OpenAPI` document and warn about dangling `$ref` targets.
 ///
 /// Scans the entire document (operations, request bodies, responses, and schemas)
@@ -721,6 +734,9 @@ mod tests {
         };
         spec.vendor_extensions.x_odata_filter = Some(filter);
         spec.vendor_extensions.x_odata_orderby = Some(order_by);
+        spec.vendor_extensions.x_odata_apply = Some(operation_builder::ODataPagination {
+            allowed_fields: vec!["age".to_owned()],
+        });
 
         registry.register_operation(&spec);
         let info = OpenApiInfo::default();
@@ -745,6 +761,11 @@ mod tests {
         let allowed_order = order_ext.get("allowedFields").unwrap().as_array().unwrap();
         assert!(allowed_order.iter().any(|v| v.as_str() == Some("name asc")));
         assert!(allowed_order.iter().any(|v| v.as_str() == Some("age desc")));
+
+        let apply_ext = op
+            .get("x-odata-apply")
+            .expect("x-odata-apply should be present");
+        assert_eq!(apply_ext["allowedFields"], serde_json::json!(["age"]));
     }
 
     /// Helper: build a minimal `OpenAPI` doc with the given component schemas.
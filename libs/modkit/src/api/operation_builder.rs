@@ -230,6 +230,9 @@ pub struct VendorExtensions {
     pub x_odata_filter: Option<ODataPagination<BTreeMap<String, Vec<String>>>>,
     #[serde(rename = "x-odata-orderby", skip_serializing_if = "Option::is_none")]
     pub x_odata_orderby: Option<ODataPagination<Vec<String>>>,
+    /// Fields `$apply` may group by.
+    #[serde(rename = "x-odata-apply", skip_serializing_if = "Option::is_none")]
+    pub x_odata_apply: Option<ODataPagination<Vec<String>>>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -273,6 +276,28 @@ pub trait OperationBuilderODataExt<S, H, R> {
     fn with_odata_orderby<T>(self) -> Self
     where
         T: modkit_odata::filter::FilterField;
+
+    /// Adds optional `$count` query parameter to `OpenAPI`.
+    ///
+    /// The handler must enable counting in its `ODataLimits`.
+    #[must_use]
+    fn with_odata_count(self) -> Self;
+
+    /// Adds optional `$apply` query parameter to `OpenAPI`, declaring the
+    /// fields it may group by.
+    ///
+    /// The handler must pass the same fields to `ODataLimits::with_apply`.
+    #[must_use]
+    fn with_odata_apply<T>(self, groupable: &[T]) -> Self
+    where
+        T: modkit_odata::filter::FilterField;
+
+    /// Adds optional `$search` query parameter to `OpenAPI`.
+    ///
+    /// The handler must enable search in its `ODataLimits` on an entity with
+    /// searchable columns; `relevance` becomes a valid `$orderby` field.
+    #[must_use]
+    fn with_odata_search(self) -> Self;
 }
 
 impl<S, H, R, A, L> OperationBuilderODataExt<S, H, R> for OperationBuilder<H, R, S, A, L>
@@ -377,6 +402,88 @@ where
         self.spec.vendor_extensions.x_odata_orderby = Some(order_by);
         self
     }
+
+    fn with_odata_count(mut self) -> Self {
+        self.spec.params.push(ParamSpec {
+            name: "$count".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(
+                "OData v4 count: `true` adds the total number of matching items to page_info"
+                    .to_owned(),
+            ),
+            param_type: "boolean".to_owned(),
+        });
+        self
+    }
+
+    fn with_odata_apply<T>(mut self, groupable: &[T]) -> Self
+    where
+        T: modkit_odata::filter::FilterField,
+    {
+        use modkit_odata::filter::FieldKind;
+        use std::fmt::Write as _;
+
+        let mut apply = self
+            .spec
+            .vendor_extensions
+            .x_odata_apply
+            .unwrap_or_default();
+
+        let mut description = "OData v4 aggregation: `filter(...)`, `groupby((...))` and \
+            `aggregate(... as alias)`; `$count` counts rows"
+            .to_owned();
+        description.push_str("\n\ngroupby:");
+        for field in groupable {
+            let name = field.name().to_owned();
+            _ = write!(description, "\n- {name}");
+            if !apply.allowed_fields.contains(&name) {
+                apply.allowed_fields.push(name);
+            }
+        }
+        description.push_str("\n\naggregate:");
+        for field in T::FIELDS {
+            let methods = match field.kind() {
+                FieldKind::I64 | FieldKind::F64 | FieldKind::Decimal => "sum|min|max|average",
+                FieldKind::String | FieldKind::DateTimeUtc | FieldKind::Date | FieldKind::Time => {
+                    "min|max"
+                }
+                FieldKind::Bool | FieldKind::Uuid => continue,
+            };
+            _ = write!(description, "\n- {}: {methods}", field.name());
+        }
+        self.spec.params.push(ParamSpec {
+            name: "$apply".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(description),
+            param_type: "string".to_owned(),
+        });
+        self.spec.vendor_extensions.x_odata_apply = Some(apply);
+        self
+    }
+
+    fn with_odata_search(mut self) -> Self {
+        if let Some(order_by) = self.spec.vendor_extensions.x_odata_orderby.as_mut() {
+            for key in ["relevance asc", "relevance desc"] {
+                if !order_by.allowed_fields.iter().any(|f| f == key) {
+                    order_by.allowed_fields.push(key.to_owned());
+                }
+            }
+        }
+        self.spec.params.push(ParamSpec {
+            name: "$search".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(
+                "OData v4 search: words and \"phrases\", all required; `NOT` or `-` \
+                 excludes a term. Order by `relevance desc` for best matches first"
+                    .to_owned(),
+            ),
+            param_type: "string".to_owned(),
+        });
+        self
+    }
 }
 
 // Re-export from openapi_registry for backward compatibility
@@ -1574,6 +1681,88 @@ where
     }
 }
 
+// -------------------------------------------------------------------------------------------------
+// Conditional requests — needs the handler (route layer) and a first response
+// -------------------------------------------------------------------------------------------------
+impl<S, A, L> OperationBuilder<Present, Present, S, A, L>
+where
+    S: Clone + Send + Sync + 'static,
+    A: AuthState,
+    L: LicenseState,
+{
+    /// Support conditional requests (RFC 9110 §13) on this operation.
+    ///
+    /// - GET: documents `If-None-Match` and `304 Not Modified`, and answers
+    ///   with 304 when `If-None-Match` matches the `ETag` the handler returned.
+    /// - PUT/PATCH/DELETE: requires `If-Match` or `If-None-Match`, answering
+    ///   requests without either with the `428 Precondition Required`
+    ///   problem, and documents both headers and the `412 Precondition
+    ///   Failed` problem; the handler evaluates them through
+    ///   [`Preconditions`](crate::api::conditional::Preconditions).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use axum::Router;
+    /// # use http::StatusCode;
+    /// # use modkit::api::{
+    /// #     openapi_registry::OpenApiRegistryImpl,
+    /// #     operation_builder::OperationBuilder,
+    /// # };
+    /// # async fn get_note() -> &'static str { "note" }
+    /// # let registry = OpenApiRegistryImpl::new();
+    /// # let router: Router<()> = Router::new();
+    /// let router = OperationBuilder::get("/notes/v1/notes/{id}")
+    ///     .public()
+    ///     .handler(get_note)
+    ///     .json_response(StatusCode::OK, "Note")
+    ///     .conditional(&registry)
+    ///     .register(router, &registry);
+    /// # let _ = router;
+    /// ```
+    pub fn conditional(mut self, registry: &dyn OpenApiRegistry) -> Self {
+        let header = |name: &str, description: &str| ParamSpec {
+            name: name.to_owned(),
+            location: ParamLocation::Header,
+            required: false,
+            description: Some(description.to_owned()),
+            param_type: "string".to_owned(),
+        };
+
+        if matches!(self.spec.method, Method::GET | Method::HEAD) {
+            self.spec.params.push(header(
+                "If-None-Match",
+                "Entity tags of cached representations",
+            ));
+            self.method_router = self.method_router.layer(axum::middleware::from_fn(
+                crate::api::conditional::not_modified_middleware,
+            ));
+            return self.no_content_response(http::StatusCode::NOT_MODIFIED, "Not Modified");
+        }
+
+        self.spec.params.push(header(
+            "If-Match",
+            "Only apply if the resource has this entity tag (required unless If-None-Match is set)",
+        ));
+        self.spec.params.push(header(
+            "If-None-Match",
+            "Only apply if the resource has none of these entity tags (`*`: does not exist)",
+        ));
+        self.method_router = self.method_router.layer(axum::middleware::from_fn(
+            crate::api::conditional::precondition_required_middleware,
+        ));
+        self.problem_response(
+            registry,
+            http::StatusCode::PRECONDITION_FAILED,
+            "Precondition Failed",
+        )
+        .problem_response(
+            registry,
+            http::StatusCode::PRECONDITION_REQUIRED,
+            "Precondition Required",
+        )
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 // Registration — only available when handler, response, AND auth are all set
 // -------------------------------------------------------------------------------------------------
@@ -0,0 +1,255 @@
+use chrono::{DateTime, Utc};
+use modkit_db::outbox::{
+    CategorySnapshot, DeadLetterMessage, DeadLetterScope, PartitionHealth, QueueHealth,
+};
+
+/// A dead letter without its payload.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct DeadLetterDto {
+    pub id: i64,
+    /// Queue of the partition; absent if the partition is no longer registered.
+    pub queue: Option<String>,
+    pub partition_id: i64,
+    pub seq: i64,
+    pub payload_type: String,
+    pub created_at: DateTime<Utc>,
+    pub failed_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub attempts: i16,
+    /// `pending`, `reprocessing`, `resolved`, or `discarded`.
+    pub status: String,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Claim expiry of a `reprocessing` dead letter.
+    pub deadline: Option<DateTime<Utc>>,
+}
+
+impl DeadLetterDto {
+    pub(super) fn new(msg: &DeadLetterMessage, queue: Option<String>) -> Self {
+        Self {
+            id: msg.id,
+            queue,
+            partition_id: msg.partition_id,
+            seq: msg.seq,
+            payload_type: msg.payload_type.clone(),
+            created_at: msg.created_at,
+            failed_at: msg.failed_at,
+            last_error: msg.last_error.clone(),
+            attempts: msg.attempts,
+            status: msg.status.to_string(),
+            completed_at: msg.completed_at,
+            deadline: msg.deadline,
+        }
+    }
+}
+
+/// A dead letter with its payload.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct DeadLetterDetailDto {
+    #[serde(flatten)]
+    pub dead_letter: DeadLetterDto,
+    pub payload_size: usize,
+    /// The payload as text; absent if it is not valid UTF-8.
+    pub payload_text: Option<String>,
+}
+
+impl DeadLetterDetailDto {
+    pub(super) fn new(msg: DeadLetterMessage, queue: Option<String>) -> Self {
+        let dead_letter = DeadLetterDto::new(&msg, queue);
+        let payload_size = msg.payload.len();
+        Self {
+            dead_letter,
+            payload_size,
+            payload_text: String::from_utf8(msg.payload).ok(),
+        }
+    }
+}
+
+/// Selects the `pending` dead letters to replay. Omitted fields match all.
+#[derive(Debug, Clone, Default)]
+#[modkit_macros::api_dto(request)]
+pub struct ReplayReq {
+    pub id: Option<i64>,
+    pub queue: Option<String>,
+    pub partition_id: Option<i64>,
+    pub payload_type: Option<String>,
+    /// Maximum number of dead letters to replay.
+    pub limit: Option<u32>,
+}
+
+impl From<ReplayReq> for DeadLetterScope {
+    fn from(req: ReplayReq) -> Self {
+        Self {
+            id: req.id,
+            partition_id: req.partition_id,
+            queue: req.queue,
+            payload_type: req.payload_type,
+            limit: req.limit,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct ReplayResponse {
+    /// Number of dead letters enqueued again.
+    pub requeued: u64,
+}
+
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request)]
+pub struct ResolveReq {
+    /// IDs of `pending` dead letters to mark resolved.
+    pub ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct ResolveResponse {
+    /// Number of dead letters marked resolved.
+    pub resolved: u64,
+}
+
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct QueueHealthDto {
+    pub queue: String,
+    /// Current partition generation; each repartition adds one.
+    pub generation: i16,
+    pub partitions: usize,
+    /// Partitions of earlier generations still holding messages.
+    pub draining_partitions: usize,
+    /// Sequenced messages not yet processed.
+    pub backlog: i64,
+    /// Messages waiting to be sequenced.
+    pub incoming: i64,
+    /// Age of the oldest unprocessed message in milliseconds.
+    pub max_lag_ms: Option<i64>,
+    /// Partitions whose processor is retrying a failed batch.
+    pub retrying_partitions: usize,
+    pub pending_dead_letters: u64,
+}
+
+impl From<QueueHealth> for QueueHealthDto {
+    fn from(q: QueueHealth) -> Self {
+        Self {
+            queue: q.queue,
+            generation: q.generation,
+            partitions: q.partitions,
+            draining_partitions: q.draining_partitions,
+            backlog: q.backlog,
+            incoming: q.incoming,
+            max_lag_ms: q.max_lag_ms,
+            retrying_partitions: q.retrying_partitions,
+            pending_dead_letters: q.pending_dead_letters,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct PartitionHealthDto {
+    pub partition_id: i64,
+    /// Index within the partition's generation.
+    pub partition: i16,
+    pub generation: i16,
+    /// Last assigned sequence number.
+    pub sequence: i64,
+    pub processed_seq: i64,
+    pub backlog: i64,
+    pub incoming: i64,
+    pub lag_ms: Option<i64>,
+    /// Consecutive failed attempts of the current batch.
+    pub attempts: i16,
+    pub last_error: Option<String>,
+}
+
+impl From<PartitionHealth> for PartitionHealthDto {
+    fn from(p: PartitionHealth) -> Self {
+        Self {
+            partition_id: p.partition_id,
+            partition: p.partition_index,
+            generation: p.generation,
+            sequence: p.sequence,
+            processed_seq: p.processed_seq,
+            backlog: p.backlog(),
+            incoming: p.incoming,
+            lag_ms: p.lag_ms,
+            attempts: p.attempts,
+            last_error: p.last_error,
+        }
+    }
+}
+
+/// Lifetime counters of one worker category.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct WorkerStatsDto {
+    /// `processor`, `sequencer`, or `vacuum`.
+    pub category: String,
+    pub workers: usize,
+    pub executions: u64,
+    pub failures: u64,
+    pub messages: u64,
+    pub avg_exec_us: u64,
+    pub max_exec_us: u64,
+}
+
+impl WorkerStatsDto {
+    pub(super) fn new(category: String, stats: &CategorySnapshot) -> Self {
+        let s = &stats.snapshot;
+        Self {
+            category,
+            workers: stats.workers,
+            executions: s.executions,
+            failures: s.failures,
+            messages: s.total_msgs,
+            avg_exec_us: s.avg_exec_us(),
+            max_exec_us: s.max_exec_us,
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use modkit_db::outbox::DeadLetterStatus;
+
+    fn dead_letter(payload: &[u8]) -> DeadLetterMessage {
+        DeadLetterMessage {
+            id: 7,
+            partition_id: 3,
+            seq: 11,
+            payload: payload.to_vec(),
+            payload_type: "application/json".to_owned(),
+            created_at: DateTime::default(),
+            failed_at: DateTime::default(),
+            last_error: Some("boom".to_owned()),
+            attempts: 1,
+            status: DeadLetterStatus::Reprocessing,
+            completed_at: None,
+            deadline: None,
+        }
+    }
+
+    #[test]
+    fn detail_includes_utf8_payload() {
+        let dto = DeadLetterDetailDto::new(dead_letter(b"{}"), Some("orders".to_owned()));
+        assert_eq!(dto.payload_size, 2);
+        assert_eq!(dto.payload_text.as_deref(), Some("{}"));
+        assert_eq!(dto.dead_letter.status, "reprocessing");
+
+        let json = serde_json::to_value(&dto).unwrap();
+        assert_eq!(json["id"], 7);
+        assert_eq!(json["queue"], "orders");
+    }
+
+    #[test]
+    fn detail_omits_binary_payload_text() {
+        let dto = DeadLetterDetailDto::new(dead_letter(&[0xff, 0xfe]), None);
+        assert_eq!(dto.payload_size, 2);
+        assert!(dto.payload_text.is_none());
+    }
+}
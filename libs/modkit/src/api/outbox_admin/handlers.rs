@@ -0,0 +1,305 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Extension;
+use axum::extract::{Path, State};
+use axum::http::Uri;
+use modkit_db::Db;
+use modkit_db::outbox::{DeadLetterFilter, DeadLetterScope, Outbox, OutboxError};
+use modkit_odata::filter::FilterError;
+use modkit_odata::{CursorV1, Error as ODataError, ODataQuery, Page, PageInfo, SortDir};
+use modkit_security::SecurityContext;
+
+use super::dto::{
+    DeadLetterDetailDto, DeadLetterDto, PartitionHealthDto, QueueHealthDto, ReplayReq,
+    ReplayResponse, ResolveReq, ResolveResponse, WorkerStatsDto,
+};
+use super::{OutboxAdminAction, OutboxAdminAuthorizer};
+use crate::api::odata::{OData, odata_error_to_problem};
+use crate::api::prelude::*;
+use crate::api::{bad_request, conflict, internal_error, not_found};
+
+/// Page size when the request has no `limit`.
+const DEFAULT_LIMIT: u64 = 50;
+/// Largest accepted `limit`.
+const MAX_LIMIT: u64 = 500;
+/// Largest number of IDs accepted by one resolve request.
+const MAX_RESOLVE_IDS: usize = 1000;
+/// Sort order of dead letter listings, as recorded in their cursors.
+const LIST_ORDER: &str = "-id";
+/// How long replay and resolve hold their claim. Both finish in one
+/// transaction, so this only matters if the request dies mid-way.
+const CLAIM_TIMEOUT: Duration = Duration::from_mins(5);
+
+#[derive(Clone)]
+pub(super) struct AdminState {
+    pub(super) db: Db,
+    pub(super) outbox: Arc<Outbox>,
+    pub(super) authorizer: Arc<dyn OutboxAdminAuthorizer>,
+}
+
+impl AdminState {
+    async fn authorize(&self, ctx: &SecurityContext, action: OutboxAdminAction) -> ApiResult<()> {
+        self.authorizer.authorize(ctx, action).await
+    }
+
+    fn dead_letter_queue(&self, partition_id: i64) -> Option<String> {
+        self.outbox.partition_to_queue(partition_id)
+    }
+}
+
+fn filter_error(e: &FilterError) -> Problem {
+    bad_request(format!("invalid $filter: {e}"))
+}
+
+fn outbox_error(e: &OutboxError) -> Problem {
+    if let OutboxError::PartitionNotRegistered(_) = e {
+        conflict(e.to_string())
+    } else {
+        tracing::error!(error = %e, "outbox admin: operation failed");
+        internal_error("outbox operation failed")
+    }
+}
+
+fn transaction_error(e: anyhow::Error) -> Problem {
+    match e.downcast::<OutboxError>() {
+        Ok(e) => outbox_error(&e),
+        Err(e) => {
+            tracing::error!(error = %e, "outbox admin: transaction failed");
+            internal_error("outbox operation failed")
+        }
+    }
+}
+
+fn db_error(e: &modkit_db::DbError) -> Problem {
+    tracing::error!(error = %e, "outbox admin: no database connection");
+    internal_error("database unavailable")
+}
+
+/// The dead letter ID a listing continues after, from the request cursor.
+fn cursor_before_id(query: &ODataQuery) -> Result<Option<i64>, ODataError> {
+    let Some(cursor) = &query.cursor else {
+        return Ok(None);
+    };
+    if cursor.s != LIST_ORDER {
+        return Err(ODataError::OrderMismatch);
+    }
+    if cursor.f != query.filter_hash {
+        return Err(ODataError::FilterMismatch);
+    }
+    match cursor.k.as_slice() {
+        [id] => id.parse().map(Some).map_err(|_| ODataError::InvalidCursor),
+        _ => Err(ODataError::InvalidCursor),
+    }
+}
+
+/// Cursor continuing a listing after the dead letter `last_id`.
+fn next_cursor(query: &ODataQuery, last_id: i64) -> serde_json::Result<String> {
+    CursorV1 {
+        k: vec![last_id.to_string()],
+        o: SortDir::Desc,
+        s: LIST_ORDER.to_owned(),
+        f: query.filter_hash.clone(),
+        d: "fwd".to_owned(),
+    }
+    .encode()
+}
+
+/// List dead letters matching the `$filter` (pending only by default),
+/// newest first. `next_cursor` continues the listing.
+pub(super) async fn list_dead_letters(
+    State(state): State<AdminState>,
+    Extension(ctx): Extension<SecurityContext>,
+    uri: Uri,
+    OData(query): OData,
+) -> ApiResult<JsonPage<DeadLetterDto>> {
+    state
+        .authorize(&ctx, OutboxAdminAction::ListDeadLetters)
+        .await?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let mut filter = match query.filter() {
+        Some(expr) => DeadLetterFilter::from_odata(expr).map_err(|e| filter_error(&e))?,
+        None => DeadLetterFilter::default(),
+    };
+    let before =
+        cursor_before_id(&query).map_err(|e| odata_error_to_problem(&e, uri.path(), None))?;
+    if let Some(before) = before {
+        filter = filter.before(before);
+    }
+    // One more row than the page tells whether another page follows.
+    // Clamped to MAX_LIMIT above, so the conversion cannot fail.
+    let filter = filter.limit(u32::try_from(limit + 1).unwrap_or(u32::MAX));
+
+    let conn = state.db.conn().map_err(|e| db_error(&e))?;
+    let mut messages = state
+        .outbox
+        .dead_letter_list(&conn, &filter)
+        .await
+        .map_err(|e| outbox_error(&e))?;
+    let page_len = usize::try_from(limit).unwrap_or(usize::MAX);
+    let next_cursor = if messages.len() > page_len {
+        messages.truncate(page_len);
+        let last = messages.last().map(|last| next_cursor(&query, last.id));
+        last.transpose().map_err(|e| {
+            tracing::error!(error = %e, "outbox admin: failed to encode cursor");
+            internal_error("failed to encode cursor")
+        })?
+    } else {
+        None
+    };
+    let items = messages
+        .iter()
+        .map(|msg| DeadLetterDto::new(msg, state.dead_letter_queue(msg.partition_id)))
+        .collect();
+
+    Ok(Json(Page::new(
+        items,
+        PageInfo {
+            next_cursor,
+            prev_cursor: None,
+            limit,
+            total_count: None,
+        },
+    )))
+}
+
+/// Get one dead letter, in any status, with its payload.
+pub(super) async fn get_dead_letter(
+    State(state): State<AdminState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Path(id): Path<i64>,
+) -> ApiResult<JsonBody<DeadLetterDetailDto>> {
+    state
+        .authorize(&ctx, OutboxAdminAction::ReadDeadLetter)
+        .await?;
+    let filter = DeadLetterFilter::default().any_status().id(id).limit(1);
+    let conn = state.db.conn().map_err(|e| db_error(&e))?;
+    let msg = state
+        .outbox
+        .dead_letter_list(&conn, &filter)
+        .await
+        .map_err(|e| outbox_error(&e))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| not_found(format!("dead letter {id} not found")))?;
+
+    let queue = state.dead_letter_queue(msg.partition_id);
+    Ok(Json(DeadLetterDetailDto::new(msg, queue)))
+}
+
+/// Enqueue matching pending dead letters again and mark them resolved.
+pub(super) async fn replay_dead_letters(
+    State(state): State<AdminState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Json(req): Json<ReplayReq>,
+) -> ApiResult<JsonBody<ReplayResponse>> {
+    state
+        .authorize(&ctx, OutboxAdminAction::ReplayDeadLetters)
+        .await?;
+    let scope = DeadLetterScope::from(req);
+    let outbox = Arc::clone(&state.outbox);
+    let (_, result) = state
+        .outbox
+        .transaction(state.db.clone(), move |tx| {
+            Box::pin(async move {
+                outbox
+                    .dead_letter_requeue(tx, &scope, CLAIM_TIMEOUT)
+                    .await
+                    .map_err(anyhow::Error::from)
+            })
+        })
+        .await;
+    let requeued = result.map_err(transaction_error)?;
+
+    Ok(Json(ReplayResponse { requeued }))
+}
+
+/// Mark pending dead letters resolved without processing them.
+pub(super) async fn resolve_dead_letters(
+    State(state): State<AdminState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Json(req): Json<ResolveReq>,
+) -> ApiResult<JsonBody<ResolveResponse>> {
+    state
+        .authorize(&ctx, OutboxAdminAction::ResolveDeadLetters)
+        .await?;
+    if req.ids.len() > MAX_RESOLVE_IDS {
+        return Err(bad_request(format!(
+            "at most {MAX_RESOLVE_IDS} ids can be resolved at once"
+        )));
+    }
+    let outbox = Arc::clone(&state.outbox);
+    let (_, result) = state
+        .outbox
+        .transaction(state.db.clone(), move |tx| {
+            Box::pin(async move {
+                // Resolve only applies to claimed rows, so claim each first.
+                let mut claimed = Vec::with_capacity(req.ids.len());
+                for id in req.ids {
+                    let scope = DeadLetterScope::default().id(id);
+                    let msgs = outbox.dead_letter_replay(tx, &scope, CLAIM_TIMEOUT).await?;
+                    claimed.extend(msgs.iter().map(|m| m.id));
+                }
+                Ok(outbox.dead_letter_resolve(tx, &claimed).await?)
+            })
+        })
+        .await;
+    let resolved = result.map_err(transaction_error)?;
+
+    Ok(Json(ResolveResponse { resolved }))
+}
+
+/// Backlog, lag, and dead-letter counts per queue.
+pub(super) async fn list_queues(
+    State(state): State<AdminState>,
+    Extension(ctx): Extension<SecurityContext>,
+) -> ApiResult<JsonBody<Vec<QueueHealthDto>>> {
+    state.authorize(&ctx, OutboxAdminAction::ReadHealth).await?;
+    let conn = state.db.conn().map_err(|e| db_error(&e))?;
+    let queues = state
+        .outbox
+        .queue_health(&conn)
+        .await
+        .map_err(|e| outbox_error(&e))?;
+
+    Ok(Json(queues.into_iter().map(QueueHealthDto::from).collect()))
+}
+
+/// Backlog, lag, and processor state of each partition of a queue.
+pub(super) async fn list_partitions(
+    State(state): State<AdminState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Path(queue): Path<String>,
+) -> ApiResult<JsonBody<Vec<PartitionHealthDto>>> {
+    state.authorize(&ctx, OutboxAdminAction::ReadHealth).await?;
+    let conn = state.db.conn().map_err(|e| db_error(&e))?;
+    let partitions: Vec<PartitionHealthDto> = state
+        .outbox
+        .queue_partition_health(&conn, &queue)
+        .await
+        .map_err(|e| outbox_error(&e))?
+        .into_iter()
+        .map(PartitionHealthDto::from)
+        .collect();
+    if partitions.is_empty() {
+        return Err(not_found(format!("queue '{queue}' not found")));
+    }
+
+    Ok(Json(partitions))
+}
+
+/// Lifetime counters of the outbox workers.
+pub(super) async fn worker_stats(
+    State(state): State<AdminState>,
+    Extension(ctx): Extension<SecurityContext>,
+) -> ApiResult<JsonBody<Vec<WorkerStatsDto>>> {
+    state.authorize(&ctx, OutboxAdminAction::ReadHealth).await?;
+    let counters = state
+        .outbox
+        .worker_stats()
+        .into_iter()
+        .map(|(category, stats)| WorkerStatsDto::new(category, &stats))
+        .collect();
+
+    Ok(Json(counters))
+}
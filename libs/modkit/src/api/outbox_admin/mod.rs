@@ -0,0 +1,281 @@
+//! REST admin API for a transactional outbox.
+//!
+//! [`OutboxAdminRoutes`] registers endpoints to list, inspect, replay, and
+//! resolve dead letters and to view queue backlog, partition lag, and worker
+//! stats. A module that owns an [`Outbox`] mounts them from its REST
+//! registration:
+//!
+//! ```ignore
+//! router = OutboxAdminRoutes::new("orders", db, Arc::clone(handle.outbox()), authorizer)
+//!     .register(router, openapi);
+//! ```
+//!
+//! | Method | Path | |
+//! |--------|------|-|
+//! | `GET`  | `/{prefix}/v1/outbox/dead-letters` | List, `$filter` over [`DeadLetterFilterField`] |
+//! | `GET`  | `/{prefix}/v1/outbox/dead-letters/{id}` | One dead letter with payload |
+//! | `POST` | `/{prefix}/v1/outbox/dead-letters/replay` | Enqueue again and resolve |
+//! | `POST` | `/{prefix}/v1/outbox/dead-letters/resolve` | Resolve without processing |
+//! | `GET`  | `/{prefix}/v1/outbox/queues` | Per-queue health |
+//! | `GET`  | `/{prefix}/v1/outbox/queues/{queue}/partitions` | Per-partition health |
+//! | `GET`  | `/{prefix}/v1/outbox/stats` | Worker counters |
+//!
+//! All endpoints require authentication, and every request is checked by the
+//! module's [`OutboxAdminAuthorizer`] before the outbox is touched: the
+//! endpoints expose message payloads and can re-run side effects.
+
+mod dto;
+mod handlers;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::Router;
+use http::StatusCode;
+use modkit_db::Db;
+use modkit_db::outbox::{DeadLetterFilterField, Outbox};
+use modkit_security::SecurityContext;
+
+use crate::api::openapi_registry::OpenApiRegistry;
+use crate::api::operation_builder::{OperationBuilder, OperationBuilderODataExt};
+use crate::api::problem::Problem;
+
+pub use dto::{
+    DeadLetterDetailDto, DeadLetterDto, PartitionHealthDto, QueueHealthDto, ReplayReq,
+    ReplayResponse, ResolveReq, ResolveResponse, WorkerStatsDto,
+};
+
+use handlers::AdminState;
+
+/// Operation an outbox admin request performs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxAdminAction {
+    /// List dead letters (without payloads).
+    ListDeadLetters,
+    /// Read one dead letter including its payload.
+    ReadDeadLetter,
+    /// Enqueue dead letters again.
+    ReplayDeadLetters,
+    /// Resolve dead letters without processing them.
+    ResolveDeadLetters,
+    /// Read queue and partition health and worker stats.
+    ReadHealth,
+}
+
+/// Decides whether the caller may perform an outbox admin operation.
+///
+/// Called at the start of every admin request. Return `Err` (typically
+/// `403 Forbidden`) to reject it.
+#[async_trait]
+pub trait OutboxAdminAuthorizer: Send + Sync {
+    async fn authorize(
+        &self,
+        ctx: &SecurityContext,
+        action: OutboxAdminAction,
+    ) -> Result<(), Problem>;
+}
+
+/// Builder for the outbox admin routes of one module.
+#[must_use]
+pub struct OutboxAdminRoutes {
+    prefix: String,
+    tag: String,
+    state: AdminState,
+}
+
+impl OutboxAdminRoutes {
+    /// Routes under `/{prefix}/v1/outbox` with operation IDs
+    /// `{prefix}.outbox_*` (dashes replaced by underscores). `authorizer`
+    /// checks every request.
+    pub fn new(
+        prefix: impl Into<String>,
+        db: Db,
+        outbox: Arc<Outbox>,
+        authorizer: Arc<dyn OutboxAdminAuthorizer>,
+    ) -> Self {
+        Self {
+            prefix: prefix.into(),
+            tag: "Outbox".to_owned(),
+            state: AdminState {
+                db,
+                outbox,
+                authorizer,
+            },
+        }
+    }
+
+    /// `OpenAPI` tag of the routes. Defaults to `Outbox`.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = tag.into();
+        self
+    }
+
+    /// Register the routes on `router`.
+    pub fn register(self, router: Router, openapi: &dyn OpenApiRegistry) -> Router {
+        let base = format!("/{}/v1/outbox", self.prefix);
+        let op = |name: &str| format!("{}.outbox_{name}", self.prefix.replace('-', "_"));
+        let tag = self.tag.as_str();
+        let mut admin: Router<AdminState> = Router::new();
+
+        admin = OperationBuilder::get(format!("{base}/dead-letters"))
+            .operation_id(op("list_dead_letters"))
+            .summary("List dead letters")
+            .description(
+                "List dead letters without payloads, newest first. `$filter` supports \
+                 `eq` on the listed fields combined with `and`; without a `status` term \
+                 only pending dead letters are returned. Pass `next_cursor` as `cursor` \
+                 with the same `$filter` for the next page.",
+            )
+            .tag(tag)
+            .authenticated()
+            .no_license_required()
+            .query_param_typed(
+                "limit",
+                false,
+                "Maximum number of dead letters to return",
+                "integer",
+            )
+            .query_param(
+                "cursor",
+                false,
+                "Cursor from a previous page's `next_cursor`",
+            )
+            .handler(handlers::list_dead_letters)
+            .json_response_with_schema::<modkit_odata::Page<DeadLetterDto>>(
+                openapi,
+                StatusCode::OK,
+                "Dead letters",
+            )
+            .with_odata_filter::<DeadLetterFilterField>()
+            .error_400(openapi)
+            .error_403(openapi)
+            .error_500(openapi)
+            .register(admin, openapi);
+
+        admin = OperationBuilder::get(format!("{base}/dead-letters/{{id}}"))
+            .operation_id(op("get_dead_letter"))
+            .summary("Get dead letter")
+            .description("Get a dead letter in any status, including its payload.")
+            .tag(tag)
+            .authenticated()
+            .no_license_required()
+            .path_param("id", "Dead letter ID")
+            .handler(handlers::get_dead_letter)
+            .json_response_with_schema::<DeadLetterDetailDto>(
+                openapi,
+                StatusCode::OK,
+                "Dead letter",
+            )
+            .error_404(openapi)
+            .error_403(openapi)
+            .error_500(openapi)
+            .register(admin, openapi);
+
+        admin = OperationBuilder::post(format!("{base}/dead-letters/replay"))
+            .operation_id(op("replay_dead_letters"))
+            .summary("Replay dead letters")
+            .description(
+                "Enqueue the matching pending dead letters again on their original \
+                 partition and mark them resolved, in one transaction. Headers are \
+                 not carried over.",
+            )
+            .tag(tag)
+            .authenticated()
+            .no_license_required()
+            .json_request::<ReplayReq>(openapi, "Dead letters to replay")
+            .handler(handlers::replay_dead_letters)
+            .json_response_with_schema::<ReplayResponse>(
+                openapi,
+                StatusCode::OK,
+                "Number of replayed dead letters",
+            )
+            .error_400(openapi)
+            .error_409(openapi)
+            .error_403(openapi)
+            .error_500(openapi)
+            .register(admin, openapi);
+
+        admin = OperationBuilder::post(format!("{base}/dead-letters/resolve"))
+            .operation_id(op("resolve_dead_letters"))
+            .summary("Resolve dead letters")
+            .description(
+                "Mark pending dead letters resolved without processing them. \
+                 IDs that are not pending are skipped.",
+            )
+            .tag(tag)
+            .authenticated()
+            .no_license_required()
+            .json_request::<ResolveReq>(openapi, "Dead letters to resolve")
+            .handler(handlers::resolve_dead_letters)
+            .json_response_with_schema::<ResolveResponse>(
+                openapi,
+                StatusCode::OK,
+                "Number of resolved dead letters",
+            )
+            .error_400(openapi)
+            .error_403(openapi)
+            .error_500(openapi)
+            .register(admin, openapi);
+
+        admin = OperationBuilder::get(format!("{base}/queues"))
+            .operation_id(op("list_queues"))
+            .summary("List queue health")
+            .description(
+                "Partition generation, backlog, incoming count, lag, retrying \
+                 partitions, and pending dead letters of each queue.",
+            )
+            .tag(tag)
+            .authenticated()
+            .no_license_required()
+            .handler(handlers::list_queues)
+            .json_response_with_schema::<Vec<QueueHealthDto>>(
+                openapi,
+                StatusCode::OK,
+                "Queue health",
+            )
+            .error_403(openapi)
+            .error_500(openapi)
+            .register(admin, openapi);
+
+        admin = OperationBuilder::get(format!("{base}/queues/{{queue}}/partitions"))
+            .operation_id(op("list_partitions"))
+            .summary("List partition health")
+            .description("Backlog, lag, and processor state of each partition of a queue.")
+            .tag(tag)
+            .authenticated()
+            .no_license_required()
+            .path_param("queue", "Queue name")
+            .handler(handlers::list_partitions)
+            .json_response_with_schema::<Vec<PartitionHealthDto>>(
+                openapi,
+                StatusCode::OK,
+                "Partition health",
+            )
+            .error_404(openapi)
+            .error_403(openapi)
+            .error_500(openapi)
+            .register(admin, openapi);
+
+        admin = OperationBuilder::get(format!("{base}/stats"))
+            .operation_id(op("worker_stats"))
+            .summary("Get worker stats")
+            .description(
+                "Lifetime counters per worker category. Empty when stats are \
+                 disabled on the outbox.",
+            )
+            .tag(tag)
+            .authenticated()
+            .no_license_required()
+            .handler(handlers::worker_stats)
+            .json_response_with_schema::<Vec<WorkerStatsDto>>(
+                openapi,
+                StatusCode::OK,
+                "Worker stats",
+            )
+            .error_403(openapi)
+            .error_500(openapi)
+            .register(admin, openapi);
+
+        router.merge(admin.with_state(self.state))
+    }
+}
@@ -109,6 +109,26 @@ pub fn apply_select<T: serde::Serialize>(value: T, selected_fields: Option<&[Str
     }
 }
 
+/// Refuse a `$select` naming a field the caller can never read.
+///
+/// `hidden_fields` are the fields masked on every access path of the caller's
+/// scope (`AccessScope::hidden_fields()`), which would always come back empty.
+/// Fields masked on only some rows may be selected; they are returned masked
+/// on those rows. Matching is case-insensitive, like [`project_json`], and a
+/// nested selector (`address.city`) is refused when its top-level field is
+/// hidden. `OPager` applies the same check to `ODataQuery::select`.
+///
+/// # Errors
+///
+/// Returns a 403 `Problem` naming the first selected hidden field.
+#[allow(clippy::result_large_err)]
+pub fn ensure_selectable(
+    selected_fields: Option<&[String]>,
+    hidden_fields: &[&str],
+) -> Result<(), crate::api::problem::Problem> {
+    modkit_odata::ensure_selectable(selected_fields, hidden_fields).map_err(Into::into)
+}
+
 /// Convert a page of items to a page of projected JSON values.
 ///
 /// This is a convenience function that combines serialization and projection
@@ -257,4 +277,21 @@ mod tests {
         assert_eq!(result.get("id").and_then(|v| v.as_str()), Some("123"));
         assert_eq!(result.get("name").and_then(|v| v.as_str()), Some("John"));
     }
+
+    #[test]
+    fn test_ensure_selectable_refuses_hidden_fields() {
+        let hidden = ["salary"];
+
+        assert!(ensure_selectable(None, &hidden).is_ok());
+        let visible = vec!["id".to_owned(), "email".to_owned()];
+        assert!(ensure_selectable(Some(&visible), &hidden).is_ok());
+
+        for selected in [
+            vec!["id".to_owned(), "Salary".to_owned()],
+            vec!["salary.base".to_owned()],
+        ] {
+            let problem = ensure_selectable(Some(&selected), &hidden).unwrap_err();
+            assert_eq!(problem.status, http::StatusCode::FORBIDDEN);
+        }
+    }
 }
@@ -2002,6 +2002,7 @@ logging:
                 file: None,
                 path: None,
                 server: None,
+                replicas: None,
             },
         );
 
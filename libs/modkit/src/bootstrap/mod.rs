@@ -32,6 +32,6 @@ pub use config::{
 pub use oop::{OopRunOptions, run_oop_with_options};
 
 mod run;
-pub use run::{run_migrate, run_server};
+pub use run::{MigrateCommand, run_migrate, run_migrate_command, run_server};
 
 pub use crypto::{CryptoProviderError, init_crypto_provider};
@@ -350,6 +350,7 @@ mod database_merge {
                     max_lifetime: None,
                     test_before_acquire: None,
                 }),
+                replicas: None,
             },
         );
         GlobalDatabaseConfig {
@@ -372,6 +373,7 @@ mod database_merge {
             path: None,
             params: None,
             pool: None,
+            replicas: None,
         }
     }
 
@@ -551,6 +553,7 @@ mod database_merge {
                 path: None,
                 params: None,
                 pool: None,
+                replicas: None,
             },
         );
         local_config.database = Some(GlobalDatabaseConfig {
@@ -130,6 +130,121 @@ pub async fn run_migrate(config: AppConfig) -> Result<()> {
     })?;
     tracing::info!("Starting migration mode...");
 
+    let host = migration_host(config)?;
+
+    // Run only the migration phases (pre-init + DB migration)
+    let result = host.run_migration_phases().await;
+
+    // Graceful shutdown - flush remaining telemetry
+    #[cfg(feature = "otel")]
+    tracing_shutdown();
+
+    result?;
+
+    tracing::info!("All migrations completed successfully");
+    Ok(())
+}
+
+/// Migration tooling that inspects or undoes migrations instead of applying them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrateCommand {
+    /// List applied and pending migrations of every module.
+    Status,
+    /// Print the SQL pending migrations would execute, without applying them.
+    DryRun,
+    /// Roll back the last `steps` applied migrations of `module` with their `down()`.
+    Rollback { module: String, steps: usize },
+}
+
+/// Run a [`MigrateCommand`] against the configured databases and exit.
+///
+/// Results are printed to stdout. Like [`run_migrate`], this runs the pre-init
+/// phase to wire runtime internals but never initializes or starts modules.
+///
+/// # Errors
+///
+/// Returns an error if there is no database configuration, module discovery
+/// or pre-init fails, the rollback target has no migrations, or a migration
+/// operation fails.
+///
+/// # Preconditions
+///
+/// The TLS crypto provider must be installed before calling this function
+/// (see [`super::init_crypto_provider`]).
+pub async fn run_migrate_command(config: AppConfig, command: MigrateCommand) -> Result<()> {
+    init_procedure(&config).map_err(|e| {
+        tracing::error!(error = %e, "Initialization failed");
+        e
+    })?;
+    tracing::info!(?command, "Starting migration tooling...");
+
+    let host = migration_host(config)?;
+    let result = run_migrate_command_on(host, command).await;
+
+    #[cfg(feature = "otel")]
+    tracing_shutdown();
+
+    result
+}
+
+async fn run_migrate_command_on(
+    host: crate::runtime::HostRuntime,
+    command: MigrateCommand,
+) -> Result<()> {
+    use modkit_db::migration_runner::{
+        get_migration_status, preview_pending_migrations, rollback_migrations,
+    };
+
+    let modules = host.into_module_migrations().await?;
+    match command {
+        MigrateCommand::Status => {
+            for m in &modules {
+                let status = get_migration_status(&m.db, m.module, &m.migrations).await?;
+                print_migrations(m.module, "applied", &status.applied);
+                print_migrations(m.module, "pending", &status.pending);
+            }
+        }
+        MigrateCommand::DryRun => {
+            for m in &modules {
+                for preview in preview_pending_migrations(&m.db, m.module, &m.migrations).await? {
+                    print_preview(m.module, &preview);
+                }
+            }
+        }
+        MigrateCommand::Rollback { module, steps } => {
+            let target = modules
+                .into_iter()
+                .find(|m| m.module == module)
+                .ok_or_else(|| anyhow::anyhow!("module '{module}' has no database migrations"))?;
+            let rolled_back =
+                rollback_migrations(&target.db, target.module, target.migrations, steps).await?;
+            print_migrations(target.module, "rolled back", &rolled_back);
+        }
+    }
+    Ok(())
+}
+
+#[allow(unknown_lints, de1301_no_print_macros)] // migration tooling reports to stdout
+fn print_migrations(module: &str, label: &str, names: &[String]) {
+    if names.is_empty() {
+        println!("{module}: no {label} migrations");
+    }
+    for name in names {
+        println!("{module}: {label} {name}");
+    }
+}
+
+#[allow(unknown_lints, de1301_no_print_macros)] // migration tooling reports to stdout
+fn print_preview(module: &str, preview: &modkit_db::migration_runner::MigrationPreview) {
+    println!("-- {module} / {}", preview.name);
+    for statement in &preview.statements {
+        println!("{statement};");
+    }
+}
+
+/// Build a host runtime for migration work: no `OoP` spawning, signals
+/// cancel the run.
+fn migration_host(config: AppConfig) -> Result<crate::runtime::HostRuntime> {
     // Generate process-level instance ID for this migration run
     let instance_id = uuid::Uuid::new_v4();
     tracing::info!(instance_id = %instance_id, "Generated migration instance ID");
@@ -155,8 +270,7 @@ pub async fn run_migrate(config: AppConfig) -> Result<()> {
         "Discovered modules for migration"
     );
 
-    // Create the host runtime
-    let host = crate::runtime::HostRuntime::new(
+    Ok(crate::runtime::HostRuntime::new(
         registry,
         Arc::new(config),
         db_options,
@@ -164,19 +278,7 @@ pub async fn run_migrate(config: AppConfig) -> Result<()> {
         cancel,
         instance_id,
         None, // No OoP spawning during migration
-    );
-
-    // Run only the migration phases (pre-init + DB migration)
-    let result = host.run_migration_phases().await;
-
-    // Graceful shutdown - flush remaining telemetry
-    #[cfg(feature = "otel")]
-    tracing_shutdown();
-
-    result?;
-
-    tracing::info!("All migrations completed successfully");
-    Ok(())
+    ))
 }
 
 fn resolve_db_options(config: &AppConfig) -> Result<DbOptions> {
@@ -63,8 +63,9 @@
 //! // Implement the declared capabilities...
 //! ```
 
-// When running tests, make ::modkit resolve to this crate so macros work
-#[cfg(test)]
+// Make ::modkit resolve to this crate so macros work in tests and in the
+// in-crate DTOs of `api::outbox_admin`
+#[cfg(any(test, feature = "preview-outbox"))]
 extern crate self as modkit;
 
 pub use anyhow::Result;
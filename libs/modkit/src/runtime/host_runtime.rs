@@ -52,6 +52,14 @@ pub enum RunMode {
     MigrateOnly,
 }
 
+/// A module's database and the migrations it provides, for migration tooling.
+#[cfg(feature = "db")]
+pub struct ModuleMigrations {
+    pub module: &'static str,
+    pub db: modkit_db::Db,
+    pub migrations: Vec<Box<dyn sea_orm_migration::MigrationTrait>>,
+}
+
 /// Environment variable name for passing directory endpoint to `OoP` modules.
 pub const MODKIT_DIRECTORY_ENDPOINT_ENV: &str = "MODKIT_DIRECTORY_ENDPOINT";
 
@@ -321,6 +329,36 @@ impl HostRuntime {
         Ok(())
     }
 
+    /// Run pre-init and collect the migrations of every module with a database,
+    /// system modules first, without applying them.
+    ///
+    /// Used by tooling that inspects or rolls back migrations instead of the DB phase.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if pre-init fails or a module's database cannot be resolved.
+    #[cfg(feature = "db")]
+    pub async fn into_module_migrations(self) -> anyhow::Result<Vec<ModuleMigrations>> {
+        self.run_pre_init_phase()?;
+
+        let mut out = Vec::new();
+        for entry in self.registry.modules_by_system_priority() {
+            let ctx = self.module_context(entry.name).await?;
+            let db_module = entry.caps.query::<DatabaseCap>();
+            if let Some((db, dbm)) = self
+                .db_migration_target(entry.name, &ctx, db_module)
+                .await?
+            {
+                out.push(ModuleMigrations {
+                    module: entry.name,
+                    db,
+                    migrations: dbm.migrations(),
+                });
+            }
+        }
+        Ok(out)
+    }
+
     /// INIT phase: initialize all modules in topological order.
     ///
     /// System modules initialize first, followed by user modules.
@@ -11,6 +11,8 @@ pub mod shutdown;
 mod tests;
 
 pub use grpc_installers::{GrpcInstallerData, GrpcInstallerStore, ModuleInstallers};
+#[cfg(feature = "db")]
+pub use host_runtime::ModuleMigrations;
 pub use host_runtime::{
     DEFAULT_SHUTDOWN_DEADLINE, DbOptions, HostRuntime, MODKIT_DIRECTORY_ENDPOINT_ENV,
     MODKIT_MODULE_CONFIG_ENV,
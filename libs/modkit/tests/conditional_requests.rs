@@ -0,0 +1,114 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use axum::{
+    Json, Router,
+    http::{Request, StatusCode, header},
+    response::IntoResponse,
+};
+use modkit::api::canonical_prelude::{ApiResult, Problem};
+use modkit::api::conditional::{ETag, Preconditions};
+use modkit::api::{OpenApiRegistryImpl, OperationBuilder};
+use tower::ServiceExt;
+
+const CURRENT_VERSION: i64 = 3;
+
+async fn get_note() -> impl IntoResponse {
+    (ETag::from_version(CURRENT_VERSION), Json("note"))
+}
+
+async fn put_note(pre: Preconditions) -> ApiResult<StatusCode> {
+    if pre.expected_version()? != Some(CURRENT_VERSION) {
+        return Err(Problem::precondition_failed(
+            "if-match",
+            "resource has been modified",
+        ));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn app() -> Router {
+    let registry = OpenApiRegistryImpl::new();
+    let router = OperationBuilder::get("/notes/v1/notes/{id}")
+        .public()
+        .handler(get_note)
+        .json_response(StatusCode::OK, "Note")
+        .conditional(&registry)
+        .register(Router::new(), &registry);
+    OperationBuilder::put("/notes/v1/notes/{id}")
+        .public()
+        .handler(put_note)
+        .no_content_response(StatusCode::NO_CONTENT, "Updated")
+        .conditional(&registry)
+        .register(router, &registry)
+}
+
+fn request(method: &str, header: Option<(header::HeaderName, &str)>) -> Request<axum::body::Body> {
+    let mut req = Request::builder().method(method).uri("/notes/v1/notes/1");
+    if let Some((name, value)) = header {
+        req = req.header(name, value);
+    }
+    req.body(axum::body::Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn get_emits_etag_and_honours_if_none_match() {
+    let resp = app().oneshot(request("GET", None)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::ETAG], "\"3\"");
+
+    let resp = app()
+        .oneshot(request("GET", Some((header::IF_NONE_MATCH, "\"3\""))))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(resp.headers()[header::ETAG], "\"3\"");
+
+    let resp = app()
+        .oneshot(request("GET", Some((header::IF_NONE_MATCH, "\"2\""))))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn put_with_stale_if_match_is_412_problem() {
+    let resp = app()
+        .oneshot(request("PUT", Some((header::IF_MATCH, "\"3\""))))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = app()
+        .oneshot(request("PUT", Some((header::IF_MATCH, "\"2\""))))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let problem: Problem = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem.status, 412);
+    assert!(problem.problem_type.ends_with("failed_precondition.v1~"));
+}
+
+#[tokio::test]
+async fn put_without_precondition_is_428_problem() {
+    let resp = app().oneshot(request("PUT", None)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::PRECONDITION_REQUIRED);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let problem: Problem = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem.status, 428);
+}
+
+#[tokio::test]
+async fn put_with_wildcard_or_weak_if_match_is_412() {
+    for value in ["*", "W/\"3\""] {
+        let resp = app()
+            .oneshot(request("PUT", Some((header::IF_MATCH, value))))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED, "{value}");
+    }
+}
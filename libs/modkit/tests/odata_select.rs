@@ -79,6 +79,9 @@ fn test_odata_params_with_select() {
         select: Some("id, name".to_owned()),
         limit: None,
         cursor: None,
+        count: None,
+        apply: None,
+        search: None,
     };
     assert_eq!(params.select, Some("id, name".to_owned()));
 }
@@ -475,6 +478,7 @@ fn test_page_to_projected_json() {
             next_cursor: Some("abc123".to_owned()),
             prev_cursor: None,
             limit: 10,
+            total_count: None,
         },
     };
 
@@ -531,6 +535,7 @@ fn test_page_to_projected_json_without_fields() {
             next_cursor: None,
             prev_cursor: None,
             limit: 20,
+            total_count: None,
         },
     };
 
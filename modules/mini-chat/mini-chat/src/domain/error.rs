@@ -182,6 +182,10 @@ impl From<ScopeError> for DomainError {
                 tracing::error!("invalid scope: {msg}");
                 DomainError::internal(msg)
             }
+            ScopeError::VersionConflict { expected } => DomainError::conflict(
+                "version_conflict",
+                format!("resource changed since version {expected}"),
+            ),
         }
     }
 }
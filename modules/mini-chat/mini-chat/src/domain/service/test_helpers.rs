@@ -101,7 +101,10 @@ impl AuthZResolverClient for MockAuthZResolver {
                 subject_id,
             )));
 
-            let constraints = vec![Constraint { predicates }];
+            let constraints = vec![Constraint {
+                predicates,
+                fields: vec![],
+            }];
 
             Ok(EvaluationResponse {
                 decision: true,
@@ -308,7 +311,10 @@ impl AuthZResolverClient for TenantOnlyAuthZResolver {
                 )));
             }
             // Deliberately omit OWNER_ID so `no_owner` entities pass secure_insert.
-            let constraints = vec![Constraint { predicates }];
+            let constraints = vec![Constraint {
+                predicates,
+                fields: vec![],
+            }];
             Ok(EvaluationResponse {
                 decision: true,
                 context: EvaluationResponseContext {
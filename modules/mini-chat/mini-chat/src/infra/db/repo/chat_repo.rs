@@ -6,7 +6,7 @@ use modkit_db::odata::{LimitCfg, paginate_odata};
 use modkit_db::secure::{
     DBRunner, SecureEntityExt, SecureUpdateExt, secure_insert, secure_update_with_scope,
 };
-use modkit_odata::{ODataQuery, Page, SortDir};
+use modkit_odata::{ODataLimits, ODataQuery, Page, SortDir};
 use modkit_security::AccessScope;
 use sea_orm::sea_query::{Expr, LockType};
 use sea_orm::{EntityTrait, FromQueryResult, QueryFilter, QuerySelect, Set};
@@ -74,6 +74,7 @@ impl crate::domain::repos::ChatRepository for ChatRepository {
             query,
             ("updated_at", SortDir::Desc),
             self.limit_cfg,
+            &ODataLimits::default(),
             Into::into,
         )
         .await
@@ -5,7 +5,7 @@ use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64;
 use modkit_db::odata::{LimitCfg, paginate_odata};
 use modkit_db::secure::{DBRunner, SecureEntityExt, SecureUpdateExt, secure_insert};
-use modkit_odata::{ODataQuery, Page, SortDir};
+use modkit_odata::{ODataLimits, ODataQuery, Page, SortDir};
 use modkit_security::AccessScope;
 use sea_orm::prelude::Expr;
 use sea_orm::{
@@ -204,6 +204,7 @@ impl crate::domain::repos::MessageRepository for MessageRepository {
             query,
             ("created_at", SortDir::Asc),
             self.limit_cfg,
+            &ODataLimits::default(),
             std::convert::identity,
         )
         .await
@@ -92,6 +92,7 @@ fn endpoint_for(entry: &ProviderEntry) -> oagw_sdk::Endpoint {
         scheme,
         host: entry.host.clone(),
         port,
+        weight: 1,
     }
 }
 
@@ -105,6 +106,8 @@ async fn create_upstream(
 
     let server = Server {
         endpoints: vec![endpoint_for(entry)],
+        load_balancing: None,
+        health_check: None,
     };
 
     let mut builder =
@@ -170,6 +173,8 @@ async fn create_tenant_upstream(
 
     let server = Server {
         endpoints: vec![ep],
+        load_balancing: None,
+        health_check: None,
     };
 
     let mut builder =
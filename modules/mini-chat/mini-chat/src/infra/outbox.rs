@@ -521,7 +521,7 @@ mod tests {
         MiniChatModelPolicyPluginError, PolicySnapshot, PolicyVersionInfo, PublishError,
         TurnAuditEvent, TurnDeleteAuditEvent, TurnEditAuditEvent, TurnRetryAuditEvent, UserLimits,
     };
-    use modkit_db::outbox::{LeasedMessageHandler, MessageResult, OutboxMessage};
+    use modkit_db::outbox::{LeasedMessageHandler, MessageHeaders, MessageResult, OutboxMessage};
     use std::sync::atomic::{AtomicU32, Ordering};
     use time::OffsetDateTime;
     use uuid::Uuid;
@@ -556,6 +556,7 @@ mod tests {
             seq: 42,
             payload,
             payload_type: "application/json".to_owned(),
+            headers: MessageHeaders::default(),
             created_at: chrono::Utc::now(),
             attempts: 0,
         }
@@ -505,6 +505,7 @@ impl LeasedMessageHandler for ChatCleanupHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use modkit_db::outbox::MessageHeaders;
 
     fn make_msg() -> OutboxMessage {
         OutboxMessage {
@@ -512,6 +513,7 @@ mod tests {
             seq: 1,
             payload: b"{}".to_vec(),
             payload_type: "application/json".to_owned(),
+            headers: MessageHeaders::default(),
             created_at: chrono::Utc::now(),
             attempts: 0i16,
         }
@@ -534,6 +536,7 @@ mod tests {
             seq: 1,
             payload: serde_json::to_vec(&event).unwrap(),
             payload_type: "application/json".to_owned(),
+            headers: MessageHeaders::default(),
             created_at: chrono::Utc::now(),
             attempts: 0i16,
         }
@@ -619,6 +622,7 @@ mod tests {
             seq: 1,
             payload: serde_json::to_vec(&event).unwrap(),
             payload_type: "application/json".to_owned(),
+            headers: MessageHeaders::default(),
             created_at: chrono::Utc::now(),
             attempts: 0i16,
         }
@@ -567,7 +567,7 @@ fn is_context_length_error(e: &crate::infra::llm::LlmProviderError) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use modkit_db::outbox::LeasedMessageHandler;
+    use modkit_db::outbox::{LeasedMessageHandler, MessageHeaders};
 
     // `rejects_invalid_payload` removed — superseded by `e2e_handler_rejects_invalid_payload`
     // which exercises the actual handler's Reject branch.
@@ -699,6 +699,7 @@ mod tests {
             seq: 1,
             payload: serde_json::to_vec(payload).unwrap(),
             payload_type: "application/json".to_owned(),
+            headers: MessageHeaders::default(),
             created_at: chrono::Utc::now(),
             attempts: 0i16,
         }
@@ -744,6 +745,7 @@ mod tests {
             seq: 1,
             payload: b"not json".to_vec(),
             payload_type: "application/json".to_owned(),
+            headers: MessageHeaders::default(),
             created_at: chrono::Utc::now(),
             attempts: 0i16,
         };
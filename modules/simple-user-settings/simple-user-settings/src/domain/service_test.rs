@@ -75,7 +75,10 @@ mod tests {
             Ok(EvaluationResponse {
                 decision: true,
                 context: EvaluationResponseContext {
-                    constraints: vec![Constraint { predicates }],
+                    constraints: vec![Constraint {
+                        predicates,
+                        fields: vec![],
+                    }],
                     ..Default::default()
                 },
             })
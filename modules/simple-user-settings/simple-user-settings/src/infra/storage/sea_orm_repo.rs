@@ -34,6 +34,9 @@ fn map_scope_error(e: ScopeError) -> DomainError {
         ScopeError::TenantNotInScope { tenant_id } => {
             DomainError::forbidden(format!("tenant {tenant_id} not in scope"))
         }
+        ScopeError::VersionConflict { expected } => {
+            DomainError::internal(format!("unexpected version conflict (expected {expected})"))
+        }
     }
 }
 
@@ -102,6 +102,7 @@ impl AuthZResolverClient for ConstraintBearingAuthZResolver {
                         pep_properties::OWNER_TENANT_ID,
                         self.owner_tenant_id,
                     ))],
+                    fields: vec![],
                 }],
                 deny_reason: None,
             },
@@ -148,6 +148,7 @@ impl ResourceGroupClient for FakeRgClient {
                     next_cursor: None,
                     prev_cursor: None,
                     limit: 1,
+                    total_count: None,
                 },
             )),
             FakeBehaviour::ListErr => {
@@ -170,5 +170,8 @@ fn map_scope_err(err: modkit_db::secure::ScopeError) -> DomainError {
         ScopeError::Denied(msg) => DomainError::internal(format!(
             "unexpected access denied in AM integrity-check loader: {msg}"
         )),
+        ScopeError::VersionConflict { expected } => DomainError::internal(format!(
+            "unexpected version conflict in AM integrity-check loader (expected {expected})"
+        )),
     }
 }
@@ -295,6 +295,9 @@ fn map_acquire_err(err: modkit_db::secure::ScopeError) -> DomainError {
         ScopeError::Denied(msg) => DomainError::internal(format!(
             "unexpected access denied in AM integrity-check lock: {msg}"
         )),
+        ScopeError::VersionConflict { expected } => DomainError::internal(format!(
+            "unexpected version conflict in AM integrity-check lock (expected {expected})"
+        )),
     }
 }
 
@@ -307,5 +310,8 @@ fn map_scope_err(err: modkit_db::secure::ScopeError) -> DomainError {
         ScopeError::Denied(msg) => DomainError::internal(format!(
             "unexpected access denied in AM integrity-check lock: {msg}"
         )),
+        ScopeError::VersionConflict { expected } => DomainError::internal(format!(
+            "unexpected version conflict in AM integrity-check lock (expected {expected})"
+        )),
     }
 }
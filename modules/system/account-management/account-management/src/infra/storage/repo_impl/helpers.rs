@@ -92,6 +92,10 @@ pub(super) fn map_scope_to_tx(err: ScopeError) -> TxError {
             diagnostic: format!("unexpected access denied in AM repo: {msg}"),
             cause: None,
         }),
+        ScopeError::VersionConflict { expected } => TxError::Domain(DomainError::Internal {
+            diagnostic: format!("unexpected version conflict in AM repo (expected {expected})"),
+            cause: None,
+        }),
     }
 }
 
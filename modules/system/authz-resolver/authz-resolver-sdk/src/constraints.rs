@@ -10,6 +10,13 @@
 //! - `InGroup` - group membership subquery: resource visible if member of any listed group
 //! - `InGroupSubtree` - group subtree subquery: resource visible if member of any descendant of listed ancestors
 //! - `InTenantSubtree` - tenant subtree subquery: resource visible if its tenant is a descendant of a single root tenant
+//!
+//! ## Field restrictions
+//!
+//! A constraint may also list `fields` the caller cannot read on the rows it
+//! grants. They are compiled into masked fields of the `ScopeConstraint`: the
+//! secure ORM returns them as `NULL` or a redacted value, and `$select`
+//! refuses fields that are restricted by every constraint.
 
 use crate::models::BarrierMode;
 use crate::pep::IntoPropertyValue;
@@ -26,6 +33,69 @@ pub struct Constraint {
     /// The predicates within this constraint. All predicates are `ANDed`:
     /// a resource matches this constraint only if ALL predicates are satisfied.
     pub predicates: Vec<Predicate>,
+    /// Fields withheld on the rows matched by this constraint. A field stays
+    /// readable on a row matched by another constraint that does not list it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldRestriction>,
+}
+
+impl Constraint {
+    /// Create a constraint with no field restrictions.
+    #[must_use]
+    pub fn new(predicates: Vec<Predicate>) -> Self {
+        Self {
+            predicates,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Withhold the given fields on the rows matched by this constraint.
+    #[must_use]
+    pub fn with_fields(mut self, fields: Vec<FieldRestriction>) -> Self {
+        self.fields = fields;
+        self
+    }
+}
+
+/// A resource field the caller may not read.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FieldRestriction {
+    /// Resource field name, as exposed by the API (e.g. `email`).
+    pub field: String,
+    /// How the field is withheld. Defaults to [`MaskMode::Hide`].
+    #[serde(default)]
+    pub mask: MaskMode,
+}
+
+impl FieldRestriction {
+    /// Hide `field` (returned as `null`).
+    #[must_use]
+    pub fn hide(field: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            mask: MaskMode::Hide,
+        }
+    }
+
+    /// Redact `field` (text returned as a placeholder, other types as `null`).
+    #[must_use]
+    pub fn redact(field: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            mask: MaskMode::Redact,
+        }
+    }
+}
+
+/// How a restricted field is withheld.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MaskMode {
+    /// Returned as `null`.
+    #[default]
+    Hide,
+    /// Text returned as a placeholder, other types as `null`.
+    Redact,
 }
 
 /// A predicate comparing a resource property to a value or subquery.
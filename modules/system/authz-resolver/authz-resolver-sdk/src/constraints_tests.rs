@@ -19,6 +19,7 @@ fn constraint_serialization_roundtrip() {
                 value: json!("33333333-3333-3333-3333-333333333333"),
             }),
         ],
+        fields: vec![],
     };
 
     let json_str = serde_json::to_string(&constraint).unwrap();
@@ -192,6 +193,7 @@ fn constraint_with_group_predicates_roundtrip() {
                 ],
             }),
         ],
+        fields: vec![],
     };
 
     let json_str = serde_json::to_string(&constraint).unwrap();
@@ -199,3 +201,23 @@ fn constraint_with_group_predicates_roundtrip() {
     assert_eq!(deserialized.predicates.len(), 2);
     assert!(matches!(deserialized.predicates[1], Predicate::InGroup(_)));
 }
+
+#[test]
+fn constraint_fields_default_and_roundtrip() {
+    let without: Constraint = serde_json::from_value(json!({ "predicates": [] })).unwrap();
+    assert!(without.fields.is_empty());
+    assert!(!serde_json::to_string(&without).unwrap().contains("fields"));
+
+    let with: Constraint = serde_json::from_value(json!({
+        "predicates": [],
+        "fields": [{ "field": "email", "mask": "redact" }, { "field": "ssn" }],
+    }))
+    .unwrap();
+    assert_eq!(
+        with.fields,
+        [
+            FieldRestriction::redact("email"),
+            FieldRestriction::hide("ssn")
+        ]
+    );
+}
@@ -53,8 +53,8 @@ pub mod plugin_api;
 // Re-export main types at crate root
 pub use api::AuthZResolverClient;
 pub use constraints::{
-    Constraint, EqPredicate, InGroupPredicate, InGroupSubtreePredicate, InPredicate,
-    InTenantSubtreePredicate, Predicate,
+    Constraint, EqPredicate, FieldRestriction, InGroupPredicate, InGroupSubtreePredicate,
+    InPredicate, InTenantSubtreePredicate, MaskMode, Predicate,
 };
 pub use error::AuthZResolverError;
 pub use gts::AuthZResolverPluginSpecV1;
@@ -33,10 +33,18 @@
 //! is rejected the same way. Its optional `descendant_status` list is
 //! converted element-wise (via `TenantStatus::as_smallint`) and bound to
 //! the SQL `descendant_status` column; an empty list disables the filter.
+//!
+//! ## Field restrictions
+//!
+//! A constraint's `fields` become the masked fields of its `ScopeConstraint`.
+//! Constraints without predicates only collapse to `allow_all()` when none of
+//! them restricts a field; otherwise they are kept so the masks survive.
 
-use modkit_security::{AccessScope, ScopeConstraint, ScopeFilter, ScopeValue};
+use modkit_security::{
+    AccessScope, FieldMask, MaskedField, ScopeConstraint, ScopeFilter, ScopeValue,
+};
 
-use crate::constraints::{Constraint, Predicate};
+use crate::constraints::{Constraint, FieldRestriction, MaskMode, Predicate};
 use crate::models::{BarrierMode, EvaluationResponse};
 
 /// Error during constraint compilation.
@@ -112,8 +120,12 @@ pub fn compile_to_access_scope(
         });
     }
 
-    // If all compiled constraints are empty (no filters), it means allow-all
-    if constraints.iter().all(ScopeConstraint::is_empty) {
+    // If all compiled constraints are empty (no filters) and mask nothing,
+    // it means allow-all
+    if constraints
+        .iter()
+        .all(|c| c.is_empty() && c.masked_fields().is_empty())
+    {
         return Ok(AccessScope::allow_all());
     }
 
@@ -226,7 +238,16 @@ fn compile_constraint(
         filters.push(filter);
     }
 
-    Ok(ScopeConstraint::new(filters))
+    let masked_fields = constraint.fields.iter().map(compile_field).collect();
+    Ok(ScopeConstraint::new(filters).with_masked_fields(masked_fields))
+}
+
+fn compile_field(restriction: &FieldRestriction) -> MaskedField {
+    let mask = match restriction.mask {
+        MaskMode::Hide => FieldMask::Hide,
+        MaskMode::Redact => FieldMask::Redact,
+    };
+    MaskedField::new(restriction.field.clone(), mask)
 }
 
 /// Convert a `serde_json::Value` to a UUID `ScopeValue`.
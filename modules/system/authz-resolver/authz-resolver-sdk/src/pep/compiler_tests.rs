@@ -44,6 +44,7 @@ fn no_require_constraints_with_constraints_compiles_them() {
                     property: pep_properties::OWNER_TENANT_ID.to_owned(),
                     value: jid(T1),
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -83,6 +84,7 @@ fn single_tenant_eq_constraint() {
                     property: pep_properties::OWNER_TENANT_ID.to_owned(),
                     value: jid(T1),
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -114,6 +116,7 @@ fn multiple_tenants_in_constraint() {
                     property: pep_properties::OWNER_TENANT_ID.to_owned(),
                     values: vec![jid(T1), jid(T2)],
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -136,6 +139,7 @@ fn resource_id_eq_constraint() {
                     property: pep_properties::RESOURCE_ID.to_owned(),
                     value: jid(R1),
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -168,12 +172,14 @@ fn multiple_constraints_produce_or_scope() {
                         property: pep_properties::OWNER_TENANT_ID.to_owned(),
                         values: vec![jid(T1)],
                     })],
+                    fields: vec![],
                 },
                 Constraint {
                     predicates: vec![Predicate::In(InPredicate {
                         property: pep_properties::OWNER_TENANT_ID.to_owned(),
                         values: vec![jid(T2)],
                     })],
+                    fields: vec![],
                 },
             ],
             ..Default::default()
@@ -198,6 +204,7 @@ fn unknown_predicate_fails_constraint() {
                     property: "unknown_property".to_owned(),
                     value: jid(T1),
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -222,6 +229,7 @@ fn mixed_known_and_unknown_constraints() {
                         property: "group_id".to_owned(),
                         value: jid(T1),
                     })],
+                    fields: vec![],
                 },
                 // This constraint is valid → succeeds
                 Constraint {
@@ -229,6 +237,7 @@ fn mixed_known_and_unknown_constraints() {
                         property: pep_properties::OWNER_TENANT_ID.to_owned(),
                         values: vec![jid(T2)],
                     })],
+                    fields: vec![],
                 },
             ],
             ..Default::default()
@@ -259,6 +268,7 @@ fn both_tenant_and_resource_in_single_constraint() {
                         value: jid(R1),
                     }),
                 ],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -295,12 +305,14 @@ fn mixed_shape_constraints_produce_or_scope() {
                             value: jid(R1),
                         }),
                     ],
+                    fields: vec![],
                 },
                 Constraint {
                     predicates: vec![Predicate::In(InPredicate {
                         property: pep_properties::OWNER_TENANT_ID.to_owned(),
                         values: vec![jid(T2)],
                     })],
+                    fields: vec![],
                 },
             ],
             ..Default::default()
@@ -329,6 +341,7 @@ fn in_group_predicate_compiles_to_in_group_filter() {
                     property: pep_properties::RESOURCE_ID.to_owned(),
                     group_ids: vec![jid(g1)],
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -357,6 +370,7 @@ fn in_group_subtree_predicate_compiles_to_subtree_filter() {
                     property: pep_properties::RESOURCE_ID.to_owned(),
                     ancestor_ids: vec![jid(ancestor)],
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -389,6 +403,7 @@ fn tenant_plus_in_group_in_single_constraint() {
                         group_ids: vec![jid(R1)],
                     }),
                 ],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -413,6 +428,7 @@ fn supported_properties_validation() {
                     property: pep_properties::RESOURCE_ID.to_owned(),
                     value: jid(R1),
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -437,6 +453,7 @@ fn empty_in_values_fails_constraint() {
                     property: pep_properties::OWNER_TENANT_ID.to_owned(),
                     values: vec![],
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -464,6 +481,7 @@ fn empty_in_group_ids_fails_constraint() {
                     property: pep_properties::RESOURCE_ID.to_owned(),
                     group_ids: vec![],
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -495,6 +513,7 @@ fn in_tenant_subtree_predicate_compiles_to_subtree_filter_respecting_barriers()
                     barrier_mode: BarrierMode::Respect,
                     descendant_status: Vec::new(),
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -528,6 +547,7 @@ fn in_tenant_subtree_predicate_ignore_barriers_propagates_to_filter() {
                     barrier_mode: BarrierMode::Ignore,
                     descendant_status: Vec::new(),
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -564,6 +584,7 @@ fn in_tenant_subtree_with_descendant_status_compiles_to_smallint_filter() {
                     barrier_mode: BarrierMode::Respect,
                     descendant_status: vec![TenantStatus::Active, TenantStatus::Suspended],
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -599,6 +620,7 @@ fn in_tenant_subtree_non_uuid_root_bool_fails_constraint() {
                     barrier_mode: BarrierMode::Respect,
                     descendant_status: Vec::new(),
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -629,6 +651,7 @@ fn in_tenant_subtree_non_uuid_root_int_fails_constraint() {
                     barrier_mode: BarrierMode::Respect,
                     descendant_status: Vec::new(),
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -659,6 +682,7 @@ fn in_tenant_subtree_non_uuid_root_string_fails_constraint() {
                     barrier_mode: BarrierMode::Respect,
                     descendant_status: Vec::new(),
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -689,6 +713,7 @@ fn in_tenant_subtree_non_uuid_root_null_fails_constraint() {
                     barrier_mode: BarrierMode::Respect,
                     descendant_status: Vec::new(),
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -719,6 +744,7 @@ fn in_tenant_subtree_non_uuid_root_array_fails_constraint() {
                     barrier_mode: BarrierMode::Respect,
                     descendant_status: Vec::new(),
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -746,6 +772,7 @@ fn empty_in_group_subtree_ancestor_ids_fails_constraint() {
                     property: pep_properties::RESOURCE_ID.to_owned(),
                     ancestor_ids: vec![],
                 })],
+                fields: vec![],
             }],
             ..Default::default()
         },
@@ -760,3 +787,54 @@ fn empty_in_group_subtree_ancestor_ids_fails_constraint() {
         "empty InGroupSubtree ancestor_ids must fail-closed, got: {result:?}"
     );
 }
+
+// === Field Restriction Tests ===
+
+#[test]
+fn field_restrictions_compile_to_masked_fields() {
+    use crate::constraints::FieldRestriction;
+    use modkit_security::FieldMask;
+
+    let response = EvaluationResponse {
+        decision: true,
+        context: EvaluationResponseContext {
+            constraints: vec![
+                Constraint::new(vec![Predicate::Eq(EqPredicate::new(
+                    pep_properties::OWNER_TENANT_ID,
+                    uuid(T1),
+                ))])
+                .with_fields(vec![
+                    FieldRestriction::redact("email"),
+                    FieldRestriction::hide("salary"),
+                ]),
+            ],
+            ..Default::default()
+        },
+    };
+
+    let scope = compile_to_access_scope(&response, true, DEFAULT_PROPS).unwrap();
+    let constraint = &scope.constraints()[0];
+    assert_eq!(constraint.mask_for("email"), Some(FieldMask::Redact));
+    assert_eq!(constraint.mask_for("salary"), Some(FieldMask::Hide));
+    assert_eq!(scope.hidden_fields(), ["email", "salary"]);
+}
+
+#[test]
+fn unfiltered_constraint_with_field_restrictions_is_not_allow_all() {
+    use crate::constraints::FieldRestriction;
+
+    let response = EvaluationResponse {
+        decision: true,
+        context: EvaluationResponseContext {
+            constraints: vec![
+                Constraint::new(vec![]).with_fields(vec![FieldRestriction::hide("ssn")]),
+            ],
+            ..Default::default()
+        },
+    };
+
+    let scope = compile_to_access_scope(&response, false, DEFAULT_PROPS).unwrap();
+    assert!(!scope.is_unconstrained());
+    assert!(!scope.is_deny_all());
+    assert_eq!(scope.hidden_fields(), ["ssn"]);
+}
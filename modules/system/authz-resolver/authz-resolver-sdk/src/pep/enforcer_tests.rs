@@ -63,6 +63,7 @@ impl AuthZResolverClient for AllowAllMock {
                         pep_properties::OWNER_TENANT_ID,
                         [tid],
                     ))],
+                    fields: vec![],
                 }],
                 ..Default::default()
             },
@@ -68,6 +68,7 @@ impl Service {
                         pep_properties::OWNER_TENANT_ID,
                         [tid],
                     ))],
+                    fields: vec![],
                 }],
                 ..Default::default()
             },
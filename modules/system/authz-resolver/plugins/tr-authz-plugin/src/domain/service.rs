@@ -370,7 +370,10 @@ impl Service {
         EvaluationResponse {
             decision: true,
             context: EvaluationResponseContext {
-                constraints: vec![Constraint { predicates }],
+                constraints: vec![Constraint {
+                    predicates,
+                    fields: vec![],
+                }],
                 ..Default::default()
             },
         }
@@ -393,7 +396,7 @@ impl Service {
         response: &mut EvaluationResponse,
         props: &std::collections::HashMap<String, serde_json::Value>,
     ) -> Result<(), ()> {
-        let Some(Constraint { predicates }) = response.context.constraints.get_mut(0) else {
+        let Some(Constraint { predicates, .. }) = response.context.constraints.get_mut(0) else {
             return Ok(());
         };
         if let Some(group_ids) = props.get("group_ids") {
@@ -9,14 +9,16 @@ pub mod ws;
 pub mod models;
 
 pub use models::{
-    AuthConfig, BudgetConfig, BudgetMode, BurstConfig, CorsConfig, CorsHttpMethod,
-    CreateRouteRequest, CreateRouteRequestBuilder, CreateUpstreamRequest,
-    CreateUpstreamRequestBuilder, Endpoint, GrpcMatch, HeadersConfig, HttpMatch, HttpMethod,
-    ListQuery, MatchRules, PassthroughMode, PathSuffixMode, PluginBinding, PluginsConfig,
+    AuthConfig, BackoffConfig, BudgetConfig, BudgetMode, BurstConfig, CacheConfig,
+    CircuitBreakerConfig, CircuitBreakerScope, CorsConfig, CorsHttpMethod, CreateRouteRequest,
+    CreateRouteRequestBuilder, CreateUpstreamRequest, CreateUpstreamRequestBuilder, Endpoint,
+    FailureConditions, FailureRateConfig, GrpcMatch, HashKey, HeadersConfig, HealthCheckConfig,
+    HealthProbe, HttpMatch, HttpMethod, ListQuery, LoadBalancing, MatchRules,
+    OutlierDetectionConfig, PassthroughMode, PathSuffixMode, PluginBinding, PluginsConfig,
     RateLimitAlgorithm, RateLimitConfig, RateLimitScope, RateLimitStrategy, RequestHeaderRules,
-    ResponseHeaderRules, Route, Scheme, Server, SharingMode, SustainedRate, UpdateRouteRequest,
-    UpdateRouteRequestBuilder, UpdateUpstreamRequest, UpdateUpstreamRequestBuilder, Upstream,
-    Window,
+    ResponseHeaderRules, RetryBudget, RetryConditions, RetryConfig, Route, Scheme, Server,
+    SharingMode, SustainedRate, TimeoutConfig, UpdateRouteRequest, UpdateRouteRequestBuilder,
+    UpdateUpstreamRequest, UpdateUpstreamRequestBuilder, Upstream, Window,
 };
 
 pub use api::ServiceGatewayClientV1;
@@ -42,6 +42,8 @@ pub struct Endpoint {
     pub scheme: Scheme,
     pub host: String,
     pub port: u16,
+    /// Relative share of traffic for weighted strategies (1–100, default 1).
+    pub weight: u32,
 }
 
 impl Endpoint {
@@ -65,6 +67,94 @@ fn is_standard_port(port: u16) -> bool {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Server {
     pub endpoints: Vec<Endpoint>,
+    /// Endpoint selection strategy. `None` means weighted round-robin.
+    pub load_balancing: Option<LoadBalancing>,
+    /// Active health checking and passive outlier ejection. `None` means a
+    /// TCP connect probe every 10 seconds and no ejection.
+    pub health_check: Option<HealthCheckConfig>,
+}
+
+// ---------------------------------------------------------------------------
+// LoadBalancing / HealthCheckConfig
+// ---------------------------------------------------------------------------
+
+/// How requests are spread over the healthy endpoints of an upstream.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum LoadBalancing {
+    /// Weighted round-robin.
+    #[default]
+    RoundRobin,
+    /// Fewest in-flight requests relative to endpoint weight.
+    LeastInFlight,
+    /// Consistent hashing so the same key keeps hitting the same endpoint.
+    ConsistentHash { key: HashKey },
+}
+
+/// Request attribute hashed by [`LoadBalancing::ConsistentHash`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashKey {
+    /// Value of the named request header.
+    Header(String),
+    /// The caller's tenant ID.
+    Tenant,
+}
+
+/// Active health checking of upstream endpoints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthCheckConfig {
+    pub probe: HealthProbe,
+    pub interval_secs: u32,
+    /// Per-probe connect/read timeout.
+    pub timeout_ms: u32,
+    /// Consecutive successful probes before an endpoint is marked healthy.
+    pub healthy_threshold: u32,
+    /// Consecutive failed probes before an endpoint is marked unhealthy.
+    pub unhealthy_threshold: u32,
+    pub outlier_detection: Option<OutlierDetectionConfig>,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            probe: HealthProbe::Tcp,
+            interval_secs: 10,
+            timeout_ms: 1_000,
+            healthy_threshold: 1,
+            unhealthy_threshold: 1,
+            outlier_detection: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthProbe {
+    /// TCP connect only.
+    Tcp,
+    /// `GET {path}`; healthy when the response status is in `expected_status`.
+    Http {
+        path: String,
+        expected_status: Vec<u16>,
+    },
+}
+
+/// Passive health: eject an endpoint after consecutive failed requests
+/// (5xx, timeouts, connection errors) seen on live traffic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlierDetectionConfig {
+    pub consecutive_failures: u32,
+    pub ejection_secs: u32,
+    /// Upper bound on the share of endpoints ejected at once.
+    pub max_ejection_percent: u32,
+}
+
+impl Default for OutlierDetectionConfig {
+    fn default() -> Self {
+        Self {
+            consecutive_failures: 5,
+            ejection_secs: 30,
+            max_ejection_percent: 50,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -209,6 +299,200 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+/// Circuit breaker configuration: fail fast while an upstream is unhealthy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitBreakerConfig {
+    pub sharing: SharingMode,
+    pub enabled: bool,
+    /// Consecutive failures that open the circuit.
+    pub failure_threshold: u32,
+    /// Optional failure-rate trigger over a rolling window.
+    pub failure_rate: Option<FailureRateConfig>,
+    /// Consecutive successful probes that close a half-open circuit.
+    pub success_threshold: u32,
+    /// Seconds the circuit stays open before probing the upstream again.
+    pub timeout_secs: u32,
+    /// Maximum probe requests in flight while half-open.
+    pub half_open_max_requests: u32,
+    pub failure_conditions: FailureConditions,
+    pub scope: CircuitBreakerScope,
+}
+
+/// Failure-rate trigger: open once `threshold` (0.0–1.0) of the requests in
+/// the last `window_secs` failed and at least `minimum_requests` were seen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureRateConfig {
+    pub threshold: f64,
+    pub minimum_requests: u32,
+    pub window_secs: u32,
+}
+
+/// Upstream outcomes counted as failures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureConditions {
+    /// Response status codes counted as failures.
+    pub status_codes: Vec<u16>,
+    /// Count timeouts as failures.
+    pub timeout: bool,
+    /// Count connection errors as failures.
+    pub connection_error: bool,
+}
+
+impl Default for FailureConditions {
+    fn default() -> Self {
+        Self {
+            status_codes: vec![500, 502, 503, 504],
+            timeout: true,
+            connection_error: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CircuitBreakerScope {
+    /// One breaker for the whole upstream.
+    #[default]
+    Global,
+    /// One breaker per endpoint.
+    PerEndpoint,
+}
+
+// ---------------------------------------------------------------------------
+// TimeoutConfig / RetryConfig
+// ---------------------------------------------------------------------------
+
+/// Route-level upstream timeouts in milliseconds. Unset fields fall back to
+/// the gateway-wide proxy timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TimeoutConfig {
+    /// TCP/TLS connect timeout, per attempt.
+    pub connect_ms: Option<u32>,
+    /// Maximum wait for a single read from the upstream, per attempt.
+    pub read_ms: Option<u32>,
+    /// Deadline for the response headers, spanning all retry attempts.
+    pub total_ms: Option<u32>,
+}
+
+/// Route-level retry policy. Only requests with a buffered body are retried;
+/// streaming request bodies cannot be replayed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryConfig {
+    /// Total attempts, including the first one (1–10).
+    pub max_attempts: u32,
+    pub backoff: BackoffConfig,
+    pub retry_on: RetryConditions,
+    /// Also retry non-idempotent methods (POST, PATCH).
+    pub retry_non_idempotent: bool,
+    pub budget: RetryBudget,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: BackoffConfig::default(),
+            retry_on: RetryConditions::default(),
+            retry_non_idempotent: false,
+            budget: RetryBudget::default(),
+        }
+    }
+}
+
+/// Exponential backoff between attempts, capped at `max_ms`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackoffConfig {
+    pub initial_ms: u32,
+    pub max_ms: u32,
+    pub multiplier: f64,
+    /// Randomize each delay within the upper half of its value.
+    pub jitter: bool,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        Self {
+            initial_ms: 100,
+            max_ms: 2_000,
+            multiplier: 2.0,
+            jitter: true,
+        }
+    }
+}
+
+/// Upstream outcomes that trigger a retry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryConditions {
+    pub status_codes: Vec<u16>,
+    pub timeout: bool,
+    pub connection_error: bool,
+}
+
+impl Default for RetryConditions {
+    fn default() -> Self {
+        Self {
+            status_codes: vec![502, 503, 504],
+            timeout: true,
+            connection_error: true,
+        }
+    }
+}
+
+/// Limits retries to `ratio` of the route's recent requests, with a floor of
+/// `min_retries_per_sec`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryBudget {
+    pub ratio: f64,
+    pub min_retries_per_sec: u32,
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self {
+            ratio: 0.2,
+            min_retries_per_sec: 10,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CacheConfig
+// ---------------------------------------------------------------------------
+
+/// Opt-in HTTP response cache for a route. Only GET responses the upstream
+/// marks cacheable (`Cache-Control`, validators, `Vary`) are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// `private` partitions entries per requesting tenant; `inherit` and
+    /// `enforce` let descendant tenants share the route owner's entries.
+    pub sharing: SharingMode,
+    pub enabled: bool,
+    /// Freshness for responses without an explicit lifetime. With 0 such
+    /// responses are stored only when they carry a validator and are
+    /// revalidated on every use.
+    pub default_ttl_secs: u32,
+    /// Larger responses are passed through without being stored.
+    pub max_object_bytes: u32,
+    /// Also cache responses obtained with per-user credentials; entries are
+    /// then keyed by subject as well.
+    pub allow_user_credentials: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            sharing: SharingMode::Private,
+            enabled: true,
+            default_ttl_secs: 0,
+            max_object_bytes: 1024 * 1024,
+            allow_user_credentials: false,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // PluginBinding / PluginsConfig
 // ---------------------------------------------------------------------------
@@ -317,6 +601,9 @@ pub struct Route {
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
     pub cors: Option<CorsConfig>,
+    pub timeouts: Option<TimeoutConfig>,
+    pub retry: Option<RetryConfig>,
+    pub cache: Option<CacheConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -337,6 +624,7 @@ pub struct Upstream {
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
     pub cors: Option<CorsConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub tags: Vec<String>,
 }
 
@@ -374,6 +662,7 @@ pub struct CreateUpstreamRequest {
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
     cors: Option<CorsConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
     tags: Vec<String>,
     enabled: bool,
 }
@@ -390,6 +679,7 @@ impl CreateUpstreamRequest {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         }
@@ -419,6 +709,9 @@ impl CreateUpstreamRequest {
     pub fn cors(&self) -> Option<&CorsConfig> {
         self.cors.as_ref()
     }
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreakerConfig> {
+        self.circuit_breaker.as_ref()
+    }
     pub fn tags(&self) -> &[String] {
         &self.tags
     }
@@ -436,6 +729,7 @@ pub struct CreateUpstreamRequestBuilder {
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
     cors: Option<CorsConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
     tags: Vec<String>,
     enabled: bool,
 }
@@ -465,6 +759,10 @@ impl CreateUpstreamRequestBuilder {
         self.cors = Some(cors);
         self
     }
+    pub fn circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = tags;
         self
@@ -483,6 +781,7 @@ impl CreateUpstreamRequestBuilder {
             plugins: self.plugins,
             rate_limit: self.rate_limit,
             cors: self.cors,
+            circuit_breaker: self.circuit_breaker,
             tags: self.tags,
             enabled: self.enabled,
         }
@@ -501,6 +800,7 @@ pub struct UpdateUpstreamRequest {
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
     cors: Option<CorsConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
     tags: Vec<String>,
     enabled: bool,
 }
@@ -517,6 +817,7 @@ impl UpdateUpstreamRequest {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         }
@@ -546,6 +847,9 @@ impl UpdateUpstreamRequest {
     pub fn cors(&self) -> Option<&CorsConfig> {
         self.cors.as_ref()
     }
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreakerConfig> {
+        self.circuit_breaker.as_ref()
+    }
     pub fn tags(&self) -> &[String] {
         &self.tags
     }
@@ -563,6 +867,7 @@ pub struct UpdateUpstreamRequestBuilder {
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
     cors: Option<CorsConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
     tags: Vec<String>,
     enabled: bool,
 }
@@ -592,6 +897,10 @@ impl UpdateUpstreamRequestBuilder {
         self.cors = Some(cors);
         self
     }
+    pub fn circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = tags;
         self
@@ -610,6 +919,7 @@ impl UpdateUpstreamRequestBuilder {
             plugins: self.plugins,
             rate_limit: self.rate_limit,
             cors: self.cors,
+            circuit_breaker: self.circuit_breaker,
             tags: self.tags,
             enabled: self.enabled,
         }
@@ -628,6 +938,9 @@ pub struct CreateRouteRequest {
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
     cors: Option<CorsConfig>,
+    timeouts: Option<TimeoutConfig>,
+    retry: Option<RetryConfig>,
+    cache: Option<CacheConfig>,
     tags: Vec<String>,
     priority: i32,
     enabled: bool,
@@ -642,6 +955,9 @@ impl CreateRouteRequest {
             plugins: None,
             rate_limit: None,
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -663,6 +979,15 @@ impl CreateRouteRequest {
     pub fn cors(&self) -> Option<&CorsConfig> {
         self.cors.as_ref()
     }
+    pub fn timeouts(&self) -> Option<&TimeoutConfig> {
+        self.timeouts.as_ref()
+    }
+    pub fn retry(&self) -> Option<&RetryConfig> {
+        self.retry.as_ref()
+    }
+    pub fn cache(&self) -> Option<&CacheConfig> {
+        self.cache.as_ref()
+    }
     pub fn tags(&self) -> &[String] {
         &self.tags
     }
@@ -680,6 +1005,9 @@ pub struct CreateRouteRequestBuilder {
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
     cors: Option<CorsConfig>,
+    timeouts: Option<TimeoutConfig>,
+    retry: Option<RetryConfig>,
+    cache: Option<CacheConfig>,
     tags: Vec<String>,
     priority: i32,
     enabled: bool,
@@ -698,6 +1026,18 @@ impl CreateRouteRequestBuilder {
         self.cors = Some(cors);
         self
     }
+    pub fn timeouts(mut self, timeouts: TimeoutConfig) -> Self {
+        self.timeouts = Some(timeouts);
+        self
+    }
+    pub fn retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = tags;
         self
@@ -717,6 +1057,9 @@ impl CreateRouteRequestBuilder {
             plugins: self.plugins,
             rate_limit: self.rate_limit,
             cors: self.cors,
+            timeouts: self.timeouts,
+            retry: self.retry,
+            cache: self.cache,
             tags: self.tags,
             priority: self.priority,
             enabled: self.enabled,
@@ -732,6 +1075,9 @@ pub struct UpdateRouteRequest {
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
     cors: Option<CorsConfig>,
+    timeouts: Option<TimeoutConfig>,
+    retry: Option<RetryConfig>,
+    cache: Option<CacheConfig>,
     tags: Vec<String>,
     priority: i32,
     enabled: bool,
@@ -745,6 +1091,9 @@ impl UpdateRouteRequest {
             plugins: None,
             rate_limit: None,
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -763,6 +1112,15 @@ impl UpdateRouteRequest {
     pub fn cors(&self) -> Option<&CorsConfig> {
         self.cors.as_ref()
     }
+    pub fn timeouts(&self) -> Option<&TimeoutConfig> {
+        self.timeouts.as_ref()
+    }
+    pub fn retry(&self) -> Option<&RetryConfig> {
+        self.retry.as_ref()
+    }
+    pub fn cache(&self) -> Option<&CacheConfig> {
+        self.cache.as_ref()
+    }
     pub fn tags(&self) -> &[String] {
         &self.tags
     }
@@ -779,6 +1137,9 @@ pub struct UpdateRouteRequestBuilder {
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
     cors: Option<CorsConfig>,
+    timeouts: Option<TimeoutConfig>,
+    retry: Option<RetryConfig>,
+    cache: Option<CacheConfig>,
     tags: Vec<String>,
     priority: i32,
     enabled: bool,
@@ -797,6 +1158,18 @@ impl UpdateRouteRequestBuilder {
         self.cors = Some(cors);
         self
     }
+    pub fn timeouts(mut self, timeouts: TimeoutConfig) -> Self {
+        self.timeouts = Some(timeouts);
+        self
+    }
+    pub fn retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = tags;
         self
@@ -815,6 +1188,9 @@ impl UpdateRouteRequestBuilder {
             plugins: self.plugins,
             rate_limit: self.rate_limit,
             cors: self.cors,
+            timeouts: self.timeouts,
+            retry: self.retry,
+            cache: self.cache,
             tags: self.tags,
             priority: self.priority,
             enabled: self.enabled,
@@ -836,6 +1212,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 443,
+            weight: 1,
         };
         assert_eq!(ep.alias_contribution(), "api.openai.com");
     }
@@ -846,6 +1223,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "example.com".into(),
             port: 80,
+            weight: 1,
         };
         assert_eq!(ep.alias_contribution(), "example.com");
     }
@@ -856,6 +1234,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 8443,
+            weight: 1,
         };
         assert_eq!(ep.alias_contribution(), "api.openai.com:8443");
     }
@@ -871,6 +1250,7 @@ mod tests {
             scheme: Scheme::Wss,
             host: "stream.example.com".into(),
             port: 9090,
+            weight: 1,
         };
         let ep2 = ep.clone();
         assert_eq!(ep, ep2);
@@ -894,6 +1274,9 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -929,6 +1312,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn default_circuit_breaker_conditions_count_5xx_timeouts_and_connection_errors() {
+        let conditions = FailureConditions::default();
+        assert_eq!(conditions.status_codes, vec![500, 502, 503, 504]);
+        assert!(conditions.timeout);
+        assert!(conditions.connection_error);
+        assert_eq!(CircuitBreakerScope::default(), CircuitBreakerScope::Global);
+    }
+
     #[test]
     fn default_window_is_second() {
         assert_eq!(Window::default(), Window::Second);
@@ -45,15 +45,111 @@ pub struct Endpoint {
     pub host: String,
     #[serde(default = "default_port")]
     pub port: u16,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
 }
 
 fn default_port() -> u16 {
     443
 }
 
+fn default_weight() -> u32 {
+    1
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct Server {
     pub endpoints: Vec<Endpoint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_balancing: Option<LoadBalancing>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheckConfig>,
+}
+
+// ---------------------------------------------------------------------------
+// LoadBalancing / HealthCheckConfig
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum LoadBalancing {
+    RoundRobin,
+    LeastInFlight,
+    ConsistentHash { hash_key: HashKey },
+}
+
+/// `{"header": "<name>"}` or `"tenant"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HashKey {
+    Header(String),
+    Tenant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct HealthCheckConfig {
+    #[serde(default)]
+    pub probe: HealthProbe,
+    #[serde(default = "default_health_interval_seconds")]
+    pub interval_seconds: u32,
+    #[serde(default = "default_health_timeout_ms")]
+    pub timeout_ms: u32,
+    #[serde(default = "default_health_threshold")]
+    pub healthy_threshold: u32,
+    #[serde(default = "default_health_threshold")]
+    pub unhealthy_threshold: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outlier_detection: Option<OutlierDetectionConfig>,
+}
+
+fn default_health_interval_seconds() -> u32 {
+    10
+}
+
+fn default_health_timeout_ms() -> u32 {
+    1_000
+}
+
+fn default_health_threshold() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HealthProbe {
+    #[default]
+    Tcp,
+    Http {
+        path: String,
+        #[serde(default = "default_health_expected_status")]
+        expected_status: Vec<u16>,
+    },
+}
+
+fn default_health_expected_status() -> Vec<u16> {
+    vec![200]
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct OutlierDetectionConfig {
+    #[serde(default = "default_outlier_consecutive_failures")]
+    pub consecutive_failures: u32,
+    #[serde(default = "default_outlier_ejection_seconds")]
+    pub ejection_seconds: u32,
+    #[serde(default = "default_outlier_max_ejection_percent")]
+    pub max_ejection_percent: u32,
+}
+
+fn default_outlier_consecutive_failures() -> u32 {
+    5
+}
+
+fn default_outlier_ejection_seconds() -> u32 {
+    30
+}
+
+fn default_outlier_max_ejection_percent() -> u32 {
+    50
 }
 
 // ---------------------------------------------------------------------------
@@ -213,6 +309,235 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CircuitBreakerConfig {
+    #[serde(default)]
+    pub sharing: SharingMode,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_rate: Option<FailureRateConfig>,
+    #[serde(default = "default_success_threshold")]
+    pub success_threshold: u32,
+    #[serde(default = "default_circuit_timeout_seconds")]
+    pub timeout_seconds: u32,
+    #[serde(default = "default_half_open_max_requests")]
+    pub half_open_max_requests: u32,
+    #[serde(default)]
+    pub failure_conditions: FailureConditions,
+    #[serde(default)]
+    pub scope: CircuitBreakerScope,
+}
+
+fn default_failure_threshold() -> u32 {
+    5
+}
+
+fn default_success_threshold() -> u32 {
+    3
+}
+
+fn default_circuit_timeout_seconds() -> u32 {
+    30
+}
+
+fn default_half_open_max_requests() -> u32 {
+    3
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FailureRateConfig {
+    pub threshold: f64,
+    #[serde(default = "default_minimum_requests")]
+    pub minimum_requests: u32,
+    #[serde(default = "default_failure_rate_window_seconds")]
+    pub window_seconds: u32,
+}
+
+fn default_minimum_requests() -> u32 {
+    20
+}
+
+fn default_failure_rate_window_seconds() -> u32 {
+    60
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FailureConditions {
+    #[serde(default = "default_failure_status_codes")]
+    pub status_codes: Vec<u16>,
+    #[serde(default = "default_true")]
+    pub timeout: bool,
+    #[serde(default = "default_true")]
+    pub connection_error: bool,
+}
+
+impl Default for FailureConditions {
+    fn default() -> Self {
+        Self {
+            status_codes: default_failure_status_codes(),
+            timeout: true,
+            connection_error: true,
+        }
+    }
+}
+
+fn default_failure_status_codes() -> Vec<u16> {
+    vec![500, 502, 503, 504]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitBreakerScope {
+    #[default]
+    Global,
+    PerEndpoint,
+}
+
+// ---------------------------------------------------------------------------
+// TimeoutConfig / RetryConfig
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TimeoutConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_ms: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_ms: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_ms: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RetryConfig {
+    #[serde(default = "default_retry_max_attempts")]
+    pub max_attempts: u32,
+    #[serde(default)]
+    pub backoff: BackoffConfig,
+    #[serde(default)]
+    pub retry_on: RetryConditions,
+    #[serde(default)]
+    pub retry_non_idempotent: bool,
+    #[serde(default)]
+    pub budget: RetryBudget,
+}
+
+fn default_retry_max_attempts() -> u32 {
+    3
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BackoffConfig {
+    #[serde(default = "default_backoff_initial_ms")]
+    pub initial_ms: u32,
+    #[serde(default = "default_backoff_max_ms")]
+    pub max_ms: u32,
+    #[serde(default = "default_backoff_multiplier")]
+    pub multiplier: f64,
+    #[serde(default = "default_true")]
+    pub jitter: bool,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        Self {
+            initial_ms: default_backoff_initial_ms(),
+            max_ms: default_backoff_max_ms(),
+            multiplier: default_backoff_multiplier(),
+            jitter: true,
+        }
+    }
+}
+
+fn default_backoff_initial_ms() -> u32 {
+    100
+}
+
+fn default_backoff_max_ms() -> u32 {
+    2_000
+}
+
+fn default_backoff_multiplier() -> f64 {
+    2.0
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RetryConditions {
+    #[serde(default = "default_retry_status_codes")]
+    pub status_codes: Vec<u16>,
+    #[serde(default = "default_true")]
+    pub timeout: bool,
+    #[serde(default = "default_true")]
+    pub connection_error: bool,
+}
+
+impl Default for RetryConditions {
+    fn default() -> Self {
+        Self {
+            status_codes: default_retry_status_codes(),
+            timeout: true,
+            connection_error: true,
+        }
+    }
+}
+
+fn default_retry_status_codes() -> Vec<u16> {
+    vec![502, 503, 504]
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RetryBudget {
+    #[serde(default = "default_retry_budget_ratio")]
+    pub ratio: f64,
+    #[serde(default = "default_min_retries_per_sec")]
+    pub min_retries_per_sec: u32,
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self {
+            ratio: default_retry_budget_ratio(),
+            min_retries_per_sec: default_min_retries_per_sec(),
+        }
+    }
+}
+
+fn default_retry_budget_ratio() -> f64 {
+    0.2
+}
+
+fn default_min_retries_per_sec() -> u32 {
+    10
+}
+
+// ---------------------------------------------------------------------------
+// CacheConfig
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CacheConfig {
+    #[serde(default)]
+    pub sharing: SharingMode,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub default_ttl_secs: u32,
+    #[serde(default = "default_cache_max_object_bytes")]
+    pub max_object_bytes: u32,
+    #[serde(default)]
+    pub allow_user_credentials: bool,
+}
+
+fn default_cache_max_object_bytes() -> u32 {
+    1024 * 1024
+}
+
 // ---------------------------------------------------------------------------
 // CorsConfig
 // ---------------------------------------------------------------------------
@@ -332,6 +657,8 @@ pub struct CreateUpstreamRequest {
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cors: Option<CorsConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     #[serde(default)]
     pub tags: Vec<String>,
     #[serde(default = "default_true")]
@@ -354,6 +681,8 @@ pub struct UpdateUpstreamRequest {
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cors: Option<CorsConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub tags: Vec<String>,
     pub enabled: bool,
 }
@@ -373,6 +702,12 @@ pub struct CreateRouteRequest {
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cors: Option<CorsConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeouts: Option<TimeoutConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheConfig>,
     #[serde(default)]
     pub tags: Vec<String>,
     #[serde(default)]
@@ -391,6 +726,12 @@ pub struct UpdateRouteRequest {
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cors: Option<CorsConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeouts: Option<TimeoutConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -418,10 +759,39 @@ pub struct UpstreamResponse {
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cors: Option<CorsConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointHealthStatus {
+    Healthy,
+    Unhealthy,
+    Ejected,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct EndpointHealthResponse {
+    #[serde(flatten)]
+    pub endpoint: Endpoint,
+    /// Resolved `ip:port`; absent until a load balancer has been built.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    pub status: EndpointHealthStatus,
+    pub in_flight: usize,
+}
+
+/// Endpoint health as observed by the node serving the request.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UpstreamHealthResponse {
+    pub upstream_id: String,
+    pub endpoints: Vec<EndpointHealthResponse>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct RouteResponse {
     pub id: String,
@@ -435,6 +805,12 @@ pub struct RouteResponse {
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cors: Option<CorsConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeouts: Option<TimeoutConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheConfig>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
     pub priority: i32,
@@ -473,6 +849,7 @@ impl From<Endpoint> for domain::Endpoint {
             scheme: v.scheme.into(),
             host: v.host,
             port: v.port,
+            weight: v.weight,
         }
     }
 }
@@ -481,6 +858,67 @@ impl From<Server> for domain::Server {
     fn from(v: Server) -> Self {
         Self {
             endpoints: v.endpoints.into_iter().map(Into::into).collect(),
+            load_balancing: v.load_balancing.map(Into::into),
+            health_check: v.health_check.map(Into::into),
+        }
+    }
+}
+
+impl From<LoadBalancing> for domain::LoadBalancing {
+    fn from(v: LoadBalancing) -> Self {
+        match v {
+            LoadBalancing::RoundRobin => Self::RoundRobin,
+            LoadBalancing::LeastInFlight => Self::LeastInFlight,
+            LoadBalancing::ConsistentHash { hash_key } => Self::ConsistentHash {
+                key: hash_key.into(),
+            },
+        }
+    }
+}
+
+impl From<HashKey> for domain::HashKey {
+    fn from(v: HashKey) -> Self {
+        match v {
+            HashKey::Header(name) => Self::Header(name),
+            HashKey::Tenant => Self::Tenant,
+        }
+    }
+}
+
+impl From<HealthCheckConfig> for domain::HealthCheckConfig {
+    fn from(v: HealthCheckConfig) -> Self {
+        Self {
+            probe: v.probe.into(),
+            interval_secs: v.interval_seconds,
+            timeout_ms: v.timeout_ms,
+            healthy_threshold: v.healthy_threshold,
+            unhealthy_threshold: v.unhealthy_threshold,
+            outlier_detection: v.outlier_detection.map(Into::into),
+        }
+    }
+}
+
+impl From<HealthProbe> for domain::HealthProbe {
+    fn from(v: HealthProbe) -> Self {
+        match v {
+            HealthProbe::Tcp => Self::Tcp,
+            HealthProbe::Http {
+                path,
+                expected_status,
+            } => Self::Http {
+                path,
+                expected_status,
+            },
+        }
+    }
+}
+
+impl From<OutlierDetectionConfig> for domain::OutlierDetectionConfig {
+    fn from(v: OutlierDetectionConfig) -> Self {
+        Self {
+            consecutive_failures: v.consecutive_failures,
+            ejection_secs: v.ejection_seconds,
+            max_ejection_percent: v.max_ejection_percent,
         }
     }
 }
@@ -677,6 +1115,97 @@ impl From<CorsConfig> for domain::CorsConfig {
     }
 }
 
+impl From<CircuitBreakerConfig> for domain::CircuitBreakerConfig {
+    fn from(v: CircuitBreakerConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            failure_rate: v.failure_rate.map(Into::into),
+            success_threshold: v.success_threshold,
+            timeout_secs: v.timeout_seconds,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: v.failure_conditions.into(),
+            scope: v.scope.into(),
+        }
+    }
+}
+
+impl From<FailureRateConfig> for domain::FailureRateConfig {
+    fn from(v: FailureRateConfig) -> Self {
+        Self {
+            threshold: v.threshold,
+            minimum_requests: v.minimum_requests,
+            window_secs: v.window_seconds,
+        }
+    }
+}
+
+impl From<FailureConditions> for domain::FailureConditions {
+    fn from(v: FailureConditions) -> Self {
+        Self {
+            status_codes: v.status_codes,
+            timeout: v.timeout,
+            connection_error: v.connection_error,
+        }
+    }
+}
+
+impl From<CircuitBreakerScope> for domain::CircuitBreakerScope {
+    fn from(v: CircuitBreakerScope) -> Self {
+        match v {
+            CircuitBreakerScope::Global => Self::Global,
+            CircuitBreakerScope::PerEndpoint => Self::PerEndpoint,
+        }
+    }
+}
+
+impl From<TimeoutConfig> for domain::TimeoutConfig {
+    fn from(v: TimeoutConfig) -> Self {
+        Self {
+            connect_ms: v.connect_ms,
+            read_ms: v.read_ms,
+            total_ms: v.total_ms,
+        }
+    }
+}
+
+impl From<RetryConfig> for domain::RetryConfig {
+    fn from(v: RetryConfig) -> Self {
+        Self {
+            max_attempts: v.max_attempts,
+            backoff: domain::BackoffConfig {
+                initial_ms: v.backoff.initial_ms,
+                max_ms: v.backoff.max_ms,
+                multiplier: v.backoff.multiplier,
+                jitter: v.backoff.jitter,
+            },
+            retry_on: domain::RetryConditions {
+                status_codes: v.retry_on.status_codes,
+                timeout: v.retry_on.timeout,
+                connection_error: v.retry_on.connection_error,
+            },
+            retry_non_idempotent: v.retry_non_idempotent,
+            budget: domain::RetryBudget {
+                ratio: v.budget.ratio,
+                min_retries_per_sec: v.budget.min_retries_per_sec,
+            },
+        }
+    }
+}
+
+impl From<CacheConfig> for domain::CacheConfig {
+    fn from(v: CacheConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            enabled: v.enabled,
+            default_ttl_secs: v.default_ttl_secs,
+            max_object_bytes: v.max_object_bytes,
+            allow_user_credentials: v.allow_user_credentials,
+        }
+    }
+}
+
 impl From<HttpMethod> for domain::HttpMethod {
     fn from(v: HttpMethod) -> Self {
         match v {
@@ -759,6 +1288,7 @@ impl From<domain::Endpoint> for Endpoint {
             scheme: v.scheme.into(),
             host: v.host,
             port: v.port,
+            weight: v.weight,
         }
     }
 }
@@ -767,6 +1297,67 @@ impl From<domain::Server> for Server {
     fn from(v: domain::Server) -> Self {
         Self {
             endpoints: v.endpoints.into_iter().map(Into::into).collect(),
+            load_balancing: v.load_balancing.map(Into::into),
+            health_check: v.health_check.map(Into::into),
+        }
+    }
+}
+
+impl From<domain::LoadBalancing> for LoadBalancing {
+    fn from(v: domain::LoadBalancing) -> Self {
+        match v {
+            domain::LoadBalancing::RoundRobin => Self::RoundRobin,
+            domain::LoadBalancing::LeastInFlight => Self::LeastInFlight,
+            domain::LoadBalancing::ConsistentHash { key } => Self::ConsistentHash {
+                hash_key: key.into(),
+            },
+        }
+    }
+}
+
+impl From<domain::HashKey> for HashKey {
+    fn from(v: domain::HashKey) -> Self {
+        match v {
+            domain::HashKey::Header(name) => Self::Header(name),
+            domain::HashKey::Tenant => Self::Tenant,
+        }
+    }
+}
+
+impl From<domain::HealthCheckConfig> for HealthCheckConfig {
+    fn from(v: domain::HealthCheckConfig) -> Self {
+        Self {
+            probe: v.probe.into(),
+            interval_seconds: v.interval_secs,
+            timeout_ms: v.timeout_ms,
+            healthy_threshold: v.healthy_threshold,
+            unhealthy_threshold: v.unhealthy_threshold,
+            outlier_detection: v.outlier_detection.map(Into::into),
+        }
+    }
+}
+
+impl From<domain::HealthProbe> for HealthProbe {
+    fn from(v: domain::HealthProbe) -> Self {
+        match v {
+            domain::HealthProbe::Tcp => Self::Tcp,
+            domain::HealthProbe::Http {
+                path,
+                expected_status,
+            } => Self::Http {
+                path,
+                expected_status,
+            },
+        }
+    }
+}
+
+impl From<domain::OutlierDetectionConfig> for OutlierDetectionConfig {
+    fn from(v: domain::OutlierDetectionConfig) -> Self {
+        Self {
+            consecutive_failures: v.consecutive_failures,
+            ejection_seconds: v.ejection_secs,
+            max_ejection_percent: v.max_ejection_percent,
         }
     }
 }
@@ -962,6 +1553,97 @@ impl From<domain::CorsConfig> for CorsConfig {
     }
 }
 
+impl From<domain::CircuitBreakerConfig> for CircuitBreakerConfig {
+    fn from(v: domain::CircuitBreakerConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            failure_rate: v.failure_rate.map(Into::into),
+            success_threshold: v.success_threshold,
+            timeout_seconds: v.timeout_secs,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: v.failure_conditions.into(),
+            scope: v.scope.into(),
+        }
+    }
+}
+
+impl From<domain::FailureRateConfig> for FailureRateConfig {
+    fn from(v: domain::FailureRateConfig) -> Self {
+        Self {
+            threshold: v.threshold,
+            minimum_requests: v.minimum_requests,
+            window_seconds: v.window_secs,
+        }
+    }
+}
+
+impl From<domain::FailureConditions> for FailureConditions {
+    fn from(v: domain::FailureConditions) -> Self {
+        Self {
+            status_codes: v.status_codes,
+            timeout: v.timeout,
+            connection_error: v.connection_error,
+        }
+    }
+}
+
+impl From<domain::CircuitBreakerScope> for CircuitBreakerScope {
+    fn from(v: domain::CircuitBreakerScope) -> Self {
+        match v {
+            domain::CircuitBreakerScope::Global => Self::Global,
+            domain::CircuitBreakerScope::PerEndpoint => Self::PerEndpoint,
+        }
+    }
+}
+
+impl From<domain::TimeoutConfig> for TimeoutConfig {
+    fn from(v: domain::TimeoutConfig) -> Self {
+        Self {
+            connect_ms: v.connect_ms,
+            read_ms: v.read_ms,
+            total_ms: v.total_ms,
+        }
+    }
+}
+
+impl From<domain::RetryConfig> for RetryConfig {
+    fn from(v: domain::RetryConfig) -> Self {
+        Self {
+            max_attempts: v.max_attempts,
+            backoff: BackoffConfig {
+                initial_ms: v.backoff.initial_ms,
+                max_ms: v.backoff.max_ms,
+                multiplier: v.backoff.multiplier,
+                jitter: v.backoff.jitter,
+            },
+            retry_on: RetryConditions {
+                status_codes: v.retry_on.status_codes,
+                timeout: v.retry_on.timeout,
+                connection_error: v.retry_on.connection_error,
+            },
+            retry_non_idempotent: v.retry_non_idempotent,
+            budget: RetryBudget {
+                ratio: v.budget.ratio,
+                min_retries_per_sec: v.budget.min_retries_per_sec,
+            },
+        }
+    }
+}
+
+impl From<domain::CacheConfig> for CacheConfig {
+    fn from(v: domain::CacheConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            enabled: v.enabled,
+            default_ttl_secs: v.default_ttl_secs,
+            max_object_bytes: v.max_object_bytes,
+            allow_user_credentials: v.allow_user_credentials,
+        }
+    }
+}
+
 impl From<domain::HttpMethod> for HttpMethod {
     fn from(v: domain::HttpMethod) -> Self {
         match v {
@@ -1028,6 +1710,7 @@ impl From<CreateUpstreamRequest> for domain::CreateUpstreamRequest {
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
             cors: r.cors.map(Into::into),
+            circuit_breaker: r.circuit_breaker.map(Into::into),
             tags: r.tags,
             enabled: r.enabled,
         }
@@ -1045,6 +1728,7 @@ impl From<UpdateUpstreamRequest> for domain::UpdateUpstreamRequest {
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
             cors: r.cors.map(Into::into),
+            circuit_breaker: r.circuit_breaker.map(Into::into),
             tags: r.tags,
             enabled: r.enabled,
         }
@@ -1060,6 +1744,9 @@ impl From<(Uuid, CreateRouteRequest)> for domain::CreateRouteRequest {
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
             cors: r.cors.map(Into::into),
+            timeouts: r.timeouts.map(Into::into),
+            retry: r.retry.map(Into::into),
+            cache: r.cache.map(Into::into),
             tags: r.tags,
             priority: r.priority,
             enabled: r.enabled,
@@ -1074,6 +1761,9 @@ impl From<UpdateRouteRequest> for domain::UpdateRouteRequest {
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
             cors: r.cors.map(Into::into),
+            timeouts: r.timeouts.map(Into::into),
+            retry: r.retry.map(Into::into),
+            cache: r.cache.map(Into::into),
             tags: r.tags,
             priority: r.priority,
             enabled: r.enabled,
@@ -1092,6 +1782,7 @@ impl modkit::api::api_dto::RequestApiDto for UpdateRouteRequest {}
 
 impl modkit::api::api_dto::ResponseApiDto for UpstreamResponse {}
 impl modkit::api::api_dto::ResponseApiDto for RouteResponse {}
+impl modkit::api::api_dto::ResponseApiDto for UpstreamHealthResponse {}
 
 // ---------------------------------------------------------------------------
 // Helpers
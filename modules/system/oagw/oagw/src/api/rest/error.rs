@@ -11,6 +11,8 @@
 //! * `error_response` — proxy-pipeline helper that produces an axum
 //!   `Response` with the gateway-specific `x-oagw-error-source`,
 //!   `Retry-After`, and `x-ratelimit-*` headers.
+//! * `grpc_error_response` — the same for gRPC calls: a trailers-only
+//!   response carrying `grpc-status` / `grpc-message`.
 //!
 //! ## `permission_denied` is fixed-detail by design
 //!
@@ -42,6 +44,7 @@ use modkit_canonical_errors::{CanonicalError, Problem, resource_error};
 
 use crate::domain::error::DomainError;
 use crate::domain::gts_helpers as gts;
+use crate::infra::proxy::grpc;
 use oagw_sdk::api::ErrorSource;
 
 // ---------------------------------------------------------------------------
@@ -567,6 +570,38 @@ pub fn error_response(err: DomainError) -> Response {
     response
 }
 
+/// Convert a `DomainError` into a trailers-only gRPC response.
+///
+/// gRPC clients read the call status from `grpc-status`, not the HTTP
+/// status, so the response is `200 OK` with the canonical error's status
+/// mapped to the matching gRPC code and its detail as `grpc-message`.
+pub fn grpc_error_response(err: DomainError) -> Response {
+    let rate_limited = matches!(err, DomainError::RateLimitExceeded { .. });
+    let problem: Problem = err.into();
+    let message = if rate_limited {
+        "rate limit exceeded"
+    } else {
+        problem.detail.as_str()
+    };
+    grpc_status_response(grpc::status_for_http(problem.status), message)
+}
+
+/// Build a trailers-only gRPC response originating from the gateway.
+pub(crate) fn grpc_status_response(code: u8, message: &str) -> Response {
+    let mut response = Response::new(axum::body::Body::empty());
+    let headers = response.headers_mut();
+    headers.extend(grpc::status_headers(code, message));
+    headers.insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static("application/grpc"),
+    );
+    headers.insert(
+        "x-oagw-error-source",
+        HeaderValue::from_static(ErrorSource::Gateway.as_str()),
+    );
+    response
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1171,4 +1206,32 @@ mod tests {
             "gateway"
         );
     }
+
+    #[test]
+    fn grpc_error_response_is_trailers_only_status() {
+        let err = DomainError::RequestTimeout {
+            detail: "request timed out".into(),
+            instance: "/grpc/example.v1.UserService/GetUser".into(),
+        };
+        let resp = grpc_error_response(err);
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(resp.headers()["content-type"], "application/grpc");
+        assert_eq!(resp.headers()["grpc-status"], "4");
+        assert_eq!(resp.headers()["x-oagw-error-source"], "gateway");
+    }
+
+    #[test]
+    fn grpc_error_response_maps_rate_limit_to_resource_exhausted() {
+        let err = DomainError::RateLimitExceeded {
+            detail: "upstream:0000:tenant:abc".into(),
+            instance: String::new(),
+            retry_after_secs: Some(1),
+            limit: None,
+            remaining: None,
+            reset_epoch: None,
+        };
+        let resp = grpc_error_response(err);
+        assert_eq!(resp.headers()["grpc-status"], "8");
+        assert_eq!(resp.headers()["grpc-message"], "rate limit exceeded");
+    }
 }
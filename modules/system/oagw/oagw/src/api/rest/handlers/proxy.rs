@@ -1,18 +1,21 @@
 // Updated: 2026-04-07 by Constructor Tech
 use bytes::Bytes;
+use futures_util::TryStreamExt;
 
 use crate::domain::error::DomainError;
+use crate::infra::proxy::grpc::{self, GrpcResponseBody, GrpcTrailersHandle};
 use crate::infra::proxy::headers;
 use crate::infra::proxy::websocket::{WebSocketBridgeHandle, websocket_bridge};
 use axum::body::Body;
 use axum::extract::{Extension, Request};
 use axum::response::Response;
-use http::StatusCode;
+use http::{StatusCode, Uri};
 use modkit_security::SecurityContext;
 use oagw_sdk::api::ErrorSource;
+use oagw_sdk::body::{BodyStream, BoxError};
 use tracing::Instrument;
 
-use crate::api::rest::error::error_response;
+use crate::api::rest::error::{error_response, grpc_error_response, grpc_status_response};
 use crate::module::AppState;
 
 /// Proxy handler for `/oagw/v1/proxy/{alias}/{path:.*}`.
@@ -23,6 +26,9 @@ use crate::module::AppState;
 /// For WebSocket upgrade requests (`Upgrade: websocket`), extracts the
 /// `hyper::upgrade::OnUpgrade` handle, skips body buffering, and spawns a
 /// bidirectional byte-forwarding task after receiving a 101 response.
+///
+/// gRPC calls (`Content-Type: application/grpc`) are streamed in both
+/// directions and answered with gRPC statuses; see [`grpc_proxy`].
 pub async fn proxy_handler(
     Extension(state): Extension<AppState>,
     Extension(ctx): Extension<SecurityContext>,
@@ -72,6 +78,12 @@ pub async fn proxy_handler(
     let max_body_size = state.config.max_body_size_bytes;
     let (mut parts, body) = req.into_parts();
 
+    if headers::is_grpc_content_type(&parts.headers) {
+        return Ok(grpc_proxy(state, ctx, parts, body)
+            .await
+            .unwrap_or_else(grpc_error_response));
+    }
+
     // Detect WebSocket upgrade and extract the hyper upgrade handle.
     // This must happen before body consumption — the OnUpgrade future is
     // stored in extensions by hyper and is needed after returning the 101.
@@ -106,28 +118,10 @@ pub async fn proxy_handler(
         None
     };
 
-    // Parse alias from the URI to validate it's present.
-    let path = parts.uri.path();
-    let prefix = "/oagw/v1/proxy/";
-    let remaining = path.strip_prefix(prefix).ok_or_else(|| {
-        error_response(DomainError::Validation {
-            field: "path",
-            reason: "INVALID_PROXY_PATH",
-            detail: "invalid proxy path".into(),
-            instance: path.to_string(),
-        })
-    })?;
-
-    // Validate alias is not empty.
-    let alias_end = remaining.find('/').unwrap_or(remaining.len());
-    if alias_end == 0 {
-        return Err(error_response(DomainError::Validation {
-            field: "path",
-            reason: "MISSING_ALIAS",
-            detail: "missing alias in proxy path".into(),
-            instance: path.to_string(),
-        }));
-    }
+    // Validate the alias and strip the proxy prefix so the DP receives
+    // /{alias}/{path}?query.
+    let path = parts.uri.path().to_string();
+    let target_uri = proxy_target_uri(&parts.uri).map_err(error_response)?;
 
     // Validate Content-Length if present (skip for WebSocket — no body).
     if !is_upgrade && let Some(cl) = parts.headers.get(http::header::CONTENT_LENGTH) {
@@ -172,20 +166,7 @@ pub async fn proxy_handler(
             })?
     };
 
-    // Strip the proxy prefix from the URI so the DP receives /{alias}/{path}?query.
-    let new_uri_str = if let Some(query) = parts.uri.query() {
-        format!("/{remaining}?{query}")
-    } else {
-        format!("/{remaining}")
-    };
-    parts.uri = new_uri_str.parse().map_err(|_| {
-        error_response(DomainError::Validation {
-            field: "path",
-            reason: "INVALID_REWRITTEN_URI",
-            detail: "failed to parse proxy URI".into(),
-            instance: path.to_string(),
-        })
-    })?;
+    parts.uri = target_uri;
 
     // Build http::Request<Body> for the DP service.
     let sdk_body = oagw_sdk::Body::from(body_bytes);
@@ -283,6 +264,108 @@ pub async fn proxy_handler(
     })
 }
 
+/// Strip the `/oagw/v1/proxy/` prefix from a proxy URI, keeping the query,
+/// and check that an alias is present.
+fn proxy_target_uri(uri: &Uri) -> Result<Uri, DomainError> {
+    // Parse alias from the URI to validate it's present.
+    let path = uri.path();
+    let prefix = "/oagw/v1/proxy/";
+    let remaining = path
+        .strip_prefix(prefix)
+        .ok_or_else(|| DomainError::Validation {
+            field: "path",
+            reason: "INVALID_PROXY_PATH",
+            detail: "invalid proxy path".into(),
+            instance: path.to_string(),
+        })?;
+
+    // Validate alias is not empty.
+    let alias_end = remaining.find('/').unwrap_or(remaining.len());
+    if alias_end == 0 {
+        return Err(DomainError::Validation {
+            field: "path",
+            reason: "MISSING_ALIAS",
+            detail: "missing alias in proxy path".into(),
+            instance: path.to_string(),
+        });
+    }
+
+    let new_uri_str = if let Some(query) = uri.query() {
+        format!("/{remaining}?{query}")
+    } else {
+        format!("/{remaining}")
+    };
+    new_uri_str.parse().map_err(|_| DomainError::Validation {
+        field: "path",
+        reason: "INVALID_REWRITTEN_URI",
+        detail: "failed to parse proxy URI".into(),
+        instance: path.to_string(),
+    })
+}
+
+/// Proxy a gRPC call.
+///
+/// The request body is streamed to the Data Plane as it arrives (the DP
+/// enforces the body size limit on the stream) so client-streaming and
+/// bidirectional calls work. Upstream trailers are re-emitted as HTTP/2
+/// trailers, and errors raised by the gateway — here or inside the proxy —
+/// become trailers-only responses with a gRPC status.
+async fn grpc_proxy(
+    state: AppState,
+    ctx: SecurityContext,
+    mut parts: http::request::Parts,
+    body: Body,
+) -> Result<Response, DomainError> {
+    parts.uri = proxy_target_uri(&parts.uri)?;
+
+    let body_stream: BodyStream = Box::pin(body.into_data_stream().map_err(BoxError::from));
+    let proxy_req = http::Request::from_parts(parts, oagw_sdk::Body::Stream(body_stream));
+    let proxy_resp = state.dp.proxy_request(ctx, proxy_req).await?;
+
+    let (mut resp_parts, sdk_body) = proxy_resp.into_parts();
+    let error_source = resp_parts
+        .extensions
+        .get::<ErrorSource>()
+        .copied()
+        .unwrap_or(ErrorSource::Gateway);
+    let trailers = resp_parts
+        .extensions
+        .remove::<GrpcTrailersHandle>()
+        .and_then(|h| h.take());
+
+    // Proxy-level failures (connect errors, upstream timeouts) come back as
+    // RFC 9457 problem bodies; answer them with the matching gRPC status.
+    if error_source == ErrorSource::Gateway && !headers::is_grpc_content_type(&resp_parts.headers) {
+        let body = sdk_body.into_bytes().await.unwrap_or_default();
+        let detail = serde_json::from_slice::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|p| p.get("detail")?.as_str().map(str::to_owned))
+            .unwrap_or_else(|| "gateway error".to_string());
+        return Ok(grpc_status_response(
+            grpc::status_for_http(resp_parts.status.as_u16()),
+            &detail,
+        ));
+    }
+
+    let mut builder = Response::builder().status(resp_parts.status);
+    for (name, value) in &resp_parts.headers {
+        builder = builder.header(name, value);
+    }
+    builder = builder.header("x-oagw-error-source", error_source.as_str());
+
+    let body = match trailers {
+        Some(trailers) => Body::new(GrpcResponseBody::new(sdk_body.into_stream(), trailers)),
+        None => Body::from_stream(sdk_body.into_stream()),
+    };
+
+    builder
+        .body(body)
+        .map_err(|e| DomainError::DownstreamError {
+            detail: format!("failed to build response: {e}"),
+            instance: String::new(),
+        })
+}
+
 #[cfg(test)]
 #[path = "proxy_tests.rs"]
 mod proxy_tests;
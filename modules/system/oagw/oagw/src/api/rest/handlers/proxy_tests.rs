@@ -58,3 +58,28 @@ fn percent_encoded_param_name_decoded() {
     assert_eq!(params.len(), 1);
     assert_eq!(params[0], ("my key".into(), "value".into()));
 }
+
+#[test]
+fn proxy_target_uri_strips_prefix_and_keeps_query() {
+    let uri: http::Uri = "/oagw/v1/proxy/grpc.example.com/example.v1.UserService/GetUser?x=1"
+        .parse()
+        .unwrap();
+    let target = super::proxy_target_uri(&uri).unwrap();
+    assert_eq!(
+        target,
+        "/grpc.example.com/example.v1.UserService/GetUser?x=1"
+    );
+}
+
+#[test]
+fn proxy_target_uri_rejects_missing_alias() {
+    let uri: http::Uri = "/oagw/v1/proxy//v1/models".parse().unwrap();
+    let err = super::proxy_target_uri(&uri).unwrap_err();
+    assert!(matches!(
+        err,
+        crate::domain::error::DomainError::Validation {
+            reason: "MISSING_ALIAS",
+            ..
+        }
+    ));
+}
@@ -21,6 +21,9 @@ fn to_response(r: Route) -> RouteResponse {
         plugins: r.plugins.map(Into::into),
         rate_limit: r.rate_limit.map(Into::into),
         cors: r.cors.map(Into::into),
+        timeouts: r.timeouts.map(Into::into),
+        retry: r.retry.map(Into::into),
+        cache: r.cache.map(Into::into),
         tags: r.tags,
         priority: r.priority,
         enabled: r.enabled,
@@ -108,22 +111,28 @@ pub async fn update_route(
 ) -> Result<impl IntoResponse, Problem> {
     let instance = format!("/oagw/v1/routes/{id}");
     let uuid = parse_gts_id(&id, gts::ROUTE_SCHEMA, &instance)?;
-    // Snapshot old rate_limit before update so we can detect changes and
-    // clean up stale rate-limit keys (avoids accumulating orphaned buckets).
-    let old_rate_limit = state
+    // Snapshot the old route before update so we can detect changes and
+    // clean up stale rate-limit keys, retry budgets and cached responses
+    // (avoids accumulating orphaned state).
+    let old = state
         .cp
         .get_route(&ctx, uuid)
         .await
-        .map_err(|e| domain_error_to_problem(e, &instance))?
-        .rate_limit;
+        .map_err(|e| domain_error_to_problem(e, &instance))?;
     let route = state
         .cp
         .update_route(&ctx, uuid, req.into())
         .await
         .map_err(|e| domain_error_to_problem(e, &instance))?;
-    if route.rate_limit != old_rate_limit {
+    if route.rate_limit != old.rate_limit {
         state.dp.remove_rate_limit_keys_for_route(uuid);
     }
+    if route.retry != old.retry {
+        state.dp.remove_retry_budget_for_route(uuid);
+    }
+    if route != old {
+        state.dp.remove_cached_responses_for_route(uuid);
+    }
     Ok(Json(to_response(route)))
 }
 
@@ -140,5 +149,7 @@ pub async fn delete_route(
         .await
         .map_err(|e| domain_error_to_problem(e, &instance))?;
     state.dp.remove_rate_limit_keys_for_route(uuid);
+    state.dp.remove_retry_budget_for_route(uuid);
+    state.dp.remove_cached_responses_for_route(uuid);
     Ok(StatusCode::NO_CONTENT)
 }
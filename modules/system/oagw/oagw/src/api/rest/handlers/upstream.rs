@@ -5,11 +5,15 @@ use http::StatusCode;
 use modkit_canonical_errors::Problem;
 use modkit_security::SecurityContext;
 
-use crate::api::rest::dto::{CreateUpstreamRequest, UpdateUpstreamRequest, UpstreamResponse};
+use crate::api::rest::dto::{
+    CreateUpstreamRequest, EndpointHealthResponse, EndpointHealthStatus, UpdateUpstreamRequest,
+    UpstreamHealthResponse, UpstreamResponse,
+};
 use crate::api::rest::error::domain_error_to_problem;
 use crate::api::rest::extractors::{PaginationQuery, parse_gts_id};
 use crate::domain::gts_helpers as gts;
 use crate::domain::model::Upstream;
+use crate::domain::services::{self, EndpointHealth};
 use crate::module::AppState;
 
 fn to_response(u: Upstream) -> UpstreamResponse {
@@ -25,6 +29,7 @@ fn to_response(u: Upstream) -> UpstreamResponse {
         plugins: u.plugins.map(Into::into),
         rate_limit: u.rate_limit.map(Into::into),
         cors: u.cors.map(Into::into),
+        circuit_breaker: u.circuit_breaker.map(Into::into),
         tags: u.tags,
     }
 }
@@ -59,6 +64,56 @@ pub async fn get_upstream(
     Ok(Json(to_response(upstream)))
 }
 
+fn to_health_response(h: EndpointHealth) -> EndpointHealthResponse {
+    EndpointHealthResponse {
+        endpoint: h.endpoint.into(),
+        address: Some(h.address),
+        status: match h.status {
+            services::EndpointHealthStatus::Healthy => EndpointHealthStatus::Healthy,
+            services::EndpointHealthStatus::Unhealthy => EndpointHealthStatus::Unhealthy,
+            services::EndpointHealthStatus::Ejected => EndpointHealthStatus::Ejected,
+        },
+        in_flight: h.in_flight,
+    }
+}
+
+pub async fn get_upstream_health(
+    Extension(state): Extension<AppState>,
+    Extension(ctx): Extension<SecurityContext>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, Problem> {
+    let instance = format!("/oagw/v1/upstreams/{id}/health");
+    let uuid = parse_gts_id(&id, gts::UPSTREAM_SCHEMA, &instance)?;
+    // Fetching the upstream enforces tenant visibility.
+    let upstream = state
+        .cp
+        .get_upstream(&ctx, uuid)
+        .await
+        .map_err(|e| domain_error_to_problem(e, &instance))?;
+    let health = state.backend_selector.health(uuid);
+    // No balancer yet (no traffic on this node, or a bypassed single
+    // endpoint): report the configured endpoints as unknown.
+    let endpoints = if health.is_empty() {
+        upstream
+            .server
+            .endpoints
+            .into_iter()
+            .map(|ep| EndpointHealthResponse {
+                endpoint: ep.into(),
+                address: None,
+                status: EndpointHealthStatus::Unknown,
+                in_flight: 0,
+            })
+            .collect()
+    } else {
+        health.into_iter().map(to_health_response).collect()
+    };
+    Ok(Json(UpstreamHealthResponse {
+        upstream_id: gts::format_upstream_gts(uuid),
+        endpoints,
+    }))
+}
+
 pub async fn list_upstreams(
     Extension(state): Extension<AppState>,
     Extension(ctx): Extension<SecurityContext>,
@@ -82,23 +137,26 @@ pub async fn update_upstream(
 ) -> Result<impl IntoResponse, Problem> {
     let instance = format!("/oagw/v1/upstreams/{id}");
     let uuid = parse_gts_id(&id, gts::UPSTREAM_SCHEMA, &instance)?;
-    // Snapshot old rate_limit before update so we can detect changes and
-    // clean up stale rate-limit keys (avoids accumulating orphaned buckets).
-    let old_rate_limit = state
+    // Snapshot the old upstream before update so we can detect changes and
+    // clean up stale rate-limit keys and circuit breakers (avoids accumulating
+    // orphaned in-memory state).
+    let old = state
         .cp
         .get_upstream(&ctx, uuid)
         .await
-        .map_err(|e| domain_error_to_problem(e, &instance))?
-        .rate_limit;
+        .map_err(|e| domain_error_to_problem(e, &instance))?;
     let upstream = state
         .cp
         .update_upstream(&ctx, uuid, req.into())
         .await
         .map_err(|e| domain_error_to_problem(e, &instance))?;
     state.backend_selector.invalidate(upstream.id);
-    if upstream.rate_limit != old_rate_limit {
+    if upstream.rate_limit != old.rate_limit {
         state.dp.remove_rate_limit_keys_for_upstream(uuid);
     }
+    if upstream.circuit_breaker != old.circuit_breaker || upstream.server != old.server {
+        state.dp.remove_circuit_breakers_for_upstream(uuid);
+    }
     Ok(Json(to_response(upstream)))
 }
 
@@ -116,8 +174,11 @@ pub async fn delete_upstream(
         .map_err(|e| domain_error_to_problem(e, &instance))?;
     state.backend_selector.invalidate(uuid);
     state.dp.remove_rate_limit_keys_for_upstream(uuid);
+    state.dp.remove_circuit_breakers_for_upstream(uuid);
     for route_id in deleted_route_ids {
         state.dp.remove_rate_limit_keys_for_route(route_id);
+        state.dp.remove_retry_budget_for_route(route_id);
+        state.dp.remove_cached_responses_for_route(route_id);
     }
     Ok(StatusCode::NO_CONTENT)
 }
@@ -56,6 +56,10 @@ pub fn test_router(state: AppState, ctx: modkit_security::SecurityContext) -> Ro
                 .put(upstream_h::update_upstream)
                 .delete(upstream_h::delete_upstream),
         )
+        .route(
+            "/oagw/v1/upstreams/{id}/health",
+            get(upstream_h::get_upstream_health),
+        )
         // Route CRUD
         .route(
             "/oagw/v1/routes",
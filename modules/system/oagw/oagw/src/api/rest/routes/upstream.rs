@@ -75,6 +75,27 @@ pub(super) fn register(
         .standard_errors(openapi)
         .register(router, openapi);
 
+    // GET /oagw/v1/upstreams/{id}/health — Endpoint health
+    router = OperationBuilder::get("/oagw/v1/upstreams/{id}/health")
+        .operation_id("oagw.get_upstream_health")
+        .summary("Get upstream endpoint health")
+        .description(
+            "Report active health-check status, outlier ejection and in-flight requests \
+             for each endpoint of an upstream, as seen by the serving node",
+        )
+        .tag(API_TAG)
+        .path_param("id", "Upstream GTS identifier")
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::upstream::get_upstream_health)
+        .json_response_with_schema::<dto::UpstreamHealthResponse>(
+            openapi,
+            http::StatusCode::OK,
+            "Endpoint health",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
     if writable {
         // PUT /oagw/v1/upstreams/{id} — Update upstream
         router = OperationBuilder::put("/oagw/v1/upstreams/{id}")
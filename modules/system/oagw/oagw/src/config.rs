@@ -48,6 +48,11 @@ pub struct OagwConfig {
     /// will use ALPN H2H1 negotiation). Default: 3600 (1 hour).
     #[serde(default = "default_protocol_cache_ttl_secs")]
     pub protocol_cache_ttl_secs: u64,
+    /// Memory budget in bytes of the route response cache, shared by all
+    /// routes with `cache` enabled. Entries are weighed by their body and
+    /// header size. Must be > 0. Default: 256 MiB.
+    #[serde(default = "default_response_cache_max_bytes")]
+    pub response_cache_max_bytes: usize,
     /// Whether the upstream/route management REST APIs are enabled.
     /// When `true`, all CRUD endpoints are registered. When `false`, only
     /// read-only endpoints (list / get) are available — write operations
@@ -69,6 +74,7 @@ impl Default for OagwConfig {
             websocket_max_frame_size_bytes: None,
             streaming_idle_timeout_secs: default_streaming_idle_timeout_secs(),
             protocol_cache_ttl_secs: default_protocol_cache_ttl_secs(),
+            response_cache_max_bytes: default_response_cache_max_bytes(),
             management_api_enabled: true,
         }
     }
@@ -110,6 +116,10 @@ fn default_protocol_cache_ttl_secs() -> u64 {
     3600 // 1 hour — per spec cpt-cf-oagw-algo-protocol-version-negotiation
 }
 
+fn default_response_cache_max_bytes() -> usize {
+    256 * 1024 * 1024 // 256 MiB
+}
+
 impl OagwConfig {
     /// Validate configuration values. Returns an error for values that
     /// would cause broken runtime behaviour.
@@ -123,6 +133,9 @@ impl OagwConfig {
         if self.streaming_idle_timeout_secs == 0 {
             return Err("streaming_idle_timeout_secs must be > 0".to_owned());
         }
+        if self.response_cache_max_bytes == 0 {
+            return Err("response_cache_max_bytes must be > 0".to_owned());
+        }
         Ok(())
     }
 }
@@ -203,6 +216,7 @@ impl fmt::Debug for OagwConfig {
                 &self.streaming_idle_timeout_secs,
             )
             .field("protocol_cache_ttl_secs", &self.protocol_cache_ttl_secs)
+            .field("response_cache_max_bytes", &self.response_cache_max_bytes)
             .field("management_api_enabled", &self.management_api_enabled)
             .finish()
     }
@@ -277,6 +291,15 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn validate_rejects_zero_response_cache_max_bytes() {
+        let config = OagwConfig {
+            response_cache_max_bytes: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn validate_accepts_zero_protocol_cache_ttl() {
         let config = OagwConfig {
@@ -0,0 +1,502 @@
+//! HTTP caching rules for the route response cache: the subset of RFC 9111
+//! a shared cache needs to decide what may be stored, for how long, and
+//! whether a stored response can answer a request.
+
+use std::time::Duration;
+
+use http::header::{self, HeaderMap, HeaderName};
+use modkit_macros::domain_model;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::model::{CacheConfig, SharingMode};
+
+/// Upper bound for `cache.max_object_bytes`.
+pub const MAX_CACHE_OBJECT_BYTES: u32 = 16 * 1024 * 1024;
+
+/// Upper bound for `cache.default_ttl_secs`.
+pub const MAX_CACHE_DEFAULT_TTL_SECS: u32 = 86_400;
+
+/// Statuses a cache may store (RFC 9110 §15.1, heuristically cacheable).
+const CACHEABLE_STATUSES: [u16; 11] = [200, 203, 204, 300, 301, 308, 404, 405, 410, 414, 501];
+
+/// Request headers that make the client responsible for validation or ask
+/// for a partial body; such requests are forwarded without the cache.
+const BYPASS_REQUEST_HEADERS: [HeaderName; 6] = [
+    header::IF_NONE_MATCH,
+    header::IF_MODIFIED_SINCE,
+    header::IF_MATCH,
+    header::IF_UNMODIFIED_SINCE,
+    header::IF_RANGE,
+    header::RANGE,
+];
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+/// Validate a route cache config at creation/update time.
+pub fn validate_cache_config(config: &CacheConfig) -> Result<(), DomainError> {
+    if !(1..=MAX_CACHE_OBJECT_BYTES).contains(&config.max_object_bytes) {
+        return Err(DomainError::validation_for(
+            "cache.max_object_bytes",
+            "INVALID_CACHE_CONFIG",
+            format!(
+                "cache.max_object_bytes must be between 1 and {MAX_CACHE_OBJECT_BYTES}, got {}",
+                config.max_object_bytes
+            ),
+        ));
+    }
+    if config.default_ttl_secs > MAX_CACHE_DEFAULT_TTL_SECS {
+        return Err(DomainError::validation_for(
+            "cache.default_ttl_secs",
+            "INVALID_CACHE_CONFIG",
+            format!(
+                "cache.default_ttl_secs must not exceed {MAX_CACHE_DEFAULT_TTL_SECS}, got {}",
+                config.default_ttl_secs
+            ),
+        ));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Cache-Control
+// ---------------------------------------------------------------------------
+
+/// The `Cache-Control` directives the cache acts on. A qualified
+/// `no-cache="field"` is treated as unqualified; stale entries are never
+/// served, so `must-revalidate` needs no handling.
+#[domain_model]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheControl {
+    pub no_store: bool,
+    pub no_cache: bool,
+    pub private: bool,
+    pub max_age: Option<u64>,
+    pub s_maxage: Option<u64>,
+}
+
+impl CacheControl {
+    /// Parse every `Cache-Control` field in `headers`. An unparsable
+    /// `max-age`/`s-maxage` counts as 0 (stale), per RFC 9111 §1.2.2.
+    #[must_use]
+    pub fn parse(headers: &HeaderMap) -> Self {
+        let mut cc = Self::default();
+        let values = headers
+            .get_all(header::CACHE_CONTROL)
+            .iter()
+            .filter_map(|v| v.to_str().ok());
+        for directive in values.flat_map(|v| v.split(',')) {
+            let (name, arg) = match directive.split_once('=') {
+                Some((name, arg)) => (name.trim(), Some(arg.trim().trim_matches('"'))),
+                None => (directive.trim(), None),
+            };
+            let seconds = || Some(arg.and_then(|a| a.parse().ok()).unwrap_or(0));
+            match name.to_ascii_lowercase().as_str() {
+                "no-store" => cc.no_store = true,
+                "no-cache" => cc.no_cache = true,
+                "private" => cc.private = true,
+                "max-age" => cc.max_age = seconds(),
+                "s-maxage" => cc.s_maxage = seconds(),
+                _ => {}
+            }
+        }
+        cc
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Storage and reuse
+// ---------------------------------------------------------------------------
+
+/// Freshness of a stored response. `initial_age` is the `Age` the upstream
+/// reported when the response was received.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Freshness {
+    pub lifetime: Duration,
+    pub initial_age: Duration,
+}
+
+/// Whether the cache may answer `method` with these request headers. Only
+/// plain GETs qualify: conditional and range requests are left to the
+/// upstream, and `no-store` opts the request out entirely.
+#[must_use]
+pub fn is_cacheable_request(method: &http::Method, req_headers: &HeaderMap) -> bool {
+    method == http::Method::GET
+        && !BYPASS_REQUEST_HEADERS
+            .iter()
+            .any(|name| req_headers.contains_key(name))
+        && !CacheControl::parse(req_headers).no_store
+}
+
+/// Freshness for a response the upstream returned, or `None` when a shared
+/// cache must not store it.
+///
+/// The lifetime comes from `s-maxage`, then `max-age`, then `default_ttl`;
+/// `Expires` is not consulted. `no-cache` responses are stored with a zero
+/// lifetime, so every reuse is revalidated. A response that would be stale
+/// on arrival is kept only when it carries a validator.
+#[must_use]
+pub fn response_freshness(
+    status: u16,
+    resp_headers: &HeaderMap,
+    default_ttl: Duration,
+) -> Option<Freshness> {
+    if !CACHEABLE_STATUSES.contains(&status)
+        || resp_headers.contains_key(header::SET_COOKIE)
+        || vary_names(resp_headers).is_none()
+    {
+        return None;
+    }
+    let cc = CacheControl::parse(resp_headers);
+    if cc.no_store || cc.private {
+        return None;
+    }
+
+    let lifetime = if cc.no_cache {
+        Duration::ZERO
+    } else {
+        cc.s_maxage
+            .or(cc.max_age)
+            .map_or(default_ttl, Duration::from_secs)
+    };
+    let initial_age = resp_headers
+        .get(header::AGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+        .map_or(Duration::ZERO, Duration::from_secs);
+
+    if lifetime <= initial_age && !has_validator(resp_headers) {
+        return None;
+    }
+    Some(Freshness {
+        lifetime,
+        initial_age,
+    })
+}
+
+/// Whether a stored response of the given `age` can be served without
+/// revalidation. The request may tighten freshness with `no-cache` or
+/// `max-age`; it cannot extend it past the response's own lifetime.
+#[must_use]
+pub fn is_fresh(freshness: &Freshness, age: Duration, req_cc: &CacheControl) -> bool {
+    if req_cc.no_cache {
+        return false;
+    }
+    if let Some(max_age) = req_cc.max_age
+        && age > Duration::from_secs(max_age)
+    {
+        return false;
+    }
+    age < freshness.lifetime
+}
+
+/// Header names listed in `Vary`, lower-cased; `None` for `Vary: *`, which
+/// no later request can match.
+#[must_use]
+pub fn vary_names(resp_headers: &HeaderMap) -> Option<Vec<HeaderName>> {
+    let mut names = Vec::new();
+    let values = resp_headers
+        .get_all(header::VARY)
+        .iter()
+        .filter_map(|v| v.to_str().ok());
+    for name in values.flat_map(|v| v.split(',')).map(str::trim) {
+        if name == "*" {
+            return None;
+        }
+        if let Ok(name) = HeaderName::from_bytes(name.as_bytes())
+            && !names.contains(&name)
+        {
+            names.push(name);
+        }
+    }
+    Some(names)
+}
+
+/// Whether the response can be revalidated with a conditional request.
+#[must_use]
+pub fn has_validator(resp_headers: &HeaderMap) -> bool {
+    resp_headers.contains_key(header::ETAG) || resp_headers.contains_key(header::LAST_MODIFIED)
+}
+
+// ---------------------------------------------------------------------------
+// Cache key
+// ---------------------------------------------------------------------------
+
+/// Inputs for [`build_cache_key`].
+pub struct CacheKeyContext<'a> {
+    pub sharing: SharingMode,
+    /// Bumped when the route's entries are purged, orphaning older keys.
+    pub generation: u64,
+    pub route_id: Uuid,
+    /// Tenant that owns the route.
+    pub route_tenant_id: Uuid,
+    /// Tenant the request is made for.
+    pub tenant_id: Uuid,
+    /// Set when the response is produced with per-user credentials.
+    pub subject_id: Option<Uuid>,
+    /// The auth plugin injected a credential not owned by the route tenant.
+    pub foreign_credentials: bool,
+    pub upstream_id: Uuid,
+    pub method: &'a str,
+    /// Upstream path and query string, as sent to the upstream.
+    pub target: &'a str,
+}
+
+/// Build a response cache key.
+///
+/// Key format:
+/// `oagw:cache:route:{route_id}:{generation}:tenant:{tenant_id}:subject:{subject_id|-}:upstream:{upstream_id}:{method}:{target}`
+///
+/// The tenant segment is the requesting tenant for `private` sharing and the
+/// route owner for `inherit`/`enforce`, so descendants inheriting a shared
+/// route also share its entries. Responses obtained with a credential the
+/// route owner does not own (e.g. a descendant's own secret) are keyed by
+/// the requesting tenant whatever the sharing mode.
+#[must_use]
+pub fn build_cache_key(ctx: &CacheKeyContext<'_>) -> String {
+    let tenant_id = match (ctx.sharing, ctx.foreign_credentials) {
+        (SharingMode::Private, _) | (_, true) => ctx.tenant_id,
+        (SharingMode::Inherit | SharingMode::Enforce, false) => ctx.route_tenant_id,
+    };
+    let subject = ctx
+        .subject_id
+        .map_or_else(|| "-".to_owned(), |id| id.to_string());
+    format!(
+        "oagw:cache:route:{}:{}:tenant:{tenant_id}:subject:{subject}:upstream:{}:{}:{}",
+        ctx.route_id, ctx.generation, ctx.upstream_id, ctx.method, ctx.target
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.append(*name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    fn key_ctx(sharing: SharingMode, tenant_id: Uuid) -> CacheKeyContext<'static> {
+        CacheKeyContext {
+            sharing,
+            generation: 0,
+            route_id: Uuid::nil(),
+            route_tenant_id: Uuid::from_u128(1),
+            tenant_id,
+            subject_id: None,
+            foreign_credentials: false,
+            upstream_id: Uuid::nil(),
+            method: "GET",
+            target: "/v1/models",
+        }
+    }
+
+    #[test]
+    fn validate_rejects_out_of_range_values() {
+        let ok = CacheConfig::default();
+        assert!(validate_cache_config(&ok).is_ok());
+
+        let zero = CacheConfig {
+            max_object_bytes: 0,
+            ..ok
+        };
+        assert!(validate_cache_config(&zero).is_err());
+
+        let huge = CacheConfig {
+            max_object_bytes: MAX_CACHE_OBJECT_BYTES + 1,
+            ..ok
+        };
+        assert!(validate_cache_config(&huge).is_err());
+
+        let long_ttl = CacheConfig {
+            default_ttl_secs: MAX_CACHE_DEFAULT_TTL_SECS + 1,
+            ..ok
+        };
+        assert!(validate_cache_config(&long_ttl).is_err());
+    }
+
+    #[test]
+    fn parses_cache_control_directives() {
+        let cc = CacheControl::parse(&headers(&[
+            ("cache-control", "public, Max-Age=60"),
+            ("cache-control", "s-maxage=\"120\", must-revalidate"),
+        ]));
+        assert_eq!(cc.max_age, Some(60));
+        assert_eq!(cc.s_maxage, Some(120));
+        assert!(!cc.no_store && !cc.no_cache && !cc.private);
+
+        let invalid = CacheControl::parse(&headers(&[("cache-control", "max-age=soon")]));
+        assert_eq!(invalid.max_age, Some(0));
+    }
+
+    #[test]
+    fn only_plain_gets_are_cacheable_requests() {
+        assert!(is_cacheable_request(&http::Method::GET, &HeaderMap::new()));
+        assert!(!is_cacheable_request(
+            &http::Method::POST,
+            &HeaderMap::new()
+        ));
+        assert!(!is_cacheable_request(
+            &http::Method::HEAD,
+            &HeaderMap::new()
+        ));
+        assert!(!is_cacheable_request(
+            &http::Method::GET,
+            &headers(&[("if-none-match", "\"v1\"")])
+        ));
+        assert!(!is_cacheable_request(
+            &http::Method::GET,
+            &headers(&[("range", "bytes=0-10")])
+        ));
+        assert!(!is_cacheable_request(
+            &http::Method::GET,
+            &headers(&[("cache-control", "no-store")])
+        ));
+    }
+
+    #[test]
+    fn freshness_prefers_s_maxage_and_records_age() {
+        let f = response_freshness(
+            200,
+            &headers(&[("cache-control", "max-age=60, s-maxage=300"), ("age", "10")]),
+            Duration::ZERO,
+        )
+        .unwrap();
+        assert_eq!(f.lifetime, Duration::from_secs(300));
+        assert_eq!(f.initial_age, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn default_ttl_applies_without_explicit_lifetime() {
+        let f = response_freshness(200, &HeaderMap::new(), Duration::from_secs(30)).unwrap();
+        assert_eq!(f.lifetime, Duration::from_secs(30));
+        assert!(response_freshness(200, &HeaderMap::new(), Duration::ZERO).is_none());
+    }
+
+    #[test]
+    fn uncacheable_responses_are_not_stored() {
+        let ttl = Duration::from_secs(60);
+        assert!(response_freshness(500, &HeaderMap::new(), ttl).is_none());
+        assert!(response_freshness(302, &HeaderMap::new(), ttl).is_none());
+        for h in [
+            ("cache-control", "no-store"),
+            ("cache-control", "private, max-age=60"),
+            ("set-cookie", "session=abc"),
+            ("vary", "*"),
+        ] {
+            assert!(
+                response_freshness(200, &headers(&[h]), ttl).is_none(),
+                "{h:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn no_cache_response_needs_a_validator() {
+        assert!(
+            response_freshness(
+                200,
+                &headers(&[("cache-control", "no-cache")]),
+                Duration::from_secs(60)
+            )
+            .is_none()
+        );
+        let f = response_freshness(
+            200,
+            &headers(&[("cache-control", "no-cache"), ("etag", "\"v1\"")]),
+            Duration::from_secs(60),
+        )
+        .unwrap();
+        assert_eq!(f.lifetime, Duration::ZERO);
+    }
+
+    #[test]
+    fn request_directives_tighten_freshness() {
+        let f = Freshness {
+            lifetime: Duration::from_secs(60),
+            initial_age: Duration::ZERO,
+        };
+        let none = CacheControl::default();
+        assert!(is_fresh(&f, Duration::from_secs(30), &none));
+        assert!(!is_fresh(&f, Duration::from_secs(60), &none));
+
+        let no_cache = CacheControl {
+            no_cache: true,
+            ..none
+        };
+        assert!(!is_fresh(&f, Duration::ZERO, &no_cache));
+
+        let max_age = CacheControl {
+            max_age: Some(10),
+            ..none
+        };
+        assert!(!is_fresh(&f, Duration::from_secs(30), &max_age));
+        assert!(is_fresh(&f, Duration::from_secs(5), &max_age));
+    }
+
+    #[test]
+    fn vary_names_are_collected_and_star_rejected() {
+        let names = vary_names(&headers(&[
+            ("vary", "Accept-Language, accept"),
+            ("vary", "accept"),
+        ]))
+        .unwrap();
+        assert_eq!(names, vec![header::ACCEPT_LANGUAGE, header::ACCEPT]);
+        assert!(vary_names(&headers(&[("vary", "accept, *")])).is_none());
+    }
+
+    #[test]
+    fn private_sharing_keys_by_requesting_tenant() {
+        let a = build_cache_key(&key_ctx(SharingMode::Private, Uuid::from_u128(2)));
+        let b = build_cache_key(&key_ctx(SharingMode::Private, Uuid::from_u128(3)));
+        assert_ne!(a, b);
+        assert!(a.contains(&Uuid::from_u128(2).to_string()));
+    }
+
+    #[test]
+    fn inherited_sharing_keys_by_route_owner() {
+        for sharing in [SharingMode::Inherit, SharingMode::Enforce] {
+            let a = build_cache_key(&key_ctx(sharing, Uuid::from_u128(2)));
+            let b = build_cache_key(&key_ctx(sharing, Uuid::from_u128(3)));
+            assert_eq!(a, b);
+            assert!(a.contains(&Uuid::from_u128(1).to_string()));
+        }
+    }
+
+    #[test]
+    fn foreign_credentials_key_by_requesting_tenant() {
+        for sharing in [SharingMode::Inherit, SharingMode::Enforce] {
+            let foreign = |tenant_id| CacheKeyContext {
+                foreign_credentials: true,
+                ..key_ctx(sharing, tenant_id)
+            };
+            let a = build_cache_key(&foreign(Uuid::from_u128(2)));
+            let b = build_cache_key(&foreign(Uuid::from_u128(3)));
+            assert_ne!(a, b, "{sharing:?}");
+            assert!(a.contains(&format!("tenant:{}", Uuid::from_u128(2))));
+        }
+    }
+
+    #[test]
+    fn subject_and_generation_partition_keys() {
+        let base = key_ctx(SharingMode::Private, Uuid::from_u128(2));
+        let user = CacheKeyContext {
+            subject_id: Some(Uuid::from_u128(9)),
+            ..key_ctx(SharingMode::Private, Uuid::from_u128(2))
+        };
+        let purged = CacheKeyContext {
+            generation: 1,
+            ..key_ctx(SharingMode::Private, Uuid::from_u128(2))
+        };
+        let base = build_cache_key(&base);
+        assert_ne!(base, build_cache_key(&user));
+        assert_ne!(base, build_cache_key(&purged));
+    }
+}
@@ -0,0 +1,698 @@
+use std::time::Instant;
+
+use crate::domain::clock::now;
+use crate::domain::error::DomainError;
+use crate::domain::model::{
+    CircuitBreakerConfig, CircuitBreakerScope, Endpoint, FailureConditions, FailureRateConfig,
+};
+use dashmap::DashMap;
+use modkit_macros::domain_model;
+use uuid::Uuid;
+
+/// Result of one upstream call as seen by a circuit breaker.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallOutcome {
+    Success,
+    Failure,
+}
+
+/// Classify an upstream response against the failure conditions.
+///
+/// `gateway_error` marks responses synthesized by the proxy layer when the
+/// upstream could not be reached (`x-oagw-error-source: gateway`): a 504 is
+/// a timeout, anything else a connection error.
+#[must_use]
+pub fn classify_response(
+    conditions: &FailureConditions,
+    status: u16,
+    gateway_error: bool,
+) -> CallOutcome {
+    let failed = if gateway_error {
+        if status == 504 {
+            conditions.timeout
+        } else {
+            conditions.connection_error
+        }
+    } else {
+        conditions.status_codes.contains(&status)
+    };
+    if failed {
+        CallOutcome::Failure
+    } else {
+        CallOutcome::Success
+    }
+}
+
+/// Classify a data-plane error raised while talking to the upstream.
+///
+/// Returns `None` for errors that say nothing about upstream health (payload
+/// limits, guard rejections, ...); such calls are not counted.
+#[must_use]
+pub fn classify_error(conditions: &FailureConditions, err: &DomainError) -> Option<CallOutcome> {
+    let failed = match err {
+        DomainError::RequestTimeout { .. } | DomainError::ConnectionTimeout { .. } => {
+            conditions.timeout
+        }
+        DomainError::DownstreamError { .. }
+        | DomainError::LinkUnavailable { .. }
+        | DomainError::StreamAborted { .. }
+        | DomainError::ProtocolError { .. } => conditions.connection_error,
+        _ => return None,
+    };
+    Some(if failed {
+        CallOutcome::Failure
+    } else {
+        CallOutcome::Success
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+/// Validate a circuit breaker configuration at creation/update time.
+///
+/// Returns `Err(DomainError::Validation)` if the configuration is invalid.
+pub fn validate_circuit_breaker_config(config: &CircuitBreakerConfig) -> Result<(), DomainError> {
+    let positive = [
+        (
+            "circuit_breaker.failure_threshold",
+            config.failure_threshold,
+        ),
+        (
+            "circuit_breaker.success_threshold",
+            config.success_threshold,
+        ),
+        ("circuit_breaker.timeout_seconds", config.timeout_secs),
+        (
+            "circuit_breaker.half_open_max_requests",
+            config.half_open_max_requests,
+        ),
+    ];
+    for (field, value) in positive {
+        if value == 0 {
+            return Err(DomainError::validation_for(
+                field,
+                "INVALID_CIRCUIT_BREAKER",
+                format!("{field} must be at least 1"),
+            ));
+        }
+    }
+
+    if let Some(rate) = &config.failure_rate {
+        if !(rate.threshold > 0.0 && rate.threshold <= 1.0) {
+            return Err(DomainError::validation_for(
+                "circuit_breaker.failure_rate.threshold",
+                "INVALID_CIRCUIT_BREAKER",
+                format!(
+                    "failure_rate.threshold must be in (0.0, 1.0], got {}",
+                    rate.threshold
+                ),
+            ));
+        }
+        if rate.window_secs == 0 || rate.minimum_requests == 0 {
+            return Err(DomainError::validation_for(
+                "circuit_breaker.failure_rate",
+                "INVALID_CIRCUIT_BREAKER",
+                "failure_rate.window_seconds and failure_rate.minimum_requests must be at least 1",
+            ));
+        }
+    }
+
+    if let Some(code) = config
+        .failure_conditions
+        .status_codes
+        .iter()
+        .find(|c| !(100..=599).contains(*c))
+    {
+        return Err(DomainError::validation_for(
+            "circuit_breaker.failure_conditions.status_codes",
+            "INVALID_CIRCUIT_BREAKER",
+            format!("invalid HTTP status code {code}"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Build a circuit breaker key.
+///
+/// Key format: `oagw:circuit:upstream:{upstream_id}:global` or
+/// `oagw:circuit:upstream:{upstream_id}:endpoint:{host}:{port}`, so all
+/// breakers of an upstream share a prefix (same layout as rate-limit keys).
+#[must_use]
+pub fn build_circuit_breaker_key(
+    upstream_id: Uuid,
+    scope: CircuitBreakerScope,
+    endpoint: &Endpoint,
+) -> String {
+    match scope {
+        CircuitBreakerScope::Global => format!("oagw:circuit:upstream:{upstream_id}:global"),
+        CircuitBreakerScope::PerEndpoint => format!(
+            "oagw:circuit:upstream:{upstream_id}:endpoint:{}:{}",
+            endpoint.host.to_ascii_lowercase(),
+            endpoint.port
+        ),
+    }
+}
+
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Closed,
+    Open { opened_at: Instant },
+    HalfOpen { in_flight: u32, successes: u32 },
+}
+
+/// Per-second request/failure counters over a rolling window, used by the
+/// failure-rate trigger.
+#[domain_model]
+struct RollingCounts {
+    origin: Instant,
+    /// `(second since origin, requests, failures)` ring buffer.
+    slots: Vec<(u64, u32, u32)>,
+}
+
+impl RollingCounts {
+    fn new(window_secs: u32) -> Self {
+        Self {
+            origin: now(),
+            slots: vec![(u64::MAX, 0, 0); window_secs.max(1) as usize],
+        }
+    }
+
+    fn current_second(&self) -> u64 {
+        now().duration_since(self.origin).as_secs()
+    }
+
+    fn add(&mut self, failed: bool) {
+        let second = self.current_second();
+        let len = self.slots.len() as u64;
+        let slot = &mut self.slots[(second % len) as usize];
+        if slot.0 != second {
+            *slot = (second, 0, 0);
+        }
+        slot.1 = slot.1.saturating_add(1);
+        if failed {
+            slot.2 = slot.2.saturating_add(1);
+        }
+    }
+
+    /// `(requests, failures)` within the window.
+    fn totals(&self) -> (u32, u32) {
+        let second = self.current_second();
+        let len = self.slots.len() as u64;
+        self.slots
+            .iter()
+            .filter(|(s, _, _)| *s != u64::MAX && *s + len > second)
+            .fold((0u32, 0u32), |(t, f), (_, rt, rf)| {
+                (t.saturating_add(*rt), f.saturating_add(*rf))
+            })
+    }
+
+    fn trips(&self, rate: &FailureRateConfig) -> bool {
+        let (total, failures) = self.totals();
+        total >= rate.minimum_requests.max(1)
+            && f64::from(failures) / f64::from(total) >= rate.threshold
+    }
+}
+
+#[domain_model]
+struct Breaker {
+    /// Config the breaker was built from; a changed config resets it.
+    config: CircuitBreakerConfig,
+    state: State,
+    /// Bumped on every state transition so outcomes of calls admitted under
+    /// an earlier state are not applied to the current one.
+    generation: u64,
+    consecutive_failures: u32,
+    window: Option<RollingCounts>,
+}
+
+impl Breaker {
+    fn new(config: &CircuitBreakerConfig) -> Self {
+        Self {
+            config: config.clone(),
+            state: State::Closed,
+            generation: 0,
+            consecutive_failures: 0,
+            window: config
+                .failure_rate
+                .as_ref()
+                .map(|r| RollingCounts::new(r.window_secs)),
+        }
+    }
+
+    fn transition(&mut self, state: State) {
+        self.state = state;
+        self.generation += 1;
+        self.consecutive_failures = 0;
+        if let Some(rate) = &self.config.failure_rate {
+            self.window = Some(RollingCounts::new(rate.window_secs));
+        }
+    }
+
+    fn open(&mut self) {
+        self.transition(State::Open { opened_at: now() });
+    }
+}
+
+/// Handle for one admitted upstream call.
+///
+/// Report the outcome with [`CircuitPermit::record`]. A permit dropped
+/// without an outcome (the request failed before reaching the upstream)
+/// only gives back its half-open probe slot.
+#[domain_model]
+pub struct CircuitPermit<'a> {
+    breakers: &'a CircuitBreakers,
+    key: String,
+    generation: u64,
+    probe: bool,
+    conditions: FailureConditions,
+    settled: bool,
+}
+
+impl CircuitPermit<'_> {
+    pub fn record(mut self, outcome: CallOutcome) {
+        self.settled = true;
+        self.breakers
+            .record(&self.key, self.generation, self.probe, Some(outcome));
+    }
+
+    /// Record the outcome of an upstream response (see [`classify_response`]).
+    pub fn record_response(self, status: u16, gateway_error: bool) {
+        let outcome = classify_response(&self.conditions, status, gateway_error);
+        self.record(outcome);
+    }
+
+    /// Record a data-plane error (see [`classify_error`]); errors that say
+    /// nothing about upstream health release the permit without counting.
+    pub fn record_error(self, err: &DomainError) {
+        if let Some(outcome) = classify_error(&self.conditions, err) {
+            self.record(outcome);
+        }
+    }
+}
+
+impl Drop for CircuitPermit<'_> {
+    fn drop(&mut self) {
+        if !self.settled {
+            self.breakers
+                .record(&self.key, self.generation, self.probe, None);
+        }
+    }
+}
+
+/// In-memory circuit breakers keyed by [`build_circuit_breaker_key`].
+///
+/// State machine: `Closed` → `Open` once `failure_threshold` consecutive
+/// failures (or the optional failure rate) are reached; `Open` rejects
+/// every call until `timeout_secs` elapsed, then lets up to
+/// `half_open_max_requests` probes through (`HalfOpen`);
+/// `success_threshold` successful probes close the circuit, a failed probe
+/// re-opens it.
+#[domain_model]
+pub struct CircuitBreakers {
+    breakers: DashMap<String, Breaker>,
+}
+
+impl CircuitBreakers {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            breakers: DashMap::new(),
+        }
+    }
+
+    /// Remove all breakers associated with an upstream (every endpoint).
+    pub fn remove_keys_for_upstream(&self, upstream_id: Uuid) {
+        let prefix = format!("oagw:circuit:upstream:{upstream_id}:");
+        self.breakers.retain(|k, _| !k.starts_with(&prefix));
+    }
+
+    /// Admit a call through the breaker at `key`.
+    ///
+    /// # Errors
+    /// Returns `DomainError::CircuitBreakerOpen` while the circuit is open, or
+    /// half-open with every probe slot taken.
+    pub fn try_acquire(
+        &self,
+        key: &str,
+        config: &CircuitBreakerConfig,
+        instance_uri: &str,
+    ) -> Result<CircuitPermit<'_>, DomainError> {
+        let mut entry = self
+            .breakers
+            .entry(key.to_string())
+            .and_modify(|b| {
+                if b.config != *config {
+                    *b = Breaker::new(config);
+                }
+            })
+            .or_insert_with(|| Breaker::new(config));
+        let breaker = &mut *entry;
+
+        if let State::Open { opened_at } = breaker.state {
+            let cool_down = u64::from(config.timeout_secs);
+            let elapsed = now().duration_since(opened_at).as_secs();
+            if elapsed < cool_down {
+                return Err(DomainError::CircuitBreakerOpen {
+                    detail: format!(
+                        "circuit breaker is open; upstream calls resume in {}s",
+                        cool_down - elapsed
+                    ),
+                    instance: instance_uri.to_string(),
+                });
+            }
+            breaker.transition(State::HalfOpen {
+                in_flight: 0,
+                successes: 0,
+            });
+        }
+
+        let probe = match &mut breaker.state {
+            State::Closed => false,
+            State::HalfOpen { in_flight, .. } if *in_flight < config.half_open_max_requests => {
+                *in_flight += 1;
+                true
+            }
+            _ => {
+                return Err(DomainError::CircuitBreakerOpen {
+                    detail: "circuit breaker is half-open and all probe requests are in flight"
+                        .to_string(),
+                    instance: instance_uri.to_string(),
+                });
+            }
+        };
+
+        Ok(CircuitPermit {
+            breakers: self,
+            key: key.to_string(),
+            generation: breaker.generation,
+            probe,
+            conditions: config.failure_conditions.clone(),
+            settled: false,
+        })
+    }
+
+    fn record(&self, key: &str, generation: u64, probe: bool, outcome: Option<CallOutcome>) {
+        let Some(mut entry) = self.breakers.get_mut(key) else {
+            return;
+        };
+        let breaker = &mut *entry;
+        if breaker.generation != generation {
+            return;
+        }
+        let success_threshold = breaker.config.success_threshold;
+        let failure_threshold = breaker.config.failure_threshold;
+
+        match (&mut breaker.state, outcome) {
+            (State::Closed, None) => {}
+            (State::Closed, Some(outcome)) => {
+                let failed = outcome == CallOutcome::Failure;
+                if failed {
+                    breaker.consecutive_failures += 1;
+                } else {
+                    breaker.consecutive_failures = 0;
+                }
+                let rate_trips = match (&mut breaker.window, &breaker.config.failure_rate) {
+                    (Some(window), Some(rate)) => {
+                        window.add(failed);
+                        failed && window.trips(rate)
+                    }
+                    _ => false,
+                };
+                if failed && (breaker.consecutive_failures >= failure_threshold || rate_trips) {
+                    tracing::warn!(key, "circuit breaker opened");
+                    breaker.open();
+                }
+            }
+            (State::HalfOpen { in_flight, .. }, None) if probe => {
+                *in_flight = in_flight.saturating_sub(1);
+            }
+            (State::HalfOpen { .. }, Some(CallOutcome::Failure)) if probe => {
+                tracing::warn!(key, "circuit breaker probe failed; re-opening");
+                breaker.open();
+            }
+            (
+                State::HalfOpen {
+                    in_flight,
+                    successes,
+                },
+                Some(CallOutcome::Success),
+            ) if probe => {
+                *in_flight = in_flight.saturating_sub(1);
+                *successes += 1;
+                if *successes >= success_threshold {
+                    tracing::info!(key, "circuit breaker closed");
+                    breaker.transition(State::Closed);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for CircuitBreakers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::domain::clock::{clear_mock_time, set_mock_time};
+    use crate::domain::model::{Scheme, SharingMode};
+
+    use super::*;
+
+    fn make_config(failure_threshold: u32) -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            sharing: SharingMode::Private,
+            enabled: true,
+            failure_threshold,
+            failure_rate: None,
+            success_threshold: 2,
+            timeout_secs: 30,
+            half_open_max_requests: 1,
+            failure_conditions: FailureConditions::default(),
+            scope: CircuitBreakerScope::Global,
+        }
+    }
+
+    fn fail(cb: &CircuitBreakers, key: &str, config: &CircuitBreakerConfig) {
+        cb.try_acquire(key, config, "/test")
+            .unwrap()
+            .record(CallOutcome::Failure);
+    }
+
+    fn succeed(cb: &CircuitBreakers, key: &str, config: &CircuitBreakerConfig) {
+        cb.try_acquire(key, config, "/test")
+            .unwrap()
+            .record(CallOutcome::Success);
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let cb = CircuitBreakers::new();
+        let config = make_config(3);
+        fail(&cb, "k", &config);
+        fail(&cb, "k", &config);
+        assert!(cb.try_acquire("k", &config, "/test").is_ok());
+        fail(&cb, "k", &config);
+
+        let err = cb.try_acquire("k", &config, "/test").err().unwrap();
+        assert!(matches!(err, DomainError::CircuitBreakerOpen { .. }));
+    }
+
+    #[test]
+    fn success_resets_consecutive_failures() {
+        let cb = CircuitBreakers::new();
+        let config = make_config(2);
+        fail(&cb, "k", &config);
+        succeed(&cb, "k", &config);
+        fail(&cb, "k", &config);
+        assert!(cb.try_acquire("k", &config, "/test").is_ok());
+    }
+
+    #[test]
+    fn half_open_after_cool_down_and_closes_on_successful_probes() {
+        let start = Instant::now();
+        set_mock_time(start);
+        let cb = CircuitBreakers::new();
+        let config = make_config(1);
+        fail(&cb, "k", &config);
+        assert!(cb.try_acquire("k", &config, "/test").is_err());
+
+        set_mock_time(start + Duration::from_secs(30));
+        let probe = cb.try_acquire("k", &config, "/test").unwrap();
+        // Only one probe may be in flight.
+        assert!(cb.try_acquire("k", &config, "/test").is_err());
+        probe.record(CallOutcome::Success);
+        succeed(&cb, "k", &config);
+
+        // Closed again: calls flow freely.
+        assert!(cb.try_acquire("k", &config, "/test").is_ok());
+        assert!(cb.try_acquire("k", &config, "/test").is_ok());
+        clear_mock_time();
+    }
+
+    #[test]
+    fn failed_probe_reopens_circuit() {
+        let start = Instant::now();
+        set_mock_time(start);
+        let cb = CircuitBreakers::new();
+        let config = make_config(1);
+        fail(&cb, "k", &config);
+
+        set_mock_time(start + Duration::from_secs(31));
+        fail(&cb, "k", &config);
+        assert!(cb.try_acquire("k", &config, "/test").is_err());
+
+        set_mock_time(start + Duration::from_secs(61));
+        assert!(cb.try_acquire("k", &config, "/test").is_ok());
+        clear_mock_time();
+    }
+
+    #[test]
+    fn dropped_probe_releases_its_slot() {
+        let start = Instant::now();
+        set_mock_time(start);
+        let cb = CircuitBreakers::new();
+        let config = make_config(1);
+        fail(&cb, "k", &config);
+
+        set_mock_time(start + Duration::from_secs(30));
+        drop(cb.try_acquire("k", &config, "/test").unwrap());
+        assert!(cb.try_acquire("k", &config, "/test").is_ok());
+        clear_mock_time();
+    }
+
+    #[test]
+    fn failure_rate_opens_circuit() {
+        let cb = CircuitBreakers::new();
+        let mut config = make_config(100);
+        config.failure_rate = Some(FailureRateConfig {
+            threshold: 0.5,
+            minimum_requests: 4,
+            window_secs: 60,
+        });
+        succeed(&cb, "k", &config);
+        fail(&cb, "k", &config);
+        succeed(&cb, "k", &config);
+        // 2 of 4 failed → 50%.
+        fail(&cb, "k", &config);
+        assert!(cb.try_acquire("k", &config, "/test").is_err());
+    }
+
+    #[test]
+    fn failure_rate_needs_minimum_requests() {
+        let cb = CircuitBreakers::new();
+        let mut config = make_config(100);
+        config.failure_rate = Some(FailureRateConfig {
+            threshold: 0.5,
+            minimum_requests: 10,
+            window_secs: 60,
+        });
+        fail(&cb, "k", &config);
+        fail(&cb, "k", &config);
+        assert!(cb.try_acquire("k", &config, "/test").is_ok());
+    }
+
+    #[test]
+    fn config_change_resets_breaker() {
+        let cb = CircuitBreakers::new();
+        let config = make_config(1);
+        fail(&cb, "k", &config);
+        assert!(cb.try_acquire("k", &config, "/test").is_err());
+
+        let relaxed = make_config(5);
+        assert!(cb.try_acquire("k", &relaxed, "/test").is_ok());
+    }
+
+    #[test]
+    fn classify_response_uses_conditions() {
+        let conditions = FailureConditions::default();
+        assert_eq!(
+            classify_response(&conditions, 503, false),
+            CallOutcome::Failure
+        );
+        assert_eq!(
+            classify_response(&conditions, 404, false),
+            CallOutcome::Success
+        );
+
+        let lenient = FailureConditions {
+            status_codes: vec![],
+            timeout: false,
+            connection_error: true,
+        };
+        assert_eq!(classify_response(&lenient, 504, true), CallOutcome::Success);
+        assert_eq!(classify_response(&lenient, 502, true), CallOutcome::Failure);
+    }
+
+    #[test]
+    fn classify_error_ignores_non_upstream_errors() {
+        let conditions = FailureConditions::default();
+        let timeout = DomainError::RequestTimeout {
+            detail: "t".into(),
+            instance: "/test".into(),
+        };
+        assert_eq!(
+            classify_error(&conditions, &timeout),
+            Some(CallOutcome::Failure)
+        );
+        let too_large = DomainError::PayloadTooLarge {
+            detail: "p".into(),
+            instance: "/test".into(),
+        };
+        assert_eq!(classify_error(&conditions, &too_large), None);
+    }
+
+    #[test]
+    fn validate_rejects_zero_thresholds_and_bad_rates() {
+        assert!(validate_circuit_breaker_config(&make_config(5)).is_ok());
+        assert!(validate_circuit_breaker_config(&make_config(0)).is_err());
+
+        let mut config = make_config(5);
+        config.failure_rate = Some(FailureRateConfig {
+            threshold: 1.5,
+            minimum_requests: 10,
+            window_secs: 60,
+        });
+        assert!(validate_circuit_breaker_config(&config).is_err());
+
+        let mut config = make_config(5);
+        config.failure_conditions.status_codes = vec![700];
+        assert!(validate_circuit_breaker_config(&config).is_err());
+    }
+
+    #[test]
+    fn keys_are_scoped_per_upstream_and_endpoint() {
+        let id = Uuid::new_v4();
+        let ep = Endpoint {
+            scheme: Scheme::Https,
+            host: "API.example.com".into(),
+            port: 443,
+            weight: 1,
+        };
+        assert_eq!(
+            build_circuit_breaker_key(id, CircuitBreakerScope::Global, &ep),
+            format!("oagw:circuit:upstream:{id}:global")
+        );
+        assert_eq!(
+            build_circuit_breaker_key(id, CircuitBreakerScope::PerEndpoint, &ep),
+            format!("oagw:circuit:upstream:{id}:endpoint:api.example.com:443")
+        );
+
+        let cb = CircuitBreakers::new();
+        let config = make_config(1);
+        let key = build_circuit_breaker_key(id, CircuitBreakerScope::Global, &ep);
+        fail(&cb, &key, &config);
+        cb.remove_keys_for_upstream(id);
+        assert!(cb.try_acquire(&key, &config, "/test").is_ok());
+    }
+}
@@ -0,0 +1,33 @@
+//! Clock abstraction — allows deterministic time control in tests.
+//!
+//! Domain modules read the time through [`now`]. Under `cfg(test)` it returns
+//! the instant set with [`set_mock_time`] on the current thread, if any.
+
+use std::time::Instant;
+
+#[cfg(not(test))]
+pub(crate) fn now() -> Instant {
+    Instant::now()
+}
+
+#[cfg(test)]
+thread_local! {
+    static MOCK_NOW: std::cell::Cell<Option<Instant>> = const { std::cell::Cell::new(None) };
+}
+
+#[cfg(test)]
+pub(crate) fn now() -> Instant {
+    MOCK_NOW.with(|cell| cell.get().unwrap_or_else(Instant::now))
+}
+
+/// Pin [`now`] to `t` on the current thread.
+#[cfg(test)]
+pub(crate) fn set_mock_time(t: Instant) {
+    MOCK_NOW.with(|cell| cell.set(Some(t)));
+}
+
+/// Let [`now`] follow the real clock again on the current thread.
+#[cfg(test)]
+pub(crate) fn clear_mock_time() {
+    MOCK_NOW.with(|cell| cell.set(None));
+}
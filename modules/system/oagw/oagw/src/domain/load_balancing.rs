@@ -0,0 +1,440 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::domain::clock::now;
+use crate::domain::error::DomainError;
+use crate::domain::model::{
+    Endpoint, HashKey, HealthCheckConfig, HealthProbe, LoadBalancing, OutlierDetectionConfig,
+    Server,
+};
+use dashmap::DashMap;
+use modkit_macros::domain_model;
+use uuid::Uuid;
+
+/// Upper bound for `Endpoint::weight`. Weighted round-robin expands each
+/// backend `weight` times, so the range is kept small.
+pub const MAX_ENDPOINT_WEIGHT: u32 = 100;
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+/// Validate endpoint weights, the balancing strategy and health checking of
+/// an upstream server at creation/update time.
+pub fn validate_server_config(server: &Server) -> Result<(), DomainError> {
+    if let Some((i, ep)) = server
+        .endpoints
+        .iter()
+        .enumerate()
+        .find(|(_, ep)| !(1..=MAX_ENDPOINT_WEIGHT).contains(&ep.weight))
+    {
+        return Err(DomainError::validation_for(
+            "server.endpoints.weight",
+            "INVALID_ENDPOINT_WEIGHT",
+            format!(
+                "endpoint[{i}] weight must be between 1 and {MAX_ENDPOINT_WEIGHT}, got {}",
+                ep.weight
+            ),
+        ));
+    }
+
+    if let Some(LoadBalancing::ConsistentHash {
+        key: HashKey::Header(name),
+    }) = &server.load_balancing
+        && http::HeaderName::from_bytes(name.as_bytes()).is_err()
+    {
+        return Err(DomainError::validation_for(
+            "server.load_balancing.key",
+            "INVALID_LOAD_BALANCING",
+            format!("'{name}' is not a valid header name"),
+        ));
+    }
+
+    if let Some(ref hc) = server.health_check {
+        validate_health_check(hc)?;
+    }
+    Ok(())
+}
+
+fn validate_health_check(hc: &HealthCheckConfig) -> Result<(), DomainError> {
+    let minimums = [
+        ("server.health_check.interval_secs", hc.interval_secs),
+        ("server.health_check.timeout_ms", hc.timeout_ms),
+        (
+            "server.health_check.healthy_threshold",
+            hc.healthy_threshold,
+        ),
+        (
+            "server.health_check.unhealthy_threshold",
+            hc.unhealthy_threshold,
+        ),
+    ];
+    for (field, value) in minimums {
+        if value == 0 {
+            return Err(DomainError::validation_for(
+                field,
+                "INVALID_HEALTH_CHECK",
+                format!("{field} must be at least 1"),
+            ));
+        }
+    }
+
+    if let HealthProbe::Http {
+        path,
+        expected_status,
+    } = &hc.probe
+    {
+        if !path.starts_with('/') || path.parse::<http::uri::PathAndQuery>().is_err() {
+            return Err(DomainError::validation_for(
+                "server.health_check.path",
+                "INVALID_HEALTH_CHECK",
+                format!("health check path '{path}' must be an absolute path"),
+            ));
+        }
+        if expected_status.is_empty() || expected_status.iter().any(|c| !(100..=599).contains(c)) {
+            return Err(DomainError::validation_for(
+                "server.health_check.expected_status",
+                "INVALID_HEALTH_CHECK",
+                "expected_status must list HTTP status codes between 100 and 599",
+            ));
+        }
+    }
+
+    if let Some(ref od) = hc.outlier_detection {
+        if od.consecutive_failures == 0 || od.ejection_secs == 0 {
+            return Err(DomainError::validation_for(
+                "server.health_check.outlier_detection",
+                "INVALID_HEALTH_CHECK",
+                "outlier_detection.consecutive_failures and ejection_secs must be at least 1",
+            ));
+        }
+        if od.max_ejection_percent > 100 {
+            return Err(DomainError::validation_for(
+                "server.health_check.outlier_detection.max_ejection_percent",
+                "INVALID_HEALTH_CHECK",
+                format!(
+                    "max_ejection_percent must be at most 100, got {}",
+                    od.max_ejection_percent
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Selection helpers
+// ---------------------------------------------------------------------------
+
+fn endpoint_key(upstream_id: Uuid, endpoint: &Endpoint) -> (Uuid, String) {
+    (
+        upstream_id,
+        format!("{}:{}", endpoint.normalized_host(), endpoint.port),
+    )
+}
+
+/// Index of the candidate with the fewest in-flight requests per unit of
+/// weight. `loads` holds `(in_flight, weight)` pairs; the scan starts at
+/// `start` so ties rotate instead of piling onto the first candidate.
+#[must_use]
+pub fn pick_least_loaded(loads: &[(usize, u32)], start: usize) -> Option<usize> {
+    let n = loads.len();
+    (0..n).map(|i| (start + i) % n).min_by(|&a, &b| {
+        let (in_flight_a, weight_a) = loads[a];
+        let (in_flight_b, weight_b) = loads[b];
+        // in_flight_a / weight_a vs in_flight_b / weight_b, without division.
+        (in_flight_a as u64 * u64::from(weight_b.max(1)))
+            .cmp(&(in_flight_b as u64 * u64::from(weight_a.max(1))))
+    })
+}
+
+/// Whether ejecting `ejected` of `total` endpoints stays within the
+/// configured cap. Past the cap ejections are ignored (panic mode).
+#[must_use]
+pub fn ejection_allowed(ejected: usize, total: usize, config: &OutlierDetectionConfig) -> bool {
+    ejected * 100 <= total * config.max_ejection_percent as usize
+}
+
+// ---------------------------------------------------------------------------
+// Passive outlier detection
+// ---------------------------------------------------------------------------
+
+#[domain_model]
+#[derive(Default)]
+struct OutlierState {
+    consecutive_failures: u32,
+    ejected_until: Option<Instant>,
+}
+
+/// Per-node passive health of upstream endpoints, fed by live traffic.
+#[domain_model]
+pub struct OutlierDetector {
+    states: DashMap<(Uuid, String), OutlierState>,
+}
+
+impl OutlierDetector {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            states: DashMap::new(),
+        }
+    }
+
+    /// Record the outcome of one request sent to `endpoint`.
+    pub fn record(
+        &self,
+        upstream_id: Uuid,
+        endpoint: &Endpoint,
+        success: bool,
+        config: &OutlierDetectionConfig,
+    ) {
+        let mut state = self
+            .states
+            .entry(endpoint_key(upstream_id, endpoint))
+            .or_default();
+        if success {
+            state.consecutive_failures = 0;
+            return;
+        }
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= config.consecutive_failures {
+            state.consecutive_failures = 0;
+            state.ejected_until =
+                Some(now() + Duration::from_secs(u64::from(config.ejection_secs)));
+            tracing::warn!(
+                %upstream_id,
+                host = %endpoint.host,
+                port = endpoint.port,
+                ejection_secs = config.ejection_secs,
+                "endpoint ejected after consecutive failures"
+            );
+        }
+    }
+
+    /// Whether `endpoint` is currently ejected from selection.
+    #[must_use]
+    pub fn is_ejected(&self, upstream_id: Uuid, endpoint: &Endpoint) -> bool {
+        self.states
+            .get(&endpoint_key(upstream_id, endpoint))
+            .and_then(|s| s.ejected_until)
+            .is_some_and(|until| now() < until)
+    }
+
+    /// Drop all state of a deleted or reconfigured upstream.
+    pub fn remove_upstream(&self, upstream_id: Uuid) {
+        self.states.retain(|(id, _), _| *id != upstream_id);
+    }
+}
+
+impl Default for OutlierDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// In-flight accounting
+// ---------------------------------------------------------------------------
+
+/// Per-node count of requests awaiting an upstream response, per endpoint.
+#[domain_model]
+pub struct InFlightCounters {
+    counts: DashMap<(Uuid, String), Arc<AtomicUsize>>,
+}
+
+impl InFlightCounters {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            counts: DashMap::new(),
+        }
+    }
+
+    /// Count a request against `endpoint` until the guard is dropped.
+    #[must_use]
+    pub fn acquire(&self, upstream_id: Uuid, endpoint: &Endpoint) -> InFlightGuard {
+        let count = self
+            .counts
+            .entry(endpoint_key(upstream_id, endpoint))
+            .or_default()
+            .clone();
+        count.fetch_add(1, Ordering::Relaxed);
+        InFlightGuard { count }
+    }
+
+    #[must_use]
+    pub fn count(&self, upstream_id: Uuid, endpoint: &Endpoint) -> usize {
+        self.counts
+            .get(&endpoint_key(upstream_id, endpoint))
+            .map_or(0, |c| c.load(Ordering::Relaxed))
+    }
+
+    /// Drop the counters of a deleted or reconfigured upstream. Outstanding
+    /// guards keep their own reference and release into the detached counter.
+    pub fn remove_upstream(&self, upstream_id: Uuid) {
+        self.counts.retain(|(id, _), _| *id != upstream_id);
+    }
+}
+
+impl Default for InFlightCounters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Holds one in-flight slot on an endpoint; released on drop.
+#[domain_model]
+#[derive(Debug)]
+pub struct InFlightGuard {
+    count: Arc<AtomicUsize>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.count.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::clock::{clear_mock_time, set_mock_time};
+    use crate::domain::model::Scheme;
+
+    fn ep(host: &str, weight: u32) -> Endpoint {
+        Endpoint {
+            scheme: Scheme::Https,
+            host: host.to_string(),
+            port: 443,
+            weight,
+        }
+    }
+
+    fn server(endpoints: Vec<Endpoint>) -> Server {
+        Server {
+            endpoints,
+            load_balancing: None,
+            health_check: None,
+        }
+    }
+
+    #[test]
+    fn validate_rejects_out_of_range_weight() {
+        assert!(validate_server_config(&server(vec![ep("a.com", 1), ep("b.com", 100)])).is_ok());
+
+        let err = validate_server_config(&server(vec![ep("a.com", 0)])).unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::Validation {
+                reason: "INVALID_ENDPOINT_WEIGHT",
+                ..
+            }
+        ));
+        assert!(validate_server_config(&server(vec![ep("a.com", 101)])).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_bad_hash_header_and_health_check() {
+        let mut s = server(vec![ep("a.com", 1)]);
+        s.load_balancing = Some(LoadBalancing::ConsistentHash {
+            key: HashKey::Header("bad header".into()),
+        });
+        assert!(validate_server_config(&s).is_err());
+
+        let mut s = server(vec![ep("a.com", 1)]);
+        s.health_check = Some(HealthCheckConfig {
+            probe: HealthProbe::Http {
+                path: "healthz".into(),
+                expected_status: vec![200],
+            },
+            ..HealthCheckConfig::default()
+        });
+        assert!(validate_server_config(&s).is_err());
+
+        s.health_check = Some(HealthCheckConfig {
+            probe: HealthProbe::Http {
+                path: "/healthz".into(),
+                expected_status: vec![200, 204],
+            },
+            interval_secs: 0,
+            ..HealthCheckConfig::default()
+        });
+        assert!(validate_server_config(&s).is_err());
+
+        s.health_check = Some(HealthCheckConfig {
+            outlier_detection: Some(OutlierDetectionConfig {
+                max_ejection_percent: 150,
+                ..OutlierDetectionConfig::default()
+            }),
+            ..HealthCheckConfig::default()
+        });
+        assert!(validate_server_config(&s).is_err());
+    }
+
+    #[test]
+    fn least_loaded_respects_weight_and_rotates_ties() {
+        // b has twice the in-flight of a but three times the weight.
+        assert_eq!(pick_least_loaded(&[(2, 1), (4, 3)], 0), Some(1));
+        // All idle: the start offset decides.
+        assert_eq!(pick_least_loaded(&[(0, 1), (0, 1), (0, 1)], 0), Some(0));
+        assert_eq!(pick_least_loaded(&[(0, 1), (0, 1), (0, 1)], 2), Some(2));
+        assert_eq!(pick_least_loaded(&[], 0), None);
+    }
+
+    #[test]
+    fn ejection_cap_limits_share_of_endpoints() {
+        let config = OutlierDetectionConfig::default(); // 50%
+        assert!(ejection_allowed(1, 2, &config));
+        assert!(!ejection_allowed(2, 2, &config));
+        assert!(!ejection_allowed(2, 3, &config));
+    }
+
+    #[test]
+    fn outlier_ejected_after_consecutive_failures_then_restored() {
+        let t0 = Instant::now();
+        set_mock_time(t0);
+        let detector = OutlierDetector::new();
+        let upstream_id = Uuid::new_v4();
+        let a = ep("a.com", 1);
+        let config = OutlierDetectionConfig {
+            consecutive_failures: 2,
+            ejection_secs: 30,
+            max_ejection_percent: 50,
+        };
+
+        // A success in between resets the streak.
+        detector.record(upstream_id, &a, false, &config);
+        detector.record(upstream_id, &a, true, &config);
+        detector.record(upstream_id, &a, false, &config);
+        assert!(!detector.is_ejected(upstream_id, &a));
+
+        detector.record(upstream_id, &a, false, &config);
+        assert!(detector.is_ejected(upstream_id, &a));
+        assert!(!detector.is_ejected(upstream_id, &ep("b.com", 1)));
+
+        set_mock_time(t0 + Duration::from_secs(31));
+        assert!(!detector.is_ejected(upstream_id, &a));
+
+        detector.record(upstream_id, &a, false, &config);
+        detector.record(upstream_id, &a, false, &config);
+        detector.remove_upstream(upstream_id);
+        assert!(!detector.is_ejected(upstream_id, &a));
+        clear_mock_time();
+    }
+
+    #[test]
+    fn in_flight_guard_releases_on_drop() {
+        let counters = InFlightCounters::new();
+        let upstream_id = Uuid::new_v4();
+        let a = ep("a.com", 1);
+
+        let g1 = counters.acquire(upstream_id, &a);
+        let g2 = counters.acquire(upstream_id, &a);
+        assert_eq!(counters.count(upstream_id, &a), 2);
+        drop(g1);
+        assert_eq!(counters.count(upstream_id, &a), 1);
+        drop(g2);
+        assert_eq!(counters.count(upstream_id, &a), 0);
+    }
+}
@@ -1,10 +1,15 @@
+pub(crate) mod cache;
+pub(crate) mod circuit_breaker;
+pub(crate) mod clock;
 pub(crate) mod cors;
 pub(crate) mod error;
 pub(crate) mod gts_helpers;
+pub(crate) mod load_balancing;
 pub(crate) mod model;
 pub(crate) mod plugin;
 pub(crate) mod rate_limit;
 pub(crate) mod repo;
+pub(crate) mod retry;
 pub(crate) mod services;
 pub(crate) mod type_catalog;
 pub(crate) mod type_provisioning;
@@ -37,6 +37,8 @@ pub struct Endpoint {
     pub scheme: Scheme,
     pub host: String,
     pub port: u16,
+    /// Relative share of traffic for weighted strategies (1 = default).
+    pub weight: u32,
 }
 
 impl Endpoint {
@@ -86,6 +88,99 @@ impl Endpoint {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Server {
     pub endpoints: Vec<Endpoint>,
+    /// Endpoint selection strategy. `None` means weighted round-robin.
+    pub load_balancing: Option<LoadBalancing>,
+    /// Active probing and passive outlier ejection. `None` means a TCP
+    /// connect probe every 10 seconds and no ejection.
+    pub health_check: Option<HealthCheckConfig>,
+}
+
+/// How the balancer picks among healthy endpoints of a multi-endpoint upstream.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum LoadBalancing {
+    /// Weighted round-robin over `Endpoint::weight`.
+    #[default]
+    RoundRobin,
+    /// Fewest requests awaiting a response, relative to endpoint weight.
+    LeastInFlight,
+    /// Consistent (Ketama) hashing so the same key sticks to one endpoint.
+    ConsistentHash { key: HashKey },
+}
+
+/// Request attribute hashed by [`LoadBalancing::ConsistentHash`].
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashKey {
+    /// Value of the named request header. Requests without it are spread
+    /// randomly.
+    Header(String),
+    /// The caller's tenant ID.
+    Tenant,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthCheckConfig {
+    pub probe: HealthProbe,
+    pub interval_secs: u32,
+    /// Per-probe connect/read timeout.
+    pub timeout_ms: u32,
+    /// Consecutive successful probes before an endpoint is marked healthy.
+    pub healthy_threshold: u32,
+    /// Consecutive failed probes before an endpoint is marked unhealthy.
+    pub unhealthy_threshold: u32,
+    pub outlier_detection: Option<OutlierDetectionConfig>,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            probe: HealthProbe::Tcp,
+            interval_secs: 10,
+            timeout_ms: 1_000,
+            healthy_threshold: 1,
+            unhealthy_threshold: 1,
+            outlier_detection: None,
+        }
+    }
+}
+
+/// Active health probe sent to every resolved endpoint address.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthProbe {
+    /// TCP connect only.
+    Tcp,
+    /// `GET {path}` (Host and SNI of the endpoint); healthy when the status
+    /// is one of `expected_status`.
+    Http {
+        path: String,
+        expected_status: Vec<u16>,
+    },
+}
+
+/// Passive health: eject an endpoint from selection after consecutive
+/// failed requests (5xx, timeouts, connection errors) observed on live
+/// traffic.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlierDetectionConfig {
+    pub consecutive_failures: u32,
+    pub ejection_secs: u32,
+    /// Upper bound on the share of endpoints ejected at once; past it,
+    /// ejections are ignored so the upstream keeps serving.
+    pub max_ejection_percent: u32,
+}
+
+impl Default for OutlierDetectionConfig {
+    fn default() -> Self {
+        Self {
+            consecutive_failures: 5,
+            ejection_secs: 30,
+            max_ejection_percent: 50,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -260,6 +355,203 @@ pub struct CorsConfig {
     pub allow_credentials: bool,
 }
 
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitBreakerConfig {
+    pub sharing: SharingMode,
+    pub enabled: bool,
+    /// Consecutive failures that open a closed circuit.
+    pub failure_threshold: u32,
+    /// Optional failure-rate trigger, evaluated alongside `failure_threshold`.
+    pub failure_rate: Option<FailureRateConfig>,
+    /// Consecutive successful probes that close a half-open circuit.
+    pub success_threshold: u32,
+    /// Cool-down: seconds an open circuit rejects requests before probing.
+    pub timeout_secs: u32,
+    /// Probes allowed in flight while half-open.
+    pub half_open_max_requests: u32,
+    pub failure_conditions: FailureConditions,
+    pub scope: CircuitBreakerScope,
+}
+
+/// Opens the circuit when the share of failed requests within a rolling
+/// window reaches `threshold`, once at least `minimum_requests` were seen.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureRateConfig {
+    /// Failure ratio (0.0–1.0, exclusive of 0).
+    pub threshold: f64,
+    pub minimum_requests: u32,
+    pub window_secs: u32,
+}
+
+/// Which upstream outcomes count as failures.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureConditions {
+    pub status_codes: Vec<u16>,
+    pub timeout: bool,
+    pub connection_error: bool,
+}
+
+impl Default for FailureConditions {
+    fn default() -> Self {
+        Self {
+            status_codes: vec![500, 502, 503, 504],
+            timeout: true,
+            connection_error: true,
+        }
+    }
+}
+
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CircuitBreakerScope {
+    /// One breaker for the whole upstream.
+    #[default]
+    Global,
+    /// One breaker per upstream endpoint (host + port).
+    PerEndpoint,
+}
+
+// ---------------------------------------------------------------------------
+// TimeoutConfig / RetryConfig
+// ---------------------------------------------------------------------------
+
+/// Route-level upstream timeouts. Unset fields fall back to the gateway-wide
+/// `proxy_timeout_secs`.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TimeoutConfig {
+    /// TCP/TLS connect timeout, per attempt.
+    pub connect_ms: Option<u32>,
+    /// Maximum wait for a single read from the upstream, per attempt.
+    pub read_ms: Option<u32>,
+    /// Deadline for the response headers, spanning all retry attempts.
+    pub total_ms: Option<u32>,
+}
+
+/// Route-level retry policy for buffered (replayable) requests.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryConfig {
+    /// Total attempts, including the first one.
+    pub max_attempts: u32,
+    pub backoff: BackoffConfig,
+    pub retry_on: RetryConditions,
+    /// Also retry non-idempotent methods (POST, PATCH). Off by default.
+    pub retry_non_idempotent: bool,
+    pub budget: RetryBudget,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: BackoffConfig::default(),
+            retry_on: RetryConditions::default(),
+            retry_non_idempotent: false,
+            budget: RetryBudget::default(),
+        }
+    }
+}
+
+/// Exponential backoff between attempts: `initial_ms * multiplier^(n-1)`,
+/// capped at `max_ms`. With `jitter` the delay is drawn from `[d/2, d]`.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackoffConfig {
+    pub initial_ms: u32,
+    pub max_ms: u32,
+    pub multiplier: f64,
+    pub jitter: bool,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        Self {
+            initial_ms: 100,
+            max_ms: 2_000,
+            multiplier: 2.0,
+            jitter: true,
+        }
+    }
+}
+
+/// Which upstream outcomes are retried.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryConditions {
+    pub status_codes: Vec<u16>,
+    pub timeout: bool,
+    pub connection_error: bool,
+}
+
+impl Default for RetryConditions {
+    fn default() -> Self {
+        Self {
+            status_codes: vec![502, 503, 504],
+            timeout: true,
+            connection_error: true,
+        }
+    }
+}
+
+/// Caps retries to a share of the route's traffic so a failing upstream
+/// does not receive a retry storm: retries are allowed while they stay below
+/// `ratio` of the requests seen recently, with a floor of
+/// `min_retries_per_sec`.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryBudget {
+    pub ratio: f64,
+    pub min_retries_per_sec: u32,
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self {
+            ratio: 0.2,
+            min_retries_per_sec: 10,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CacheConfig
+// ---------------------------------------------------------------------------
+
+/// Route-level HTTP response cache. Entries are partitioned by tenant: the
+/// requesting tenant for `Private`, the route owner for `Inherit`/`Enforce`.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    pub sharing: SharingMode,
+    pub enabled: bool,
+    /// Freshness applied when the upstream gives no explicit lifetime;
+    /// 0 stores such responses only if they carry a validator.
+    pub default_ttl_secs: u32,
+    pub max_object_bytes: u32,
+    /// Cache responses obtained with per-user credentials, keyed by subject.
+    pub allow_user_credentials: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            sharing: SharingMode::Private,
+            enabled: true,
+            default_ttl_secs: 0,
+            max_object_bytes: 1024 * 1024,
+            allow_user_credentials: false,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // PluginBinding / PluginsConfig
 // ---------------------------------------------------------------------------
@@ -316,6 +608,15 @@ pub struct GrpcMatch {
     pub method: String,
 }
 
+impl GrpcMatch {
+    /// The HTTP/2 `:path` a call to this method is sent to:
+    /// `/{package.Service}/{Method}`.
+    #[must_use]
+    pub fn path(&self) -> String {
+        format!("/{}/{}", self.service, self.method)
+    }
+}
+
 #[domain_model]
 #[derive(Debug, Clone, PartialEq)]
 pub struct MatchRules {
@@ -337,6 +638,9 @@ pub struct Route {
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
     pub cors: Option<CorsConfig>,
+    pub timeouts: Option<TimeoutConfig>,
+    pub retry: Option<RetryConfig>,
+    pub cache: Option<CacheConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -356,6 +660,7 @@ pub struct Upstream {
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
     pub cors: Option<CorsConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub tags: Vec<String>,
 }
 
@@ -395,6 +700,7 @@ pub struct CreateUpstreamRequest {
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
     pub cors: Option<CorsConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub tags: Vec<String>,
     pub enabled: bool,
 }
@@ -410,6 +716,7 @@ pub struct UpdateUpstreamRequest {
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
     pub cors: Option<CorsConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub tags: Vec<String>,
     pub enabled: bool,
 }
@@ -426,6 +733,9 @@ pub struct CreateRouteRequest {
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
     pub cors: Option<CorsConfig>,
+    pub timeouts: Option<TimeoutConfig>,
+    pub retry: Option<RetryConfig>,
+    pub cache: Option<CacheConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -438,6 +748,9 @@ pub struct UpdateRouteRequest {
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
     pub cors: Option<CorsConfig>,
+    pub timeouts: Option<TimeoutConfig>,
+    pub retry: Option<RetryConfig>,
+    pub cache: Option<CacheConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use async_trait::async_trait;
 use modkit_macros::domain_model;
 use modkit_security::SecurityContext;
+use uuid::Uuid;
 
 // ---------------------------------------------------------------------------
 // Plugin errors
@@ -39,6 +40,11 @@ pub struct AuthContext {
     pub config: HashMap<String, String>,
     /// Security context of the calling subject.
     pub security_context: SecurityContext,
+    /// Set by the plugin when the injected credential belongs to the calling
+    /// user rather than the tenant, so responses must not be shared.
+    pub user_credentials: bool,
+    /// Tenants owning the credentials the plugin injected.
+    pub credential_tenant_ids: Vec<Uuid>,
 }
 
 /// Trait for outbound authentication plugins.
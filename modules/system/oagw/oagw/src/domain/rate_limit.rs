@@ -1,31 +1,13 @@
 use std::collections::HashSet;
 use std::time::{Instant, SystemTime};
 
+use crate::domain::clock::now;
 use crate::domain::error::DomainError;
 use crate::domain::model::{RateLimitAlgorithm, RateLimitConfig, RateLimitScope, Window};
 use dashmap::DashMap;
 use modkit_macros::domain_model;
 use uuid::Uuid;
 
-// ---------------------------------------------------------------------------
-// Clock abstraction — allows deterministic time control in tests.
-// ---------------------------------------------------------------------------
-
-#[cfg(not(test))]
-fn now() -> Instant {
-    Instant::now()
-}
-
-#[cfg(test)]
-thread_local! {
-    static MOCK_NOW: std::cell::Cell<Option<Instant>> = const { std::cell::Cell::new(None) };
-}
-
-#[cfg(test)]
-fn now() -> Instant {
-    MOCK_NOW.with(|cell| cell.get().unwrap_or_else(Instant::now))
-}
-
 /// Quota metadata returned on successful token consumption.
 #[domain_model]
 #[derive(Debug, Clone, Copy)]
@@ -510,6 +492,7 @@ impl RateLimiter {
 
 #[cfg(test)]
 mod tests {
+    use crate::domain::clock::{clear_mock_time, set_mock_time};
     use crate::domain::model::{
         BurstConfig, RateLimitAlgorithm, RateLimitScope, RateLimitStrategy, SustainedRate,
     };
@@ -1022,14 +1005,6 @@ mod tests {
         }
     }
 
-    fn set_mock_time(t: Instant) {
-        MOCK_NOW.with(|cell| cell.set(Some(t)));
-    }
-
-    fn clear_mock_time() {
-        MOCK_NOW.with(|cell| cell.set(None));
-    }
-
     #[test]
     fn sliding_window_allows_within_rate() {
         let limiter = RateLimiter::new();
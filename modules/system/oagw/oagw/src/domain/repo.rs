@@ -86,6 +86,7 @@ pub trait RouteRepository: Send + Sync {
 
     /// Find the best matching route for a given method and path.
     /// Match criteria: enabled=true, method matches, longest path prefix, highest priority.
+    /// gRPC routes match a POST to exactly `/{service}/{method}`.
     async fn find_matching(
         &self,
         tenant_id: Uuid,
@@ -0,0 +1,404 @@
+use std::time::{Duration, Instant};
+
+use crate::domain::clock::now;
+use crate::domain::error::DomainError;
+use crate::domain::model::{
+    BackoffConfig, RetryBudget, RetryConditions, RetryConfig, TimeoutConfig,
+};
+use dashmap::DashMap;
+use modkit_macros::domain_model;
+use uuid::Uuid;
+
+/// Upper bound for `retry.max_attempts`.
+pub const MAX_RETRY_ATTEMPTS: u32 = 10;
+
+/// Length of the fixed window the retry budget is accounted over.
+const BUDGET_WINDOW: Duration = Duration::from_secs(10);
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+/// Validate route timeouts at creation/update time.
+pub fn validate_timeout_config(config: &TimeoutConfig) -> Result<(), DomainError> {
+    let fields = [
+        ("timeouts.connect_ms", config.connect_ms),
+        ("timeouts.read_ms", config.read_ms),
+        ("timeouts.total_ms", config.total_ms),
+    ];
+    for (field, value) in fields {
+        if value == Some(0) {
+            return Err(DomainError::validation_for(
+                field,
+                "INVALID_TIMEOUT",
+                format!("{field} must be at least 1"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Validate a route retry policy at creation/update time.
+pub fn validate_retry_config(config: &RetryConfig) -> Result<(), DomainError> {
+    if !(1..=MAX_RETRY_ATTEMPTS).contains(&config.max_attempts) {
+        return Err(DomainError::validation_for(
+            "retry.max_attempts",
+            "INVALID_RETRY_POLICY",
+            format!(
+                "retry.max_attempts must be between 1 and {MAX_RETRY_ATTEMPTS}, got {}",
+                config.max_attempts
+            ),
+        ));
+    }
+
+    let backoff = &config.backoff;
+    if backoff.initial_ms > backoff.max_ms {
+        return Err(DomainError::validation_for(
+            "retry.backoff",
+            "INVALID_RETRY_POLICY",
+            format!(
+                "retry.backoff.initial_ms ({}) must not exceed max_ms ({})",
+                backoff.initial_ms, backoff.max_ms
+            ),
+        ));
+    }
+    if !(backoff.multiplier.is_finite() && backoff.multiplier >= 1.0) {
+        return Err(DomainError::validation_for(
+            "retry.backoff.multiplier",
+            "INVALID_RETRY_POLICY",
+            format!(
+                "retry.backoff.multiplier must be >= 1.0, got {}",
+                backoff.multiplier
+            ),
+        ));
+    }
+
+    if let Some(code) = config
+        .retry_on
+        .status_codes
+        .iter()
+        .find(|c| !(100..=599).contains(*c))
+    {
+        return Err(DomainError::validation_for(
+            "retry.retry_on.status_codes",
+            "INVALID_RETRY_POLICY",
+            format!("invalid HTTP status code {code}"),
+        ));
+    }
+
+    if !(0.0..=1.0).contains(&config.budget.ratio) {
+        return Err(DomainError::validation_for(
+            "retry.budget.ratio",
+            "INVALID_RETRY_POLICY",
+            format!(
+                "retry.budget.ratio must be in [0.0, 1.0], got {}",
+                config.budget.ratio
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Retry decisions
+// ---------------------------------------------------------------------------
+
+/// Whether a request with this method may be retried under `config`.
+///
+/// Only idempotent methods (RFC 9110 §9.2.2) are retried unless the route
+/// opts into `retry_non_idempotent`.
+#[must_use]
+pub fn method_allows_retry(config: &RetryConfig, method: &http::Method) -> bool {
+    config.retry_non_idempotent || method.is_idempotent()
+}
+
+/// Whether an upstream response should be retried.
+///
+/// `gateway_error` marks responses synthesized by the proxy layer
+/// (`x-oagw-error-source: gateway`): a 504 is a timeout, anything else a
+/// connection error. Upstream responses are matched against `status_codes`.
+#[must_use]
+pub fn is_retryable_response(
+    conditions: &RetryConditions,
+    status: u16,
+    gateway_error: bool,
+) -> bool {
+    if gateway_error {
+        if status == 504 {
+            conditions.timeout
+        } else {
+            conditions.connection_error
+        }
+    } else {
+        conditions.status_codes.contains(&status)
+    }
+}
+
+/// Whether a data-plane error raised while talking to the upstream should be
+/// retried.
+#[must_use]
+pub fn is_retryable_error(conditions: &RetryConditions, err: &DomainError) -> bool {
+    match err {
+        DomainError::RequestTimeout { .. } | DomainError::ConnectionTimeout { .. } => {
+            conditions.timeout
+        }
+        DomainError::DownstreamError { .. }
+        | DomainError::LinkUnavailable { .. }
+        | DomainError::StreamAborted { .. } => conditions.connection_error,
+        _ => false,
+    }
+}
+
+/// Delay before retry number `retry` (1-based).
+///
+/// `initial_ms * multiplier^(retry - 1)`, capped at `max_ms`. With jitter the
+/// delay is drawn uniformly from the upper half of that value so concurrent
+/// clients spread out without collapsing to zero.
+#[must_use]
+pub fn backoff_delay(backoff: &BackoffConfig, retry: u32) -> Duration {
+    let exponent = i32::try_from(retry.saturating_sub(1)).unwrap_or(i32::MAX);
+    let max_ms = f64::from(backoff.max_ms);
+    let base_ms = (f64::from(backoff.initial_ms) * backoff.multiplier.powi(exponent)).min(max_ms);
+    let delay_ms = if backoff.jitter {
+        // uuid v4 is already backed by the OS RNG; no need for another dependency.
+        let unit = (Uuid::new_v4().as_u128() % 1_000) as f64 / 1_000.0;
+        base_ms / 2.0 + base_ms / 2.0 * unit
+    } else {
+        base_ms
+    };
+    Duration::from_secs_f64(delay_ms / 1_000.0)
+}
+
+// ---------------------------------------------------------------------------
+// Retry budget
+// ---------------------------------------------------------------------------
+
+#[domain_model]
+struct BudgetWindow {
+    started: Instant,
+    requests: u64,
+    retries: u64,
+}
+
+impl BudgetWindow {
+    fn new() -> Self {
+        Self {
+            started: now(),
+            requests: 0,
+            retries: 0,
+        }
+    }
+
+    fn roll(&mut self) {
+        if now().duration_since(self.started) >= BUDGET_WINDOW {
+            *self = Self::new();
+        }
+    }
+}
+
+/// Per-route retry budgets (in-memory, per node).
+///
+/// Every proxied request is recorded; a retry is granted while the retries
+/// in the current window stay below `ratio * requests`, or below the
+/// `min_retries_per_sec` floor so low-traffic routes can still retry.
+#[domain_model]
+pub struct RetryBudgets {
+    windows: DashMap<Uuid, BudgetWindow>,
+}
+
+impl RetryBudgets {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            windows: DashMap::new(),
+        }
+    }
+
+    /// Count one client request against the route's budget.
+    pub fn record_request(&self, route_id: Uuid) {
+        let mut window = self
+            .windows
+            .entry(route_id)
+            .or_insert_with(BudgetWindow::new);
+        window.roll();
+        window.requests += 1;
+    }
+
+    /// Try to spend one retry from the route's budget.
+    pub fn try_withdraw(&self, route_id: Uuid, budget: &RetryBudget) -> bool {
+        let mut window = self
+            .windows
+            .entry(route_id)
+            .or_insert_with(BudgetWindow::new);
+        window.roll();
+        let proportional = (window.requests as f64 * budget.ratio).floor() as u64;
+        let floor = u64::from(budget.min_retries_per_sec) * BUDGET_WINDOW.as_secs();
+        if window.retries < proportional.max(floor) {
+            window.retries += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Drop the budget of a deleted or reconfigured route.
+    pub fn remove(&self, route_id: Uuid) {
+        self.windows.remove(&route_id);
+    }
+}
+
+impl Default for RetryBudgets {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::clock::{clear_mock_time, set_mock_time};
+
+    #[test]
+    fn only_idempotent_methods_retry_by_default() {
+        let mut config = RetryConfig::default();
+        assert!(method_allows_retry(&config, &http::Method::GET));
+        assert!(method_allows_retry(&config, &http::Method::PUT));
+        assert!(!method_allows_retry(&config, &http::Method::POST));
+        assert!(!method_allows_retry(&config, &http::Method::PATCH));
+
+        config.retry_non_idempotent = true;
+        assert!(method_allows_retry(&config, &http::Method::POST));
+    }
+
+    #[test]
+    fn retryable_responses_follow_conditions() {
+        let conditions = RetryConditions::default();
+        assert!(is_retryable_response(&conditions, 503, false));
+        assert!(!is_retryable_response(&conditions, 500, false));
+        assert!(!is_retryable_response(&conditions, 429, false));
+        // Gateway-synthesized 502 is a connection error.
+        assert!(is_retryable_response(&conditions, 502, true));
+
+        let no_timeouts = RetryConditions {
+            timeout: false,
+            ..RetryConditions::default()
+        };
+        assert!(!is_retryable_response(&no_timeouts, 504, true));
+        assert!(!is_retryable_error(
+            &no_timeouts,
+            &DomainError::RequestTimeout {
+                detail: String::new(),
+                instance: String::new(),
+            }
+        ));
+    }
+
+    #[test]
+    fn backoff_grows_and_is_capped() {
+        let backoff = BackoffConfig {
+            initial_ms: 100,
+            max_ms: 300,
+            multiplier: 2.0,
+            jitter: false,
+        };
+        assert_eq!(backoff_delay(&backoff, 1), Duration::from_millis(100));
+        assert_eq!(backoff_delay(&backoff, 2), Duration::from_millis(200));
+        assert_eq!(backoff_delay(&backoff, 3), Duration::from_millis(300));
+        assert_eq!(backoff_delay(&backoff, 9), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn jittered_backoff_stays_within_upper_half() {
+        let backoff = BackoffConfig {
+            initial_ms: 200,
+            max_ms: 200,
+            multiplier: 1.0,
+            jitter: true,
+        };
+        for _ in 0..50 {
+            let d = backoff_delay(&backoff, 1);
+            assert!(d >= Duration::from_millis(100) && d <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn budget_caps_retries_to_share_of_traffic() {
+        let budgets = RetryBudgets::new();
+        let route = Uuid::new_v4();
+        let budget = RetryBudget {
+            ratio: 0.2,
+            min_retries_per_sec: 0,
+        };
+        for _ in 0..10 {
+            budgets.record_request(route);
+        }
+        assert!(budgets.try_withdraw(route, &budget));
+        assert!(budgets.try_withdraw(route, &budget));
+        assert!(
+            !budgets.try_withdraw(route, &budget),
+            "third retry exceeds 20% of 10 requests"
+        );
+    }
+
+    #[test]
+    fn budget_floor_and_window_reset() {
+        let t0 = Instant::now();
+        set_mock_time(t0);
+        let budgets = RetryBudgets::new();
+        let route = Uuid::new_v4();
+        let budget = RetryBudget {
+            ratio: 0.0,
+            min_retries_per_sec: 1,
+        };
+        let floor = BUDGET_WINDOW.as_secs();
+        for _ in 0..floor {
+            assert!(budgets.try_withdraw(route, &budget));
+        }
+        assert!(!budgets.try_withdraw(route, &budget));
+
+        set_mock_time(t0 + BUDGET_WINDOW);
+        assert!(
+            budgets.try_withdraw(route, &budget),
+            "new window, new budget"
+        );
+        clear_mock_time();
+    }
+
+    #[test]
+    fn validation_rejects_bad_policies() {
+        assert!(validate_retry_config(&RetryConfig::default()).is_ok());
+
+        let config = RetryConfig {
+            max_attempts: 0,
+            ..RetryConfig::default()
+        };
+        assert!(validate_retry_config(&config).is_err());
+
+        let config = RetryConfig {
+            max_attempts: MAX_RETRY_ATTEMPTS + 1,
+            ..RetryConfig::default()
+        };
+        assert!(validate_retry_config(&config).is_err());
+
+        let mut config = RetryConfig::default();
+        config.backoff.initial_ms = 5_000;
+        assert!(validate_retry_config(&config).is_err());
+
+        let mut config = RetryConfig::default();
+        config.backoff.multiplier = 0.5;
+        assert!(validate_retry_config(&config).is_err());
+
+        let mut config = RetryConfig::default();
+        config.budget.ratio = 1.5;
+        assert!(validate_retry_config(&config).is_err());
+
+        assert!(
+            validate_timeout_config(&TimeoutConfig {
+                connect_ms: Some(0),
+                ..TimeoutConfig::default()
+            })
+            .is_err()
+        );
+    }
+}
@@ -322,6 +322,10 @@ fn sdk_create_upstream_to_domain(
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
         cors: req.cors().cloned().map(cors_config_to_domain),
+        circuit_breaker: req
+            .circuit_breaker()
+            .cloned()
+            .map(circuit_breaker_config_to_domain),
         tags: req.tags().to_vec(),
         enabled: req.enabled(),
     }
@@ -339,6 +343,10 @@ fn sdk_update_upstream_to_domain(
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
         cors: req.cors().cloned().map(cors_config_to_domain),
+        circuit_breaker: req
+            .circuit_breaker()
+            .cloned()
+            .map(circuit_breaker_config_to_domain),
         tags: req.tags().to_vec(),
         enabled: req.enabled(),
     }
@@ -352,6 +360,9 @@ fn sdk_create_route_to_domain(req: oagw_sdk::CreateRouteRequest) -> model::Creat
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
         cors: req.cors().cloned().map(cors_config_to_domain),
+        timeouts: req.timeouts().copied().map(timeout_config_to_domain),
+        retry: req.retry().cloned().map(retry_config_to_domain),
+        cache: req.cache().copied().map(cache_config_to_domain),
         tags: req.tags().to_vec(),
         priority: req.priority(),
         enabled: req.enabled(),
@@ -364,6 +375,9 @@ fn sdk_update_route_to_domain(req: oagw_sdk::UpdateRouteRequest) -> model::Updat
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
         cors: req.cors().cloned().map(cors_config_to_domain),
+        timeouts: req.timeouts().copied().map(timeout_config_to_domain),
+        retry: req.retry().cloned().map(retry_config_to_domain),
+        cache: req.cache().copied().map(cache_config_to_domain),
         tags: req.tags().to_vec(),
         priority: req.priority(),
         enabled: req.enabled(),
@@ -397,12 +411,52 @@ fn endpoint_to_domain(v: oagw_sdk::Endpoint) -> model::Endpoint {
         scheme: scheme_to_domain(v.scheme),
         host: v.host,
         port: v.port,
+        weight: v.weight,
     }
 }
 
 fn server_to_domain(v: oagw_sdk::Server) -> model::Server {
     model::Server {
         endpoints: v.endpoints.into_iter().map(endpoint_to_domain).collect(),
+        load_balancing: v.load_balancing.map(load_balancing_to_domain),
+        health_check: v.health_check.map(health_check_config_to_domain),
+    }
+}
+
+fn load_balancing_to_domain(v: oagw_sdk::LoadBalancing) -> model::LoadBalancing {
+    match v {
+        oagw_sdk::LoadBalancing::RoundRobin => model::LoadBalancing::RoundRobin,
+        oagw_sdk::LoadBalancing::LeastInFlight => model::LoadBalancing::LeastInFlight,
+        oagw_sdk::LoadBalancing::ConsistentHash { key } => model::LoadBalancing::ConsistentHash {
+            key: match key {
+                oagw_sdk::HashKey::Header(name) => model::HashKey::Header(name),
+                oagw_sdk::HashKey::Tenant => model::HashKey::Tenant,
+            },
+        },
+    }
+}
+
+fn health_check_config_to_domain(v: oagw_sdk::HealthCheckConfig) -> model::HealthCheckConfig {
+    model::HealthCheckConfig {
+        probe: match v.probe {
+            oagw_sdk::HealthProbe::Tcp => model::HealthProbe::Tcp,
+            oagw_sdk::HealthProbe::Http {
+                path,
+                expected_status,
+            } => model::HealthProbe::Http {
+                path,
+                expected_status,
+            },
+        },
+        interval_secs: v.interval_secs,
+        timeout_ms: v.timeout_ms,
+        healthy_threshold: v.healthy_threshold,
+        unhealthy_threshold: v.unhealthy_threshold,
+        outlier_detection: v.outlier_detection.map(|o| model::OutlierDetectionConfig {
+            consecutive_failures: o.consecutive_failures,
+            ejection_secs: o.ejection_secs,
+            max_ejection_percent: o.max_ejection_percent,
+        }),
     }
 }
 
@@ -456,6 +510,73 @@ fn window_to_domain(v: oagw_sdk::Window) -> model::Window {
     }
 }
 
+fn circuit_breaker_config_to_domain(
+    v: oagw_sdk::CircuitBreakerConfig,
+) -> model::CircuitBreakerConfig {
+    model::CircuitBreakerConfig {
+        sharing: sharing_mode_to_domain(v.sharing),
+        enabled: v.enabled,
+        failure_threshold: v.failure_threshold,
+        failure_rate: v.failure_rate.map(|r| model::FailureRateConfig {
+            threshold: r.threshold,
+            minimum_requests: r.minimum_requests,
+            window_secs: r.window_secs,
+        }),
+        success_threshold: v.success_threshold,
+        timeout_secs: v.timeout_secs,
+        half_open_max_requests: v.half_open_max_requests,
+        failure_conditions: model::FailureConditions {
+            status_codes: v.failure_conditions.status_codes,
+            timeout: v.failure_conditions.timeout,
+            connection_error: v.failure_conditions.connection_error,
+        },
+        scope: match v.scope {
+            oagw_sdk::CircuitBreakerScope::Global => model::CircuitBreakerScope::Global,
+            oagw_sdk::CircuitBreakerScope::PerEndpoint => model::CircuitBreakerScope::PerEndpoint,
+        },
+    }
+}
+
+fn timeout_config_to_domain(v: oagw_sdk::TimeoutConfig) -> model::TimeoutConfig {
+    model::TimeoutConfig {
+        connect_ms: v.connect_ms,
+        read_ms: v.read_ms,
+        total_ms: v.total_ms,
+    }
+}
+
+fn retry_config_to_domain(v: oagw_sdk::RetryConfig) -> model::RetryConfig {
+    model::RetryConfig {
+        max_attempts: v.max_attempts,
+        backoff: model::BackoffConfig {
+            initial_ms: v.backoff.initial_ms,
+            max_ms: v.backoff.max_ms,
+            multiplier: v.backoff.multiplier,
+            jitter: v.backoff.jitter,
+        },
+        retry_on: model::RetryConditions {
+            status_codes: v.retry_on.status_codes,
+            timeout: v.retry_on.timeout,
+            connection_error: v.retry_on.connection_error,
+        },
+        retry_non_idempotent: v.retry_non_idempotent,
+        budget: model::RetryBudget {
+            ratio: v.budget.ratio,
+            min_retries_per_sec: v.budget.min_retries_per_sec,
+        },
+    }
+}
+
+fn cache_config_to_domain(v: oagw_sdk::CacheConfig) -> model::CacheConfig {
+    model::CacheConfig {
+        sharing: sharing_mode_to_domain(v.sharing),
+        enabled: v.enabled,
+        default_ttl_secs: v.default_ttl_secs,
+        max_object_bytes: v.max_object_bytes,
+        allow_user_credentials: v.allow_user_credentials,
+    }
+}
+
 fn rate_limit_config_to_domain(v: oagw_sdk::RateLimitConfig) -> model::RateLimitConfig {
     model::RateLimitConfig {
         sharing: sharing_mode_to_domain(v.sharing),
@@ -610,8 +731,11 @@ fn upstream_to_sdk(u: model::Upstream) -> oagw_sdk::Upstream {
                     scheme: scheme_to_sdk(e.scheme),
                     host: e.host,
                     port: e.port,
+                    weight: e.weight,
                 })
                 .collect(),
+            load_balancing: u.server.load_balancing.map(load_balancing_to_sdk),
+            health_check: u.server.health_check.map(health_check_config_to_sdk),
         },
         protocol: u.protocol,
         enabled: u.enabled,
@@ -651,6 +775,7 @@ fn upstream_to_sdk(u: model::Upstream) -> oagw_sdk::Upstream {
         }),
         rate_limit: u.rate_limit.map(rate_limit_config_to_sdk),
         cors: u.cors.map(cors_config_to_sdk),
+        circuit_breaker: u.circuit_breaker.map(circuit_breaker_config_to_sdk),
         tags: u.tags,
     }
 }
@@ -698,6 +823,9 @@ fn route_to_sdk(r: model::Route) -> oagw_sdk::Route {
         }),
         rate_limit: r.rate_limit.map(rate_limit_config_to_sdk),
         cors: r.cors.map(cors_config_to_sdk),
+        timeouts: r.timeouts.map(timeout_config_to_sdk),
+        retry: r.retry.map(retry_config_to_sdk),
+        cache: r.cache.map(cache_config_to_sdk),
         tags: r.tags,
         priority: r.priority,
         enabled: r.enabled,
@@ -731,6 +859,110 @@ fn cors_config_to_sdk(v: model::CorsConfig) -> oagw_sdk::CorsConfig {
     }
 }
 
+fn load_balancing_to_sdk(v: model::LoadBalancing) -> oagw_sdk::LoadBalancing {
+    match v {
+        model::LoadBalancing::RoundRobin => oagw_sdk::LoadBalancing::RoundRobin,
+        model::LoadBalancing::LeastInFlight => oagw_sdk::LoadBalancing::LeastInFlight,
+        model::LoadBalancing::ConsistentHash { key } => oagw_sdk::LoadBalancing::ConsistentHash {
+            key: match key {
+                model::HashKey::Header(name) => oagw_sdk::HashKey::Header(name),
+                model::HashKey::Tenant => oagw_sdk::HashKey::Tenant,
+            },
+        },
+    }
+}
+
+fn health_check_config_to_sdk(v: model::HealthCheckConfig) -> oagw_sdk::HealthCheckConfig {
+    oagw_sdk::HealthCheckConfig {
+        probe: match v.probe {
+            model::HealthProbe::Tcp => oagw_sdk::HealthProbe::Tcp,
+            model::HealthProbe::Http {
+                path,
+                expected_status,
+            } => oagw_sdk::HealthProbe::Http {
+                path,
+                expected_status,
+            },
+        },
+        interval_secs: v.interval_secs,
+        timeout_ms: v.timeout_ms,
+        healthy_threshold: v.healthy_threshold,
+        unhealthy_threshold: v.unhealthy_threshold,
+        outlier_detection: v
+            .outlier_detection
+            .map(|o| oagw_sdk::OutlierDetectionConfig {
+                consecutive_failures: o.consecutive_failures,
+                ejection_secs: o.ejection_secs,
+                max_ejection_percent: o.max_ejection_percent,
+            }),
+    }
+}
+
+fn circuit_breaker_config_to_sdk(v: model::CircuitBreakerConfig) -> oagw_sdk::CircuitBreakerConfig {
+    oagw_sdk::CircuitBreakerConfig {
+        sharing: sharing_mode_to_sdk(v.sharing),
+        enabled: v.enabled,
+        failure_threshold: v.failure_threshold,
+        failure_rate: v.failure_rate.map(|r| oagw_sdk::FailureRateConfig {
+            threshold: r.threshold,
+            minimum_requests: r.minimum_requests,
+            window_secs: r.window_secs,
+        }),
+        success_threshold: v.success_threshold,
+        timeout_secs: v.timeout_secs,
+        half_open_max_requests: v.half_open_max_requests,
+        failure_conditions: oagw_sdk::FailureConditions {
+            status_codes: v.failure_conditions.status_codes,
+            timeout: v.failure_conditions.timeout,
+            connection_error: v.failure_conditions.connection_error,
+        },
+        scope: match v.scope {
+            model::CircuitBreakerScope::Global => oagw_sdk::CircuitBreakerScope::Global,
+            model::CircuitBreakerScope::PerEndpoint => oagw_sdk::CircuitBreakerScope::PerEndpoint,
+        },
+    }
+}
+
+fn timeout_config_to_sdk(v: model::TimeoutConfig) -> oagw_sdk::TimeoutConfig {
+    oagw_sdk::TimeoutConfig {
+        connect_ms: v.connect_ms,
+        read_ms: v.read_ms,
+        total_ms: v.total_ms,
+    }
+}
+
+fn retry_config_to_sdk(v: model::RetryConfig) -> oagw_sdk::RetryConfig {
+    oagw_sdk::RetryConfig {
+        max_attempts: v.max_attempts,
+        backoff: oagw_sdk::BackoffConfig {
+            initial_ms: v.backoff.initial_ms,
+            max_ms: v.backoff.max_ms,
+            multiplier: v.backoff.multiplier,
+            jitter: v.backoff.jitter,
+        },
+        retry_on: oagw_sdk::RetryConditions {
+            status_codes: v.retry_on.status_codes,
+            timeout: v.retry_on.timeout,
+            connection_error: v.retry_on.connection_error,
+        },
+        retry_non_idempotent: v.retry_non_idempotent,
+        budget: oagw_sdk::RetryBudget {
+            ratio: v.budget.ratio,
+            min_retries_per_sec: v.budget.min_retries_per_sec,
+        },
+    }
+}
+
+fn cache_config_to_sdk(v: model::CacheConfig) -> oagw_sdk::CacheConfig {
+    oagw_sdk::CacheConfig {
+        sharing: sharing_mode_to_sdk(v.sharing),
+        enabled: v.enabled,
+        default_ttl_secs: v.default_ttl_secs,
+        max_object_bytes: v.max_object_bytes,
+        allow_user_credentials: v.allow_user_credentials,
+    }
+}
+
 fn rate_limit_config_to_sdk(v: model::RateLimitConfig) -> oagw_sdk::RateLimitConfig {
     oagw_sdk::RateLimitConfig {
         sharing: sharing_mode_to_sdk(v.sharing),
@@ -824,7 +1056,10 @@ mod tests {
                     scheme: model::Scheme::Https,
                     host: "example.com".into(),
                     port: 443,
+                    weight: 1,
                 }],
+                load_balancing: None,
+                health_check: None,
             },
             protocol: "http".into(),
             enabled: true,
@@ -837,6 +1072,7 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
         };
 
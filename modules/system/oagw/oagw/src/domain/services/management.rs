@@ -4,7 +4,7 @@ use super::ControlPlaneService;
 
 use crate::domain::error::DomainError;
 use crate::domain::model::{
-    CreateRouteRequest, CreateUpstreamRequest, Endpoint, ListQuery, MatchRules, Route,
+    CreateRouteRequest, CreateUpstreamRequest, Endpoint, GrpcMatch, ListQuery, MatchRules, Route,
     UpdateRouteRequest, UpdateUpstreamRequest, Upstream,
 };
 use crate::domain::repo::{RouteRepository, UpstreamRepository};
@@ -75,9 +75,13 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         req: CreateUpstreamRequest,
     ) -> Result<Upstream, DomainError> {
         validate_endpoints(&req.server.endpoints)?;
+        crate::domain::load_balancing::validate_server_config(&req.server)?;
         if let Some(ref cors) = req.cors {
             crate::domain::cors::validate_cors_config(cors)?;
         }
+        if let Some(ref cb) = req.circuit_breaker {
+            crate::domain::circuit_breaker::validate_circuit_breaker_config(cb)?;
+        }
         if let Some(ref rl) = req.rate_limit
             && let Some(ref budget) = rl.budget
         {
@@ -102,6 +106,7 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
                 rate_limit: req.rate_limit.as_ref(),
                 plugins: req.plugins.as_ref(),
                 cors: req.cors.as_ref(),
+                circuit_breaker: req.circuit_breaker.as_ref(),
             },
         )
         .await?;
@@ -121,6 +126,7 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             plugins: req.plugins,
             rate_limit: req.rate_limit,
             cors: req.cors,
+            circuit_breaker: req.circuit_breaker,
             tags: req.tags,
         };
 
@@ -168,6 +174,7 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
 
         // Full replacement: validate and apply server.
         validate_endpoints(&req.server.endpoints)?;
+        crate::domain::load_balancing::validate_server_config(&req.server)?;
         existing.server = req.server;
         existing.protocol = req.protocol;
 
@@ -201,7 +208,8 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         let has_overrides = req.auth.is_some()
             || req.rate_limit.is_some()
             || req.plugins.is_some()
-            || req.cors.is_some();
+            || req.cors.is_some()
+            || req.circuit_breaker.is_some();
 
         if has_overrides || endpoints_changed || req.alias.is_some() {
             let tenant_chain = self.build_tenant_chain(ctx).await?;
@@ -214,6 +222,7 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
                     rate_limit: req.rate_limit.as_ref(),
                     plugins: req.plugins.as_ref(),
                     cors: req.cors.as_ref(),
+                    circuit_breaker: req.circuit_breaker.as_ref(),
                 },
             )
             .await?;
@@ -258,6 +267,10 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             crate::domain::cors::validate_cors_config(cors)?;
         }
         existing.cors = req.cors;
+        if let Some(ref cb) = req.circuit_breaker {
+            crate::domain::circuit_breaker::validate_circuit_breaker_config(cb)?;
+        }
+        existing.circuit_breaker = req.circuit_breaker;
         existing.tags = req.tags;
         existing.enabled = req.enabled;
 
@@ -296,6 +309,15 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         if let Some(ref cors) = req.cors {
             crate::domain::cors::validate_cors_config(cors)?;
         }
+        if let Some(ref timeouts) = req.timeouts {
+            crate::domain::retry::validate_timeout_config(timeouts)?;
+        }
+        if let Some(ref retry) = req.retry {
+            crate::domain::retry::validate_retry_config(retry)?;
+        }
+        if let Some(ref cache) = req.cache {
+            crate::domain::cache::validate_cache_config(cache)?;
+        }
 
         let tenant_id = ctx.subject_tenant_id();
         // Validate that the upstream exists and belongs to this tenant.
@@ -317,6 +339,9 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             plugins: req.plugins,
             rate_limit: req.rate_limit,
             cors: req.cors,
+            timeouts: req.timeouts,
+            retry: req.retry,
+            cache: req.cache,
             tags: req.tags,
             priority: req.priority,
             enabled: req.enabled,
@@ -370,6 +395,18 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             crate::domain::cors::validate_cors_config(cors)?;
         }
         existing.cors = req.cors;
+        if let Some(ref timeouts) = req.timeouts {
+            crate::domain::retry::validate_timeout_config(timeouts)?;
+        }
+        existing.timeouts = req.timeouts;
+        if let Some(ref retry) = req.retry {
+            crate::domain::retry::validate_retry_config(retry)?;
+        }
+        existing.retry = req.retry;
+        if let Some(ref cache) = req.cache {
+            crate::domain::cache::validate_cache_config(cache)?;
+        }
+        existing.cache = req.cache;
         existing.tags = req.tags;
         existing.priority = req.priority;
         existing.enabled = req.enabled;
@@ -420,7 +457,8 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
 
 impl ControlPlaneServiceImpl {
     /// Check that no existing **enabled** route under the same upstream shares
-    /// `(path_prefix, priority, method)` with the candidate route.
+    /// `(path_prefix, priority, method)` with the candidate route. gRPC routes
+    /// conflict when they share `(service, method, priority)`.
     ///
     /// `exclude_id` is `Some(route.id)` on update to skip the route being
     /// modified (it will be compared against its new state, not itself).
@@ -436,10 +474,11 @@ impl ControlPlaneServiceImpl {
             return Ok(());
         }
 
-        let candidate_http = match &candidate.match_rules.http {
-            Some(h) => h,
-            None => return Ok(()), // No HTTP match rules → no overlap to check.
-        };
+        let candidate_http = candidate.match_rules.http.as_ref();
+        let candidate_grpc = candidate.match_rules.grpc.as_ref();
+        if candidate_http.is_none() && candidate_grpc.is_none() {
+            return Ok(()); // No match rules → no overlap to check.
+        }
 
         // Fetch all routes for this (tenant, upstream).
         let all = self
@@ -464,8 +503,30 @@ impl ControlPlaneServiceImpl {
             if !existing.enabled {
                 continue;
             }
-            // Must have HTTP match rules.
-            let Some(existing_http) = &existing.match_rules.http else {
+            // gRPC routes match one exact method, so only an identical match
+            // at the same priority is ambiguous.
+            if let Some(grpc) = candidate_grpc {
+                if existing.match_rules.grpc.as_ref() == Some(grpc)
+                    && existing.priority == candidate.priority
+                {
+                    return Err(DomainError::conflict(
+                        "route",
+                        format!("{}:{}", candidate.upstream_id, grpc.path()),
+                        format!(
+                            "route overlap: an enabled route already exists on upstream '{}' \
+                             for gRPC method '{}', priority {}",
+                            candidate.upstream_id,
+                            grpc.path(),
+                            candidate.priority
+                        ),
+                    ));
+                }
+                continue;
+            }
+            // Must have HTTP match rules on both sides.
+            let (Some(candidate_http), Some(existing_http)) =
+                (candidate_http, &existing.match_rules.http)
+            else {
                 continue;
             };
             // Must share path and priority.
@@ -885,10 +946,40 @@ fn validate_match_rules(rules: &MatchRules) -> Result<(), DomainError> {
         (Some(_), Some(_)) => Err(DomainError::validation(
             "match rules must specify exactly one of 'http' or 'grpc', not both",
         )),
-        _ => Ok(()),
+        (None, Some(grpc)) => validate_grpc_match(grpc),
+        (Some(_), None) => Ok(()),
     }
 }
 
+/// Ensure a gRPC match names a fully-qualified service and a method, so the
+/// derived `/{service}/{method}` path is a valid HTTP/2 `:path`.
+fn validate_grpc_match(grpc: &GrpcMatch) -> Result<(), DomainError> {
+    let is_ident_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let service_ok = !grpc.service.is_empty()
+        && grpc
+            .service
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(is_ident_char));
+    if !service_ok {
+        return Err(DomainError::validation_for(
+            "match.grpc.service",
+            "INVALID_GRPC_SERVICE",
+            format!(
+                "'{}' is not a valid gRPC service name (expected e.g. 'example.v1.UserService')",
+                grpc.service
+            ),
+        ));
+    }
+    if grpc.method.is_empty() || !grpc.method.chars().all(is_ident_char) {
+        return Err(DomainError::validation_for(
+            "match.grpc.method",
+            "INVALID_GRPC_METHOD",
+            format!("'{}' is not a valid gRPC method name", grpc.method),
+        ));
+    }
+    Ok(())
+}
+
 /// Validate budget configuration field constraints per ADR 0004 schema.
 fn validate_budget_config(budget: &crate::domain::model::BudgetConfig) -> Result<(), DomainError> {
     use crate::domain::model::BudgetMode;
@@ -1285,6 +1376,7 @@ struct BindOverrides<'a> {
     rate_limit: Option<&'a crate::domain::model::RateLimitConfig>,
     plugins: Option<&'a crate::domain::model::PluginsConfig>,
     cors: Option<&'a crate::domain::model::CorsConfig>,
+    circuit_breaker: Option<&'a crate::domain::model::CircuitBreakerConfig>,
 }
 
 /// Validate bind constraints when a descendant creates or updates an
@@ -1429,6 +1521,23 @@ async fn validate_bind_constraints(
         }
     }
 
+    // Circuit breaker sharing mode constraints (no override permission required).
+    if overrides.circuit_breaker.is_some() {
+        match ancestor.circuit_breaker.as_ref().map(|c| c.sharing) {
+            Some(SharingMode::Enforce) => {
+                return Err(DomainError::validation(
+                    "cannot override circuit_breaker: ancestor upstream has sharing mode 'enforce'",
+                ));
+            }
+            Some(SharingMode::Private) => {
+                return Err(DomainError::validation(
+                    "cannot override circuit_breaker: ancestor upstream field is private",
+                ));
+            }
+            _ => {}
+        }
+    }
+
     Ok(())
 }
 
@@ -1470,10 +1579,10 @@ async fn validate_secret_ref_accessible(
 ///
 /// Per `cpt-cf-oagw-algo-tenant-alias-shadow` step 2b, an ancestor upstream is
 /// visible if its own tenant matches the requester OR any per-field sharing flag
-/// (`auth`, `rate_limit`, `plugins`, `cors`) is not `private`.
+/// (`auth`, `rate_limit`, `plugins`, `cors`, `circuit_breaker`) is not `private`.
 ///
 /// Returns `false` when all shareable fields are `None` — this is intentional.
-/// An upstream with no auth, rate_limit, plugins, or cors has no configuration
+/// An upstream with no auth, rate_limit, plugins, cors, or circuit_breaker has no configuration
 /// to share with descendants, so it is treated as invisible. Fields without a
 /// sharing mode (e.g. `headers`) do not contribute to visibility.
 fn is_visible_to_descendant(upstream: &Upstream) -> bool {
@@ -1495,8 +1604,12 @@ fn is_visible_to_descendant(upstream: &Upstream) -> bool {
         .cors
         .as_ref()
         .is_some_and(|c| c.sharing != SharingMode::Private);
+    let circuit_shared = upstream
+        .circuit_breaker
+        .as_ref()
+        .is_some_and(|c| c.sharing != SharingMode::Private);
 
-    auth_shared || rate_shared || plugins_shared || cors_shared
+    auth_shared || rate_shared || plugins_shared || cors_shared || circuit_shared
 }
 
 /// Compute the effective upstream configuration by merging ancestor upstreams
@@ -1506,6 +1619,7 @@ fn is_visible_to_descendant(upstream: &Upstream) -> bool {
 /// - Auth:       `private` → local-only (blocked by ancestor `enforce`); `inherit` → override; `enforce` → sticky
 /// - Rate limit: `private` → local-only (constrained by ancestor `enforce` via `min()`); else `min(ancestor, descendant)`
 /// - Plugins:    `private` → local-only (ancestor `enforce` items preserved); else concatenate `ancestor + descendant`
+/// - Circuit breaker: `private` → local-only; `inherit` → override; `enforce` → sticky
 /// - Tags:       union (add-only)
 ///
 /// `ancestor_chain` is ordered root-first: `[root, parent, ..., selected]`.
@@ -1539,6 +1653,9 @@ pub(crate) fn compute_effective_config(
         // CORS merge
         merge_cors(&mut effective, layer)?;
 
+        // Circuit breaker merge
+        merge_circuit_breaker(&mut effective, layer);
+
         // Tags: union (add-only)
         for tag in &layer.tags {
             if !effective.tags.contains(tag) {
@@ -1805,6 +1922,31 @@ fn merge_cors(effective: &mut Upstream, layer: &Upstream) -> Result<(), DomainEr
     Ok(())
 }
 
+/// Merge circuit breaker config from a descendant layer onto the effective config.
+///
+/// Same replacement semantics as auth: a descendant config replaces the
+/// ancestor's unless the ancestor is `enforce`, which is sticky.  An ancestor
+/// `private` breaker does not propagate to descendants that omit the field.
+fn merge_circuit_breaker(effective: &mut Upstream, layer: &Upstream) {
+    use crate::domain::model::SharingMode;
+
+    let effective_sharing = effective.circuit_breaker.as_ref().map(|c| c.sharing);
+
+    match &layer.circuit_breaker {
+        None => {
+            if effective_sharing == Some(SharingMode::Private) {
+                effective.circuit_breaker = None;
+            }
+        }
+        Some(_) if effective_sharing == Some(SharingMode::Enforce) => {
+            // Ancestor enforced — no descendant can change it.
+        }
+        Some(descendant_cb) => {
+            effective.circuit_breaker = Some(descendant_cb.clone());
+        }
+    }
+}
+
 /// Return the stricter of two rate limit configs (lower rate wins).
 fn min_rate_limit(
     a: Option<&crate::domain::model::RateLimitConfig>,
@@ -1968,7 +2110,10 @@ mod tests {
                     scheme: Scheme::Https,
                     host: "api.openai.com".into(),
                     port: 443,
+                    weight: 1,
                 }],
+                load_balancing: None,
+                health_check: None,
             },
             protocol: "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1".into(),
             alias: None,
@@ -1977,6 +2122,7 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         }
@@ -1991,7 +2137,10 @@ mod tests {
                     scheme: Scheme::Https,
                     host: "10.0.0.1".into(),
                     port: 443,
+                    weight: 1,
                 }],
+                load_balancing: None,
+                health_check: None,
             },
             protocol: "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1".into(),
             alias: Some(alias.into()),
@@ -2000,6 +2149,7 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         }
@@ -2016,6 +2166,7 @@ mod tests {
             plugins: u.plugins.clone(),
             rate_limit: u.rate_limit.clone(),
             cors: u.cors.clone(),
+            circuit_breaker: u.circuit_breaker.clone(),
             tags: u.tags.clone(),
             enabled: u.enabled,
         }
@@ -2028,6 +2179,9 @@ mod tests {
             plugins: r.plugins.clone(),
             rate_limit: r.rate_limit.clone(),
             cors: r.cors.clone(),
+            timeouts: r.timeouts,
+            retry: r.retry.clone(),
+            cache: r.cache,
             tags: r.tags.clone(),
             priority: r.priority,
             enabled: r.enabled,
@@ -2050,6 +2204,9 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -2113,7 +2270,10 @@ mod tests {
                     scheme: Scheme::Https,
                     host: "api.openai.com".into(),
                     port: 8443,
+                    weight: 1,
                 }],
+                load_balancing: None,
+                health_check: None,
             },
             protocol: "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1".into(),
             alias: None,
@@ -2122,6 +2282,7 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         };
@@ -2330,11 +2491,13 @@ mod tests {
                 scheme: Scheme::Https,
                 host: "10.0.0.1".into(),
                 port: 443,
+                weight: 1,
             },
             Endpoint {
                 scheme: Scheme::Https,
                 host: "api.example.com".into(),
                 port: 443,
+                weight: 1,
             },
         ];
         let err = validate_endpoints(&endpoints).unwrap_err();
@@ -2356,11 +2519,13 @@ mod tests {
                 scheme: Scheme::Https,
                 host: "a.example.com".into(),
                 port: 443,
+                weight: 1,
             },
             Endpoint {
                 scheme: Scheme::Http,
                 host: "b.example.com".into(),
                 port: 443,
+                weight: 1,
             },
         ];
         let err = validate_endpoints(&endpoints).unwrap_err();
@@ -2382,11 +2547,13 @@ mod tests {
                 scheme: Scheme::Https,
                 host: "10.0.0.1".into(),
                 port: 443,
+                weight: 1,
             },
             Endpoint {
                 scheme: Scheme::Https,
                 host: "10.0.0.2".into(),
                 port: 443,
+                weight: 1,
             },
         ];
         assert!(validate_endpoints(&endpoints).is_ok());
@@ -2399,11 +2566,13 @@ mod tests {
                 scheme: Scheme::Https,
                 host: "a.example.com".into(),
                 port: 443,
+                weight: 1,
             },
             Endpoint {
                 scheme: Scheme::Https,
                 host: "b.example.com".into(),
                 port: 443,
+                weight: 1,
             },
         ];
         assert!(validate_endpoints(&endpoints).is_ok());
@@ -2416,11 +2585,13 @@ mod tests {
                 scheme: Scheme::Https,
                 host: "a.example.com".into(),
                 port: 443,
+                weight: 1,
             },
             Endpoint {
                 scheme: Scheme::Https,
                 host: "b.example.com".into(),
                 port: 8443,
+                weight: 1,
             },
         ];
         let err = validate_endpoints(&endpoints).unwrap_err();
@@ -2436,6 +2607,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 443,
+            weight: 1,
         }];
         assert!(validate_endpoints(&endpoints).is_ok());
     }
@@ -2446,6 +2618,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "::1".into(),
             port: 443,
+            weight: 1,
         }];
         let err = validate_endpoints(&endpoints).unwrap_err();
         match err {
@@ -2469,6 +2642,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "2001:db8::1".into(),
             port: 8443,
+            weight: 1,
         }];
         let err = validate_endpoints(&endpoints).unwrap_err();
         assert!(matches!(err, DomainError::Validation { .. }));
@@ -2480,6 +2654,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "[2001:db8::1]".into(),
             port: 8443,
+            weight: 1,
         }];
         let err = validate_endpoints(&endpoints).unwrap_err();
         match err {
@@ -2505,6 +2680,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api..openai.com".into(),
             port: 443,
+            weight: 1,
         }];
         let err = validate_endpoints(&endpoints).unwrap_err();
         match err {
@@ -2524,6 +2700,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "-api.openai.com".into(),
             port: 443,
+            weight: 1,
         }];
         let err = validate_endpoints(&endpoints).unwrap_err();
         match err {
@@ -2543,6 +2720,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api-.openai.com".into(),
             port: 443,
+            weight: 1,
         }];
         let err = validate_endpoints(&endpoints).unwrap_err();
         assert!(matches!(err, DomainError::Validation { .. }));
@@ -2554,6 +2732,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api_v2.openai.com".into(),
             port: 443,
+            weight: 1,
         }];
         let err = validate_endpoints(&endpoints).unwrap_err();
         match err {
@@ -2575,6 +2754,7 @@ mod tests {
             scheme: Scheme::Https,
             host,
             port: 443,
+            weight: 1,
         }];
         let err = validate_endpoints(&endpoints).unwrap_err();
         match err {
@@ -2594,6 +2774,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com.".into(),
             port: 443,
+            weight: 1,
         }];
         assert!(validate_endpoints(&endpoints).is_ok());
     }
@@ -2606,6 +2787,7 @@ mod tests {
             scheme: Scheme::Https,
             host,
             port: 443,
+            weight: 1,
         }];
         assert!(validate_endpoints(&endpoints).is_ok());
     }
@@ -2616,6 +2798,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "".into(),
             port: 443,
+            weight: 1,
         }];
         let err = validate_endpoints(&endpoints).unwrap_err();
         assert!(matches!(err, DomainError::Validation { .. }));
@@ -2899,8 +3082,9 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::domain::model::{
-        AuthConfig, CorsConfig, CorsHttpMethod, PluginBinding, PluginsConfig, RateLimitAlgorithm,
-        RateLimitConfig, RateLimitScope, RateLimitStrategy, SharingMode, SustainedRate, Window,
+        AuthConfig, CircuitBreakerConfig, CircuitBreakerScope, CorsConfig, CorsHttpMethod,
+        FailureConditions, PluginBinding, PluginsConfig, RateLimitAlgorithm, RateLimitConfig,
+        RateLimitScope, RateLimitStrategy, SharingMode, SustainedRate, Window,
     };
 
     fn make_upstream(
@@ -2920,7 +3104,10 @@ mod tests {
                     scheme: Scheme::Https,
                     host: "api.example.com".into(),
                     port: 443,
+                    weight: 1,
                 }],
+                load_balancing: None,
+                health_check: None,
             },
             protocol: "http".into(),
             enabled: true,
@@ -2929,6 +3116,7 @@ mod tests {
             plugins,
             rate_limit,
             cors: None,
+            circuit_breaker: None,
             tags,
         }
     }
@@ -3165,6 +3353,9 @@ mod tests {
             plugins: None,
             rate_limit: Some(make_rate_limit(SharingMode::Inherit, 50, Window::Minute)),
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -3344,6 +3535,9 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: Some(make_cors(SharingMode::Inherit, vec!["https://route.com"])),
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -3479,6 +3673,9 @@ mod tests {
                 expose_headers: vec![],
                 allow_credentials: true,
             }),
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -3491,6 +3688,70 @@ mod tests {
         );
     }
 
+    // -- Circuit breaker effective config merge tests --
+
+    fn make_circuit_breaker(sharing: SharingMode, failure_threshold: u32) -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            sharing,
+            enabled: true,
+            failure_threshold,
+            failure_rate: None,
+            success_threshold: 3,
+            timeout_secs: 30,
+            half_open_max_requests: 3,
+            failure_conditions: FailureConditions::default(),
+            scope: CircuitBreakerScope::Global,
+        }
+    }
+
+    #[test]
+    fn effective_config_circuit_breaker_inherit_descendant_overrides() {
+        let t = Uuid::new_v4();
+        let mut root = make_upstream(t, "api", None, None, None, vec![]);
+        root.circuit_breaker = Some(make_circuit_breaker(SharingMode::Inherit, 5));
+        let mut child = make_upstream(t, "api", None, None, None, vec![]);
+        child.circuit_breaker = Some(make_circuit_breaker(SharingMode::Inherit, 10));
+
+        let effective = compute_effective_config(&[root, child], None).unwrap();
+        assert_eq!(effective.circuit_breaker.unwrap().failure_threshold, 10);
+    }
+
+    #[test]
+    fn effective_config_circuit_breaker_absent_inherits_shared_ancestor() {
+        let t = Uuid::new_v4();
+        let mut root = make_upstream(t, "api", None, None, None, vec![]);
+        root.circuit_breaker = Some(make_circuit_breaker(SharingMode::Inherit, 5));
+        let child = make_upstream(t, "api", None, None, None, vec![]);
+
+        let effective = compute_effective_config(&[root, child], None).unwrap();
+        assert_eq!(effective.circuit_breaker.unwrap().failure_threshold, 5);
+    }
+
+    #[test]
+    fn effective_config_circuit_breaker_private_does_not_propagate() {
+        let t = Uuid::new_v4();
+        let mut root = make_upstream(t, "api", None, None, None, vec![]);
+        root.circuit_breaker = Some(make_circuit_breaker(SharingMode::Private, 5));
+        let child = make_upstream(t, "api", None, None, None, vec![]);
+
+        let effective = compute_effective_config(&[root, child], None).unwrap();
+        assert!(effective.circuit_breaker.is_none());
+    }
+
+    #[test]
+    fn effective_config_circuit_breaker_enforce_is_sticky() {
+        let t = Uuid::new_v4();
+        let mut root = make_upstream(t, "api", None, None, None, vec![]);
+        root.circuit_breaker = Some(make_circuit_breaker(SharingMode::Enforce, 5));
+        let mut child = make_upstream(t, "api", None, None, None, vec![]);
+        child.circuit_breaker = Some(make_circuit_breaker(SharingMode::Private, 50));
+
+        let effective = compute_effective_config(&[root, child], None).unwrap();
+        let cb = effective.circuit_breaker.unwrap();
+        assert_eq!(cb.sharing, SharingMode::Enforce);
+        assert_eq!(cb.failure_threshold, 5);
+    }
+
     // -- Ancestor bind validation tests --
 
     #[tokio::test]
@@ -3555,6 +3816,39 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn bind_rejects_circuit_breaker_override_on_enforce() {
+        let root = Uuid::new_v4();
+        let child = Uuid::new_v4();
+        let resolver =
+            MockTenantResolverClient::with_hierarchy(vec![TenantId(root), TenantId(child)]);
+        let svc = make_service_with_resolver(resolver);
+
+        let root_ctx = test_ctx(root);
+        let mut req = make_create_upstream_hostname();
+        req.circuit_breaker = Some(make_circuit_breaker(SharingMode::Enforce, 5));
+        svc.create_upstream(&root_ctx, req).await.unwrap();
+
+        let child_ctx = test_ctx(child);
+        let mut child_req = make_create_upstream_hostname();
+        child_req.circuit_breaker = Some(make_circuit_breaker(SharingMode::Inherit, 50));
+        let err = svc
+            .create_upstream(&child_ctx, child_req)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("circuit_breaker"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn create_upstream_rejects_invalid_circuit_breaker() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let mut req = make_create_upstream_hostname();
+        req.circuit_breaker = Some(make_circuit_breaker(SharingMode::Private, 0));
+        let result = svc.create_upstream(&ctx, req).await;
+        assert!(matches!(result, Err(DomainError::Validation { .. })));
+    }
+
     #[tokio::test]
     async fn bind_rejects_auth_override_on_enforce() {
         let root = Uuid::new_v4();
@@ -3962,6 +4256,9 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -4022,6 +4319,9 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -4051,6 +4351,9 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -4123,6 +4426,9 @@ mod tests {
             }),
             rate_limit: None,
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -4157,6 +4463,9 @@ mod tests {
             plugins: None,
             rate_limit: Some(make_rate_limit(SharingMode::Private, 10, Window::Minute)),
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -4249,6 +4558,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 443,
+            weight: 1,
         }];
         assert_eq!(compute_derived_alias(&eps), Some("api.openai.com".into()));
     }
@@ -4259,6 +4569,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 8443,
+            weight: 1,
         }];
         assert_eq!(
             compute_derived_alias(&eps),
@@ -4272,6 +4583,7 @@ mod tests {
             scheme: Scheme::Http,
             host: "api.example.com".into(),
             port: 80,
+            weight: 1,
         }];
         assert_eq!(compute_derived_alias(&eps), Some("api.example.com".into()));
     }
@@ -4282,6 +4594,7 @@ mod tests {
             scheme: Scheme::Grpc,
             host: "grpc.example.com".into(),
             port: 443,
+            weight: 1,
         }];
         assert_eq!(compute_derived_alias(&eps), Some("grpc.example.com".into()));
     }
@@ -4293,11 +4606,13 @@ mod tests {
                 scheme: Scheme::Https,
                 host: "us.vendor.com".into(),
                 port: 443,
+                weight: 1,
             },
             Endpoint {
                 scheme: Scheme::Https,
                 host: "eu.vendor.com".into(),
                 port: 443,
+                weight: 1,
             },
         ];
         assert_eq!(compute_derived_alias(&eps), Some("vendor.com".into()));
@@ -4310,11 +4625,13 @@ mod tests {
                 scheme: Scheme::Https,
                 host: "us.vendor.com".into(),
                 port: 8443,
+                weight: 1,
             },
             Endpoint {
                 scheme: Scheme::Https,
                 host: "eu.vendor.com".into(),
                 port: 8443,
+                weight: 1,
             },
         ];
         assert_eq!(compute_derived_alias(&eps), Some("vendor.com:8443".into()));
@@ -4327,11 +4644,13 @@ mod tests {
                 scheme: Scheme::Https,
                 host: "a.b.vendor.com".into(),
                 port: 443,
+                weight: 1,
             },
             Endpoint {
                 scheme: Scheme::Https,
                 host: "c.b.vendor.com".into(),
                 port: 443,
+                weight: 1,
             },
         ];
         assert_eq!(compute_derived_alias(&eps), Some("b.vendor.com".into()));
@@ -4344,11 +4663,13 @@ mod tests {
                 scheme: Scheme::Https,
                 host: "us.foo.com".into(),
                 port: 443,
+                weight: 1,
             },
             Endpoint {
                 scheme: Scheme::Https,
                 host: "eu.bar.com".into(),
                 port: 443,
+                weight: 1,
             },
         ];
         // Only 1 common label ("com") — minimum is 2.
@@ -4362,11 +4683,13 @@ mod tests {
                 scheme: Scheme::Https,
                 host: "api.vendor.com".into(),
                 port: 443,
+                weight: 1,
             },
             Endpoint {
                 scheme: Scheme::Https,
                 host: "api.vendor.com".into(),
                 port: 443,
+                weight: 1,
             },
         ];
         assert_eq!(compute_derived_alias(&eps), Some("api.vendor.com".into()));
@@ -4378,6 +4701,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "10.0.1.1".into(),
             port: 443,
+            weight: 1,
         }];
         assert_eq!(compute_derived_alias(&eps), None);
     }
@@ -4388,6 +4712,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "Api.OpenAI.COM".into(),
             port: 443,
+            weight: 1,
         }];
         assert_eq!(compute_derived_alias(&eps), Some("api.openai.com".into()));
     }
@@ -4398,6 +4723,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.example.com.".into(),
             port: 443,
+            weight: 1,
         }];
         assert_eq!(compute_derived_alias(&eps), Some("api.example.com".into()));
     }
@@ -4424,6 +4750,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "Api.Example.COM..".into(),
             port: 443,
+            weight: 1,
         };
         assert_eq!(ep.normalized_host(), "api.example.com");
     }
@@ -4434,6 +4761,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 443,
+            weight: 1,
         }];
         let err = enforce_alias_create(Some("custom-alias"), &eps).unwrap_err();
         assert!(matches!(err, DomainError::Validation { .. }));
@@ -4445,6 +4773,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 443,
+            weight: 1,
         }];
         let alias = enforce_alias_create(Some("api.openai.com"), &eps).unwrap();
         assert_eq!(alias, "api.openai.com");
@@ -4456,6 +4785,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 443,
+            weight: 1,
         }];
         let alias = enforce_alias_create(None, &eps).unwrap();
         assert_eq!(alias, "api.openai.com");
@@ -4467,6 +4797,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "10.0.1.1".into(),
             port: 443,
+            weight: 1,
         }];
         let err = enforce_alias_create(None, &eps).unwrap_err();
         assert!(matches!(err, DomainError::Validation { .. }));
@@ -4478,6 +4809,7 @@ mod tests {
             scheme: Scheme::Https,
             host: "10.0.1.1".into(),
             port: 443,
+            weight: 1,
         }];
         let alias = enforce_alias_create(Some("my-backend"), &eps).unwrap();
         assert_eq!(alias, "my-backend");
@@ -4489,11 +4821,13 @@ mod tests {
             scheme: Scheme::Https,
             host: "old.vendor.com".into(),
             port: 443,
+            weight: 1,
         }];
         let new_eps = vec![Endpoint {
             scheme: Scheme::Https,
             host: "new.vendor.com".into(),
             port: 443,
+            weight: 1,
         }];
         let alias = enforce_alias_update(None, &new_eps, "old.vendor.com", &old_eps).unwrap();
         assert_eq!(alias, "new.vendor.com");
@@ -4505,11 +4839,13 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 443,
+            weight: 1,
         }];
         let new_eps = vec![Endpoint {
             scheme: Scheme::Https,
             host: "10.0.1.1".into(),
             port: 443,
+            weight: 1,
         }];
         let err = enforce_alias_update(None, &new_eps, "api.openai.com", &old_eps).unwrap_err();
         assert!(matches!(err, DomainError::Validation { .. }));
@@ -4521,11 +4857,13 @@ mod tests {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 443,
+            weight: 1,
         }];
         let new_eps = vec![Endpoint {
             scheme: Scheme::Https,
             host: "10.0.1.1".into(),
             port: 443,
+            weight: 1,
         }];
         let alias =
             enforce_alias_update(Some("my-backend"), &new_eps, "api.openai.com", &old_eps).unwrap();
@@ -4538,11 +4876,13 @@ mod tests {
             scheme: Scheme::Https,
             host: "10.0.1.1".into(),
             port: 443,
+            weight: 1,
         }];
         let new_eps = vec![Endpoint {
             scheme: Scheme::Https,
             host: "10.0.1.2".into(),
             port: 443,
+            weight: 1,
         }];
         let alias = enforce_alias_update(None, &new_eps, "my-backend", &old_eps).unwrap();
         assert_eq!(alias, "my-backend");
@@ -4554,11 +4894,13 @@ mod tests {
             scheme: Scheme::Https,
             host: "10.0.1.1".into(),
             port: 443,
+            weight: 1,
         }];
         let new_eps = vec![Endpoint {
             scheme: Scheme::Https,
             host: "api.openai.com".into(),
             port: 443,
+            weight: 1,
         }];
         let alias = enforce_alias_update(None, &new_eps, "my-backend", &old_eps).unwrap();
         assert_eq!(alias, "api.openai.com");
@@ -4589,7 +4931,10 @@ mod tests {
                     scheme: Scheme::Https,
                     host: "10.0.0.1".into(),
                     port: 443,
+                    weight: 1,
                 }],
+                load_balancing: None,
+                health_check: None,
             },
             protocol: "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1".into(),
             alias: None,
@@ -4598,6 +4943,7 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         };
@@ -4646,7 +4992,10 @@ mod tests {
                 scheme: Scheme::Https,
                 host: "api.anthropic.com".into(),
                 port: 443,
+                weight: 1,
             }],
+            load_balancing: None,
+            health_check: None,
         };
         update_req.alias = None; // let alias be re-derived
         let updated = svc.update_upstream(&ctx, u.id, update_req).await.unwrap();
@@ -4671,7 +5020,10 @@ mod tests {
                 scheme: Scheme::Https,
                 host: "10.0.0.1".into(),
                 port: 443,
+                weight: 1,
             }],
+            load_balancing: None,
+            health_check: None,
         };
         update_req.alias = None; // no explicit alias provided
         let err = svc
@@ -4699,7 +5051,10 @@ mod tests {
                 scheme: Scheme::Https,
                 host: "10.0.0.1".into(),
                 port: 443,
+                weight: 1,
             }],
+            load_balancing: None,
+            health_check: None,
         };
         update_req.alias = Some("my-backend".into());
         let updated = svc.update_upstream(&ctx, u.id, update_req).await.unwrap();
@@ -4741,13 +5096,17 @@ mod tests {
                         scheme: Scheme::Https,
                         host: "us.vendor.com".into(),
                         port: 443,
+                        weight: 1,
                     },
                     Endpoint {
                         scheme: Scheme::Https,
                         host: "eu.vendor.com".into(),
                         port: 443,
+                        weight: 1,
                     },
                 ],
+                load_balancing: None,
+                health_check: None,
             },
             protocol: "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1".into(),
             alias: None,
@@ -4756,6 +5115,7 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         };
@@ -4778,13 +5138,17 @@ mod tests {
                         scheme: Scheme::Https,
                         host: "us.vendor.com".into(),
                         port: 8443,
+                        weight: 1,
                     },
                     Endpoint {
                         scheme: Scheme::Https,
                         host: "eu.vendor.com".into(),
                         port: 8443,
+                        weight: 1,
                     },
                 ],
+                load_balancing: None,
+                health_check: None,
             },
             protocol: "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1".into(),
             alias: None,
@@ -4793,6 +5157,7 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         };
@@ -4808,13 +5173,17 @@ mod tests {
                         scheme: Scheme::Https,
                         host: "us.vendor.com".into(),
                         port: 9443,
+                        weight: 1,
                     },
                     Endpoint {
                         scheme: Scheme::Https,
                         host: "eu.vendor.com".into(),
                         port: 9443,
+                        weight: 1,
                     },
                 ],
+                load_balancing: None,
+                health_check: None,
             },
             protocol: "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1".into(),
             alias: None,
@@ -4823,6 +5192,7 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         };
@@ -4849,13 +5219,17 @@ mod tests {
                         scheme: Scheme::Https,
                         host: "foo.co.uk".into(),
                         port: 443,
+                        weight: 1,
                     },
                     Endpoint {
                         scheme: Scheme::Https,
                         host: "bar.co.uk".into(),
                         port: 443,
+                        weight: 1,
                     },
                 ],
+                load_balancing: None,
+                health_check: None,
             },
             protocol: "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1".into(),
             alias: None,
@@ -4864,6 +5238,7 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         };
@@ -4891,13 +5266,17 @@ mod tests {
                         scheme: Scheme::Https,
                         host: "foo.co.uk".into(),
                         port: 443,
+                        weight: 1,
                     },
                     Endpoint {
                         scheme: Scheme::Https,
                         host: "bar.co.uk".into(),
                         port: 443,
+                        weight: 1,
                     },
                 ],
+                load_balancing: None,
+                health_check: None,
             },
             protocol: "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1".into(),
             alias: Some("my-uk-backends".into()),
@@ -4906,6 +5285,7 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         };
@@ -4914,6 +5294,92 @@ mod tests {
         assert_eq!(u.alias, "my-uk-backends");
     }
 
+    fn make_create_grpc_route(
+        upstream_id: Uuid,
+        service: &str,
+        method: &str,
+    ) -> CreateRouteRequest {
+        CreateRouteRequest {
+            match_rules: MatchRules {
+                http: None,
+                grpc: Some(GrpcMatch {
+                    service: service.into(),
+                    method: method.into(),
+                }),
+            },
+            ..make_create_route(upstream_id)
+        }
+    }
+
+    #[tokio::test]
+    async fn create_grpc_route_validates_service_and_method() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream_ip("grpc"))
+            .await
+            .unwrap();
+
+        svc.create_route(
+            &ctx,
+            make_create_grpc_route(u.id, "example.v1.UserService", "GetUser"),
+        )
+        .await
+        .unwrap();
+
+        for (service, method, reason) in [
+            ("", "GetUser", "INVALID_GRPC_SERVICE"),
+            ("example..UserService", "GetUser", "INVALID_GRPC_SERVICE"),
+            ("example/v1.UserService", "GetUser", "INVALID_GRPC_SERVICE"),
+            ("example.v1.UserService", "", "INVALID_GRPC_METHOD"),
+            ("example.v1.UserService", "Get/User", "INVALID_GRPC_METHOD"),
+        ] {
+            let err = svc
+                .create_route(&ctx, make_create_grpc_route(u.id, service, method))
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, DomainError::Validation { reason: r, .. } if r == reason),
+                "{service}/{method}: expected {reason}, got: {err:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn create_grpc_route_overlap_same_method_returns_conflict() {
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream_ip("grpc"))
+            .await
+            .unwrap();
+
+        svc.create_route(
+            &ctx,
+            make_create_grpc_route(u.id, "example.v1.UserService", "GetUser"),
+        )
+        .await
+        .unwrap();
+        svc.create_route(
+            &ctx,
+            make_create_grpc_route(u.id, "example.v1.UserService", "ListUsers"),
+        )
+        .await
+        .unwrap();
+
+        let err = svc
+            .create_route(
+                &ctx,
+                make_create_grpc_route(u.id, "example.v1.UserService", "GetUser"),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::Conflict { .. }),
+            "expected Conflict, got: {err:?}"
+        );
+    }
+
     // -- Route overlap determinism tests --
 
     #[tokio::test]
@@ -4975,6 +5441,9 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -5119,6 +5588,9 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -5141,6 +5613,9 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -5227,6 +5702,163 @@ mod tests {
         assert!(updated.rate_limit.is_none());
     }
 
+    #[tokio::test]
+    async fn route_timeouts_and_retry_are_validated_and_stored() {
+        use crate::domain::model::{RetryConfig, TimeoutConfig};
+
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream_ip("openai"))
+            .await
+            .unwrap();
+
+        let mut req = make_create_route(u.id);
+        req.retry = Some(RetryConfig {
+            max_attempts: 0,
+            ..RetryConfig::default()
+        });
+        let err = svc.create_route(&ctx, req).await.unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::Validation {
+                reason: "INVALID_RETRY_POLICY",
+                ..
+            }
+        ));
+
+        let mut req = make_create_route(u.id);
+        let timeouts = TimeoutConfig {
+            connect_ms: Some(500),
+            read_ms: None,
+            total_ms: Some(120_000),
+        };
+        req.timeouts = Some(timeouts);
+        req.retry = Some(RetryConfig::default());
+        let r = svc.create_route(&ctx, req).await.unwrap();
+        assert_eq!(r.timeouts, Some(timeouts));
+        assert_eq!(r.retry, Some(RetryConfig::default()));
+
+        let mut update_req = make_update_from_route(&r);
+        update_req.timeouts = Some(TimeoutConfig {
+            read_ms: Some(0),
+            ..timeouts
+        });
+        let err = svc.update_route(&ctx, r.id, update_req).await.unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::Validation {
+                reason: "INVALID_TIMEOUT",
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn route_cache_config_is_validated_and_stored() {
+        use crate::domain::model::{CacheConfig, SharingMode};
+
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream_ip("openai"))
+            .await
+            .unwrap();
+
+        let mut req = make_create_route(u.id);
+        req.cache = Some(CacheConfig {
+            max_object_bytes: 0,
+            ..CacheConfig::default()
+        });
+        let err = svc.create_route(&ctx, req).await.unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::Validation {
+                reason: "INVALID_CACHE_CONFIG",
+                ..
+            }
+        ));
+
+        let cache = CacheConfig {
+            sharing: SharingMode::Enforce,
+            default_ttl_secs: 300,
+            ..CacheConfig::default()
+        };
+        let mut req = make_create_route(u.id);
+        req.cache = Some(cache);
+        let r = svc.create_route(&ctx, req).await.unwrap();
+        assert_eq!(r.cache, Some(cache));
+
+        let mut update_req = make_update_from_route(&r);
+        update_req.cache = Some(CacheConfig {
+            default_ttl_secs: u32::MAX,
+            ..cache
+        });
+        let err = svc.update_route(&ctx, r.id, update_req).await.unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::Validation {
+                reason: "INVALID_CACHE_CONFIG",
+                ..
+            }
+        ));
+
+        let mut update_req = make_update_from_route(&r);
+        update_req.cache = None;
+        let updated = svc.update_route(&ctx, r.id, update_req).await.unwrap();
+        assert!(updated.cache.is_none());
+    }
+
+    #[tokio::test]
+    async fn upstream_load_balancing_and_health_check_are_validated_and_stored() {
+        use crate::domain::model::{HealthCheckConfig, HealthProbe, LoadBalancing};
+
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let mut req = make_create_upstream_ip("openai");
+        req.server.endpoints[0].weight = 0;
+        let err = svc.create_upstream(&ctx, req).await.unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::Validation {
+                reason: "INVALID_ENDPOINT_WEIGHT",
+                ..
+            }
+        ));
+
+        let mut req = make_create_upstream_ip("openai");
+        req.server.load_balancing = Some(LoadBalancing::LeastInFlight);
+        let health_check = HealthCheckConfig {
+            probe: HealthProbe::Http {
+                path: "/healthz".into(),
+                expected_status: vec![200],
+            },
+            ..HealthCheckConfig::default()
+        };
+        req.server.health_check = Some(health_check.clone());
+        let u = svc.create_upstream(&ctx, req).await.unwrap();
+        assert_eq!(u.server.load_balancing, Some(LoadBalancing::LeastInFlight));
+        assert_eq!(u.server.health_check, Some(health_check.clone()));
+
+        let mut update_req = make_update_from_upstream(&u);
+        update_req.server.health_check = Some(HealthCheckConfig {
+            unhealthy_threshold: 0,
+            ..health_check
+        });
+        let err = svc
+            .update_upstream(&ctx, u.id, update_req)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::Validation {
+                reason: "INVALID_HEALTH_CHECK",
+                ..
+            }
+        ));
+    }
+
     #[tokio::test]
     async fn update_route_introducing_overlap_returns_conflict() {
         let svc = make_service();
@@ -13,19 +13,46 @@ use uuid::Uuid;
 use std::net::SocketAddr;
 
 use crate::domain::error::DomainError;
+use crate::domain::load_balancing::InFlightGuard;
 use crate::domain::model::{
-    CreateRouteRequest, CreateUpstreamRequest, Endpoint, ListQuery, Route, UpdateRouteRequest,
-    UpdateUpstreamRequest, Upstream,
+    CreateRouteRequest, CreateUpstreamRequest, Endpoint, ListQuery, Route, Server,
+    UpdateRouteRequest, UpdateUpstreamRequest, Upstream,
 };
 
 /// Result of endpoint selection: the domain endpoint plus an optional
 /// pre-resolved socket address from the load balancer's DNS cache.
 #[domain_model]
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub(crate) struct SelectedEndpoint {
     pub endpoint: Endpoint,
     /// When set, `upstream_peer` can skip DNS and connect directly.
     pub resolved_addr: Option<SocketAddr>,
+    /// Counts the request against the endpoint for least-in-flight
+    /// balancing until dropped; the proxy moves it into the response body,
+    /// or the WebSocket bridge, once the upstream answers. `None` when the
+    /// load balancer was bypassed.
+    pub in_flight: Option<InFlightGuard>,
+}
+
+/// Health of one resolved endpoint address as seen by this node.
+#[domain_model]
+#[derive(Debug, Clone)]
+pub(crate) struct EndpointHealth {
+    pub endpoint: Endpoint,
+    /// Resolved `ip:port` the probes and requests go to.
+    pub address: String,
+    pub status: EndpointHealthStatus,
+    pub in_flight: usize,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EndpointHealthStatus {
+    Healthy,
+    /// Failing active health checks.
+    Unhealthy,
+    /// Temporarily removed by passive outlier detection.
+    Ejected,
 }
 
 /// Internal Control Plane service trait — configuration management and resolution.
@@ -119,6 +146,15 @@ pub(crate) trait DataPlaneService: Send + Sync {
 
     /// Remove all rate-limit buckets associated with a route.
     fn remove_rate_limit_keys_for_route(&self, route_id: Uuid);
+
+    /// Remove the retry budget associated with a route.
+    fn remove_retry_budget_for_route(&self, route_id: Uuid);
+
+    /// Remove all circuit breaker state associated with an upstream (all endpoints).
+    fn remove_circuit_breakers_for_upstream(&self, upstream_id: Uuid);
+
+    /// Drop every cached response stored for a route.
+    fn remove_cached_responses_for_route(&self, route_id: Uuid);
 }
 
 /// Endpoint selection abstraction for multi-endpoint load balancing.
@@ -126,9 +162,24 @@ pub(crate) trait DataPlaneService: Send + Sync {
 /// Implementations select the next healthy endpoint for a given upstream.
 #[async_trait]
 pub(crate) trait EndpointSelector: Send + Sync {
-    /// Select the next healthy endpoint for the given upstream.
+    /// Select the next healthy endpoint for the given upstream using the
+    /// server's load-balancing strategy. `key` is hashed by consistent
+    /// hashing and ignored otherwise.
     /// Returns `None` if all backends are unhealthy or the endpoint list is empty.
-    async fn select(&self, upstream_id: Uuid, endpoints: &[Endpoint]) -> Option<SelectedEndpoint>;
+    async fn select(
+        &self,
+        upstream_id: Uuid,
+        server: &Server,
+        key: &[u8],
+    ) -> Option<SelectedEndpoint>;
+
+    /// Feed the outcome of a request sent to `endpoint` into passive
+    /// outlier detection. A no-op unless the upstream enables it.
+    fn report_outcome(&self, upstream_id: Uuid, endpoint: &Endpoint, success: bool);
+
+    /// Per-address health of the upstream's endpoints. Empty when no load
+    /// balancer has been built for it on this node.
+    fn health(&self, upstream_id: Uuid) -> Vec<EndpointHealth>;
 
     /// Invalidate cached state for the given upstream (called on CRUD).
     fn invalidate(&self, upstream_id: Uuid);
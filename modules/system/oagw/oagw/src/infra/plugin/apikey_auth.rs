@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use credstore_sdk::{CredStoreClientV1, SecretRef};
+use credstore_sdk::{CredStoreClientV1, SecretRef, SharingMode};
 use serde::Deserialize;
 
 use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
@@ -59,6 +59,8 @@ impl AuthPlugin for ApiKeyAuthPlugin {
 
         let value = format!("{}{}", config.prefix, secret_str);
         ctx.headers.insert(config.header.to_lowercase(), value);
+        ctx.user_credentials |= response.sharing == SharingMode::Private;
+        ctx.credential_tenant_ids.push(response.owner_tenant_id.0);
 
         Ok(())
     }
@@ -102,6 +104,8 @@ mod tests {
             headers: HashMap::new(),
             config,
             security_context: test_security_context(),
+            user_credentials: false,
+            credential_tenant_ids: Vec::new(),
         }
     }
 
@@ -210,4 +214,38 @@ mod tests {
         let err = plugin.authenticate(&mut ctx).await.unwrap_err();
         assert!(matches!(err, PluginError::Internal(_)));
     }
+
+    #[tokio::test]
+    async fn private_secret_marks_user_credentials() {
+        struct SharingCredStore(SharingMode);
+
+        #[async_trait::async_trait]
+        impl CredStoreClientV1 for SharingCredStore {
+            async fn get(
+                &self,
+                _ctx: &SecurityContext,
+                _key: &SecretRef,
+            ) -> Result<Option<GetSecretResponse>, CredStoreError> {
+                Ok(Some(GetSecretResponse {
+                    value: SecretValue::new(b"sk-user".to_vec()),
+                    owner_tenant_id: CredstoreTenantId::nil(),
+                    sharing: self.0,
+                    is_inherited: false,
+                }))
+            }
+        }
+
+        for (sharing, expected) in [
+            (SharingMode::Private, true),
+            (SharingMode::Tenant, false),
+            (SharingMode::Shared, false),
+        ] {
+            let plugin = ApiKeyAuthPlugin::new(Arc::new(SharingCredStore(sharing)));
+            let mut ctx = make_auth_ctx(make_config("x-api-key", "", "cred://key"));
+
+            plugin.authenticate(&mut ctx).await.unwrap();
+            assert_eq!(ctx.user_credentials, expected, "{sharing:?}");
+            assert_eq!(ctx.credential_tenant_ids, vec![Uuid::nil()]);
+        }
+    }
 }
@@ -34,6 +34,8 @@ mod tests {
                 .subject_id(Uuid::nil())
                 .build()
                 .unwrap(),
+            user_credentials: false,
+            credential_tenant_ids: Vec::new(),
         };
 
         plugin.authenticate(&mut ctx).await.unwrap();
@@ -1,4 +1,4 @@
-use credstore_sdk::{CredStoreClientV1, SecretRef};
+use credstore_sdk::{CredStoreClientV1, SecretRef, SharingMode};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
@@ -7,6 +7,7 @@ use modkit_auth::oauth2::types::{ClientAuthMethod, SecretString};
 use modkit_auth::oauth2::{OAuthClientConfig, fetch_token};
 use pingora_memory_cache::MemoryCache;
 use url::Url;
+use uuid::Uuid;
 
 use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
 
@@ -86,6 +87,18 @@ impl OAuth2PluginConfig {
 struct CachedToken {
     key: String,
     token: SecretString,
+    /// The client credentials were per-user secrets.
+    user_credentials: bool,
+    /// Tenants owning the client credentials.
+    credential_tenant_ids: Vec<Uuid>,
+}
+
+/// A credential read from the credential store.
+struct ResolvedSecret {
+    value: String,
+    /// The secret is a per-user secret.
+    private: bool,
+    owner_tenant_id: Uuid,
 }
 
 fn build_cache_key(ctx: &AuthContext, auth_method: ClientAuthMethod) -> String {
@@ -150,12 +163,13 @@ impl OAuth2ClientCredAuthPlugin {
         self
     }
 
-    /// Resolve a `cred://` reference to its plaintext UTF-8 value.
+    /// Resolve a `cred://` reference to its plaintext UTF-8 value, whether
+    /// the secret is private to the calling user, and its owner tenant.
     async fn resolve_secret(
         &self,
         security_context: &modkit_security::SecurityContext,
         cred_ref: &str,
-    ) -> Result<String, PluginError> {
+    ) -> Result<ResolvedSecret, PluginError> {
         let raw = cred_ref.strip_prefix("cred://").unwrap_or(cred_ref);
         let secret_ref = SecretRef::new(raw)
             .map_err(|e| PluginError::Internal(format!("invalid secret ref '{raw}': {e}")))?;
@@ -165,9 +179,16 @@ impl OAuth2ClientCredAuthPlugin {
             .await
             .map_err(|e| PluginError::Internal(format!("credstore error: {e}")))?
             .ok_or_else(|| PluginError::SecretNotFound(cred_ref.to_owned()))?;
-        std::str::from_utf8(response.value.as_bytes())
+        let value = std::str::from_utf8(response.value.as_bytes())
             .map(str::to_owned)
-            .map_err(|_| PluginError::Internal(format!("secret '{cred_ref}' is not valid UTF-8")))
+            .map_err(|_| {
+                PluginError::Internal(format!("secret '{cred_ref}' is not valid UTF-8"))
+            })?;
+        Ok(ResolvedSecret {
+            value,
+            private: response.sharing == SharingMode::Private,
+            owner_tenant_id: response.owner_tenant_id.0,
+        })
     }
 }
 
@@ -186,24 +207,29 @@ impl AuthPlugin for OAuth2ClientCredAuthPlugin {
                 "authorization".into(),
                 format!("Bearer {}", entry.token.expose()),
             );
+            ctx.user_credentials |= entry.user_credentials;
+            ctx.credential_tenant_ids
+                .extend_from_slice(&entry.credential_tenant_ids);
             return Ok(());
 
             // Hash collision — treat as miss, do not use this entry.
         }
 
         // Cache miss — resolve credentials and fetch token.
-        let client_id_str = self
+        let client_id = self
             .resolve_secret(&ctx.security_context, &config.client_id_ref)
             .await?;
-        let client_secret_str = self
+        let client_secret = self
             .resolve_secret(&ctx.security_context, &config.client_secret_ref)
             .await?;
+        let user_credentials = client_id.private || client_secret.private;
+        let credential_tenant_ids = vec![client_id.owner_tenant_id, client_secret.owner_tenant_id];
 
         let mut oauth_config = OAuthClientConfig {
             token_endpoint: config.token_endpoint,
             issuer_url: config.issuer_url,
-            client_id: client_id_str,
-            client_secret: SecretString::new(client_secret_str),
+            client_id: client_id.value,
+            client_secret: SecretString::new(client_secret.value),
             scopes: config.scopes,
             auth_method: self.auth_method,
             ..Default::default()
@@ -228,6 +254,8 @@ impl AuthPlugin for OAuth2ClientCredAuthPlugin {
             CachedToken {
                 key: key.clone(),
                 token: fetched.bearer.clone(),
+                user_credentials,
+                credential_tenant_ids: credential_tenant_ids.clone(),
             },
             Some(ttl),
         );
@@ -236,6 +264,8 @@ impl AuthPlugin for OAuth2ClientCredAuthPlugin {
             "authorization".into(),
             format!("Bearer {}", fetched.bearer.expose()),
         );
+        ctx.user_credentials |= user_credentials;
+        ctx.credential_tenant_ids.extend(credential_tenant_ids);
 
         Ok(())
     }
@@ -303,6 +333,8 @@ mod tests {
             headers: HashMap::new(),
             config,
             security_context: test_security_context(),
+            user_credentials: false,
+            credential_tenant_ids: Vec::new(),
         }
     }
 
@@ -679,6 +711,8 @@ mod tests {
             headers: HashMap::new(),
             config,
             security_context: sc,
+            user_credentials: false,
+            credential_tenant_ids: Vec::new(),
         }
     }
 
@@ -1032,6 +1066,8 @@ mod tests {
             CachedToken {
                 key: key_a.clone(),
                 token: SecretString::new("secret-token-a"),
+                user_credentials: false,
+                credential_tenant_ids: Vec::new(),
             },
             Some(Duration::from_secs(3600)),
         );
@@ -0,0 +1,455 @@
+//! gRPC support for the proxy data plane.
+//!
+//! Requests reach Pingora over an in-memory HTTP/1.1 bridge, which has no
+//! room for the HTTP/2 trailers that carry a call's final `grpc-status`.
+//! `PingoraProxy` therefore appends the upstream trailers to the body as a
+//! gRPC-Web style trailer frame ([`encode_trailers`]); [`split_trailers`]
+//! takes that frame back out on the service side, and the REST handler sends
+//! the trailers as real HTTP/2 trailers through [`GrpcResponseBody`].
+
+use std::fmt::Write as _;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+use std::time::Duration;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use futures_util::StreamExt;
+use futures_util::stream::unfold;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use hyper::body::Frame;
+use oagw_sdk::body::{BodyStream, BoxError};
+use parking_lot::Mutex;
+use tokio::sync::oneshot;
+
+pub(crate) const GRPC_STATUS: &str = "grpc-status";
+pub(crate) const GRPC_MESSAGE: &str = "grpc-message";
+
+/// `UNAVAILABLE`: reported when the upstream stream breaks mid-call.
+const STATUS_UNAVAILABLE: u8 = 14;
+
+/// Request headers defined by the gRPC protocol itself. They are forwarded
+/// whatever the upstream's passthrough mode.
+const PROTOCOL_HEADERS: &[&str] = &["grpc-timeout", "grpc-encoding", "grpc-accept-encoding"];
+
+/// Flags bit marking a trailer frame in gRPC-Web framing.
+const TRAILER_FLAG: u8 = 0x80;
+
+/// Length-prefixed message header: 1 flags byte + 4-byte big-endian length.
+const FRAME_HEADER_LEN: usize = 5;
+
+/// Cap on a trailer frame (64 KiB), in line with the bridge's header cap.
+const MAX_TRAILER_BYTES: usize = 64 * 1024;
+
+/// Copy the gRPC protocol headers to the outbound request and declare
+/// trailer support (`te: trailers`), which hop-by-hop stripping removes but
+/// gRPC servers expect.
+pub(crate) fn prepare_request_headers(inbound: &HeaderMap, outbound: &mut HeaderMap) {
+    for name in PROTOCOL_HEADERS {
+        if !outbound.contains_key(*name)
+            && let Some(v) = inbound.get(*name)
+        {
+            outbound.insert(*name, v.clone());
+        }
+    }
+    outbound.insert(http::header::TE, HeaderValue::from_static("trailers"));
+}
+
+/// Parse a `grpc-timeout` value: up to 8 digits followed by a unit
+/// (`H`, `M`, `S`, `m`, `u`, `n`).
+pub(crate) fn parse_timeout(value: &str) -> Option<Duration> {
+    if !value.is_ascii() || value.len() < 2 {
+        return None;
+    }
+    let (digits, unit) = value.split_at(value.len() - 1);
+    if digits.len() > 8 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let n: u64 = digits.parse().ok()?;
+    Some(match unit {
+        "H" => Duration::from_secs(n * 3600),
+        "M" => Duration::from_secs(n * 60),
+        "S" => Duration::from_secs(n),
+        "m" => Duration::from_millis(n),
+        "u" => Duration::from_micros(n),
+        "n" => Duration::from_nanos(n),
+        _ => return None,
+    })
+}
+
+/// Map the HTTP status of a gateway-originated error to a gRPC status code.
+///
+/// Gateway errors are canonical errors whose HTTP status follows from their
+/// category, so this inverts that mapping (ADR: gRPC Support, Error Mapping).
+pub(crate) fn status_for_http(status: u16) -> u8 {
+    match status {
+        400 => 3,        // INVALID_ARGUMENT
+        401 => 16,       // UNAUTHENTICATED
+        403 => 7,        // PERMISSION_DENIED
+        404 => 5,        // NOT_FOUND
+        409 => 10,       // ABORTED
+        429 => 8,        // RESOURCE_EXHAUSTED
+        499 => 1,        // CANCELLED
+        500 => 13,       // INTERNAL
+        501 => 12,       // UNIMPLEMENTED
+        502 | 503 => 14, // UNAVAILABLE
+        504 => 4,        // DEADLINE_EXCEEDED
+        _ => 2,          // UNKNOWN
+    }
+}
+
+/// `grpc-status` / `grpc-message` headers for a status, with the message
+/// percent-encoded as the gRPC HTTP/2 protocol requires.
+pub(crate) fn status_headers(code: u8, message: &str) -> HeaderMap {
+    let mut encoded = String::with_capacity(message.len());
+    for b in message.bytes() {
+        if (0x20..=0x7e).contains(&b) && b != b'%' {
+            encoded.push(char::from(b));
+        } else {
+            let _ = write!(encoded, "%{b:02X}");
+        }
+    }
+    let mut headers = HeaderMap::new();
+    headers.insert(GRPC_STATUS, HeaderValue::from(u16::from(code)));
+    if let Ok(v) = HeaderValue::from_str(&encoded) {
+        headers.insert(GRPC_MESSAGE, v);
+    }
+    headers
+}
+
+/// Encode response trailers as a trailer frame so they can travel through
+/// the HTTP/1.1 bridge as the last piece of the body.
+pub(crate) fn encode_trailers(trailers: &HeaderMap) -> Bytes {
+    let mut block = Vec::new();
+    for (name, value) in trailers {
+        block.extend_from_slice(name.as_str().as_bytes());
+        block.extend_from_slice(b": ");
+        block.extend_from_slice(value.as_bytes());
+        block.extend_from_slice(b"\r\n");
+    }
+    let mut frame = BytesMut::with_capacity(FRAME_HEADER_LEN + block.len());
+    frame.put_u8(TRAILER_FLAG);
+    frame.put_u32(u32::try_from(block.len()).unwrap_or(u32::MAX));
+    frame.extend_from_slice(&block);
+    frame.freeze()
+}
+
+fn decode_trailers(block: &[u8]) -> HeaderMap {
+    let mut trailers = HeaderMap::new();
+    for line in block.split(|&b| b == b'\n') {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let Some(colon) = line.iter().position(|&b| b == b':') else {
+            continue;
+        };
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(&line[..colon]),
+            HeaderValue::from_bytes(line[colon + 1..].trim_ascii()),
+        ) {
+            trailers.append(name, value);
+        }
+    }
+    trailers
+}
+
+/// Remove the trailer frame written by [`encode_trailers`] from a gRPC
+/// response body.
+///
+/// Messages are passed through unchanged as they arrive. The trailers are
+/// delivered on the returned receiver once their frame is complete; the
+/// receiver resolves to an error if the body ends without one (e.g. a
+/// trailers-only response, which carries its status in the headers).
+pub(crate) fn split_trailers(inner: BodyStream) -> (BodyStream, oneshot::Receiver<HeaderMap>) {
+    let (tx, rx) = oneshot::channel();
+    let splitter = TrailerSplitter {
+        prefix: BytesMut::with_capacity(FRAME_HEADER_LEN),
+        remaining: 0,
+        trailer: None,
+        tx: Some(tx),
+    };
+    let stream = unfold((inner, splitter), |(mut inner, mut splitter)| async move {
+        loop {
+            let chunk = match inner.next().await? {
+                Ok(chunk) => chunk,
+                Err(e) => return Some((Err(e), (inner, splitter))),
+            };
+            match splitter.feed(&chunk) {
+                Ok(out) if out.is_empty() => {}
+                Ok(out) => return Some((Ok(out), (inner, splitter))),
+                Err(e) => return Some((Err(e), (inner, splitter))),
+            }
+        }
+    });
+    (Box::pin(stream), rx)
+}
+
+/// Incremental parser over the length-prefixed message stream.
+struct TrailerSplitter {
+    /// Partially received message header.
+    prefix: BytesMut,
+    /// Bytes of the current message still to pass through.
+    remaining: usize,
+    /// Trailer frame being collected: declared length and bytes so far.
+    trailer: Option<(usize, BytesMut)>,
+    tx: Option<oneshot::Sender<HeaderMap>>,
+}
+
+impl TrailerSplitter {
+    /// Consume one body chunk, returning the message bytes to forward.
+    fn feed(&mut self, mut chunk: &[u8]) -> Result<Bytes, BoxError> {
+        let mut out = BytesMut::new();
+        while !chunk.is_empty() {
+            if let Some((len, buf)) = self.trailer.as_mut() {
+                let n = (*len - buf.len()).min(chunk.len());
+                buf.extend_from_slice(&chunk[..n]);
+                chunk = &chunk[n..];
+                if buf.len() == *len {
+                    let block = buf.split().freeze();
+                    self.trailer = None;
+                    self.deliver(&block);
+                }
+                continue;
+            }
+            if self.remaining > 0 {
+                let n = self.remaining.min(chunk.len());
+                out.extend_from_slice(&chunk[..n]);
+                self.remaining -= n;
+                chunk = &chunk[n..];
+                continue;
+            }
+
+            let n = (FRAME_HEADER_LEN - self.prefix.len()).min(chunk.len());
+            self.prefix.extend_from_slice(&chunk[..n]);
+            chunk = &chunk[n..];
+            if self.prefix.len() < FRAME_HEADER_LEN {
+                continue;
+            }
+            let flags = self.prefix[0];
+            let len = u32::from_be_bytes([
+                self.prefix[1],
+                self.prefix[2],
+                self.prefix[3],
+                self.prefix[4],
+            ]) as usize;
+            if flags & TRAILER_FLAG == 0 {
+                out.extend_from_slice(&self.prefix);
+                self.remaining = len;
+            } else if len > MAX_TRAILER_BYTES {
+                return Err(format!(
+                    "gRPC trailer frame of {len} bytes exceeds maximum of {MAX_TRAILER_BYTES} bytes"
+                )
+                .into());
+            } else if len == 0 {
+                self.deliver(&[]);
+            } else {
+                self.trailer = Some((len, BytesMut::with_capacity(len)));
+            }
+            self.prefix.clear();
+        }
+        Ok(out.freeze())
+    }
+
+    fn deliver(&mut self, block: &[u8]) {
+        if let Some(tx) = self.tx.take() {
+            let _ = tx.send(decode_trailers(block));
+        }
+    }
+}
+
+/// Wrapper that satisfies `Clone + Send + Sync + 'static` required by
+/// `http::Extensions::insert`. Carries a call's trailers from the data plane
+/// to the REST handler; the receiver is taken once.
+#[derive(Clone)]
+pub(crate) struct GrpcTrailersHandle(Arc<Mutex<Option<oneshot::Receiver<HeaderMap>>>>);
+
+impl GrpcTrailersHandle {
+    pub fn new(trailers: oneshot::Receiver<HeaderMap>) -> Self {
+        Self(Arc::new(Mutex::new(Some(trailers))))
+    }
+
+    /// Take the trailers receiver out of the handle. Returns `None` if
+    /// already taken.
+    pub fn take(&self) -> Option<oneshot::Receiver<HeaderMap>> {
+        self.0.lock().take()
+    }
+}
+
+/// Response body for a proxied gRPC call: the messages as data frames, then
+/// the upstream trailers as an HTTP/2 trailers frame. If the upstream stream
+/// breaks first, the call is ended with `UNAVAILABLE` so the client sees a
+/// status rather than a reset.
+pub(crate) struct GrpcResponseBody {
+    data: Option<BodyStream>,
+    trailers: Option<oneshot::Receiver<HeaderMap>>,
+}
+
+impl GrpcResponseBody {
+    pub fn new(data: BodyStream, trailers: oneshot::Receiver<HeaderMap>) -> Self {
+        Self {
+            data: Some(data),
+            trailers: Some(trailers),
+        }
+    }
+}
+
+impl hyper::body::Body for GrpcResponseBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, BoxError>>> {
+        let this = self.get_mut();
+        if let Some(data) = this.data.as_mut() {
+            match ready!(data.poll_next_unpin(cx)) {
+                Some(Ok(chunk)) => return Poll::Ready(Some(Ok(Frame::data(chunk)))),
+                Some(Err(e)) => {
+                    tracing::debug!(error = %e, "gRPC response stream aborted");
+                    this.data = None;
+                    this.trailers = None;
+                    return Poll::Ready(Some(Ok(Frame::trailers(status_headers(
+                        STATUS_UNAVAILABLE,
+                        "upstream stream aborted",
+                    )))));
+                }
+                None => this.data = None,
+            }
+        }
+        let Some(trailers) = this.trailers.as_mut() else {
+            return Poll::Ready(None);
+        };
+        let result = ready!(Pin::new(trailers).poll(cx));
+        this.trailers = None;
+        Poll::Ready(result.ok().map(|t| Ok(Frame::trailers(t))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body_util::BodyExt;
+
+    fn message(payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0];
+        frame.extend_from_slice(&u32::try_from(payload.len()).unwrap().to_be_bytes());
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    fn trailers() -> HeaderMap {
+        let mut t = HeaderMap::new();
+        t.insert(GRPC_STATUS, HeaderValue::from_static("0"));
+        t.insert("x-request-cost", HeaderValue::from_static("7"));
+        t
+    }
+
+    fn chunked(bytes: &[u8], size: usize) -> BodyStream {
+        let chunks: Vec<Result<Bytes, BoxError>> = bytes
+            .chunks(size)
+            .map(|c| Ok(Bytes::copy_from_slice(c)))
+            .collect();
+        Box::pin(futures_util::stream::iter(chunks))
+    }
+
+    #[test]
+    fn parse_timeout_units() {
+        assert_eq!(parse_timeout("2S"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_timeout("1H"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_timeout("250m"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_timeout("10u"), Some(Duration::from_micros(10)));
+        assert_eq!(parse_timeout("S"), None);
+        assert_eq!(parse_timeout("123456789S"), None);
+        assert_eq!(parse_timeout("5s"), None);
+        assert_eq!(parse_timeout("-5S"), None);
+    }
+
+    #[test]
+    fn status_for_http_follows_adr_mapping() {
+        assert_eq!(status_for_http(401), 16);
+        assert_eq!(status_for_http(403), 7);
+        assert_eq!(status_for_http(429), 8);
+        assert_eq!(status_for_http(503), 14);
+        assert_eq!(status_for_http(504), 4);
+        assert_eq!(status_for_http(418), 2);
+    }
+
+    #[test]
+    fn status_headers_percent_encode_message() {
+        let headers = status_headers(14, "upstream 100% down\nretry");
+        assert_eq!(headers[GRPC_STATUS], "14");
+        assert_eq!(headers[GRPC_MESSAGE], "upstream 100%25 down%0Aretry");
+    }
+
+    #[test]
+    fn prepare_request_headers_forwards_protocol_headers() {
+        let mut inbound = HeaderMap::new();
+        inbound.insert("grpc-timeout", HeaderValue::from_static("5S"));
+        inbound.insert("grpc-encoding", HeaderValue::from_static("gzip"));
+        inbound.insert("x-private", HeaderValue::from_static("secret"));
+        let mut outbound = HeaderMap::new();
+
+        prepare_request_headers(&inbound, &mut outbound);
+
+        assert_eq!(outbound["grpc-timeout"], "5S");
+        assert_eq!(outbound["grpc-encoding"], "gzip");
+        assert_eq!(outbound[http::header::TE], "trailers");
+        assert!(!outbound.contains_key("x-private"));
+    }
+
+    #[tokio::test]
+    async fn split_trailers_across_chunk_boundaries() {
+        let mut wire = message(b"first");
+        wire.extend(message(b"second message"));
+        wire.extend_from_slice(&encode_trailers(&trailers()));
+
+        for size in [1, 3, 7, wire.len()] {
+            let (body, rx) = split_trailers(chunked(&wire, size));
+            let data: Vec<u8> = body.map(|c| c.unwrap().to_vec()).concat().await;
+            let mut expected = message(b"first");
+            expected.extend(message(b"second message"));
+            assert_eq!(data, expected, "chunk size {size}");
+            assert_eq!(rx.await.unwrap(), trailers(), "chunk size {size}");
+        }
+    }
+
+    #[tokio::test]
+    async fn split_trailers_without_trailer_frame_closes_receiver() {
+        let (body, rx) = split_trailers(chunked(&message(b"only"), 4));
+        let data: Vec<u8> = body.map(|c| c.unwrap().to_vec()).concat().await;
+        assert_eq!(data, message(b"only"));
+        assert!(rx.await.is_err());
+    }
+
+    #[tokio::test]
+    async fn split_trailers_rejects_oversized_trailer_frame() {
+        let mut frame = vec![TRAILER_FLAG];
+        frame.extend_from_slice(&u32::MAX.to_be_bytes());
+        let (mut body, _rx) = split_trailers(chunked(&frame, frame.len()));
+        assert!(body.next().await.unwrap().is_err());
+    }
+
+    #[tokio::test]
+    async fn response_body_emits_trailers_after_data() {
+        let (tx, rx) = oneshot::channel();
+        tx.send(trailers()).unwrap();
+        let body = GrpcResponseBody::new(chunked(&message(b"reply"), 64), rx);
+
+        let collected = body.collect().await.unwrap();
+        assert_eq!(collected.trailers(), Some(&trailers()));
+        assert_eq!(collected.to_bytes(), Bytes::from(message(b"reply")));
+    }
+
+    #[tokio::test]
+    async fn response_body_reports_unavailable_on_stream_error() {
+        let (_tx, rx) = oneshot::channel();
+        let data: BodyStream = Box::pin(futures_util::stream::iter(vec![
+            Ok(Bytes::from(message(b"partial"))),
+            Err::<Bytes, BoxError>("reset".into()),
+        ]));
+        let body = GrpcResponseBody::new(data, rx);
+
+        let collected = body.collect().await.unwrap();
+        let trailers = collected.trailers().cloned().unwrap();
+        assert_eq!(trailers[GRPC_STATUS], "14");
+    }
+}
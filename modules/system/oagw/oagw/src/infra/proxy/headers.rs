@@ -140,6 +140,21 @@ pub fn is_websocket_upgrade(headers: &HeaderMap) -> bool {
     has_upgrade_websocket && has_connection_upgrade
 }
 
+/// Returns `true` if the `Content-Type` marks a gRPC message stream:
+/// `application/grpc` or a `+proto`/`+json` style subtype. gRPC-Web
+/// (`application/grpc-web`) is a different wire format and does not match.
+pub fn is_grpc_content_type(headers: &HeaderMap) -> bool {
+    headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            let v = v.trim().to_ascii_lowercase();
+            v == "application/grpc"
+                || v.starts_with("application/grpc+")
+                || v.starts_with("application/grpc;")
+        })
+}
+
 /// Like [`strip_hop_by_hop`] but preserves `Upgrade` and `Connection` headers,
 /// which are required for WebSocket upgrade negotiation (RFC 6455 §4.1).
 pub fn strip_hop_by_hop_for_upgrade(headers: &mut HeaderMap) {
@@ -748,6 +763,34 @@ mod tests {
         assert!(!is_websocket_upgrade(&headers));
     }
 
+    #[test]
+    fn grpc_content_type_detected() {
+        for ct in [
+            "application/grpc",
+            "application/grpc+proto",
+            "Application/GRPC+json",
+            "application/grpc; charset=utf-8",
+        ] {
+            let mut headers = HeaderMap::new();
+            headers.insert("content-type", ct.parse().unwrap());
+            assert!(is_grpc_content_type(&headers), "{ct}");
+        }
+    }
+
+    #[test]
+    fn grpc_content_type_rejects_other_types() {
+        for ct in [
+            "application/grpc-web",
+            "application/json",
+            "application/grpcx",
+        ] {
+            let mut headers = HeaderMap::new();
+            headers.insert("content-type", ct.parse().unwrap());
+            assert!(!is_grpc_content_type(&headers), "{ct}");
+        }
+        assert!(!is_grpc_content_type(&HeaderMap::new()));
+    }
+
     #[test]
     fn websocket_upgrade_missing_connection() {
         let mut headers = HeaderMap::new();
@@ -12,9 +12,11 @@ pub(crate) const HOP_BY_HOP_HEADERS: &[&str] = &[
     "upgrade",
 ];
 
+pub(crate) mod grpc;
 pub(crate) mod headers;
 pub(crate) mod pingora_proxy;
 pub(crate) mod request_builder;
+pub(crate) mod response_cache;
 pub(crate) mod service;
 pub(crate) mod session_bridge;
 pub(crate) mod websocket;
@@ -1,5 +1,6 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 
 use arc_swap::ArcSwap;
@@ -10,8 +11,8 @@ use pingora_core::protocols::Digest;
 use pingora_core::upstreams::peer::HttpPeer;
 use pingora_http::ResponseHeader;
 use pingora_load_balancing::discovery::ServiceDiscovery;
-use pingora_load_balancing::health_check::TcpHealthCheck;
-use pingora_load_balancing::selection::RoundRobin;
+use pingora_load_balancing::health_check::{HealthCheck, HttpHealthCheck, TcpHealthCheck};
+use pingora_load_balancing::selection::{BackendIter, BackendSelection, Consistent, RoundRobin};
 use pingora_load_balancing::{Backend, Backends, LoadBalancer};
 use pingora_memory_cache::MemoryCache;
 use pingora_proxy::{HttpProxy, ProxyHttp, Session, http_proxy};
@@ -20,8 +21,15 @@ use tracing::{info, warn};
 use uuid::Uuid;
 
 use crate::domain::error::DomainError;
-use crate::domain::model::{Endpoint, Scheme};
-use crate::domain::services::{EndpointSelector, SelectedEndpoint};
+use crate::domain::load_balancing::{
+    InFlightCounters, OutlierDetector, ejection_allowed, pick_least_loaded,
+};
+use crate::domain::model::{
+    Endpoint, HealthCheckConfig, HealthProbe, LoadBalancing, OutlierDetectionConfig, Scheme, Server,
+};
+use crate::domain::services::{
+    EndpointHealth, EndpointHealthStatus, EndpointSelector, SelectedEndpoint,
+};
 use modkit_canonical_errors::Problem;
 
 // ---------------------------------------------------------------------------
@@ -36,6 +44,8 @@ pub(crate) const H_ENDPOINT_PORT: &str = "x-oagw-internal-endpoint-port";
 pub(crate) const H_ENDPOINT_SCHEME: &str = "x-oagw-internal-endpoint-scheme";
 pub(crate) const H_INSTANCE_URI: &str = "x-oagw-internal-instance-uri";
 pub(crate) const H_RESOLVED_ADDR: &str = "x-oagw-internal-resolved-addr";
+pub(crate) const H_CONNECT_TIMEOUT_MS: &str = "x-oagw-internal-connect-timeout-ms";
+pub(crate) const H_READ_TIMEOUT_MS: &str = "x-oagw-internal-read-timeout-ms";
 
 use super::HOP_BY_HOP_HEADERS;
 
@@ -159,9 +169,15 @@ impl PingoraProxy {
     }
 
     /// Determine the ALPN setting for an endpoint, consulting the protocol
-    /// cache for HTTPS/WT endpoints.
+    /// cache for HTTPS/WT endpoints. gRPC requires HTTP/2.
     fn select_alpn(&self, ep: &Endpoint) -> pingora_core::protocols::tls::ALPN {
-        let tls = matches!(ep.scheme, Scheme::Https | Scheme::Wss | Scheme::Wt);
+        if matches!(ep.scheme, Scheme::Grpc) {
+            return pingora_core::protocols::tls::ALPN::H2;
+        }
+        let tls = matches!(
+            ep.scheme,
+            Scheme::Https | Scheme::Wss | Scheme::Wt | Scheme::Grpc
+        );
         if tls && !matches!(ep.scheme, Scheme::Wss) {
             match self.protocol_cache.get(ep) {
                 Some(CachedProtocol::Http2) => pingora_core::protocols::tls::ALPN::H2,
@@ -261,7 +277,7 @@ impl DnsDiscovery {
                 Ok(addrs) => {
                     for sock in addrs {
                         let key = sock.to_string();
-                        if let Ok(b) = Backend::new(&key) {
+                        if let Ok(b) = Backend::new_with_weight(&key, ep.weight as usize) {
                             backends.insert(b);
                             // First endpoint wins if multiple resolve to the same IP.
                             map.entry(key).or_insert_with(|| ep.clone());
@@ -270,7 +286,7 @@ impl DnsDiscovery {
                 }
                 Err(e) => {
                     warn!(addr = %addr_str, error = %e, "DNS resolution failed after retries, using original address");
-                    if let Ok(b) = Backend::new(&addr_str) {
+                    if let Ok(b) = Backend::new_with_weight(&addr_str, ep.weight as usize) {
                         backends.insert(b);
                         map.entry(addr_str).or_insert_with(|| ep.clone());
                     }
@@ -299,115 +315,399 @@ impl ServiceDiscovery for DnsDiscovery {
 // PingoraEndpointSelector — default in-process BackendSelector (D2, D3)
 // ---------------------------------------------------------------------------
 
+/// Active HTTP health check across endpoints of one upstream.
+///
+/// Pingora's `HttpHealthCheck` is bound to a single `Host`/SNI, while an
+/// upstream's backends may belong to different hostnames. Keeps one checker
+/// per configured host and dispatches on the backend's reverse-lookup entry.
+struct EndpointHttpHealthCheck {
+    checks: HashMap<String, HttpHealthCheck>,
+    addr_map: AddrMap,
+    healthy_threshold: usize,
+    unhealthy_threshold: usize,
+}
+
+impl EndpointHttpHealthCheck {
+    fn new(
+        endpoints: &[Endpoint],
+        addr_map: AddrMap,
+        config: &HealthCheckConfig,
+        path: &str,
+        expected_status: &[u16],
+    ) -> Box<Self> {
+        let timeout = Duration::from_millis(u64::from(config.timeout_ms));
+        let mut checks = HashMap::new();
+        for ep in endpoints {
+            if checks.contains_key(&ep.host) {
+                continue;
+            }
+            let tls = matches!(
+                ep.scheme,
+                Scheme::Https | Scheme::Wss | Scheme::Wt | Scheme::Grpc
+            );
+            let mut check = HttpHealthCheck::new(&ep.host, tls);
+            if let Ok(uri) = http::Uri::try_from(path) {
+                check.req.set_uri(uri);
+            }
+            check.peer_template.options.connection_timeout = Some(timeout);
+            check.peer_template.options.read_timeout = Some(timeout);
+            let expected = expected_status.to_vec();
+            check.validator = Some(Box::new(move |resp: &ResponseHeader| {
+                let status = resp.status.as_u16();
+                if expected.contains(&status) {
+                    Ok(())
+                } else {
+                    Err(pingora_core::Error::explain(
+                        pingora_core::ErrorType::HTTPStatus(status),
+                        "unexpected health check status",
+                    ))
+                }
+            }));
+            checks.insert(ep.host.clone(), check);
+        }
+        Box::new(Self {
+            checks,
+            addr_map,
+            healthy_threshold: config.healthy_threshold as usize,
+            unhealthy_threshold: config.unhealthy_threshold as usize,
+        })
+    }
+}
+
+#[async_trait]
+impl HealthCheck for EndpointHttpHealthCheck {
+    async fn check(&self, target: &Backend) -> pingora_core::Result<()> {
+        let host = self
+            .addr_map
+            .load()
+            .get(&target.addr.to_string())
+            .map(|ep| ep.host.clone());
+        let Some(check) = host.as_deref().and_then(|h| self.checks.get(h)) else {
+            return Err(pingora_core::Error::explain(
+                pingora_core::ErrorType::InternalError,
+                "health check target does not map to a configured endpoint",
+            ));
+        };
+        check.check(target).await
+    }
+
+    fn health_threshold(&self, success: bool) -> usize {
+        if success {
+            self.healthy_threshold
+        } else {
+            self.unhealthy_threshold
+        }
+    }
+}
+
+/// Build the active health check for an upstream. Without explicit
+/// configuration this is a TCP connect probe with Pingora's defaults.
+fn build_health_check(
+    endpoints: &[Endpoint],
+    addr_map: &AddrMap,
+    config: Option<&HealthCheckConfig>,
+) -> Box<dyn HealthCheck + Send + Sync> {
+    let Some(config) = config else {
+        return TcpHealthCheck::new();
+    };
+    match &config.probe {
+        HealthProbe::Tcp => {
+            let mut check = TcpHealthCheck::new();
+            check.consecutive_success = config.healthy_threshold as usize;
+            check.consecutive_failure = config.unhealthy_threshold as usize;
+            check.peer_template.options.connection_timeout =
+                Some(Duration::from_millis(u64::from(config.timeout_ms)));
+            check
+        }
+        HealthProbe::Http {
+            path,
+            expected_status,
+        } => {
+            EndpointHttpHealthCheck::new(endpoints, addr_map.clone(), config, path, expected_status)
+        }
+    }
+}
+
+/// Pingora load balancer for the configured strategy. Least-in-flight has
+/// no Pingora counterpart: it reuses the round-robin balancer for discovery
+/// and health checks and picks among ready backends itself.
+enum Balancer {
+    RoundRobin(Arc<LoadBalancer<RoundRobin>>),
+    Consistent(Arc<LoadBalancer<Consistent>>),
+}
+
+impl Balancer {
+    fn backends(&self) -> &Backends {
+        match self {
+            Self::RoundRobin(lb) => lb.backends(),
+            Self::Consistent(lb) => lb.backends(),
+        }
+    }
+
+    fn select_with<F>(&self, key: &[u8], accept: F) -> Option<Backend>
+    where
+        F: Fn(&Backend, bool) -> bool,
+    {
+        match self {
+            Self::RoundRobin(lb) => lb.select_with(key, 256, accept),
+            Self::Consistent(lb) => lb.select_with(key, 256, accept),
+        }
+    }
+}
+
 /// Cache entry: load balancer + shared reverse-lookup map + shutdown handle.
 struct LbEntry {
-    lb: Arc<LoadBalancer<RoundRobin>>,
+    balancer: Balancer,
+    strategy: LoadBalancing,
+    outlier_detection: Option<OutlierDetectionConfig>,
     /// Shared reverse-lookup map updated by [`DnsDiscovery::discover`].
     addr_map: AddrMap,
     /// Dropping this sender signals the background update task to stop.
     _shutdown_tx: watch::Sender<bool>,
 }
 
-/// Default in-process `EndpointSelector` backed by Pingora's `LoadBalancer<RoundRobin>`
+/// Default in-process `EndpointSelector` backed by Pingora's `LoadBalancer`
 /// with DNS-aware service discovery.
 ///
 /// Lazily constructs a `LoadBalancer` per upstream on first `select()` call,
-/// caches it in a `DashMap`, and attaches a `TcpHealthCheck` with 10s interval.
-/// DNS re-resolution runs every 30s via the [`DnsDiscovery`] `ServiceDiscovery`
-/// implementation. Dropping the cache entry (via `invalidate()`) stops the
-/// background task.
+/// caches it in a `DashMap`, and attaches the configured health check (a TCP
+/// probe every 10s by default). DNS re-resolution runs every 30s via the
+/// [`DnsDiscovery`] `ServiceDiscovery` implementation. Passive outlier
+/// ejection and in-flight counts are tracked per node. Dropping the cache
+/// entry (via `invalidate()`) stops the background task.
 pub struct PingoraEndpointSelector {
     cache: DashMap<Uuid, LbEntry>,
+    outliers: OutlierDetector,
+    in_flight: InFlightCounters,
+    /// Rotating start offset so least-in-flight ties spread evenly.
+    cursor: AtomicUsize,
 }
 
 impl PingoraEndpointSelector {
     pub fn new() -> Self {
         Self {
             cache: DashMap::new(),
+            outliers: OutlierDetector::new(),
+            in_flight: InFlightCounters::new(),
+            cursor: AtomicUsize::new(0),
         }
     }
 
-    /// Build a `LoadBalancer<RoundRobin>` from domain endpoints using
+    /// Build a `LoadBalancer` for the server's strategy using
     /// [`DnsDiscovery`] for dynamic DNS re-resolution.
     ///
     /// DNS resolution uses async `tokio::net::lookup_host` to avoid blocking
     /// the Tokio worker thread.
-    async fn build_entry(&self, endpoints: &[Endpoint]) -> Option<LbEntry> {
+    async fn build_entry(&self, server: &Server) -> Option<LbEntry> {
         let addr_map: AddrMap = Arc::new(ArcSwap::from_pointee(HashMap::new()));
 
-        let mut backends = Backends::new(DnsDiscovery::new(endpoints.to_vec(), addr_map.clone()));
-        backends.set_health_check(TcpHealthCheck::new());
-
-        let mut lb = LoadBalancer::<RoundRobin>::from_backends(backends);
-        lb.health_check_frequency = Some(Duration::from_secs(10));
-        lb.update_frequency = Some(Duration::from_secs(30));
-
-        // update() calls discover() which resolves DNS and populates both
-        // the backend selector and the addr_map in a single pass.
-        lb.update().await.ok()?;
+        let mut backends = Backends::new(DnsDiscovery::new(
+            server.endpoints.clone(),
+            addr_map.clone(),
+        ));
+        backends.set_health_check(build_health_check(
+            &server.endpoints,
+            &addr_map,
+            server.health_check.as_ref(),
+        ));
+        let health_check_interval = server
+            .health_check
+            .as_ref()
+            .map_or(10, |hc| u64::from(hc.interval_secs));
+
+        let strategy = server.load_balancing.clone().unwrap_or_default();
+        let (balancer, shutdown_tx) = match strategy {
+            LoadBalancing::ConsistentHash { .. } => {
+                let (lb, tx) =
+                    start_balancer::<Consistent>(backends, health_check_interval).await?;
+                (Balancer::Consistent(lb), tx)
+            }
+            LoadBalancing::RoundRobin | LoadBalancing::LeastInFlight => {
+                let (lb, tx) =
+                    start_balancer::<RoundRobin>(backends, health_check_interval).await?;
+                (Balancer::RoundRobin(lb), tx)
+            }
+        };
 
         if addr_map.load().is_empty() {
             warn!("No backends resolved for endpoints, skipping LB creation");
             return None;
         }
 
-        let lb = Arc::new(lb);
-
-        // Delegate periodic discovery + health checks to Pingora's
-        // BackgroundService implementation, which respects
-        // update_frequency and health_check_frequency.
-        // Dropping _shutdown_tx sets the watch to `true`, signaling stop.
-        let (shutdown_tx, shutdown_rx) = watch::channel(false);
-        let lb_bg = lb.clone();
-        tokio::spawn(async move {
-            use pingora_core::services::background::BackgroundService;
-            lb_bg.start(shutdown_rx).await;
-        });
-
         Some(LbEntry {
-            lb,
+            balancer,
+            strategy,
+            outlier_detection: server
+                .health_check
+                .as_ref()
+                .and_then(|hc| hc.outlier_detection.clone()),
             addr_map,
             _shutdown_tx: shutdown_tx,
         })
     }
+
+    fn select_from(
+        &self,
+        upstream_id: Uuid,
+        entry: &LbEntry,
+        key: &[u8],
+    ) -> Option<SelectedEndpoint> {
+        let map = entry.addr_map.load();
+        let backends = entry.balancer.backends();
+
+        // Passive ejection applies only while it leaves enough of the
+        // upstream in rotation; past the cap every endpoint is eligible.
+        let ejected: HashSet<String> = match &entry.outlier_detection {
+            Some(od) => {
+                let all = backends.get_backend();
+                let ejected: HashSet<String> = all
+                    .iter()
+                    .filter(|b| {
+                        map.get(&b.addr.to_string())
+                            .is_some_and(|ep| self.outliers.is_ejected(upstream_id, ep))
+                    })
+                    .map(|b| b.addr.to_string())
+                    .collect();
+                if ejection_allowed(ejected.len(), all.len(), od) {
+                    ejected
+                } else {
+                    HashSet::new()
+                }
+            }
+            None => HashSet::new(),
+        };
+
+        let backend = match entry.strategy {
+            LoadBalancing::LeastInFlight => {
+                let all = backends.get_backend();
+                let candidates: Vec<(&Backend, &Endpoint)> = all
+                    .iter()
+                    .filter(|b| backends.ready(b) && !ejected.contains(&b.addr.to_string()))
+                    .filter_map(|b| map.get(&b.addr.to_string()).map(|ep| (b, ep)))
+                    .collect();
+                let loads: Vec<(usize, u32)> = candidates
+                    .iter()
+                    .map(|(_, ep)| (self.in_flight.count(upstream_id, ep), ep.weight))
+                    .collect();
+                let start = self.cursor.fetch_add(1, Ordering::Relaxed);
+                let i = pick_least_loaded(&loads, start)?;
+                candidates[i].0.clone()
+            }
+            LoadBalancing::RoundRobin | LoadBalancing::ConsistentHash { .. } => {
+                entry.balancer.select_with(key, |b, healthy| {
+                    healthy && !ejected.contains(&b.addr.to_string())
+                })?
+            }
+        };
+
+        let endpoint = map.get(&backend.addr.to_string())?.clone();
+        let in_flight = Some(self.in_flight.acquire(upstream_id, &endpoint));
+        Some(SelectedEndpoint {
+            endpoint,
+            resolved_addr: backend.addr.as_inet().copied(),
+            in_flight,
+        })
+    }
+}
+
+/// Run the initial discovery for a new balancer and hand it to Pingora's
+/// `BackgroundService`, which respects `update_frequency` and
+/// `health_check_frequency`. Dropping the returned sender stops the task.
+async fn start_balancer<S>(
+    backends: Backends,
+    health_check_interval_secs: u64,
+) -> Option<(Arc<LoadBalancer<S>>, watch::Sender<bool>)>
+where
+    S: BackendSelection + Send + Sync + 'static,
+    S::Iter: BackendIter,
+{
+    let mut lb = LoadBalancer::<S>::from_backends(backends);
+    lb.health_check_frequency = Some(Duration::from_secs(health_check_interval_secs));
+    lb.update_frequency = Some(Duration::from_secs(30));
+
+    // update() calls discover() which resolves DNS and populates both
+    // the backend selector and the addr_map in a single pass.
+    lb.update().await.ok()?;
+
+    let lb = Arc::new(lb);
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let lb_bg = lb.clone();
+    tokio::spawn(async move {
+        use pingora_core::services::background::BackgroundService;
+        lb_bg.start(shutdown_rx).await;
+    });
+    Some((lb, shutdown_tx))
 }
 
 #[async_trait]
 impl EndpointSelector for PingoraEndpointSelector {
-    async fn select(&self, upstream_id: Uuid, endpoints: &[Endpoint]) -> Option<SelectedEndpoint> {
+    async fn select(
+        &self,
+        upstream_id: Uuid,
+        server: &Server,
+        key: &[u8],
+    ) -> Option<SelectedEndpoint> {
         // Fast path: LB already cached.
         if let Some(entry) = self.cache.get(&upstream_id) {
-            let backend = entry.lb.select(b"", 256)?;
-            let resolved_addr = backend.addr.as_inet();
-            let addr_key = backend.addr.to_string();
-            let map = entry.addr_map.load();
-            let endpoint = map.get(&addr_key)?.clone();
-            return Some(SelectedEndpoint {
-                endpoint,
-                resolved_addr: resolved_addr.copied(),
-            });
+            return self.select_from(upstream_id, &entry, key);
         }
 
         // Slow path: build a new LB entry then atomically insert-if-absent.
         // Concurrent builders may race here; or_insert ensures only one wins
         // and losers are dropped (stopping their background task via _shutdown_tx).
-        let entry = self.build_entry(endpoints).await?;
+        let entry = self.build_entry(server).await?;
         let entry_ref = self.cache.entry(upstream_id).or_insert(entry);
-        let backend = entry_ref.lb.select(b"", 256)?;
-        let resolved_addr = backend.addr.as_inet();
-        let addr_key = backend.addr.to_string();
-        let map = entry_ref.addr_map.load();
-        let endpoint = map.get(&addr_key)?.clone();
-        Some(SelectedEndpoint {
-            endpoint,
-            resolved_addr: resolved_addr.copied(),
-        })
+        self.select_from(upstream_id, &entry_ref, key)
+    }
+
+    fn report_outcome(&self, upstream_id: Uuid, endpoint: &Endpoint, success: bool) {
+        let Some(config) = self
+            .cache
+            .get(&upstream_id)
+            .and_then(|entry| entry.outlier_detection.clone())
+        else {
+            return;
+        };
+        self.outliers
+            .record(upstream_id, endpoint, success, &config);
+    }
+
+    fn health(&self, upstream_id: Uuid) -> Vec<EndpointHealth> {
+        let Some(entry) = self.cache.get(&upstream_id) else {
+            return Vec::new();
+        };
+        let map = entry.addr_map.load();
+        let backends = entry.balancer.backends();
+        backends
+            .get_backend()
+            .iter()
+            .filter_map(|b| {
+                let endpoint = map.get(&b.addr.to_string())?;
+                let status = if entry.outlier_detection.is_some()
+                    && self.outliers.is_ejected(upstream_id, endpoint)
+                {
+                    EndpointHealthStatus::Ejected
+                } else if backends.ready(b) {
+                    EndpointHealthStatus::Healthy
+                } else {
+                    EndpointHealthStatus::Unhealthy
+                };
+                Some(EndpointHealth {
+                    endpoint: endpoint.clone(),
+                    address: b.addr.to_string(),
+                    status,
+                    in_flight: self.in_flight.count(upstream_id, endpoint),
+                })
+            })
+            .collect()
     }
 
     fn invalidate(&self, upstream_id: Uuid) {
         // Removing the entry drops LbEntry, which drops _shutdown_tx,
         // which signals the background update task to stop.
         self.cache.remove(&upstream_id);
+        self.outliers.remove_upstream(upstream_id);
+        self.in_flight.remove_upstream(upstream_id);
     }
 }
 
@@ -423,6 +723,12 @@ pub struct ProxyCtx {
     /// Pre-resolved socket address from the load balancer's DNS cache.
     /// When set, `upstream_peer` skips DNS and connects directly.
     resolved_addr: Option<std::net::SocketAddr>,
+    /// Route-level connect timeout overriding the proxy default.
+    connect_timeout: Option<Duration>,
+    /// Route-level read timeout overriding the proxy default.
+    read_timeout: Option<Duration>,
+    /// gRPC call: upstream trailers are forwarded as a trailer frame.
+    grpc: bool,
 }
 
 impl ProxyCtx {
@@ -458,9 +764,21 @@ impl ProxyCtx {
         if let Some(v) = headers.get(H_RESOLVED_ADDR).and_then(|v| v.to_str().ok()) {
             self.resolved_addr = v.parse().ok();
         }
+        self.connect_timeout = parse_timeout_ms(headers, H_CONNECT_TIMEOUT_MS);
+        self.read_timeout = parse_timeout_ms(headers, H_READ_TIMEOUT_MS);
+        self.grpc = super::headers::is_grpc_content_type(headers);
     }
 }
 
+fn parse_timeout_ms(headers: &http::HeaderMap, name: &str) -> Option<Duration> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .filter(|ms| *ms > 0)
+        .map(Duration::from_millis)
+}
+
 impl Default for ProxyCtx {
     fn default() -> Self {
         Self {
@@ -468,10 +786,14 @@ impl Default for ProxyCtx {
                 scheme: Scheme::Https,
                 host: String::new(),
                 port: 443,
+                weight: 1,
             },
             instance_uri: String::new(),
             upstream_id: None,
             resolved_addr: None,
+            connect_timeout: None,
+            read_timeout: None,
+            grpc: false,
         }
     }
 }
@@ -524,7 +846,10 @@ impl ProxyHttp for PingoraProxy {
         ctx: &mut Self::CTX,
     ) -> pingora_core::Result<Box<HttpPeer>> {
         let ep = &ctx.endpoint;
-        let tls = matches!(ep.scheme, Scheme::Https | Scheme::Wss | Scheme::Wt);
+        let tls = matches!(
+            ep.scheme,
+            Scheme::Https | Scheme::Wss | Scheme::Wt | Scheme::Grpc
+        );
 
         let addr = match ctx.resolved_addr {
             Some(a) => a,
@@ -555,8 +880,8 @@ impl ProxyHttp for PingoraProxy {
         // Pass SocketAddr directly — no DNS inside HttpPeer::new.
         let mut peer = HttpPeer::new(addr, tls, ep.host.clone());
 
-        peer.options.connection_timeout = Some(self.connect_timeout);
-        peer.options.read_timeout = Some(self.read_timeout);
+        peer.options.connection_timeout = Some(ctx.connect_timeout.unwrap_or(self.connect_timeout));
+        peer.options.read_timeout = Some(ctx.read_timeout.unwrap_or(self.read_timeout));
         peer.options.idle_timeout = Some(Duration::from_secs(90));
 
         // ALPN selection: consult protocol cache for HTTPS/WT, H1 for WSS/cleartext.
@@ -654,9 +979,26 @@ impl ProxyHttp for PingoraProxy {
         Ok(())
     }
 
-    // No fail_to_connect override: OAGW does not retry on connection failure.
-    // Per DESIGN.md §311 and scenario 12.6, upstream sees exactly one request
-    // attempt. Connection-establishment retries would violate this invariant.
+    /// Carry gRPC trailers across the HTTP/1.1 bridge, which cannot send
+    /// trailers, by appending them to the body as a trailer frame that the
+    /// data plane splits back out (see `super::grpc`).
+    async fn response_trailer_filter(
+        &self,
+        _session: &mut Session,
+        upstream_trailers: &mut http::HeaderMap,
+        ctx: &mut Self::CTX,
+    ) -> pingora_core::Result<Option<Bytes>> {
+        if !ctx.grpc {
+            return Ok(None);
+        }
+        Ok(Some(super::grpc::encode_trailers(upstream_trailers)))
+    }
+
+    // No fail_to_connect override: Pingora makes one attempt per proxied
+    // request. Retries only happen when the route opts in to a `retry` policy,
+    // and then in the service layer, which re-issues the buffered request as a
+    // new attempt under the route's budget and deadline (see
+    // cpt-cf-oagw-principle-no-retry).
 
     /// Reconnect on stale pooled connection errors for idempotent methods.
     ///
@@ -717,8 +1059,9 @@ impl ProxyHttp for PingoraProxy {
             }
             pingora_core::ErrorType::H2Error | pingora_core::ErrorType::H2Downgrade => {
                 // Evict cached protocol so the next request re-negotiates
-                // via ALPN (spec inst-proto-7a). Current request is not
-                // retried per cpt-cf-oagw-principle-no-retry.
+                // via ALPN (spec inst-proto-7a). The current request is not
+                // retried here; only a route's opt-in retry policy re-issues
+                // it (cpt-cf-oagw-principle-no-retry).
                 self.protocol_cache.evict(&ctx.endpoint);
                 DomainError::ProtocolError {
                     detail: "upstream HTTP/2 error".into(),
@@ -845,6 +1188,15 @@ mod tests {
             scheme,
             host: host.to_string(),
             port,
+            weight: 1,
+        }
+    }
+
+    fn server(endpoints: &[Endpoint]) -> Server {
+        Server {
+            endpoints: endpoints.to_vec(),
+            load_balancing: None,
+            health_check: None,
         }
     }
 
@@ -864,7 +1216,7 @@ mod tests {
         let mut port_a = 0u32;
         let mut port_b = 0u32;
         for _ in 0..4 {
-            let selected = selector.select(id, &endpoints).await.unwrap();
+            let selected = selector.select(id, &server(&endpoints), b"").await.unwrap();
             match selected.endpoint.port {
                 10001 => port_a += 1,
                 10002 => port_b += 1,
@@ -881,13 +1233,13 @@ mod tests {
         let id = Uuid::new_v4();
 
         let v1 = vec![ep("127.0.0.1", 20001, Scheme::Https)];
-        let selected = selector.select(id, &v1).await.unwrap();
+        let selected = selector.select(id, &server(&v1), b"").await.unwrap();
         assert_eq!(selected.endpoint.port, 20001);
 
         selector.invalidate(id);
 
         let v2 = vec![ep("127.0.0.1", 20002, Scheme::Https)];
-        let selected = selector.select(id, &v2).await.unwrap();
+        let selected = selector.select(id, &server(&v2), b"").await.unwrap();
         assert_eq!(selected.endpoint.port, 20002);
     }
 
@@ -897,7 +1249,7 @@ mod tests {
         let id = Uuid::new_v4();
         let endpoints = vec![ep("127.0.0.1", 30001, Scheme::Http)];
 
-        let selected = selector.select(id, &endpoints).await.unwrap();
+        let selected = selector.select(id, &server(&endpoints), b"").await.unwrap();
         assert_eq!(selected.endpoint.host, "127.0.0.1");
         assert_eq!(selected.endpoint.port, 30001);
         assert_eq!(selected.endpoint.scheme, Scheme::Http);
@@ -919,7 +1271,7 @@ mod tests {
         let mut found_1 = false;
         let mut found_2 = false;
         for _ in 0..20 {
-            let selected = selector.select(id, &endpoints).await.unwrap();
+            let selected = selector.select(id, &server(&endpoints), b"").await.unwrap();
             assert_eq!(
                 selected.endpoint.scheme,
                 Scheme::Https,
@@ -952,7 +1304,7 @@ mod tests {
         // Use "localhost" — a hostname that resolves to 127.0.0.1.
         let endpoints = vec![ep("localhost", 50001, Scheme::Https)];
 
-        let selected = selector.select(id, &endpoints).await;
+        let selected = selector.select(id, &server(&endpoints), b"").await;
         assert!(
             selected.is_some(),
             "select should succeed for hostname-based endpoint"
@@ -1088,7 +1440,7 @@ mod tests {
         let selector = PingoraEndpointSelector::new();
         let id = Uuid::new_v4();
 
-        let result = selector.select(id, &[]).await;
+        let result = selector.select(id, &server(&[]), b"").await;
         assert!(result.is_none(), "empty endpoints should return None");
         assert!(
             !selector.cache.contains_key(&id),
@@ -1104,7 +1456,7 @@ mod tests {
         let id = Uuid::new_v4();
 
         let endpoints = vec![ep("this.host.does.not.exist.invalid", 443, Scheme::Https)];
-        let result = selector.select(id, &endpoints).await;
+        let result = selector.select(id, &server(&endpoints), b"").await;
         assert!(
             result.is_none(),
             "unresolvable endpoints should return None"
@@ -1120,7 +1472,7 @@ mod tests {
 
         // Initial endpoints.
         let v1 = vec![ep("127.0.0.1", 60001, Scheme::Https)];
-        let selected = selector.select(id, &v1).await.unwrap();
+        let selected = selector.select(id, &server(&v1), b"").await.unwrap();
         assert_eq!(selected.endpoint.port, 60001);
 
         // Access the addr_map to verify it's populated.
@@ -1133,7 +1485,7 @@ mod tests {
         selector.invalidate(id);
 
         let v2 = vec![ep("127.0.0.1", 60002, Scheme::Https)];
-        let selected = selector.select(id, &v2).await.unwrap();
+        let selected = selector.select(id, &server(&v2), b"").await.unwrap();
         assert_eq!(selected.endpoint.port, 60002);
 
         // New addr_map should only contain the new endpoint.
@@ -1149,6 +1501,113 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn select_weighted_round_robin_follows_weights() {
+        let selector = PingoraEndpointSelector::new();
+        let id = Uuid::new_v4();
+        let mut heavy = ep("127.0.0.1", 61001, Scheme::Https);
+        heavy.weight = 3;
+        let endpoints = vec![heavy, ep("127.0.0.1", 61002, Scheme::Https)];
+
+        let mut heavy_count = 0;
+        for _ in 0..8 {
+            let selected = selector.select(id, &server(&endpoints), b"").await.unwrap();
+            if selected.endpoint.port == 61001 {
+                heavy_count += 1;
+            }
+        }
+        assert_eq!(heavy_count, 6, "weight 3:1 should yield 6 of 8 picks");
+    }
+
+    #[tokio::test]
+    async fn select_least_in_flight_prefers_idle_endpoint() {
+        let selector = PingoraEndpointSelector::new();
+        let id = Uuid::new_v4();
+        let endpoints = vec![
+            ep("127.0.0.1", 62001, Scheme::Https),
+            ep("127.0.0.1", 62002, Scheme::Https),
+        ];
+        let mut srv = server(&endpoints);
+        srv.load_balancing = Some(LoadBalancing::LeastInFlight);
+
+        // Holding the first selection keeps its endpoint busy.
+        let busy = selector.select(id, &srv, b"").await.unwrap();
+        for _ in 0..4 {
+            let selected = selector.select(id, &srv, b"").await.unwrap();
+            assert_ne!(selected.endpoint.port, busy.endpoint.port);
+        }
+        drop(busy);
+
+        let health = selector.health(id);
+        assert_eq!(health.len(), 2);
+        assert!(health.iter().all(|h| h.in_flight == 0));
+    }
+
+    #[tokio::test]
+    async fn select_consistent_hash_is_sticky_per_key() {
+        let selector = PingoraEndpointSelector::new();
+        let id = Uuid::new_v4();
+        let endpoints = vec![
+            ep("127.0.0.1", 63001, Scheme::Https),
+            ep("127.0.0.1", 63002, Scheme::Https),
+            ep("127.0.0.1", 63003, Scheme::Https),
+        ];
+        let mut srv = server(&endpoints);
+        srv.load_balancing = Some(LoadBalancing::ConsistentHash {
+            key: crate::domain::model::HashKey::Tenant,
+        });
+
+        for key in [b"tenant-a".as_slice(), b"tenant-b", b"tenant-c"] {
+            let first = selector.select(id, &srv, key).await.unwrap();
+            for _ in 0..5 {
+                let again = selector.select(id, &srv, key).await.unwrap();
+                assert_eq!(again.endpoint.port, first.endpoint.port);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn report_outcome_ejects_failing_endpoint() {
+        let selector = PingoraEndpointSelector::new();
+        let id = Uuid::new_v4();
+        let endpoints = vec![
+            ep("127.0.0.1", 64001, Scheme::Https),
+            ep("127.0.0.1", 64002, Scheme::Https),
+        ];
+        let mut srv = server(&endpoints);
+        srv.health_check = Some(HealthCheckConfig {
+            outlier_detection: Some(OutlierDetectionConfig {
+                consecutive_failures: 2,
+                ejection_secs: 60,
+                max_ejection_percent: 50,
+            }),
+            ..HealthCheckConfig::default()
+        });
+
+        let _ = selector.select(id, &srv, b"").await.unwrap();
+        selector.report_outcome(id, &endpoints[0], false);
+        selector.report_outcome(id, &endpoints[0], false);
+
+        for _ in 0..4 {
+            let selected = selector.select(id, &srv, b"").await.unwrap();
+            assert_eq!(selected.endpoint.port, 64002);
+        }
+        let health = selector.health(id);
+        let ejected = health
+            .iter()
+            .find(|h| h.endpoint.port == 64001)
+            .expect("ejected endpoint is reported");
+        assert_eq!(ejected.status, EndpointHealthStatus::Ejected);
+
+        // Ejecting the second endpoint too would exceed 50%: both stay eligible.
+        selector.report_outcome(id, &endpoints[1], false);
+        selector.report_outcome(id, &endpoints[1], false);
+        assert!(selector.select(id, &srv, b"").await.is_some());
+
+        selector.invalidate(id);
+        assert!(selector.health(id).is_empty());
+    }
+
     // -- upstream_peer ALPN / TLS tests --
     //
     // These tests mirror the `upstream_peer` logic to verify the peer
@@ -1169,7 +1628,10 @@ mod tests {
             Duration::from_secs(3600),
         );
         let ep = ep(host, port, scheme);
-        let tls = matches!(ep.scheme, Scheme::Https | Scheme::Wss | Scheme::Wt);
+        let tls = matches!(
+            ep.scheme,
+            Scheme::Https | Scheme::Wss | Scheme::Wt | Scheme::Grpc
+        );
         let addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
         let mut peer = HttpPeer::new(addr, tls, host.to_string());
         peer.options.alpn = proxy.select_alpn(&ep);
@@ -1220,6 +1682,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn alpn_grpc_uses_h2() {
+        let peer = build_peer(Scheme::Grpc, "example.com", 443);
+        assert!(peer.is_tls(), "gRPC peer should use TLS");
+        assert_eq!(
+            peer.options.alpn,
+            pingora_core::protocols::tls::ALPN::H2,
+            "gRPC must use H2 (trailers carry the call status)"
+        );
+    }
+
     #[test]
     fn peer_timeouts_propagate() {
         let proxy = PingoraProxy::new(
@@ -1280,6 +1753,34 @@ mod tests {
         assert!(ctx.resolved_addr.is_none());
     }
 
+    #[test]
+    fn populate_from_headers_parses_route_timeouts() {
+        let mut ctx = ProxyCtx::default();
+        let mut headers = http::HeaderMap::new();
+        headers.insert(H_CONNECT_TIMEOUT_MS, "250".parse().unwrap());
+        headers.insert(H_READ_TIMEOUT_MS, "not-a-number".parse().unwrap());
+
+        ctx.populate_from_headers(&headers);
+
+        assert_eq!(ctx.connect_timeout, Some(Duration::from_millis(250)));
+        assert!(ctx.read_timeout.is_none());
+    }
+
+    #[test]
+    fn populate_from_headers_detects_grpc() {
+        let mut ctx = ProxyCtx::default();
+        let mut headers = http::HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            "application/grpc+proto".parse().unwrap(),
+        );
+
+        ctx.populate_from_headers(&headers);
+
+        assert!(ctx.grpc);
+        assert!(!ProxyCtx::default().grpc);
+    }
+
     #[tokio::test]
     async fn select_populates_resolved_addr() {
         let selector = PingoraEndpointSelector::new();
@@ -1287,7 +1788,7 @@ mod tests {
         // IP-based endpoint — resolved_addr should be populated.
         let endpoints = vec![ep("127.0.0.1", 30001, Scheme::Http)];
 
-        let selected = selector.select(id, &endpoints).await.unwrap();
+        let selected = selector.select(id, &server(&endpoints), b"").await.unwrap();
         assert!(
             selected.resolved_addr.is_some(),
             "resolved_addr should be populated for IP endpoint"
@@ -3,9 +3,13 @@ use crate::domain::model::{Endpoint, Scheme};
 
 /// Build the full upstream URL from endpoint, route path, path suffix, and query params.
 ///
+/// gRPC endpoints are addressed over TLS like WT; the route path is the
+/// `/{service}/{method}` of the call.
+///
 /// # Errors
 ///
-/// Returns `DomainError::Validation` if the endpoint uses an unsupported scheme (e.g. gRPC).
+/// Every current `Scheme` maps to a URL scheme, so this does not fail today;
+/// the `Result` keeps room for schemes the HTTP bridge cannot carry.
 pub fn build_upstream_url(
     endpoint: &Endpoint,
     route_path: &str,
@@ -16,15 +20,7 @@ pub fn build_upstream_url(
         Scheme::Http => "http",
         Scheme::Https => "https",
         Scheme::Wss => "wss",
-        Scheme::Wt => "https",
-        Scheme::Grpc => {
-            return Err(DomainError::Validation {
-                field: "endpoint.scheme",
-                reason: "UNSUPPORTED_SCHEME",
-                detail: "gRPC scheme is not supported for HTTP proxy".into(),
-                instance: String::new(),
-            });
-        }
+        Scheme::Wt | Scheme::Grpc => "https",
     };
 
     let host_port = if is_default_port(scheme, endpoint.port) {
@@ -70,6 +66,7 @@ mod tests {
             scheme: Scheme::Https,
             host: host.into(),
             port,
+            weight: 1,
         }
     }
 
@@ -135,6 +132,7 @@ mod tests {
             scheme: Scheme::Http,
             host: "127.0.0.1".into(),
             port: 3000,
+            weight: 1,
         };
         let url = build_upstream_url(&ep, "/v1/test", "", &[]).unwrap();
         assert_eq!(url, "http://127.0.0.1:3000/v1/test");
@@ -146,6 +144,7 @@ mod tests {
             scheme: Scheme::Http,
             host: "example.com".into(),
             port: 80,
+            weight: 1,
         };
         let url = build_upstream_url(&ep, "/api", "", &[]).unwrap();
         assert_eq!(url, "http://example.com/api");
@@ -164,13 +163,17 @@ mod tests {
     }
 
     #[test]
-    fn grpc_scheme_returns_error() {
+    fn grpc_scheme_builds_https_url() {
         let ep = Endpoint {
             scheme: Scheme::Grpc,
             host: "grpc.example.com".into(),
             port: 443,
+            weight: 1,
         };
-        let err = build_upstream_url(&ep, "/service", "", &[]).unwrap_err();
-        assert!(matches!(err, DomainError::Validation { .. }));
+        let url = build_upstream_url(&ep, "/example.v1.UserService/GetUser", "", &[]).unwrap();
+        assert_eq!(
+            url,
+            "https://grpc.example.com/example.v1.UserService/GetUser"
+        );
     }
 }
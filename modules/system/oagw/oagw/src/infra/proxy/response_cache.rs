@@ -0,0 +1,517 @@
+//! In-memory store for the route response cache.
+//!
+//! Entries live in a TinyUFO cache keyed by
+//! [`build_cache_key`](crate::domain::cache::build_cache_key) and weighed by
+//! their body and header size against a byte budget. Purging a
+//! route bumps its generation, which orphans every key built before the
+//! purge; orphaned entries age out of the store on their own.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use bytes::{Bytes, BytesMut};
+use dashmap::DashMap;
+use futures_util::StreamExt;
+use futures_util::stream::unfold;
+use http::StatusCode;
+use http::header::{self, HeaderMap, HeaderName, HeaderValue};
+use oagw_sdk::body::BodyStream;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::Counter;
+use tinyufo::TinyUfo;
+use uuid::Uuid;
+
+use crate::domain::cache::{self, CacheControl, Freshness};
+
+/// How long an entry with a validator is kept after it turns stale, so it
+/// can still be revalidated instead of fetched again.
+const STALE_RETENTION: Duration = Duration::from_secs(600);
+
+/// Granularity of entry weights; the store counts weight in `u16` units.
+const WEIGHT_UNIT: usize = 1024;
+
+/// RFC 9211 response header reporting how the gateway cache handled a request.
+pub(crate) const CACHE_STATUS: &str = "cache-status";
+
+/// How the cache handled a request, reported in metrics and `Cache-Status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CacheOutcome {
+    /// Served from a fresh entry without contacting the upstream.
+    Hit,
+    /// Forwarded to the upstream; the response may have been stored.
+    Miss,
+    /// A stale entry was confirmed by a 304 and served.
+    Revalidated,
+    /// The route caches, but this request or its credentials do not qualify.
+    Bypass,
+}
+
+impl CacheOutcome {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Hit => "hit",
+            Self::Miss => "miss",
+            Self::Revalidated => "revalidated",
+            Self::Bypass => "bypass",
+        }
+    }
+
+    /// `Cache-Status` member for this outcome.
+    pub(crate) fn cache_status(self) -> HeaderValue {
+        HeaderValue::from_static(match self {
+            Self::Hit => "OAGW; hit",
+            Self::Miss => "OAGW; fwd=miss",
+            Self::Revalidated => "OAGW; fwd=stale; fwd-status=304",
+            Self::Bypass => "OAGW; fwd=bypass",
+        })
+    }
+}
+
+/// A stored upstream response.
+#[derive(Debug, Clone)]
+pub(crate) struct CachedResponse {
+    /// Full cache key. The store hashes keys, so a hit is checked against it.
+    key: String,
+    pub(crate) status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    /// Values of the request headers named by `Vary`, captured when stored.
+    vary: Vec<(HeaderName, Option<HeaderValue>)>,
+    stored_at: Instant,
+    freshness: Freshness,
+}
+
+impl CachedResponse {
+    /// Capture a response for storage. `vary_headers` are the request headers
+    /// the upstream saw.
+    pub(crate) fn new(
+        key: String,
+        status: StatusCode,
+        headers: HeaderMap,
+        body: Bytes,
+        vary_headers: &HeaderMap,
+        freshness: Freshness,
+    ) -> Self {
+        let vary = cache::vary_names(&headers)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|name| {
+                let value = vary_headers.get(&name).cloned();
+                (name, value)
+            })
+            .collect();
+        Self {
+            key,
+            status,
+            headers,
+            body,
+            vary,
+            stored_at: Instant::now(),
+            freshness,
+        }
+    }
+
+    fn age(&self, now: Instant) -> Duration {
+        self.freshness.initial_age + now.saturating_duration_since(self.stored_at)
+    }
+
+    /// Bytes the entry holds: body, headers, captured `Vary` values and key.
+    fn size(&self) -> usize {
+        let headers: usize = self
+            .headers
+            .iter()
+            .map(|(name, value)| name.as_str().len() + value.len())
+            .sum();
+        let vary: usize = self
+            .vary
+            .iter()
+            .map(|(name, value)| name.as_str().len() + value.as_ref().map_or(0, HeaderValue::len))
+            .sum();
+        self.key.len() + self.body.len() + headers + vary
+    }
+
+    fn matches_vary(&self, req_headers: &HeaderMap) -> bool {
+        self.vary
+            .iter()
+            .all(|(name, value)| req_headers.get(name) == value.as_ref())
+    }
+
+    /// Whether the entry can answer the request without revalidation.
+    pub(crate) fn is_fresh(&self, req_cc: &CacheControl, now: Instant) -> bool {
+        cache::is_fresh(&self.freshness, self.age(now), req_cc)
+    }
+
+    /// Whether the entry can be revalidated with a conditional request.
+    pub(crate) fn is_revalidatable(&self) -> bool {
+        cache::has_validator(&self.headers)
+    }
+
+    /// Add `If-None-Match` / `If-Modified-Since` built from the entry's
+    /// validators to an outbound request.
+    pub(crate) fn add_conditional_headers(&self, outbound: &mut HeaderMap) {
+        if let Some(etag) = self.headers.get(header::ETAG) {
+            outbound.insert(header::IF_NONE_MATCH, etag.clone());
+        }
+        if let Some(last_modified) = self.headers.get(header::LAST_MODIFIED) {
+            outbound.insert(header::IF_MODIFIED_SINCE, last_modified.clone());
+        }
+    }
+
+    /// The stored body as a response stream.
+    pub(crate) fn body_stream(&self) -> BodyStream {
+        let body = self.body.clone();
+        Box::pin(futures_util::stream::once(async move { Ok(body) }))
+    }
+
+    /// Headers to serve the entry with, carrying its current `Age`.
+    pub(crate) fn response_headers(&self, now: Instant) -> HeaderMap {
+        let mut headers = self.headers.clone();
+        headers.insert(header::AGE, HeaderValue::from(self.age(now).as_secs()));
+        headers
+    }
+
+    /// The entry updated by a 304: header fields the 304 carries replace the
+    /// stored ones (RFC 9111 §3.2) and freshness restarts from now. The flag
+    /// is `false` when the updated response may no longer be stored; it is
+    /// still served for this request.
+    pub(crate) fn revalidated(
+        &self,
+        not_modified: &HeaderMap,
+        default_ttl: Duration,
+    ) -> (Self, bool) {
+        let mut updated = self.clone();
+        for name in not_modified.keys() {
+            if *name == header::CONTENT_LENGTH || *name == header::TRANSFER_ENCODING {
+                continue;
+            }
+            updated.headers.remove(name);
+            for value in not_modified.get_all(name) {
+                updated.headers.append(name.clone(), value.clone());
+            }
+        }
+        updated.stored_at = Instant::now();
+        match cache::response_freshness(updated.status.as_u16(), &updated.headers, default_ttl) {
+            Some(freshness) => {
+                updated.freshness = freshness;
+                (updated, true)
+            }
+            None => {
+                updated.freshness.initial_age = Duration::ZERO;
+                (updated, false)
+            }
+        }
+    }
+
+    /// How long the store should keep the entry.
+    fn retention(&self) -> Duration {
+        let fresh_for = self
+            .freshness
+            .lifetime
+            .saturating_sub(self.freshness.initial_age);
+        if self.is_revalidatable() {
+            fresh_for + STALE_RETENTION
+        } else {
+            fresh_for
+        }
+    }
+}
+
+/// A stored entry with the time the store drops it.
+#[derive(Clone)]
+struct Slot {
+    entry: Arc<CachedResponse>,
+    expires_at: Instant,
+}
+
+/// Per-node response cache shared by all routes.
+pub(crate) struct ResponseCache {
+    entries: TinyUfo<String, Slot>,
+    /// Weight budget in [`WEIGHT_UNIT`]s.
+    weight_limit: usize,
+    /// Current generation of each purged route; absent means 0.
+    generations: DashMap<Uuid, u64>,
+    next_generation: AtomicU64,
+    lookups: Counter<u64>,
+}
+
+impl ResponseCache {
+    /// Create a store holding at most about `max_bytes` of responses.
+    pub(crate) fn new(max_bytes: usize) -> Self {
+        let scope = opentelemetry::InstrumentationScope::builder("oagw").build();
+        let meter = opentelemetry::global::meter_with_scope(scope);
+        let lookups = meter
+            .u64_counter("oagw_cache_lookups")
+            .with_description("Requests on caching routes by cache outcome")
+            .build();
+        let weight_limit = max_bytes.div_ceil(WEIGHT_UNIT).max(1);
+        Self {
+            entries: TinyUfo::new(weight_limit, weight_limit),
+            weight_limit,
+            generations: DashMap::new(),
+            next_generation: AtomicU64::new(1),
+            lookups,
+        }
+    }
+
+    /// Generation to build the route's cache keys with.
+    pub(crate) fn generation(&self, route_id: Uuid) -> u64 {
+        self.generations.get(&route_id).map_or(0, |g| *g)
+    }
+
+    /// Orphan every entry stored for the route.
+    pub(crate) fn purge_route(&self, route_id: Uuid) {
+        let generation = self.next_generation.fetch_add(1, Ordering::Relaxed);
+        self.generations.insert(route_id, generation);
+    }
+
+    /// Entry stored under `key` whose `Vary` headers match the request.
+    pub(crate) fn lookup(&self, key: &str, req_headers: &HeaderMap) -> Option<Arc<CachedResponse>> {
+        let key = key.to_owned();
+        let slot = self.entries.get(&key)?;
+        if slot.expires_at <= Instant::now() {
+            self.entries.remove(&key);
+            return None;
+        }
+        Some(slot.entry).filter(|e| e.key == key && e.matches_vary(req_headers))
+    }
+
+    /// Store `entry`, evicting others to stay within the byte budget.
+    /// Entries heavier than the whole budget are not stored.
+    pub(crate) fn store(&self, entry: CachedResponse) {
+        let retention = entry.retention();
+        if retention.is_zero() {
+            return;
+        }
+        let weight = entry.size().div_ceil(WEIGHT_UNIT).max(1);
+        let Ok(weight) = u16::try_from(weight) else {
+            return;
+        };
+        if usize::from(weight) > self.weight_limit {
+            return;
+        }
+        let key = entry.key.clone();
+        let slot = Slot {
+            entry: Arc::new(entry),
+            expires_at: Instant::now() + retention,
+        };
+        self.entries.put(key, slot, weight);
+    }
+
+    pub(crate) fn record(&self, outcome: CacheOutcome) {
+        self.lookups
+            .add(1, &[KeyValue::new("outcome", outcome.label())]);
+    }
+}
+
+/// Pass `inner` through unchanged while collecting it; once it ends cleanly
+/// within `max_bytes`, hand the full body to `on_complete`. Bodies that
+/// overflow or fail mid-stream are not handed over.
+pub(crate) fn collect_for_cache<F>(
+    inner: BodyStream,
+    max_bytes: usize,
+    on_complete: F,
+) -> BodyStream
+where
+    F: FnOnce(Bytes) + Send + 'static,
+{
+    let state = (inner, Some(BytesMut::new()), Some(on_complete));
+    let stream = unfold(
+        state,
+        move |(mut inner, mut buf, mut on_complete)| async move {
+            match inner.next().await {
+                Some(Ok(chunk)) => {
+                    if buf
+                        .as_ref()
+                        .is_some_and(|b| b.len() + chunk.len() > max_bytes)
+                    {
+                        buf = None;
+                    }
+                    if let Some(b) = buf.as_mut() {
+                        b.extend_from_slice(&chunk);
+                    }
+                    Some((Ok(chunk), (inner, buf, on_complete)))
+                }
+                Some(Err(e)) => Some((Err(e), (inner, None, on_complete))),
+                None => {
+                    if let (Some(b), Some(f)) = (buf, on_complete.take()) {
+                        f(b.freeze());
+                    }
+                    None
+                }
+            }
+        },
+    );
+    Box::pin(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::stream;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.append(*name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    fn entry(key: &str, resp: HeaderMap, req: &HeaderMap) -> CachedResponse {
+        let freshness =
+            cache::response_freshness(200, &resp, Duration::from_secs(60)).expect("storable");
+        CachedResponse::new(
+            key.into(),
+            StatusCode::OK,
+            resp,
+            Bytes::from_static(b"body"),
+            req,
+            freshness,
+        )
+    }
+
+    #[test]
+    fn lookup_returns_stored_entry_for_same_key() {
+        let cache = ResponseCache::new(64 * 1024);
+        cache.store(entry("k1", HeaderMap::new(), &HeaderMap::new()));
+
+        let hit = cache.lookup("k1", &HeaderMap::new()).expect("hit");
+        assert_eq!(hit.body, Bytes::from_static(b"body"));
+        assert!(hit.is_fresh(&CacheControl::default(), Instant::now()));
+        assert!(cache.lookup("k2", &HeaderMap::new()).is_none());
+    }
+
+    #[test]
+    fn lookup_matches_vary_headers() {
+        let cache = ResponseCache::new(64 * 1024);
+        let en = headers(&[("accept-language", "en")]);
+        cache.store(entry("k", headers(&[("vary", "accept-language")]), &en));
+
+        assert!(cache.lookup("k", &en).is_some());
+        assert!(
+            cache
+                .lookup("k", &headers(&[("accept-language", "de")]))
+                .is_none()
+        );
+        assert!(cache.lookup("k", &HeaderMap::new()).is_none());
+    }
+
+    #[test]
+    fn store_evicts_to_stay_within_byte_budget() {
+        let cache = ResponseCache::new(8 * WEIGHT_UNIT);
+        let big = |key: &str| {
+            let freshness =
+                cache::response_freshness(200, &HeaderMap::new(), Duration::from_secs(60))
+                    .expect("storable");
+            CachedResponse::new(
+                key.into(),
+                StatusCode::OK,
+                HeaderMap::new(),
+                Bytes::from(vec![b'x'; 3 * WEIGHT_UNIT]),
+                &HeaderMap::new(),
+                freshness,
+            )
+        };
+        for i in 0..4 {
+            cache.store(big(&format!("k{i}")));
+        }
+        let stored = (0..4)
+            .filter(|i| cache.lookup(&format!("k{i}"), &HeaderMap::new()).is_some())
+            .count();
+        assert!(stored < 4, "four 3 KiB bodies exceed an 8 KiB budget");
+
+        let oversized = ResponseCache::new(2 * WEIGHT_UNIT);
+        oversized.store(big("k"));
+        assert!(oversized.lookup("k", &HeaderMap::new()).is_none());
+    }
+
+    #[test]
+    fn purge_route_changes_generation() {
+        let cache = ResponseCache::new(64 * 1024);
+        let route = Uuid::new_v4();
+        assert_eq!(cache.generation(route), 0);
+        cache.purge_route(route);
+        let first = cache.generation(route);
+        cache.purge_route(route);
+        assert!(cache.generation(route) > first);
+        assert_eq!(cache.generation(Uuid::new_v4()), 0);
+    }
+
+    #[test]
+    fn conditional_headers_come_from_validators() {
+        let e = entry(
+            "k",
+            headers(&[
+                ("etag", "\"v1\""),
+                ("last-modified", "Tue, 01 Sep 2026 00:00:00 GMT"),
+            ]),
+            &HeaderMap::new(),
+        );
+        let mut outbound = HeaderMap::new();
+        e.add_conditional_headers(&mut outbound);
+        assert_eq!(outbound.get(header::IF_NONE_MATCH).unwrap(), "\"v1\"");
+        assert_eq!(
+            outbound.get(header::IF_MODIFIED_SINCE).unwrap(),
+            "Tue, 01 Sep 2026 00:00:00 GMT"
+        );
+    }
+
+    #[test]
+    fn revalidation_merges_headers_and_restarts_freshness() {
+        let e = entry(
+            "k",
+            headers(&[("etag", "\"v1\""), ("cache-control", "max-age=0")]),
+            &HeaderMap::new(),
+        );
+        assert!(!e.is_fresh(&CacheControl::default(), Instant::now()));
+
+        let (updated, storable) = e.revalidated(
+            &headers(&[("cache-control", "max-age=120"), ("content-length", "0")]),
+            Duration::ZERO,
+        );
+        assert!(storable);
+        assert!(updated.is_fresh(&CacheControl::default(), Instant::now()));
+        assert_eq!(updated.headers.get(header::ETAG).unwrap(), "\"v1\"");
+        assert!(updated.headers.get(header::CONTENT_LENGTH).is_none());
+
+        let (_, storable) =
+            e.revalidated(&headers(&[("cache-control", "no-store")]), Duration::ZERO);
+        assert!(!storable);
+    }
+
+    #[test]
+    fn response_headers_carry_age() {
+        let e = entry("k", headers(&[("age", "7")]), &HeaderMap::new());
+        let h = e.response_headers(Instant::now());
+        assert_eq!(h.get(header::AGE).unwrap(), "7");
+    }
+
+    fn chunks(parts: &[&'static [u8]]) -> BodyStream {
+        let items: Vec<Result<Bytes, oagw_sdk::body::BoxError>> =
+            parts.iter().map(|p| Ok(Bytes::from_static(p))).collect();
+        Box::pin(stream::iter(items))
+    }
+
+    #[tokio::test]
+    async fn collect_for_cache_hands_over_complete_body() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let body = collect_for_cache(chunks(&[b"ab", b"cd"]), 8, move |b| {
+            tx.send(b).unwrap();
+        });
+        let out: Vec<u8> = body.map(|c| c.unwrap().to_vec()).concat().await;
+        assert_eq!(out, b"abcd");
+        assert_eq!(rx.try_recv().unwrap(), Bytes::from_static(b"abcd"));
+    }
+
+    #[tokio::test]
+    async fn collect_for_cache_skips_oversized_body() {
+        let (tx, rx) = std::sync::mpsc::channel::<Bytes>();
+        let body = collect_for_cache(chunks(&[b"abc", b"def"]), 4, move |b| {
+            tx.send(b).unwrap();
+        });
+        let out: Vec<u8> = body.map(|c| c.unwrap().to_vec()).concat().await;
+        assert_eq!(out, b"abcdef");
+        assert!(rx.try_recv().is_err());
+    }
+}
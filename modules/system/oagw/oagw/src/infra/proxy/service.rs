@@ -1,5 +1,5 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use authz_resolver_sdk::PolicyEnforcer;
@@ -7,8 +7,10 @@ use authz_resolver_sdk::pep::AccessRequest;
 use bytes::Bytes;
 use credstore_sdk::CredStoreClientV1;
 use futures_util::StreamExt;
+use futures_util::stream::unfold;
 use http::{HeaderMap, HeaderValue};
 use modkit_security::SecurityContext;
+use oagw_sdk::api::ErrorSource;
 use oagw_sdk::body::{Body, BodyStream};
 use pingora_core::apps::HttpServerApp;
 use pingora_proxy::HttpProxy;
@@ -18,9 +20,13 @@ use tokio::sync::watch;
 use uuid::Uuid;
 
 use crate::config::TokenCacheConfig;
+use crate::domain::cache::{self, CacheControl, CacheKeyContext};
+use crate::domain::circuit_breaker::{CircuitBreakers, CircuitPermit, build_circuit_breaker_key};
 use crate::domain::error::DomainError;
+use crate::domain::load_balancing::InFlightGuard;
 use crate::domain::model::{
-    PassthroughMode, PathSuffixMode, ResponseHeaderRules, Scheme, Upstream,
+    CacheConfig, CircuitBreakerScope, Endpoint, GrpcMatch, HashKey, LoadBalancing, PassthroughMode,
+    PathSuffixMode, ResponseHeaderRules, Scheme, Server, TimeoutConfig, Upstream,
 };
 use crate::domain::plugin::{
     AuthContext, GuardContext, GuardDecision, TransformErrorContext, TransformRequestContext,
@@ -29,6 +35,7 @@ use crate::domain::plugin::{
 use crate::domain::rate_limit::{
     RateLimitKeyContext, RateLimitOutcome, RateLimitResource, RateLimiter, build_rate_limit_key,
 };
+use crate::domain::retry::{self, RetryBudgets};
 use crate::domain::services::{
     ControlPlaneService, DataPlaneService, EndpointSelector, SelectedEndpoint,
 };
@@ -37,14 +44,19 @@ use crate::infra::proxy::{actions, resources};
 
 use super::headers;
 use super::pingora_proxy::{
-    H_ENDPOINT_HOST, H_ENDPOINT_PORT, H_ENDPOINT_SCHEME, H_INSTANCE_URI, H_RESOLVED_ADDR,
-    H_UPSTREAM_ID, PingoraProxy,
+    H_CONNECT_TIMEOUT_MS, H_ENDPOINT_HOST, H_ENDPOINT_PORT, H_ENDPOINT_SCHEME, H_INSTANCE_URI,
+    H_READ_TIMEOUT_MS, H_RESOLVED_ADDR, H_UPSTREAM_ID, PingoraProxy,
 };
-use super::{request_builder, session_bridge};
+use super::response_cache::{
+    CACHE_STATUS, CacheOutcome, CachedResponse, ResponseCache, collect_for_cache,
+};
+use super::{grpc, request_builder, session_bridge};
 
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 /// Default maximum request body size: 100 MB.
 const MAX_BODY_SIZE: usize = 100 * 1024 * 1024;
+/// Default memory budget of the response cache.
+const RESPONSE_CACHE_MAX_BYTES: usize = 256 * 1024 * 1024;
 
 /// Data Plane service implementation: proxy orchestration and plugin execution.
 pub struct DataPlaneServiceImpl {
@@ -58,6 +70,13 @@ pub struct DataPlaneServiceImpl {
     guard_registry: GuardPluginRegistry,
     transform_registry: TransformPluginRegistry,
     rate_limiter: RateLimiter,
+    /// Per-node circuit breaker state, keyed by upstream (and endpoint).
+    circuit_breakers: CircuitBreakers,
+    /// Per-node retry budgets, keyed by route.
+    retry_budgets: RetryBudgets,
+    /// Per-node HTTP response cache for routes with `cache` enabled.
+    response_cache: Arc<ResponseCache>,
+    /// Default total timeout; a route's `timeouts.total_ms` overrides it.
     request_timeout: Duration,
     /// Enforces authorization policy before proxying each request.
     policy_enforcer: PolicyEnforcer,
@@ -102,6 +121,9 @@ impl DataPlaneServiceImpl {
             guard_registry,
             transform_registry,
             rate_limiter,
+            circuit_breakers: CircuitBreakers::new(),
+            retry_budgets: RetryBudgets::new(),
+            response_cache: Arc::new(ResponseCache::new(RESPONSE_CACHE_MAX_BYTES)),
             request_timeout: REQUEST_TIMEOUT,
             policy_enforcer,
             allow_http_upstream: false,
@@ -162,6 +184,13 @@ impl DataPlaneServiceImpl {
         self
     }
 
+    /// Override the memory budget of the response cache, in bytes.
+    #[must_use]
+    pub fn with_response_cache_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.response_cache = Arc::new(ResponseCache::new(max_bytes));
+        self
+    }
+
     /// Execute the post-response plugin pipeline (guard + transform) and build
     /// the final proxy response.
     async fn finalize_response(
@@ -172,6 +201,13 @@ impl DataPlaneServiceImpl {
         resp_body_stream: BodyStream,
         instance_uri: String,
     ) -> Result<http::Response<Body>, DomainError> {
+        let (status, resp_headers, resp_body_stream) = match pipeline.cache {
+            Some(ref cache_request) => {
+                self.apply_response_cache(cache_request, status, resp_headers, resp_body_stream)
+            }
+            None => (status, resp_headers, resp_body_stream),
+        };
+
         execute_guard_responses(
             &self.guard_registry,
             &pipeline.guard_bindings,
@@ -228,6 +264,17 @@ impl DataPlaneServiceImpl {
             resp_headers.insert("x-ratelimit-reset", HeaderValue::from(outcome.reset_epoch));
         }
 
+        // gRPC responses: split the trailer frame back out of the body and
+        // hand the trailers to the REST layer alongside the messages.
+        if pipeline.grpc && headers::is_grpc_content_type(&resp_headers) {
+            let (resp_body_stream, trailers) = grpc::split_trailers(resp_body_stream);
+            let mut resp =
+                build_proxy_response(status, resp_headers, resp_body_stream, instance_uri)?;
+            resp.extensions_mut()
+                .insert(grpc::GrpcTrailersHandle::new(trailers));
+            return Ok(resp);
+        }
+
         // Apply streaming lifecycle management for SSE responses:
         // idle timeout and graceful shutdown awareness.
         let resp_body_stream = if oagw_sdk::sse::is_server_events_response(&resp_headers) {
@@ -243,13 +290,83 @@ impl DataPlaneServiceImpl {
         build_proxy_response(status, resp_headers, resp_body_stream, instance_uri)
     }
 
+    /// Cache the upstream response when it is storable, or answer the 304 of
+    /// a revalidation with the stored response. Gateway-generated errors are
+    /// never stored.
+    fn apply_response_cache(
+        &self,
+        cache_request: &CacheRequest,
+        status: http::StatusCode,
+        mut resp_headers: HeaderMap,
+        resp_body_stream: BodyStream,
+    ) -> (http::StatusCode, HeaderMap, BodyStream) {
+        let default_ttl = Duration::from_secs(u64::from(cache_request.config.default_ttl_secs));
+        let from_upstream = matches!(
+            headers::extract_error_source(&resp_headers),
+            ErrorSource::Upstream
+        );
+
+        if let Some(ref stale) = cache_request.stale
+            && status == http::StatusCode::NOT_MODIFIED
+            && from_upstream
+        {
+            let (entry, storable) = stale.revalidated(&resp_headers, default_ttl);
+            let mut cached_headers = entry.response_headers(Instant::now());
+            cached_headers.append(CACHE_STATUS, CacheOutcome::Revalidated.cache_status());
+            let (status, body) = (entry.status, entry.body_stream());
+            if storable {
+                self.response_cache.store(entry);
+            }
+            self.response_cache.record(CacheOutcome::Revalidated);
+            return (status, cached_headers, body);
+        }
+
+        self.response_cache.record(CacheOutcome::Miss);
+        let max_bytes = cache_request.config.max_object_bytes as usize;
+        let within_limit = resp_headers
+            .get(http::header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<usize>().ok())
+            .is_none_or(|len| len <= max_bytes);
+        let freshness = cache::response_freshness(status.as_u16(), &resp_headers, default_ttl)
+            .filter(|_| {
+                from_upstream
+                    && within_limit
+                    && !oagw_sdk::sse::is_server_events_response(&resp_headers)
+            });
+
+        let resp_body_stream = match freshness {
+            Some(freshness) => {
+                let store = Arc::clone(&self.response_cache);
+                let key = cache_request.key.clone();
+                let stored_headers = resp_headers.clone();
+                let vary_headers = cache_request.vary_headers.clone();
+                collect_for_cache(resp_body_stream, max_bytes, move |body| {
+                    store.store(CachedResponse::new(
+                        key,
+                        status,
+                        stored_headers,
+                        body,
+                        &vary_headers,
+                        freshness,
+                    ));
+                })
+            }
+            None => resp_body_stream,
+        };
+        resp_headers.append(CACHE_STATUS, CacheOutcome::Miss.cache_status());
+        (status, resp_headers, resp_body_stream)
+    }
+
     /// Two-tier endpoint selection (D1):
     /// 1. `X-OAGW-Target-Host` header → validate against endpoint list
-    /// 2. Round-robin via `BackendSelector` for multi-endpoint, direct for single
+    /// 2. The upstream's load-balancing strategy via `BackendSelector` for
+    ///    multi-endpoint (or health-checked) upstreams, direct for single
     async fn select_endpoint(
         &self,
         upstream: &Upstream,
         req_headers: &http::HeaderMap,
+        balance_key: &[u8],
         instance_uri: &str,
     ) -> Result<SelectedEndpoint, DomainError> {
         let endpoints = &upstream.server.endpoints;
@@ -302,30 +419,310 @@ impl DataPlaneServiceImpl {
             return Ok(SelectedEndpoint {
                 endpoint,
                 resolved_addr: None,
+                in_flight: None,
             });
         }
 
         // Tier 2: Automatic selection.
-        if endpoints.len() == 1 {
+        if endpoints.len() == 1 && upstream.server.health_check.is_none() {
             // Single-endpoint: bypass LB. `resolved_addr` is None, so
             // `upstream_peer` will fall back to DNS on the request path.
             // Acceptable trade-off: single-endpoint upstreams don't benefit
-            // from health-checked LB selection anyway.
+            // from LB selection anyway. An explicit health check still goes
+            // through the LB so an unhealthy endpoint fails fast.
             return Ok(SelectedEndpoint {
                 endpoint: endpoints[0].clone(),
                 resolved_addr: None,
+                in_flight: None,
             });
         }
 
-        // Multi-endpoint: round-robin via BackendSelector.
+        // Multi-endpoint: the configured strategy via BackendSelector.
         self.backend_selector
-            .select(upstream.id, endpoints)
+            .select(upstream.id, &upstream.server, balance_key)
             .await
             .ok_or_else(|| DomainError::DownstreamError {
                 detail: "all backends are unhealthy".into(),
                 instance: instance_uri.to_string(),
             })
     }
+
+    /// `select_endpoint`, steering away from `avoid` while the upstream still
+    /// offers another endpoint. Used to move a retry off the endpoint that
+    /// just failed; a pinned `X-OAGW-Target-Host` or a single endpoint keeps
+    /// the original choice.
+    async fn select_endpoint_avoiding(
+        &self,
+        upstream: &Upstream,
+        req_headers: &http::HeaderMap,
+        balance_key: &[u8],
+        instance_uri: &str,
+        avoid: Option<&Endpoint>,
+    ) -> Result<SelectedEndpoint, DomainError> {
+        let mut selected = self
+            .select_endpoint(upstream, req_headers, balance_key, instance_uri)
+            .await?;
+        let Some(avoid) = avoid else {
+            return Ok(selected);
+        };
+        for _ in 1..upstream.server.endpoints.len() {
+            if selected.endpoint != *avoid {
+                break;
+            }
+            selected = self
+                .select_endpoint(upstream, req_headers, balance_key, instance_uri)
+                .await?;
+        }
+        Ok(selected)
+    }
+
+    /// Endpoint selection gated by the upstream's circuit breaker.
+    ///
+    /// Returns the selected endpoint together with the permit the caller
+    /// settles once the upstream call completes (`None` when no breaker is
+    /// enabled). With `per_endpoint` scope an open breaker only rules out its
+    /// own endpoint, so selection is retried up to once per endpoint.
+    async fn select_endpoint_with_breaker(
+        &self,
+        upstream: &Upstream,
+        req_headers: &http::HeaderMap,
+        balance_key: &[u8],
+        instance_uri: &str,
+        avoid: Option<&Endpoint>,
+    ) -> Result<(SelectedEndpoint, Option<CircuitPermit<'_>>), DomainError> {
+        let Some(cb) = upstream.circuit_breaker.as_ref().filter(|cb| cb.enabled) else {
+            let selected = self
+                .select_endpoint_avoiding(upstream, req_headers, balance_key, instance_uri, avoid)
+                .await?;
+            return Ok((selected, None));
+        };
+
+        let attempts = match cb.scope {
+            CircuitBreakerScope::Global => 1,
+            CircuitBreakerScope::PerEndpoint => upstream.server.endpoints.len().max(1),
+        };
+        let mut attempt = 1;
+        loop {
+            let selected = self
+                .select_endpoint_avoiding(upstream, req_headers, balance_key, instance_uri, avoid)
+                .await?;
+            let key = build_circuit_breaker_key(upstream.id, cb.scope, &selected.endpoint);
+            match self.circuit_breakers.try_acquire(&key, cb, instance_uri) {
+                Ok(permit) => return Ok((selected, Some(permit))),
+                Err(err) if attempt >= attempts => return Err(err),
+                Err(_) => attempt += 1,
+            }
+        }
+    }
+
+    /// Feed the outcome of an upstream exchange into passive outlier
+    /// detection: a 5xx or a transport error/timeout counts against the
+    /// endpoint.
+    fn report_endpoint_outcome<T>(
+        &self,
+        upstream_id: Uuid,
+        selected: &SelectedEndpoint,
+        result: &Result<(http::StatusCode, HeaderMap, T), DomainError>,
+    ) {
+        let success = matches!(result, Ok((status, _, _)) if !status.is_server_error());
+        self.backend_selector
+            .report_outcome(upstream_id, &selected.endpoint, success);
+    }
+
+    /// Bind the outbound request to a selected endpoint: enforce the
+    /// HTTPS-only constraint, set `Host`, build the upstream URL and inject
+    /// the internal context headers for `PingoraProxy` (D9).
+    ///
+    /// Idempotent over `outbound_headers`, so a retry can rebind the same
+    /// request to another endpoint.
+    fn bind_endpoint(
+        &self,
+        target: &BindTarget<'_>,
+        selected: &SelectedEndpoint,
+        outbound_headers: &mut HeaderMap,
+    ) -> Result<String, DomainError> {
+        let endpoint = &selected.endpoint;
+
+        // Enforce HTTPS-only constraint (cpt-cf-oagw-constraint-https-only).
+        if !self.allow_http_upstream && matches!(endpoint.scheme, Scheme::Http) {
+            return Err(DomainError::Validation {
+                field: "endpoint.scheme",
+                reason: "HTTP_UPSTREAM_FORBIDDEN",
+                detail: "upstream endpoint uses HTTP; only HTTPS endpoints are permitted".into(),
+                instance: target.instance_uri.to_string(),
+            });
+        }
+
+        headers::set_host_header(outbound_headers, &endpoint.host, endpoint.port);
+
+        let url = request_builder::build_upstream_url(
+            endpoint,
+            target.route_path,
+            target.remaining_suffix,
+            target.query_params,
+        )?;
+
+        let scheme_str = match endpoint.scheme {
+            Scheme::Http => "http",
+            Scheme::Https => "https",
+            Scheme::Wss => "wss",
+            Scheme::Wt => "wt",
+            Scheme::Grpc => "grpc",
+        };
+        if let Ok(v) = HeaderValue::from_str(&target.upstream_id.to_string()) {
+            outbound_headers.insert(H_UPSTREAM_ID, v);
+        }
+        if let Ok(v) = HeaderValue::from_str(&endpoint.host) {
+            outbound_headers.insert(H_ENDPOINT_HOST, v);
+        }
+        if let Ok(v) = HeaderValue::from_str(&endpoint.port.to_string()) {
+            outbound_headers.insert(H_ENDPOINT_PORT, v);
+        }
+        outbound_headers.insert(H_ENDPOINT_SCHEME, HeaderValue::from_static(scheme_str));
+        if let Ok(v) = HeaderValue::from_str(target.instance_uri) {
+            outbound_headers.insert(H_INSTANCE_URI, v);
+        }
+        outbound_headers.remove(H_RESOLVED_ADDR);
+        if let Some(addr) = selected.resolved_addr
+            && let Ok(v) = HeaderValue::from_str(&addr.to_string())
+        {
+            outbound_headers.insert(H_RESOLVED_ADDR, v);
+        }
+        if let Some(timeouts) = target.timeouts {
+            if let Some(ms) = timeouts.connect_ms {
+                outbound_headers.insert(H_CONNECT_TIMEOUT_MS, HeaderValue::from(ms));
+            }
+            if let Some(ms) = timeouts.read_ms {
+                outbound_headers.insert(H_READ_TIMEOUT_MS, HeaderValue::from(ms));
+            }
+        }
+
+        Ok(url)
+    }
+
+    /// Start a Pingora session on one end of an in-memory `DuplexStream` and
+    /// return the other end for writing the request and reading the response.
+    fn open_bridge(&self) -> tokio::io::DuplexStream {
+        let (client_io, server_io) = tokio::io::duplex(65_536);
+
+        // Create Pingora H1 session from the server side of the DuplexStream.
+        // Pingora implements all IO traits for DuplexStream (in ext_io_impl).
+        let session = pingora_core::protocols::http::ServerSession::new_http1(Box::new(server_io));
+
+        // Spawn Pingora proxy processing in background.
+        let proxy = self.proxy.clone();
+        let shutdown = self.shutdown_rx.clone();
+        tokio::spawn(async move {
+            proxy.process_new_http(session, &shutdown).await;
+        });
+
+        client_io
+    }
+
+    /// Send one fully buffered request through a fresh bridge session and
+    /// parse the response head within `timeout`.
+    async fn send_buffered(
+        &self,
+        method: &http::Method,
+        url: &str,
+        outbound_headers: &HeaderMap,
+        body: &Bytes,
+        timeout: Duration,
+        instance_uri: &str,
+    ) -> Result<(http::StatusCode, HeaderMap, BodyStream), DomainError> {
+        let mut client_io = self.open_bridge();
+        let wire =
+            session_bridge::serialize_request_wire(method, url, outbound_headers, Some(body));
+        client_io
+            .write_all(&wire)
+            .await
+            .map_err(|e| DomainError::DownstreamError {
+                detail: format!("failed to write to proxy bridge: {e}"),
+                instance: instance_uri.to_string(),
+            })?;
+        // Do NOT shutdown the write side — Pingora uses Content-Length to
+        // determine the request boundary, and an early write-close is
+        // misinterpreted as "downstream dropped the connection".
+
+        tokio::time::timeout(timeout, session_bridge::parse_response_stream(client_io))
+            .await
+            .map_err(|_| DomainError::RequestTimeout {
+                detail: format!("request to {url} timed out after {timeout:?}"),
+                instance: instance_uri.to_string(),
+            })
+            .and_then(|r| {
+                r.map_err(|e| DomainError::DownstreamError {
+                    detail: format!("proxy bridge error: {e}"),
+                    instance: instance_uri.to_string(),
+                })
+            })
+    }
+}
+
+/// Request-scoped inputs for [`DataPlaneServiceImpl::bind_endpoint`].
+struct BindTarget<'a> {
+    upstream_id: Uuid,
+    route_path: &'a str,
+    remaining_suffix: &'a str,
+    query_params: &'a [(String, String)],
+    timeouts: Option<&'a TimeoutConfig>,
+    instance_uri: &'a str,
+}
+
+/// Settle a circuit breaker permit with the outcome of the upstream exchange.
+fn record_circuit_outcome<T>(
+    permit: Option<CircuitPermit<'_>>,
+    result: &Result<(http::StatusCode, HeaderMap, T), DomainError>,
+) {
+    let Some(permit) = permit else {
+        return;
+    };
+    match result {
+        Ok((status, resp_headers, _)) => {
+            let gateway_error = matches!(
+                headers::extract_error_source(resp_headers),
+                ErrorSource::Gateway
+            );
+            permit.record_response(status.as_u16(), gateway_error);
+        }
+        Err(err) => permit.record_error(err),
+    }
+}
+
+/// Pass `inner` through unchanged and release `guard` once it ends, so the
+/// endpoint counts the request as in flight until the body is streamed.
+fn hold_in_flight(inner: BodyStream, guard: Option<InFlightGuard>) -> BodyStream {
+    let Some(guard) = guard else {
+        return inner;
+    };
+    let stream = unfold((inner, guard), |(mut inner, guard)| async move {
+        match inner.next().await {
+            Some(item) => Some((item, (inner, guard))),
+            None => {
+                drop(guard);
+                None
+            }
+        }
+    });
+    Box::pin(stream)
+}
+
+/// Key hashed by consistent-hash balancing: the configured request header or
+/// the caller's tenant. Requests without the header get a random key, so they
+/// spread across endpoints instead of all landing on one.
+fn balance_key(server: &Server, req_headers: &HeaderMap, tenant_id: Uuid) -> Vec<u8> {
+    match &server.load_balancing {
+        Some(LoadBalancing::ConsistentHash {
+            key: HashKey::Header(name),
+        }) => req_headers.get(name.as_str()).map_or_else(
+            || Uuid::new_v4().as_bytes().to_vec(),
+            |v| v.as_bytes().to_vec(),
+        ),
+        Some(LoadBalancing::ConsistentHash {
+            key: HashKey::Tenant,
+        }) => tenant_id.as_bytes().to_vec(),
+        _ => Vec::new(),
+    }
 }
 
 #[async_trait]
@@ -379,6 +776,7 @@ impl DataPlaneService for DataPlaneServiceImpl {
         let req_headers = parts.headers;
 
         let is_upgrade = headers::is_websocket_upgrade(&req_headers);
+        let is_grpc = headers::is_grpc_content_type(&req_headers);
 
         // Validate Content-Type format if present.
         if !headers::is_valid_content_type(&req_headers) {
@@ -425,6 +823,16 @@ impl DataPlaneService for DataPlaneServiceImpl {
             .resolve_proxy_target(&ctx, &alias, method.as_ref(), &path_suffix)
             .await?;
 
+        // 1b. A gRPC route only serves gRPC calls.
+        if route.match_rules.grpc.is_some() && !is_grpc {
+            return Err(DomainError::Validation {
+                field: "content-type",
+                reason: "GRPC_CONTENT_TYPE_REQUIRED",
+                detail: "gRPC route requires Content-Type application/grpc".into(),
+                instance: instance_uri,
+            });
+        }
+
         // 1c. CORS origin enforcement for actual cross-origin requests.
         // Preflight is handled permissively at the handler level (no upstream resolution).
         // Here we validate the Origin against the upstream's CORS config and reject
@@ -531,7 +939,15 @@ impl DataPlaneService for DataPlaneServiceImpl {
             }
         }
 
+        // gRPC protocol headers (deadline, compression) and `te: trailers`
+        // are forwarded regardless of the passthrough mode.
+        if is_grpc {
+            grpc::prepare_request_headers(&req_headers, &mut outbound_headers);
+        }
+
         // 4. Execute auth plugin.
+        let mut user_credentials = false;
+        let mut foreign_credentials = false;
         if let Some(ref auth) = upstream.auth {
             tracing::debug!(plugin = %auth.plugin_type, "executing auth plugin");
             let plugin = self.auth_registry.resolve(&auth.plugin_type).map_err(|e| {
@@ -545,6 +961,8 @@ impl DataPlaneService for DataPlaneServiceImpl {
                 headers: headers::header_map_to_hash_map(&outbound_headers),
                 config: auth.config.clone().unwrap_or_default(),
                 security_context: ctx.clone(),
+                user_credentials: false,
+                credential_tenant_ids: Vec::new(),
             };
             plugin
                 .authenticate(&mut auth_ctx)
@@ -581,6 +999,11 @@ impl DataPlaneService for DataPlaneServiceImpl {
                     }
                 })?;
             outbound_headers = headers::hash_map_to_header_map(&auth_ctx.headers);
+            user_credentials = auth_ctx.user_credentials;
+            foreign_credentials = auth_ctx
+                .credential_tenant_ids
+                .iter()
+                .any(|tenant_id| *tenant_id != route.tenant_id);
             tracing::debug!(plugin = %auth.plugin_type, "auth plugin succeeded");
         }
 
@@ -700,23 +1123,128 @@ impl DataPlaneService for DataPlaneServiceImpl {
             query_params = transform_query;
         }
 
-        // 5a. Endpoint selection (D1 — two-tier).
-        let selected = self
-            .select_endpoint(&upstream, &req_headers, &instance_uri)
-            .await?;
-        let endpoint = &selected.endpoint;
+        let response_header_rules = upstream
+            .headers
+            .as_ref()
+            .and_then(|hc| hc.response.as_ref());
 
-        // 5b. Enforce HTTPS-only constraint (cpt-cf-oagw-constraint-https-only).
-        if !self.allow_http_upstream && matches!(endpoint.scheme, Scheme::Http) {
-            return Err(DomainError::Validation {
-                field: "endpoint.scheme",
-                reason: "HTTP_UPSTREAM_FORBIDDEN",
-                detail: "upstream endpoint uses HTTP; only HTTPS endpoints are permitted".into(),
-                instance: instance_uri,
-            });
+        // 5c. Response cache. A fresh entry is served here, before endpoint
+        //     selection, circuit breaking and rate limiting, since the
+        //     upstream is not contacted. A stale entry with validators is
+        //     revalidated with a conditional request. Responses obtained with
+        //     per-user credentials are cached only when the route allows it,
+        //     and then per subject.
+        let mut cache_request = None;
+        if let Some(config) = route.cache.filter(|c| c.enabled)
+            && !is_upgrade
+            && !is_grpc
+        {
+            if body_stream.is_some()
+                || !body_bytes.is_empty()
+                || !cache::is_cacheable_request(&method, &req_headers)
+                || (user_credentials && !config.allow_user_credentials)
+            {
+                self.response_cache.record(CacheOutcome::Bypass);
+            } else {
+                let target = if query_params.is_empty() {
+                    path_suffix.clone()
+                } else {
+                    let qs = form_urlencoded::Serializer::new(String::new())
+                        .extend_pairs(&query_params)
+                        .finish();
+                    format!("{path_suffix}?{qs}")
+                };
+                let key = cache::build_cache_key(&CacheKeyContext {
+                    sharing: config.sharing,
+                    generation: self.response_cache.generation(route.id),
+                    route_id: route.id,
+                    route_tenant_id: route.tenant_id,
+                    tenant_id: ctx.subject_tenant_id(),
+                    subject_id: user_credentials.then(|| ctx.subject_id()),
+                    foreign_credentials,
+                    upstream_id: upstream.id,
+                    method: method.as_str(),
+                    target: &target,
+                });
+                let vary_headers = outbound_headers.clone();
+                let mut stale = None;
+                if let Some(entry) = self.response_cache.lookup(&key, &vary_headers) {
+                    let now = Instant::now();
+                    if entry.is_fresh(&CacheControl::parse(&req_headers), now) {
+                        self.response_cache.record(CacheOutcome::Hit);
+                        let mut resp_headers = entry.response_headers(now);
+                        resp_headers.append(CACHE_STATUS, CacheOutcome::Hit.cache_status());
+                        let pipeline = ResponsePipelineCtx {
+                            guard_bindings,
+                            transform_bindings,
+                            method: method.as_str(),
+                            path_suffix: &path_suffix,
+                            ctx: &ctx,
+                            cors_config: effective_cors.as_ref(),
+                            origin: request_origin,
+                            response_header_rules,
+                            rate_limit_outcome: None,
+                            grpc: false,
+                            cache: None,
+                        };
+                        return self
+                            .finalize_response(
+                                &pipeline,
+                                entry.status,
+                                resp_headers,
+                                entry.body_stream(),
+                                instance_uri,
+                            )
+                            .await;
+                    }
+                    if entry.is_revalidatable() {
+                        entry.add_conditional_headers(&mut outbound_headers);
+                        stale = Some(entry);
+                    }
+                }
+                cache_request = Some(CacheRequest {
+                    key,
+                    config,
+                    vary_headers,
+                    stale,
+                });
+            }
         }
 
-        headers::set_host_header(&mut outbound_headers, &endpoint.host, endpoint.port);
+        // 5a. Endpoint selection (D1 — two-tier), gated by the circuit breaker.
+        //     The permit is settled once the upstream answers; early returns
+        //     drop it without counting the request.
+        let balance_key = balance_key(&upstream.server, &req_headers, ctx.subject_tenant_id());
+        let (selected, circuit_permit) = self
+            .select_endpoint_with_breaker(
+                &upstream,
+                &req_headers,
+                &balance_key,
+                &instance_uri,
+                None,
+            )
+            .await?;
+
+        // 5b. Bind to the endpoint: HTTPS-only check, Host, URL and internal
+        //     context headers. path_suffix is the full path from the proxy URL;
+        //     strip the route prefix so we get:
+        //     endpoint + route_path + remaining_suffix. gRPC routes address
+        //     exactly `/{service}/{method}`.
+        let grpc_path = route.match_rules.grpc.as_ref().map(GrpcMatch::path);
+        let route_path = match (&route.match_rules.http, &grpc_path) {
+            (Some(h), _) => h.path.as_str(),
+            (None, Some(p)) => p.as_str(),
+            (None, None) => "/",
+        };
+        let bind_target = BindTarget {
+            upstream_id: upstream.id,
+            route_path,
+            remaining_suffix: path_suffix.strip_prefix(route_path).unwrap_or(""),
+            query_params: &query_params,
+            timeouts: route.timeouts.as_ref(),
+            instance_uri: &instance_uri,
+        };
+        let url = self.bind_endpoint(&bind_target, &selected, &mut outbound_headers)?;
 
         // 6. Check rate limit (upstream then route) with scope-aware keying.
         //    Both try_consume calls decrement their respective buckets
@@ -772,53 +1300,23 @@ impl DataPlaneService for DataPlaneServiceImpl {
             }
         }
 
-        // 7. Build URL.
-        // path_suffix is the full path from the proxy URL; strip the route prefix
-        // so we get: endpoint + route_path + remaining_suffix.
-        let route_path = route
-            .match_rules
-            .http
-            .as_ref()
-            .map_or("/", |h| h.path.as_str());
-        let remaining_suffix = path_suffix.strip_prefix(route_path).unwrap_or("");
-        let url = request_builder::build_upstream_url(
-            endpoint,
-            route_path,
-            remaining_suffix,
-            &query_params,
-        )?;
-
-        // 7b. Inject internal context headers for PingoraProxy (D9).
-        let scheme_str = match endpoint.scheme {
-            Scheme::Http => "http",
-            Scheme::Https => "https",
-            Scheme::Wss => "wss",
-            Scheme::Wt => "wt",
-            Scheme::Grpc => "grpc",
+        // 7. Total timeout: until response headers, across all attempts.
+        let timeout = route
+            .timeouts
+            .and_then(|t| t.total_ms)
+            .map_or(self.request_timeout, |ms| {
+                Duration::from_millis(u64::from(ms))
+            });
+        // A shorter client deadline (`grpc-timeout`) caps the route timeout.
+        let timeout = if is_grpc {
+            req_headers
+                .get("grpc-timeout")
+                .and_then(|v| v.to_str().ok())
+                .and_then(grpc::parse_timeout)
+                .map_or(timeout, |deadline| timeout.min(deadline))
+        } else {
+            timeout
         };
-        if let Ok(v) = HeaderValue::from_str(&upstream.id.to_string()) {
-            outbound_headers.insert(H_UPSTREAM_ID, v);
-        }
-        if let Ok(v) = HeaderValue::from_str(&endpoint.host) {
-            outbound_headers.insert(H_ENDPOINT_HOST, v);
-        }
-        if let Ok(v) = HeaderValue::from_str(&endpoint.port.to_string()) {
-            outbound_headers.insert(H_ENDPOINT_PORT, v);
-        }
-        outbound_headers.insert(H_ENDPOINT_SCHEME, HeaderValue::from_static(scheme_str));
-        if let Ok(v) = HeaderValue::from_str(&instance_uri) {
-            outbound_headers.insert(H_INSTANCE_URI, v);
-        }
-        if let Some(addr) = selected.resolved_addr
-            && let Ok(v) = HeaderValue::from_str(&addr.to_string())
-        {
-            outbound_headers.insert(H_RESOLVED_ADDR, v);
-        }
-
-        let response_header_rules = upstream
-            .headers
-            .as_ref()
-            .and_then(|hc| hc.response.as_ref());
 
         let pipeline = ResponsePipelineCtx {
             guard_bindings,
@@ -830,19 +1328,14 @@ impl DataPlaneService for DataPlaneServiceImpl {
             origin: request_origin,
             response_header_rules,
             rate_limit_outcome,
+            grpc: is_grpc,
+            cache: cache_request,
         };
 
         // 8. WebSocket upgrade path: bypass the normal request/response bridge
         // and set up a bidirectional raw-byte tunnel through Pingora.
         if is_upgrade {
-            let (mut client_io, server_io) = tokio::io::duplex(65_536);
-            let session =
-                pingora_core::protocols::http::ServerSession::new_http1(Box::new(server_io));
-            let proxy = self.proxy.clone();
-            let shutdown = self.shutdown_rx.clone();
-            tokio::spawn(async move {
-                proxy.process_new_http(session, &shutdown).await;
-            });
+            let mut client_io = self.open_bridge();
 
             // Write the upgrade request (Connection: Upgrade, no body).
             let wire =
@@ -856,8 +1349,8 @@ impl DataPlaneService for DataPlaneServiceImpl {
                 })?;
 
             // Parse only the response headers (IO stays intact for bidirectional copy).
-            let upgrade_timeout = self.request_timeout;
-            let (status, resp_headers, leftover) = tokio::time::timeout(
+            let upgrade_timeout = timeout;
+            let parsed = tokio::time::timeout(
                 upgrade_timeout,
                 session_bridge::parse_upgrade_response(&mut client_io),
             )
@@ -865,11 +1358,16 @@ impl DataPlaneService for DataPlaneServiceImpl {
             .map_err(|_| DomainError::RequestTimeout {
                 detail: format!("WebSocket upgrade to {url} timed out after {upgrade_timeout:?}"),
                 instance: instance_uri.clone(),
-            })?
-            .map_err(|e| DomainError::DownstreamError {
-                detail: format!("proxy bridge error during WebSocket upgrade: {e}"),
-                instance: instance_uri.clone(),
-            })?;
+            })
+            .and_then(|r| {
+                r.map_err(|e| DomainError::DownstreamError {
+                    detail: format!("proxy bridge error during WebSocket upgrade: {e}"),
+                    instance: instance_uri.clone(),
+                })
+            });
+            record_circuit_outcome(circuit_permit, &parsed);
+            self.report_endpoint_outcome(upstream.id, &selected, &parsed);
+            let (status, resp_headers, leftover) = parsed?;
 
             if status != http::StatusCode::SWITCHING_PROTOCOLS {
                 return Err(DomainError::ProtocolError {
@@ -923,34 +1421,20 @@ impl DataPlaneService for DataPlaneServiceImpl {
                         close_timeout: self.websocket_close_timeout,
                         max_frame_size: self.websocket_max_frame_size,
                         shutdown_rx: self.shutdown_rx.clone(),
+                        in_flight: selected.in_flight,
                     },
                 ));
             return Ok(resp);
         }
 
         // 8. Bridge request into Pingora via in-memory DuplexStream.
-        let (client_io, server_io) = tokio::io::duplex(65_536);
-
-        // Create Pingora H1 session from the server side of the DuplexStream.
-        // Pingora implements all IO traits for DuplexStream (in ext_io_impl).
-        let session = pingora_core::protocols::http::ServerSession::new_http1(Box::new(server_io));
-
-        // Spawn Pingora proxy processing in background.
-        let proxy = self.proxy.clone();
-        let shutdown = self.shutdown_rx.clone();
-        tokio::spawn(async move {
-            proxy.process_new_http(session, &shutdown).await;
-        });
-
-        // Write the request and read the response from the client side.
-        let timeout = self.request_timeout;
-
         let upstream_result: Result<http::Response<Body>, DomainError> = if let Some(
             mut body_stream,
         ) = body_stream
         {
             // Streaming path: write headers, then forward body chunks concurrently.
-            let (client_read, mut client_write) = tokio::io::split(client_io);
+            // A consumed body stream cannot be replayed, so no retries here.
+            let (client_read, mut client_write) = tokio::io::split(self.open_bridge());
 
             let header_bytes =
                 session_bridge::serialize_request_wire(&method, &url, &outbound_headers, None);
@@ -1051,15 +1535,21 @@ impl DataPlaneService for DataPlaneServiceImpl {
                     })
                 }
                 result = resp_future => {
-                    let (status, resp_headers, resp_body_stream) = result
+                    let parsed = result
                         .map_err(|_| DomainError::RequestTimeout {
                             detail: format!("request to {url} timed out after {timeout:?}"),
                             instance: instance_uri.clone(),
-                        })?
-                        .map_err(|e| DomainError::DownstreamError {
-                            detail: format!("proxy bridge error: {e}"),
-                            instance: instance_uri.clone(),
-                        })?;
+                        })
+                        .and_then(|r| {
+                            r.map_err(|e| DomainError::DownstreamError {
+                                detail: format!("proxy bridge error: {e}"),
+                                instance: instance_uri.clone(),
+                            })
+                        });
+                    record_circuit_outcome(circuit_permit, &parsed);
+                    self.report_endpoint_outcome(upstream.id, &selected, &parsed);
+                    let (status, resp_headers, resp_body_stream) = parsed?;
+                    let resp_body_stream = hold_in_flight(resp_body_stream, selected.in_flight);
                     self.finalize_response(
                         &pipeline,
                         status,
@@ -1071,37 +1561,94 @@ impl DataPlaneService for DataPlaneServiceImpl {
                 }
             }
         } else {
-            // Buffered path: write full request, shutdown write side, then read response.
-            let wire = session_bridge::serialize_request_wire(
-                &method,
-                &url,
-                &outbound_headers,
-                Some(&body_bytes),
-            );
-            let mut client_io = client_io;
-            client_io
-                .write_all(&wire)
-                .await
-                .map_err(|e| DomainError::DownstreamError {
-                    detail: format!("failed to write to proxy bridge: {e}"),
-                    instance: instance_uri.clone(),
-                })?;
-            // Do NOT shutdown the write side — Pingora uses Content-Length to
-            // determine the request boundary, and an early write-close is
-            // misinterpreted as "downstream dropped the connection".
+            // Buffered path: the body is replayable, so the route's retry
+            // policy applies. Each attempt gets its own bridge session and the
+            // remainder of the total timeout.
+            let retry_policy = route
+                .retry
+                .as_ref()
+                .filter(|policy| retry::method_allows_retry(policy, &method));
+            if route.retry.is_some() {
+                self.retry_budgets.record_request(route.id);
+            }
+            let deadline = tokio::time::Instant::now() + timeout;
+            let mut selected = selected;
+            let mut circuit_permit = circuit_permit;
+            let mut url = url;
+            let mut attempt: u32 = 1;
+
+            // 9. Parse response (retrying per the route policy).
+            let parsed = loop {
+                let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+                let parsed = self
+                    .send_buffered(
+                        &method,
+                        &url,
+                        &outbound_headers,
+                        &body_bytes,
+                        remaining,
+                        &instance_uri,
+                    )
+                    .await;
+                record_circuit_outcome(circuit_permit.take(), &parsed);
+                self.report_endpoint_outcome(upstream.id, &selected, &parsed);
+
+                let Some(policy) = retry_policy else {
+                    break parsed;
+                };
+                let retryable = match &parsed {
+                    Ok((status, resp_headers, _)) => retry::is_retryable_response(
+                        &policy.retry_on,
+                        status.as_u16(),
+                        matches!(
+                            headers::extract_error_source(resp_headers),
+                            ErrorSource::Gateway
+                        ),
+                    ),
+                    Err(err) => retry::is_retryable_error(&policy.retry_on, err),
+                };
+                let delay = retry::backoff_delay(&policy.backoff, attempt);
+                if !retryable
+                    || attempt >= policy.max_attempts
+                    || delay >= deadline.saturating_duration_since(tokio::time::Instant::now())
+                    || !self.retry_budgets.try_withdraw(route.id, &policy.budget)
+                {
+                    break parsed;
+                }
 
-            // 9. Parse response.
-            let (status, resp_headers, resp_body_stream) =
-                tokio::time::timeout(timeout, session_bridge::parse_response_stream(client_io))
+                // Move to another endpoint when the upstream has one. If none
+                // can be bound (e.g. every breaker is open) the last outcome
+                // stands.
+                tokio::time::sleep(delay).await;
+                let Ok((next, permit)) = self
+                    .select_endpoint_with_breaker(
+                        &upstream,
+                        &req_headers,
+                        &balance_key,
+                        &instance_uri,
+                        Some(&selected.endpoint),
+                    )
                     .await
-                    .map_err(|_| DomainError::RequestTimeout {
-                        detail: format!("request to {url} timed out after {timeout:?}"),
-                        instance: instance_uri.clone(),
-                    })?
-                    .map_err(|e| DomainError::DownstreamError {
-                        detail: format!("proxy bridge error: {e}"),
-                        instance: instance_uri.clone(),
-                    })?;
+                else {
+                    break parsed;
+                };
+                let Ok(next_url) = self.bind_endpoint(&bind_target, &next, &mut outbound_headers)
+                else {
+                    break parsed;
+                };
+                attempt += 1;
+                tracing::debug!(
+                    route_id = %route.id,
+                    attempt,
+                    host = %next.endpoint.host,
+                    "retrying upstream request"
+                );
+                selected = next;
+                circuit_permit = permit;
+                url = next_url;
+            };
+            let (status, resp_headers, resp_body_stream) = parsed?;
+            let resp_body_stream = hold_in_flight(resp_body_stream, selected.in_flight);
 
             self.finalize_response(
                 &pipeline,
@@ -1136,6 +1683,18 @@ impl DataPlaneService for DataPlaneServiceImpl {
     fn remove_rate_limit_keys_for_route(&self, route_id: Uuid) {
         self.rate_limiter.remove_keys_for_route(route_id);
     }
+
+    fn remove_retry_budget_for_route(&self, route_id: Uuid) {
+        self.retry_budgets.remove(route_id);
+    }
+
+    fn remove_circuit_breakers_for_upstream(&self, upstream_id: Uuid) {
+        self.circuit_breakers.remove_keys_for_upstream(upstream_id);
+    }
+
+    fn remove_cached_responses_for_route(&self, route_id: Uuid) {
+        self.response_cache.purge_route(route_id);
+    }
 }
 
 /// Collect plugin bindings from the effective upstream, filtered by a type predicate.
@@ -1284,6 +1843,20 @@ struct ResponsePipelineCtx<'a> {
     origin: Option<String>,
     response_header_rules: Option<&'a ResponseHeaderRules>,
     rate_limit_outcome: Option<(RateLimitOutcome, bool)>,
+    /// The request is a gRPC call; a gRPC response carries a trailer frame.
+    grpc: bool,
+    /// Set when the response may be stored in the route's response cache.
+    cache: Option<CacheRequest>,
+}
+
+/// Response cache state for a request forwarded on a caching route.
+struct CacheRequest {
+    key: String,
+    config: CacheConfig,
+    /// Outbound request headers (before conditional headers), for `Vary`.
+    vary_headers: HeaderMap,
+    /// Stale entry being revalidated with a conditional request.
+    stale: Option<Arc<CachedResponse>>,
 }
 
 /// Execute `on_error` for all transform bindings, logging errors without aborting.
@@ -1424,7 +1997,7 @@ fn normalize_path(path: &str) -> String {
 mod tests {
     use super::*;
     use crate::domain::model::{Endpoint, Scheme, Server, Upstream};
-    use crate::domain::services::EndpointSelector;
+    use crate::domain::services::{EndpointHealth, EndpointSelector};
     use std::sync::atomic::{AtomicUsize, Ordering};
     use uuid::Uuid;
 
@@ -1453,6 +2026,27 @@ mod tests {
         assert_eq!(normalize_path("/alias/v1/chat"), "/alias/v1/chat");
     }
 
+    #[tokio::test]
+    async fn hold_in_flight_releases_guard_when_body_ends() {
+        use crate::domain::load_balancing::InFlightCounters;
+
+        let counters = InFlightCounters::new();
+        let upstream_id = Uuid::new_v4();
+        let endpoint = ep("a.com", 443);
+        let chunks: Vec<Result<Bytes, oagw_sdk::body::BoxError>> =
+            vec![Ok(Bytes::from_static(b"ab")), Ok(Bytes::from_static(b"cd"))];
+        let mut body = hold_in_flight(
+            Box::pin(futures_util::stream::iter(chunks)),
+            Some(counters.acquire(upstream_id, &endpoint)),
+        );
+
+        assert!(body.next().await.is_some());
+        assert!(body.next().await.is_some());
+        assert_eq!(counters.count(upstream_id, &endpoint), 1);
+        assert!(body.next().await.is_none());
+        assert_eq!(counters.count(upstream_id, &endpoint), 0);
+    }
+
     // -----------------------------------------------------------------------
     // select_endpoint() unit tests
     // -----------------------------------------------------------------------
@@ -1462,6 +2056,7 @@ mod tests {
             scheme: Scheme::Https,
             host: host.to_string(),
             port,
+            weight: 1,
         }
     }
 
@@ -1470,7 +2065,11 @@ mod tests {
             id: Uuid::new_v4(),
             tenant_id: Uuid::new_v4(),
             alias: "test".to_string(),
-            server: Server { endpoints },
+            server: Server {
+                endpoints,
+                load_balancing: None,
+                health_check: None,
+            },
             protocol: "http".to_string(),
             enabled: true,
             auth: None,
@@ -1478,6 +2077,7 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
         }
     }
@@ -1504,15 +2104,24 @@ mod tests {
         async fn select(
             &self,
             _upstream_id: Uuid,
-            endpoints: &[Endpoint],
+            server: &Server,
+            _key: &[u8],
         ) -> Option<SelectedEndpoint> {
+            let endpoints = &server.endpoints;
             let idx = self.call_count.fetch_add(1, Ordering::Relaxed) % endpoints.len();
             Some(SelectedEndpoint {
                 endpoint: endpoints[idx].clone(),
                 resolved_addr: None,
+                in_flight: None,
             })
         }
 
+        fn report_outcome(&self, _upstream_id: Uuid, _endpoint: &Endpoint, _success: bool) {}
+
+        fn health(&self, _upstream_id: Uuid) -> Vec<EndpointHealth> {
+            Vec::new()
+        }
+
         fn invalidate(&self, _upstream_id: Uuid) {}
     }
 
@@ -1705,10 +2314,11 @@ mod tests {
             scheme: Scheme::Http,
             host: "insecure.example.com".to_string(),
             port: 80,
+            weight: 1,
         }]);
         let headers = HeaderMap::new();
 
-        let err = svc.select_endpoint(&upstream, &headers, "/test").await;
+        let err = svc.select_endpoint(&upstream, &headers, b"", "/test").await;
 
         // select_endpoint itself doesn't enforce HTTPS — the check is in proxy_request
         // after select_endpoint returns. Verify the endpoint is returned here (enforcement
@@ -1728,7 +2338,7 @@ mod tests {
         headers.insert("x-oagw-target-host", "a.com".parse().unwrap());
 
         let result = svc
-            .select_endpoint(&upstream, &headers, "/test")
+            .select_endpoint(&upstream, &headers, b"", "/test")
             .await
             .unwrap();
         assert_eq!(result.endpoint.host, "a.com");
@@ -1745,7 +2355,7 @@ mod tests {
         headers.insert("x-oagw-target-host", "evil.com".parse().unwrap());
 
         let err = svc
-            .select_endpoint(&upstream, &headers, "/test")
+            .select_endpoint(&upstream, &headers, b"", "/test")
             .await
             .unwrap_err();
         assert!(
@@ -1772,7 +2382,7 @@ mod tests {
             let mut headers = HeaderMap::new();
             headers.insert("x-oagw-target-host", bad_value.parse().unwrap());
             let err = svc
-                .select_endpoint(&upstream, &headers, "/test")
+                .select_endpoint(&upstream, &headers, b"", "/test")
                 .await
                 .unwrap_err();
             assert!(
@@ -1786,7 +2396,7 @@ mod tests {
         let mut headers = HeaderMap::new();
         headers.insert("x-oagw-target-host", HeaderValue::from_static(""));
         let err = svc
-            .select_endpoint(&upstream, &headers, "/test")
+            .select_endpoint(&upstream, &headers, b"", "/test")
             .await
             .unwrap_err();
         assert!(
@@ -1804,11 +2414,11 @@ mod tests {
         let headers = HeaderMap::new();
 
         let ep1 = svc
-            .select_endpoint(&upstream, &headers, "/test")
+            .select_endpoint(&upstream, &headers, b"", "/test")
             .await
             .unwrap();
         let ep2 = svc
-            .select_endpoint(&upstream, &headers, "/test")
+            .select_endpoint(&upstream, &headers, b"", "/test")
             .await
             .unwrap();
 
@@ -1831,7 +2441,7 @@ mod tests {
         let headers = HeaderMap::new();
 
         let result = svc
-            .select_endpoint(&upstream, &headers, "/test")
+            .select_endpoint(&upstream, &headers, b"", "/test")
             .await
             .unwrap();
         assert_eq!(result.endpoint.host, "only.com");
@@ -1842,6 +2452,48 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn select_endpoint_single_endpoint_with_health_check_uses_selector() {
+        let selector = Arc::new(MockSelector::new());
+        let svc = build_svc(selector.clone());
+        let mut upstream = upstream_with(vec![ep("only.com", 443)]);
+        upstream.server.health_check = Some(crate::domain::model::HealthCheckConfig::default());
+
+        svc.select_endpoint(&upstream, &HeaderMap::new(), b"", "/test")
+            .await
+            .unwrap();
+        assert_eq!(selector.calls(), 1);
+    }
+
+    #[test]
+    fn balance_key_follows_consistent_hash_source() {
+        let tenant_id = Uuid::new_v4();
+        let mut server = upstream_with(vec![ep("a.com", 443)]).server;
+        let mut headers = HeaderMap::new();
+        headers.insert("x-session", HeaderValue::from_static("abc"));
+
+        assert!(balance_key(&server, &headers, tenant_id).is_empty());
+
+        server.load_balancing = Some(LoadBalancing::ConsistentHash {
+            key: HashKey::Tenant,
+        });
+        assert_eq!(
+            balance_key(&server, &headers, tenant_id),
+            tenant_id.as_bytes()
+        );
+
+        server.load_balancing = Some(LoadBalancing::ConsistentHash {
+            key: HashKey::Header("x-session".into()),
+        });
+        assert_eq!(balance_key(&server, &headers, tenant_id), b"abc");
+        // Missing header: a random key per request.
+        let empty = HeaderMap::new();
+        assert_ne!(
+            balance_key(&server, &empty, tenant_id),
+            balance_key(&server, &empty, tenant_id)
+        );
+    }
+
     // positive-1.2 (custom-header-routing): Single-endpoint upstream validates header if present.
     #[tokio::test]
     async fn select_endpoint_single_endpoint_validates_header() {
@@ -1852,7 +2504,7 @@ mod tests {
         let mut headers = HeaderMap::new();
         headers.insert("x-oagw-target-host", "a.com".parse().unwrap());
         let result = svc
-            .select_endpoint(&upstream, &headers, "/test")
+            .select_endpoint(&upstream, &headers, b"", "/test")
             .await
             .unwrap();
         assert_eq!(result.endpoint.host, "a.com");
@@ -1861,7 +2513,7 @@ mod tests {
         let mut headers = HeaderMap::new();
         headers.insert("x-oagw-target-host", "b.com".parse().unwrap());
         let err = svc
-            .select_endpoint(&upstream, &headers, "/test")
+            .select_endpoint(&upstream, &headers, b"", "/test")
             .await
             .unwrap_err();
         assert!(
@@ -1869,4 +2521,124 @@ mod tests {
             "expected UnknownTargetHost for mismatched header on single-endpoint upstream"
         );
     }
+
+    #[tokio::test]
+    async fn select_endpoint_avoiding_skips_failed_endpoint() {
+        let selector = Arc::new(MockSelector::new());
+        let svc = build_svc(selector.clone());
+        let upstream = upstream_with(vec![ep("a.com", 443), ep("b.com", 443), ep("c.com", 443)]);
+        let headers = HeaderMap::new();
+
+        // Round-robin would hand out a.com first; avoiding it moves on.
+        let selected = svc
+            .select_endpoint_avoiding(&upstream, &headers, b"", "/test", Some(&ep("a.com", 443)))
+            .await
+            .unwrap();
+        assert_eq!(selected.endpoint.host, "b.com");
+        assert_eq!(selector.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn select_endpoint_avoiding_keeps_single_endpoint() {
+        let svc = build_svc(Arc::new(MockSelector::new()));
+        let upstream = upstream_with(vec![ep("only.com", 443)]);
+
+        let selected = svc
+            .select_endpoint_avoiding(
+                &upstream,
+                &HeaderMap::new(),
+                b"",
+                "/test",
+                Some(&ep("only.com", 443)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(selected.endpoint.host, "only.com");
+    }
+
+    fn breaker_upstream(scope: CircuitBreakerScope) -> Upstream {
+        use crate::domain::model::{CircuitBreakerConfig, FailureConditions, SharingMode};
+
+        let mut upstream = upstream_with(vec![ep("a.com", 443), ep("b.com", 443)]);
+        upstream.circuit_breaker = Some(CircuitBreakerConfig {
+            sharing: SharingMode::Private,
+            enabled: true,
+            failure_threshold: 1,
+            failure_rate: None,
+            success_threshold: 1,
+            timeout_secs: 30,
+            half_open_max_requests: 1,
+            failure_conditions: FailureConditions::default(),
+            scope,
+        });
+        upstream
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_per_endpoint_skips_open_endpoint() {
+        let svc = build_svc(Arc::new(MockSelector::new()));
+        let upstream = breaker_upstream(CircuitBreakerScope::PerEndpoint);
+        let headers = HeaderMap::new();
+
+        // First call lands on a.com; a 503 opens its breaker.
+        let (selected, permit) = svc
+            .select_endpoint_with_breaker(&upstream, &headers, b"", "/test", None)
+            .await
+            .unwrap();
+        assert_eq!(selected.endpoint.host, "a.com");
+        permit.unwrap().record_response(503, false);
+
+        // Round-robin would pick b.com next anyway; the call after that must
+        // skip a.com instead of failing.
+        for _ in 0..2 {
+            let (selected, permit) = svc
+                .select_endpoint_with_breaker(&upstream, &headers, b"", "/test", None)
+                .await
+                .unwrap();
+            assert_eq!(selected.endpoint.host, "b.com");
+            permit.unwrap().record_response(200, false);
+        }
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_global_scope_rejects_when_open() {
+        let svc = build_svc(Arc::new(MockSelector::new()));
+        let upstream = breaker_upstream(CircuitBreakerScope::Global);
+        let headers = HeaderMap::new();
+
+        let (_, permit) = svc
+            .select_endpoint_with_breaker(&upstream, &headers, b"", "/test", None)
+            .await
+            .unwrap();
+        permit.unwrap().record_response(504, true);
+
+        let Err(err) = svc
+            .select_endpoint_with_breaker(&upstream, &headers, b"", "/test", None)
+            .await
+        else {
+            panic!("an open circuit must reject the request");
+        };
+        assert!(matches!(err, DomainError::CircuitBreakerOpen { .. }));
+
+        svc.remove_circuit_breakers_for_upstream(upstream.id);
+        assert!(
+            svc.select_endpoint_with_breaker(&upstream, &headers, b"", "/test", None)
+                .await
+                .is_ok(),
+            "removing the upstream's breakers must close the circuit"
+        );
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_disabled_returns_no_permit() {
+        let svc = build_svc(Arc::new(MockSelector::new()));
+        let mut upstream = breaker_upstream(CircuitBreakerScope::Global);
+        upstream.circuit_breaker.as_mut().unwrap().enabled = false;
+
+        let (_, permit) = svc
+            .select_endpoint_with_breaker(&upstream, &HeaderMap::new(), b"", "/test", None)
+            .await
+            .unwrap();
+        assert!(permit.is_none());
+    }
 }
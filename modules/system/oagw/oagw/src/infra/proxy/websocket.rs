@@ -10,6 +10,8 @@ use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
 use tokio::sync::watch;
 use tracing::{debug, warn};
 
+use crate::domain::load_balancing::InFlightGuard;
+
 // ---------------------------------------------------------------------------
 // PrefixedReader — prepends buffered bytes before an inner AsyncRead
 // ---------------------------------------------------------------------------
@@ -478,6 +480,8 @@ pub(crate) struct WebSocketBridgeIo {
     pub close_timeout: Duration,
     pub max_frame_size: Option<usize>,
     pub shutdown_rx: watch::Receiver<bool>,
+    /// Counts the session against the upstream endpoint until the bridge ends.
+    pub in_flight: Option<InFlightGuard>,
 }
 
 /// Wrapper that satisfies `Clone + Send + Sync + 'static` required by
@@ -517,6 +521,7 @@ pub(crate) async fn websocket_bridge(
         close_timeout,
         max_frame_size,
         shutdown_rx,
+        in_flight: _in_flight,
     } = bridge;
     let (upstream_read, mut upstream_write) = split(io);
     // Prepend any leftover bytes from 101 header parsing to the upstream
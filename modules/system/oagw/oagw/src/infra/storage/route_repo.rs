@@ -128,23 +128,28 @@ impl RouteRepository for InMemoryRouteRepo {
             if !route.enabled {
                 continue;
             }
-            // Must have HTTP match rules.
-            let Some(http_match) = &route.match_rules.http else {
+            let path_len = if let Some(http_match) = &route.match_rules.http {
+                // Method must match (unknown methods never match).
+                let Some(req_method) = &request_method else {
+                    continue;
+                };
+                if !http_match.methods.contains(req_method) {
+                    continue;
+                }
+                // Path must be a prefix match.
+                if !path.starts_with(&http_match.path) {
+                    continue;
+                }
+                http_match.path.len()
+            } else if let Some(grpc_match) = &route.match_rules.grpc {
+                // gRPC calls are POSTs to exactly `/{service}/{method}`.
+                if request_method != Some(HttpMethod::Post) || path != grpc_match.path() {
+                    continue;
+                }
+                path.len()
+            } else {
                 continue;
             };
-            // Method must match (unknown methods never match).
-            let Some(req_method) = &request_method else {
-                continue;
-            };
-            if !http_match.methods.contains(req_method) {
-                continue;
-            }
-            // Path must be a prefix match.
-            if !path.starts_with(&http_match.path) {
-                continue;
-            }
-
-            let path_len = http_match.path.len();
             let priority = route.priority;
 
             // Select by longest path prefix, then highest priority.
@@ -233,7 +238,7 @@ fn parse_method(s: &str) -> Option<HttpMethod> {
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::model::{HttpMatch, MatchRules, PathSuffixMode};
+    use crate::domain::model::{GrpcMatch, HttpMatch, MatchRules, PathSuffixMode};
 
     use super::*;
 
@@ -260,6 +265,9 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            timeouts: None,
+            retry: None,
+            cache: None,
             tags: vec![],
             priority,
             enabled: true,
@@ -366,6 +374,58 @@ mod tests {
         assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
     }
 
+    fn make_grpc_route(tenant_id: Uuid, upstream_id: Uuid, service: &str, method: &str) -> Route {
+        let mut route = make_route(tenant_id, upstream_id, vec![], "/", 0);
+        route.match_rules = MatchRules {
+            http: None,
+            grpc: Some(GrpcMatch {
+                service: service.into(),
+                method: method.into(),
+            }),
+        };
+        route
+    }
+
+    #[tokio::test]
+    async fn find_matching_grpc_route_by_exact_path() {
+        let repo = InMemoryRouteRepo::new();
+        let tenant = Uuid::new_v4();
+        let upstream = Uuid::new_v4();
+
+        let catch_all = make_route(tenant, upstream, vec![HttpMethod::Post], "/", 10);
+        let grpc = make_grpc_route(tenant, upstream, "example.v1.UserService", "GetUser");
+        repo.create(catch_all).await.unwrap();
+        repo.create(grpc.clone()).await.unwrap();
+
+        let matched = repo
+            .find_matching(tenant, upstream, "POST", "/example.v1.UserService/GetUser")
+            .await
+            .unwrap();
+        assert_eq!(matched.id, grpc.id);
+    }
+
+    #[tokio::test]
+    async fn find_matching_grpc_route_rejects_other_methods_and_paths() {
+        let repo = InMemoryRouteRepo::new();
+        let tenant = Uuid::new_v4();
+        let upstream = Uuid::new_v4();
+
+        let grpc = make_grpc_route(tenant, upstream, "example.v1.UserService", "GetUser");
+        repo.create(grpc).await.unwrap();
+
+        for (method, path) in [
+            ("GET", "/example.v1.UserService/GetUser"),
+            ("POST", "/example.v1.UserService/GetUserExtra"),
+            ("POST", "/example.v1.UserService/GetUser/extra"),
+        ] {
+            let result = repo.find_matching(tenant, upstream, method, path).await;
+            assert!(
+                matches!(result, Err(RepositoryError::NotFound { .. })),
+                "{method} {path} must not match"
+            );
+        }
+    }
+
     #[tokio::test]
     async fn list_by_upstream_returns_correct_set() {
         let repo = InMemoryRouteRepo::new();
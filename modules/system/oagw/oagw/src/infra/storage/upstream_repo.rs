@@ -185,7 +185,10 @@ mod tests {
                     scheme: Scheme::Https,
                     host: "api.openai.com".into(),
                     port: 443,
+                    weight: 1,
                 }],
+                load_balancing: None,
+                health_check: None,
             },
             protocol: "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1".into(),
             enabled: true,
@@ -194,6 +197,7 @@ mod tests {
             plugins: None,
             rate_limit: None,
             cors: None,
+            circuit_breaker: None,
             tags: vec![],
         }
     }
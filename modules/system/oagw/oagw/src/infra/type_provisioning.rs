@@ -56,11 +56,98 @@ struct Endpoint {
     host: String,
     #[serde(default = "default_port")]
     port: u16,
+    #[serde(default = "default_weight")]
+    weight: u32,
+}
+
+fn default_weight() -> u32 {
+    1
 }
 
 #[derive(Deserialize)]
 struct Server {
     endpoints: Vec<Endpoint>,
+    #[serde(default)]
+    load_balancing: Option<LoadBalancing>,
+    #[serde(default)]
+    health_check: Option<HealthCheckConfig>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+enum LoadBalancing {
+    RoundRobin,
+    LeastInFlight,
+    ConsistentHash { hash_key: HashKey },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum HashKey {
+    Header(String),
+    Tenant,
+}
+
+#[derive(Deserialize)]
+struct HealthCheckConfig {
+    #[serde(default)]
+    probe: HealthProbe,
+    #[serde(default = "default_health_interval_seconds")]
+    interval_seconds: u32,
+    #[serde(default = "default_health_timeout_ms")]
+    timeout_ms: u32,
+    #[serde(default = "default_health_threshold")]
+    healthy_threshold: u32,
+    #[serde(default = "default_health_threshold")]
+    unhealthy_threshold: u32,
+    #[serde(default)]
+    outlier_detection: Option<OutlierDetectionConfig>,
+}
+
+fn default_health_interval_seconds() -> u32 {
+    10
+}
+
+fn default_health_timeout_ms() -> u32 {
+    1_000
+}
+
+fn default_health_threshold() -> u32 {
+    1
+}
+
+#[derive(Deserialize, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum HealthProbe {
+    #[default]
+    Tcp,
+    Http {
+        path: String,
+        #[serde(default)]
+        expected_status: Option<Vec<u16>>,
+    },
+}
+
+#[derive(Deserialize)]
+struct OutlierDetectionConfig {
+    #[serde(default = "default_outlier_consecutive_failures")]
+    consecutive_failures: u32,
+    #[serde(default = "default_outlier_ejection_seconds")]
+    ejection_seconds: u32,
+    #[serde(default = "default_outlier_max_ejection_percent")]
+    max_ejection_percent: u32,
+}
+
+fn default_outlier_consecutive_failures() -> u32 {
+    5
+}
+
+fn default_outlier_ejection_seconds() -> u32 {
+    30
+}
+
+fn default_outlier_max_ejection_percent() -> u32 {
+    50
 }
 
 #[derive(Deserialize, Default)]
@@ -228,6 +315,151 @@ enum BudgetMode {
     Shared,
 }
 
+#[derive(Deserialize)]
+struct CircuitBreakerConfig {
+    #[serde(default)]
+    sharing: SharingMode,
+    #[serde(default = "default_true")]
+    enabled: bool,
+    #[serde(default = "default_failure_threshold")]
+    failure_threshold: u32,
+    #[serde(default)]
+    failure_rate: Option<FailureRateConfig>,
+    #[serde(default = "default_success_threshold")]
+    success_threshold: u32,
+    #[serde(default = "default_circuit_timeout_seconds")]
+    timeout_seconds: u32,
+    #[serde(default = "default_half_open_max_requests")]
+    half_open_max_requests: u32,
+    #[serde(default)]
+    failure_conditions: Option<FailureConditions>,
+    #[serde(default)]
+    scope: CircuitBreakerScope,
+}
+
+fn default_failure_threshold() -> u32 {
+    5
+}
+
+fn default_success_threshold() -> u32 {
+    3
+}
+
+fn default_circuit_timeout_seconds() -> u32 {
+    30
+}
+
+fn default_half_open_max_requests() -> u32 {
+    3
+}
+
+#[derive(Deserialize)]
+struct FailureRateConfig {
+    threshold: f64,
+    #[serde(default = "default_minimum_requests")]
+    minimum_requests: u32,
+    #[serde(default = "default_failure_rate_window_seconds")]
+    window_seconds: u32,
+}
+
+fn default_minimum_requests() -> u32 {
+    20
+}
+
+fn default_failure_rate_window_seconds() -> u32 {
+    60
+}
+
+#[derive(Deserialize)]
+struct FailureConditions {
+    #[serde(default)]
+    status_codes: Option<Vec<u16>>,
+    #[serde(default = "default_true")]
+    timeout: bool,
+    #[serde(default = "default_true")]
+    connection_error: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum CircuitBreakerScope {
+    #[default]
+    Global,
+    PerEndpoint,
+}
+
+#[derive(Deserialize)]
+struct TimeoutConfig {
+    #[serde(default)]
+    connect_ms: Option<u32>,
+    #[serde(default)]
+    read_ms: Option<u32>,
+    #[serde(default)]
+    total_ms: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct RetryConfig {
+    #[serde(default = "default_retry_max_attempts")]
+    max_attempts: u32,
+    #[serde(default)]
+    backoff: Option<BackoffConfig>,
+    #[serde(default)]
+    retry_on: Option<RetryConditions>,
+    #[serde(default)]
+    retry_non_idempotent: bool,
+    #[serde(default)]
+    budget: Option<RetryBudget>,
+}
+
+fn default_retry_max_attempts() -> u32 {
+    3
+}
+
+#[derive(Deserialize)]
+struct BackoffConfig {
+    #[serde(default)]
+    initial_ms: Option<u32>,
+    #[serde(default)]
+    max_ms: Option<u32>,
+    #[serde(default)]
+    multiplier: Option<f64>,
+    #[serde(default = "default_true")]
+    jitter: bool,
+}
+
+#[derive(Deserialize)]
+struct RetryConditions {
+    #[serde(default)]
+    status_codes: Option<Vec<u16>>,
+    #[serde(default = "default_true")]
+    timeout: bool,
+    #[serde(default = "default_true")]
+    connection_error: bool,
+}
+
+#[derive(Deserialize)]
+struct RetryBudget {
+    #[serde(default)]
+    ratio: Option<f64>,
+    #[serde(default)]
+    min_retries_per_sec: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct CacheConfig {
+    #[serde(default)]
+    sharing: SharingMode,
+    #[serde(default = "default_true")]
+    enabled: bool,
+    #[serde(default)]
+    default_ttl_secs: u32,
+    #[serde(default)]
+    max_object_bytes: Option<u32>,
+    #[serde(default)]
+    allow_user_credentials: bool,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 enum CorsHttpMethod {
@@ -317,6 +549,8 @@ struct UpstreamPayload {
     #[serde(default)]
     cors: Option<CorsConfig>,
     #[serde(default)]
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default)]
     tags: Vec<String>,
     #[serde(default = "default_true")]
     enabled: bool,
@@ -336,6 +570,12 @@ struct RoutePayload {
     #[serde(default)]
     cors: Option<CorsConfig>,
     #[serde(default)]
+    timeouts: Option<TimeoutConfig>,
+    #[serde(default)]
+    retry: Option<RetryConfig>,
+    #[serde(default)]
+    cache: Option<CacheConfig>,
+    #[serde(default)]
     tags: Vec<String>,
     #[serde(default)]
     priority: i32,
@@ -365,6 +605,7 @@ impl From<Endpoint> for domain::Endpoint {
             scheme: v.scheme.into(),
             host: v.host,
             port: v.port,
+            weight: v.weight,
         }
     }
 }
@@ -373,6 +614,49 @@ impl From<Server> for domain::Server {
     fn from(v: Server) -> Self {
         Self {
             endpoints: v.endpoints.into_iter().map(Into::into).collect(),
+            load_balancing: v.load_balancing.map(Into::into),
+            health_check: v.health_check.map(Into::into),
+        }
+    }
+}
+
+impl From<LoadBalancing> for domain::LoadBalancing {
+    fn from(v: LoadBalancing) -> Self {
+        match v {
+            LoadBalancing::RoundRobin => Self::RoundRobin,
+            LoadBalancing::LeastInFlight => Self::LeastInFlight,
+            LoadBalancing::ConsistentHash { hash_key } => Self::ConsistentHash {
+                key: match hash_key {
+                    HashKey::Header(name) => domain::HashKey::Header(name),
+                    HashKey::Tenant => domain::HashKey::Tenant,
+                },
+            },
+        }
+    }
+}
+
+impl From<HealthCheckConfig> for domain::HealthCheckConfig {
+    fn from(v: HealthCheckConfig) -> Self {
+        Self {
+            probe: match v.probe {
+                HealthProbe::Tcp => domain::HealthProbe::Tcp,
+                HealthProbe::Http {
+                    path,
+                    expected_status,
+                } => domain::HealthProbe::Http {
+                    path,
+                    expected_status: expected_status.unwrap_or_else(|| vec![200]),
+                },
+            },
+            interval_secs: v.interval_seconds,
+            timeout_ms: v.timeout_ms,
+            healthy_threshold: v.healthy_threshold,
+            unhealthy_threshold: v.unhealthy_threshold,
+            outlier_detection: v.outlier_detection.map(|o| domain::OutlierDetectionConfig {
+                consecutive_failures: o.consecutive_failures,
+                ejection_secs: o.ejection_seconds,
+                max_ejection_percent: o.max_ejection_percent,
+            }),
         }
     }
 }
@@ -579,6 +863,113 @@ impl From<CorsConfig> for domain::CorsConfig {
     }
 }
 
+impl From<CircuitBreakerConfig> for domain::CircuitBreakerConfig {
+    fn from(v: CircuitBreakerConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            failure_rate: v.failure_rate.map(|r| domain::FailureRateConfig {
+                threshold: r.threshold,
+                minimum_requests: r.minimum_requests,
+                window_secs: r.window_seconds,
+            }),
+            success_threshold: v.success_threshold,
+            timeout_secs: v.timeout_seconds,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: v.failure_conditions.map(Into::into).unwrap_or_default(),
+            scope: match v.scope {
+                CircuitBreakerScope::Global => domain::CircuitBreakerScope::Global,
+                CircuitBreakerScope::PerEndpoint => domain::CircuitBreakerScope::PerEndpoint,
+            },
+        }
+    }
+}
+
+impl From<FailureConditions> for domain::FailureConditions {
+    fn from(v: FailureConditions) -> Self {
+        Self {
+            status_codes: v
+                .status_codes
+                .unwrap_or_else(|| Self::default().status_codes),
+            timeout: v.timeout,
+            connection_error: v.connection_error,
+        }
+    }
+}
+
+impl From<TimeoutConfig> for domain::TimeoutConfig {
+    fn from(v: TimeoutConfig) -> Self {
+        Self {
+            connect_ms: v.connect_ms,
+            read_ms: v.read_ms,
+            total_ms: v.total_ms,
+        }
+    }
+}
+
+impl From<RetryConfig> for domain::RetryConfig {
+    fn from(v: RetryConfig) -> Self {
+        Self {
+            max_attempts: v.max_attempts,
+            backoff: v.backoff.map(Into::into).unwrap_or_default(),
+            retry_on: v.retry_on.map(Into::into).unwrap_or_default(),
+            retry_non_idempotent: v.retry_non_idempotent,
+            budget: v.budget.map(Into::into).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<BackoffConfig> for domain::BackoffConfig {
+    fn from(v: BackoffConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            initial_ms: v.initial_ms.unwrap_or(defaults.initial_ms),
+            max_ms: v.max_ms.unwrap_or(defaults.max_ms),
+            multiplier: v.multiplier.unwrap_or(defaults.multiplier),
+            jitter: v.jitter,
+        }
+    }
+}
+
+impl From<RetryConditions> for domain::RetryConditions {
+    fn from(v: RetryConditions) -> Self {
+        Self {
+            status_codes: v
+                .status_codes
+                .unwrap_or_else(|| Self::default().status_codes),
+            timeout: v.timeout,
+            connection_error: v.connection_error,
+        }
+    }
+}
+
+impl From<RetryBudget> for domain::RetryBudget {
+    fn from(v: RetryBudget) -> Self {
+        let defaults = Self::default();
+        Self {
+            ratio: v.ratio.unwrap_or(defaults.ratio),
+            min_retries_per_sec: v
+                .min_retries_per_sec
+                .unwrap_or(defaults.min_retries_per_sec),
+        }
+    }
+}
+
+impl From<CacheConfig> for domain::CacheConfig {
+    fn from(v: CacheConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            enabled: v.enabled,
+            default_ttl_secs: v.default_ttl_secs,
+            max_object_bytes: v
+                .max_object_bytes
+                .unwrap_or(Self::default().max_object_bytes),
+            allow_user_credentials: v.allow_user_credentials,
+        }
+    }
+}
+
 impl From<HttpMethod> for domain::HttpMethod {
     fn from(v: HttpMethod) -> Self {
         match v {
@@ -643,6 +1034,7 @@ impl UpstreamPayload {
                 plugins: self.plugins.map(Into::into),
                 rate_limit: self.rate_limit.map(Into::into),
                 cors: self.cors.map(Into::into),
+                circuit_breaker: self.circuit_breaker.map(Into::into),
                 tags: self.tags,
                 enabled: self.enabled,
             },
@@ -674,6 +1066,9 @@ impl RoutePayload {
                 plugins: self.plugins.map(Into::into),
                 rate_limit: self.rate_limit.map(Into::into),
                 cors: self.cors.map(Into::into),
+                timeouts: self.timeouts.map(Into::into),
+                retry: self.retry.map(Into::into),
+                cache: self.cache.map(Into::into),
                 tags: self.tags,
                 priority: self.priority,
                 enabled: self.enabled,
@@ -1061,6 +1456,79 @@ mod tests {
         assert_eq!(rr.passthrough, domain::PassthroughMode::All);
     }
 
+    #[test]
+    fn deserialize_upstream_circuit_breaker_with_defaults() {
+        let json = serde_json::json!({
+            "tenant_id": Uuid::new_v4(),
+            "server": {"endpoints": [{"host": "api.openai.com"}]},
+            "protocol": "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
+            "circuit_breaker": {
+                "failure_threshold": 10,
+                "failure_rate": {"threshold": 0.5},
+                "scope": "per_endpoint"
+            }
+        });
+
+        let payload: UpstreamPayload = serde_json::from_value(json).unwrap();
+        let provisioned = payload.into_provisioned(None);
+        let cb = provisioned.request.circuit_breaker.unwrap();
+
+        assert!(cb.enabled);
+        assert_eq!(cb.failure_threshold, 10);
+        assert_eq!(cb.success_threshold, 3);
+        assert_eq!(cb.timeout_secs, 30);
+        assert_eq!(cb.scope, domain::CircuitBreakerScope::PerEndpoint);
+        assert_eq!(cb.failure_conditions, domain::FailureConditions::default());
+        let rate = cb.failure_rate.unwrap();
+        assert_eq!(rate.minimum_requests, 20);
+        assert_eq!(rate.window_secs, 60);
+    }
+
+    #[test]
+    fn deserialize_upstream_load_balancing_and_health_check_with_defaults() {
+        let json = serde_json::json!({
+            "tenant_id": Uuid::new_v4(),
+            "server": {
+                "endpoints": [
+                    {"host": "a.example.com", "weight": 3},
+                    {"host": "b.example.com"}
+                ],
+                "load_balancing": {"strategy": "consistent_hash", "hash_key": {"header": "x-session-id"}},
+                "health_check": {
+                    "probe": {"type": "http", "path": "/healthz"},
+                    "outlier_detection": {"consecutive_failures": 3}
+                }
+            },
+            "protocol": "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1"
+        });
+
+        let payload: UpstreamPayload = serde_json::from_value(json).unwrap();
+        let server = payload.into_provisioned(None).request.server;
+
+        assert_eq!(server.endpoints[0].weight, 3);
+        assert_eq!(server.endpoints[1].weight, 1);
+        assert_eq!(
+            server.load_balancing,
+            Some(domain::LoadBalancing::ConsistentHash {
+                key: domain::HashKey::Header("x-session-id".into()),
+            })
+        );
+        let hc = server.health_check.unwrap();
+        assert_eq!(
+            hc.probe,
+            domain::HealthProbe::Http {
+                path: "/healthz".into(),
+                expected_status: vec![200],
+            }
+        );
+        assert_eq!(hc.interval_secs, 10);
+        assert_eq!(hc.unhealthy_threshold, 1);
+        let od = hc.outlier_detection.unwrap();
+        assert_eq!(od.consecutive_failures, 3);
+        assert_eq!(od.ejection_secs, 30);
+        assert_eq!(od.max_ejection_percent, 50);
+    }
+
     #[test]
     fn deserialize_valid_route_payload() {
         let tenant = Uuid::new_v4();
@@ -1128,6 +1596,71 @@ mod tests {
         assert_eq!(rl.cost, 2);
     }
 
+    #[test]
+    fn deserialize_route_timeouts_and_retry_with_defaults() {
+        let json = serde_json::json!({
+            "tenant_id": Uuid::new_v4(),
+            "upstream_id": Uuid::new_v4(),
+            "match": {"http": {"methods": ["POST"], "path": "/v1/chat"}},
+            "timeouts": {"connect_ms": 500, "total_ms": 120000},
+            "retry": {
+                "max_attempts": 2,
+                "backoff": {"initial_ms": 50},
+                "retry_on": {"status_codes": [503]}
+            }
+        });
+
+        let payload: RoutePayload = serde_json::from_value(json).unwrap();
+        let provisioned = payload
+            .into_provisioned(
+                "gts.cf.core.oagw.route.v1~cf.core.oagw.test.v1",
+                Uuid::new_v4(),
+            )
+            .unwrap();
+
+        let timeouts = provisioned.request.timeouts.unwrap();
+        assert_eq!(timeouts.connect_ms, Some(500));
+        assert_eq!(timeouts.read_ms, None);
+        assert_eq!(timeouts.total_ms, Some(120_000));
+
+        let retry = provisioned.request.retry.unwrap();
+        assert_eq!(retry.max_attempts, 2);
+        assert!(!retry.retry_non_idempotent);
+        assert_eq!(retry.backoff.initial_ms, 50);
+        assert_eq!(retry.backoff.max_ms, 2_000);
+        assert_eq!(retry.retry_on.status_codes, vec![503]);
+        assert!(retry.retry_on.timeout);
+        assert_eq!(retry.budget, domain::RetryBudget::default());
+    }
+
+    #[test]
+    fn deserialize_route_cache_with_defaults() {
+        let json = serde_json::json!({
+            "tenant_id": Uuid::new_v4(),
+            "upstream_id": Uuid::new_v4(),
+            "match": {"http": {"methods": ["GET"], "path": "/v1/models"}},
+            "cache": {"sharing": "enforce", "default_ttl_secs": 60}
+        });
+
+        let payload: RoutePayload = serde_json::from_value(json).unwrap();
+        let provisioned = payload
+            .into_provisioned(
+                "gts.cf.core.oagw.route.v1~cf.core.oagw.test.v1",
+                Uuid::new_v4(),
+            )
+            .unwrap();
+
+        let cache = provisioned.request.cache.unwrap();
+        assert_eq!(cache.sharing, domain::SharingMode::Enforce);
+        assert!(cache.enabled);
+        assert_eq!(cache.default_ttl_secs, 60);
+        assert_eq!(
+            cache.max_object_bytes,
+            domain::CacheConfig::default().max_object_bytes
+        );
+        assert!(!cache.allow_user_credentials);
+    }
+
     #[test]
     fn deserialize_missing_field_returns_error() {
         // Missing required "server" field.
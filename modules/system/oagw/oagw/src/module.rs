@@ -130,7 +130,8 @@ impl Module for OutboundApiGatewayModule {
             .with_websocket_idle_timeout(Duration::from_secs(cfg.websocket_idle_timeout_secs))
             .with_websocket_close_timeout(Duration::from_secs(cfg.websocket_close_timeout_secs))
             .with_websocket_max_frame_size(cfg.websocket_max_frame_size_bytes)
-            .with_streaming_idle_timeout(Duration::from_secs(cfg.streaming_idle_timeout_secs)),
+            .with_streaming_idle_timeout(Duration::from_secs(cfg.streaming_idle_timeout_secs))
+            .with_response_cache_max_bytes(cfg.response_cache_max_bytes),
         );
 
         // -- Facade (for external SDK consumers) --
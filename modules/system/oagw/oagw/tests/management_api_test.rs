@@ -55,7 +55,10 @@ async fn get_upstream_by_gts_id() {
                         scheme: oagw_sdk::Scheme::Https,
                         host: "api.openai.com".into(),
                         port: 443,
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -70,6 +73,55 @@ async fn get_upstream_by_gts_id() {
     assert_eq!(json["alias"].as_str().unwrap(), "api.openai.com");
 }
 
+// GET upstream health before any traffic: configured endpoints are unknown.
+#[tokio::test]
+async fn get_upstream_health_reports_unknown_before_traffic() {
+    let h = AppHarness::builder().build().await;
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            h.security_context().clone(),
+            oagw_sdk::CreateUpstreamRequest::builder(
+                oagw_sdk::Server {
+                    endpoints: vec![oagw_sdk::Endpoint {
+                        scheme: oagw_sdk::Scheme::Https,
+                        host: "api.openai.com".into(),
+                        port: 443,
+                        weight: 2,
+                    }],
+                    load_balancing: Some(oagw_sdk::LoadBalancing::LeastInFlight),
+                    health_check: None,
+                },
+                "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let gts_id = format_upstream_gts(upstream.id);
+    let resp = h
+        .api_v1()
+        .get_upstream_health(&gts_id)
+        .expect_status(200)
+        .await;
+    let json = resp.json();
+    assert_eq!(json["upstream_id"].as_str().unwrap(), gts_id);
+    let endpoints = json["endpoints"].as_array().unwrap();
+    assert_eq!(endpoints.len(), 1);
+    assert_eq!(endpoints[0]["host"], "api.openai.com");
+    assert_eq!(endpoints[0]["weight"], 2);
+    assert_eq!(endpoints[0]["status"], "unknown");
+    assert_eq!(endpoints[0]["in_flight"], 0);
+
+    let resp = h.api_v1().get_upstream(&gts_id).expect_status(200).await;
+    assert_eq!(
+        resp.json()["server"]["load_balancing"]["strategy"],
+        "least_in_flight"
+    );
+}
+
 // 7.9: GET with invalid GTS format -> 400.
 #[tokio::test]
 async fn get_upstream_invalid_gts_returns_400() {
@@ -112,7 +164,10 @@ async fn update_upstream_preserves_id() {
                         scheme: oagw_sdk::Scheme::Https,
                         host: "10.0.0.1".into(),
                         port: 443,
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -159,7 +214,10 @@ async fn delete_upstream_returns_204() {
                         scheme: oagw_sdk::Scheme::Https,
                         host: "api.openai.com".into(),
                         port: 443,
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -188,7 +246,10 @@ async fn create_route_success() {
                         scheme: oagw_sdk::Scheme::Https,
                         host: "api.openai.com".into(),
                         port: 443,
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -240,7 +301,10 @@ async fn list_upstreams_with_pagination() {
                             scheme: oagw_sdk::Scheme::Https,
                             host: format!("host{i}.example.com"),
                             port: 443,
+                            weight: 1,
                         }],
+                        load_balancing: None,
+                        health_check: None,
                     },
                     "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
                 )
@@ -9,11 +9,11 @@ use oagw::test_support::{
 use oagw_sdk::Body;
 use oagw_sdk::api::ErrorSource;
 use oagw_sdk::{
-    BurstConfig, CorsConfig, CorsHttpMethod, CreateRouteRequest, CreateUpstreamRequest, Endpoint,
-    HeadersConfig, HttpMatch, HttpMethod, MatchRules, PassthroughMode, PathSuffixMode,
-    PluginBinding, PluginsConfig, RateLimitAlgorithm, RateLimitConfig, RateLimitScope,
-    RateLimitStrategy, RequestHeaderRules, ResponseHeaderRules, Scheme, Server, SharingMode,
-    SustainedRate, Window,
+    BurstConfig, CacheConfig, CorsConfig, CorsHttpMethod, CreateRouteRequest,
+    CreateUpstreamRequest, Endpoint, GrpcMatch, HeadersConfig, HttpMatch, HttpMethod, MatchRules,
+    PassthroughMode, PathSuffixMode, PluginBinding, PluginsConfig, RateLimitAlgorithm,
+    RateLimitConfig, RateLimitScope, RateLimitStrategy, RequestHeaderRules, ResponseHeaderRules,
+    Scheme, Server, SharingMode, SustainedRate, Window,
 };
 use serde_json::json;
 
@@ -134,7 +134,10 @@ async fn proxy_injects_auth_header() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -235,7 +238,10 @@ async fn proxy_sse_streaming() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -351,7 +357,10 @@ async fn proxy_disabled_upstream_returns_503() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: 9999,
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -392,7 +401,10 @@ async fn proxy_rate_limit_exceeded_returns_429() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -479,7 +491,10 @@ async fn proxy_rate_limit_scope_user_isolates_by_subject() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -617,7 +632,10 @@ async fn proxy_rate_limit_scope_route_isolates_by_route() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -786,7 +804,10 @@ async fn proxy_upstream_timeout_returns_504() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -846,7 +867,10 @@ async fn proxy_query_allowlist_allowed_param_succeeds() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -900,7 +924,10 @@ async fn proxy_query_allowlist_unknown_param_rejected() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -960,7 +987,10 @@ async fn proxy_nonexistent_auth_plugin_returns_error() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -1040,7 +1070,10 @@ async fn proxy_recorded_request_has_correct_uri_and_body() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -1106,7 +1139,10 @@ async fn proxy_response_headers_sanitized() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -1198,7 +1234,10 @@ async fn proxy_path_suffix_disabled_rejects_extra_path() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -1252,6 +1291,83 @@ async fn proxy_path_suffix_disabled_rejects_extra_path() {
     }
 }
 
+// A gRPC route matches `POST /{service}/{method}` and only accepts gRPC calls.
+#[tokio::test]
+async fn proxy_grpc_route_requires_grpc_content_type() {
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                        weight: 1,
+                    }],
+                    load_balancing: None,
+                    health_check: None,
+                },
+                "gts.cf.core.oagw.protocol.v1~cf.core.oagw.grpc.v1",
+            )
+            .alias("grpc-ct-test")
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: None,
+                    grpc: Some(GrpcMatch {
+                        service: "example.v1.UserService".into(),
+                        method: "GetUser".into(),
+                    }),
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let req = http::Request::builder()
+        .method(Method::POST)
+        .uri("/grpc-ct-test/example.v1.UserService/GetUser")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"id":1}"#))
+        .unwrap();
+    match h.facade().proxy_request(ctx.clone(), req).await {
+        Err(err) => assert!(matches!(
+            err,
+            oagw_sdk::error::ServiceGatewayError::ValidationError { .. }
+        )),
+        Ok(_) => panic!("expected validation error for non-gRPC call to a gRPC route"),
+    }
+
+    // Other methods on the service do not match the route.
+    let req = http::Request::builder()
+        .method(Method::POST)
+        .uri("/grpc-ct-test/example.v1.UserService/DeleteUser")
+        .header("content-type", "application/grpc")
+        .body(Body::Empty)
+        .unwrap();
+    match h.facade().proxy_request(ctx.clone(), req).await {
+        Err(err) => assert!(matches!(
+            err,
+            oagw_sdk::error::ServiceGatewayError::NotFound { .. }
+        )),
+        Ok(_) => panic!("expected route not found for an unmatched gRPC method"),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Multi-endpoint load balancing integration tests
 // ---------------------------------------------------------------------------
@@ -1283,13 +1399,17 @@ async fn proxy_multi_endpoint_round_robin() {
                             scheme: Scheme::Http,
                             host: "127.0.0.1".into(),
                             port,
+                            weight: 1,
                         },
                         Endpoint {
                             scheme: Scheme::Http,
                             host: "127.0.0.1".into(),
                             port,
+                            weight: 1,
                         },
                     ],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -1363,13 +1483,17 @@ async fn proxy_target_host_header_selects_endpoint() {
                             scheme: Scheme::Http,
                             host: "127.0.0.1".into(),
                             port,
+                            weight: 1,
                         },
                         Endpoint {
                             scheme: Scheme::Http,
                             host: "127.0.0.1".into(),
                             port,
+                            weight: 1,
                         },
                     ],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -1426,7 +1550,10 @@ async fn proxy_target_host_unknown_returns_error() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -1495,7 +1622,10 @@ async fn proxy_all_backends_unreachable() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: 19991,
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -1678,7 +1808,10 @@ async fn proxy_with_mock_guard_custom_response() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -1747,7 +1880,10 @@ async fn proxy_websocket_upgrade_returns_101() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -1825,7 +1961,10 @@ async fn proxy_websocket_upgrade_rejected_returns_503_protocol_error() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -1940,7 +2079,10 @@ async fn proxy_websocket_auth_injected_during_handshake() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -2055,7 +2197,10 @@ async fn proxy_websocket_rate_limit_on_handshake() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -2196,7 +2341,10 @@ async fn proxy_unreachable_backend_returns_rfc9457_problem_body() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: 19993,
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -2313,7 +2461,10 @@ async fn proxy_streaming_body_exceeding_limit_returns_400() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -2401,7 +2552,10 @@ async fn proxy_streaming_body_post_arrives_intact() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -2492,7 +2646,10 @@ async fn proxy_streaming_body_with_empty_chunks_succeeds() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -2586,7 +2743,10 @@ async fn proxy_streaming_body_single_chunk() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -2672,7 +2832,10 @@ async fn proxy_streaming_body_error_mid_stream_does_not_send_terminator() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -2784,7 +2947,10 @@ async fn proxy_oauth2_client_cred_injects_bearer_token() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -2889,7 +3055,10 @@ async fn proxy_oauth2_missing_credentials_returns_error() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -2988,7 +3157,10 @@ async fn proxy_guard_allows_when_required_header_present() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -3072,7 +3244,10 @@ async fn proxy_guard_rejects_missing_required_header() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -3168,7 +3343,10 @@ async fn proxy_guard_allows_unconfigured() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -3254,7 +3432,10 @@ async fn proxy_transform_injects_request_id() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -3346,7 +3527,10 @@ async fn proxy_transform_preserves_request_id() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -3449,7 +3633,10 @@ async fn proxy_transform_error_continues_pipeline() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -3536,7 +3723,10 @@ async fn setup_cors_upstream(
                 scheme: Scheme::Http,
                 host: "127.0.0.1".into(),
                 port: h.mock_port(),
+                weight: 1,
             }],
+            load_balancing: None,
+            health_check: None,
         },
         "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
     )
@@ -3870,7 +4060,10 @@ async fn cors_route_inherit_merges_origins() {
                 scheme: Scheme::Http,
                 host: "127.0.0.1".into(),
                 port: h.mock_port(),
+                weight: 1,
             }],
+            load_balancing: None,
+            health_check: None,
         },
         "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
     )
@@ -3997,7 +4190,10 @@ async fn cors_actual_request_disallowed_method_rejected_before_upstream() {
                 scheme: Scheme::Http,
                 host: "127.0.0.1".into(),
                 port: h.mock_port(),
+                weight: 1,
             }],
+            load_balancing: None,
+            health_check: None,
         },
         "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
     )
@@ -4345,7 +4541,10 @@ async fn setup_ws_upstream(h: &AppHarness, alias: &str) {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -4819,7 +5018,10 @@ async fn proxy_response_header_rules_applied() {
                         scheme: Scheme::Http,
                         host: "127.0.0.1".into(),
                         port: h.mock_port(),
+                        weight: 1,
                     }],
+                    load_balancing: None,
+                    health_check: None,
                 },
                 "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
             )
@@ -4886,6 +5088,91 @@ async fn proxy_response_header_rules_applied() {
     );
 }
 
+// Response cache: a cacheable GET is served from the cache on the second call.
+#[tokio::test]
+async fn proxy_route_cache_serves_repeat_get_from_cache() {
+    let mut guard = MockGuard::new();
+    let cached = |n: u32| MockResponse {
+        status: 200,
+        headers: vec![
+            ("cache-control".into(), "max-age=60".into()),
+            ("etag".into(), format!("\"v{n}\"")),
+        ],
+        body: MockBody::Json(json!({"n": n})),
+    };
+    guard.mock_sequence("GET", "/v1/models", vec![cached(1), cached(2)]);
+
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                        weight: 1,
+                    }],
+                    load_balancing: None,
+                    health_check: None,
+                },
+                "gts.cf.core.oagw.protocol.v1~cf.core.oagw.http.v1",
+            )
+            .alias("cache-test")
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/v1/models"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                    }),
+                    grpc: None,
+                },
+            )
+            .cache(CacheConfig::default())
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let mut statuses = Vec::new();
+    for _ in 0..2 {
+        let req = http::Request::builder()
+            .method(Method::GET)
+            .uri(format!("/cache-test{}", guard.path("/v1/models")))
+            .body(Body::Empty)
+            .unwrap();
+        let response = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        statuses.push(
+            response.headers()["cache-status"]
+                .to_str()
+                .unwrap()
+                .to_owned(),
+        );
+        let body_bytes = response.into_body().into_bytes().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(body["n"], 1, "second call must be served from the cache");
+    }
+
+    assert_eq!(statuses, ["OAGW; fwd=miss", "OAGW; hit"]);
+    assert_eq!(guard.recorded_requests().await.len(), 1);
+}
+
 // Verify that ws_handshake retries when the bridge sends Close before the
 // readiness Pong. This deterministically reproduces the CI race condition
 // where the spawned bridge task hasn't started reading frames yet.
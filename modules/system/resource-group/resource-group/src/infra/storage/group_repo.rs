@@ -9,7 +9,7 @@
 use async_trait::async_trait;
 use modkit_db::odata::{LimitCfg, paginate_odata};
 use modkit_db::secure::{DBRunner, SecureDeleteExt, SecureEntityExt, SecureUpdateExt};
-use modkit_odata::{CursorV1, ODataQuery, Page, SortDir};
+use modkit_odata::{CursorV1, ODataLimits, ODataQuery, Page, SortDir};
 use modkit_security::AccessScope;
 use resource_group_sdk::models::{
     GroupHierarchy, GroupHierarchyWithDepth, ResourceGroup, ResourceGroupWithDepth,
@@ -112,6 +112,7 @@ impl GroupRepository {
                     next_cursor: None,
                     prev_cursor: None,
                     limit: query.limit.unwrap_or(25).min(200),
+                    total_count: None,
                 },
             });
         }
@@ -197,6 +198,7 @@ impl GroupRepository {
                 next_cursor,
                 prev_cursor,
                 limit: limit_val,
+                total_count: None,
             },
         })
     }
@@ -513,6 +515,7 @@ impl GroupRepositoryTrait for GroupRepository {
             &query_no_filter,
             ("id", SortDir::Desc),
             GROUP_LIMIT_CFG,
+            &ODataLimits::default(),
             |m: rg_entity::Model| m,
         )
         .await
@@ -8,7 +8,7 @@
 use async_trait::async_trait;
 use modkit_db::odata::{LimitCfg, paginate_odata};
 use modkit_db::secure::{DBRunner, SecureDeleteExt, SecureEntityExt};
-use modkit_odata::{ODataQuery, Page, SortDir};
+use modkit_odata::{ODataLimits, ODataQuery, Page, SortDir};
 use modkit_security::AccessScope;
 use resource_group_sdk::models::ResourceGroupMembership;
 use resource_group_sdk::odata::MembershipFilterField;
@@ -57,6 +57,7 @@ impl MembershipRepositoryTrait for MembershipRepository {
             query,
             ("group_id", SortDir::Desc),
             MEMBERSHIP_LIMIT_CFG,
+            &ODataLimits::default(),
             |m: membership_entity::Model| m,
         )
         .await
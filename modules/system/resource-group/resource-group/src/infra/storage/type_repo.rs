@@ -8,7 +8,7 @@
 use async_trait::async_trait;
 use modkit_db::odata::{LimitCfg, paginate_odata};
 use modkit_db::secure::{DBRunner, SecureDeleteExt, SecureEntityExt, SecureUpdateExt};
-use modkit_odata::{ODataQuery, Page, SortDir};
+use modkit_odata::{ODataLimits, ODataQuery, Page, SortDir};
 use modkit_security::AccessScope;
 use resource_group_sdk::ResourceGroupType;
 use resource_group_sdk::odata::TypeFilterField;
@@ -493,6 +493,7 @@ impl TypeRepositoryTrait for TypeRepository {
             query,
             ("code", SortDir::Desc),
             TYPE_LIMIT_CFG,
+            &ODataLimits::default(),
             |m: gts_type::Model| m,
         )
         .await
@@ -88,6 +88,7 @@ impl AuthZResolverClient for AllowAllAuthZ {
                         pep_properties::OWNER_TENANT_ID,
                         [tenant_id],
                     ))],
+                    fields: vec![],
                 }],
                 deny_reason: None,
             },
@@ -56,6 +56,7 @@ impl AuthZResolverClient for TenantScopingAuthZ {
                             pep_properties::OWNER_TENANT_ID,
                             [tid],
                         ))],
+                        fields: vec![],
                     }],
                     deny_reason: None,
                 },
@@ -276,6 +277,7 @@ async fn enforcer_passes_resource_id_to_pdp() {
                             pep_properties::OWNER_TENANT_ID,
                             [tid],
                         ))],
+                        fields: vec![],
                     }],
                     deny_reason: None,
                 },
@@ -371,6 +373,7 @@ async fn full_chain_list_groups_calls_enforcer_with_correct_params() {
                             pep_properties::OWNER_TENANT_ID,
                             [tid],
                         ))],
+                        fields: vec![],
                     }],
                     deny_reason: None,
                 },
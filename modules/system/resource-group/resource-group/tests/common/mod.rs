@@ -209,6 +209,7 @@ impl AuthZResolverClient for AllowAllAuthZ {
                         pep_properties::OWNER_TENANT_ID,
                         [tenant_id],
                     ))],
+                    fields: vec![],
                 }],
                 deny_reason: None,
             },
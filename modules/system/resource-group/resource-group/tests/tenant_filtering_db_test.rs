@@ -59,6 +59,7 @@ impl AuthZResolverClient for TenantScopingAuthZ {
                         pep_properties::OWNER_TENANT_ID,
                         [tenant_id],
                     ))],
+                    fields: vec![],
                 }],
                 deny_reason: None,
             },
@@ -421,6 +422,7 @@ impl AuthZResolverClient for GroupScopingAuthZ {
                             self.allowed_group_ids.clone(),
                         )),
                     ],
+                    fields: vec![],
                 }],
                 deny_reason: None,
             },
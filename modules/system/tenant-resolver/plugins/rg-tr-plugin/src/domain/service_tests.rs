@@ -94,6 +94,7 @@ impl ResourceGroupReadHierarchy for MockRgHierarchy {
                 next_cursor: None,
                 prev_cursor: None,
                 limit: 100,
+                total_count: None,
             },
         })
     }
@@ -119,6 +120,7 @@ impl ResourceGroupReadHierarchy for MockRgHierarchy {
                 next_cursor: None,
                 prev_cursor: None,
                 limit: 100,
+                total_count: None,
             },
         })
     }
@@ -171,6 +173,7 @@ impl ResourceGroupReadHierarchy for MockRgHierarchy {
                 next_cursor: None,
                 prev_cursor: None,
                 limit: 100,
+                total_count: None,
             },
         })
     }